@@ -0,0 +1,127 @@
+//! Solve time of every built-in algorithm across instance sizes, the cost of
+//! evaluating a machine re-sequencing move incrementally versus rebuilding the schedule,
+//! and the decoders local search calls on every move, up to 200 jobs on 50 machines.
+//!
+//! The instances are generated from a fixed seed so that runs compare like with like.
+//! Record a baseline before a change and compare against it afterwards:
+//!
+//! ```text
+//! cargo bench --bench solvers -- --save-baseline main
+//! cargo bench --bench solvers -- --baseline main
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jssp_scheduler::jssp::incremental::DisjunctiveGraph;
+use jssp_scheduler::jssp::{Instance, Job, Operation, SolverRegistry};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// (jobs, machines) of the benchmarked instances, smallest first
+const SIZES: [(usize, usize); 3] = [(10, 5), (20, 10), (50, 10)];
+/// (jobs, machines) of the decoder benchmarks, which are cheap enough for larger instances
+const DECODE_SIZES: [(usize, usize); 2] = [(50, 10), (200, 50)];
+
+const SEED: u64 = 42;
+
+/// A Taillard-style instance: every job visits every machine once in random order, with
+/// whole-number durations from 1 to 99
+fn instance(num_jobs: usize, num_machines: usize) -> Instance {
+    let mut rng = StdRng::seed_from_u64(SEED ^ ((num_jobs as u64) << 16) ^ num_machines as u64);
+    let jobs = (0..num_jobs)
+        .map(|job_id| {
+            let mut machines: Vec<usize> = (0..num_machines).collect();
+            machines.shuffle(&mut rng);
+            Job {
+                id: job_id,
+                operations: machines.into_iter()
+                    .enumerate()
+                    .map(|(operation_id, machine_id)| Operation {
+                        job_id,
+                        operation_id,
+                        machine_id,
+                        duration: rng.gen_range(1..100) as f64,
+                        alternatives: Vec::new(),
+                    })
+                    .collect(),
+                release_time: 0.0,
+                due_date: None,
+                weight: 1.0,
+            }
+        })
+        .collect();
+    Instance::new(jobs, num_machines).expect("generated routes use existing machines")
+}
+
+fn solve(c: &mut Criterion) {
+    let registry = SolverRegistry::default();
+    for (num_jobs, num_machines) in SIZES {
+        let instance = instance(num_jobs, num_machines);
+        let mut group = c.benchmark_group(format!("solve/{}x{}", num_jobs, num_machines));
+        group.sample_size(10).measurement_time(Duration::from_secs(5));
+        for solver in registry.solvers() {
+            group.bench_function(BenchmarkId::from_parameter(solver.name()), |b| {
+                b.iter(|| solver.solve_seeded(&instance, SEED))
+            });
+        }
+        group.finish();
+    }
+}
+
+/// Every adjacent swap on every machine of a greedy schedule, evaluated with the
+/// disjunctive graph's estimate and by re-timing the swapped machine orders
+fn evaluate_swaps(c: &mut Criterion) {
+    for (num_jobs, num_machines) in SIZES {
+        let instance = instance(num_jobs, num_machines);
+        let schedule = instance.solve_greedy();
+        let graph = DisjunctiveGraph::new(&instance, &schedule).expect("greedy schedules are consistent");
+        let sequences = instance.machine_sequences(&schedule);
+        let swaps: Vec<(usize, usize)> = sequences.iter()
+            .enumerate()
+            .flat_map(|(machine_id, sequence)| (0..sequence.len().saturating_sub(1)).map(move |position| (machine_id, position)))
+            .collect();
+
+        let mut group = c.benchmark_group(format!("evaluate_swaps/{}x{}", num_jobs, num_machines));
+        group.throughput(Throughput::Elements(swaps.len() as u64));
+        group.bench_function("incremental", |b| {
+            b.iter(|| swaps.iter().filter_map(|&(machine_id, position)| graph.swap_estimate(machine_id, position)).fold(f64::INFINITY, f64::min))
+        });
+        group.bench_function("rebuild", |b| {
+            b.iter(|| {
+                swaps.iter()
+                    .filter_map(|&(machine_id, position)| {
+                        let mut swapped = sequences.clone();
+                        swapped[machine_id].swap(position, position + 1);
+                        instance.schedule_from_sequences(&swapped, &HashMap::new())
+                    })
+                    .map(|schedule| instance.calculate_makespan(&schedule))
+                    .fold(f64::INFINITY, f64::min)
+            })
+        });
+        group.finish();
+    }
+}
+
+/// Decoding an operation sequence and re-timing machine orders, the two ways local
+/// search turns a neighbour into a schedule
+fn decode(c: &mut Criterion) {
+    for (num_jobs, num_machines) in DECODE_SIZES {
+        let instance = instance(num_jobs, num_machines);
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let mut sequence = instance.job_by_job_sequence();
+        sequence.shuffle(&mut rng);
+        let sequences = instance.machine_sequences(&instance.decode(&sequence));
+
+        let mut group = c.benchmark_group(format!("decode/{}x{}", num_jobs, num_machines));
+        group.throughput(Throughput::Elements(instance.num_operations() as u64));
+        group.bench_function("sequence", |b| b.iter(|| instance.decode(&sequence)));
+        group.bench_function("machine_orders", |b| b.iter(|| instance.schedule_from_sequences(&sequences, &HashMap::new())));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solve, evaluate_swaps, decode);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+//! OR-Tools JSON instances: parsing never panics, and whatever parses can be
+//! scheduled, validated and written back without changing the problem.
+#![no_main]
+
+use jssp_scheduler::jssp::ortools::{from_ortools_json, to_ortools_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok((instance, solution)) = from_ortools_json(text) else {
+        return;
+    };
+    if let Some(solution) = solution {
+        let _ = instance.validate(&solution);
+    }
+    let schedule = instance.solve_greedy();
+    assert!(instance.validate(&schedule).is_ok(), "greedy schedule of a parsed instance is infeasible");
+
+    let written = to_ortools_json(&instance, None).to_string();
+    let (reread, _) = from_ortools_json(&written).expect("a written instance parses");
+    assert_eq!(reread.fingerprint(), instance.fingerprint());
+});
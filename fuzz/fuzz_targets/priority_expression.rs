@@ -0,0 +1,21 @@
+//! Custom dispatching rule expressions: parsing never panics, and a parsed rule
+//! dispatches a schedule that is feasible.
+#![no_main]
+
+use jssp_scheduler::jssp::dynamic::DispatchRule;
+use jssp_scheduler::jssp::priority::PriorityExpression;
+use jssp_scheduler::jssp::testing::ft06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(expression) = PriorityExpression::parse(source) else {
+        return;
+    };
+    let instance = ft06().instance;
+    let source = expression.source().to_string();
+    let run = instance.dispatch(DispatchRule::Custom(expression));
+    assert!(instance.validate(&run.schedule).is_ok(), "rule {:?} dispatched an infeasible schedule", source);
+});
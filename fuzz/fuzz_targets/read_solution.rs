@@ -0,0 +1,17 @@
+//! Solution files in every format `read_solution` recognizes, read against ft06:
+//! reading never panics, and validating what was read never panics either.
+#![no_main]
+
+use jssp_scheduler::jssp::solution_formats::read_solution;
+use jssp_scheduler::jssp::testing::ft06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let instance = ft06().instance;
+    if let Ok(schedule) = read_solution(&instance, text) {
+        let _ = instance.validate(&schedule);
+    }
+});
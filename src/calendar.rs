@@ -0,0 +1,326 @@
+//! Wall-clock dates for schedule times, and the working hours of the factory.
+//!
+//! A `Calendar` maps time 0 of a schedule to a timestamp and every time unit to a
+//! number of minutes, which the Gantt chart and the exports use to show dates. With
+//! `working_hours` it also says when the shop works: its shifts, the breaks taken
+//! every day and the days of the week it is open. Everything else is off shift, and an
+//! instance with such a calendar treats off-shift time like downtime on every machine
+//! (see `Instance::set_calendar`): operations wait for the next shift, or on a
+//! preemptive instance pause over the night and resume in the morning.
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use jssp_scheduler::calendar::{Calendar, WorkingHours};
+//!
+//! // Monday 06:00, one unit per hour, two shifts with breaks, Monday to Friday
+//! let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(6, 0, 0).unwrap();
+//! let calendar = Calendar { start, minutes_per_unit: 60.0, working_hours: Some(WorkingHours::default()), ..Calendar::default() };
+//! let off = calendar.off_shift(0.0, 24.0);
+//! assert_eq!(off, vec![(4.0, 4.5), (12.0, 12.5), (16.0, 24.0)]);
+//! // 15 hours of work a day: 29 hours of work end on Tuesday at 21:00
+//! assert_eq!(calendar.working_time_end(0.0, 29.0), 39.0);
+//! assert_eq!(calendar.iso(39.0), "2024-01-02T21:00:00");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// Maps abstract schedule time units onto wall-clock timestamps
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    /// Timestamp corresponding to time 0 of the schedule
+    pub start: NaiveDateTime,
+    /// Length of one schedule time unit in minutes
+    pub minutes_per_unit: f64,
+    /// Number of equally long shifts a day is divided into, for the time scale header
+    pub shifts_per_day: u32,
+    /// When the shop works; without, it works around the clock
+    pub working_hours: Option<WorkingHours>,
+}
+
+/// A span of the day from `start` to `end`; it runs past midnight if `end` is not
+/// after `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DaySpan {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl DaySpan {
+    /// From `start` to `end` as (hour, minute)
+    pub fn new(start: (u32, u32), end: (u32, u32)) -> Self {
+        let time = |(hour, minute): (u32, u32)| NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN);
+        Self { start: time(start), end: time(end) }
+    }
+
+    /// The span on the day `date`, as timestamps
+    fn on(&self, date: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        let start = date.and_time(self.start);
+        let end = date.and_time(self.end);
+        (start, if end <= start { end + Duration::days(1) } else { end })
+    }
+}
+
+impl fmt::Display for DaySpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start.format("%H:%M"), self.end.format("%H:%M"))
+    }
+}
+
+impl FromStr for DaySpan {
+    type Err = String;
+
+    /// "06:00-14:00"
+    fn from_str(text: &str) -> Result<Self, String> {
+        let (start, end) = text.split_once('-').ok_or_else(|| format!("\"{}\" is not a span like 06:00-14:00", text.trim()))?;
+        let time = |text: &str| NaiveTime::parse_from_str(text.trim(), "%H:%M").map_err(|_| format!("\"{}\" is not a time like 06:00", text.trim()));
+        Ok(Self { start: time(start)?, end: time(end)? })
+    }
+}
+
+/// The shifts, breaks and working days of a shop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingHours {
+    /// Worked on every working day they start on
+    pub shifts: Vec<DaySpan>,
+    /// Taken every day, also when they fall into a shift that started the day before
+    pub breaks: Vec<DaySpan>,
+    /// Whether the shop works on each day of the week, Monday first
+    pub working_days: [bool; 7],
+}
+
+impl Default for WorkingHours {
+    /// Two shifts from 06:00 to 22:00 with a half-hour break each, Monday to Friday
+    fn default() -> Self {
+        Self {
+            shifts: vec![DaySpan::new((6, 0), (14, 0)), DaySpan::new((14, 0), (22, 0))],
+            breaks: vec![DaySpan::new((10, 0), (10, 30)), DaySpan::new((18, 0), (18, 30))],
+            working_days: [true, true, true, true, true, false, false],
+        }
+    }
+}
+
+impl WorkingHours {
+    /// Spans separated by commas, e.g. "06:00-14:00, 14:00-22:00"; blank for none
+    pub fn parse_spans(text: &str) -> Result<Vec<DaySpan>, String> {
+        text.split(',').filter(|span| !span.trim().is_empty()).map(str::parse).collect()
+    }
+
+    /// The spans as `parse_spans` reads them
+    pub fn format_spans(spans: &[DaySpan]) -> String {
+        spans.iter().map(DaySpan::to_string).collect::<Vec<_>>().join(", ")
+    }
+
+    /// When the shop works between `from` and `to`, as merged intervals in order
+    fn working(&self, from: NaiveDateTime, to: NaiveDateTime) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        // A shift or break of the day before may still run at `from`
+        let days = || {
+            let first = from.date().pred_opt().unwrap_or(from.date());
+            first.iter_days().take_while(move |date| date.and_time(NaiveTime::MIN) < to)
+        };
+        let mut working: Vec<(NaiveDateTime, NaiveDateTime)> = days()
+            .filter(|date| self.working_days[date.weekday().num_days_from_monday() as usize])
+            .flat_map(|date| self.shifts.iter().map(move |shift| shift.on(date)))
+            .collect();
+        working.sort();
+        let mut merged: Vec<(NaiveDateTime, NaiveDateTime)> = Vec::with_capacity(working.len());
+        for (start, end) in working {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        for (break_start, break_end) in days().flat_map(|date| self.breaks.iter().map(move |pause| pause.on(date))) {
+            merged = merged.into_iter()
+                .flat_map(|(start, end)| {
+                    if break_end <= start || break_start >= end {
+                        return vec![(start, end)];
+                    }
+                    [(start, break_start), (break_end, end)].into_iter().filter(|(from, to)| from < to).collect()
+                })
+                .collect();
+        }
+        merged.into_iter()
+            .map(|(start, end)| (start.max(from), end.min(to)))
+            .filter(|(start, end)| start < end)
+            .collect()
+    }
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        let start = chrono::Local::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        Self {
+            start,
+            minutes_per_unit: 60.0,
+            shifts_per_day: 3,
+            working_hours: None,
+        }
+    }
+}
+
+/// Granularity of a tick in the time scale header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    Month,
+    Day,
+    Shift,
+}
+
+impl Calendar {
+    /// Convert a schedule time into a timestamp
+    pub fn to_datetime(&self, time: f64) -> NaiveDateTime {
+        let seconds = (time * self.minutes_per_unit * 60.0).round() as i64;
+        self.start + Duration::seconds(seconds)
+    }
+
+    /// Convert a timestamp into schedule time
+    pub fn to_time(&self, datetime: NaiveDateTime) -> f64 {
+        let seconds = (datetime - self.start).num_seconds() as f64;
+        seconds / 60.0 / self.minutes_per_unit
+    }
+
+    /// A schedule time as an ISO 8601 timestamp, e.g. "2024-01-02T21:00:00"
+    pub fn iso(&self, time: f64) -> String {
+        self.to_datetime(time).format("%Y-%m-%dT%H:%M:%S").to_string()
+    }
+
+    /// Schedule time units in a week
+    fn week(&self) -> f64 {
+        7.0 * 24.0 * 60.0 / self.minutes_per_unit
+    }
+
+    /// Whether the calendar can be used for scheduling: a positive unit length and, with
+    /// working hours, some working time in a week
+    pub fn check(&self) -> Result<(), String> {
+        if !(self.minutes_per_unit.is_finite() && self.minutes_per_unit > 0.0) {
+            return Err(format!("Minutes per unit must be positive, got {}", self.minutes_per_unit));
+        }
+        if self.working_hours.is_some() && self.off_shift(0.0, self.week()) == [(0.0, self.week())] {
+            return Err("The working hours leave no time to work".to_string());
+        }
+        Ok(())
+    }
+
+    /// The off-shift windows between the schedule times `from` and `to`, in order; none
+    /// without working hours
+    pub fn off_shift(&self, from: f64, to: f64) -> Vec<(f64, f64)> {
+        let Some(hours) = &self.working_hours else {
+            return Vec::new();
+        };
+        let mut windows = Vec::new();
+        let mut free_from = from;
+        for (start, end) in hours.working(self.to_datetime(from), self.to_datetime(to)) {
+            let (start, end) = (self.to_time(start), self.to_time(end));
+            if start > free_from {
+                windows.push((free_from, start));
+            }
+            free_from = end;
+        }
+        if free_from < to {
+            windows.push((free_from, to));
+        }
+        windows
+    }
+
+    /// The longest time worked without a break, in schedule time units; unlimited
+    /// without working hours
+    pub fn longest_working_stretch(&self) -> f64 {
+        let Some(hours) = &self.working_hours else {
+            return f64::INFINITY;
+        };
+        // A stretch may run into the next week, so look at two
+        let from = self.start.date().and_time(NaiveTime::MIN);
+        hours.working(from, from + Duration::days(14)).into_iter()
+            .map(|(start, end)| self.to_time(end) - self.to_time(start))
+            .fold(0.0, f64::max)
+    }
+
+    /// The schedule time at which `work` units of working time after `from` have
+    /// passed; `from + work` without working hours. Needs working time in every week
+    /// (see `check`).
+    pub fn working_time_end(&self, from: f64, work: f64) -> f64 {
+        let (mut at, mut remaining) = (from, work);
+        loop {
+            let until = at + self.week();
+            for (start, end) in self.off_shift(at, until).into_iter().chain([(until, until)]) {
+                if start - at >= remaining {
+                    return at + remaining;
+                }
+                remaining -= start - at;
+                at = end;
+            }
+        }
+    }
+
+    /// Nominal length of a tick of the given scale, in schedule time units
+    pub fn step_size(&self, scale: TimeScale) -> f64 {
+        let minutes = match scale {
+            TimeScale::Month => 30.0 * 24.0 * 60.0,
+            TimeScale::Day => 24.0 * 60.0,
+            TimeScale::Shift => 24.0 * 60.0 / self.shifts_per_day.max(1) as f64,
+        };
+        minutes / self.minutes_per_unit
+    }
+
+    /// Identify the scale of a tick from its step size
+    pub fn scale_of(&self, step_size: f64) -> Option<TimeScale> {
+        [TimeScale::Month, TimeScale::Day, TimeScale::Shift]
+            .into_iter()
+            .find(|&scale| (self.step_size(scale) - step_size).abs() < 1e-9)
+    }
+
+    /// Tick positions of one scale that fall within `[from, to]`
+    pub fn ticks(&self, scale: TimeScale, from: f64, to: f64) -> Vec<f64> {
+        let first = self.to_datetime(from.max(0.0));
+        let last = self.to_datetime(to.max(0.0));
+        let mut ticks = Vec::new();
+
+        match scale {
+            TimeScale::Month => {
+                let mut date = NaiveDate::from_ymd_opt(first.year(), first.month(), 1).unwrap();
+                while date.and_hms_opt(0, 0, 0).unwrap() <= last {
+                    ticks.push(self.to_time(date.and_hms_opt(0, 0, 0).unwrap()));
+                    date = date.checked_add_months(chrono::Months::new(1)).unwrap();
+                }
+            }
+            TimeScale::Day => {
+                let mut date = first.date();
+                while date.and_hms_opt(0, 0, 0).unwrap() <= last {
+                    ticks.push(self.to_time(date.and_hms_opt(0, 0, 0).unwrap()));
+                    date = date.succ_opt().unwrap();
+                }
+            }
+            TimeScale::Shift => {
+                let shift_minutes = 24 * 60 / self.shifts_per_day.max(1) as i64;
+                let mut datetime = first.date().and_hms_opt(0, 0, 0).unwrap();
+                while datetime <= last {
+                    ticks.push(self.to_time(datetime));
+                    datetime += Duration::minutes(shift_minutes);
+                }
+            }
+        }
+
+        ticks.retain(|&t| t >= from && t <= to);
+        ticks
+    }
+
+    /// Header label for a tick at `time`
+    pub fn label(&self, scale: TimeScale, time: f64) -> String {
+        let datetime = self.to_datetime(time);
+        match scale {
+            TimeScale::Month => datetime.format("%B %Y").to_string(),
+            TimeScale::Day => datetime.format("%a %d").to_string(),
+            TimeScale::Shift => {
+                let shift_minutes = 24 * 60 / self.shifts_per_day.max(1);
+                let minute_of_day = datetime.hour() * 60 + datetime.minute();
+                format!("S{} {}", minute_of_day / shift_minutes + 1, datetime.format("%H:%M"))
+            }
+        }
+    }
+}
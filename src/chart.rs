@@ -0,0 +1,538 @@
+//! Standalone Gantt and utilization chart rendering to SVG and PNG, independent of the GUI.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::{Instance, JsspError, ScheduledOperation, TimeMode};
+
+/// Job colors shared by the GUI and exported charts, indexed by `job_id % len`
+pub const JOB_COLORS: [[u8; 3]; 20] = [
+    [255, 99, 71],    // Tomato
+    [70, 130, 180],   // Steel Blue
+    [60, 179, 113],   // Medium Sea Green
+    [255, 165, 0],    // Orange
+    [147, 112, 219],  // Medium Purple
+    [255, 215, 0],    // Gold
+    [220, 20, 60],    // Crimson
+    [0, 191, 255],    // Deep Sky Blue
+    [50, 205, 50],    // Lime Green
+    [255, 105, 180],  // Hot Pink
+    [138, 43, 226],   // Blue Violet
+    [255, 140, 0],    // Dark Orange
+    [72, 209, 204],   // Medium Turquoise
+    [199, 21, 133],   // Medium Violet Red
+    [0, 206, 209],    // Dark Turquoise
+    [255, 69, 0],     // Red Orange
+    [186, 85, 211],   // Medium Orchid
+    [34, 139, 34],    // Forest Green
+    [255, 20, 147],   // Deep Pink
+    [30, 144, 255],   // Dodger Blue
+];
+
+/// The Okabe–Ito palette, distinguishable with every common form of color blindness
+const OKABE_ITO: [[u8; 3]; 8] = [
+    [230, 159, 0],    // Orange
+    [86, 180, 233],   // Sky Blue
+    [0, 158, 115],    // Bluish Green
+    [240, 228, 66],   // Yellow
+    [0, 114, 178],    // Blue
+    [213, 94, 0],     // Vermillion
+    [204, 121, 167],  // Reddish Purple
+    [0, 0, 0],        // Black
+];
+
+/// Saturated colors far apart in lightness as well as hue, for washed-out projectors
+const HIGH_CONTRAST: [[u8; 3]; 8] = [
+    [0, 0, 255],
+    [255, 255, 0],
+    [255, 0, 0],
+    [0, 255, 255],
+    [0, 128, 0],
+    [255, 0, 255],
+    [0, 0, 0],
+    [255, 128, 0],
+];
+
+/// Job colors of the GUI and exported charts. Jobs with a color of their own on the
+/// instance (`Instance::job_colors`) keep it in every palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Palette {
+    /// `JOB_COLORS`
+    #[default]
+    Standard,
+    /// Eight colors that stay apart with color blindness; jobs repeat them sooner
+    OkabeIto,
+    /// Strong colors with black outlines and labels in black or white, whichever reads better
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [Palette::Standard, Palette::OkabeIto, Palette::HighContrast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::OkabeIto => "Colorblind-Safe (Okabe–Ito)",
+            Palette::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Colors in order, repeated when there are more jobs
+    pub fn colors(&self) -> &'static [[u8; 3]] {
+        match self {
+            Palette::Standard => &JOB_COLORS,
+            Palette::OkabeIto => &OKABE_ITO,
+            Palette::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+
+    /// The `index`-th color, e.g. of a machine
+    pub fn color(&self, index: usize) -> [u8; 3] {
+        let colors = self.colors();
+        colors[index % colors.len()]
+    }
+
+    /// The instance's color for the job if it has one, otherwise the palette's
+    pub fn job_color(&self, instance: &Instance, job_id: usize) -> [u8; 3] {
+        instance.job_colors.get(&job_id).copied().unwrap_or_else(|| self.color(job_id))
+    }
+
+    /// Label color on a block of the given color. The standard palette always uses white.
+    pub fn label_color(&self, [r, g, b]: [u8; 3]) -> [u8; 3] {
+        let luma = 0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b);
+        if *self == Palette::Standard || luma < 150.0 {
+            [255, 255, 255]
+        } else {
+            [0, 0, 0]
+        }
+    }
+
+    /// Whether blocks get a heavy black outline
+    pub fn bold_outlines(&self) -> bool {
+        *self == Palette::HighContrast
+    }
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Text with the characters XML reserves escaped, for job and machine names
+pub(crate) fn xml_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// At most `max` characters of a name, ending in "…" when cut
+fn shortened(name: &str, max: usize) -> String {
+    if name.chars().count() <= max {
+        name.to_string()
+    } else {
+        name.chars().take(max - 1).chain(std::iter::once('…')).collect()
+    }
+}
+
+const WIDTH: f64 = 1200.0;
+const MARGIN_LEFT: f64 = 90.0;
+const MARGIN_RIGHT: f64 = 30.0;
+const MARGIN_TOP: f64 = 50.0;
+const ROW_HEIGHT: f64 = 40.0;
+const AXIS_HEIGHT: f64 = 45.0;
+const LEGEND_ROW_HEIGHT: f64 = 24.0;
+const LEGEND_ITEM_WIDTH: f64 = 90.0;
+
+/// Tick spacing of 1, 2 or 5 times a power of ten giving about `target` ticks
+pub(crate) fn tick_step(range: f64, target: f64) -> f64 {
+    let raw = (range / target).max(f64::MIN_POSITIVE);
+    let magnitude = 10f64.powf(raw.log10().floor());
+    [1.0, 2.0, 5.0, 10.0].iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= raw)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// The Gantt chart of a schedule as an SVG document (machines as rows, time on x)
+///
+/// ```
+/// use jssp_scheduler::chart::{gantt_svg, Palette};
+/// use jssp_scheduler::jssp::testing::instance_from_routes;
+/// use jssp_scheduler::jssp::TimeMode;
+///
+/// let mut instance = instance_from_routes(&[&[(0, 0.7), (1, 0.8)]], 2);
+/// let svg = gantt_svg(&instance, &instance.solve_greedy(), Palette::default());
+/// assert!(svg.contains(">0.6</text>") && !svg.contains("0.6000"));
+///
+/// // Whole units only in integer mode
+/// instance.set_time_mode(TimeMode::Integer);
+/// let svg = gantt_svg(&instance, &instance.solve_greedy(), Palette::default());
+/// assert!(svg.contains(">1</text>") && !svg.contains(">0.2</text>"));
+/// ```
+pub fn gantt_svg(instance: &Instance, schedule: &[ScheduledOperation], palette: Palette) -> String {
+    let makespan = instance.calculate_makespan(schedule).max(1.0);
+    let num_machines = schedule.iter()
+        .map(|op| op.machine_id + 1)
+        .max()
+        .unwrap_or(0)
+        .max(instance.num_machines());
+    let num_jobs = instance.jobs().len();
+    let time = instance.time_mode();
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = num_machines as f64 * ROW_HEIGHT;
+    let legend_columns = ((plot_width / LEGEND_ITEM_WIDTH).floor() as usize).max(1);
+    let legend_rows = num_jobs.div_ceil(legend_columns);
+    let height = MARGIN_TOP + plot_height + AXIS_HEIGHT + legend_rows as f64 * LEGEND_ROW_HEIGHT + 20.0;
+
+    let x_of = |t: f64| MARGIN_LEFT + t / makespan * plot_width;
+    // Machine 0 at the bottom, as in the GUI
+    let row_top = |machine_id: usize| MARGIN_TOP + (num_machines - 1 - machine_id) as f64 * ROW_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+        w = WIDTH, h = height
+    );
+    svg.push_str(concat!(
+        r#"<defs><pattern id="setup" width="6" height="6" patternUnits="userSpaceOnUse" patternTransform="rotate(45)">"#,
+        r##"<rect width="6" height="6" fill="#d9d9d9"/><line x1="0" y1="0" x2="0" y2="6" stroke="#808080" stroke-width="2"/>"##,
+        "</pattern></defs>\n",
+    ));
+    let _ = writeln!(svg, r#"<rect width="{}" height="{}" fill="white"/>"#, WIDTH, height);
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="30" font-size="18" fill="black">Gantt Chart - makespan {}</text>"#,
+        MARGIN_LEFT, time.format(instance.calculate_makespan(schedule))
+    );
+
+    // Machine rows and time grid
+    for machine_id in 0..num_machines {
+        let y = row_top(machine_id);
+        let fill = if machine_id % 2 == 0 { "#f5f5f5" } else { "#ffffff" };
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            MARGIN_LEFT, y, plot_width, ROW_HEIGHT, fill
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="13" text-anchor="end" dominant-baseline="central" fill="black">{}</text>"#,
+            MARGIN_LEFT - 8.0, y + ROW_HEIGHT / 2.0, xml_text(&shortened(&instance.machine_name(machine_id), 12))
+        );
+    }
+    let axis_y = MARGIN_TOP + plot_height;
+    // Whole units in integer mode; labels as precise as the step, at most the time mode's
+    let step = match time {
+        TimeMode::Integer => tick_step(makespan, 10.0).max(1.0),
+        TimeMode::Fractional => tick_step(makespan, 10.0),
+    };
+    let decimals = ((-step.log10().floor()).max(0.0) as usize).min(time.decimals());
+    // Counted rather than summed, so that steps like 0.1 do not pile up rounding errors
+    for tick in (0..).map(|i| i as f64 * step).take_while(|tick| *tick <= makespan + 1e-9) {
+        let x = x_of(tick);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="#cccccc" stroke-width="1"/>"##,
+            MARGIN_TOP, axis_y + 5.0
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" font-size="12" text-anchor="middle" fill="black">{:.*}</text>"#,
+            axis_y + 20.0, decimals, tick
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{axis_y}" x2="{}" y2="{axis_y}" stroke="black" stroke-width="1"/>"#,
+        MARGIN_LEFT, MARGIN_LEFT + plot_width
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="13" text-anchor="middle" fill="black">Time (units)</text>"#,
+        MARGIN_LEFT + plot_width / 2.0, axis_y + 38.0
+    );
+
+    // Machine downtime as gray bands, cut off at the makespan
+    for window in instance.downtime().iter().filter(|window| window.start < makespan) {
+        let (x, end) = (x_of(window.start), x_of(window.end.min(makespan)));
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#b0b0b0" fill-opacity="0.6"><title>Downtime | {} - {}</title></rect>"##,
+            x, row_top(window.machine_id), end - x, ROW_HEIGHT, time.format(window.start), time.format(window.end)
+        );
+    }
+
+    // Operations, with setups hatched in front of them
+    let block_height = ROW_HEIGHT * 0.8;
+    let high_priority = instance.high_priority_jobs();
+    for op in schedule {
+        let y = row_top(op.machine_id) + (ROW_HEIGHT - block_height) / 2.0;
+        if op.setup_time > 0.0 {
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="url(#setup)" stroke="#808080" stroke-width="0.5"/>"##,
+                x_of(op.setup_start()), y, x_of(op.start_time) - x_of(op.setup_start()), block_height
+            );
+        }
+        let color = palette.job_color(instance, op.job_id);
+        // A preempted operation is drawn as its segments, joined by a thin line
+        if !op.segments.is_empty() {
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{}" y1="{y_mid}" x2="{}" y2="{y_mid}" stroke="{}" stroke-width="1.5"/>"#,
+                x_of(op.start_time), x_of(op.end_time), hex(color), y_mid = y + block_height / 2.0
+            );
+        }
+        let (mut x, mut width) = (0.0, 0.0);
+        for (from, to) in op.intervals() {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="black" stroke-width="{}"><title>{} Op {} | {} - {}</title></rect>"#,
+                x_of(from), y, x_of(to) - x_of(from), block_height, hex(color),
+                if high_priority.contains(&op.job_id) { 3.0 } else if palette.bold_outlines() { 2.0 } else { 0.5 },
+                xml_text(&instance.job_name(op.job_id)), op.operation_id, time.format(from), time.format(to)
+            );
+            if x_of(to) - x_of(from) > width {
+                (x, width) = (x_of(from), x_of(to) - x_of(from));
+            }
+        }
+
+        // Full name if it fits, short name if that fits, nothing otherwise; about 7
+        // points per character
+        let name = instance.job_name(op.job_id);
+        let label = if width > (12.0 + 7.0 * name.chars().count() as f64).max(60.0) {
+            xml_text(&name)
+        } else if width > 24.0 {
+            format!("J{}", op.job_id)
+        } else {
+            continue;
+        };
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="12" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+            x + width / 2.0, y + block_height / 2.0, hex(palette.label_color(color)), label
+        );
+    }
+
+    // Transfers between machines as thin arrows from the end of an operation to when
+    // the job arrives at the next machine
+    if instance.transfer_times().is_some() {
+        svg.push_str(concat!(
+            r#"<defs><marker id="transfer" viewBox="0 0 6 6" refX="6" refY="3" markerWidth="6" markerHeight="6" orient="auto">"#,
+            r##"<path d="M0,0 L6,3 L0,6 z" fill="#606060"/></marker></defs>"##,
+            "\n",
+        ));
+        let mid = |machine_id: usize| row_top(machine_id) + ROW_HEIGHT / 2.0;
+        let orders = instance.job_sequences(schedule);
+        for op in schedule {
+            // The operation the job goes on to, in the order the schedule processes them
+            let order = orders.get(op.job_id).map_or(&[][..], |order| &order[..]);
+            let next_id = order.iter().position(|&id| id == op.operation_id).and_then(|index| order.get(index + 1));
+            let Some(next) = next_id.and_then(|&next_id| schedule.iter().find(|next| next.job_id == op.job_id && next.operation_id == next_id)) else {
+                continue;
+            };
+            let transfer = instance.transfer_time(Some(op.machine_id), next.machine_id);
+            if transfer > 0.0 {
+                let _ = writeln!(
+                    svg,
+                    r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#606060" stroke-width="1" marker-end="url(#transfer)"/>"##,
+                    x_of(op.end_time), mid(op.machine_id), x_of(op.end_time + transfer), mid(next.machine_id)
+                );
+            }
+        }
+    }
+
+    // Legend
+    let legend_top = axis_y + AXIS_HEIGHT + 5.0;
+    for (index, job) in instance.jobs().iter().enumerate() {
+        let x = MARGIN_LEFT + (index % legend_columns) as f64 * LEGEND_ITEM_WIDTH;
+        let y = legend_top + (index / legend_columns) as f64 * LEGEND_ROW_HEIGHT;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="{y}" width="14" height="14" fill="{}"/><text x="{}" y="{}" font-size="12" dominant-baseline="central" fill="black">{}</text>"#,
+            hex(palette.job_color(instance, job.id)), x + 20.0, y + 7.0, xml_text(&shortened(&instance.job_name(job.id), 9))
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Stacked busy/setup/idle bars per machine as an SVG document, in percent of the makespan
+pub fn utilization_svg(instance: &Instance, schedule: &[ScheduledOperation]) -> String {
+    const BUSY: &str = "#4682b4";
+    const IDLE: &str = "#e6e6e6";
+
+    let stats = instance.statistics(schedule);
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = stats.machines.len() as f64 * ROW_HEIGHT;
+    let axis_y = MARGIN_TOP + plot_height;
+    let height = axis_y + AXIS_HEIGHT + LEGEND_ROW_HEIGHT + 20.0;
+    let x_of = |percent: f64| MARGIN_LEFT + percent / 100.0 * plot_width;
+    let bar_height = ROW_HEIGHT * 0.6;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+        w = WIDTH, h = height
+    );
+    svg.push_str(concat!(
+        r#"<defs><pattern id="setup" width="6" height="6" patternUnits="userSpaceOnUse" patternTransform="rotate(45)">"#,
+        r##"<rect width="6" height="6" fill="#d9d9d9"/><line x1="0" y1="0" x2="0" y2="6" stroke="#808080" stroke-width="2"/>"##,
+        "</pattern></defs>\n",
+    ));
+    let _ = writeln!(svg, r#"<rect width="{}" height="{}" fill="white"/>"#, WIDTH, height);
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="30" font-size="18" fill="black">Machine Utilization - average {:.1}%</text>"#,
+        MARGIN_LEFT, stats.average_utilization() * 100.0
+    );
+
+    // Machine 0 at the top, reading like the statistics tables
+    for (row, machine) in stats.machines.iter().enumerate() {
+        let y = MARGIN_TOP + row as f64 * ROW_HEIGHT + (ROW_HEIGHT - bar_height) / 2.0;
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="13" text-anchor="end" dominant-baseline="central" fill="black">{}</text>"#,
+            MARGIN_LEFT - 8.0, y + bar_height / 2.0, xml_text(&shortened(&instance.machine_name(machine.machine_id), 12))
+        );
+        let mut offset = 0.0;
+        for (time, fill) in [(machine.busy, BUSY), (machine.setup, "url(#setup)"), (machine.idle, IDLE)] {
+            let percent = stats.percent_of_makespan(time);
+            if percent <= 0.0 {
+                continue;
+            }
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="#808080" stroke-width="0.5"><title>{} ({:.1}%)</title></rect>"##,
+                x_of(offset), y, x_of(offset + percent) - x_of(offset), bar_height, fill, instance.time_mode().format(time), percent
+            );
+            offset += percent;
+        }
+        let busy_percent = stats.percent_of_makespan(machine.busy);
+        if x_of(busy_percent) - x_of(0.0) > 50.0 {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-size="12" text-anchor="end" dominant-baseline="central" fill="white">{:.1}%</text>"#,
+                x_of(busy_percent) - 6.0, y + bar_height / 2.0, busy_percent
+            );
+        }
+    }
+
+    for tick in (0..=100).step_by(10) {
+        let x = x_of(tick as f64);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{x}" y1="{}" x2="{x}" y2="{}" stroke="#cccccc" stroke-width="1"/>"##,
+            axis_y, axis_y + 5.0
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x}" y="{}" font-size="12" text-anchor="middle" fill="black">{}%</text>"#,
+            axis_y + 20.0, tick
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{}" y1="{axis_y}" x2="{}" y2="{axis_y}" stroke="black" stroke-width="1"/>"#,
+        MARGIN_LEFT, MARGIN_LEFT + plot_width
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="13" text-anchor="middle" fill="black">Share of makespan</text>"#,
+        MARGIN_LEFT + plot_width / 2.0, axis_y + 38.0
+    );
+
+    let legend_top = axis_y + AXIS_HEIGHT + 5.0;
+    for (index, (name, fill)) in [("Busy", BUSY), ("Setup", "url(#setup)"), ("Idle", IDLE)].iter().enumerate() {
+        let x = MARGIN_LEFT + index as f64 * LEGEND_ITEM_WIDTH;
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{x}" y="{legend_top}" width="14" height="14" fill="{}" stroke="#808080" stroke-width="0.5"/><text x="{}" y="{}" font-size="12" dominant-baseline="central" fill="black">{}</text>"##,
+            fill, x + 20.0, legend_top + 7.0, name
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The Gantt chart rasterized to PNG bytes, using the same font as the GUI
+pub fn gantt_png(instance: &Instance, schedule: &[ScheduledOperation], palette: Palette) -> Result<Vec<u8>, JsspError> {
+    rasterize(&gantt_svg(instance, schedule, palette))
+}
+
+/// The utilization chart rasterized to PNG bytes
+pub fn utilization_png(instance: &Instance, schedule: &[ScheduledOperation]) -> Result<Vec<u8>, JsspError> {
+    rasterize(&utilization_svg(instance, schedule))
+}
+
+fn rasterize(svg: &str) -> Result<Vec<u8>, JsspError> {
+    use resvg::{tiny_skia, usvg};
+
+    let mut options = usvg::Options::default();
+    let fonts = egui::FontDefinitions::default();
+    let fontdb = options.fontdb_mut();
+    // The GUI's proportional font becomes sans-serif, the others serve as fallbacks
+    let proportional = &fonts.families[&egui::FontFamily::Proportional];
+    for name in proportional {
+        if let Some(font) = fonts.font_data.get(name) {
+            fontdb.load_font_data(font.font.to_vec());
+        }
+    }
+    let family = fontdb.faces().next().and_then(|face| face.families.first()).map(|f| f.0.clone());
+    if let Some(family) = family {
+        fontdb.set_sans_serif_family(family);
+    }
+
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| JsspError::Serialization(format!("Failed to build chart: {}", e)))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| JsspError::Serialization("Chart is too large to rasterize".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.encode_png().map_err(|e| JsspError::Serialization(format!("Failed to encode PNG: {}", e)))
+}
+
+/// `svg` with the run that produced its schedule as JSON in a `<metadata>` element
+pub fn svg_with_run(svg: &str, run: &RunMetadata) -> String {
+    let metadata = format!("<metadata id=\"jssp-run\">{}</metadata>", xml_text(&run.to_json().to_string()));
+    match svg.find("<svg").and_then(|open| svg[open..].find('>').map(|end| open + end + 1)) {
+        Some(at) => format!("{}{}{}", &svg[..at], metadata, &svg[at..]),
+        None => svg.to_string(),
+    }
+}
+
+/// `png` with the run that produced its schedule as JSON in an `iTXt` chunk with the
+/// keyword `jssp-run`, right after the header chunk
+pub fn png_with_run(png: &[u8], run: &RunMetadata) -> Vec<u8> {
+    // Signature, then the IHDR chunk: length, type, 13 bytes of data and the CRC
+    const AFTER_HEADER: usize = 8 + 4 + 4 + 13 + 4;
+    if png.len() < AFTER_HEADER {
+        return png.to_vec();
+    }
+    // Keyword, no compression, no language tag and no translated keyword, then the text
+    let mut chunk = b"iTXt".to_vec();
+    chunk.extend_from_slice(b"jssp-run\0\0\0\0\0");
+    chunk.extend_from_slice(run.to_json().to_string().as_bytes());
+    let mut bytes = png[..AFTER_HEADER].to_vec();
+    bytes.extend_from_slice(&(chunk.len() as u32 - 4).to_be_bytes());
+    bytes.extend_from_slice(&chunk);
+    bytes.extend_from_slice(&crc32(&chunk).to_be_bytes());
+    bytes.extend_from_slice(&png[AFTER_HEADER..]);
+    bytes
+}
+
+/// The CRC-32 that closes every PNG chunk
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
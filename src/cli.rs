@@ -0,0 +1,648 @@
+//! Command-line front end: solve an instance file without opening the window.
+//!
+//! Interactive use gets a live progress bar (runs done, incumbent makespan and gap to
+//! the lower bound) on stderr and colored summary tables at the end. `--json` prints one
+//! JSON document instead and `--quiet` only the objective value, both without progress
+//! output, for use from scripts. Colors are dropped when stdout is not a terminal or
+//! `NO_COLOR` is set. `batch` solves a folder of instance files and prints a results CSV,
+//! and `milp` writes an instance as a model for MILP solvers (see `jssp::milp`).
+//! `solve` and `batch` can run a program of one's own as an algorithm with `--external`
+//! (see `jssp::external`).
+//! `rpc` keeps the process running and answers JSON requests instead (see `rpc`), and
+//! `--serve`, with the `rest-api` feature, answers them over HTTP (see `rest`).
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use clap::{Args, Parser, Subcommand};
+use colored::{ColoredString, Colorize};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+use crate::jssp::batch::{default_threads, instance_files, load_instance, results_csv, spawn_batch, write_solutions, BatchResult, BatchSolution};
+use crate::jssp::cache::{InstanceCache, InstanceSet};
+use crate::jssp::bounds::{optimality_gap, GapTracker};
+use crate::jssp::external::ExternalSolver;
+use crate::jssp::milp::{write_milp, MilpFormat};
+use crate::jssp::multistart::{multi_start_with_progress, MultiStartConfig};
+use crate::jssp::presets::{self, AlgorithmParams, Presets};
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::solution_formats::SolutionFormat;
+use crate::jssp::{Buffering, Instance, JsspError, Objective, ProblemType, Schedule, Solver, SolverRegistry};
+use crate::rpc;
+
+#[derive(Parser, Debug)]
+#[command(name = "jssp-scheduler", version, about = "Job shop scheduling; opens the GUI when run without a command")]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Answer REST requests on this address, e.g. "127.0.0.1:8080", instead of opening the GUI
+    #[cfg(feature = "rest-api")]
+    #[arg(long, value_name = "ADDRESS")]
+    pub serve: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Solve an instance file (OR-Tools JSON layout) and report the schedule
+    Solve(Box<SolveArgs>),
+    /// Solve every instance file of a folder and write a results CSV
+    Batch(Box<BatchArgs>),
+    /// List the algorithms with how they work, their running time and what to cite
+    ListAlgos {
+        /// Print the list as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write an instance file as a MILP model for an external solver
+    Milp {
+        /// Instance file in the OR-Tools `jobs_data` JSON layout
+        instance: PathBuf,
+        /// Write the model to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// "lp" or "mps"; defaults to the extension of `--output`, else "lp"
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Answer newline-delimited JSON-RPC requests on stdin, for use as a subprocess
+    Rpc,
+}
+
+#[derive(Args, Debug)]
+pub struct SolveArgs {
+    /// Instance file in the OR-Tools `jobs_data` JSON layout
+    pub instance: PathBuf,
+    /// Algorithm name, e.g. "Greedy" or "simulated-annealing" (case and separators are ignored)
+    #[arg(short, long, default_value = "Greedy")]
+    pub algorithm: String,
+    /// Independent runs of a stochastic algorithm, keeping the best
+    #[arg(short, long, default_value_t = 1)]
+    pub runs: usize,
+    /// Worker threads for the runs; 0 uses every core
+    #[arg(short, long, default_value_t = 0)]
+    pub threads: usize,
+    /// Seed of the first run; random if omitted
+    #[arg(short, long)]
+    pub seed: Option<u64>,
+    /// Objective the best run minimizes, by key (e.g. "makespan", "total_tardiness")
+    #[arg(long, default_value = "makespan")]
+    pub objective: String,
+    /// Also write the instance and solution to this file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Format of the `--output` file: "ortools", "jobshoplib" or "machine-order"
+    #[arg(long, default_value = "ortools")]
+    pub format: String,
+    /// Solve without buffers between machines: "no_wait" or "blocking" ("unlimited"
+    /// drops them again); defaults to what the instance file says
+    #[arg(long)]
+    pub buffering: Option<String>,
+    /// Let machine downtime interrupt operations, which resume after it
+    #[arg(long)]
+    pub preemptive: bool,
+    /// Solve as "job_shop", "flow_shop" or "open_shop"; defaults to what the instance
+    /// file says
+    #[arg(long)]
+    pub problem_type: Option<String>,
+    #[command(flatten)]
+    pub tuning: TuningArgs,
+    /// Print only the objective value
+    #[arg(short, long, conflicts_with = "json")]
+    pub quiet: bool,
+    /// Print the result as JSON
+    #[arg(long)]
+    pub json: bool,
+    /// Record the run in this experiment log (SQLite), e.g. the one the GUI's History tab shows
+    #[cfg(feature = "experiment-log")]
+    #[arg(long, value_name = "DATABASE")]
+    pub log: Option<PathBuf>,
+}
+
+/// Algorithm parameters, shared by `solve` and `batch`
+#[derive(Args, Debug)]
+pub struct TuningArgs {
+    /// Parameter preset of the algorithm: "fast", "balanced", "quality" or one from the
+    /// presets file
+    #[arg(long, default_value = presets::DEFAULT_PRESET)]
+    pub preset: String,
+    /// Presets file (TOML); defaults to the one the GUI uses
+    #[arg(long, value_name = "FILE")]
+    pub presets: Option<PathBuf>,
+    /// Override a preset value for this run, e.g. `--set simulated_annealing.iterations=50000`;
+    /// may be repeated
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    pub overrides: Vec<String>,
+    /// Offer a program as an algorithm, e.g. `--external 'mine=./solve {instance} {solution}'`
+    /// (see `jssp::external` for the placeholders and formats); may be repeated
+    #[arg(long = "external", value_name = "NAME=COMMAND")]
+    pub external: Vec<String>,
+    /// Stop runs of external solvers after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub external_time_limit: Option<f64>,
+}
+
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// Folder of instance files in the OR-Tools `jobs_data` JSON layout
+    pub folder: PathBuf,
+    /// Algorithm name, e.g. "Greedy" or "simulated-annealing" (case and separators are ignored)
+    #[arg(short, long, default_value = "Greedy")]
+    pub algorithm: String,
+    /// Worker threads, each solving one file at a time; 0 uses every core
+    #[arg(short, long, default_value_t = 0)]
+    pub threads: usize,
+    /// Write the results CSV to this file instead of stdout
+    #[arg(long, value_name = "FILE")]
+    pub csv: Option<PathBuf>,
+    /// Also write each schedule into this folder as `<instance>_solution.<ext>`
+    #[arg(long, value_name = "DIR")]
+    pub solutions: Option<PathBuf>,
+    /// Format of the `--solutions` files: "ortools", "jobshoplib" or "machine-order"
+    #[arg(long, default_value = "ortools")]
+    pub format: String,
+    /// Keep binary copies of the instances in the folder's `.jssp-cache`, as the GUI can
+    #[arg(long)]
+    pub cache: bool,
+    #[command(flatten)]
+    pub tuning: TuningArgs,
+    /// No progress bar or summary on stderr
+    #[arg(short, long)]
+    pub quiet: bool,
+}
+
+/// What a `solve` command produced
+struct Outcome {
+    instance: Instance,
+    algorithm: String,
+    runs: usize,
+    seed: u64,
+    objective: Objective,
+    schedule: Schedule,
+    runtime: Duration,
+    /// The same run as recorded in solution files
+    run: RunMetadata,
+}
+
+/// Run a command, returning the process exit code
+pub fn run(command: Command) -> i32 {
+    let result = match command {
+        Command::Solve(args) => solve(&args),
+        Command::Batch(args) => batch(&args),
+        Command::ListAlgos { json } => list_algorithms(json),
+        Command::Milp { instance, output, format } => export_milp(&instance, output.as_deref(), format.as_deref()),
+        Command::Rpc => rpc::serve(rpc::Session::default(), std::io::stdin().lock(), std::io::stdout().lock())
+            .map_err(|e| e.to_string()),
+    };
+    exit_code(result)
+}
+
+/// Run the REST API server (see `rest`), returning the process exit code
+#[cfg(feature = "rest-api")]
+pub fn serve(address: &str) -> i32 {
+    exit_code(crate::rest::serve(crate::rest::Api::default(), address))
+}
+
+fn exit_code(result: Result<(), String>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{} {}", "error:".red().bold(), e);
+            1
+        }
+    }
+}
+
+fn solve(args: &SolveArgs) -> Result<(), String> {
+    let params = algorithm_params(&args.tuning)?;
+    let registry = solver_registry(&args.tuning, &params)?;
+    let solver = find_solver(&registry, &args.algorithm)?;
+    let objective = find_objective(&args.objective)?;
+    let format = find_format(&args.format)?;
+    let mut instance = load_instance(&args.instance)?;
+    if let Some(key) = &args.buffering {
+        instance.buffering = find_buffering(key)?;
+    }
+    instance.preemptive |= args.preemptive;
+    if let Some(key) = &args.problem_type {
+        instance.set_problem_type(find_problem_type(key)?)?;
+    }
+
+    let runs = if solver.is_stochastic() { args.runs.max(1) } else { 1 };
+    if runs < args.runs && !args.quiet && !args.json {
+        eprintln!("{} {} is deterministic; solving once", "note:".yellow().bold(), solver.name());
+    }
+    let config = MultiStartConfig {
+        starts: runs,
+        threads: args.threads,
+        seed: args.seed.unwrap_or_else(rand::random),
+        objective,
+    };
+
+    let progress = if args.quiet || args.json { ProgressBar::hidden() } else { progress_bar(runs as u64, "runs") };
+    let tracker = Mutex::new(GapTracker::for_instance(&instance));
+    let started = Instant::now();
+    let result = multi_start_with_progress(solver, &instance, &config, &|schedule, _| {
+        let mut tracker = tracker.lock().unwrap();
+        tracker.update(instance.calculate_makespan(schedule));
+        if let Some(best) = tracker.best_makespan {
+            progress.set_message(format!("best makespan {}, {}", instance.time_mode().format(best), tracker.label()));
+        }
+        progress.inc(1);
+    });
+    progress.finish_and_clear();
+    if let Some(error) = result.failure() {
+        return Err(error.to_string());
+    }
+    if let Some(error) = result.errors.first().filter(|_| !args.quiet && !args.json) {
+        eprintln!("{} {} of {} runs failed: {}", "warning:".yellow().bold(), result.errors.len(), runs, error);
+    }
+
+    let runtime = started.elapsed();
+    let mut parameters = run_parameters(&args.tuning, &params, solver.name());
+    if runs > 1 {
+        parameters["threads"] = serde_json::json!(args.threads);
+    }
+    let outcome = Outcome {
+        algorithm: solver.name().to_string(),
+        runs,
+        seed: config.seed,
+        objective,
+        schedule: result.best,
+        runtime,
+        run: RunMetadata::new(solver.name(), parameters, solver.is_stochastic().then_some(config.seed), runs, runtime),
+        instance,
+    };
+    #[cfg(feature = "experiment-log")]
+    if let Some(path) = &args.log {
+        let log = crate::jssp::experiments::ExperimentLog::open(path)?;
+        log.record(&crate::jssp::experiments::Run {
+            instance: &outcome.instance,
+            schedule: &outcome.schedule,
+            algorithm: &outcome.algorithm,
+            parameters: serde_json::json!({
+                "runs": runs,
+                "threads": args.threads,
+                "preset": args.tuning.preset,
+                "algorithm": params.to_json(),
+            }),
+            seed: solver.is_stochastic().then_some(outcome.seed),
+            objective,
+            runtime: outcome.runtime,
+        })?;
+    }
+    if let Some(path) = &args.output {
+        let text = format.write_with_run(&outcome.instance, &outcome.schedule, Some(&outcome.run))?;
+        std::fs::write(path, text).map_err(JsspError::io("write", path))?;
+    }
+
+    if args.json {
+        let text = serde_json::to_string_pretty(&outcome_json(&outcome)).map_err(|e| e.to_string())?;
+        println!("{}", text);
+    } else if args.quiet {
+        println!("{}", outcome.instance.evaluate(objective, &outcome.schedule));
+    } else {
+        print_summary(&outcome);
+        if let Some(path) = &args.output {
+            println!("\nSolution written to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn batch(args: &BatchArgs) -> Result<(), String> {
+    let params = algorithm_params(&args.tuning)?;
+    let registry = solver_registry(&args.tuning, &params)?;
+    let name = find_solver(&registry, &args.algorithm)?.name().to_string();
+    let solver = registry.shared(&name).expect("found by name");
+    let format = find_format(&args.format)?;
+    let files = instance_files(&args.folder)?;
+    if files.is_empty() {
+        return Err(format!("No instance files (*.json) in {}", args.folder.display()));
+    }
+
+    let cache = args.cache.then(|| InstanceCache::in_folder(&args.folder));
+    let instances = Arc::new(InstanceSet::new(files, cache));
+    let threads = if args.threads == 0 { default_threads() } else { args.threads };
+    let progress = if args.quiet { ProgressBar::hidden() } else { progress_bar(instances.len() as u64, "files") };
+    let started = Instant::now();
+    let mut results: Vec<BatchResult> = Vec::with_capacity(instances.len());
+    for result in spawn_batch(Arc::clone(&instances), solver, threads) {
+        let name = result.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        progress.set_message(name);
+        progress.inc(1);
+        results.push(result);
+    }
+    progress.finish_and_clear();
+    results.sort_by_key(|result| result.index);
+
+    let csv = results_csv(&name, &results);
+    match &args.csv {
+        Some(path) => std::fs::write(path, csv).map_err(JsspError::io("write", path))?,
+        None => print!("{}", csv),
+    }
+    let written = match &args.solutions {
+        Some(dir) => write_solutions(&results, dir, format, &run_parameters(&args.tuning, &params, &name))?,
+        None => 0,
+    };
+
+    if !args.quiet {
+        let solved: Vec<&BatchSolution> = results.iter().filter_map(|result| result.outcome.as_ref().ok()).collect();
+        let failed = results.len() - solved.len();
+        let mean_gap = solved.iter().fold(0.0, |total, solution| total + optimality_gap(solution.makespan, solution.lower_bound))
+            / solved.len().max(1) as f64;
+        eprintln!(
+            "{} {} of {} instances with {} in {:.1} s, mean gap {}",
+            "Solved".bold(), solved.len(), results.len(), name, started.elapsed().as_secs_f64(), colored_gap(mean_gap)
+        );
+        if failed > 0 {
+            eprintln!("{} {} file(s) could not be solved; see the Error column", "warning:".yellow().bold(), failed);
+        }
+        if let Some(path) = &args.csv {
+            eprintln!("Results written to {}", path.display());
+        }
+        if let Some(dir) = &args.solutions {
+            eprintln!("{} solution(s) written to {}", written, dir.display());
+        }
+    }
+    Ok(())
+}
+
+/// The parameters `algorithm` ran with and the preset they came from, as recorded with a run
+fn run_parameters(args: &TuningArgs, params: &AlgorithmParams, algorithm: &str) -> serde_json::Value {
+    let mut parameters = params.algorithm_json(algorithm);
+    parameters["preset"] = serde_json::json!(args.preset);
+    parameters
+}
+
+/// The parameters of the chosen preset with the `--set` overrides applied
+fn algorithm_params(args: &TuningArgs) -> Result<AlgorithmParams, String> {
+    let presets = match args.presets.clone().or_else(Presets::default_path) {
+        Some(path) => Presets::load(&path)?,
+        None => Presets::default(),
+    };
+    let mut params = presets.find(&args.preset)?.clone();
+    for assignment in &args.overrides {
+        params.set(assignment)?;
+    }
+    Ok(params)
+}
+
+/// The built-in algorithms with `params`, and the external solvers of `args`
+fn solver_registry(args: &TuningArgs, params: &AlgorithmParams) -> Result<SolverRegistry, String> {
+    let mut registry = params.registry();
+    let time_limit = match args.external_time_limit {
+        Some(seconds) if !(seconds > 0.0 && seconds.is_finite()) => {
+            return Err(format!("the external time limit must be a positive number of seconds, got {}", seconds));
+        }
+        limit => limit.map(Duration::from_secs_f64),
+    };
+    for definition in &args.external {
+        let mut solver = ExternalSolver::parse(definition)?;
+        solver.time_limit = time_limit;
+        registry.register(solver);
+    }
+    Ok(registry)
+}
+
+fn list_algorithms(json: bool) -> Result<(), String> {
+    let registry = SolverRegistry::default();
+    if json {
+        let algorithms: Vec<serde_json::Value> = registry.solvers().map(algorithm_json).collect();
+        let text = serde_json::to_string_pretty(&algorithms).map_err(|e| e.to_string())?;
+        println!("{}", text);
+        return Ok(());
+    }
+    for (index, solver) in registry.solvers().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let kind = if solver.is_stochastic() { "stochastic" } else { "deterministic" };
+        println!("{} {}", solver.name().bold().underline(), format!("({})", kind).dimmed());
+        println!("  {}", solver.description());
+        let info = solver.info();
+        let rows = [
+            ("Details", Some(info.details)),
+            ("Complexity", Some(info.complexity)),
+            ("Best for", Some(info.recommended_sizes)),
+            ("Cite", info.citation),
+        ];
+        for (label, value) in rows {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                println!("  {:<11} {}", label.dimmed(), value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Name, description and `SolverInfo` of an algorithm, as listed by `list-algos --json`
+/// and the `list_algorithms` RPC method
+pub(crate) fn algorithm_json(solver: &dyn Solver) -> serde_json::Value {
+    let info = solver.info();
+    serde_json::json!({
+        "name": solver.name(),
+        "description": solver.description(),
+        "stochastic": solver.is_stochastic(),
+        "details": info.details,
+        "complexity": info.complexity,
+        "recommended_sizes": info.recommended_sizes,
+        "citation": info.citation,
+    })
+}
+
+/// `SolverRegistry::find`, with the valid names in the error
+pub(crate) fn find_solver<'a>(registry: &'a SolverRegistry, name: &str) -> Result<&'a dyn Solver, String> {
+    registry.find(name).ok_or_else(|| {
+        let names: Vec<&str> = registry.solvers().map(|solver| solver.name()).collect();
+        format!("unknown algorithm '{}'; expected one of: {}", name, names.join(", "))
+    })
+}
+
+/// `Objective::from_key`, with the valid keys in the error
+pub(crate) fn find_objective(key: &str) -> Result<Objective, String> {
+    Objective::from_key(key).ok_or_else(|| {
+        let keys: Vec<&str> = Objective::ALL.iter().map(|objective| objective.key()).collect();
+        format!("unknown objective '{}'; expected one of: {}", key, keys.join(", "))
+    })
+}
+
+fn find_format(key: &str) -> Result<SolutionFormat, String> {
+    SolutionFormat::from_key(key).ok_or_else(|| {
+        let keys: Vec<&str> = SolutionFormat::ALL.iter().map(|format| format.key()).collect();
+        format!("unknown output format '{}'; expected one of: {}", key, keys.join(", "))
+    })
+}
+
+fn export_milp(instance: &Path, output: Option<&Path>, format: Option<&str>) -> Result<(), String> {
+    let key = format.or_else(|| output.and_then(|path| path.extension()).and_then(|ext| ext.to_str()));
+    let format = match key {
+        Some(key) => MilpFormat::from_extension(key).ok_or_else(|| format!("unknown model format '{}'; expected lp or mps", key))?,
+        None => MilpFormat::Lp,
+    };
+    let model = write_milp(&load_instance(instance)?, format);
+    match output {
+        Some(path) => std::fs::write(path, model).map_err(JsspError::io("write", path))?,
+        None => print!("{}", model),
+    }
+    Ok(())
+}
+
+fn find_buffering(key: &str) -> Result<Buffering, String> {
+    Buffering::from_key(key).ok_or_else(|| {
+        let keys: Vec<&str> = Buffering::ALL.iter().map(|buffering| buffering.key()).collect();
+        format!("unknown buffering '{}'; expected one of: {}", key, keys.join(", "))
+    })
+}
+
+fn find_problem_type(key: &str) -> Result<ProblemType, String> {
+    ProblemType::from_key(key).ok_or_else(|| {
+        let keys: Vec<&str> = ProblemType::ALL.iter().map(|problem_type| problem_type.key()).collect();
+        format!("unknown problem type '{}'; expected one of: {}", key, keys.join(", "))
+    })
+}
+
+fn progress_bar(steps: u64, unit: &str) -> ProgressBar {
+    let bar = ProgressBar::with_draw_target(Some(steps), ProgressDrawTarget::stderr());
+    bar.set_style(
+        ProgressStyle::with_template(&format!("{{spinner:.cyan}} [{{bar:30.cyan/blue}}] {{pos}}/{{len}} {}  {{msg}}  ({{elapsed}})", unit))
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    bar.set_message("solving...");
+    bar.enable_steady_tick(Duration::from_millis(100));
+    bar
+}
+
+fn outcome_json(outcome: &Outcome) -> serde_json::Value {
+    let instance = &outcome.instance;
+    let makespan = instance.calculate_makespan(&outcome.schedule);
+    let lower_bound = instance.makespan_lower_bound();
+    let stats = instance.statistics(&outcome.schedule);
+    serde_json::json!({
+        "instance": instance.name,
+        "num_jobs": instance.jobs().len(),
+        "num_machines": instance.num_machines(),
+        "buffering": instance.buffering,
+        "problem_type": instance.problem_type(),
+        "algorithm": outcome.algorithm,
+        "parameters": outcome.run.parameters,
+        "version": outcome.run.version,
+        "runs": outcome.runs,
+        "seed": outcome.seed,
+        "objective": outcome.objective.key(),
+        "objective_value": instance.evaluate(outcome.objective, &outcome.schedule),
+        "objectives": Objective::ALL.iter()
+            .map(|objective| (objective.key().to_string(), serde_json::json!(instance.evaluate(*objective, &outcome.schedule))))
+            .collect::<serde_json::Map<_, _>>(),
+        "makespan": makespan,
+        "lower_bound": lower_bound,
+        "lower_bounds": instance.lower_bounds(),
+        "gap": optimality_gap(makespan, lower_bound),
+        "best_known": instance.best_known().map(|known| serde_json::json!({
+            "makespan": known.makespan,
+            "optimal": known.optimal,
+            "gap": known.gap(makespan),
+        })),
+        "feasible": instance.validate(&outcome.schedule).is_ok(),
+        "runtime_seconds": outcome.runtime.as_secs_f64(),
+        "utilization": {
+            "average_utilization_percent": stats.average_utilization() * 100.0,
+            "total_idle": stats.total_idle(),
+            "machines": stats.machines_json(),
+        },
+        "schedule": outcome.schedule,
+    })
+}
+
+/// ", no-wait" or ", blocking" for an instance without buffers, ", preemptive" for
+/// one whose downtime interrupts operations, preceded by ", flow shop" or ", open shop"
+fn buffering_note(instance: &Instance) -> String {
+    let shop = match instance.problem_type() {
+        ProblemType::JobShop => String::new(),
+        problem_type => format!(", {}", problem_type.name().to_lowercase()),
+    };
+    let buffering = match instance.buffering {
+        Buffering::Unlimited if instance.preempts() => ", preemptive".to_string(),
+        Buffering::Unlimited => String::new(),
+        buffering => format!(", {}", buffering.name().to_lowercase()),
+    };
+    shop + &buffering
+}
+
+/// Green below 5%, yellow below 20%, red above
+fn colored_gap(gap: f64) -> ColoredString {
+    let text = format!("{:.1}%", gap * 100.0);
+    if gap < 0.05 {
+        text.green()
+    } else if gap < 0.2 {
+        text.yellow()
+    } else {
+        text.red()
+    }
+}
+
+fn print_summary(outcome: &Outcome) {
+    let instance = &outcome.instance;
+    let makespan = instance.calculate_makespan(&outcome.schedule);
+    let lower_bound = instance.makespan_lower_bound();
+    let violations = instance.validate(&outcome.schedule).err().unwrap_or_default();
+    let runs = if outcome.runs > 1 { format!(", best of {} runs", outcome.runs) } else { String::new() };
+    let mut rows: Vec<(&str, ColoredString)> = vec![
+        ("Instance", format!("{} ({} jobs × {} machines{})", instance.name, instance.jobs().len(), instance.num_machines(), buffering_note(instance)).normal()),
+        ("Algorithm", format!("{}{} (seed {})", outcome.algorithm, runs, outcome.seed).normal()),
+        ("Makespan", instance.time_mode().format(makespan).bold()),
+        ("Lower bound", instance.time_mode().format(lower_bound).normal()),
+        ("Gap", colored_gap(optimality_gap(makespan, lower_bound))),
+        (
+            "Feasible",
+            if violations.is_empty() { "yes".green() } else { format!("no, {} violation(s)", violations.len()).red().bold() },
+        ),
+        ("Runtime", format!("{:.3} s", outcome.runtime.as_secs_f64()).normal()),
+    ];
+    if let Some(known) = instance.best_known() {
+        let gap = known.gap(makespan);
+        let text = format!("{} ({}), gap {:+.1}%", instance.time_mode().format(known.makespan), known.kind(), gap * 100.0);
+        let value = if gap.abs() < 1e-9 && known.optimal { format!("{}, optimal", text).green().bold() } else { text.normal() };
+        rows.insert(5, ("Best known", value));
+    }
+    println!("{}", "Summary".bold().underline());
+    for (label, value) in rows {
+        println!("  {:<12} {}", label.dimmed(), value);
+    }
+
+    println!("\n{}", "Objectives".bold().underline());
+    for objective in Objective::ALL {
+        let line = format!("  {:<24} {:>10.2}", objective.name(), instance.evaluate(objective, &outcome.schedule));
+        if objective == outcome.objective {
+            println!("{}", line.bold());
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    let stats = instance.statistics(&outcome.schedule);
+    println!("\n{}", "Machines".bold().underline());
+    println!("{}", format!("  {:<16} {:>9} {:>9} {:>9}  {:<20}", "Machine", "Busy", "Setup", "Idle", "Utilization").dimmed());
+    for machine in &stats.machines {
+        let filled = (machine.utilization * 20.0).round().clamp(0.0, 20.0) as usize;
+        let bar = format!("{}{}", "█".repeat(filled), "░".repeat(20 - filled));
+        let bar = if machine.utilization >= 0.8 {
+            bar.green()
+        } else if machine.utilization >= 0.5 {
+            bar.yellow()
+        } else {
+            bar.red()
+        };
+        println!(
+            "  {:<16} {:>9.2} {:>9.2} {:>9.2}  {} {:>5.1}%",
+            instance.machine_name(machine.machine_id),
+            machine.busy,
+            machine.setup,
+            machine.idle,
+            bar,
+            machine.utilization * 100.0
+        );
+    }
+}
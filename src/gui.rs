@@ -1,38 +1,397 @@
+mod analytics_view;
+#[cfg(not(target_arch = "wasm32"))]
+mod batch_view;
+mod benchmark_view;
+mod coloring;
+mod compare_view;
+mod convergence_view;
+mod dashboard;
+mod disruption_view;
+mod dynamic_view;
+mod explain_view;
+#[cfg(not(target_arch = "wasm32"))]
+mod external_view;
+#[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+mod experiments_view;
+mod files;
+mod filters;
+mod gantt_blocks;
+mod gantt_edit;
+mod gantt_index;
+mod history;
+mod instance_editor;
+mod instance_stats_view;
+mod minimap;
+mod pareto_view;
+mod playback;
+mod pool_view;
+mod presets_view;
+mod scalability_view;
+mod scenarios;
+mod selection;
+mod settings;
+mod shortcuts;
+mod simulation_view;
+mod soft_constraints_view;
+mod suggestions_view;
+mod toasts;
+mod trace_view;
+mod urgent_job_view;
+
 use eframe::egui;
-use egui_plot::Plot;
-use crate::jssp::{generate_random_instance, JsspSolver, ScheduledOperation};
-use std::collections::HashSet;
+use egui_plot::{AxisHints, GridMark, Plot, VPlacement};
+use crate::calendar::{Calendar, TimeScale, WorkingHours};
+use crate::chart::{gantt_png, gantt_svg, png_with_run, svg_with_run, utilization_svg, Palette};
+#[cfg(not(target_arch = "wasm32"))]
+use batch_view::BatchState;
+use compare_view::{Baseline, CompareLayout};
+use convergence_view::Convergence;
+use dashboard::{AppTab, KpiSnapshot};
+use disruption_view::DisruptionForm;
+use dynamic_view::DynamicLab;
+use explain_view::ExplainViewer;
+#[cfg(not(target_arch = "wasm32"))]
+use external_view::ExternalSolvers;
+#[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+use experiments_view::ExperimentsState;
+use files::write_file;
+use filters::ScheduleFilter;
+use gantt_blocks::{Block, GanttBlocks, SimplifiedBars, DETAIL_LIMIT, OFF_SHIFT_LIMIT};
+use gantt_edit::{GanttDrag, BLOCK_HEIGHT};
+use gantt_index::GanttIndex;
+pub use coloring::ColorBy;
+pub use gantt_edit::GanttRows;
+use history::History;
+use instance_editor::InstanceEditor;
+use minimap::CursorReadout;
+use playback::{OperationState, Playback};
+use pool_view::PoolResult;
+use presets_view::PresetState;
+use scenarios::Scenarios;
+use selection::Selection;
+use shortcuts::GanttNavigation;
+use toasts::Toasts;
+use urgent_job_view::UrgentJobForm;
+use trace_view::TraceViewer;
+use crate::jssp::batch::csv_field;
+use crate::jssp::benchmark::Benchmark;
+use crate::jssp::bounds::optimality_gap;
+use crate::jssp::critical_path::SwapSuggestion;
+use crate::jssp::local_search::MoveStrategy;
+use crate::jssp::milp::{write_milp, MilpFormat};
+use crate::jssp::multistart::{multi_start, MultiStartConfig};
+use crate::jssp::ortools::from_ortools_json;
+use crate::jssp::pareto::{ParetoConfig, ParetoFront};
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::repair::{JobInsertion, RightShiftRepair};
+use crate::jssp::scalability::ScalabilityTest;
+use crate::jssp::simulation::{FailureModel, SimulationConfig, SimulationReport};
+use crate::jssp::solution_formats::{read_solution, SolutionFormat};
+use crate::jssp::soft::SoftConstraint;
+use crate::jssp::{generate_instance, Buffering, DurationDistribution, GeneratorConfig, JsspError, JsspSolver, Objective, Operation, ProblemType, Routing, ScheduledOperation, Solver, SolverRegistry, TimeMode, Violation};
+use crate::report::schedule_html_with_run;
+use crate::workbook::schedule_xlsx_with_run;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 pub struct JsspApp {
+    tab: AppTab,
+    /// Problems open in other tabs; the shown one is in the fields below
+    scenarios: Scenarios,
     solver: Option<JsspSolver>,
+    solvers: SolverRegistry,
+    /// Index into `solvers` of the algorithm the Solve button runs
+    selected_solver: usize,
+    /// Solve with the best of several parallel runs when the algorithm is stochastic
+    multi_start: bool,
+    /// Start warm-startable algorithms from the schedule shown (see `SolverConfig::initial_solution`)
+    warm_start: bool,
+    /// Keep the best distinct schedules of single solves (see `Solver::solve_with_pool`)
+    keep_solution_pool: bool,
+    solution_pool_size: usize,
+    multi_start_config: MultiStartConfig,
+    /// Parameters of the tunable algorithms in `solvers`
+    presets: PresetState,
     schedule: Vec<ScheduledOperation>,
     makespan: f64,
+    objective: Objective,
     num_jobs: usize,
     num_machines: usize,
     min_duration: f64,
     max_duration: f64,
-    hidden_jobs: HashSet<usize>,
+    duration_distribution: DurationDistribution,
+    /// Durations on each machine are stretched by a random factor up to `max_machine_slowdown`
+    use_machine_slowdown: bool,
+    max_machine_slowdown: f64,
+    alternative_machines: usize,
+    /// Jobs visit `min_operations..=max_operations` machines instead of all of them
+    partial_routing: bool,
+    /// With partial routing, a job may come back to a machine it visited before
+    reentrant_routing: bool,
+    /// Flow shops send every generated job along the same route
+    problem_type: ProblemType,
+    min_operations: usize,
+    max_operations: usize,
+    use_setup_times: bool,
+    max_setup_time: f64,
+    use_transfer_times: bool,
+    max_transfer_time: f64,
+    use_operators: bool,
+    num_operators: usize,
+    /// Generate uncertain durations, with a standard deviation of this share of the mean
+    use_duration_variation: bool,
+    duration_variation: f64,
+    use_release_dates: bool,
+    max_release_time: f64,
+    use_due_dates: bool,
+    due_date_slack: f64,
+    use_weights: bool,
+    max_weight: f64,
+    /// Generate whole-number times, like benchmark instances
+    integer_times: bool,
+    filter: ScheduleFilter,
+    violations: Vec<Violation>,
+    calendar_text: CalendarText,
+    /// Label the Gantt chart's time axis with dates when a calendar is attached
+    date_axis: bool,
     show_export_dialog: bool,
+    show_pareto_window: bool,
+    show_simulation_window: bool,
+    show_soft_constraints_window: bool,
+    show_trace_window: bool,
+    trace_viewer: TraceViewer,
+    show_explain_window: bool,
+    explain_viewer: ExplainViewer,
+    #[cfg(not(target_arch = "wasm32"))]
+    show_external_window: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    external_solvers: ExternalSolvers,
+    show_dynamic_window: bool,
+    dynamic_lab: DynamicLab,
+    history: History,
+    instance_editor: InstanceEditor,
+    /// Clear All was clicked and awaits confirmation
+    confirm_clear: bool,
+    show_analytics: bool,
+    pareto_config: ParetoConfig,
+    pareto_front: Option<ParetoFront>,
+    selected_pareto: Option<usize>,
+    drag_editing: bool,
+    gantt_rows: GanttRows,
+    show_critical_path: bool,
+    /// Shade the row of the most loaded machine in the Gantt chart
+    show_bottleneck: bool,
+    gantt_drag: Option<GanttDrag>,
+    gantt_navigation: GanttNavigation,
+    /// Show an overview strip below the Gantt chart
+    show_minimap: bool,
+    /// Time under the pointer in the Gantt chart when it was last there
+    gantt_cursor: Option<CursorReadout>,
+    theme: egui::ThemePreference,
+    /// Job colors of the Gantt chart and exported charts
+    palette: Palette,
+    color_by: ColorBy,
+    hovered_block: Option<(usize, usize)>,
+    /// The block last right-clicked in the Gantt chart, for its context menu
+    context_block: Option<(usize, usize)>,
+    /// The operations drawn in the Gantt chart this frame, for finding the one under the pointer
+    gantt_index: GanttIndex,
+    /// Operation picked in the Gantt chart or the Schedule Details table
+    selection: Selection,
+    edit_status: Option<Result<String, String>>,
+    swap_suggestions: Option<Vec<SwapSuggestion>>,
+    /// Moves the "Improve Solution" button tries
+    improve_strategy: MoveStrategy,
+    /// Progress of the last single solve, plotted below the Gantt chart
+    convergence: Convergence,
+    /// The run that produced the shown schedule, written into its exports; `None` for
+    /// imported schedules
+    run: Option<RunMetadata>,
+    show_convergence: bool,
+    kpi_history: Vec<KpiSnapshot>,
+    simulation_config: SimulationConfig,
+    /// Draw machine failures in the robustness simulation, not only duration deviations
+    simulation_failures: bool,
+    simulation_report: Option<SimulationReport>,
+    show_disruption_window: bool,
+    disruption_form: DisruptionForm,
+    /// The last right-shift repair, with the jobs that slipped
+    disruption_repair: Option<RightShiftRepair>,
+    show_urgent_job_window: bool,
+    urgent_job_form: UrgentJobForm,
+    /// The last urgent job inserted, with the jobs that slipped
+    urgent_insertion: Option<JobInsertion>,
+    show_solution_pool_window: bool,
+    /// The pool of the last single solve that kept one
+    solution_pool: Option<PoolResult>,
+    playback: Playback,
+    /// Schedule pinned for comparison with later solves
+    baseline: Option<Baseline>,
+    show_compare_window: bool,
+    compare_layout: CompareLayout,
+    show_benchmark_window: bool,
+    /// Benchmark in progress, advanced a few runs per frame
+    benchmark: Option<Benchmark>,
+    benchmark_results: Vec<Benchmark>,
+    show_scalability_window: bool,
+    /// Scalability test in progress or finished, advanced one size per frame
+    scalability: Option<ScalabilityTest>,
+    /// Batch solving reads folders and runs worker threads, so it is desktop-only
+    #[cfg(not(target_arch = "wasm32"))]
+    show_batch_window: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    batch: BatchState,
+    #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+    experiments: ExperimentsState,
+    /// Where the last export was saved, to start the next export dialog there
+    last_export_dir: Option<PathBuf>,
+    /// Outcomes of exports and imports
+    toasts: Toasts,
+    /// Instance files opened recently, newest first
+    recent_instances: Vec<PathBuf>,
+    #[cfg(target_arch = "wasm32")]
+    pending_import: files::PendingFile,
+    #[cfg(target_arch = "wasm32")]
+    pending_solution: files::PendingFile,
 }
 
 impl Default for JsspApp {
     fn default() -> Self {
         Self {
+            tab: AppTab::Schedule,
+            scenarios: Scenarios::default(),
             solver: None,
+            solvers: SolverRegistry::default(),
+            selected_solver: 0,
+            multi_start: false,
+            warm_start: false,
+            keep_solution_pool: false,
+            solution_pool_size: 10,
+            multi_start_config: MultiStartConfig::default(),
+            presets: PresetState::default(),
             schedule: Vec::new(),
             makespan: 0.0,
+            objective: Objective::Makespan,
             num_jobs: 5,
             num_machines: 3,
             min_duration: 1.0,
             max_duration: 10.0,
-            hidden_jobs: HashSet::new(),
+            duration_distribution: DurationDistribution::Uniform,
+            use_machine_slowdown: false,
+            max_machine_slowdown: 1.5,
+            alternative_machines: 0,
+            partial_routing: false,
+            reentrant_routing: false,
+            problem_type: ProblemType::JobShop,
+            min_operations: 2,
+            max_operations: 5,
+            use_setup_times: false,
+            max_setup_time: 5.0,
+            use_transfer_times: false,
+            max_transfer_time: 5.0,
+            use_operators: false,
+            num_operators: 2,
+            use_duration_variation: false,
+            duration_variation: 0.2,
+            use_release_dates: false,
+            max_release_time: 20.0,
+            use_due_dates: false,
+            due_date_slack: 2.0,
+            use_weights: false,
+            max_weight: 5.0,
+            integer_times: false,
+            filter: ScheduleFilter::default(),
+            violations: Vec::new(),
+            calendar_text: CalendarText::default(),
+            date_axis: true,
             show_export_dialog: false,
+            show_pareto_window: false,
+            show_simulation_window: false,
+            show_soft_constraints_window: false,
+            show_trace_window: false,
+            trace_viewer: TraceViewer::default(),
+            show_explain_window: false,
+            explain_viewer: ExplainViewer::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            show_external_window: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            external_solvers: ExternalSolvers::default(),
+            show_dynamic_window: false,
+            dynamic_lab: DynamicLab::default(),
+            history: History::default(),
+            instance_editor: InstanceEditor::default(),
+            confirm_clear: false,
+            show_analytics: false,
+            pareto_config: ParetoConfig::default(),
+            pareto_front: None,
+            selected_pareto: None,
+            drag_editing: true,
+            gantt_rows: GanttRows::Machines,
+            show_critical_path: false,
+            show_bottleneck: false,
+            gantt_drag: None,
+            gantt_navigation: GanttNavigation::default(),
+            show_minimap: false,
+            gantt_cursor: None,
+            theme: egui::ThemePreference::System,
+            palette: Palette::Standard,
+            color_by: ColorBy::Job,
+            hovered_block: None,
+            context_block: None,
+            gantt_index: GanttIndex::default(),
+            selection: Selection::default(),
+            edit_status: None,
+            swap_suggestions: None,
+            improve_strategy: MoveStrategy::SwapAndInsertion,
+            convergence: Convergence::default(),
+            run: None,
+            show_convergence: true,
+            kpi_history: Vec::new(),
+            simulation_config: SimulationConfig::uniform(3, FailureModel::default()),
+            simulation_failures: true,
+            simulation_report: None,
+            show_disruption_window: false,
+            disruption_form: DisruptionForm::default(),
+            disruption_repair: None,
+            show_urgent_job_window: false,
+            urgent_job_form: UrgentJobForm::default(),
+            urgent_insertion: None,
+            show_solution_pool_window: false,
+            solution_pool: None,
+            playback: Playback::default(),
+            baseline: None,
+            show_compare_window: false,
+            compare_layout: CompareLayout::Overlay,
+            show_benchmark_window: false,
+            benchmark: None,
+            benchmark_results: Vec::new(),
+            show_scalability_window: false,
+            scalability: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            show_batch_window: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch: BatchState::default(),
+            #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+            experiments: ExperimentsState::default(),
+            last_export_dir: None,
+            toasts: Toasts::default(),
+            recent_instances: Vec::new(),
+            #[cfg(target_arch = "wasm32")]
+            pending_import: Default::default(),
+            #[cfg(target_arch = "wasm32")]
+            pending_solution: Default::default(),
         }
     }
 }
 
 impl eframe::App for JsspApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.save_settings(storage);
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        ctx.set_theme(self.theme);
+
         // Configure better text rendering and sizing
         let mut style = (*ctx.style()).clone();
         style.text_styles = [
@@ -47,11 +406,56 @@ impl eframe::App for JsspApp {
         style.spacing.button_padding = egui::vec2(12.0, 6.0);
         style.spacing.item_spacing = egui::vec2(10.0, 8.0);
         ctx.set_style(style);
+
+        self.handle_history_shortcuts(ctx);
+        self.handle_shortcuts(ctx);
+        self.render_menu_bar(ctx);
+        self.toasts.render(ctx);
         
+        if self.show_analytics && self.tab == AppTab::Schedule && !self.schedule.is_empty() {
+            egui::SidePanel::right("analytics_panel")
+                .default_width(320.0)
+                .show(ctx, |ui| self.render_analytics_panel(ui));
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_solve(ctx);
+        if self.show_convergence && self.tab == AppTab::Schedule && !self.convergence.is_empty() {
+            egui::TopBottomPanel::bottom("convergence_panel")
+                .resizable(true)
+                .default_height(200.0)
+                .show(ctx, |ui| self.render_convergence_panel(ui));
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Job Shop Scheduling Problem - Greedy Solver");
+            ui.heading("Job Shop Scheduling Problem");
+            self.render_scenario_tabs(ui);
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.tab, AppTab::Schedule, "Schedule");
+                ui.selectable_value(&mut self.tab, AppTab::Dashboard, "Dashboard");
+                ui.selectable_value(&mut self.tab, AppTab::Instance, "Edit Instance");
+                #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+                ui.selectable_value(&mut self.tab, AppTab::History, "History")
+                    .on_hover_text("Past solves from the experiment log, with their instances and schedules");
+            });
             ui.separator();
 
+            if self.tab == AppTab::Dashboard {
+                self.render_dashboard(ui);
+                return;
+            }
+
+            if self.tab == AppTab::Instance {
+                self.render_instance_editor(ui);
+                return;
+            }
+
+            #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+            if self.tab == AppTab::History {
+                self.render_experiments_tab(ui);
+                return;
+            }
+
             // Control panel
             ui.horizontal(|ui| {
                 ui.label("Number of Jobs:");
@@ -61,6 +465,14 @@ impl eframe::App for JsspApp {
                 
                 ui.label("Number of Machines:");
                 ui.add(egui::Slider::new(&mut self.num_machines, 2..=20));
+
+                ui.separator();
+
+                ui.label("Alternative Machines:")
+                    .on_hover_text("Extra eligible machines per operation (0 = classic JSSP, >0 = flexible job shop)");
+                let max_alternatives = self.num_machines - 1;
+                self.alternative_machines = self.alternative_machines.min(max_alternatives);
+                ui.add(egui::Slider::new(&mut self.alternative_machines, 0..=max_alternatives));
             });
 
             ui.horizontal(|ui| {
@@ -81,61 +493,519 @@ impl eframe::App for JsspApp {
                         self.min_duration = (self.max_duration - 1.0).max(1.0);
                     }
                 }
+
+                ui.checkbox(&mut self.integer_times, "Integer Times")
+                    .on_hover_text("Generate whole-number durations, setups, release and due dates, like the benchmark instances of the literature");
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_setup_times, "Setup Times")
+                    .on_hover_text("Generate sequence-dependent setup times between jobs on each machine");
+                if self.use_setup_times {
+                    ui.label("Max Setup:");
+                    ui.add(egui::Slider::new(&mut self.max_setup_time, 0.0..=20.0));
+                }
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_transfer_times, "Transfer Times")
+                    .on_hover_text("Jobs need time to travel between machines, e.g. on an AGV; the Gantt chart draws each transfer as a thin arrow");
+                if self.use_transfer_times {
+                    ui.label("Max Transfer:");
+                    ui.add(egui::Slider::new(&mut self.max_transfer_time, 0.0..=20.0));
+                }
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_operators, "Operators")
+                    .on_hover_text("Every operation also needs one of a few operators, so fewer machines than there are can run at once; the Gantt chart shows the operators below the machines");
+                if self.use_operators {
+                    ui.label("Operators:");
+                    ui.add(egui::Slider::new(&mut self.num_operators, 1..=10));
+                }
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_duration_variation, "Uncertain Durations")
+                    .on_hover_text("Durations are normally distributed around the generated ones; choose Expected Makespan as the objective to plan for the average of sampled scenarios");
+                if self.use_duration_variation {
+                    ui.label("Std. Dev.:");
+                    ui.add(egui::Slider::new(&mut self.duration_variation, 0.0..=1.0).custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Durations:");
+                let current = self.duration_distribution;
+                egui::ComboBox::from_id_salt("duration_distribution")
+                    .selected_text(current.name())
+                    .show_ui(ui, |ui| {
+                        for distribution in DurationDistribution::all(self.min_duration, self.max_duration) {
+                            if ui.selectable_label(distribution.name() == current.name(), distribution.name()).clicked()
+                                && distribution.name() != current.name()
+                            {
+                                self.duration_distribution = distribution;
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("How durations spread between Min and Max Duration; uniform durations make unrealistically easy instances");
+                match &mut self.duration_distribution {
+                    DurationDistribution::Normal { mean, std_dev } => {
+                        ui.label("Mean:");
+                        ui.add(egui::DragValue::new(mean).range(self.min_duration..=self.max_duration).speed(0.5));
+                        ui.label("Std Dev:");
+                        ui.add(egui::DragValue::new(std_dev).range(0.0..=100.0).speed(0.1));
+                    }
+                    DurationDistribution::Bimodal { short_share } => {
+                        ui.label("Short Share:")
+                            .on_hover_text("Share of operations around a quarter of the range; the rest lie around three quarters");
+                        ui.add(egui::Slider::new(short_share, 0.0..=1.0));
+                    }
+                    DurationDistribution::Uniform | DurationDistribution::IntegerUniform => {}
+                }
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_machine_slowdown, "Slower Machines")
+                    .on_hover_text("Each machine stretches all its durations by its own random factor, so some machines are systematically slower");
+                if self.use_machine_slowdown {
+                    ui.label("Max Slowdown:");
+                    ui.add(egui::Slider::new(&mut self.max_machine_slowdown, 1.0..=3.0).suffix("×"));
+                }
             });
 
-            ui.separator();
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.use_release_dates, "Release Dates")
+                    .on_hover_text("Jobs become available at a random time");
+                if self.use_release_dates {
+                    ui.label("Max Release:");
+                    ui.add(egui::Slider::new(&mut self.max_release_time, 0.0..=200.0));
+                }
+
+                ui.separator();
+
+                ui.checkbox(&mut self.use_due_dates, "Due Dates")
+                    .on_hover_text("Due dates are never earlier than the job's earliest possible completion");
+                if self.use_due_dates {
+                    ui.label("Due Date Slack:");
+                    ui.add(egui::Slider::new(&mut self.due_date_slack, 1.0..=5.0).suffix("×"));
+                }
+            });
 
             ui.horizontal(|ui| {
-                if ui.add_sized([180.0, 32.0], egui::Button::new("Generate Problem")).clicked() {
-                    let jobs = generate_random_instance(
-                        self.num_jobs,
-                        self.num_machines,
-                        self.min_duration,
-                        self.max_duration,
-                    );
-                    self.solver = Some(JsspSolver::new(jobs, self.num_machines));
-                    self.schedule.clear();
-                    self.makespan = 0.0;
-                    self.hidden_jobs.clear();
+                ui.checkbox(&mut self.use_weights, "Job Weights")
+                    .on_hover_text("Give jobs random whole-number weights for the weighted objectives");
+                if self.use_weights {
+                    ui.label("Max Weight:");
+                    ui.add(egui::Slider::new(&mut self.max_weight, 1.0..=10.0).step_by(1.0));
                 }
 
-                if ui.add_sized([180.0, 32.0], egui::Button::new("Solve Schedule")).clicked() {
-                    if let Some(solver) = &self.solver {
-                        self.schedule = solver.solve_greedy();
-                        self.makespan = solver.calculate_makespan(&self.schedule);
-                    }
+                ui.separator();
+
+                ui.checkbox(&mut self.partial_routing, "Partial Routing")
+                    .on_hover_text("Jobs visit only some of the machines instead of each machine once");
+                if self.partial_routing {
+                    ui.label("Operations per Job:");
+                    ui.add(egui::DragValue::new(&mut self.min_operations).range(1..=self.max_operations));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(&mut self.max_operations).range(self.min_operations..=100));
+                    ui.checkbox(&mut self.reentrant_routing, "Reentrant")
+                        .on_hover_text("A job may return to a machine it visited before (never twice in a row)");
                 }
 
-                if ui.add_sized([180.0, 32.0], egui::Button::new("Export Solution")).clicked() {
-                    if !self.schedule.is_empty() {
-                        self.show_export_dialog = true;
-                    }
+                ui.separator();
+
+                ui.label("Problem Type:");
+                egui::ComboBox::from_id_salt("generated_problem_type")
+                    .selected_text(self.problem_type.name())
+                    .show_ui(ui, |ui| {
+                        for option in ProblemType::ALL {
+                            ui.selectable_value(&mut self.problem_type, option, option.name())
+                                .on_hover_text(option.description());
+                        }
+                    });
+            });
+
+            ui.separator();
+
+            ui.horizontal_wrapped(|ui| {
+                if ui.add_sized([180.0, 32.0], egui::Button::new("Generate Problem"))
+                    .on_hover_text("Generate a random instance with these settings (G)")
+                    .clicked()
+                {
+                    self.generate_problem();
+                }
+
+                if ui.add_sized([180.0, 32.0], egui::Button::new("Import OR-Tools"))
+                    .on_hover_text("Load a job shop instance (and optional solution) in OR-Tools' jobs_data JSON layout")
+                    .clicked()
+                {
+                    self.import_ortools_with_dialog();
+                }
+
+                if ui.add_enabled(self.solver.is_some(), egui::Button::new("Import Solution").min_size(egui::vec2(180.0, 32.0)))
+                    .on_hover_text("Show a schedule for this problem computed elsewhere: the app's JSON or CSV export, OR-Tools or JobShopLib JSON, or a machine order")
+                    .clicked()
+                {
+                    self.import_solution_with_dialog();
+                }
+
+                ui.label("Algorithm:");
+                egui::ComboBox::from_id_salt("algorithm")
+                    .selected_text(self.selected_solver_name())
+                    .show_ui(ui, |ui| {
+                        for (index, algorithm) in self.solvers.solvers().enumerate() {
+                            ui.selectable_value(&mut self.selected_solver, index, algorithm.name())
+                                .on_hover_text(algorithm.description());
+                        }
+                    });
+                let info_button = ui.small_button("ℹ").on_hover_text("How the algorithm works and what to cite");
+                let info_popup = ui.make_persistent_id("algorithm_info");
+                if info_button.clicked() {
+                    ui.memory_mut(|memory| memory.toggle_popup(info_popup));
+                }
+                if let Some(algorithm) = self.solvers.solvers().nth(self.selected_solver) {
+                    egui::popup_below_widget(ui, info_popup, &info_button, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+                        Self::render_solver_info(ui, algorithm);
+                    });
+                }
+                self.render_preset_controls(ui);
+
+                let stochastic = self.solvers.solvers().nth(self.selected_solver).is_some_and(|a| a.is_stochastic());
+                if stochastic {
+                    ui.checkbox(&mut self.multi_start, "Multi-start")
+                        .on_hover_text("Run the algorithm several times in parallel and keep the best schedule");
+                    ui.add_enabled_ui(self.multi_start, |ui| {
+                        ui.label("Runs:");
+                        ui.add(egui::DragValue::new(&mut self.multi_start_config.starts).range(1..=10_000));
+                        ui.label("Threads:");
+                        ui.add(egui::DragValue::new(&mut self.multi_start_config.threads).range(0..=256))
+                            .on_hover_text("0 uses every CPU core");
+                    });
+                }
+
+                if self.solvers.solvers().nth(self.selected_solver).is_some_and(|a| a.supports_warm_start()) {
+                    ui.add_enabled(!self.schedule.is_empty(), egui::Checkbox::new(&mut self.warm_start, "Start from current solution"))
+                        .on_hover_text("Begin the search from the schedule in the Gantt chart instead of a random one; not used by multi-start runs");
+                }
+
+                if self.solvers.solvers().nth(self.selected_solver).is_some_and(|a| a.supports_pool()) {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.keep_solution_pool, "Keep solution pool")
+                            .on_hover_text("Keep the best distinct schedules the search comes across, to browse in the Solution Pool window; \
+                                            such runs cannot be paused. Not used by multi-start runs");
+                        if self.keep_solution_pool {
+                            ui.add(egui::DragValue::new(&mut self.solution_pool_size).range(2..=50).prefix("top "));
+                        }
+                    });
+                }
+
+                if ui.add_enabled(
+                    !self.convergence.is_running(),
+                    egui::Button::new("Solve Schedule").min_size(egui::vec2(180.0, 32.0)),
+                )
+                    .on_hover_text("Solve with the selected algorithm (S)")
+                    .clicked()
+                {
+                    self.solve_schedule();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                self.render_resume_controls(ui);
+
+                if ui.add_enabled(
+                    self.solver.is_some() && !self.schedule.is_empty(),
+                    egui::Button::new("Improve Solution").min_size(egui::vec2(160.0, 32.0)),
+                )
+                    .on_hover_text("Hill-climb from the current schedule with swap or insertion moves on each machine until none shortens it")
+                    .clicked()
+                {
+                    self.improve_schedule();
+                }
+                egui::ComboBox::from_id_salt("improve_strategy")
+                    .selected_text(self.improve_strategy.name())
+                    .show_ui(ui, |ui| {
+                        for strategy in MoveStrategy::ALL {
+                            ui.selectable_value(&mut self.improve_strategy, strategy, strategy.name());
+                        }
+                    });
+
+                let benchmark_label = format!("Run Benchmark (x{})", benchmark_view::BENCHMARK_RUNS);
+                if ui.add_enabled(
+                    self.solver.is_some() && self.benchmark.is_none(),
+                    egui::Button::new(benchmark_label).min_size(egui::vec2(180.0, 32.0)),
+                )
+                    .on_hover_text("Solve repeatedly with the selected algorithm and compare makespan and runtime statistics")
+                    .clicked()
+                {
+                    self.start_benchmark();
+                }
+
+                let scalability_running = self.scalability.as_ref().is_some_and(|test| !test.is_complete());
+                if ui.add_enabled(
+                    !scalability_running,
+                    egui::Button::new("Scalability Test").min_size(egui::vec2(160.0, 32.0)),
+                )
+                    .on_hover_text("Solve generated instances from 10×5 to 200×50 with the selected algorithm, timing the solver and the drawing of each schedule")
+                    .clicked()
+                {
+                    self.start_scalability_test();
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Batch Solve"))
+                    .on_hover_text("Solve a folder of instances in parallel with the selected algorithm")
+                    .clicked()
+                {
+                    self.show_batch_window = true;
+                }
+
+                ui.label("Objective:");
+                egui::ComboBox::from_id_salt("objective")
+                    .selected_text(self.objective.name())
+                    .show_ui(ui, |ui| {
+                        for objective in Objective::ALL {
+                            ui.selectable_value(&mut self.objective, objective, objective.name());
+                        }
+                    });
+
+                if ui.add_sized([180.0, 32.0], egui::Button::new("Export Solution"))
+                    .on_hover_text("Save the schedule as JSON, CSV, a report or a chart (E)")
+                    .clicked()
+                    && !self.schedule.is_empty()
+                {
+                    self.show_export_dialog = true;
+                }
+
+                if ui.add_enabled(
+                    !self.schedule.is_empty(),
+                    egui::Button::new("Pin Baseline").min_size(egui::vec2(140.0, 32.0)),
+                )
+                    .on_hover_text("Keep this schedule to compare later solves against: makespan change, moved operations and an overlaid Gantt chart")
+                    .clicked()
+                {
+                    self.pin_baseline();
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Pareto Front")).clicked() {
+                    self.show_pareto_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Robustness"))
+                    .on_hover_text("Simulate the schedule under uncertain durations and random machine breakdowns")
+                    .clicked()
+                {
+                    self.show_simulation_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Disruption"))
+                    .on_hover_text("Break down a machine or stretch an operation and repair the schedule by right-shifting")
+                    .clicked()
+                {
+                    self.show_disruption_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Urgent Job"))
+                    .on_hover_text("Insert a rush order into the running schedule, keeping what has already started")
+                    .clicked()
+                {
+                    self.show_urgent_job_window = true;
                 }
 
-                if ui.add_sized([120.0, 32.0], egui::Button::new("Clear All")).clicked() {
-                    self.solver = None;
-                    self.schedule.clear();
-                    self.makespan = 0.0;
-                    self.hidden_jobs.clear();
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Solution Pool"))
+                    .on_hover_text("Browse the best distinct schedules of the last solve and load any of them")
+                    .clicked()
+                {
+                    self.show_solution_pool_window = true;
+                }
+
+                if ui.add_sized([160.0, 32.0], egui::Button::new("Dynamic Arrivals"))
+                    .on_hover_text("Dispatch jobs arriving over time with a dispatching rule and compare the rules")
+                    .clicked()
+                {
+                    self.show_dynamic_window = true;
+                }
+
+                if ui.add_sized([160.0, 32.0], egui::Button::new("Soft Constraints"))
+                    .on_hover_text("Due dates, waiting limits, operator limits and pins that may be broken at a penalty")
+                    .clicked()
+                {
+                    self.show_soft_constraints_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Search Trace"))
+                    .on_hover_text("Record a local-search run and replay it move by move")
+                    .clicked()
+                {
+                    self.show_trace_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::Button::new("Explain Mode"))
+                    .on_hover_text("Build a schedule step by step, seeing which operations competed and why one was placed")
+                    .clicked()
+                {
+                    self.show_explain_window = true;
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.add_sized([150.0, 32.0], egui::Button::new("External Solvers"))
+                    .on_hover_text("Run a program of your own as an algorithm, e.g. a solver written in C++")
+                    .clicked()
+                {
+                    self.show_external_window = true;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::SelectableLabel::new(self.show_analytics, "Analytics"))
+                    .on_hover_text("Show machine utilization, idle time and job flow/waiting times beside the Gantt chart")
+                    .clicked()
+                {
+                    self.show_analytics = !self.show_analytics;
+                }
+
+                if ui.add_sized([140.0, 32.0], egui::SelectableLabel::new(self.show_convergence, "Convergence"))
+                    .on_hover_text("Plot the best value against the iteration while an iterative algorithm solves, to see whether the search is still improving")
+                    .clicked()
+                {
+                    self.show_convergence = !self.show_convergence;
+                }
+
+                self.render_history_buttons(ui);
+
+                if ui.add_enabled(self.solver.is_some(), egui::Button::new("Clear All").min_size(egui::vec2(120.0, 32.0)))
+                    .clicked()
+                {
+                    self.confirm_clear = true;
                 }
             });
 
+            if self.solver.is_some() {
+                ui.horizontal(|ui| {
+                    self.render_time_mode_control(ui);
+                    ui.separator();
+                    self.render_problem_type_control(ui);
+                    self.render_buffering_control(ui);
+                    self.render_preemption_control(ui);
+                    ui.separator();
+                    self.render_calendar_controls(ui);
+                });
+                self.render_working_hours_controls(ui);
+            }
+
             ui.separator();
 
             // Display problem information
+            let mut show_suggestions = false;
             if let Some(solver) = &self.solver {
+                let shop = solver.problem_type().name().to_lowercase();
+                let variant = match (solver.is_flexible(), solver.buffering) {
+                    (false, Buffering::Unlimited) if solver.preempts() => format!(" (preemptive {})", shop),
+                    (true, Buffering::Unlimited) if solver.preempts() => format!(" (preemptive flexible {})", shop),
+                    (false, Buffering::Unlimited) if solver.problem_type() == ProblemType::JobShop => String::new(),
+                    (false, Buffering::Unlimited) => format!(" ({})", shop),
+                    (true, Buffering::Unlimited) => format!(" (flexible {})", shop),
+                    (false, buffering) => format!(" ({} {})", buffering.name().to_lowercase(), shop),
+                    (true, buffering) => format!(" ({} flexible {})", buffering.name().to_lowercase(), shop),
+                };
                 ui.label(format!(
-                    "Problem: {} jobs, {} machines, {} total operations",
-                    solver.jobs.len(),
-                    solver.num_machines,
-                    solver.jobs.iter().map(|j| j.operations.len()).sum::<usize>()
+                    "Problem{}: {} jobs, {} machines, {} total operations{}",
+                    if solver.name.is_empty() { String::new() } else { format!(" '{}'", solver.name) },
+                    solver.jobs().len(),
+                    solver.num_machines(),
+                    solver.num_operations(),
+                    variant
                 ));
+                self.render_instance_statistics(ui);
 
+                let time = solver.time_mode();
                 if !self.schedule.is_empty() {
-                    ui.colored_label(
-                        egui::Color32::GREEN,
-                        format!("✓ Solution found! Makespan: {:.2}", self.makespan)
-                    );
+                    if self.violations.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::GREEN,
+                            format!(
+                                "✓ Solution found! {}: {}",
+                                self.objective.name(),
+                                time.format(solver.evaluate(self.objective, &self.schedule))
+                            )
+                        );
+                        let bounds = solver.lower_bounds();
+                        ui.label(format!(
+                            "Makespan {}, LB {}, gap {:.1}%",
+                            time.format(self.makespan),
+                            time.format(bounds.best()),
+                            optimality_gap(self.makespan, bounds.best()) * 100.0
+                        )).on_hover_text(format!(
+                            "No schedule can finish before the lower bound (LB); the gap is how far above it this schedule is.\n\n\
+                            Job length: {}\nMachine load: {}\nOne-machine relaxation: {}\nPooled machines: {}",
+                            time.format(bounds.job_length), time.format(bounds.machine_load),
+                            time.format(bounds.one_machine), time.format(bounds.pooled)
+                        ));
+                        if let Some(known) = solver.best_known() {
+                            let gap = known.gap(self.makespan);
+                            let hover = "From the table of classic benchmark results; the gap is (makespan - best known) / best known, as benchmark papers report it";
+                            if gap.abs() < 1e-9 && known.optimal {
+                                ui.colored_label(egui::Color32::GREEN, format!("Optimal: matches the proven optimum {} of {}", time.format(known.makespan), known.name))
+                                    .on_hover_text(hover);
+                            } else if gap < 0.0 {
+                                ui.colored_label(egui::Color32::GOLD, format!("Below the best known {} of {} by {:.2}%", time.format(known.makespan), known.name, -gap * 100.0))
+                                    .on_hover_text(hover);
+                            } else {
+                                ui.label(format!("{} {}: {}, gap {:.1}%", known.name, known.kind(), time.format(known.makespan), gap * 100.0))
+                                    .on_hover_text(hover);
+                            }
+                        }
+                        // Every metric, whichever was optimized; due-date ones only when jobs have due dates
+                        let has_due_dates = solver.jobs().iter().any(|j| j.due_date.is_some());
+                        ui.label(
+                            self.objective_values(solver).iter()
+                                .filter(|(objective, _)| !matches!(objective, Objective::Penalized | Objective::ExpectedMakespan))
+                                .filter(|(objective, _)| has_due_dates || !objective.uses_due_dates())
+                                .map(|(objective, value)| format!("{}: {}", objective.name(), time.format(*value)))
+                                .collect::<Vec<_>>()
+                                .join("  |  ")
+                        );
+                        if solver.setup_times().is_some() {
+                            ui.label(format!("Total setup time: {}", time.format(solver.calculate_total_setup(&self.schedule))));
+                        }
+                        if solver.is_stochastic() {
+                            ui.label(format!(
+                                "Uncertain durations: expected makespan {}, 95th percentile {}",
+                                time.format(solver.expected_makespan(&self.schedule)),
+                                time.format(solver.makespan_percentile(&self.schedule, 0.95))
+                            ))
+                            .on_hover_text(format!(
+                                "The schedule's machine sequences played out in {} sampled scenarios of the durations, each operation as early as possible",
+                                solver.scenario_count()
+                            ));
+                        }
+                        if !solver.soft_constraints.is_empty() {
+                            let violations = solver.soft_violations(&self.schedule);
+                            let penalty = violations.iter().map(|v| v.penalty).fold(0.0, |total, p| total + p);
+                            ui.label(format!("Soft constraint penalty: {:.2} ({} violation(s))", penalty, violations.len()))
+                                .on_hover_text("Open Soft Constraints for the details");
+                        }
+                        show_suggestions = true;
+                    } else {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!(
+                                "✗ Infeasible schedule: {} violation(s). Makespan: {}",
+                                self.violations.len(),
+                                time.format(self.makespan)
+                            )
+                        );
+                        egui::CollapsingHeader::new("Violations")
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                egui::ScrollArea::vertical()
+                                    .id_salt("violations_scroll")
+                                    .max_height(100.0)
+                                    .show(ui, |ui| {
+                                        for violation in &self.violations {
+                                            ui.colored_label(egui::Color32::LIGHT_RED, violation.to_string());
+                                        }
+                                    });
+                            });
+                    }
                 }
             } else {
                 ui.colored_label(
@@ -144,16 +1014,96 @@ impl eframe::App for JsspApp {
                 );
             }
 
+            if show_suggestions {
+                self.render_swap_suggestions(ui);
+            }
+
             ui.separator();
 
             // Gantt Chart
             if !self.schedule.is_empty() {
-                ui.heading("Gantt Chart (by Machine)");
-                
+                ui.heading(match self.gantt_rows {
+                    GanttRows::Machines => "Gantt Chart (by Machine)",
+                    GanttRows::Jobs => "Gantt Chart (by Job)",
+                });
+
                 self.render_gantt_chart(ui);
             }
         });
 
+        if self.show_pareto_window {
+            self.render_pareto_window(ctx);
+        }
+
+        if self.show_simulation_window {
+            self.render_simulation_window(ctx);
+        }
+
+        if self.show_disruption_window {
+            self.render_disruption_window(ctx);
+        }
+
+        if self.show_urgent_job_window {
+            self.render_urgent_job_window(ctx);
+        }
+
+        if self.show_solution_pool_window {
+            self.render_solution_pool_window(ctx);
+        }
+
+        if self.show_soft_constraints_window {
+            self.render_soft_constraints_window(ctx);
+        }
+
+        if self.show_trace_window {
+            self.render_trace_window(ctx);
+        }
+
+        if self.show_explain_window {
+            self.render_explain_window(ctx);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_external_window {
+            self.render_external_window(ctx);
+        }
+
+        if self.show_dynamic_window {
+            self.render_dynamic_window(ctx);
+        }
+
+        if self.show_compare_window {
+            self.render_compare_window(ctx);
+        }
+
+        if self.scenarios.show_compare_window {
+            self.render_scenario_compare_window(ctx);
+        }
+
+        if self.confirm_clear {
+            self.render_clear_confirmation(ctx);
+        }
+
+        self.step_benchmark(ctx);
+        if self.show_benchmark_window {
+            self.render_benchmark_window(ctx);
+        }
+
+        self.step_scalability(ctx);
+        if self.show_scalability_window {
+            self.render_scalability_window(ctx);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.poll_batch(ctx);
+            if self.show_batch_window {
+                self.render_batch_window(ctx);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.poll_pending_import(ctx);
+
         // Export dialog window
         if self.show_export_dialog {
             egui::Window::new("Export Solution")
@@ -165,6 +1115,14 @@ impl eframe::App for JsspApp {
                     ui.add_space(10.0);
                     
                     ui.label("Select the format you want to export:");
+                    if self.filter.is_active() {
+                        let visible = {
+                            let visible = self.visibility();
+                            self.schedule.iter().filter(|op| visible(op)).count()
+                        };
+                        ui.checkbox(&mut self.filter.export_visible, format!("Only Visible Operations ({} of {})", visible, self.schedule.len()))
+                            .on_hover_text("Export only what the Gantt filters show, in JSON, CSV, HTML and the chart; the other formats need the whole schedule");
+                    }
                     ui.add_space(10.0);
 
                     if ui.add_sized([360.0, 30.0], egui::Button::new("JSON - Structured Data")).clicked() {
@@ -181,6 +1139,20 @@ impl eframe::App for JsspApp {
                     ui.small("Table format compatible with Excel and analysis tools");
                     ui.add_space(8.0);
 
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("XLSX - Excel Workbook")).clicked() {
+                        self.export_with_dialog("xlsx");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("Schedule and utilization sheets, plus a Gantt sheet drawn with conditional formatting");
+                    ui.add_space(8.0);
+
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("HTML - Printable Report")).clicked() {
+                        self.export_with_dialog("html");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("A single self-contained page with the results, Gantt chart, utilization and run parameters");
+                    ui.add_space(8.0);
+
                     if ui.add_sized([360.0, 30.0], egui::Button::new("TXT - Summary Report")).clicked() {
                         self.export_with_dialog("txt");
                         self.show_export_dialog = false;
@@ -188,11 +1160,46 @@ impl eframe::App for JsspApp {
                     ui.small("Human-readable summary with formatted table");
                     ui.add_space(8.0);
 
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("Export Chart - PNG or SVG")).clicked() {
+                        self.export_with_dialog("chart");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("Gantt chart image with job colors and labels, for reports");
+                    ui.add_space(8.0);
+
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("OR-Tools JSON - CP-SAT Interop")).clicked() {
+                        self.export_with_dialog("ortools");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("Instance and solution in the jobs_data layout of OR-Tools' examples");
+                    ui.add_space(8.0);
+
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("JobShopLib JSON - Python Interop")).clicked() {
+                        self.export_with_dialog("jobshoplib");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("Instance matrices and job sequences per machine, as JobShopLib's Schedule.to_dict()");
+                    ui.add_space(8.0);
+
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("Machine Order TXT - Solution Validators")).clicked() {
+                        self.export_with_dialog("machine-order");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("The jobs of every machine in processing order, for academic validators");
+                    ui.add_space(8.0);
+
+                    if ui.add_sized([360.0, 30.0], egui::Button::new("MILP Model - LP or MPS")).clicked() {
+                        self.export_with_dialog("milp");
+                        self.show_export_dialog = false;
+                    }
+                    ui.small("The problem as a disjunctive MILP for Gurobi, CBC or HiGHS; Load Solution reads their solution files back");
+                    ui.add_space(8.0);
+
                     if ui.add_sized([360.0, 30.0], egui::Button::new("ALL - Export All Formats")).clicked() {
                         self.export_with_dialog("all");
                         self.show_export_dialog = false;
                     }
-                    ui.small("Save JSON, CSV, and TXT together to a folder");
+                    ui.small("Save JSON, CSV, TXT, the Excel workbook and the utilization chart together to a folder");
                     
                     ui.add_space(15.0);
                     ui.separator();
@@ -207,372 +1214,1782 @@ impl eframe::App for JsspApp {
 }
 
 impl JsspApp {
-    fn render_gantt_chart(&mut self, ui: &mut egui::Ui) {
-        let colors = [
-            egui::Color32::from_rgb(255, 99, 71),    // Tomato
-            egui::Color32::from_rgb(70, 130, 180),   // Steel Blue
-            egui::Color32::from_rgb(60, 179, 113),   // Medium Sea Green
-            egui::Color32::from_rgb(255, 165, 0),    // Orange
-            egui::Color32::from_rgb(147, 112, 219),  // Medium Purple
-            egui::Color32::from_rgb(255, 215, 0),    // Gold
-            egui::Color32::from_rgb(220, 20, 60),    // Crimson
-            egui::Color32::from_rgb(0, 191, 255),    // Deep Sky Blue
-            egui::Color32::from_rgb(50, 205, 50),    // Lime Green
-            egui::Color32::from_rgb(255, 105, 180),  // Hot Pink
-            egui::Color32::from_rgb(138, 43, 226),   // Blue Violet
-            egui::Color32::from_rgb(255, 140, 0),    // Dark Orange
-            egui::Color32::from_rgb(72, 209, 204),   // Medium Turquoise
-            egui::Color32::from_rgb(199, 21, 133),   // Medium Violet Red
-            egui::Color32::from_rgb(0, 206, 209),    // Dark Turquoise
-            egui::Color32::from_rgb(255, 69, 0),     // Red Orange
-            egui::Color32::from_rgb(186, 85, 211),   // Medium Orchid
-            egui::Color32::from_rgb(34, 139, 34),    // Forest Green
-            egui::Color32::from_rgb(255, 20, 147),   // Deep Pink
-            egui::Color32::from_rgb(30, 144, 255),   // Dodger Blue
-        ];
-
-        // Create custom legend with colored circles and clickable job names
-        ui.horizontal(|ui| {
-            ui.label("Jobs:");
-            let unique_jobs: HashSet<usize> = self.schedule.iter().map(|op| op.job_id).collect();
-            let mut sorted_jobs: Vec<usize> = unique_jobs.into_iter().collect();
-            sorted_jobs.sort();
-            
-            for job_id in sorted_jobs {
-                let color = colors[job_id % colors.len()];
-                let is_hidden = self.hidden_jobs.contains(&job_id);
-                
-                ui.horizontal(|ui| {
-                    // Draw colored circle
-                    let (rect, response) = ui.allocate_exact_size(
-                        egui::vec2(12.0, 12.0),
-                        egui::Sense::click()
-                    );
-                    
-                    if is_hidden {
-                        ui.painter().circle_stroke(
-                            rect.center(),
-                            6.0,
-                            egui::Stroke::new(2.0, color)
-                        );
-                    } else {
-                        ui.painter().circle_filled(
-                            rect.center(),
-                            6.0,
-                            color
-                        );
-                    }
-                    
-                    // Job label
-                    let label_response = ui.selectable_label(false, format!("Job {}", job_id));
-                    
-                    // Toggle visibility on click
-                    if response.clicked() || label_response.clicked() {
-                        if is_hidden {
-                            self.hidden_jobs.remove(&job_id);
+    /// Create an app that shows an already solved instance
+    pub fn with_solution(solver: JsspSolver, schedule: Vec<ScheduledOperation>) -> Self {
+        let makespan = solver.calculate_makespan(&schedule);
+        let violations = solver.validate(&schedule).err().unwrap_or_default();
+        Self {
+            num_jobs: solver.jobs().len(),
+            num_machines: solver.num_machines(),
+            schedule,
+            makespan,
+            violations,
+            solver: Some(solver),
+            ..Default::default()
+        }
+    }
+
+    /// Create an app offering the algorithms of `solvers` instead of the built-in ones
+    pub fn with_solvers(solvers: SolverRegistry) -> Self {
+        Self { solvers, ..Default::default() }
+    }
+
+    /// Start with the Gantt chart rows showing machines or jobs, colored by job or by
+    /// machine respectively
+    pub fn with_gantt_rows(self, gantt_rows: GanttRows) -> Self {
+        let color_by = match gantt_rows {
+            GanttRows::Machines => ColorBy::Job,
+            GanttRows::Jobs => ColorBy::Machine,
+        };
+        Self { gantt_rows, color_by, ..self }
+    }
+
+    /// Start with the Gantt chart blocks colored by `color_by`
+    pub fn with_color_by(self, color_by: ColorBy) -> Self {
+        Self { color_by, ..self }
+    }
+
+    /// Time mode of the loaded problem, which decides how times are shown and exported
+    fn time_mode(&self) -> TimeMode {
+        self.solver.as_ref().map_or(TimeMode::Fractional, |solver| solver.time_mode())
+    }
+
+    fn selected_solver_name(&self) -> String {
+        self.solvers.solvers()
+            .nth(self.selected_solver)
+            .map_or_else(|| "None".to_string(), |algorithm| algorithm.name().to_string())
+    }
+
+    /// The algorithm info popover: description, `SolverInfo` and a button copying the citation
+    fn render_solver_info(ui: &mut egui::Ui, algorithm: &dyn Solver) {
+        let info = algorithm.info();
+        ui.set_width(380.0);
+        ui.heading(algorithm.name());
+        ui.label(algorithm.description());
+        if !info.details.is_empty() {
+            ui.separator();
+            ui.label(info.details);
+        }
+        egui::Grid::new("algorithm_info_grid").num_columns(2).spacing([12.0, 6.0]).show(ui, |ui| {
+            let kind = if algorithm.is_stochastic() { "Stochastic; benefits from multi-start" } else { "Deterministic" };
+            for (label, value) in [("Runs", kind), ("Complexity", info.complexity), ("Best for", info.recommended_sizes)] {
+                if !value.is_empty() {
+                    ui.strong(label);
+                    ui.add(egui::Label::new(value).wrap());
+                    ui.end_row();
+                }
+            }
+        });
+        if let Some(citation) = info.citation {
+            ui.separator();
+            ui.strong("Cite as");
+            ui.add(egui::Label::new(egui::RichText::new(citation).italics()).wrap());
+            if ui.button("Copy Citation").clicked() {
+                ui.ctx().copy_text(citation.to_string());
+            }
+        }
+    }
+
+    /// The color of every job of the loaded instance by job id: its own color if it has
+    /// one, otherwise the palette's
+    pub(super) fn job_colors(&self) -> Vec<egui::Color32> {
+        (0..self.instance_jobs())
+            .map(|job_id| {
+                let [r, g, b] = match &self.solver {
+                    Some(solver) => self.palette.job_color(solver, job_id),
+                    None => self.palette.color(job_id),
+                };
+                egui::Color32::from_rgb(r, g, b)
+            })
+            .collect()
+    }
+
+    /// Jobs of the loaded instance; the `num_jobs` field is only the generator setting
+    fn instance_jobs(&self) -> usize {
+        self.solver.as_ref().map_or(0, |solver| solver.jobs().len())
+    }
+
+    /// Machines of the loaded instance; the `num_machines` field is only the generator setting
+    fn instance_machines(&self) -> usize {
+        self.solver.as_ref().map_or(0, |solver| solver.num_machines())
+    }
+
+    /// The job's name in the loaded instance, or "Job <id>"
+    fn job_name(&self, job_id: usize) -> String {
+        self.solver.as_ref().map_or_else(|| format!("Job {}", job_id), |solver| solver.job_name(job_id))
+    }
+
+    /// The machine's name in the loaded instance, or "Machine <id>"
+    fn machine_name(&self, machine_id: usize) -> String {
+        self.solver.as_ref().map_or_else(|| format!("Machine {}", machine_id), |solver| solver.machine_name(machine_id))
+    }
+
+    /// Machines visited by generated jobs, from the routing controls
+    fn routing(&self) -> Routing {
+        let (min, max) = (self.min_operations, self.max_operations);
+        match (self.partial_routing, self.reentrant_routing) {
+            (false, _) => Routing::Complete,
+            (true, false) => Routing::Partial { min, max },
+            (true, true) => Routing::Reentrant { min, max },
+        }
+    }
+
+    /// Replace the problem with a random instance from the generator settings
+    fn generate_problem(&mut self) {
+        let config = GeneratorConfig {
+            num_jobs: self.num_jobs,
+            num_machines: self.num_machines,
+            min_duration: self.min_duration,
+            max_duration: self.max_duration,
+            alternatives_per_operation: self.alternative_machines,
+            routing: self.routing(),
+            problem_type: self.problem_type,
+            duration_distribution: self.duration_distribution,
+            max_machine_slowdown: self.use_machine_slowdown.then_some(self.max_machine_slowdown),
+            max_release_time: self.use_release_dates.then_some(self.max_release_time),
+            mean_interarrival: None,
+            due_date_slack: self.use_due_dates.then_some(self.due_date_slack),
+            max_setup_time: self.use_setup_times.then_some(self.max_setup_time),
+            max_transfer_time: self.use_transfer_times.then_some(self.max_transfer_time),
+            max_weight: self.use_weights.then_some(self.max_weight),
+            operators: self.use_operators.then_some(self.num_operators),
+            duration_variation: self.use_duration_variation.then_some(self.duration_variation),
+            // Keep the calendar of the previous problem attached
+            calendar: self.solver.as_ref().and_then(|s| s.calendar().cloned()),
+            time_mode: if self.integer_times { TimeMode::Integer } else { TimeMode::Fractional },
+        };
+        let mut instance = generate_instance(&config);
+        // Pins refer to operations of the previous problem; other soft constraints still apply
+        if let Some(solver) = &self.solver {
+            instance.soft_constraints = solver.soft_constraints.iter()
+                .filter(|c| !matches!(c, SoftConstraint::Pin { .. }))
+                .cloned()
+                .collect();
+        }
+        self.remember("Generate Problem");
+        self.replace_problem(Some(instance.into()));
+    }
+
+    /// Solve the loaded problem with the selected algorithm, as the Solve button does
+    fn solve_schedule(&mut self) {
+        if self.convergence.is_running() {
+            return;
+        }
+        let stochastic = self.solvers.solvers().nth(self.selected_solver).is_some_and(|a| a.is_stochastic());
+        let seed = rand::random();
+        if !(stochastic && self.multi_start) {
+            self.start_solve(seed);
+            return;
+        }
+        let algorithm = self.solvers.solvers().nth(self.selected_solver);
+        if let (Some(solver), Some(algorithm)) = (&self.solver, algorithm) {
+            let started = web_time::Instant::now();
+            self.multi_start_config.seed = seed;
+            self.multi_start_config.objective = self.objective;
+            let result = multi_start(algorithm, solver, &self.multi_start_config);
+            if let Some(error) = result.failure() {
+                self.toasts.error(&JsspError::Process(error.to_string()));
+                return;
+            }
+            if let Some(error) = result.errors.first() {
+                self.toasts.error(&JsspError::Process(format!("{} of {} runs failed: {}", result.errors.len(), result.values.len(), error)));
+            }
+            self.toasts.success(format!("Best of {} runs: {} {:.2}", result.values.len(), self.objective.name(), result.best_value));
+            let (name, runtime) = (algorithm.name().to_string(), started.elapsed());
+            let run = self.run_metadata(&name, seed, result.values.len(), runtime);
+            // The runs converge separately, so there is no single history to show
+            self.convergence = Convergence::default();
+            self.finish_solve(result.best, run);
+            #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+            self.record_solve(&name, Some(seed), true, runtime);
+        }
+    }
+
+    /// Show a newly solved schedule and the run it came from, keeping the previous one for undo
+    fn finish_solve(&mut self, schedule: Vec<ScheduledOperation>, run: RunMetadata) {
+        if self.solver.is_none() {
+            return;
+        }
+        self.remember("Solve");
+        self.edit_status = None;
+        // Solvers plan around pinned operations; re-timing puts them back exactly
+        let schedule = self.solver.as_ref().map_or(schedule.clone(), |solver| solver.hold_pins(schedule));
+        self.apply_edited_schedule(schedule);
+        self.run = Some(run);
+    }
+
+    /// The parameters of `algorithm` in the current preset, and the preset's name
+    fn run_parameters(&self, algorithm: &str) -> serde_json::Value {
+        let mut parameters = self.presets.params.algorithm_json(algorithm);
+        parameters["preset"] = serde_json::json!(self.presets.name);
+        parameters
+    }
+
+    /// A run of `algorithm` with the parameters of the current preset; the seed is kept
+    /// only for stochastic algorithms, and the threads only for more than one run
+    fn run_metadata(&self, algorithm: &str, seed: u64, runs: usize, runtime: std::time::Duration) -> RunMetadata {
+        let stochastic = self.solvers.get(algorithm).is_some_and(|a| a.is_stochastic());
+        let mut parameters = self.run_parameters(algorithm);
+        if runs > 1 {
+            parameters["threads"] = serde_json::json!(self.multi_start_config.threads);
+        }
+        RunMetadata::new(algorithm, parameters, stochastic.then_some(seed), runs, runtime)
+    }
+
+    /// Add the current schedule to the experiment log as a solve by `algorithm`
+    #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+    fn record_solve(&mut self, algorithm: &str, seed: Option<u64>, multi_start: bool, runtime: std::time::Duration) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let stochastic = self.solvers.get(algorithm).is_some_and(|a| a.is_stochastic());
+        let recorded = self.experiments.record(&crate::jssp::experiments::Run {
+            instance: solver.instance(),
+            schedule: &self.schedule,
+            algorithm,
+            parameters: if multi_start {
+                serde_json::json!({
+                    "runs": self.multi_start_config.starts,
+                    "threads": self.multi_start_config.threads,
+                    "preset": self.presets.name,
+                    "algorithm": self.presets.params.to_json(),
+                })
+            } else {
+                serde_json::json!({ "preset": self.presets.name, "algorithm": self.presets.params.to_json() })
+            },
+            seed: seed.filter(|_| stochastic),
+            objective: self.objective,
+            runtime,
+        });
+        if let Err(e) = recorded {
+            self.toasts.error(&e);
+        }
+    }
+
+    /// Switch to another problem (or none), dropping everything derived from the old one
+    fn replace_problem(&mut self, solver: Option<JsspSolver>) {
+        self.history.problem_id += 1;
+        if let Some(solver) = &solver {
+            self.num_jobs = solver.jobs().len();
+            self.num_machines = solver.num_machines();
+        }
+        self.solver = solver;
+        self.pareto_front = None;
+        self.schedule.clear();
+        self.makespan = 0.0;
+        self.filter.reset();
+        self.violations.clear();
+        self.edit_status = None;
+        self.selection = Selection::default();
+        self.swap_suggestions = None;
+        self.kpi_history.clear();
+        self.simulation_report = None;
+        self.disruption_repair = None;
+        self.urgent_insertion = None;
+        self.solution_pool = None;
+        self.benchmark = None;
+        self.baseline = None;
+        self.trace_viewer = TraceViewer::default();
+        self.explain_viewer = ExplainViewer::default();
+        self.convergence = Convergence::default();
+        self.run = None;
+        self.playback = Playback { enabled: self.playback.enabled, ..Playback::default() };
+    }
+
+    /// Switch the loaded problem between integer and fractional times. Switching to
+    /// integer times rounds the data, and the schedule keeps its machine sequences.
+    fn render_time_mode_control(&mut self, ui: &mut egui::Ui) {
+        let Some(current) = self.solver.as_ref().map(|solver| solver.time_mode()) else {
+            return;
+        };
+        let mut mode = current;
+        ui.label("Time:");
+        egui::ComboBox::from_id_salt("time_mode")
+            .selected_text(mode.name())
+            .show_ui(ui, |ui| {
+                for option in TimeMode::ALL {
+                    ui.selectable_value(&mut mode, option, option.name());
+                }
+            })
+            .response
+            .on_hover_text("Integer times are shown and exported as whole numbers; switching to them rounds every time in the problem");
+        if mode == current {
+            return;
+        }
+        self.remember("Time Mode");
+        let Some(solver) = &mut self.solver else {
+            return;
+        };
+        solver.set_time_mode(mode);
+        if self.schedule.is_empty() {
+            return;
+        }
+        let sequences = solver.machine_sequences(&self.schedule);
+        let schedule = solver.schedule_from_sequences(&sequences, &HashMap::new()).unwrap_or_default();
+        self.apply_edited_schedule(schedule);
+    }
+
+    /// Choose how the operations of a job are ordered in the loaded problem; the schedule
+    /// is re-timed with its machine orders, or replaced by a greedy one if they conflict
+    /// with the jobs' order
+    fn render_problem_type_control(&mut self, ui: &mut egui::Ui) {
+        let Some(current) = self.solver.as_ref().map(|solver| solver.problem_type()) else {
+            return;
+        };
+        let mut problem_type = current;
+        ui.label("Shop:");
+        egui::ComboBox::from_id_salt("problem_type")
+            .selected_text(problem_type.name())
+            .show_ui(ui, |ui| {
+                for option in ProblemType::ALL {
+                    ui.selectable_value(&mut problem_type, option, option.name())
+                        .on_hover_text(option.description());
+                }
+            })
+            .response
+            .on_hover_text("Open shops leave the order of a job's operations to the scheduler; they need unlimited buffers");
+        if problem_type == current {
+            return;
+        }
+        self.remember("Problem Type");
+        let Some(solver) = &mut self.solver else {
+            return;
+        };
+        if let Err(error) = solver.set_problem_type(problem_type) {
+            self.edit_status = Some(Err(error));
+            return;
+        }
+        if self.schedule.is_empty() {
+            return;
+        }
+        let sequences = solver.machine_sequences(&self.schedule);
+        let schedule = solver.schedule_from_sequences(&sequences, &HashMap::new()).unwrap_or_else(|| solver.solve_greedy());
+        self.apply_edited_schedule(schedule);
+    }
+
+    /// Choose whether jobs of the loaded problem may wait between machines; the schedule
+    /// is re-timed to match, keeping its machine orders where possible
+    fn render_buffering_control(&mut self, ui: &mut egui::Ui) {
+        let Some(current) = self.solver.as_ref().map(|solver| solver.buffering) else {
+            return;
+        };
+        let mut buffering = current;
+        ui.label("Buffers:");
+        egui::ComboBox::from_id_salt("buffering")
+            .selected_text(buffering.name())
+            .show_ui(ui, |ui| {
+                for option in Buffering::ALL {
+                    ui.selectable_value(&mut buffering, option, option.name())
+                        .on_hover_text(option.description());
+                }
+            })
+            .response
+            .on_hover_text("No-wait and blocking job shops have no buffers between machines, as in chemical or steel plants");
+        if buffering == current {
+            return;
+        }
+        self.remember("Buffering");
+        let Some(solver) = &mut self.solver else {
+            return;
+        };
+        solver.buffering = buffering;
+        if self.schedule.is_empty() {
+            return;
+        }
+        let schedule = solver.enforce_buffering(self.schedule.clone());
+        self.apply_edited_schedule(schedule);
+    }
+
+    /// Choose whether downtime interrupts operations instead of delaying them; the
+    /// schedule is re-timed with its machine orders
+    fn render_preemption_control(&mut self, ui: &mut egui::Ui) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let mut preemptive = solver.preemptive;
+        let changed = ui.add_enabled(solver.buffering == Buffering::Unlimited, egui::Checkbox::new(&mut preemptive, "Preemptive"))
+            .on_hover_text("Operations pause during machine downtime and resume after it")
+            .on_disabled_hover_text("Only jobs that can wait between machines can be interrupted")
+            .changed();
+        if !changed {
+            return;
+        }
+        self.remember("Preemption");
+        let Some(solver) = &mut self.solver else {
+            return;
+        };
+        solver.preemptive = preemptive;
+        if self.schedule.is_empty() {
+            return;
+        }
+        let sequences = solver.machine_sequences(&self.schedule);
+        let schedule = solver.schedule_from_sequences(&sequences, &HashMap::new()).unwrap_or_default();
+        self.apply_edited_schedule(schedule);
+    }
+
+    /// Attach a calendar mapping time units to dates
+    fn render_calendar_controls(&mut self, ui: &mut egui::Ui) {
+        let Some(current) = self.solver.as_ref().map(|solver| solver.calendar().cloned()) else {
+            return;
+        };
+        let mut calendar = current.clone();
+        let text = &mut self.calendar_text;
+        let mut attached = calendar.is_some();
+        if ui.checkbox(&mut attached, "Attach Calendar").changed() {
+            calendar = attached.then(|| Calendar { start: text.start().unwrap_or(Calendar::default().start), ..Calendar::default() });
+        }
+
+        if let Some(calendar) = &mut calendar {
+            ui.separator();
+            ui.label("Start:");
+            if ui.add(egui::TextEdit::singleline(&mut text.start).desired_width(140.0)).changed() {
+                if let Some(start) = text.start() {
+                    calendar.start = start;
+                }
+            }
+
+            ui.separator();
+            ui.label("Minutes per Unit:");
+            ui.add(egui::DragValue::new(&mut calendar.minutes_per_unit).range(1.0..=1440.0));
+
+            ui.separator();
+            ui.label("Shifts per Day:");
+            ui.add(egui::DragValue::new(&mut calendar.shifts_per_day).range(1..=4));
+
+            ui.separator();
+            ui.checkbox(&mut self.date_axis, "Date Axis")
+                .on_hover_text("Label the Gantt chart's time axis with months, days and shifts instead of time units");
+            let mut working = calendar.working_hours.is_some();
+            if ui.checkbox(&mut working, "Working Hours")
+                .on_hover_text("Machines only work during shifts: operations wait over breaks, nights and weekends, or pause over them when preemptive")
+                .changed()
+            {
+                calendar.working_hours = working.then(|| text.working_hours());
+            }
+        }
+        self.apply_calendar(current, calendar);
+    }
+
+    /// Edit the shifts, breaks and working days of an attached calendar
+    fn render_working_hours_controls(&mut self, ui: &mut egui::Ui) {
+        let Some(current) = self.solver.as_ref().and_then(|solver| solver.calendar().cloned()) else {
+            return;
+        };
+        let Some(mut hours) = current.working_hours.clone() else {
+            return;
+        };
+        let text = &mut self.calendar_text;
+        ui.horizontal(|ui| {
+            ui.label("Shifts:");
+            let shifts = ui.add(egui::TextEdit::singleline(&mut text.shifts).desired_width(180.0))
+                .on_hover_text("Shifts as start-end times separated by commas, e.g. 06:00-14:00, 14:00-22:00; a shift may run past midnight");
+            if shifts.changed() {
+                if let Ok(spans) = WorkingHours::parse_spans(&text.shifts).map_err(|e| e.to_string()).and_then(|spans| {
+                    if spans.is_empty() { Err("No shifts".to_string()) } else { Ok(spans) }
+                }) {
+                    hours.shifts = spans;
+                }
+            }
+            ui.label("Breaks:");
+            let breaks = ui.add(egui::TextEdit::singleline(&mut text.breaks).desired_width(180.0))
+                .on_hover_text("Breaks taken every day, e.g. 10:00-10:30, 18:00-18:30");
+            if breaks.changed() {
+                if let Ok(spans) = WorkingHours::parse_spans(&text.breaks) {
+                    hours.breaks = spans;
+                }
+            }
+            ui.separator();
+            for (day, name) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].into_iter().enumerate() {
+                ui.toggle_value(&mut hours.working_days[day], name);
+            }
+            // An operation that fits into no stretch of working time waits until the end of the calendar
+            let too_long = self.solver.as_ref().is_some_and(|solver| {
+                let shortest = |op: &Operation| op.options().map(|option| option.duration).fold(f64::INFINITY, f64::min);
+                !solver.preempts()
+                    && solver.jobs().iter().flat_map(|job| &job.operations).any(|op| shortest(op) > current.longest_working_stretch())
+            });
+            if too_long {
+                ui.colored_label(egui::Color32::from_rgb(220, 140, 0), "⚠ Some operations only fit between breaks when preemptive");
+            }
+        });
+        let calendar = Calendar { working_hours: Some(hours), ..current.clone() };
+        self.apply_calendar(Some(current), Some(calendar));
+    }
+
+    /// Attach the edited calendar; when that changes the time off shift, the schedule
+    /// is re-timed with its machine orders
+    fn apply_calendar(&mut self, current: Option<Calendar>, calendar: Option<Calendar>) {
+        if calendar == current {
+            return;
+        }
+        if let Some(Err(e)) = calendar.as_ref().map(Calendar::check) {
+            self.toasts.error(&JsspError::Infeasible(e));
+            return;
+        }
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let mut edited = solver.instance().clone();
+        edited.set_calendar(calendar).expect("the calendar was checked");
+        let retime = edited.unavailable() != solver.unavailable();
+        if retime {
+            self.remember("Working Hours");
+        }
+        let Some(solver) = &mut self.solver else {
+            return;
+        };
+        **solver = edited;
+        if !retime || self.schedule.is_empty() {
+            return;
+        }
+        let sequences = solver.machine_sequences(&self.schedule);
+        let schedule = solver.schedule_from_sequences(&sequences, &HashMap::new()).unwrap_or_default();
+        self.apply_edited_schedule(schedule);
+    }
+
+    /// Grid spacer and header rows for a calendar-based time axis (months → days → shifts)
+    fn calendar_time_axes(calendar: &Calendar) -> (impl Fn(egui_plot::GridInput) -> Vec<GridMark>, Vec<AxisHints<'static>>) {
+        let spacer_calendar = calendar.clone();
+        let spacer = move |input: egui_plot::GridInput| {
+            let (from, to) = input.bounds;
+            let mut marks = Vec::new();
+            for scale in [TimeScale::Month, TimeScale::Day, TimeScale::Shift] {
+                let step_size = spacer_calendar.step_size(scale);
+                // Skip scales whose ticks would be packed too densely at this zoom level
+                if step_size < input.base_step_size {
+                    continue;
+                }
+                marks.extend(
+                    spacer_calendar.ticks(scale, from, to)
+                        .into_iter()
+                        .map(|value| GridMark { value, step_size })
+                );
+            }
+            marks
+        };
+
+        let axes = [TimeScale::Month, TimeScale::Day, TimeScale::Shift]
+            .into_iter()
+            .map(|scale| {
+                let axis_calendar = calendar.clone();
+                AxisHints::new_x()
+                    .placement(VPlacement::Top)
+                    .formatter(move |mark, _range| {
+                        if axis_calendar.scale_of(mark.step_size) == Some(scale) {
+                            axis_calendar.label(scale, mark.value)
                         } else {
-                            self.hidden_jobs.insert(job_id);
+                            String::new()
                         }
+                    })
+            })
+            .collect();
+
+        (spacer, axes)
+    }
+
+    /// Grid spacer with ticks on whole time units only, for instances in integer mode
+    fn integer_grid_spacer() -> impl Fn(egui_plot::GridInput) -> Vec<GridMark> {
+        let spacer = egui_plot::log_grid_spacer(10);
+        move |input| {
+            spacer(input).into_iter()
+                .filter(|mark| (mark.value - mark.value.round()).abs() < 1e-9)
+                .map(|mark| GridMark { value: mark.value.round(), step_size: mark.step_size.max(1.0) })
+                .collect()
+        }
+    }
+
+    /// Draw the legend, Gantt chart and schedule details table
+    /// A thin arrow for every transfer of a job between machines, from the end of an
+    /// operation to when the job arrives at the next one
+    fn draw_transfer_arrows(&self, plot_ui: &mut egui_plot::PlotUi) {
+        let Some(solver) = self.solver.as_ref().filter(|solver| solver.transfer_times().is_some()) else {
+            return;
+        };
+        let visible = self.visibility();
+        let by_key: HashMap<(usize, usize), &ScheduledOperation> = self.schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op))
+            .collect();
+        // The operation each job goes on to, in the order the schedule processes them
+        let mut job_next: HashMap<(usize, usize), usize> = HashMap::new();
+        for (job_id, order) in solver.job_sequences(&self.schedule).into_iter().enumerate() {
+            job_next.extend(order.windows(2).map(|pair| ((job_id, pair[0]), pair[1])));
+        }
+        let (mut origins, mut tips) = (Vec::new(), Vec::new());
+        for op in self.schedule.iter().filter(|op| visible(op) && !self.hidden_by_playback(op.job_id)) {
+            let next = job_next.get(&(op.job_id, op.operation_id)).and_then(|&next| by_key.get(&(op.job_id, next)));
+            let Some(next) = next.filter(|next| visible(next)) else {
+                continue;
+            };
+            let transfer = solver.transfer_time(Some(op.machine_id), next.machine_id);
+            if transfer > 0.0 {
+                origins.push([op.end_time, self.gantt_row(op)]);
+                tips.push([op.end_time + transfer, self.gantt_row(next)]);
+            }
+        }
+        if !origins.is_empty() {
+            plot_ui.arrows(
+                egui_plot::Arrows::new(origins, tips)
+                    .color(egui::Color32::from_gray(120))
+                    .tip_length(6.0)
+                    .name("Transfer")
+                    .allow_hover(false)
+            );
+        }
+    }
+
+    pub fn render_gantt_chart(&mut self, ui: &mut egui::Ui) {
+        let time = self.time_mode();
+        let job_colors = self.job_colors();
+        let colors = self.block_colors();
+        let palette = self.palette;
+        let machine_color = move |machine_id: usize| {
+            let [r, g, b] = palette.color(machine_id);
+            egui::Color32::from_rgb(r, g, b)
+        };
+
+        // Create custom legend with colored circles and clickable job names, wrapping
+        // and scrolling when there are many jobs rather than widening the chart
+        let mut recolor = None;
+        egui::ScrollArea::vertical().id_salt("job_legend").max_height(60.0).show(ui, |ui| ui.horizontal_wrapped(|ui| {
+            ui.label("Jobs:");
+            let unique_jobs: HashSet<usize> = self.schedule.iter().map(|op| op.job_id).collect();
+            let mut sorted_jobs: Vec<usize> = unique_jobs.into_iter().collect();
+            sorted_jobs.sort();
+            
+            for job_id in sorted_jobs {
+                let color = colors.job(job_id);
+                let is_hidden = self.filter.hidden_jobs.contains(&job_id);
+                let name = self.job_name(job_id);
+
+                // Draw colored circle
+                let (rect, response) = ui.allocate_exact_size(
+                    egui::vec2(12.0, 12.0),
+                    egui::Sense::click()
+                );
+                
+                if is_hidden {
+                    ui.painter().circle_stroke(
+                        rect.center(),
+                        6.0,
+                        egui::Stroke::new(2.0, color)
+                    );
+                } else {
+                    ui.painter().circle_filled(
+                        rect.center(),
+                        6.0,
+                        color
+                    );
+                }
+                
+                // Job label
+                let label_response = ui.selectable_label(false, &name);
+                
+                // Toggle visibility on click
+                if response.clicked() || label_response.clicked() {
+                    if is_hidden {
+                        self.filter.hidden_jobs.remove(&job_id);
+                    } else {
+                        self.filter.hidden_jobs.insert(job_id);
                     }
-                    
-                    // Show tooltip
-                    if response.hovered() || label_response.hovered() {
-                        response.on_hover_text(format!("Click to {} Job {}", if is_hidden { "show" } else { "hide" }, job_id));
+                }
+                
+                // Pick a color of the job's own, saved with the instance
+                label_response.context_menu(|ui| {
+                    let own = job_colors.get(job_id).copied().unwrap_or(color);
+                    let mut rgb = [own.r(), own.g(), own.b()];
+                    ui.horizontal(|ui| {
+                        ui.label("Color:");
+                        if egui::color_picker::color_edit_button_srgb(ui, &mut rgb).changed() {
+                            recolor = Some((job_id, Some(rgb)));
+                        }
+                    });
+                    if ui.button("Use Palette Color").clicked() {
+                        recolor = Some((job_id, None));
+                        ui.close_menu();
                     }
                 });
+
+                // Show tooltip
+                if response.hovered() || label_response.hovered() {
+                    response.on_hover_text(format!(
+                        "Click to {} {}; right-click the name to change its color",
+                        if is_hidden { "show" } else { "hide" }, name
+                    ));
+                }
             }
+        }));
+        if let (Some((job_id, rgb)), Some(solver)) = (recolor, &mut self.solver) {
+            match rgb {
+                Some(rgb) => solver.job_colors.insert(job_id, rgb),
+                None => solver.job_colors.remove(&job_id),
+            };
+        }
+        if self.color_by == ColorBy::Machine {
+            ui.horizontal(|ui| {
+                ui.label("Machines:");
+                for machine_id in 0..self.instance_machines() {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                    ui.painter().circle_filled(rect.center(), 6.0, machine_color(machine_id));
+                    ui.label(self.machine_name(machine_id));
+                }
+            });
+        }
+
+        self.render_color_legend(ui);
+
+        ui.horizontal(|ui| {
+            ui.label("Rows:");
+            if ui.selectable_value(&mut self.gantt_rows, GanttRows::Machines, "Machines")
+                .on_hover_text("One row per machine, colored by job")
+                .clicked()
+            {
+                self.color_by = ColorBy::Job;
+            }
+            if ui.selectable_value(&mut self.gantt_rows, GanttRows::Jobs, "Jobs")
+                .on_hover_text("One row per job, colored by machine")
+                .clicked()
+            {
+                self.color_by = ColorBy::Machine;
+            }
+            ui.separator();
+            egui::ComboBox::from_label("Color by")
+                .selected_text(self.color_by.name())
+                .show_ui(ui, |ui| {
+                    for color_by in ColorBy::ALL {
+                        ui.selectable_value(&mut self.color_by, color_by, color_by.name())
+                            .on_hover_text(color_by.description());
+                    }
+                });
+            ui.separator();
+            ui.checkbox(&mut self.drag_editing, "Drag to Reschedule")
+                .on_hover_text("Drag an operation to another time or eligible machine; the rest of the schedule is repaired");
+            ui.checkbox(&mut self.show_critical_path, "Show Critical Path")
+                .on_hover_text("Outline operations with zero slack; delaying any of them delays the makespan");
+            ui.add_enabled_ui(self.gantt_rows == GanttRows::Machines, |ui| {
+                ui.checkbox(&mut self.show_bottleneck, "Highlight Bottleneck")
+                    .on_hover_text("Shade the row of the most loaded machine (see Bottlenecks under Analytics)");
+            });
+            ui.checkbox(&mut self.playback.enabled, "Playback")
+                .on_hover_text("Sweep a time cursor through the schedule, showing what each machine is doing and waiting for");
+            ui.separator();
+            self.render_gantt_view_controls(ui);
+            self.render_drag_status(ui);
         });
+        self.render_filter_controls(ui);
+        self.render_playback_controls(ui);
 
         ui.add_space(5.0);
 
-        let plot_response = Plot::new("gantt_chart")
+        let color_of = |op: &ScheduledOperation| colors.operation(op);
+
+        // Operations involved in a feasibility violation get a red outline
+        let offending: HashSet<(usize, usize)> = self.violations.iter()
+            .flat_map(|v| v.operations())
+            .collect();
+
+        // Slack is only meaningful for a feasible schedule
+        let slack = match &self.solver {
+            Some(solver) if self.violations.is_empty() => solver.operation_slack(&self.schedule),
+            _ => HashMap::new(),
+        };
+        let critical: HashSet<(usize, usize)> = if self.show_critical_path {
+            slack.iter()
+                .filter(|(_, slack)| **slack < 1e-6)
+                .map(|(key, _)| *key)
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        // Jobs weighing more than the average get a bold border
+        let high_priority = self.solver.as_ref().map(|solver| solver.high_priority_jobs()).unwrap_or_default();
+        // Pinned operations get a dark border and a pin before their label
+        let pinned: HashSet<(usize, usize)> = self.solver.as_ref()
+            .filter(|solver| solver.uses_pins())
+            .map(|solver| solver.pins().iter().map(|pin| (pin.job_id, pin.operation_id)).collect())
+            .unwrap_or_default();
+
+        let playback_time = self.playback_time();
+        let row_label = match self.gantt_rows {
+            GanttRows::Machines => "Machine",
+            GanttRows::Jobs => "Job",
+        };
+
+        let decimals = self.time_mode().decimals();
+        let mut plot = Plot::new("gantt_chart")
             .height(400.0)
             .show_axes([true, true])
             .show_grid([true, true])  // Show grid for better readability
-            .y_axis_label("Machine")
+            .y_axis_label(row_label)
             .x_axis_label("Time (units)")
-            .label_formatter(|name, value| {
+            .label_formatter(move |name, value| {
                 if !name.is_empty() {
-                    format!("{}", name)
+                    name.to_string()
                 } else {
-                    format!("Time: {:.1}\nMachine: {:.0}", value.x, value.y)
+                    format!("Time: {:.*}\n{}: {:.0}", decimals, value.x, row_label, value.y)
                 }
             })
-            .allow_drag(true)  // Enable panning
+            .allow_drag(self.gantt_allows_panning())  // Pan unless an operation is being dragged
             .allow_zoom(true)  // Enable zoom
-            .allow_scroll(true)  // Enable scroll wheel zoom
+            .allow_scroll(true);  // Enable scroll wheel zoom
+
+        // Named rows are labelled with their names instead of numbers; secondary
+        // resources get rows of their own below the machines
+        let resource_names: Vec<String> = self.resource_rows().into_iter().map(|(name, _)| name).collect();
+        let row_names: Vec<String> = match (&self.solver, self.gantt_rows) {
+            (Some(solver), GanttRows::Machines) if !solver.machine_names().is_empty() || !resource_names.is_empty() => {
+                (0..solver.num_machines()).map(|machine_id| solver.machine_name(machine_id))
+                    .chain(resource_names.iter().cloned())
+                    .collect()
+            }
+            (Some(solver), GanttRows::Jobs) if !solver.job_names().is_empty() => {
+                (0..solver.jobs().len()).map(|job_id| solver.job_name(job_id)).collect()
+            }
+            _ => Vec::new(),
+        };
+        if !row_names.is_empty() {
+            plot = plot.y_axis_formatter(move |mark, _range| {
+                let row = mark.value.round();
+                if (mark.value - row).abs() > 1e-6 || row < 0.0 {
+                    return String::new();
+                }
+                row_names.get(row as usize).cloned().unwrap_or_default()
+            });
+        }
+
+        // With a calendar attached, the numeric time axis may be replaced by a date header;
+        // integer instances get ticks on whole units only
+        if let Some(calendar) = self.solver.as_ref().and_then(|s| s.calendar()).filter(|_| self.date_axis) {
+            let (spacer, axes) = Self::calendar_time_axes(calendar);
+            plot = plot.x_grid_spacer(spacer).custom_x_axes(axes);
+        } else if self.time_mode() == TimeMode::Integer {
+            plot = plot.x_grid_spacer(Self::integer_grid_spacer());
+        }
+
+        // A block picked in the table flashes for a moment
+        let flash = self.selection.flash_on();
+        if flash.is_some() {
+            ui.ctx().request_repaint();
+        }
+        let selection_color = ui.visuals().strong_text_color();
+
+        let navigation = std::mem::take(&mut self.gantt_navigation);
+        let extent = self.gantt_extent();
+        let mut cursor = None;
+        let mut drawn = Vec::new();
+        // Operations drawn simplified, when there are too many for full detail
+        let mut simplified = None;
+        let plot_response = plot
             .show(ui, |plot_ui| {
-                // Group operations by machine
-                for machine_id in 0..self.num_machines {
-                    let machine_ops: Vec<&ScheduledOperation> = self.schedule.iter()
-                        .filter(|op| op.machine_id == machine_id)
-                        .collect();
-
-                    for op in machine_ops {
-                        // Skip hidden jobs
-                        if self.hidden_jobs.contains(&op.job_id) {
-                            continue;
-                        }
+                navigation.apply(plot_ui, extent);
 
-                        let color = colors[op.job_id % colors.len()];
-                        
-                        let y_pos = machine_id as f64;
-                        let height = 0.8;
-                        
-                        // Draw operation as a rectangle
-                        let points = vec![
-                            [op.start_time, y_pos - height/2.0],
-                            [op.end_time, y_pos - height/2.0],
-                            [op.end_time, y_pos + height/2.0],
-                            [op.start_time, y_pos + height/2.0],
-                        ];
-                        
+                // The most loaded machine as an orange band behind its row
+                if let (Some(solver), GanttRows::Machines, true) = (&self.solver, self.gantt_rows, self.show_bottleneck) {
+                    if let Some(bottleneck) = solver.bottleneck_ranking(&self.schedule).first() {
+                        let (y, end) = (bottleneck.machine_id as f64, solver.calculate_makespan(&self.schedule));
+                        let band_color = egui::Color32::from_rgb(255, 165, 0);
                         plot_ui.polygon(
-                            egui_plot::Polygon::new(points)
-                                .fill_color(color)
-                                .name(format!(
-                                    "Job {} | Op {} | Machine {} | {:.1}->{:.1} ({:.1})",
-                                    op.job_id,
-                                    op.operation_id,
-                                    op.machine_id,
-                                    op.start_time,
-                                    op.end_time,
-                                    op.duration
-                                ))
+                            egui_plot::Polygon::new(vec![[0.0, y - 0.5], [end, y - 0.5], [end, y + 0.5], [0.0, y + 0.5]])
+                                .fill_color(band_color.gamma_multiply(0.2))
+                                .stroke(egui::Stroke::new(1.5, band_color))
+                                .name(format!("Bottleneck | {} | load {}", solver.machine_name(bottleneck.machine_id), time.format(bottleneck.load)))
                         );
+                    }
+                }
 
-                        // Add text label - only show if block is wide enough
-                        let block_width = op.end_time - op.start_time;
-                        if block_width > 2.0 {  // Only show text if block is wide enough
-                            let text_content = if block_width > 8.0 {
-                                format!("Job {}", op.job_id)
-                            } else {
-                                format!("J{}", op.job_id)
-                            };
-                            
-                            plot_ui.text(
-                                egui_plot::Text::new(
-                                    egui_plot::PlotPoint::new(
-                                        (op.start_time + op.end_time) / 2.0,
-                                        y_pos
-                                    ),
-                                    text_content
-                                )
-                                .color(egui::Color32::WHITE)
-                                .name("")  // Empty name so text doesn't create duplicate tooltip
+                // Time off shift as light bands across every row; when zoomed out too far
+                // to tell them apart they are left out
+                if let Some(calendar) = self.solver.as_ref().and_then(|solver| solver.calendar()) {
+                    let view = plot_ui.plot_bounds();
+                    let off_shift = calendar.off_shift(view.min()[0].max(0.0), view.max()[0].max(0.0));
+                    if off_shift.len() <= OFF_SHIFT_LIMIT {
+                        let (bottom, top) = (extent.min()[1], extent.max()[1]);
+                        let band_color = egui::Color32::from_gray(150);
+                        for (start, end) in off_shift {
+                            plot_ui.polygon(
+                                egui_plot::Polygon::new(vec![[start, bottom], [end, bottom], [end, top], [start, top]])
+                                    .fill_color(band_color.gamma_multiply(0.15))
+                                    .stroke(egui::Stroke::NONE)
+                                    .name(format!(
+                                        "Off shift | {} -> {}",
+                                        calendar.to_datetime(start).format("%a %H:%M"), calendar.to_datetime(end).format("%a %H:%M")
+                                    ))
                             );
                         }
                     }
                 }
+
+                // Machine downtime as gray bands behind the machine rows
+                if let (Some(solver), GanttRows::Machines) = (&self.solver, self.gantt_rows) {
+                    let band_color = egui::Color32::from_gray(110);
+                    for window in solver.downtime() {
+                        let y = window.machine_id as f64;
+                        plot_ui.polygon(
+                            egui_plot::Polygon::new(vec![
+                                [window.start, y - 0.5],
+                                [window.end, y - 0.5],
+                                [window.end, y + 0.5],
+                                [window.start, y + 0.5],
+                            ])
+                            .fill_color(band_color.gamma_multiply(0.35))
+                            .stroke(egui::Stroke::new(1.0, band_color))
+                            .name(format!(
+                                "Downtime | {} | {}->{}",
+                                solver.machine_name(window.machine_id), time.format(window.start), time.format(window.end)
+                            ))
+                        );
+                    }
+                }
+
+                // Blocks go into one batched item; labels are drawn over them afterwards
+                let mut blocks = GanttBlocks::default();
+                let mut labels = Vec::new();
+                let visible = self.visibility();
+
+                // Large schedules get full detail only where zoomed in far enough, and
+                // plain bars elsewhere, to keep the frame rate up
+                let mut bars = SimplifiedBars::default();
+                let view = plot_ui.plot_bounds();
+                let in_view = |op: &ScheduledOperation| {
+                    let row = self.gantt_row(op);
+                    row + 0.5 >= view.min()[1] && row - 0.5 <= view.max()[1]
+                        && op.end_time >= view.min()[0] && op.setup_start() <= view.max()[0]
+                };
+                let simplify = self.schedule.len() > DETAIL_LIMIT;
+                let detail_in_view = simplify && self.schedule.iter().filter(|op| visible(op) && in_view(op)).count() <= DETAIL_LIMIT;
+
+                for (position, op) in self.schedule.iter().enumerate() {
+                    // Skip filtered operations, and during playback jobs not yet released if asked to
+                    if !visible(op) || self.hidden_by_playback(op.job_id) {
+                        continue;
+                    }
+
+                    let color = color_of(op);
+                    
+                    let y_pos = self.gantt_row(op);
+                    drawn.push((y_pos as usize, op.start_time, op.end_time, position));
+                    let selected = self.selection.operation == Some((op.job_id, op.operation_id));
+                    if simplify && !selected && !(detail_in_view && in_view(op)) {
+                        bars.push(y_pos as usize, op.job_id, op.setup_start(), op.end_time, color);
+                        continue;
+                    }
+                    let bottom = y_pos - BLOCK_HEIGHT/2.0;
+                    let top = y_pos + BLOCK_HEIGHT/2.0;
+                    
+                    // Draw the setup preceding the operation as a hatched segment
+                    if op.setup_time > 0.0 {
+                        let setup_start = op.setup_start();
+                        let setup_color = egui::Color32::from_gray(140);
+
+                        blocks.push(Block {
+                            min: [setup_start, bottom],
+                            max: [op.start_time, top],
+                            fill: setup_color.gamma_multiply(0.15),
+                            stroke: egui::Stroke::new(1.0, setup_color),
+                            label: format!(
+                                "Setup for {} | {} | {}->{} ({})",
+                                self.job_name(op.job_id), self.machine_name(op.machine_id), time.format(setup_start), time.format(op.start_time), time.format(op.setup_time)
+                            ),
+                        });
+
+                        // Zig-zag hatching across the setup interval
+                        let teeth = ((op.setup_time / 0.5).ceil() as usize).clamp(2, 12);
+                        let hatch: Vec<[f64; 2]> = (0..=teeth)
+                            .map(|i| {
+                                let x = setup_start + op.setup_time * i as f64 / teeth as f64;
+                                [x, if i % 2 == 0 { bottom } else { top }]
+                            })
+                            .collect();
+                        for pair in hatch.windows(2) {
+                            blocks.push_segment(pair[0], pair[1], egui::Stroke::new(1.0, setup_color));
+                        }
+                    }
+
+                    // During playback, operations not yet started are faded and running ones outlined
+                    let state = playback_time.map(|time| Self::operation_state(op, time));
+                    let stroke = if offending.contains(&(op.job_id, op.operation_id)) {
+                        egui::Stroke::new(3.0, egui::Color32::RED)
+                    } else if state == Some(OperationState::Running) {
+                        egui::Stroke::new(3.0, egui::Color32::YELLOW)
+                    } else if critical.contains(&(op.job_id, op.operation_id)) {
+                        egui::Stroke::new(2.5, egui::Color32::from_rgb(255, 40, 40))
+                    } else if pinned.contains(&(op.job_id, op.operation_id)) {
+                        egui::Stroke::new(2.5, egui::Color32::from_rgb(40, 40, 40))
+                    } else if high_priority.contains(&op.job_id) {
+                        egui::Stroke::new(3.0, egui::Color32::BLACK)
+                    } else if self.palette.bold_outlines() {
+                        egui::Stroke::new(2.0, egui::Color32::BLACK)
+                    } else {
+                        egui::Stroke::new(1.0, color)
+                    };
+                    let mut fill = if state == Some(OperationState::Pending) { color.gamma_multiply(0.25) } else { color };
+                    // The selection is outlined over every other state so it stays findable
+                    let stroke = match self.selection_style((op.job_id, op.operation_id), flash, selection_color) {
+                        Some((selected, flash_fill)) => {
+                            fill = flash_fill.unwrap_or(fill);
+                            selected
+                        }
+                        None => stroke,
+                    };
+
+                    // A preempted operation is a block per segment, joined by a thin line
+                    // across the downtime in between
+                    let mut widest = (op.start_time, op.end_time);
+                    let mut previous_end: Option<f64> = None;
+                    for (from, to) in op.intervals() {
+                        if let Some(end) = previous_end {
+                            blocks.push_segment([end, y_pos], [from, y_pos], egui::Stroke::new(1.5, color));
+                        }
+                        blocks.push(Block {
+                            min: [from, bottom],
+                            max: [to, top],
+                            fill,
+                            stroke,
+                            label: format!(
+                                "{} | Op {} | {} | {}->{} ({})",
+                                self.job_name(op.job_id),
+                                op.operation_id,
+                                self.machine_name(op.machine_id),
+                                time.format(from),
+                                time.format(to),
+                                time.format(op.duration)
+                            ),
+                        });
+                        if previous_end.is_none() || to - from > widest.1 - widest.0 {
+                            widest = (from, to);
+                        }
+                        previous_end = Some(to);
+                    }
+
+                    // Add text label to the widest segment - only show if it is wide enough
+                    let block_width = widest.1 - widest.0;
+                    if block_width > 2.0 {  // Only show text if block is wide enough
+                        let text_content = match (self.gantt_rows, block_width > 8.0) {
+                            (GanttRows::Machines, true) => self.job_name(op.job_id),
+                            (GanttRows::Machines, false) => format!("J{}", op.job_id),
+                            (GanttRows::Jobs, true) => self.machine_name(op.machine_id),
+                            (GanttRows::Jobs, false) => format!("M{}", op.machine_id),
+                        };
+                        let text_content = if pinned.contains(&(op.job_id, op.operation_id)) { format!("📌 {}", text_content) } else { text_content };
+                        let [r, g, b] = self.palette.label_color([color.r(), color.g(), color.b()]);
+                        labels.push(
+                            egui_plot::Text::new(
+                                egui_plot::PlotPoint::new((widest.0 + widest.1) / 2.0, y_pos),
+                                text_content
+                            )
+                            .color(egui::Color32::from_rgb(r, g, b))
+                            .name("")  // Empty name so text doesn't create duplicate tooltip
+                        );
+                    }
+                }
+                if simplify {
+                    simplified = Some(bars.len());
+                    bars.add_to(&mut blocks, plot_ui.transform());
+                }
+                plot_ui.add(blocks);
+                for label in labels {
+                    plot_ui.text(label);
+                }
+
+                if !simplify {
+                    self.draw_transfer_arrows(plot_ui);
+                }
+
+                // Operations holding a secondary resource, one lane per unit, as thinner
+                // blocks below a line under the last machine
+                let resource_rows = self.resource_rows();
+                if !resource_rows.is_empty() {
+                    let first_row = self.instance_machines();
+                    plot_ui.hline(egui_plot::HLine::new(first_row as f64 - 0.5).color(egui::Color32::GRAY).name(""));
+                    let mut held = GanttBlocks::default();
+                    for (offset, (name, ops)) in resource_rows.iter().enumerate() {
+                        let y = (first_row + offset) as f64;
+                        for op in ops.iter().filter(|op| visible(op)) {
+                            let color = color_of(op);
+                            held.push(Block {
+                                min: [op.start_time, y - BLOCK_HEIGHT / 3.0],
+                                max: [op.end_time, y + BLOCK_HEIGHT / 3.0],
+                                fill: color.gamma_multiply(0.6),
+                                stroke: egui::Stroke::new(1.0, color),
+                                label: format!(
+                                    "{} | {} | Op {} | {}->{}",
+                                    name, self.job_name(op.job_id), op.operation_id, time.format(op.start_time), time.format(op.end_time)
+                                ),
+                            });
+                        }
+                    }
+                    plot_ui.add(held);
+                }
+                self.draw_drag_preview(plot_ui);
+                self.draw_explain_candidates(plot_ui);
+                self.draw_playback_cursor(plot_ui);
+                cursor = self.track_gantt_cursor(plot_ui);
             });
 
+        if let Some(count) = simplified.filter(|&count| count > 0) {
+            ui.weak(format!(
+                "{} operations: {} drawn as plain bars. Zoom in to {} or fewer to see them in full.",
+                self.schedule.len(), count, DETAIL_LIMIT
+            ));
+        }
+        self.gantt_index = GanttIndex::new(drawn);
+        self.handle_gantt_drag(&plot_response);
+        self.handle_gantt_click(&plot_response);
+        self.handle_gantt_context_menu(&plot_response);
+        if cursor.is_some() {
+            self.gantt_cursor = cursor;
+        }
+        if self.show_minimap {
+            let view = *plot_response.transform.bounds();
+            self.render_gantt_minimap(ui, &view, color_of);
+        }
+        self.render_cursor_readout(ui);
+        self.render_machine_queues(ui);
+
+        // The block under the pointer is also highlighted in the table
+        let chart_hover = plot_response.response.hover_pos()
+            .and_then(|pos| self.operation_at(plot_response.transform.value_from_position(pos)))
+            .map(|op| (op.job_id, op.operation_id));
+
         // Show hover details in a separate area
-        if let Some(pointer_pos) = plot_response.response.hover_pos() {
+        if let Some(pointer_pos) = plot_response.response.hover_pos().filter(|_| self.gantt_drag.is_none()) {
             let plot_pos = plot_response.transform.value_from_position(pointer_pos);
             // Find if we're hovering over any operation
-            for op in &self.schedule {
-                if self.hidden_jobs.contains(&op.job_id) {
-                    continue;
-                }
-                
-                let y_pos = op.machine_id as f64;
-                let height = 0.8;
-                
-                // Check if pointer is inside this operation's rectangle
-                if plot_pos.x >= op.start_time && plot_pos.x <= op.end_time
-                    && plot_pos.y >= (y_pos - height/2.0) && plot_pos.y <= (y_pos + height/2.0) {
-                    
-                    plot_response.response.on_hover_ui(|ui| {
-                        ui.set_max_width(250.0);
-                        let color = colors[op.job_id % colors.len()];
-                        ui.horizontal(|ui| {
-                            ui.painter().circle_filled(
-                                ui.cursor().center_top() + egui::vec2(6.0, 6.0),
-                                5.0,
-                                color
-                            );
-                            ui.add_space(15.0);
-                            ui.heading(format!("Job {}", op.job_id));
-                        });
-                        ui.separator();
-                        ui.label(format!("Operation: {}", op.operation_id));
-                        ui.label(format!("Machine: {}", op.machine_id));
-                        ui.label(format!("Start Time: {:.2}", op.start_time));
-                        ui.label(format!("End Time: {:.2}", op.end_time));
-                        ui.label(format!("Duration: {:.2}", op.duration));
+            if let Some(op) = self.operation_at(plot_pos) {
+                plot_response.response.on_hover_ui(|ui| {
+                    ui.set_max_width(250.0);
+                    let color = color_of(op);
+                    ui.horizontal(|ui| {
+                        ui.painter().circle_filled(
+                            ui.cursor().center_top() + egui::vec2(6.0, 6.0),
+                            5.0,
+                            color
+                        );
+                        ui.add_space(15.0);
+                        ui.heading(self.job_name(op.job_id));
                     });
-                    break;
-                }
+                    ui.separator();
+                    ui.label(format!("Operation: {}", op.operation_id));
+                    if let Some(job) = self.solver.as_ref().and_then(|s| s.jobs().iter().find(|j| j.id == op.job_id)) {
+                        if job.release_time > 0.0 {
+                            ui.label(format!("Job Release: {}", time.format(job.release_time)));
+                        }
+                        if let Some(due_date) = job.due_date {
+                            ui.label(format!("Job Due Date: {}", time.format(due_date)));
+                        }
+                    }
+                    ui.label(format!("Machine: {}", self.machine_name(op.machine_id)));
+                    if pinned.contains(&(op.job_id, op.operation_id)) {
+                        ui.label("📌 Pinned");
+                    }
+                    if let Some(solver) = self.solver.as_ref().filter(|solver| solver.uses_resources()) {
+                        if let Some(resource_id) = solver.resource_of(op.job_id, op.operation_id) {
+                            ui.label(format!("Resource: {}", solver.resource_name(resource_id)));
+                        }
+                    }
+                    ui.label(format!("Start Time: {}", time.format(op.start_time)));
+                    ui.label(format!("End Time: {}", time.format(op.end_time)));
+                    ui.label(format!("Duration: {}", time.format(op.duration)));
+                    if !op.segments.is_empty() {
+                        ui.label(format!("Interrupted: {} segments", op.segments.len()));
+                    }
+                    if op.setup_time > 0.0 {
+                        ui.label(format!("Setup: {} (from {})", time.format(op.setup_time), time.format(op.setup_start())));
+                    }
+                    let previous_machine = op.operation_id.checked_sub(1)
+                        .and_then(|previous| self.schedule.iter().find(|other| other.job_id == op.job_id && other.operation_id == previous))
+                        .map(|previous| previous.machine_id);
+                    let transfer = self.solver.as_ref().map_or(0.0, |solver| solver.transfer_time(previous_machine, op.machine_id));
+                    if transfer > 0.0 {
+                        ui.label(format!("Transfer: {} from the previous machine", time.format(transfer)));
+                    }
+                });
             }
         }
 
-        // Job information table
+        // Job information table, with the same operations as the chart
+        let rows: Vec<&ScheduledOperation> = {
+            let visible = self.visibility();
+            self.schedule.iter().filter(|op| visible(op)).collect()
+        };
         ui.separator();
-        ui.heading("Schedule Details");
-        
-        egui::ScrollArea::vertical()
-            .max_height(200.0)
-            .show(ui, |ui| {
-                egui::Grid::new("schedule_grid")
-                    .striped(true)
-                    .spacing([10.0, 4.0])
-                    .show(ui, |ui| {
-                        ui.label("Job");
-                        ui.label("Operation");
-                        ui.label("Machine");
-                        ui.label("Start");
-                        ui.label("End");
-                        ui.label("Duration");
-                        ui.end_row();
-
-                        for op in &self.schedule {
-                            ui.label(format!("{}", op.job_id));
-                            ui.label(format!("{}", op.operation_id));
-                            ui.label(format!("{}", op.machine_id));
-                            ui.label(format!("{:.2}", op.start_time));
-                            ui.label(format!("{:.2}", op.end_time));
-                            ui.label(format!("{:.2}", op.duration));
-                            ui.end_row();
-                        }
-                    });
-            });
+        ui.horizontal(|ui| {
+            ui.heading("Schedule Details");
+            if rows.len() < self.schedule.len() {
+                ui.label(format!("{} of {} operations", rows.len(), self.schedule.len()));
+            }
+            if ui.button("Copy to Clipboard")
+                .on_hover_text("Copy the table as tab-separated text, ready to paste into Excel or Google Sheets")
+                .clicked()
+            {
+                ui.ctx().copy_text(Self::schedule_tsv(&rows, &slack, time.decimals(), self.solver.as_ref()));
+            }
+        });
+
+        // Named jobs and machines are shown by name, the others by index
+        let solver = self.solver.as_ref();
+        let job_cell = |job_id: usize| match solver.filter(|solver| !solver.job_names().is_empty()) {
+            Some(solver) => solver.job_name(job_id),
+            None => job_id.to_string(),
+        };
+        let machine_cell = |machine_id: usize| match solver.filter(|solver| !solver.machine_names().is_empty()) {
+            Some(solver) => solver.machine_name(machine_id),
+            None => machine_id.to_string(),
+        };
+
+        // Only the visible rows are laid out, so long schedules stay cheap to draw
+        let (mut clicked_row, mut hovered_row) = (None, None);
+        ui.scope(|ui| {
+            ui.spacing_mut().item_spacing.y = 4.0;
+            let row_height = ui.text_style_height(&egui::TextStyle::Body);
+            let mut scroll_area = egui::ScrollArea::vertical().max_height(200.0);
+            // A block clicked in the chart brings its row into view, below the header
+            if self.selection.take_scroll() {
+                let selected = self.selection.operation;
+                if let Some(index) = rows.iter().position(|op| Some((op.job_id, op.operation_id)) == selected) {
+                    let row_top = (index + 1) as f32 * (row_height + ui.spacing().item_spacing.y);
+                    scroll_area = scroll_area.vertical_scroll_offset((row_top - 80.0).max(0.0));
+                }
+            }
+            scroll_area
+                .show_rows(ui, row_height, rows.len() + 1, |ui, range| {
+                    egui::Grid::new("schedule_grid")
+                        .striped(true)
+                        .spacing([10.0, 4.0])
+                        .start_row(range.start)
+                        .show(ui, |ui| {
+                            if range.start == 0 {
+                                ui.label("Job");
+                                ui.label("Operation");
+                                ui.label("Machine");
+                                ui.label("Start");
+                                ui.label("End");
+                                ui.label("Duration");
+                                ui.label("Setup");
+                                ui.label("Slack");
+                                ui.end_row();
+                            }
+
+                            // Row 0 is the header
+                            let ops = rows.get(range.start.saturating_sub(1)..range.end.saturating_sub(1)).unwrap_or_default();
+                            for op in ops {
+                                let key = (op.job_id, op.operation_id);
+                                // The selected row and the one under the pointer in the chart are shaded
+                                let background = if self.selection.operation == Some(key) {
+                                    Some(ui.visuals().selection.bg_fill)
+                                } else if chart_hover == Some(key) {
+                                    Some(ui.visuals().selection.bg_fill.gamma_multiply(0.4))
+                                } else {
+                                    None
+                                };
+                                // Reserved behind the cells, and filled once the row is laid out
+                                let background_shape = ui.painter().add(egui::Shape::Noop);
+                                let cell = |ui: &mut egui::Ui, text: egui::RichText| {
+                                    ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+                                };
+                                let slack_text = match slack.get(&key) {
+                                    Some(&value) if value < 1e-6 => egui::RichText::new(format!("{} (critical)", time.format(0.0)))
+                                        .color(egui::Color32::from_rgb(255, 90, 90)),
+                                    Some(&value) => egui::RichText::new(time.format(value)),
+                                    None => egui::RichText::new("-"),
+                                };
+                                let mut row = cell(ui, egui::RichText::new(job_cell(op.job_id)));
+                                for text in [
+                                    op.operation_id.to_string(),
+                                    machine_cell(op.machine_id),
+                                    time.format(op.start_time),
+                                    time.format(op.end_time),
+                                    time.format(op.duration),
+                                    time.format(op.setup_time),
+                                ] {
+                                    row = row.union(cell(ui, egui::RichText::new(text)));
+                                }
+                                row = row.union(cell(ui, slack_text));
+                                ui.end_row();
+                                if let Some(color) = background {
+                                    ui.painter().set(background_shape, egui::Shape::rect_filled(row.rect.expand2(egui::vec2(4.0, 2.0)), 2.0, color));
+                                }
+
+                                if row.clicked() {
+                                    clicked_row = Some(key);
+                                }
+                                if row.hovered() {
+                                    hovered_row = Some(key);
+                                }
+                            }
+                        });
+                });
+        });
+
+        if let Some(key) = clicked_row {
+            self.selection.select_in_table(key);
+        }
+        self.selection.table_hover = hovered_row;
+    }
+
+    /// Value of every objective for the current schedule
+    fn objective_values(&self, solver: &JsspSolver) -> Vec<(Objective, f64)> {
+        Objective::ALL.iter()
+            .map(|&objective| (objective, solver.evaluate(objective, &self.schedule)))
+            .collect()
     }
 
-    fn export_with_dialog(&self, format: &str) {
+    fn export_with_dialog(&mut self, format: &str) {
         use chrono::Local;
-        use rfd::FileDialog;
 
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        
+
         match format {
             "json" => {
-                if let Some(path) = FileDialog::new()
-                    .set_file_name(&format!("jssp_solution_{}.json", timestamp))
-                    .add_filter("JSON", &["json"])
-                    .save_file() 
-                {
-                    self.export_json(&path.to_string_lossy());
+                if let Some(path) = self.save_path(&format!("jssp_solution_{}.json", timestamp), &[("JSON", &["json"])]) {
+                    self.report_export("JSON", self.solution_json().and_then(|text| write_file(&path, text.as_bytes())));
                 }
             }
             "csv" => {
-                if let Some(path) = FileDialog::new()
-                    .set_file_name(&format!("jssp_solution_{}.csv", timestamp))
-                    .add_filter("CSV", &["csv"])
-                    .save_file()
-                {
-                    self.export_csv(&path.to_string_lossy());
+                if let Some(path) = self.save_path(&format!("jssp_solution_{}.csv", timestamp), &[("CSV", &["csv"])]) {
+                    self.report_export("CSV", write_file(&path, self.solution_csv().as_bytes()));
+                }
+            }
+            "xlsx" => {
+                if let Some(path) = self.save_path(&format!("jssp_solution_{}.xlsx", timestamp), &[("Excel Workbook", &["xlsx"])]) {
+                    self.report_export("Excel workbook", self.solution_xlsx().and_then(|bytes| write_file(&path, &bytes)));
+                }
+            }
+            "html" => {
+                if let Some(path) = self.save_path(&format!("jssp_report_{}.html", timestamp), &[("HTML", &["html", "htm"])]) {
+                    self.report_export("HTML report", self.solution_html().and_then(|html| write_file(&path, html.as_bytes())));
                 }
             }
             "txt" => {
-                if let Some(path) = FileDialog::new()
-                    .set_file_name(&format!("jssp_summary_{}.txt", timestamp))
-                    .add_filter("Text", &["txt"])
-                    .save_file()
-                {
-                    self.export_summary(&path.to_string_lossy());
+                if let Some(path) = self.save_path(&format!("jssp_summary_{}.txt", timestamp), &[("Text", &["txt"])]) {
+                    self.report_export("summary", write_file(&path, self.solution_summary().as_bytes()));
+                }
+            }
+            "chart" => {
+                let filters: &[(&str, &[&str])] = &[("PNG Image", &["png"]), ("SVG Image", &["svg"])];
+                if let Some(path) = self.save_path(&format!("jssp_gantt_{}.png", timestamp), filters) {
+                    let is_svg = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+                    self.report_export("chart", self.chart_image(is_svg).and_then(|bytes| write_file(&path, &bytes)));
+                }
+            }
+            "ortools" | "jobshoplib" | "machine-order" => {
+                let Some(solution_format) = SolutionFormat::from_key(format) else {
+                    return;
+                };
+                let extension = solution_format.extension();
+                let file_name = format!("jssp_{}_{}.{}", format.replace('-', "_"), timestamp, extension);
+                if let Some(path) = self.save_path(&file_name, &[(solution_format.name(), &[extension])]) {
+                    self.report_export(solution_format.name(), self.solution_document(solution_format).and_then(|text| write_file(&path, text.as_bytes())));
+                }
+            }
+            "milp" => {
+                let filters: &[(&str, &[&str])] = &[("LP Model", &["lp"]), ("MPS Model", &["mps"])];
+                if let Some(path) = self.save_path(&format!("jssp_model_{}.lp", timestamp), filters) {
+                    let format = path.extension()
+                        .and_then(|ext| ext.to_str())
+                        .and_then(MilpFormat::from_extension)
+                        .unwrap_or(MilpFormat::Lp);
+                    let Some(model) = self.solver.as_ref().map(|solver| write_milp(solver, format)) else {
+                        return;
+                    };
+                    self.report_export("MILP model", write_file(&path, model.as_bytes()));
                 }
             }
             "all" => {
-                if let Some(dir) = FileDialog::new().pick_folder() {
-                    let dir_path = dir.to_string_lossy();
-                    let json_path = format!("{}/jssp_solution_{}.json", dir_path, timestamp);
-                    let csv_path = format!("{}/jssp_solution_{}.csv", dir_path, timestamp);
-                    let txt_path = format!("{}/jssp_summary_{}.txt", dir_path, timestamp);
-                    
-                    self.export_json(&json_path);
-                    self.export_csv(&csv_path);
-                    self.export_summary(&txt_path);
+                if let Some(dir) = self.save_folder() {
+                    let files = [
+                        ("JSON", format!("jssp_solution_{}.json", timestamp), self.solution_json().map(String::into_bytes)),
+                        ("CSV", format!("jssp_solution_{}.csv", timestamp), Ok(self.solution_csv().into_bytes())),
+                        ("summary", format!("jssp_summary_{}.txt", timestamp), Ok(self.solution_summary().into_bytes())),
+                        ("Excel workbook", format!("jssp_solution_{}.xlsx", timestamp), self.solution_xlsx()),
+                        ("HTML report", format!("jssp_report_{}.html", timestamp), self.solution_html().map(String::into_bytes)),
+                        ("utilization chart", format!("jssp_utilization_{}.svg", timestamp), self.utilization_chart().map(String::into_bytes)),
+                    ];
+                    for (what, file_name, content) in files {
+                        self.report_export(what, content.and_then(|bytes| write_file(&dir.join(file_name), &bytes)));
+                    }
                 }
             }
             _ => {}
         }
     }
 
-    fn export_json(&self, path: &str) {
-        use std::fs::File;
-        use std::io::Write;
+    fn solution_json(&self) -> Result<String, JsspError> {
         use chrono::Local;
 
-        match serde_json::to_string_pretty(&serde_json::json!({
+        let json = serde_json::json!({
             "metadata": {
                 "timestamp": Local::now().to_rfc3339(),
-                "num_jobs": self.num_jobs,
-                "num_machines": self.num_machines,
+                "instance": self.solver.as_ref().map(|solver| solver.name.clone()).unwrap_or_default(),
+                "num_jobs": self.instance_jobs(),
+                "num_machines": self.instance_machines(),
+                "buffering": self.solver.as_ref().map(|solver| solver.buffering).unwrap_or_default(),
+                "problem_type": self.solver.as_ref().map(|solver| solver.problem_type()).unwrap_or_default(),
+                "job_names": self.solver.as_ref().map(|solver| solver.job_names()).filter(|names| !names.is_empty()),
+                "machine_names": self.solver.as_ref().map(|solver| solver.machine_names()).filter(|names| !names.is_empty()),
                 "makespan": self.makespan,
-                "algorithm": "Greedy"
+                "objective": self.objective.key(),
+                "objectives": self.solver.as_ref()
+                    .map(|solver| {
+                        self.objective_values(solver).into_iter()
+                            .map(|(objective, value)| (objective.key().to_string(), serde_json::json!(value)))
+                            .collect::<serde_json::Map<_, _>>()
+                    })
+                    .unwrap_or_default(),
+                "total_setup_time": self.schedule.iter().map(|op| op.setup_time).sum::<f64>(),
+                "makespan_p95": self.solver.as_ref()
+                    .filter(|solver| solver.is_stochastic())
+                    .map(|solver| solver.makespan_percentile(&self.schedule, 0.95)),
+                "algorithm": self.run.as_ref().map_or_else(|| self.selected_solver_name(), |run| run.algorithm.clone()),
+                "run": self.run.as_ref().map(RunMetadata::to_json),
+                // Only the operations passing the Gantt filters are in the schedule
+                "filtered": self.filter.export_visible && self.filter.is_active(),
+                "lower_bound": self.solver.as_ref().map(|solver| solver.makespan_lower_bound()),
+                "lower_bounds": self.solver.as_ref().map(|solver| solver.lower_bounds()),
+                "gap": self.solver.as_ref().map(|solver| optimality_gap(self.makespan, solver.makespan_lower_bound())),
+                "best_known": self.solver.as_ref().and_then(|solver| solver.best_known()).map(|known| serde_json::json!({
+                    "makespan": known.makespan,
+                    "optimal": known.optimal,
+                    "gap": known.gap(self.makespan),
+                })),
             },
-            "schedule": self.schedule
-        })) {
-            Ok(json_content) => {
-                if let Ok(mut file) = File::create(path) {
-                    if file.write_all(json_content.as_bytes()).is_ok() {
-                        println!("✓ Exported JSON to {}", path);
+            "convergence": self.convergence.to_json(),
+            "utilization": self.solver.as_ref()
+                .map(|solver| {
+                    let stats = solver.statistics(&self.schedule);
+                    serde_json::json!({
+                        "average_utilization_percent": stats.average_utilization() * 100.0,
+                        "total_idle": stats.total_idle(),
+                        "machines": stats.machines_json(),
+                    })
+                })
+                .unwrap_or_default(),
+            "schedule": self.exported_schedule()
+        });
+        let mut json = json;
+        // With a calendar, every operation also gets its start and end as timestamps
+        if let Some(calendar) = self.solver.as_ref().and_then(|solver| solver.calendar()) {
+            json["metadata"]["calendar_start"] = serde_json::json!(calendar.iso(0.0));
+            for op in json["schedule"].as_array_mut().into_iter().flatten() {
+                for (time, timestamp) in [("start_time", "start_timestamp"), ("end_time", "end_timestamp")] {
+                    if let Some(value) = op[time].as_f64() {
+                        op[timestamp] = serde_json::json!(calendar.iso(value));
                     }
                 }
             }
-            Err(e) => println!("Failed to serialize JSON: {}", e),
         }
+        let json = match self.time_mode() {
+            TimeMode::Integer => whole_numbers(json),
+            TimeMode::Fractional => json,
+        };
+        serde_json::to_string_pretty(&json).map_err(|e| JsspError::Serialization(format!("Failed to serialize JSON: {}", e)))
+    }
+
+    /// The Gantt chart as SVG or PNG
+    fn chart_image(&self, svg: bool) -> Result<Vec<u8>, JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("No problem to draw".to_string()));
+        };
+        let schedule = self.exported_schedule();
+        match (svg, &self.run) {
+            (true, Some(run)) => Ok(svg_with_run(&gantt_svg(solver, &schedule, self.palette), run).into_bytes()),
+            (true, None) => Ok(gantt_svg(solver, &schedule, self.palette).into_bytes()),
+            (false, Some(run)) => gantt_png(solver, &schedule, self.palette).map(|png| png_with_run(&png, run)),
+            (false, None) => gantt_png(solver, &schedule, self.palette),
+        }
+    }
+
+    /// The whole schedule as an Excel workbook; the Gantt filters do not apply
+    fn solution_xlsx(&self) -> Result<Vec<u8>, JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("No problem to export".to_string()));
+        };
+        schedule_xlsx_with_run(solver, &self.schedule, self.palette, self.run.as_ref())
+    }
+
+    /// The HTML report of the operations to export, filtered like the chart
+    fn solution_html(&self) -> Result<String, JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("No problem to export".to_string()));
+        };
+        Ok(schedule_html_with_run(solver, &self.exported_schedule(), self.palette, self.run.as_ref()))
+    }
+
+    fn utilization_chart(&self) -> Result<String, JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("No problem to draw".to_string()));
+        };
+        let svg = utilization_svg(solver, &self.schedule);
+        Ok(match &self.run {
+            Some(run) => svg_with_run(&svg, run),
+            None => svg,
+        })
+    }
+
+    fn solution_document(&self, format: SolutionFormat) -> Result<String, JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("No problem to export".to_string()));
+        };
+        format.write_with_run(solver, &self.schedule, self.run.as_ref())
+    }
+
+    /// Load an OR-Tools document; `source` is the file it came from (just its name on the web)
+    fn import_ortools_text(&mut self, text: &str, source: &Path) -> Result<(), JsspError> {
+        let (mut instance, schedule) = from_ortools_json(text)?;
+        self.toasts.success(format!("Imported OR-Tools instance from {}", source.display()));
+        if instance.name.is_empty() {
+            if let Some(stem) = source.file_stem() {
+                instance.name = stem.to_string_lossy().into_owned();
+            }
+        }
+        instance.metadata.insert("source".to_string(), source.display().to_string());
+        self.remember("Import");
+        self.replace_problem(Some(instance.into()));
+        if let Some(schedule) = schedule {
+            self.apply_edited_schedule(schedule);
+        }
+        Ok(())
     }
 
-    fn export_csv(&self, path: &str) {
-        use std::fs::File;
-        use std::io::Write;
+    /// Show a solution of the loaded problem from another tool; `source` as for `import_ortools_text`
+    fn import_solution_text(&mut self, text: &str, source: &Path) -> Result<(), JsspError> {
+        let Some(solver) = &self.solver else {
+            return Err(JsspError::Infeasible("Load the problem the solution is for first".to_string()));
+        };
+        let schedule = read_solution(solver, text)?;
+        let time = solver.time_mode();
+        self.remember("Import Solution");
+        self.apply_edited_schedule(schedule);
+        self.run = None;
+        let name = source.file_name().map_or_else(|| source.display().to_string(), |name| name.to_string_lossy().into_owned());
+        self.edit_status = Some(if self.violations.is_empty() {
+            Ok(format!("Imported solution from {}: feasible, makespan {}", name, time.format(self.makespan)))
+        } else {
+            Err(format!("Imported solution from {}: makespan {}, {} violation(s)", name, time.format(self.makespan), self.violations.len()))
+        });
+        Ok(())
+    }
+
+    /// Rows of the Schedule Details table as tab-separated text, with a header row and `p` decimals
+    /// and, if `instance` names its jobs or machines, their names in two more columns
+    fn schedule_tsv(ops: &[&ScheduledOperation], slack: &HashMap<(usize, usize), f64>, p: usize, instance: Option<&JsspSolver>) -> String {
+        let names = instance.filter(|instance| !instance.job_names().is_empty() || !instance.machine_names().is_empty());
+        let mut tsv = String::from("Job\tOperation\tMachine\tStart\tEnd\tDuration\tSetup\tSlack");
+        tsv.push_str(if names.is_some() { "\tJob Name\tMachine Name\n" } else { "\n" });
+        for op in ops {
+            let slack = slack.get(&(op.job_id, op.operation_id)).map_or_else(String::new, |value| format!("{:.p$}", value));
+            tsv.push_str(&format!(
+                "{}\t{}\t{}\t{:.p$}\t{:.p$}\t{:.p$}\t{:.p$}\t{}",
+                op.job_id, op.operation_id, op.machine_id,
+                op.start_time, op.end_time, op.duration, op.setup_time, slack
+            ));
+            if let Some(instance) = names {
+                // Tabs and line breaks would split the cell
+                let cell = |name: String| name.replace(['\t', '\n', '\r'], " ");
+                tsv.push_str(&format!("\t{}\t{}", cell(instance.job_name(op.job_id)), cell(instance.machine_name(op.machine_id))));
+            }
+            tsv.push('\n');
+        }
+        tsv
+    }
 
-        if let Ok(mut file) = File::create(path) {
-            let mut csv_content = String::from("Job,Operation,Machine,Start Time,End Time,Duration\n");
-            for op in &self.schedule {
+    fn solution_csv(&self) -> String {
+        let p = self.time_mode().decimals();
+        // Names go last, so readers that split on commas still find the other columns
+        let names = self.solver.as_ref()
+            .filter(|solver| !solver.job_names().is_empty() || !solver.machine_names().is_empty());
+        let calendar = self.solver.as_ref().and_then(|solver| solver.calendar());
+        let mut csv_content = String::from("Job,Operation,Machine,Start Time,End Time,Duration,Setup Time");
+        if calendar.is_some() {
+            csv_content.push_str(",Start Timestamp,End Timestamp");
+        }
+        csv_content.push_str(if names.is_some() { ",Job Name,Machine Name\n" } else { "\n" });
+        for op in &self.exported_schedule() {
+            csv_content.push_str(&format!(
+                "{},{},{},{:.p$},{:.p$},{:.p$},{:.p$}",
+                op.job_id, op.operation_id, op.machine_id, 
+                op.start_time, op.end_time, op.duration, op.setup_time
+            ));
+            if let Some(calendar) = calendar {
+                csv_content.push_str(&format!(",{},{}", calendar.iso(op.start_time), calendar.iso(op.end_time)));
+            }
+            if let Some(solver) = names {
+                csv_content.push_str(&format!(",{},{}", csv_field(&solver.job_name(op.job_id)), csv_field(&solver.machine_name(op.machine_id))));
+            }
+            csv_content.push('\n');
+        }
+        // Second table after a blank line, so spreadsheets show both
+        if let Some(solver) = &self.solver {
+            let stats = solver.statistics(&self.schedule);
+            csv_content.push_str("\nMachine,Busy,Setup,Idle,Busy %,Setup %,Idle %");
+            csv_content.push_str(if names.is_some() { ",Machine Name\n" } else { "\n" });
+            for m in &stats.machines {
                 csv_content.push_str(&format!(
-                    "{},{},{},{:.2},{:.2},{:.2}\n",
-                    op.job_id, op.operation_id, op.machine_id, 
-                    op.start_time, op.end_time, op.duration
+                    "{},{:.p$},{:.p$},{:.p$},{:.1},{:.1},{:.1}",
+                    m.machine_id, m.busy, m.setup, m.idle,
+                    stats.percent_of_makespan(m.busy),
+                    stats.percent_of_makespan(m.setup),
+                    stats.percent_of_makespan(m.idle)
                 ));
+                if names.is_some() {
+                    csv_content.push_str(&format!(",{}", csv_field(&solver.machine_name(m.machine_id))));
+                }
+                csv_content.push('\n');
             }
-            if file.write_all(csv_content.as_bytes()).is_ok() {
-                println!("✓ Exported CSV to {}", path);
+        }
+        if let Some(run) = &self.run {
+            csv_content.push_str("\nRun,Value\n");
+            for (label, value) in run.fields() {
+                csv_content.push_str(&format!("{},{}\n", label, csv_field(&value)));
             }
         }
+        csv_content
     }
 
-    fn export_summary(&self, path: &str) {
-        use std::fs::File;
-        use std::io::Write;
+    fn solution_summary(&self) -> String {
         use chrono::Local;
 
-        if let Ok(mut file) = File::create(path) {
-            let summary = format!(
-                "JSSP Solution Summary\n\
-                =====================\n\
-                Timestamp: {}\n\
-                Algorithm: Greedy\n\
-                Number of Jobs: {}\n\
-                Number of Machines: {}\n\
-                Total Operations: {}\n\
-                Makespan: {:.2}\n\
-                \n\
-                Schedule Details:\n\
-                -----------------\n",
-                Local::now().format("%Y-%m-%d %H:%M:%S"),
-                self.num_jobs,
-                self.num_machines,
-                self.schedule.len(),
-                self.makespan
-            );
-            
-            let mut full_content = summary;
-            full_content.push_str("Job | Op | Machine | Start  | End    | Duration\n");
-            full_content.push_str("----+----+---------+--------+--------+---------\n");
-            
-            for op in &self.schedule {
+        let p = self.time_mode().decimals();
+        let objectives: String = self.solver.as_ref()
+            .map(|solver| {
+                self.objective_values(solver).iter()
+                    .map(|(objective, value)| {
+                        let marker = if *objective == self.objective { " (selected)" } else { "" };
+                        format!("{}: {:.p$}{}\n", objective.name(), value, marker)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // The algorithm is on a line of its own above the other fields of the run
+        let run: String = self.run.iter()
+            .flat_map(|run| run.fields())
+            .filter(|(label, _)| *label != "Algorithm")
+            .map(|(label, value)| format!("{}: {}\n", label, value))
+            .collect();
+        let summary = format!(
+            "JSSP Solution Summary\n\
+            =====================\n\
+            Timestamp: {}\n\
+            Algorithm: {}\n\
+            {}\
+            Number of Jobs: {}\n\
+            Number of Machines: {}\n\
+            Buffering: {}\n\
+            Total Operations: {}\n\
+            Makespan: {:.p$}\n\
+            Total Setup Time: {:.p$}\n\
+            {}\
+            \n\
+            Schedule Details:\n\
+            -----------------\n",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            self.run.as_ref().map_or_else(|| self.selected_solver_name(), |run| run.algorithm.clone()),
+            run,
+            self.instance_jobs(),
+            self.instance_machines(),
+            self.solver.as_ref().map(|solver| solver.buffering).unwrap_or_default().name(),
+            self.schedule.len(),
+            self.makespan,
+            self.schedule.iter().map(|op| op.setup_time).sum::<f64>(),
+            objectives
+        );
+        
+        let mut full_content = summary;
+        // The table keeps to indices; named jobs and machines are listed in front of it
+        if let Some(solver) = &self.solver {
+            for (heading, names) in [("Job Names", solver.job_names()), ("Machine Names", solver.machine_names())] {
+                if names.is_empty() {
+                    continue;
+                }
+                full_content.push_str(&format!("{}:\n", heading));
+                for (id, name) in names.iter().enumerate().filter(|(_, name)| !name.is_empty()) {
+                    full_content.push_str(&format!("{:7} | {}\n", id, name));
+                }
+                full_content.push('\n');
+            }
+        }
+        full_content.push_str("Job | Op | Machine | Start  | End    | Duration | Setup\n");
+        full_content.push_str("----+----+---------+--------+--------+----------+-------\n");
+        
+        for op in &self.schedule {
+            full_content.push_str(&format!(
+                "{:3} | {:2} | {:7} | {:6.p$} | {:6.p$} | {:8.p$} | {:5.p$}\n",
+                op.job_id, op.operation_id, op.machine_id,
+                op.start_time, op.end_time, op.duration, op.setup_time
+            ));
+        }
+
+        if let Some(solver) = &self.solver {
+            let stats = solver.statistics(&self.schedule);
+            full_content.push_str(&format!(
+                "\nMachine Utilization (average {:.1}%, total idle {:.p$}):\n\
+                -----------------------------------------------------\n",
+                stats.average_utilization() * 100.0,
+                stats.total_idle()
+            ));
+            full_content.push_str("Machine |   Busy |  Setup |   Idle | Busy % | Setup % | Idle %\n");
+            full_content.push_str("--------+--------+--------+--------+--------+---------+-------\n");
+            for m in &stats.machines {
                 full_content.push_str(&format!(
-                    "{:3} | {:2} | {:7} | {:6.2} | {:6.2} | {:6.2}\n",
-                    op.job_id, op.operation_id, op.machine_id,
-                    op.start_time, op.end_time, op.duration
+                    "{:7} | {:6.p$} | {:6.p$} | {:6.p$} | {:5.1}% | {:6.1}% | {:5.1}%\n",
+                    m.machine_id, m.busy, m.setup, m.idle,
+                    stats.percent_of_makespan(m.busy),
+                    stats.percent_of_makespan(m.setup),
+                    stats.percent_of_makespan(m.idle)
                 ));
             }
-            
-            if file.write_all(full_content.as_bytes()).is_ok() {
-                println!("✓ Exported summary to {}", path);
-            }
         }
+
+        full_content
+    }
+}
+
+/// What is typed into the calendar fields, kept while it does not parse
+struct CalendarText {
+    start: String,
+    shifts: String,
+    breaks: String,
+}
+
+impl Default for CalendarText {
+    fn default() -> Self {
+        let hours = WorkingHours::default();
+        Self {
+            start: Calendar::default().start.format("%Y-%m-%d %H:%M").to_string(),
+            shifts: WorkingHours::format_spans(&hours.shifts),
+            breaks: WorkingHours::format_spans(&hours.breaks),
+        }
+    }
+}
+
+impl CalendarText {
+    fn start(&self) -> Option<chrono::NaiveDateTime> {
+        chrono::NaiveDateTime::parse_from_str(&self.start, "%Y-%m-%d %H:%M").ok()
+    }
+
+    /// The typed shifts and breaks, where they parse, on the default working days
+    fn working_hours(&self) -> WorkingHours {
+        let defaults = WorkingHours::default();
+        WorkingHours {
+            shifts: WorkingHours::parse_spans(&self.shifts).ok().filter(|spans| !spans.is_empty()).unwrap_or(defaults.shifts),
+            breaks: WorkingHours::parse_spans(&self.breaks).unwrap_or(defaults.breaks),
+            working_days: defaults.working_days,
+        }
+    }
+}
+
+/// `value` with every whole number written without a fraction, as integer-mode
+/// exports expect
+fn whole_numbers(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if float.fract() == 0.0 && float.abs() < i64::MAX as f64 => Value::from(float as i64),
+            _ => Value::Number(number),
+        },
+        Value::Array(items) => Value::Array(items.into_iter().map(whole_numbers).collect()),
+        Value::Object(fields) => Value::Object(fields.into_iter().map(|(key, field)| (key, whole_numbers(field))).collect()),
+        other => other,
     }
 }
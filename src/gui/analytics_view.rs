@@ -0,0 +1,100 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Plot};
+
+use super::JsspApp;
+
+impl JsspApp {
+    /// Utilization, idle and waiting time statistics shown beside the Gantt chart
+    pub(super) fn render_analytics_panel(&self, ui: &mut egui::Ui) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let stats = solver.statistics(&self.schedule);
+        let time = solver.time_mode();
+
+        ui.heading("Analytics");
+        ui.label(format!("Average utilization: {:.1}%", stats.average_utilization() * 100.0));
+        ui.label(format!("Total idle time: {}", time.format(stats.total_idle())));
+        ui.label(format!("Mean flow time: {:.2}", stats.mean_flow_time()));
+        ui.label(format!("Mean waiting time: {:.2}", stats.mean_waiting_time()));
+        ui.separator();
+
+        ui.label("Machine utilization (%)");
+        let bars: Vec<Bar> = stats.machines.iter()
+            .map(|machine| {
+                Bar::new(machine.machine_id as f64, machine.utilization * 100.0)
+                    .name(format!(
+                        "{}: busy {}, setup {}, idle {}",
+                        solver.machine_name(machine.machine_id), time.format(machine.busy), time.format(machine.setup), time.format(machine.idle)
+                    ))
+                    .width(0.6)
+            })
+            .collect();
+        Plot::new("utilization_chart")
+            .height(220.0)
+            .include_y(0.0)
+            .include_y(100.0)
+            .x_axis_label("Machine")
+            .allow_drag(false)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(
+                    BarChart::new(bars)
+                        .color(egui::Color32::from_rgb(70, 130, 180))
+                        .element_formatter(Box::new(|bar, _| format!("{}\n{:.1}%", bar.name, bar.value)))
+                );
+            });
+
+        ui.separator();
+        ui.label("Bottlenecks")
+            .on_hover_text("Machines by processing and setup load; gaps are idle periods between operations");
+        egui::Grid::new("bottleneck_grid")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("#");
+                ui.label("Machine");
+                ui.label("Load");
+                ui.label("% of makespan");
+                ui.label("Gaps");
+                ui.label("Gap time");
+                ui.end_row();
+                for (rank, machine) in solver.bottleneck_ranking(&self.schedule).iter().enumerate() {
+                    ui.label((rank + 1).to_string());
+                    if rank == 0 {
+                        ui.colored_label(egui::Color32::from_rgb(255, 165, 0), machine.machine_id.to_string());
+                    } else {
+                        ui.label(machine.machine_id.to_string());
+                    }
+                    ui.label(time.format(machine.load));
+                    ui.label(format!("{:.1}%", stats.percent_of_makespan(machine.load)));
+                    ui.label(machine.idle_gaps.to_string());
+                    ui.label(time.format(machine.gap_time))
+                        .on_hover_text(format!("Longest gap: {}", time.format(machine.longest_gap)));
+                    ui.end_row();
+                }
+            });
+
+        ui.separator();
+        ui.label("Job flow and waiting times");
+        egui::ScrollArea::vertical()
+            .id_salt("job_statistics")
+            .max_height(250.0)
+            .show(ui, |ui| {
+                egui::Grid::new("job_statistics_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Job");
+                        ui.label("Flow");
+                        ui.label("Waiting");
+                        ui.end_row();
+                        for job in &stats.jobs {
+                            ui.label(job.job_id.to_string());
+                            ui.label(time.format(job.flow_time));
+                            ui.label(time.format(job.waiting_time));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
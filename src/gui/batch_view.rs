@@ -0,0 +1,316 @@
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use super::files::write_file;
+use super::{AppTab, JsspApp};
+use crate::jssp::batch::{default_threads, instance_files, results_csv, spawn_batch, write_solutions, BatchResult};
+use crate::jssp::bounds::optimality_gap;
+use crate::jssp::cache::{InstanceCache, InstanceSet};
+use crate::jssp::solution_formats::SolutionFormat;
+use crate::jssp::JsspSolver;
+
+/// A folder of instances being (or having been) solved in the background
+pub(super) struct BatchState {
+    folder: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    /// The files, loaded once and kept for further runs over the same folder
+    instances: Option<Arc<InstanceSet>>,
+    /// Keep binary copies of the files in the folder's `.jssp-cache`
+    use_cache: bool,
+    threads: usize,
+    solver_name: String,
+    /// Indexed like `files`; `None` until that file's result arrives
+    results: Vec<Option<BatchResult>>,
+    receiver: Option<Receiver<BatchResult>>,
+    started: Option<Instant>,
+    elapsed: Duration,
+    error: Option<String>,
+    /// Format of the solution files written by "Save Solutions"
+    solution_format: SolutionFormat,
+    /// Outcome of the last export
+    export_status: Option<Result<String, String>>,
+}
+
+impl Default for BatchState {
+    fn default() -> Self {
+        Self {
+            folder: None,
+            files: Vec::new(),
+            instances: None,
+            use_cache: true,
+            threads: default_threads(),
+            solver_name: String::new(),
+            results: Vec::new(),
+            receiver: None,
+            started: None,
+            elapsed: Duration::ZERO,
+            error: None,
+            solution_format: SolutionFormat::OrTools,
+            export_status: None,
+        }
+    }
+}
+
+impl BatchState {
+    fn is_running(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    fn completed(&self) -> usize {
+        self.results.iter().filter(|result| result.is_some()).count()
+    }
+
+    /// The results that have arrived, in file order
+    fn finished_results(&self) -> Vec<BatchResult> {
+        self.results.iter().flatten().cloned().collect()
+    }
+}
+
+impl JsspApp {
+    /// Collect results that finished since the last frame
+    pub(super) fn poll_batch(&mut self, ctx: &egui::Context) {
+        let batch = &mut self.batch;
+        let Some(receiver) = &batch.receiver else {
+            return;
+        };
+        let finished = loop {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    let index = result.index;
+                    batch.results[index] = Some(result);
+                }
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if let Some(started) = batch.started {
+            batch.elapsed = started.elapsed();
+        }
+        if finished {
+            batch.receiver = None;
+        } else {
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+    }
+
+    fn choose_batch_folder(&mut self) {
+        let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        self.batch.results.clear();
+        self.batch.instances = None;
+        match instance_files(&folder) {
+            Ok(files) => {
+                self.batch.files = files;
+                self.batch.error = None;
+            }
+            Err(e) => {
+                self.batch.files.clear();
+                self.batch.error = Some(e.to_string());
+            }
+        }
+        self.batch.folder = Some(folder);
+    }
+
+    fn start_batch(&mut self) {
+        let solver_name = self.selected_solver_name();
+        let Some(solver) = self.solvers.shared(&solver_name) else {
+            return;
+        };
+        let batch = &mut self.batch;
+        let cache = batch.folder.as_deref().filter(|_| batch.use_cache).map(InstanceCache::in_folder);
+        let instances = match &batch.instances {
+            Some(instances) if instances.cache() == cache.as_ref() => Arc::clone(instances),
+            _ => Arc::new(InstanceSet::new(batch.files.clone(), cache)),
+        };
+        batch.instances = Some(Arc::clone(&instances));
+        batch.results = vec![None; batch.files.len()];
+        batch.receiver = Some(spawn_batch(instances, solver, batch.threads));
+        batch.solver_name = solver_name;
+        batch.started = Some(Instant::now());
+        batch.elapsed = Duration::ZERO;
+        batch.export_status = None;
+    }
+
+    fn export_batch_csv_with_dialog(&mut self) {
+        let csv = results_csv(&self.batch.solver_name, &self.batch.finished_results());
+        let folder = self.batch.folder.as_ref().and_then(|folder| folder.file_name()).map(|name| name.to_string_lossy().into_owned());
+        let file_name = format!("{}_{}.csv", folder.unwrap_or_else(|| "batch".to_string()), self.batch.solver_name.replace(' ', "_").to_lowercase());
+        let Some(path) = self.save_path(&file_name, &[("CSV", &["csv"])]) else {
+            return;
+        };
+        self.batch.export_status = Some(
+            write_file(&path, csv.as_bytes())
+                .map(|location| format!("Exported the results to {}", location))
+                .map_err(String::from),
+        );
+    }
+
+    fn save_batch_solutions_with_dialog(&mut self) {
+        let Some(dir) = self.save_folder() else {
+            return;
+        };
+        self.batch.export_status = Some(
+            write_solutions(&self.batch.finished_results(), &dir, self.batch.solution_format, &self.run_parameters(&self.batch.solver_name))
+                .map(|written| format!("Saved {} solution(s) to {}", written, dir.display()))
+                .map_err(String::from),
+        );
+    }
+
+    /// Load a solved batch row into the Schedule tab
+    fn open_batch_result(&mut self, index: usize) {
+        let Some(Some(BatchResult { outcome: Ok(solution), .. })) = self.batch.results.get(index) else {
+            return;
+        };
+        let (instance, schedule) = (solution.instance.clone(), solution.schedule.clone());
+        let run = solution.run(self.run_parameters(&solution.algorithm));
+        self.remember("Open Batch Result");
+        self.replace_problem(Some(JsspSolver::new(instance)));
+        self.apply_edited_schedule(schedule);
+        self.run = Some(run);
+        self.tab = AppTab::Schedule;
+    }
+
+    pub(super) fn render_batch_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_batch_window;
+        let mut open_row = None;
+        let (mut export_csv, mut save_solutions) = (false, false);
+        egui::Window::new("Batch Solve")
+            .open(&mut open)
+            .default_width(720.0)
+            .show(ctx, |ui| {
+                ui.label("Solve every OR-Tools JSON instance in a folder, in parallel, with the selected algorithm.");
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.batch.is_running(), egui::Button::new("Choose Folder...")).clicked() {
+                        self.choose_batch_folder();
+                    }
+                    match &self.batch.folder {
+                        Some(folder) => ui.label(format!("{} ({} instance files)", folder.display(), self.batch.files.len())),
+                        None => ui.colored_label(egui::Color32::GRAY, "No folder selected"),
+                    };
+                });
+                if let Some(error) = &self.batch.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("Algorithm: {}", self.selected_solver_name()));
+                    ui.label("Threads:");
+                    ui.add(egui::DragValue::new(&mut self.batch.threads).range(1..=256));
+                    ui.checkbox(&mut self.batch.use_cache, "Binary cache")
+                        .on_hover_text("Keep compact binary copies of the instances in the folder's .jssp-cache, so later runs skip parsing the JSON files");
+                    let can_start = !self.batch.files.is_empty() && !self.batch.is_running();
+                    if ui.add_enabled(can_start, egui::Button::new("Solve All")).clicked() {
+                        self.start_batch();
+                    }
+                });
+
+                if self.batch.results.is_empty() {
+                    return;
+                }
+
+                let (completed, total) = (self.batch.completed(), self.batch.results.len());
+                ui.add(
+                    egui::ProgressBar::new(completed as f32 / total.max(1) as f32)
+                        .text(format!(
+                            "{} {}/{} solved in {:.1}s",
+                            self.batch.solver_name, completed, total, self.batch.elapsed.as_secs_f64()
+                        ))
+                );
+                ui.horizontal(|ui| {
+                    let any_solved = self.batch.completed() > 0;
+                    export_csv = ui.add_enabled(any_solved, egui::Button::new("Export CSV..."))
+                        .on_hover_text("One row per file with size, makespan, lower bound, gap, feasibility and runtime")
+                        .clicked();
+                    save_solutions = ui.add_enabled(any_solved, egui::Button::new("Save Solutions..."))
+                        .on_hover_text("Write each schedule into a folder as <instance>_solution.<ext>")
+                        .clicked();
+                    egui::ComboBox::from_id_salt("batch_solution_format")
+                        .selected_text(self.batch.solution_format.name())
+                        .show_ui(ui, |ui| {
+                            for format in SolutionFormat::ALL {
+                                ui.selectable_value(&mut self.batch.solution_format, format, format.name());
+                            }
+                        });
+                });
+                match &self.batch.export_status {
+                    Some(Ok(message)) => {
+                        ui.label(message);
+                    }
+                    Some(Err(message)) => {
+                        ui.colored_label(egui::Color32::RED, message);
+                    }
+                    None => {}
+                }
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("batch_results")
+                            .striped(true)
+                            .num_columns(8)
+                            .show(ui, |ui| {
+                                for header in ["Instance", "Size", "Makespan", "Lower Bound", "Gap", "vs Best Known", "Runtime (ms)", ""] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for (index, (path, result)) in self.batch.files.iter().zip(&self.batch.results).enumerate() {
+                                    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+                                    ui.label(file_name.as_ref());
+                                    match result.as_ref().map(|result| &result.outcome) {
+                                        None => {
+                                            ui.add(egui::Spinner::new());
+                                            for _ in 0..6 {
+                                                ui.label("");
+                                            }
+                                        }
+                                        Some(Err(e)) => {
+                                            ui.colored_label(egui::Color32::RED, "error").on_hover_text(e);
+                                            for _ in 0..6 {
+                                                ui.label("");
+                                            }
+                                        }
+                                        Some(Ok(solution)) => {
+                                            ui.label(format!("{}x{}", solution.instance.jobs().len(), solution.instance.num_machines()));
+                                            if solution.feasible {
+                                                ui.label(format!("{:.2}", solution.makespan));
+                                            } else {
+                                                ui.colored_label(egui::Color32::RED, format!("{:.2} (infeasible)", solution.makespan));
+                                            }
+                                            ui.label(format!("{:.2}", solution.lower_bound));
+                                            ui.label(format!("{:.1}%", optimality_gap(solution.makespan, solution.lower_bound) * 100.0));
+                                            match solution.instance.best_known() {
+                                                Some(known) => ui.label(format!("{:+.1}%", known.gap(solution.makespan) * 100.0))
+                                                    .on_hover_text(format!("{} {}: {}", known.name, known.kind(), known.makespan)),
+                                                None => ui.label("-"),
+                                            };
+                                            ui.label(format!("{:.3}", solution.runtime.as_secs_f64() * 1000.0));
+                                            if ui.button("Open").on_hover_text("Show this instance and schedule in the Schedule tab").clicked() {
+                                                open_row = Some(index);
+                                            }
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_batch_window = open;
+        if let Some(index) = open_row {
+            self.open_batch_result(index);
+        }
+        if export_csv {
+            self.export_batch_csv_with_dialog();
+        }
+        if save_solutions {
+            self.save_batch_solutions_with_dialog();
+        }
+    }
+}
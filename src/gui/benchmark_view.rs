@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use eframe::egui;
+use web_time::Instant;
+
+use super::files::write_file;
+use super::JsspApp;
+use crate::jssp::benchmark::{benchmarks_to_csv, Benchmark};
+use crate::jssp::bounds::GapTracker;
+
+/// Runs per benchmark started from the toolbar
+pub(super) const BENCHMARK_RUNS: usize = 30;
+/// Solver time spent per frame while a benchmark runs, so the window stays responsive
+const FRAME_BUDGET: Duration = Duration::from_millis(30);
+
+impl JsspApp {
+    /// Start benchmarking the selected algorithm on the current instance
+    pub(super) fn start_benchmark(&mut self) {
+        let (Some(solver), Some(algorithm)) = (&self.solver, self.solvers.solvers().nth(self.selected_solver)) else {
+            return;
+        };
+        self.benchmark = Some(Benchmark::new(algorithm, solver.instance(), BENCHMARK_RUNS));
+        self.show_benchmark_window = true;
+    }
+
+    /// Advance a running benchmark by as many runs as fit in the frame budget
+    pub(super) fn step_benchmark(&mut self, ctx: &egui::Context) {
+        let Some(benchmark) = &mut self.benchmark else {
+            return;
+        };
+        let (Some(solver), Some(algorithm)) = (&self.solver, self.solvers.get(&benchmark.solver)) else {
+            self.benchmark = None;
+            return;
+        };
+
+        let started = Instant::now();
+        let mut failed = false;
+        while !benchmark.is_complete() && started.elapsed() < FRAME_BUDGET {
+            if let Err(e) = benchmark.run_once(algorithm, solver.instance()) {
+                // Further runs would most likely fail the same way
+                self.toasts.error(&e);
+                failed = true;
+                break;
+            }
+        }
+        if benchmark.is_complete() || failed {
+            self.benchmark_results.extend(self.benchmark.take());
+        } else {
+            ctx.request_repaint();
+        }
+    }
+
+    pub(super) fn render_benchmark_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_benchmark_window;
+        egui::Window::new("Benchmark Results")
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                let mut cancel = false;
+                if let Some(benchmark) = &self.benchmark {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Running {} on {}", benchmark.solver, benchmark.instance));
+                        gap_progress_bar(
+                            ui,
+                            &benchmark.gap,
+                            &format!("run {}/{}", benchmark.runs.len(), benchmark.planned_runs),
+                        );
+                        cancel = ui.button("Cancel").clicked();
+                    });
+                    ui.separator();
+                }
+                if cancel {
+                    self.benchmark = None;
+                }
+
+                if self.benchmark_results.is_empty() {
+                    ui.colored_label(egui::Color32::GRAY, "Completed benchmarks are listed here for comparison.");
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("benchmark_results")
+                            .striped(true)
+                            .num_columns(9)
+                            .show(ui, |ui| {
+                                for header in ["Solver", "Instance", "Runs", "Best", "Mean", "Std", "Worst", "Gap", "Runtime (ms)"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for benchmark in &self.benchmark_results {
+                                    ui.label(&benchmark.solver);
+                                    ui.label(&benchmark.instance);
+                                    let infeasible = benchmark.runs.len() - benchmark.feasible_runs();
+                                    if let Some(error) = benchmark.first_error() {
+                                        ui.colored_label(egui::Color32::RED, format!("{} ({} failed)", benchmark.runs.len(), benchmark.failed_runs()))
+                                            .on_hover_text(error);
+                                    } else if infeasible > 0 {
+                                        ui.colored_label(egui::Color32::RED, format!("{} ({} infeasible)", benchmark.runs.len(), infeasible));
+                                    } else {
+                                        ui.label(benchmark.runs.len().to_string());
+                                    }
+                                    ui.label(format!("{:.2}", benchmark.best_makespan()));
+                                    ui.label(format!("{:.2}", benchmark.mean_makespan()));
+                                    ui.label(format!("{:.2}", benchmark.std_makespan()));
+                                    ui.label(format!("{:.2}", benchmark.worst_makespan()));
+                                    ui.label(benchmark.gap.current_gap().map_or("-".to_string(), |gap| format!("{:.1}%", gap * 100.0)))
+                                        .on_hover_text(format!("Lower bound {:.2}", benchmark.gap.lower_bound));
+                                    ui.label(format!("{:.3} ± {:.3}", benchmark.mean_runtime_ms(), benchmark.std_runtime_ms()));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Export CSV").clicked() {
+                        self.export_benchmarks_with_dialog();
+                    }
+                    if ui.button("Clear").clicked() {
+                        self.benchmark_results.clear();
+                    }
+                });
+            });
+        self.show_benchmark_window = open;
+    }
+
+    fn export_benchmarks_with_dialog(&mut self) {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        if let Some(path) = self.save_path(&format!("jssp_benchmark_{}.csv", timestamp), &[("CSV", &["csv"])]) {
+            let saved = write_file(&path, benchmarks_to_csv(&self.benchmark_results).as_bytes());
+            self.report_export("benchmark results", saved);
+        }
+    }
+}
+
+/// Progress of an anytime search as the share of the way from the incumbent down to
+/// the lower bound, labelled with how the gap closed (plus `detail`, e.g. the run count)
+pub(super) fn gap_progress_bar(ui: &mut egui::Ui, gap: &GapTracker, detail: &str) {
+    ui.add(
+        egui::ProgressBar::new(gap.fraction())
+            .desired_width(280.0)
+            .text(format!("{}  ({})", gap.label(), detail))
+    ).on_hover_text(format!(
+        "Filled to lower bound / best makespan; lower bound {:.2}",
+        gap.lower_bound
+    ));
+}
@@ -0,0 +1,194 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use super::JsspApp;
+use crate::chart::Palette;
+use crate::jssp::ScheduledOperation;
+
+/// Slack left at completion, as a share of the time from release to due date, below
+/// which a job still on time counts as near its due date
+const NEAR_DUE_SHARE: f64 = 0.1;
+/// Priority colors from the lightest job to the heaviest
+const LIGHT_PRIORITY: [u8; 3] = [198, 219, 239];
+const HEAVY_PRIORITY: [u8; 3] = [8, 48, 107];
+
+/// What the blocks of the Gantt chart are colored by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorBy {
+    #[default]
+    Job,
+    Machine,
+    /// Green on time, amber near the due date, red late
+    Lateness,
+    /// Darker for heavier jobs
+    Priority,
+}
+
+impl ColorBy {
+    pub const ALL: [ColorBy; 4] = [ColorBy::Job, ColorBy::Machine, ColorBy::Lateness, ColorBy::Priority];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorBy::Job => "Job",
+            ColorBy::Machine => "Machine",
+            ColorBy::Lateness => "Lateness",
+            ColorBy::Priority => "Priority",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ColorBy::Job => "Each job in its own color",
+            ColorBy::Machine => "Each machine in its own color",
+            ColorBy::Lateness => "Jobs by lateness risk: green on time, amber finishing within 10% of their time to the due date, red late, gray without a due date",
+            ColorBy::Priority => "Jobs by weight, from light for the lightest to dark for the heaviest",
+        }
+    }
+}
+
+/// Where a job's completion in the schedule stands against its due date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LatenessRisk {
+    OnTime,
+    NearDueDate,
+    Late,
+    NoDueDate,
+}
+
+impl LatenessRisk {
+    const ALL: [LatenessRisk; 4] = [LatenessRisk::OnTime, LatenessRisk::NearDueDate, LatenessRisk::Late, LatenessRisk::NoDueDate];
+
+    fn name(self) -> &'static str {
+        match self {
+            LatenessRisk::OnTime => "On time",
+            LatenessRisk::NearDueDate => "Near due date",
+            LatenessRisk::Late => "Late",
+            LatenessRisk::NoDueDate => "No due date",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            LatenessRisk::OnTime => egui::Color32::from_rgb(60, 170, 80),
+            LatenessRisk::NearDueDate => egui::Color32::from_rgb(235, 165, 30),
+            LatenessRisk::Late => egui::Color32::from_rgb(210, 60, 60),
+            LatenessRisk::NoDueDate => egui::Color32::from_gray(150),
+        }
+    }
+}
+
+/// The block colors of one frame of the Gantt chart
+pub(super) struct BlockColors {
+    color_by: ColorBy,
+    /// By job id; empty when coloring by machine
+    jobs: Vec<egui::Color32>,
+    palette: Palette,
+}
+
+impl BlockColors {
+    pub fn operation(&self, op: &ScheduledOperation) -> egui::Color32 {
+        match self.color_by {
+            ColorBy::Machine => {
+                let [r, g, b] = self.palette.color(op.machine_id);
+                egui::Color32::from_rgb(r, g, b)
+            }
+            _ => self.job(op.job_id),
+        }
+    }
+
+    /// A job's color in the legend; gray when coloring by machine
+    pub fn job(&self, job_id: usize) -> egui::Color32 {
+        self.jobs.get(job_id).copied().unwrap_or(egui::Color32::GRAY)
+    }
+}
+
+impl JsspApp {
+    pub(super) fn block_colors(&self) -> BlockColors {
+        let jobs = match self.color_by {
+            ColorBy::Job => self.job_colors(),
+            ColorBy::Machine => Vec::new(),
+            ColorBy::Lateness => self.lateness_risks().into_iter().map(LatenessRisk::color).collect(),
+            ColorBy::Priority => self.priority_colors(),
+        };
+        BlockColors { color_by: self.color_by, jobs, palette: self.palette }
+    }
+
+    /// Lateness risk of every job by job id, from its last operation in the schedule
+    fn lateness_risks(&self) -> Vec<LatenessRisk> {
+        let Some(solver) = &self.solver else {
+            return Vec::new();
+        };
+        let completion = solver.job_completion_times(&self.schedule);
+        solver.jobs().iter()
+            .map(|job| {
+                let (Some(due_date), Some(&completed)) = (job.due_date, completion.get(&job.id)) else {
+                    return LatenessRisk::NoDueDate;
+                };
+                let allowance = (due_date - job.release_time).max(0.0);
+                if completed > due_date + 1e-6 {
+                    LatenessRisk::Late
+                } else if due_date - completed <= NEAR_DUE_SHARE * allowance {
+                    LatenessRisk::NearDueDate
+                } else {
+                    LatenessRisk::OnTime
+                }
+            })
+            .collect()
+    }
+
+    /// The color of every job by job id, shaded by its weight among all jobs
+    fn priority_colors(&self) -> Vec<egui::Color32> {
+        let Some(solver) = &self.solver else {
+            return Vec::new();
+        };
+        let weights = solver.jobs().iter().map(|job| job.weight);
+        let lightest = weights.clone().fold(f64::INFINITY, f64::min);
+        let heaviest = weights.clone().fold(f64::NEG_INFINITY, f64::max);
+        weights
+            .map(|weight| {
+                let share = if heaviest > lightest { (weight - lightest) / (heaviest - lightest) } else { 0.5 };
+                priority_color(share)
+            })
+            .collect()
+    }
+
+    /// What the lateness and priority colors mean, with the number of jobs of each risk
+    pub(super) fn render_color_legend(&self, ui: &mut egui::Ui) {
+        let swatch = |ui: &mut egui::Ui, color: egui::Color32| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+            ui.painter().rect_filled(rect, 2.0, color);
+        };
+        match self.color_by {
+            ColorBy::Lateness => {
+                let risks = self.lateness_risks();
+                ui.horizontal(|ui| {
+                    ui.label("Lateness:");
+                    for risk in LatenessRisk::ALL {
+                        swatch(ui, risk.color());
+                        ui.label(format!("{} ({})", risk.name(), risks.iter().filter(|&&r| r == risk).count()));
+                    }
+                });
+            }
+            ColorBy::Priority => {
+                let Some(solver) = &self.solver else {
+                    return;
+                };
+                let weights = solver.jobs().iter().map(|job| job.weight);
+                ui.horizontal(|ui| {
+                    ui.label(format!("Weight {:.2}", weights.clone().fold(f64::INFINITY, f64::min)));
+                    for step in 0..5 {
+                        swatch(ui, priority_color(step as f64 / 4.0));
+                    }
+                    ui.label(format!("{:.2}", weights.fold(f64::NEG_INFINITY, f64::max)));
+                });
+            }
+            ColorBy::Job | ColorBy::Machine => {}
+        }
+    }
+}
+
+/// Between the lightest (0) and the heaviest (1) priority color
+fn priority_color(share: f64) -> egui::Color32 {
+    let channel = |i: usize| (LIGHT_PRIORITY[i] as f64 + (HEAVY_PRIORITY[i] as f64 - LIGHT_PRIORITY[i] as f64) * share).round() as u8;
+    egui::Color32::from_rgb(channel(0), channel(1), channel(2))
+}
@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use eframe::egui;
+use egui_plot::{Line, Plot, PlotUi};
+
+use super::gantt_blocks::{Block, GanttBlocks};
+use super::gantt_edit::BLOCK_HEIGHT;
+use super::JsspApp;
+use crate::jssp::diff::{diff_schedules, ScheduleDiff};
+use crate::jssp::{Schedule, ScheduledOperation};
+
+/// A schedule pinned to compare later solves against
+pub(super) struct Baseline {
+    /// What produced it, e.g. the algorithm name
+    pub label: String,
+    pub schedule: Schedule,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CompareLayout {
+    /// Baseline outlines drawn over the current schedule
+    Overlay,
+    /// Baseline above, current schedule below, on a shared time axis
+    Split,
+}
+
+impl JsspApp {
+    pub(super) fn pin_baseline(&mut self) {
+        let label = self.selected_solver_name();
+        self.baseline = Some(Baseline { label, schedule: self.schedule.clone() });
+        self.show_compare_window = true;
+        self.toasts.success(format!("Pinned the current schedule as baseline (makespan {:.2})", self.makespan));
+    }
+
+    pub(super) fn render_compare_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_compare_window;
+        let mut repin = false;
+        let job_colors = self.job_colors();
+        egui::Window::new("Compare with Baseline")
+            .open(&mut open)
+            .default_width(720.0)
+            .show(ctx, |ui| {
+                let Some(baseline) = &self.baseline else {
+                    ui.label("Pin a schedule as baseline, then solve again to see what changed.");
+                    return;
+                };
+                let diff = diff_schedules(&baseline.schedule, &self.schedule);
+                Self::render_diff_summary(ui, &baseline.label, &diff);
+
+                ui.horizontal(|ui| {
+                    ui.label("Layout:");
+                    ui.selectable_value(&mut self.compare_layout, CompareLayout::Overlay, "Overlay")
+                        .on_hover_text("Baseline operations outlined on top of the current schedule");
+                    ui.selectable_value(&mut self.compare_layout, CompareLayout::Split, "Split")
+                        .on_hover_text("Baseline and current schedule one above the other");
+                    ui.separator();
+                    if ui.button("Pin Current").on_hover_text("Make the current schedule the new baseline").clicked() {
+                        repin = true;
+                    }
+                });
+
+                let moved: HashSet<(usize, usize)> = diff.moves.iter()
+                    .map(|m| (m.job_id, m.operation_id))
+                    .collect();
+                let is_visible = self.visibility();
+                let visible = |ops: &Schedule| -> Vec<ScheduledOperation> {
+                    ops.iter().filter(|op| is_visible(op)).cloned().collect()
+                };
+                let (before, after) = (visible(&baseline.schedule), visible(&self.schedule));
+                let gantt_rows = self.gantt_rows;
+                let row = |op: &ScheduledOperation| gantt_rows.row(op);
+
+                match self.compare_layout {
+                    CompareLayout::Overlay => {
+                        Plot::new("compare_overlay").height(320.0).show(ui, |plot_ui| {
+                            draw_blocks(plot_ui, &after, &row, &moved, &job_colors, true);
+                            draw_blocks(plot_ui, &before, &row, &moved, &job_colors, false);
+                            // Connect each moved operation's old and new position
+                            for m in &diff.moves {
+                                let (Some(old), Some(new)) = (
+                                    before.iter().find(|op| (op.job_id, op.operation_id) == (m.job_id, m.operation_id)),
+                                    after.iter().find(|op| (op.job_id, op.operation_id) == (m.job_id, m.operation_id)),
+                                ) else {
+                                    continue;
+                                };
+                                plot_ui.line(
+                                    Line::new(vec![
+                                        [(old.start_time + old.end_time) / 2.0, row(old)],
+                                        [(new.start_time + new.end_time) / 2.0, row(new)],
+                                    ])
+                                    .color(egui::Color32::YELLOW.gamma_multiply(0.6))
+                                    .width(1.0)
+                                    .allow_hover(false),
+                                );
+                            }
+                        });
+                    }
+                    CompareLayout::Split => {
+                        let link = ui.id().with("compare_split");
+                        ui.label(format!("Baseline ({})", baseline.label));
+                        Plot::new("compare_baseline").height(180.0).link_axis(link, [true, true]).show(ui, |plot_ui| {
+                            draw_blocks(plot_ui, &before, &row, &moved, &job_colors, true);
+                        });
+                        ui.label("Current");
+                        Plot::new("compare_current").height(180.0).link_axis(link, [true, true]).show(ui, |plot_ui| {
+                            draw_blocks(plot_ui, &after, &row, &moved, &job_colors, true);
+                        });
+                    }
+                }
+
+                egui::CollapsingHeader::new(format!("Moved Operations ({})", diff.moves.len()))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical().id_salt("moved_operations").max_height(160.0).show(ui, |ui| {
+                            egui::Grid::new("moved_operations_grid").striped(true).show(ui, |ui| {
+                                for header in ["Job", "Op", "Machine", "Start", "Shift"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+                                for m in &diff.moves {
+                                    ui.label(m.job_id.to_string());
+                                    ui.label(m.operation_id.to_string());
+                                    if m.changed_machine() {
+                                        ui.colored_label(egui::Color32::YELLOW, format!("M{} ➡ M{}", m.baseline_machine, m.machine));
+                                    } else {
+                                        ui.label(format!("M{}", m.machine));
+                                    }
+                                    ui.label(format!("{:.1} ➡ {:.1}", m.baseline_start, m.start));
+                                    ui.label(format!("{:+.1}", m.shift()));
+                                    ui.end_row();
+                                }
+                            });
+                        });
+                    });
+            });
+        self.show_compare_window = open;
+        if repin {
+            self.pin_baseline();
+        }
+    }
+
+    fn render_diff_summary(ui: &mut egui::Ui, label: &str, diff: &ScheduleDiff) {
+        ui.label(format!("Baseline: {}, makespan {:.2}", label, diff.baseline_makespan));
+        let delta = diff.makespan_delta();
+        let color = if delta < -1e-9 {
+            egui::Color32::GREEN
+        } else if delta > 1e-9 {
+            egui::Color32::LIGHT_RED
+        } else {
+            ui.visuals().text_color()
+        };
+        let percent = if diff.baseline_makespan > 0.0 { delta / diff.baseline_makespan * 100.0 } else { 0.0 };
+        ui.colored_label(color, format!("Current makespan {:.2} (Δ {:+.2}, {:+.1}%)", diff.makespan, delta, percent));
+        ui.label(format!(
+            "{} operation(s) moved ({} to another machine), {} unchanged",
+            diff.moves.len(),
+            diff.machine_changes(),
+            diff.unchanged
+        ));
+        if !diff.unmatched.is_empty() {
+            ui.colored_label(
+                egui::Color32::LIGHT_RED,
+                format!("⚠ {} operation(s) appear in only one schedule; was the instance edited?", diff.unmatched.len()),
+            );
+        }
+    }
+}
+
+/// Draw operations colored by job: filled, or as outlines for the baseline in the
+/// overlay. Operations placed the same in both schedules are faded.
+fn draw_blocks(
+    plot_ui: &mut PlotUi,
+    ops: &[ScheduledOperation],
+    row: &dyn Fn(&ScheduledOperation) -> f64,
+    moved: &HashSet<(usize, usize)>,
+    job_colors: &[egui::Color32],
+    filled: bool,
+) {
+    let mut blocks = GanttBlocks::default();
+    for op in ops {
+        let mut color = job_colors.get(op.job_id).copied().unwrap_or(egui::Color32::GRAY);
+        if !moved.contains(&(op.job_id, op.operation_id)) {
+            if !filled {
+                continue;
+            }
+            color = color.gamma_multiply(0.3);
+        }
+        let y = row(op);
+        let half = BLOCK_HEIGHT / 2.0;
+        let stroke = if filled {
+            egui::Stroke::new(1.0, color)
+        } else {
+            egui::Stroke::new(1.5, egui::Color32::WHITE.gamma_multiply(0.8))
+        };
+        blocks.push(Block {
+            min: [op.start_time, y - half],
+            max: [op.end_time, y + half],
+            fill: if filled { color } else { egui::Color32::TRANSPARENT },
+            stroke,
+            label: format!(
+                "{}Job {} | Op {} | Machine {} | {:.1}->{:.1}",
+                if filled { "" } else { "Baseline: " },
+                op.job_id,
+                op.operation_id,
+                op.machine_id,
+                op.start_time,
+                op.end_time
+            ),
+        });
+    }
+    plot_ui.add(blocks);
+}
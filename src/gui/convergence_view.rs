@@ -0,0 +1,364 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+use super::JsspApp;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::jssp::checkpoint::{Checkpoint, Checkpointing, RunEnd, RunStart};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::jssp::Solver;
+use crate::jssp::pool::SolutionPool;
+use crate::jssp::trace::ConvergencePoint;
+use crate::jssp::{Instance, JsspError, Objective, Schedule, SolverConfig};
+
+/// How often a running solve writes its checkpoint file, besides when it pauses
+#[cfg(not(target_arch = "wasm32"))]
+const CHECKPOINT_PERIOD: Duration = Duration::from_secs(10);
+
+/// Best value over iterations of the last single solve, for algorithms that report their
+/// progress (see `Solver::solve_with_progress`)
+pub(super) struct Convergence {
+    /// Algorithm of the run the points belong to
+    solver_name: String,
+    objective: Objective,
+    points: Vec<ConvergencePoint>,
+    /// The solve still running on a worker thread, so the plot grows while it runs
+    #[cfg(not(target_arch = "wasm32"))]
+    running: Option<RunningSolve>,
+    /// The checkpoint file of a paused or interrupted run (see `Checkpoint::default_path`)
+    #[cfg(not(target_arch = "wasm32"))]
+    saved: Option<Checkpoint>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct RunningSolve {
+    receiver: Receiver<SolveUpdate>,
+    seed: u64,
+    started: Instant,
+    /// Asks the run to stop at its next checkpoint
+    pause: Arc<AtomicBool>,
+    /// Iteration of the last checkpoint, for algorithms that take them
+    checkpoint: Option<usize>,
+    /// The run started from the schedule shown before it
+    warm_start: bool,
+    /// The solution pool of the run, once it has finished
+    pool: Option<SolutionPool>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+enum SolveUpdate {
+    Progress(ConvergencePoint),
+    Checkpoint(usize),
+    /// Sent just before `Finished` by runs that keep a solution pool
+    Pool(SolutionPool),
+    Finished(Schedule),
+    Paused(Checkpoint),
+    Failed(String),
+}
+
+impl Default for Convergence {
+    fn default() -> Self {
+        Self {
+            solver_name: String::new(),
+            objective: Objective::Makespan,
+            points: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            running: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            saved: Checkpoint::default_path().and_then(|path| Checkpoint::load(&path).ok().flatten()),
+        }
+    }
+}
+
+impl Convergence {
+    /// Whether there is anything to plot or wait for
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty() && !self.is_running()
+    }
+
+    pub fn is_running(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.running.is_some();
+        #[cfg(target_arch = "wasm32")]
+        false
+    }
+
+    /// The history as exported with the solution, or null if there is none
+    pub fn to_json(&self) -> serde_json::Value {
+        if self.points.is_empty() {
+            return serde_json::Value::Null;
+        }
+        serde_json::json!({
+            "algorithm": self.solver_name,
+            "objective": self.objective.key(),
+            "points": self.points,
+        })
+    }
+}
+
+impl JsspApp {
+    /// Run the selected algorithm once with `seed`, recording its convergence. On the
+    /// desktop the run goes to a worker thread and `poll_solve` shows the result; on the
+    /// web it runs right away.
+    pub(super) fn start_solve(&mut self, seed: u64) {
+        let solver_name = self.selected_solver_name();
+        let (Some(solver), Some(algorithm)) = (&self.solver, self.solvers.shared(&solver_name)) else {
+            return;
+        };
+        let (instance, objective) = (Instance::clone(solver), self.objective);
+        let warm_start = self.warm_start && algorithm.supports_warm_start() && !self.schedule.is_empty();
+        let initial = warm_start.then(|| self.schedule.clone());
+        let pool_capacity = self.solution_pool_capacity(algorithm.supports_pool());
+        self.convergence = Convergence { solver_name: solver_name.clone(), objective, ..Convergence::default() };
+        self.solution_pool = None;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.spawn_solve(algorithm, instance, RunStart::New { objective, seed }, initial, pool_capacity);
+        #[cfg(target_arch = "wasm32")]
+        {
+            let started = web_time::Instant::now();
+            let points = &mut self.convergence.points;
+            let config = SolverConfig { initial_solution: initial, ..SolverConfig::new(objective, seed) };
+            let mut pool = SolutionPool::new(pool_capacity);
+            let schedule = algorithm.solve_with_pool(&instance, &config, &mut pool, &mut |point| points.push(point));
+            let mut run = self.run_metadata(&solver_name, seed, 1, started.elapsed());
+            if warm_start {
+                run.parameters["warm_start"] = serde_json::json!(true);
+            }
+            self.finish_solve(schedule, run);
+            self.set_solution_pool(pool, objective);
+        }
+    }
+
+    /// Continue the run of the saved checkpoint, with its algorithm and objective
+    #[cfg(not(target_arch = "wasm32"))]
+    fn resume_solve(&mut self, checkpoint: Checkpoint) {
+        let (Some(solver), Some(algorithm)) = (&self.solver, self.solvers.shared(&checkpoint.algorithm)) else {
+            return;
+        };
+        let (instance, objective) = (Instance::clone(solver), checkpoint.objective().unwrap_or(self.objective));
+        if let Some(index) = self.solvers.solvers().position(|solver| solver.name() == checkpoint.algorithm) {
+            self.selected_solver = index;
+        }
+        self.objective = objective;
+        self.convergence = Convergence { solver_name: checkpoint.algorithm.clone(), objective, ..Convergence::default() };
+        self.spawn_solve(algorithm, instance, RunStart::Resume(checkpoint), None, 0);
+    }
+
+    /// Run on a worker thread, resumably if the algorithm supports it: the checkpoints
+    /// go to the checkpoint file every `CHECKPOINT_PERIOD` and when pausing, and the file
+    /// is deleted once the run that wrote it finishes. A warm start from `initial` and a
+    /// run keeping a solution pool of `pool_capacity` schedules go without checkpoints.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn_solve(&mut self, algorithm: Arc<dyn Solver>, instance: Instance, start: RunStart, initial: Option<Schedule>, pool_capacity: usize) {
+        let (objective, seed) = match &start {
+            RunStart::New { objective, seed } => (*objective, *seed),
+            RunStart::Resume(checkpoint) => (checkpoint.objective().unwrap_or(self.objective), checkpoint.seed),
+        };
+        let warm_start = initial.is_some();
+        let (sender, receiver) = mpsc::channel();
+        let pause = Arc::new(AtomicBool::new(false));
+        let paused = Arc::clone(&pause);
+        std::thread::spawn(move || {
+            let progress = sender.clone();
+            let mut report = |point| {
+                let _ = progress.send(SolveUpdate::Progress(point));
+            };
+            let path = Checkpoint::default_path();
+            let mut owns_file = matches!(start, RunStart::Resume(_));
+            let mut last_saved = Instant::now();
+            let mut save = |checkpoint: &Checkpoint| {
+                let _ = progress.send(SolveUpdate::Checkpoint(checkpoint.iteration));
+                if !paused.load(Ordering::Relaxed) && last_saved.elapsed() < CHECKPOINT_PERIOD {
+                    return;
+                }
+                if let Some(path) = &path {
+                    let written = path.parent().map_or(Ok(()), std::fs::create_dir_all)
+                        .map_err(JsspError::io("create", path))
+                        .and_then(|()| checkpoint.save(path));
+                    owns_file |= written.is_ok();
+                }
+                last_saved = Instant::now();
+            };
+            let pause_requested = || paused.load(Ordering::Relaxed);
+            let mut checkpointing = Checkpointing { save: &mut save, pause: &pause_requested };
+            let config = SolverConfig { initial_solution: initial, ..SolverConfig::new(objective, seed) };
+            let resumable = match config.initial_solution {
+                None if pool_capacity == 0 => algorithm.solve_resumable(&instance, start, &mut checkpointing, &mut report),
+                _ => None,
+            };
+            let update = match resumable {
+                Some(Ok(RunEnd::Paused(checkpoint))) => SolveUpdate::Paused(checkpoint),
+                Some(Ok(RunEnd::Finished(schedule))) => {
+                    if let Some(path) = path.filter(|_| owns_file) {
+                        let _ = std::fs::remove_file(path);
+                    }
+                    SolveUpdate::Finished(schedule)
+                }
+                Some(Err(e)) => SolveUpdate::Failed(e),
+                None if pool_capacity > 0 => {
+                    let mut pool = SolutionPool::new(pool_capacity);
+                    let schedule = algorithm.solve_with_pool(&instance, &config, &mut pool, &mut report);
+                    let _ = sender.send(SolveUpdate::Pool(pool));
+                    SolveUpdate::Finished(schedule)
+                }
+                None => SolveUpdate::Finished(algorithm.solve_with_config(&instance, &config, &mut report)),
+            };
+            let _ = sender.send(update);
+        });
+        self.convergence.running = Some(RunningSolve { receiver, seed, started: Instant::now(), pause, checkpoint: None, warm_start, pool: None });
+    }
+
+    /// Collect the progress of a running solve, and show its schedule once it is done
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn poll_solve(&mut self, ctx: &egui::Context) {
+        let convergence = &mut self.convergence;
+        let Some(running) = &mut convergence.running else {
+            return;
+        };
+        let mut finished = None;
+        loop {
+            match running.receiver.try_recv() {
+                Ok(SolveUpdate::Progress(point)) => convergence.points.push(point),
+                Ok(SolveUpdate::Checkpoint(iteration)) => running.checkpoint = Some(iteration),
+                Ok(SolveUpdate::Pool(pool)) => running.pool = Some(pool),
+                Ok(update) => finished = Some(update),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    convergence.running = None;
+                    return;
+                }
+            }
+        }
+        let Some(update) = finished else {
+            ctx.request_repaint_after(Duration::from_millis(50));
+            return;
+        };
+        let Some(running) = convergence.running.take() else {
+            return;
+        };
+        let schedule = match update {
+            SolveUpdate::Finished(schedule) => {
+                convergence.saved = Checkpoint::default_path().and_then(|path| Checkpoint::load(&path).ok().flatten());
+                schedule
+            }
+            SolveUpdate::Paused(checkpoint) => {
+                let status = format!(
+                    "Paused at iteration {} of {}; showing the best schedule so far. Resume Previous Run continues the run.",
+                    checkpoint.iteration, checkpoint.iterations
+                );
+                let best = checkpoint.best.clone();
+                convergence.saved = Some(checkpoint);
+                let run = self.run_metadata(&self.convergence.solver_name, running.seed, 1, running.started.elapsed());
+                self.finish_solve(best, run);
+                self.edit_status = Some(Ok(status));
+                return;
+            }
+            SolveUpdate::Failed(e) => {
+                self.edit_status = Some(Err(e));
+                return;
+            }
+            SolveUpdate::Progress(_) | SolveUpdate::Checkpoint(_) | SolveUpdate::Pool(_) => return,
+        };
+        let runtime = running.started.elapsed();
+        let mut run = self.run_metadata(&self.convergence.solver_name, running.seed, 1, runtime);
+        if running.warm_start {
+            run.parameters["warm_start"] = serde_json::json!(true);
+        }
+        self.finish_solve(schedule, run);
+        if let Some(pool) = running.pool {
+            self.set_solution_pool(pool, self.convergence.objective);
+        }
+        #[cfg(feature = "experiment-log")]
+        self.record_solve(&self.convergence.solver_name.clone(), Some(running.seed), false, runtime);
+    }
+
+    /// Pause while a resumable run is going, or resume the saved run if it solves the
+    /// loaded problem
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn render_resume_controls(&mut self, ui: &mut egui::Ui) {
+        if let Some(running) = &self.convergence.running {
+            let pausing = running.pause.load(Ordering::Relaxed);
+            if ui.add_enabled(
+                running.checkpoint.is_some() && !pausing,
+                egui::Button::new("⏸ Pause").min_size(egui::vec2(100.0, 32.0)),
+            )
+                .on_hover_text("Stop at the next checkpoint and keep it, to resume the run later")
+                .on_disabled_hover_text("Only runs that take checkpoints (Simulated Annealing) can be paused")
+                .clicked()
+            {
+                running.pause.store(true, Ordering::Relaxed);
+            }
+            return;
+        }
+        let Some(checkpoint) = &self.convergence.saved else {
+            return;
+        };
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        if checkpoint.check(&checkpoint.algorithm, solver).is_err() {
+            return;
+        }
+        let hover = format!("{} ({:.0}% done), saved {}", checkpoint.summary(), checkpoint.progress() * 100.0, checkpoint.saved_at);
+        if ui.add_sized([180.0, 32.0], egui::Button::new("▶ Resume Previous Run")).on_hover_text(hover).clicked() {
+            self.resume_solve(checkpoint.clone());
+        }
+    }
+
+    /// The convergence plot, in a panel below the Gantt chart
+    pub(super) fn render_convergence_panel(&self, ui: &mut egui::Ui) {
+        let convergence = &self.convergence;
+        ui.horizontal(|ui| {
+            ui.strong(format!("Convergence: {}", convergence.solver_name));
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(running) = &convergence.running {
+                ui.spinner();
+                if running.pause.load(Ordering::Relaxed) {
+                    ui.label("Pausing at the next checkpoint…");
+                } else {
+                    ui.label(format!("Solving… {:.1} s", running.started.elapsed().as_secs_f64()));
+                }
+                if let Some(iteration) = running.checkpoint {
+                    ui.label(format!("(checkpoint at iteration {})", iteration));
+                }
+            }
+            if let Some(last) = convergence.points.last() {
+                // Improvements are reported at the iteration they are found
+                let improved = convergence.points.iter().find(|point| point.best <= last.best).map_or(0, |point| point.iteration);
+                ui.label(format!(
+                    "best {} {:.2} after {} iterations, last improved at iteration {}",
+                    convergence.objective.name(), last.best, last.iteration, improved
+                ));
+            }
+        });
+        if convergence.points.is_empty() {
+            return;
+        }
+        // A step line: the best value holds until the next improvement
+        let mut previous = convergence.points[0].best;
+        let points: PlotPoints = convergence.points.iter()
+            .flat_map(|point| {
+                let x = point.iteration as f64;
+                [[x, std::mem::replace(&mut previous, point.best)], [x, point.best]]
+            })
+            .collect();
+        Plot::new("convergence_plot")
+            .legend(Legend::default())
+            .x_axis_label("Iteration")
+            .y_axis_label(convergence.objective.name())
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(points).name("Best so far"));
+            });
+    }
+}
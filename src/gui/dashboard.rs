@@ -0,0 +1,177 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::{JsspSolver, ScheduledOperation};
+
+/// Number of past solutions kept for the sparklines
+const KPI_HISTORY_LEN: usize = 20;
+
+const TILE_WIDTH: f32 = 220.0;
+const TILE_MARGIN: f32 = 16.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AppTab {
+    Schedule,
+    Dashboard,
+    Instance,
+    /// Past solves from the experiment log
+    #[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+    History,
+}
+
+/// Headline figures of one solution
+#[derive(Debug, Clone, Copy)]
+pub(super) struct KpiSnapshot {
+    makespan: f64,
+    utilization: f64,
+    total_tardiness: f64,
+    total_setup: f64,
+    late_jobs: f64,
+}
+
+impl KpiSnapshot {
+    fn of(solver: &JsspSolver, schedule: &[ScheduledOperation]) -> Self {
+        Self {
+            makespan: solver.calculate_makespan(schedule),
+            utilization: solver.calculate_average_utilization(schedule),
+            total_tardiness: solver.calculate_total_tardiness(schedule),
+            total_setup: solver.calculate_total_setup(schedule),
+            late_jobs: solver.count_late_jobs(schedule) as f64,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum KpiFormat {
+    Number,
+    Percent,
+    Count,
+}
+
+impl KpiFormat {
+    fn value(self, value: f64) -> String {
+        match self {
+            KpiFormat::Number => format!("{:.2}", value),
+            KpiFormat::Percent => format!("{:.1}%", value * 100.0),
+            KpiFormat::Count => format!("{:.0}", value),
+        }
+    }
+
+    fn delta(self, delta: f64) -> String {
+        match self {
+            KpiFormat::Number => format!("{:+.2}", delta),
+            KpiFormat::Percent => format!("{:+.1} pp", delta * 100.0),
+            KpiFormat::Count => format!("{:+.0}", delta),
+        }
+    }
+}
+
+/// A KPI tile: title, value accessor, format and whether higher is better
+type Tile = (&'static str, fn(&KpiSnapshot) -> f64, KpiFormat, bool);
+
+const TILES: [Tile; 5] = [
+    ("Makespan", |k| k.makespan, KpiFormat::Number, false),
+    ("Avg Utilization", |k| k.utilization, KpiFormat::Percent, true),
+    ("Total Tardiness", |k| k.total_tardiness, KpiFormat::Number, false),
+    ("Total Setup", |k| k.total_setup, KpiFormat::Number, false),
+    ("Late Jobs", |k| k.late_jobs, KpiFormat::Count, false),
+];
+
+impl JsspApp {
+    /// Remember the KPIs of the current schedule for the dashboard trends
+    pub(super) fn record_kpis(&mut self) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        if self.schedule.is_empty() {
+            return;
+        }
+        self.kpi_history.push(KpiSnapshot::of(solver, &self.schedule));
+        if self.kpi_history.len() > KPI_HISTORY_LEN {
+            self.kpi_history.remove(0);
+        }
+    }
+
+    pub(super) fn render_dashboard(&self, ui: &mut egui::Ui) {
+        let Some(current) = self.kpi_history.last() else {
+            ui.colored_label(egui::Color32::GRAY, "Solve a problem to see its KPIs here.");
+            return;
+        };
+        let previous = self.kpi_history.len().checked_sub(2).map(|i| &self.kpi_history[i]);
+
+        if !self.violations.is_empty() {
+            ui.colored_label(egui::Color32::RED, format!("✗ Current schedule is infeasible ({} violation(s))", self.violations.len()));
+        }
+        ui.label(format!("Trends over the last {} solution(s)", self.kpi_history.len()));
+        ui.add_space(10.0);
+
+        // Lay tiles out in as many columns as fit, so the dashboard works on any screen
+        let tile_width = TILE_WIDTH + 2.0 * TILE_MARGIN;
+        let spacing = ui.spacing().item_spacing.x;
+        let columns = (((ui.available_width() + spacing) / (tile_width + spacing)).floor() as usize).max(1);
+
+        for row in TILES.chunks(columns) {
+            ui.horizontal(|ui| {
+                for &(title, value_of, format, higher_is_better) in row {
+                    let value = value_of(current);
+
+                    egui::Frame::group(ui.style())
+                        .inner_margin(egui::Margin::same(TILE_MARGIN))
+                        .show(ui, |ui| {
+                            ui.set_width(TILE_WIDTH);
+                            ui.vertical(|ui| {
+                                ui.label(egui::RichText::new(title).size(18.0).color(egui::Color32::GRAY));
+                                ui.label(egui::RichText::new(format.value(value)).size(44.0).strong());
+
+                                match previous.map(value_of) {
+                                    Some(before) if (value - before).abs() > 1e-9 => {
+                                        let improved = (value > before) == higher_is_better;
+                                        let color = if improved {
+                                            egui::Color32::LIGHT_GREEN
+                                        } else {
+                                            egui::Color32::LIGHT_RED
+                                        };
+                                        let arrow = if value > before { "⏶" } else { "⏷" };
+                                        ui.colored_label(color, format!("{} {} vs previous", arrow, format.delta(value - before)));
+                                    }
+                                    Some(_) => {
+                                        ui.colored_label(egui::Color32::GRAY, "= unchanged vs previous");
+                                    }
+                                    None => {
+                                        ui.colored_label(egui::Color32::GRAY, "no previous solution");
+                                    }
+                                }
+
+                                let values: Vec<f64> = self.kpi_history.iter().map(value_of).collect();
+                                Self::sparkline(ui, &values);
+                            });
+                        });
+                }
+            });
+        }
+    }
+
+    /// Small line chart of a KPI across past solutions
+    fn sparkline(ui: &mut egui::Ui, values: &[f64]) {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(200.0, 40.0), egui::Sense::hover());
+        if values.len() < 2 {
+            return;
+        }
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = if max - min > 1e-9 { max - min } else { 1.0 };
+
+        let points: Vec<egui::Pos2> = values.iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let x = rect.left() + rect.width() * i as f32 / (values.len() - 1) as f32;
+                let y = rect.bottom() - rect.height() * ((value - min) / range) as f32;
+                egui::pos2(x, y)
+            })
+            .collect();
+        let last = *points.last().unwrap();
+        let color = egui::Color32::from_rgb(70, 130, 180);
+        ui.painter().add(egui::Shape::line(points, egui::Stroke::new(2.0, color)));
+        ui.painter().circle_filled(last, 3.5, color);
+    }
+}
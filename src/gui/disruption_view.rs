@@ -0,0 +1,152 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::repair::Disruption;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DisruptionKind {
+    Breakdown,
+    Overrun,
+}
+
+/// Inputs of the "Disruption" window
+pub(super) struct DisruptionForm {
+    kind: DisruptionKind,
+    machine_id: usize,
+    start: f64,
+    end: f64,
+    job_id: usize,
+    operation_id: usize,
+    extra: f64,
+    error: Option<String>,
+}
+
+impl Default for DisruptionForm {
+    fn default() -> Self {
+        Self { kind: DisruptionKind::Breakdown, machine_id: 0, start: 0.0, end: 10.0, job_id: 0, operation_id: 0, extra: 5.0, error: None }
+    }
+}
+
+impl DisruptionForm {
+    fn disruption(&self) -> Disruption {
+        match self.kind {
+            DisruptionKind::Breakdown => Disruption::Breakdown { machine_id: self.machine_id, start: self.start, end: self.end },
+            DisruptionKind::Overrun => Disruption::Overrun { job_id: self.job_id, operation_id: self.operation_id, extra: self.extra },
+        }
+    }
+}
+
+impl JsspApp {
+    /// Add the disruption to the problem and right-shift the schedule around it
+    fn apply_disruption(&mut self) {
+        let disruption = self.disruption_form.disruption();
+        let Some(mut instance) = self.solver.as_ref().map(|solver| solver.instance().clone()) else {
+            return;
+        };
+        match instance.right_shift_repair(&self.schedule, &disruption) {
+            Ok(repair) => {
+                self.remember("Disruption");
+                if let Some(solver) = &mut self.solver {
+                    **solver = instance;
+                }
+                self.edit_status = None;
+                self.apply_edited_schedule(repair.schedule.clone());
+                self.disruption_form.error = None;
+                self.disruption_repair = Some(repair);
+            }
+            Err(e) => self.disruption_form.error = Some(e),
+        }
+    }
+
+    pub(super) fn render_disruption_window(&mut self, ctx: &egui::Context) {
+        let num_machines = self.instance_machines();
+        let operations_of = |job_id: usize| {
+            self.solver.as_ref().and_then(|solver| solver.jobs().get(job_id)).map_or(0, |job| job.operations.len())
+        };
+        let num_jobs = self.solver.as_ref().map_or(0, |solver| solver.jobs().len());
+        let num_operations = operations_of(self.disruption_form.job_id);
+
+        let mut open = self.show_disruption_window;
+        let mut apply = false;
+        egui::Window::new("Disruption")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label("Record a machine breakdown or an operation that overran. The schedule is repaired by \
+                          right-shifting: machine orders stay as they are and nothing starts earlier than planned.");
+
+                let form = &mut self.disruption_form;
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut form.kind, DisruptionKind::Breakdown, "Machine breakdown");
+                    ui.selectable_value(&mut form.kind, DisruptionKind::Overrun, "Operation overrun");
+                });
+                ui.horizontal(|ui| match form.kind {
+                    DisruptionKind::Breakdown => {
+                        ui.label("Machine:");
+                        ui.add(egui::DragValue::new(&mut form.machine_id).range(0..=num_machines.saturating_sub(1)));
+                        ui.label("from");
+                        ui.add(egui::DragValue::new(&mut form.start).range(0.0..=f64::MAX).speed(1.0));
+                        ui.label("to");
+                        ui.add(egui::DragValue::new(&mut form.end).range(form.start..=f64::MAX).speed(1.0));
+                    }
+                    DisruptionKind::Overrun => {
+                        ui.label("Job:");
+                        ui.add(egui::DragValue::new(&mut form.job_id).range(0..=num_jobs.saturating_sub(1)));
+                        ui.label("Op:");
+                        ui.add(egui::DragValue::new(&mut form.operation_id).range(0..=num_operations.saturating_sub(1)));
+                        ui.label("takes");
+                        ui.add(egui::DragValue::new(&mut form.extra).range(0.0..=f64::MAX).speed(1.0));
+                        ui.label("longer");
+                    }
+                });
+
+                let can_apply = self.solver.is_some() && !self.schedule.is_empty();
+                if ui.add_enabled(can_apply, egui::Button::new("Apply and Repair"))
+                    .on_hover_text("Adds the disruption to the problem; Undo takes it back")
+                    .clicked()
+                {
+                    apply = true;
+                }
+                if let Some(error) = &self.disruption_form.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                let Some(repair) = &self.disruption_repair else {
+                    return;
+                };
+                ui.separator();
+                let time = self.time_mode();
+                ui.label(format!(
+                    "Makespan: {} → {} (+{})",
+                    time.format(repair.makespan_before),
+                    time.format(repair.makespan_after),
+                    time.format(repair.makespan_after - repair.makespan_before)
+                ));
+                if repair.slipped.is_empty() {
+                    ui.label("No job finishes later than planned.");
+                    return;
+                }
+                ui.label(format!("{} of {} jobs slipped:", repair.slipped.len(), num_jobs));
+                egui::ScrollArea::vertical().id_salt("slipped_jobs").max_height(200.0).show(ui, |ui| {
+                    egui::Grid::new("slipped_jobs_grid").striped(true).num_columns(4).show(ui, |ui| {
+                        ui.strong("Job");
+                        ui.strong("Planned");
+                        ui.strong("Repaired");
+                        ui.strong("Delay");
+                        ui.end_row();
+                        for slip in &repair.slipped {
+                            ui.label(self.job_name(slip.job_id));
+                            ui.label(time.format(slip.planned_completion));
+                            ui.label(time.format(slip.repaired_completion));
+                            ui.colored_label(egui::Color32::from_rgb(200, 80, 40), format!("+{}", time.format(slip.delay())));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_disruption_window = open;
+        if apply {
+            self.apply_disruption();
+        }
+    }
+}
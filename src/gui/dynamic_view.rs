@@ -0,0 +1,244 @@
+use eframe::egui;
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+use super::playback::Playback;
+use super::JsspApp;
+use crate::jssp::dynamic::{DispatchRule, DynamicRun, RollingStats};
+use crate::jssp::priority::PriorityExpression;
+use crate::jssp::{generate_instance, GeneratorConfig, Instance};
+
+/// Settings and results of the "Dynamic Arrivals" window: one stream of arriving jobs,
+/// run with every dispatching rule, including a custom one
+pub(super) struct DynamicLab {
+    num_jobs: usize,
+    num_machines: usize,
+    mean_interarrival: f64,
+    min_duration: f64,
+    max_duration: f64,
+    rule: DispatchRule,
+    /// Completed jobs the rolling mean flow time is taken over
+    window: usize,
+    /// The custom rule as typed, and why it does not parse
+    custom_source: String,
+    custom_error: Option<String>,
+    custom: Option<PriorityExpression>,
+    instance: Option<Instance>,
+    /// One run per rule, in the order of `DispatchRule::ALL`, then the custom rule
+    runs: Vec<DynamicRun>,
+}
+
+impl Default for DynamicLab {
+    fn default() -> Self {
+        Self {
+            num_jobs: 50,
+            num_machines: 4,
+            mean_interarrival: 7.0,
+            min_duration: 1.0,
+            max_duration: 10.0,
+            rule: DispatchRule::ShortestProcessingTime,
+            window: 10,
+            custom_source: "duration - 0.1 * waiting".to_string(),
+            custom_error: None,
+            custom: None,
+            instance: None,
+            runs: Vec::new(),
+        }
+    }
+}
+
+impl DynamicLab {
+    /// Expected share of time every machine is busy, as each job visits every machine
+    /// once: above 1 the queues grow without bound
+    fn offered_load(&self) -> f64 {
+        (self.min_duration + self.max_duration) / 2.0 / self.mean_interarrival
+    }
+
+    fn generate(&mut self) {
+        let config = GeneratorConfig {
+            num_jobs: self.num_jobs,
+            num_machines: self.num_machines,
+            min_duration: self.min_duration,
+            max_duration: self.max_duration,
+            mean_interarrival: Some(self.mean_interarrival),
+            ..GeneratorConfig::default()
+        };
+        let instance = generate_instance(&config)
+            .with_name(format!("arrivals {}x{}", self.num_jobs, self.num_machines));
+        self.runs = self.rules().map(|rule| instance.dispatch(rule)).collect();
+        self.instance = Some(instance);
+    }
+
+    fn rules(&self) -> impl Iterator<Item = DispatchRule> {
+        DispatchRule::ALL.into_iter().chain(self.custom.clone().map(DispatchRule::Custom))
+    }
+
+    /// Parse the custom rule, select it and dispatch the current stream with it
+    fn apply_custom(&mut self) {
+        let expression = match PriorityExpression::parse(&self.custom_source) {
+            Ok(expression) => expression,
+            Err(e) => {
+                self.custom_error = Some(e);
+                return;
+            }
+        };
+        self.custom_error = None;
+        self.custom = Some(expression.clone());
+        self.rule = DispatchRule::Custom(expression);
+        self.runs.retain(|run| !matches!(run.rule, DispatchRule::Custom(_)));
+        if let Some(instance) = &self.instance {
+            self.runs.push(instance.dispatch(self.rule.clone()));
+        }
+    }
+
+    fn run(&self) -> Option<&DynamicRun> {
+        self.runs.iter().find(|run| run.rule == self.rule)
+    }
+}
+
+impl JsspApp {
+    pub(super) fn render_dynamic_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_dynamic_window;
+        let mut animate = false;
+        egui::Window::new("Dynamic Arrivals")
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                let lab = &mut self.dynamic_lab;
+                ui.label("Jobs arrive over time (Poisson arrivals) and are dispatched on the fly: an idle machine starts the waiting operation the rule ranks first, without knowing future arrivals.");
+
+                ui.horizontal(|ui| {
+                    ui.label("Jobs:");
+                    ui.add(egui::DragValue::new(&mut lab.num_jobs).range(1..=2000));
+                    ui.label("Machines:");
+                    ui.add(egui::DragValue::new(&mut lab.num_machines).range(1..=50));
+                    ui.label("Mean time between arrivals:");
+                    ui.add(egui::DragValue::new(&mut lab.mean_interarrival).range(0.1..=1000.0).speed(0.1));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Durations:");
+                    ui.add(egui::DragValue::new(&mut lab.min_duration).range(1.0..=1000.0).speed(0.5));
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(&mut lab.max_duration).range(lab.min_duration..=1000.0).speed(0.5));
+                    ui.separator();
+                    let load = lab.offered_load();
+                    let color = if load >= 1.0 { egui::Color32::LIGHT_RED } else { ui.visuals().text_color() };
+                    ui.colored_label(color, format!("Load ≈ {:.0}% per machine", load * 100.0))
+                        .on_hover_text("Mean duration over mean time between arrivals; at 100% or more the queues keep growing");
+                });
+                ui.horizontal(|ui| {
+                    if ui.add_sized([160.0, 32.0], egui::Button::new("Generate & Run"))
+                        .on_hover_text("Draw a new stream of arriving jobs and dispatch it with every rule")
+                        .clicked()
+                    {
+                        lab.generate();
+                    }
+                    ui.label("Rule:");
+                    egui::ComboBox::from_id_salt("dispatch_rule")
+                        .selected_text(lab.rule.name())
+                        .show_ui(ui, |ui| {
+                            for rule in lab.rules().collect::<Vec<_>>() {
+                                let name = rule.name().to_string();
+                                let description = rule.description();
+                                ui.selectable_value(&mut lab.rule, rule, name).on_hover_text(description);
+                            }
+                        });
+                    ui.label("Rolling window:");
+                    ui.add(egui::DragValue::new(&mut lab.window).range(1..=500).suffix(" jobs"));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Custom rule:");
+                    let variables: Vec<String> = PriorityExpression::VARIABLES.iter()
+                        .map(|(name, meaning)| format!("{}: {}", name, meaning))
+                        .collect();
+                    let edit = ui.add(egui::TextEdit::singleline(&mut lab.custom_source).desired_width(280.0).code_editor())
+                        .on_hover_text(format!(
+                            "The waiting operation with the lowest value goes first. Use + - * / ^, parentheses, min, max, abs, sqrt and\n{}",
+                            variables.join("\n")
+                        ));
+                    let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.button("Use").clicked() || entered {
+                        lab.apply_custom();
+                    }
+                });
+                if let Some(error) = &lab.custom_error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                ui.separator();
+                let Some(run) = lab.run() else {
+                    ui.colored_label(egui::Color32::GRAY, "Generate a stream of arrivals to compare the dispatching rules.");
+                    return;
+                };
+                ui.label(format!(
+                    "{}: makespan {:.2}  |  mean flow time {:.2}  |  max flow time {:.2}",
+                    run.rule.name(), run.makespan(), run.mean_flow_time(), run.max_flow_time()
+                ));
+
+                let rolling = run.rolling(lab.window);
+                let points = |value: fn(&RollingStats) -> f64| -> PlotPoints {
+                    rolling.iter().map(|stats| [stats.time, value(stats)]).collect()
+                };
+                let link = ui.id().with("dynamic_plots");
+                Plot::new("dynamic_flow_time")
+                    .height(160.0)
+                    .legend(Legend::default())
+                    .y_axis_label("Flow time")
+                    .link_axis(link, [true, false])
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(points(|s| s.mean_flow_time)).name(format!("Last {} jobs", lab.window)));
+                        plot_ui.line(Line::new(points(|s| s.cumulative_mean_flow_time)).name("All jobs so far"));
+                    });
+                Plot::new("dynamic_in_system")
+                    .height(120.0)
+                    .legend(Legend::default())
+                    .x_axis_label("Time (units)")
+                    .y_axis_label("Jobs")
+                    .link_axis(link, [true, false])
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(points(|s| s.in_system as f64)).name("In system"));
+                    });
+
+                egui::Grid::new("dynamic_rules_grid").striped(true).show(ui, |ui| {
+                    for header in ["Rule", "Mean Flow Time", "Max Flow Time", "Makespan"] {
+                        ui.strong(header);
+                    }
+                    ui.end_row();
+                    let best = lab.runs.iter().map(DynamicRun::mean_flow_time).fold(f64::INFINITY, f64::min);
+                    for run in &lab.runs {
+                        ui.label(run.rule.name()).on_hover_text(run.rule.description());
+                        let mean = run.mean_flow_time();
+                        if mean <= best {
+                            ui.colored_label(egui::Color32::GREEN, format!("{:.2}", mean));
+                        } else {
+                            ui.label(format!("{:.2}", mean));
+                        }
+                        ui.label(format!("{:.2}", run.max_flow_time()));
+                        ui.label(format!("{:.2}", run.makespan()));
+                        ui.end_row();
+                    }
+                });
+
+                if ui.add_sized([180.0, 32.0], egui::Button::new("▶ Animate in Gantt"))
+                    .on_hover_text("Load the stream and this rule's schedule and play it back, hiding jobs until they arrive")
+                    .clicked()
+                {
+                    animate = true;
+                }
+            });
+        self.show_dynamic_window = open;
+
+        if animate {
+            let lab = &self.dynamic_lab;
+            if let (Some(instance), Some(run)) = (lab.instance.clone(), lab.run()) {
+                let schedule = run.schedule.clone();
+                self.remember("Dynamic Arrivals");
+                self.replace_problem(Some(instance.into()));
+                self.apply_edited_schedule(schedule);
+                self.playback = Playback { enabled: true, playing: true, hide_unreleased: true, ..Playback::default() };
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use super::dashboard::AppTab;
+use super::JsspApp;
+use crate::jssp::experiments::{instance_hash, ExperimentLog, Run, RunSummary};
+use crate::jssp::JsspError;
+
+/// The experiment log behind the History tab, opened on first use
+pub(super) struct ExperimentsState {
+    /// Record every solve
+    pub record: bool,
+    log: Option<Result<ExperimentLog, String>>,
+    /// Runs as last read from the log; `None` until the tab needs them again
+    runs: Option<Vec<RunSummary>>,
+    filter: String,
+    /// Only list runs on the loaded instance
+    current_instance_only: bool,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for ExperimentsState {
+    fn default() -> Self {
+        Self { record: true, log: None, runs: None, filter: String::new(), current_instance_only: false, status: None }
+    }
+}
+
+/// Where the log is kept: next to the app's other saved state
+fn log_path() -> Option<PathBuf> {
+    eframe::storage_dir("JSSP Scheduler").map(|dir| dir.join("experiments.sqlite"))
+}
+
+impl ExperimentsState {
+    /// Add a solve to the log, if recording is on
+    pub fn record(&mut self, run: &Run) -> Result<(), JsspError> {
+        if !self.record {
+            return Ok(());
+        }
+        self.runs = None;
+        self.log()
+            .and_then(|log| log.record(run))
+            .map(|_| ())
+            .map_err(|e| JsspError::Serialization(format!("Failed to record the run: {}", e)))
+    }
+
+    fn log(&mut self) -> Result<&ExperimentLog, String> {
+        self.log
+            .get_or_insert_with(|| ExperimentLog::open(&log_path().ok_or("No folder to keep the experiment log in")?))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+}
+
+impl JsspApp {
+    pub(super) fn render_experiments_tab(&mut self, ui: &mut egui::Ui) {
+        let state = &mut self.experiments;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut state.record, "Record every solve")
+                .on_hover_text("Keep algorithm, parameters, seed, results, instance and schedule of each solve");
+            if ui.button("Refresh").clicked() {
+                state.runs = None;
+            }
+            if let Some(path) = log_path() {
+                ui.weak(path.display().to_string());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(egui::TextEdit::singleline(&mut state.filter).hint_text("instance, algorithm or hash").desired_width(240.0));
+            ui.add_enabled(
+                self.solver.is_some(),
+                egui::Checkbox::new(&mut state.current_instance_only, "Loaded instance only"),
+            );
+        });
+
+        if state.runs.is_none() {
+            match state.log().and_then(|log| log.runs()) {
+                Ok(runs) => state.runs = Some(runs),
+                Err(e) => {
+                    ui.colored_label(egui::Color32::LIGHT_RED, e);
+                    return;
+                }
+            }
+        }
+        match &state.status {
+            Some(Ok(message)) => {
+                ui.colored_label(egui::Color32::GREEN, message);
+            }
+            Some(Err(message)) => {
+                ui.colored_label(egui::Color32::LIGHT_RED, message);
+            }
+            None => {}
+        }
+
+        let current_hash = self.solver.as_ref()
+            .filter(|_| state.current_instance_only)
+            .map(|solver| instance_hash(solver));
+        let filter = state.filter.trim().to_lowercase();
+        let runs: Vec<&RunSummary> = state.runs.iter().flatten()
+            .filter(|run| current_hash.as_ref().is_none_or(|hash| *hash == run.instance_hash))
+            .filter(|run| {
+                filter.is_empty()
+                    || [&run.instance_name, &run.algorithm, &run.instance_hash].iter().any(|field| field.to_lowercase().contains(&filter))
+            })
+            .collect();
+        ui.label(format!("{} run(s)", runs.len()));
+        ui.separator();
+
+        let mut load = None;
+        let mut delete = None;
+        egui::ScrollArea::both().id_salt("experiment_runs").show(ui, |ui| {
+            egui::Grid::new("experiment_runs_grid").striped(true).show(ui, |ui| {
+                for header in ["When", "Instance", "Algorithm", "Seed", "Objective", "Value", "Makespan", "Runtime", "", ""] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for run in runs {
+                    ui.label(&run.recorded_at);
+                    let name = if run.instance_name.is_empty() { "(unnamed)" } else { run.instance_name.as_str() };
+                    ui.label(name).on_hover_text(format!("Instance hash {}", run.instance_hash));
+                    ui.label(&run.algorithm).on_hover_text(format!("Parameters: {}", run.parameters));
+                    ui.label(run.seed.map_or_else(|| "-".to_string(), |seed| seed.to_string()));
+                    ui.label(&run.objective);
+                    ui.label(format!("{:.2}", run.objective_value));
+                    if run.feasible {
+                        ui.label(format!("{:.2}", run.makespan));
+                    } else {
+                        ui.colored_label(egui::Color32::LIGHT_RED, format!("{:.2}", run.makespan))
+                            .on_hover_text("The schedule was infeasible");
+                    }
+                    ui.label(format!("{:.1} ms", run.runtime_ms));
+                    if ui.small_button("Load").on_hover_text("Open this run's instance and schedule").clicked() {
+                        load = Some(run.id);
+                    }
+                    if ui.small_button("Delete").clicked() {
+                        delete = Some(run.id);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        if let Some(id) = delete {
+            let state = &mut self.experiments;
+            state.status = Some(state.log().and_then(|log| log.delete(id)).map(|()| format!("Deleted run {}", id)));
+            state.runs = None;
+        }
+        if let Some(id) = load {
+            match self.experiments.log().and_then(|log| log.load(id)) {
+                Ok((instance, schedule)) => {
+                    self.remember("Load Run");
+                    self.replace_problem(Some(instance.into()));
+                    self.apply_edited_schedule(schedule);
+                    self.experiments.status = None;
+                    self.tab = AppTab::Schedule;
+                }
+                Err(e) => self.experiments.status = Some(Err(e)),
+            }
+        }
+    }
+}
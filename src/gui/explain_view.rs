@@ -0,0 +1,229 @@
+use eframe::egui;
+use egui_plot::PlotUi;
+
+use super::gantt_edit::{GanttRows, BLOCK_HEIGHT};
+use super::JsspApp;
+use crate::jssp::explain::{ExplainedAlgorithm, Explanation};
+
+/// A constructive run being stepped through; the schedule built up to the selected
+/// step is drawn in the Gantt chart, with the other candidates of the step outlined
+pub(super) struct ExplainViewer {
+    algorithm: ExplainedAlgorithm,
+    explanation: Option<Explanation>,
+    /// Step selected with the controls
+    step: usize,
+    /// The step last put into the Gantt chart
+    shown: Option<usize>,
+    playing: bool,
+    /// Steps advanced per second while playing
+    speed: f64,
+    /// Fraction of a step accumulated between frames while playing
+    carry: f64,
+    error: Option<String>,
+}
+
+impl Default for ExplainViewer {
+    fn default() -> Self {
+        Self {
+            algorithm: ExplainedAlgorithm::GifflerThompson,
+            explanation: None,
+            step: 0,
+            shown: None,
+            playing: false,
+            speed: 2.0,
+            carry: 0.0,
+            error: None,
+        }
+    }
+}
+
+impl JsspApp {
+    fn run_explained(&mut self) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        match solver.explain(self.explain_viewer.algorithm) {
+            Ok(explanation) => {
+                self.remember("Explain Mode");
+                self.explain_viewer = ExplainViewer {
+                    algorithm: self.explain_viewer.algorithm,
+                    explanation: Some(explanation),
+                    speed: self.explain_viewer.speed,
+                    ..ExplainViewer::default()
+                };
+            }
+            Err(e) => self.explain_viewer.error = Some(e),
+        }
+    }
+
+    /// Put the schedule built up to the selected step into the Gantt chart, selecting
+    /// the operation placed in it
+    fn show_explain_step(&mut self) {
+        let (Some(solver), Some(explanation)) = (&self.solver, &self.explain_viewer.explanation) else {
+            return;
+        };
+        let step = self.explain_viewer.step;
+        if self.explain_viewer.shown == Some(step) {
+            return;
+        }
+        let schedule = explanation.schedule_after(step).to_vec();
+        let complete = schedule.len() == explanation.schedule.len();
+        let chosen = explanation.steps[step].chosen();
+        self.selection.operation = Some((chosen.job_id, chosen.operation_id));
+        self.makespan = solver.calculate_makespan(&schedule);
+        // A partial schedule misses operations by design
+        self.violations = if complete { solver.validate(&schedule).err().unwrap_or_default() } else { Vec::new() };
+        self.schedule = schedule;
+        self.run = None;
+        self.swap_suggestions = None;
+        self.explain_viewer.shown = Some(step);
+    }
+
+    /// Outline where the candidates of the shown step would have gone: the competing
+    /// ones in amber, the others in gray
+    pub(super) fn draw_explain_candidates(&self, plot_ui: &mut PlotUi) {
+        let viewer = &self.explain_viewer;
+        if !self.show_explain_window {
+            return;
+        }
+        let Some(step) = viewer.shown.and_then(|step| viewer.explanation.as_ref()?.steps.get(step)) else {
+            return;
+        };
+        for (index, candidate) in step.candidates.iter().enumerate() {
+            if index == step.chosen {
+                continue;
+            }
+            let row = match self.gantt_rows {
+                GanttRows::Machines => candidate.machine_id,
+                GanttRows::Jobs => candidate.job_id,
+            } as f64;
+            let color = if candidate.competing { egui::Color32::from_rgb(255, 180, 0) } else { egui::Color32::GRAY };
+            plot_ui.polygon(
+                egui_plot::Polygon::new(vec![
+                    [candidate.start, row - BLOCK_HEIGHT / 2.0],
+                    [candidate.end, row - BLOCK_HEIGHT / 2.0],
+                    [candidate.end, row + BLOCK_HEIGHT / 2.0],
+                    [candidate.start, row + BLOCK_HEIGHT / 2.0],
+                ])
+                .fill_color(color.gamma_multiply(0.12))
+                .stroke(egui::Stroke::new(1.5, color))
+                .allow_hover(false)
+            );
+        }
+    }
+
+    pub(super) fn render_explain_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_explain_window;
+        let mut run = false;
+        let time = self.time_mode();
+        egui::Window::new("Explain Mode")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.label("Build a schedule one operation at a time and see, at every step, which operations competed \
+                          and why the placed one won. The Gantt chart shows the schedule so far, the placed operation \
+                          selected and the other candidates outlined (amber: in the running, gray: not considered).");
+                ui.horizontal(|ui| {
+                    for algorithm in ExplainedAlgorithm::ALL {
+                        ui.selectable_value(&mut self.explain_viewer.algorithm, algorithm, algorithm.name())
+                            .on_hover_text(algorithm.description());
+                    }
+                    if ui.add_enabled(self.solver.is_some(), egui::Button::new("Run Step by Step")).clicked() {
+                        run = true;
+                    }
+                });
+                if let Some(error) = &self.explain_viewer.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                let viewer = &mut self.explain_viewer;
+                let Some(explanation) = &viewer.explanation else {
+                    return;
+                };
+                let Some(last) = explanation.steps.len().checked_sub(1) else {
+                    ui.label("The problem has no operations.");
+                    return;
+                };
+
+                if viewer.playing {
+                    viewer.carry += ui.input(|i| i.stable_dt).min(0.1) as f64 * viewer.speed;
+                    let advance = viewer.carry.floor();
+                    viewer.carry -= advance;
+                    viewer.step = (viewer.step + advance as usize).min(last);
+                    if viewer.step == last {
+                        viewer.playing = false;
+                    }
+                    ui.ctx().request_repaint();
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("⏮ Start").clicked() {
+                        viewer.step = 0;
+                    }
+                    if ui.button("Prev").clicked() {
+                        viewer.step = viewer.step.saturating_sub(1);
+                    }
+                    let label = if viewer.playing { "⏸ Pause" } else { "▶ Play" };
+                    if ui.button(label).clicked() {
+                        if !viewer.playing && viewer.step == last {
+                            viewer.step = 0;
+                        }
+                        viewer.playing = !viewer.playing;
+                    }
+                    if ui.button("Next").clicked() {
+                        viewer.step = (viewer.step + 1).min(last);
+                    }
+                    if ui.button("End ⏭").clicked() {
+                        viewer.step = last;
+                    }
+                    ui.label("Steps/s:");
+                    ui.add(egui::DragValue::new(&mut viewer.speed).range(0.5..=100.0).speed(0.5));
+                });
+                ui.spacing_mut().slider_width = (ui.available_width() - 120.0).max(100.0);
+                ui.add(egui::Slider::new(&mut viewer.step, 0..=last).text("step"));
+
+                let step = &explanation.steps[viewer.step];
+                ui.strong(format!("{}, step {} of {}", explanation.algorithm.name(), viewer.step + 1, last + 1));
+                ui.label(&step.reason);
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical().id_salt("explain_candidates").max_height(240.0).show(ui, |ui| {
+                    egui::Grid::new("explain_candidates_grid").striped(true).num_columns(7).show(ui, |ui| {
+                        for header in ["Operation", "Machine", "Job ready", "Machine ready", "Start", "End", "Starts when"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+                        for (index, candidate) in step.candidates.iter().enumerate() {
+                            let color = if index == step.chosen {
+                                egui::Color32::from_rgb(60, 170, 80)
+                            } else if candidate.competing {
+                                egui::Color32::from_rgb(220, 150, 0)
+                            } else {
+                                ui.visuals().weak_text_color()
+                            };
+                            let cells = [
+                                format!("J{} O{}", candidate.job_id, candidate.operation_id),
+                                format!("M{}", candidate.machine_id),
+                                time.format(candidate.job_ready),
+                                time.format(candidate.machine_ready),
+                                time.format(candidate.start),
+                                time.format(candidate.end),
+                                candidate.bound().description().to_string(),
+                            ];
+                            for cell in cells {
+                                ui.colored_label(color, cell);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_explain_window = open;
+        if run {
+            self.run_explained();
+        }
+        if self.show_explain_window {
+            self.show_explain_step();
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::external::ExternalSolver;
+use crate::jssp::SolverRegistry;
+
+/// Programs of the user's offered as algorithms, and the one being added or edited
+#[derive(Default)]
+pub(super) struct ExternalSolvers {
+    /// In the order added; each is also in the app's registry
+    pub configured: Vec<ExternalSolver>,
+    name: String,
+    command: String,
+    /// Seconds; 0 for no limit
+    time_limit: f64,
+    error: Option<String>,
+}
+
+impl JsspApp {
+    /// Offer `solvers` in the algorithm list in place of the configured ones, keeping the
+    /// selected algorithm selected
+    pub(super) fn set_external_solvers(&mut self, solvers: Vec<ExternalSolver>) {
+        let selected = self.selected_solver_name();
+        for solver in &self.external_solvers.configured {
+            self.solvers.remove(&solver.name);
+        }
+        for solver in &solvers {
+            self.solvers.register(solver.clone());
+        }
+        self.external_solvers.configured = solvers;
+        self.selected_solver = self.solvers.solvers().position(|solver| solver.name() == selected).unwrap_or(0);
+    }
+
+    /// Add the solver of the form, or replace the one of the same name
+    fn save_external_solver(&mut self) {
+        let external = &mut self.external_solvers;
+        let name = external.name.trim().to_string();
+        let command = external.command.trim().to_string();
+        external.error = if name.is_empty() || command.is_empty() {
+            Some("Give the solver a name and a command.".to_string())
+        } else if SolverRegistry::default().get(&name).is_some() {
+            Some(format!("{} is a built-in algorithm; choose another name.", name))
+        } else {
+            None
+        };
+        if external.error.is_some() {
+            return;
+        }
+        let solver = ExternalSolver {
+            name,
+            command,
+            time_limit: (external.time_limit > 0.0).then(|| Duration::from_secs_f64(external.time_limit)),
+        };
+        let mut solvers = external.configured.clone();
+        match solvers.iter_mut().find(|configured| configured.name == solver.name) {
+            Some(configured) => *configured = solver,
+            None => solvers.push(solver),
+        }
+        self.set_external_solvers(solvers);
+    }
+
+    pub(super) fn render_external_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_external_window;
+        let mut edit = None;
+        let mut remove = None;
+        let mut solve = None;
+        let mut save = false;
+        egui::Window::new("External Solvers")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label("Run a program of your own as an algorithm. For every run the problem is written to a \
+                          temporary file in OR-Tools JSON, the command is run, and the schedule is read from the \
+                          solution file, or from its output, in any format Import Solution reads.");
+                ui.weak("Placeholders: {instance} the problem file (added at the end if missing), {solution} the \
+                         file to write the schedule to, {seed} the run's seed.");
+                ui.add_space(4.0);
+
+                let external = &mut self.external_solvers;
+                if external.configured.is_empty() {
+                    ui.label("No external solvers yet.");
+                } else {
+                    egui::Grid::new("external_solvers_grid").striped(true).num_columns(4).show(ui, |ui| {
+                        for header in ["Name", "Command", "Time limit", ""] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+                        for solver in &external.configured {
+                            ui.label(&solver.name);
+                            ui.monospace(&solver.command);
+                            ui.label(solver.time_limit.map_or_else(|| "None".to_string(), |limit| format!("{:.1} s", limit.as_secs_f64())));
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(self.solver.is_some(), egui::Button::new("Solve"))
+                                    .on_hover_text("Select it in the algorithm list and solve the problem with it")
+                                    .clicked()
+                                {
+                                    solve = Some(solver.name.clone());
+                                }
+                                if ui.button("Edit").clicked() {
+                                    edit = Some(solver.clone());
+                                }
+                                if ui.button("Remove").clicked() {
+                                    remove = Some(solver.name.clone());
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                }
+
+                ui.separator();
+                egui::Grid::new("external_solver_form").num_columns(2).show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(egui::TextEdit::singleline(&mut external.name).hint_text("My Solver").desired_width(200.0));
+                    ui.end_row();
+                    ui.label("Command:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut external.command)
+                            .hint_text("./my_solver {instance} {solution} --seed {seed}")
+                            .desired_width(400.0)
+                            .code_editor(),
+                    );
+                    ui.end_row();
+                    ui.label("Time limit (s):");
+                    ui.add(egui::DragValue::new(&mut external.time_limit).range(0.0..=86_400.0).speed(1.0))
+                        .on_hover_text("Stop the program and fail the run after this long; 0 for no limit");
+                    ui.end_row();
+                });
+                let exists = external.configured.iter().any(|solver| solver.name == external.name.trim());
+                if ui.button(if exists { "Save" } else { "Add" }).clicked() {
+                    save = true;
+                }
+                if let Some(error) = &external.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+        self.show_external_window = open;
+
+        if let Some(solver) = edit {
+            let external = &mut self.external_solvers;
+            external.time_limit = solver.time_limit.map_or(0.0, |limit| limit.as_secs_f64());
+            external.name = solver.name;
+            external.command = solver.command;
+            external.error = None;
+        }
+        if let Some(name) = remove {
+            let mut solvers = self.external_solvers.configured.clone();
+            solvers.retain(|solver| solver.name != name);
+            self.set_external_solvers(solvers);
+        }
+        if save {
+            self.save_external_solver();
+        }
+        let chosen = solve.and_then(|name| self.solvers.solvers().position(|solver| solver.name() == name));
+        if let Some(index) = chosen {
+            self.selected_solver = index;
+            self.solve_schedule();
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Opening and saving files. The desktop build uses native dialogs and the file system;
+//! in the browser, instances are picked with the browser's file chooser and exports are
+//! saved as downloads.
+
+use std::path::{Path, PathBuf};
+#[cfg(target_arch = "wasm32")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(target_arch = "wasm32")]
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::JsspError;
+
+/// A file picked in the browser (name and contents), read asynchronously and imported
+/// on the next frame
+#[cfg(target_arch = "wasm32")]
+pub(super) type PendingFile = Arc<Mutex<Option<(String, Vec<u8>)>>>;
+
+impl JsspApp {
+    /// Where to save an export: a path picked in a save dialog, or just `file_name` in
+    /// the browser, where `write_file` turns it into a download. `None` if cancelled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn save_path(&mut self, file_name: &str, filters: &[(&str, &[&str])]) -> Option<PathBuf> {
+        let mut dialog = self.export_dialog().set_file_name(file_name);
+        for (name, extensions) in filters {
+            dialog = dialog.add_filter(*name, extensions);
+        }
+        let path = dialog.save_file()?;
+        self.remember_export_dir(&path);
+        Some(path)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn save_path(&mut self, file_name: &str, _filters: &[(&str, &[&str])]) -> Option<PathBuf> {
+        Some(PathBuf::from(file_name))
+    }
+
+    /// Folder to save several exports into; in the browser each becomes its own download
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn save_folder(&mut self) -> Option<PathBuf> {
+        let dir = self.export_dialog().pick_folder()?;
+        self.remember_export_dir(&dir);
+        Some(dir)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn save_folder(&mut self) -> Option<PathBuf> {
+        Some(PathBuf::new())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn import_ortools_with_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.import_ortools_file(&path);
+    }
+
+    /// Open the browser's file chooser; the file is imported by `poll_pending_import`
+    /// once it has been read
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn import_ortools_with_dialog(&mut self) {
+        let pending = Arc::clone(&self.pending_import);
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Some(file) = rfd::AsyncFileDialog::new().add_filter("JSON", &["json"]).pick_file().await {
+                let bytes = file.read().await;
+                *pending.lock().unwrap() = Some((file.file_name(), bytes));
+            }
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn import_ortools_file(&mut self, path: &Path) {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                self.toasts.error(&JsspError::io("read", path)(e));
+                self.recent_instances.retain(|recent| recent != path);
+                return;
+            }
+        };
+        match self.import_ortools_text(&text, path) {
+            Ok(()) => self.add_recent_instance(path),
+            Err(e) => self.toasts.error(&e.context(path.display())),
+        }
+    }
+
+    /// Pick a solution of the loaded problem to show, in any format `read_solution` reads
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn import_solution_with_dialog(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Solution", &["json", "csv", "tsv", "txt"])
+            .pick_file()
+        else {
+            return;
+        };
+        let imported = std::fs::read_to_string(&path)
+            .map_err(JsspError::io("read", &path))
+            .and_then(|text| self.import_solution_text(&text, &path));
+        if let Err(e) = imported {
+            self.toasts.error(&e.context(path.display()));
+        }
+    }
+
+    /// Open the browser's file chooser; the solution is imported by `poll_pending_import`
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn import_solution_with_dialog(&mut self) {
+        let pending = Arc::clone(&self.pending_solution);
+        wasm_bindgen_futures::spawn_local(async move {
+            let dialog = rfd::AsyncFileDialog::new().add_filter("Solution", &["json", "csv", "tsv", "txt"]);
+            if let Some(file) = dialog.pick_file().await {
+                let bytes = file.read().await;
+                *pending.lock().unwrap() = Some((file.file_name(), bytes));
+            }
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn poll_pending_import(&mut self, ctx: &egui::Context) {
+        // The async read holds the other handle while the chooser is open
+        if Arc::strong_count(&self.pending_import) > 1 || Arc::strong_count(&self.pending_solution) > 1 {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        let ortools = self.pending_import.lock().unwrap().take();
+        if let Some((name, bytes)) = ortools {
+            let imported = String::from_utf8(bytes)
+                .map_err(|e| JsspError::Parse(e.to_string()))
+                .and_then(|text| self.import_ortools_text(&text, Path::new(&name)));
+            if let Err(e) = imported {
+                self.toasts.error(&e.context(&name));
+            }
+        }
+        let solution = self.pending_solution.lock().unwrap().take();
+        if let Some((name, bytes)) = solution {
+            let imported = String::from_utf8(bytes)
+                .map_err(|e| JsspError::Parse(e.to_string()))
+                .and_then(|text| self.import_solution_text(&text, Path::new(&name)));
+            if let Err(e) = imported {
+                self.toasts.error(&e.context(&name));
+            }
+        }
+    }
+}
+
+/// Write an export to `path`, or download it under the path's file name in the browser.
+/// Returns where it went, for the status message.
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn write_file(path: &Path, bytes: &[u8]) -> Result<String, JsspError> {
+    std::fs::write(path, bytes)
+        .map(|()| path.display().to_string())
+        .map_err(JsspError::io("write", path))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn write_file(path: &Path, bytes: &[u8]) -> Result<String, JsspError> {
+    use wasm_bindgen::JsCast;
+
+    let file_name = path.file_name().map_or_else(|| "export".to_string(), |name| name.to_string_lossy().into_owned());
+    let failed = |e: wasm_bindgen::JsValue| JsspError::Serialization(format!("Failed to download {}: {:?}", file_name, e));
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(failed)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(failed)?;
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsspError::Serialization("No document to download from".to_string()))?;
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a").map_err(failed)?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&file_name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url).map_err(failed)?;
+    Ok(format!("downloads ({})", file_name))
+}
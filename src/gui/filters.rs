@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::ScheduledOperation;
+
+/// Which operations the Gantt chart, the Schedule Details table and (if asked) the
+/// exports show
+#[derive(Default)]
+pub(super) struct ScheduleFilter {
+    pub hidden_jobs: HashSet<usize>,
+    pub hidden_machines: HashSet<usize>,
+    /// Keep only operations overlapping `from..to`
+    pub time_window: bool,
+    pub from: f64,
+    pub to: f64,
+    /// Keep only operations with zero slack; ignored while the schedule is infeasible
+    pub critical_only: bool,
+    /// Export just the operations that pass the filters
+    pub export_visible: bool,
+}
+
+impl ScheduleFilter {
+    /// Whether any filter hides operations
+    pub fn is_active(&self) -> bool {
+        !self.hidden_jobs.is_empty() || !self.hidden_machines.is_empty() || self.time_window || self.critical_only
+    }
+
+    /// Drop the filters that refer to the previous problem
+    pub fn reset(&mut self) {
+        *self = Self { export_visible: self.export_visible, ..Self::default() };
+    }
+}
+
+impl JsspApp {
+    /// Whether an operation passes the job, machine, time window and critical path filters
+    pub(super) fn visibility(&self) -> impl Fn(&ScheduledOperation) -> bool + '_ {
+        let filter = &self.filter;
+        let critical: Option<HashSet<(usize, usize)>> = match &self.solver {
+            Some(solver) if filter.critical_only && self.violations.is_empty() => Some(
+                solver.operation_slack(&self.schedule).into_iter()
+                    .filter(|(_, slack)| *slack < 1e-6)
+                    .map(|(key, _)| key)
+                    .collect(),
+            ),
+            _ => None,
+        };
+        move |op| {
+            !filter.hidden_jobs.contains(&op.job_id)
+                && !filter.hidden_machines.contains(&op.machine_id)
+                && (!filter.time_window || (op.end_time >= filter.from && op.setup_start() <= filter.to))
+                && critical.as_ref().is_none_or(|critical| critical.contains(&(op.job_id, op.operation_id)))
+        }
+    }
+
+    /// The operations to export: those passing the filters if asked, otherwise all
+    pub(super) fn exported_schedule(&self) -> Vec<ScheduledOperation> {
+        if !(self.filter.export_visible && self.filter.is_active()) {
+            return self.schedule.clone();
+        }
+        let visible = self.visibility();
+        self.schedule.iter().filter(|op| visible(op)).cloned().collect()
+    }
+
+    /// Machine checkboxes, a time window and the critical path filter, above the Gantt chart
+    pub(super) fn render_filter_controls(&mut self, ui: &mut egui::Ui) {
+        let num_machines = self.instance_machines();
+        let machine_names: Vec<String> = (0..num_machines).map(|machine_id| self.machine_name(machine_id)).collect();
+        let makespan = self.makespan;
+        let decimals = self.time_mode().decimals();
+        let filter = &mut self.filter;
+        ui.horizontal(|ui| {
+            ui.label("Filters:");
+            let shown = num_machines - filter.hidden_machines.iter().filter(|&&machine_id| machine_id < num_machines).count();
+            let button = ui.button(format!("Machines ({}/{}) ⏷", shown, num_machines))
+                .on_hover_text("Choose the machines to show");
+            let popup = ui.make_persistent_id("machine_filter");
+            if button.clicked() {
+                ui.memory_mut(|memory| memory.toggle_popup(popup));
+            }
+            egui::popup_below_widget(ui, popup, &button, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+                ui.set_min_width(160.0);
+                ui.horizontal(|ui| {
+                    if ui.small_button("Show All").clicked() {
+                        filter.hidden_machines.clear();
+                    }
+                    if ui.small_button("Hide All").clicked() {
+                        filter.hidden_machines.extend(0..num_machines);
+                    }
+                });
+                egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    for (machine_id, name) in machine_names.iter().enumerate() {
+                        let mut shown = !filter.hidden_machines.contains(&machine_id);
+                        if ui.checkbox(&mut shown, name).changed() {
+                            if shown {
+                                filter.hidden_machines.remove(&machine_id);
+                            } else {
+                                filter.hidden_machines.insert(machine_id);
+                            }
+                        }
+                    }
+                });
+            });
+
+            ui.separator();
+            if ui.checkbox(&mut filter.time_window, "Time Window")
+                .on_hover_text("Show only operations (with their setups) that overlap this interval")
+                .changed()
+                && filter.time_window
+                && filter.to <= filter.from
+            {
+                filter.from = 0.0;
+                filter.to = makespan;
+            }
+            ui.add_enabled_ui(filter.time_window, |ui| {
+                let speed = (makespan * 0.005).max(0.1);
+                ui.add(egui::DragValue::new(&mut filter.from).range(0.0..=filter.to).speed(speed).max_decimals(decimals));
+                ui.label("–");
+                ui.add(egui::DragValue::new(&mut filter.to).range(filter.from..=f64::MAX).speed(speed).max_decimals(decimals));
+            });
+
+            ui.separator();
+            ui.checkbox(&mut filter.critical_only, "Only Critical Path")
+                .on_hover_text("Show only operations with zero slack (for a feasible schedule)");
+
+            if filter.is_active() && ui.small_button("Clear Filters").on_hover_text("Show all jobs, machines and times again").clicked() {
+                filter.reset();
+            }
+        });
+    }
+}
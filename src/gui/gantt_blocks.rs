@@ -0,0 +1,249 @@
+use std::ops::RangeInclusive;
+
+use eframe::egui::{self, Color32, Mesh, Pos2, Rect, Shape, Stroke};
+use egui_plot::{
+    ClosestElem, Cursor, LabelFormatter, PlotBounds, PlotConfig, PlotGeometry, PlotItem, PlotPoint, PlotTransform,
+};
+
+use super::gantt_edit::BLOCK_HEIGHT;
+use super::gantt_index::GanttIndex;
+
+/// Farthest (in points) a block may be from the pointer to be hovered; egui's default
+/// interaction radius is well within this
+const HOVER_REACH: f64 = 16.0;
+
+/// Operations past which the Gantt chart draws simplified bars, except for the
+/// zoomed-in region once it holds no more than this many
+pub(super) const DETAIL_LIMIT: usize = 5000;
+
+/// Off-shift windows in view past which the Gantt chart leaves them out, as they would
+/// be too narrow to see
+pub(super) const OFF_SHIFT_LIMIT: usize = 1000;
+
+/// A filled, outlined rectangle of a Gantt chart, in plot coordinates
+pub(super) struct Block {
+    pub min: [f64; 2],
+    pub max: [f64; 2],
+    /// Transparent for an outline only
+    pub fill: Color32,
+    pub stroke: Stroke,
+    /// Shown next to the pointer when hovered
+    pub label: String,
+}
+
+/// All blocks of a Gantt chart as a single plot item, drawn as one mesh. An item per
+/// operation gets sluggish past about a thousand operations, since the plot bounds,
+/// hit-tests and tessellates every item on its own each frame. Hovering looks blocks up
+/// by row and time in an index instead of measuring the distance to each one.
+#[derive(Default)]
+pub(super) struct GanttBlocks {
+    blocks: Vec<Block>,
+    /// Lines drawn over the blocks without hover, such as the hatching of setups
+    segments: Vec<([f64; 2], [f64; 2], Stroke)>,
+    /// Lines standing in for the blocks of a large schedule (see `SimplifiedBars`)
+    bars: Vec<([f64; 2], [f64; 2], Stroke)>,
+    highlight: bool,
+    /// Blocks by the row they are centered on, built before the plot looks for hovers
+    index: GanttIndex,
+}
+
+impl GanttBlocks {
+    pub fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+    }
+
+    pub fn push_segment(&mut self, from: [f64; 2], to: [f64; 2], stroke: Stroke) {
+        self.segments.push((from, to, stroke));
+    }
+
+    fn screen_rect(block: &Block, transform: &PlotTransform) -> Rect {
+        transform.rect_from_values(&PlotPoint::from(block.min), &PlotPoint::from(block.max))
+    }
+}
+
+/// Operations drawn as plain bars for a large schedule: a thick line per operation
+/// without outline, label or hover text, with the operations of a job that follow each
+/// other on a row less than a pixel apart merged into one
+#[derive(Default)]
+pub(super) struct SimplifiedBars {
+    /// Row, job, start, end and color of every operation
+    spans: Vec<(usize, usize, f64, f64, Color32)>,
+}
+
+impl SimplifiedBars {
+    pub fn push(&mut self, row: usize, job_id: usize, start: f64, end: f64, color: Color32) {
+        self.spans.push((row, job_id, start, end, color));
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Add the bars to `blocks` as segments sized for `transform`; each is at least a
+    /// pixel long, so short operations of a wide view do not vanish
+    pub fn add_to(mut self, blocks: &mut GanttBlocks, transform: &PlotTransform) {
+        let pixel = 1.0 / transform.dpos_dvalue_x().abs();
+        let thickness = (BLOCK_HEIGHT * transform.dpos_dvalue_y().abs()) as f32;
+        self.spans.sort_by(|a, b| a.0.cmp(&b.0).then(a.2.total_cmp(&b.2)));
+        let mut spans = self.spans.into_iter();
+        let Some(mut bar) = spans.next() else {
+            return;
+        };
+        let mut push = |(row, _, start, end, color): (usize, usize, f64, f64, Color32)| {
+            let y = row as f64;
+            blocks.bars.push(([start, y], [end.max(start + pixel), y], Stroke::new(thickness, color)));
+        };
+        for span in spans {
+            let (row, job_id, start, end, color) = span;
+            if row == bar.0 && job_id == bar.1 && color == bar.4 && start - bar.3 < pixel {
+                bar.3 = bar.3.max(end);
+            } else {
+                push(bar);
+                bar = span;
+            }
+        }
+        push(bar);
+    }
+}
+
+/// A line of `stroke.width` points from `a` to `b` as a quad
+fn add_segment(mesh: &mut Mesh, a: Pos2, b: Pos2, stroke: Stroke) {
+    let direction = (b - a).normalized();
+    if !direction.is_finite() {
+        return;
+    }
+    let offset = direction.rot90() * (stroke.width / 2.0);
+    let first = mesh.vertices.len() as u32;
+    for pos in [a - offset, a + offset, b + offset, b - offset] {
+        mesh.colored_vertex(pos, stroke.color);
+    }
+    mesh.add_triangle(first, first + 1, first + 2);
+    mesh.add_triangle(first, first + 2, first + 3);
+}
+
+/// An outline centered on the edges of `rect`, as four rectangles
+fn add_outline(mesh: &mut Mesh, rect: Rect, stroke: Stroke) {
+    let (outer, inner) = (rect.expand(stroke.width / 2.0), rect.shrink(stroke.width / 2.0));
+    for edge in [
+        Rect::from_min_max(outer.min, egui::pos2(outer.max.x, inner.min.y)),
+        Rect::from_min_max(egui::pos2(outer.min.x, inner.max.y), outer.max),
+        Rect::from_min_max(egui::pos2(outer.min.x, inner.min.y), egui::pos2(inner.min.x, inner.max.y)),
+        Rect::from_min_max(egui::pos2(inner.max.x, inner.min.y), egui::pos2(outer.max.x, inner.max.y)),
+    ] {
+        if edge.is_positive() {
+            mesh.add_colored_rect(edge, stroke.color);
+        }
+    }
+}
+
+impl PlotItem for GanttBlocks {
+    fn shapes(&self, _ui: &egui::Ui, transform: &PlotTransform, shapes: &mut Vec<Shape>) {
+        let mut mesh = Mesh::default();
+        for block in &self.blocks {
+            let rect = Self::screen_rect(block, transform);
+            if block.fill != Color32::TRANSPARENT {
+                mesh.add_colored_rect(rect, block.fill);
+            }
+            if !block.stroke.is_empty() {
+                add_outline(&mut mesh, rect, block.stroke);
+            }
+        }
+        for &(from, to, stroke) in self.bars.iter().chain(&self.segments) {
+            let from = transform.position_from_point(&PlotPoint::from(from));
+            let to = transform.position_from_point(&PlotPoint::from(to));
+            add_segment(&mut mesh, from, to, stroke);
+        }
+        shapes.push(Shape::mesh(mesh));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+        self.index = GanttIndex::new(self.blocks.iter().enumerate().map(|(index, block)| {
+            let row = ((block.min[1] + block.max[1]) / 2.0).round().max(0.0) as usize;
+            (row, block.min[0], block.max[0], index)
+        }));
+    }
+
+    fn name(&self) -> &str {
+        ""
+    }
+
+    fn color(&self) -> Color32 {
+        Color32::TRANSPARENT
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn allow_hover(&self) -> bool {
+        true
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for block in &self.blocks {
+            bounds.extend_with(&PlotPoint::from(block.min));
+            bounds.extend_with(&PlotPoint::from(block.max));
+        }
+        // Simplified bars are lines along the middle of their rows
+        for &(from, to, _) in &self.bars {
+            bounds.extend_with(&PlotPoint::new(from[0], from[1] - BLOCK_HEIGHT / 2.0));
+            bounds.extend_with(&PlotPoint::new(to[0], to[1] + BLOCK_HEIGHT / 2.0));
+        }
+        bounds
+    }
+
+    fn id(&self) -> Option<egui::Id> {
+        None
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &PlotTransform) -> Option<ClosestElem> {
+        // Only the blocks of rows and times within reach of the pointer can be hovered
+        let value = transform.value_from_position(point);
+        let (reach_x, reach_y) = (HOVER_REACH / transform.dpos_dvalue_x().abs(), HOVER_REACH / transform.dpos_dvalue_y().abs());
+        let first_row = (value.y - reach_y).round().max(0.0) as usize;
+        let last_row = (value.y + reach_y).round().max(0.0) as usize;
+        self.index.overlapping(first_row..=last_row, value.x - reach_x, value.x + reach_x)
+            .map(|index| ClosestElem { index, dist_sq: Self::screen_rect(&self.blocks[index], transform).distance_sq_to_pos(point) })
+            .min_by(|a, b| a.dist_sq.total_cmp(&b.dist_sq))
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        cursors: &mut Vec<Cursor>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter<'_>,
+    ) {
+        let block = &self.blocks[elem.index];
+        let rect = Self::screen_rect(block, plot.transform);
+        shapes.push(Shape::rect_stroke(rect, 0.0, Stroke::new(2.0, plot.ui.visuals().text_color())));
+
+        let pointer = plot.ui.input(|i| i.pointer.hover_pos()).unwrap_or(rect.center());
+        let value = plot.transform.value_from_position(pointer);
+        if plot.show_x {
+            cursors.push(Cursor::Vertical { x: value.x });
+        }
+        if plot.show_y {
+            cursors.push(Cursor::Horizontal { y: value.y });
+        }
+        let text = match label_formatter {
+            Some(format) => format(&block.label, &value),
+            None => block.label.clone(),
+        };
+        let font_id = egui::TextStyle::Body.resolve(plot.ui.style());
+        let color = plot.ui.visuals().text_color();
+        plot.ui.fonts(|fonts| {
+            shapes.push(Shape::text(fonts, pointer + egui::vec2(3.0, -2.0), egui::Align2::LEFT_BOTTOM, text, font_id, color));
+        });
+    }
+}
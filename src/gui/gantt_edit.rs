@@ -0,0 +1,329 @@
+use eframe::egui;
+use egui_plot::{PlotPoint, PlotResponse, PlotUi};
+use serde::{Deserialize, Serialize};
+
+use super::JsspApp;
+use crate::jssp::repair::MoveError;
+use crate::jssp::{Buffering, JsspSolver, ScheduledOperation};
+
+/// Height of an operation block in machine rows
+pub(super) const BLOCK_HEIGHT: f64 = 0.8;
+
+/// What the rows of the Gantt chart stand for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GanttRows {
+    /// One row per machine, blocks colored by job
+    #[default]
+    Machines,
+    /// One row per job, blocks colored by machine
+    Jobs,
+}
+
+impl GanttRows {
+    /// Row of an operation's block in this layout
+    pub(super) fn row(self, op: &ScheduledOperation) -> f64 {
+        match self {
+            GanttRows::Machines => op.machine_id as f64,
+            GanttRows::Jobs => op.job_id as f64,
+        }
+    }
+}
+
+/// An operation block currently being dragged in the Gantt chart
+pub(super) struct GanttDrag {
+    operation: (usize, usize),
+    /// Distance between the grab point and the block's start time
+    grab_offset: f64,
+    target_machine: usize,
+    target_start: f64,
+    preview: Result<Vec<ScheduledOperation>, MoveError>,
+}
+
+/// What the context menu of a Gantt block does with pins
+enum PinAction {
+    Pin,
+    Unpin,
+    UnpinAll,
+}
+
+impl JsspApp {
+    /// Row of an operation's block in the current chart layout
+    pub(super) fn gantt_row(&self, op: &ScheduledOperation) -> f64 {
+        self.gantt_rows.row(op)
+    }
+
+    /// Rows below the machines showing which operations hold a unit of each secondary
+    /// resource: a name and the operations of every lane, first row right after the last
+    /// machine. Empty by job and without resources.
+    pub(super) fn resource_rows(&self) -> Vec<(String, Vec<&ScheduledOperation>)> {
+        let Some(solver) = self.solver.as_ref().filter(|solver| solver.uses_resources() && self.gantt_rows == GanttRows::Machines) else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for (resource_id, lanes) in solver.resource_lanes(&self.schedule).into_iter().enumerate() {
+            let name = solver.resource_name(resource_id);
+            let count = lanes.len().max(solver.resources()[resource_id].capacity);
+            let mut lanes = lanes.into_iter();
+            for lane in 0..count {
+                let ops = lanes.next().unwrap_or_default();
+                rows.push((if count == 1 { name.clone() } else { format!("{} {}", name, lane + 1) }, ops));
+            }
+        }
+        rows
+    }
+
+    /// The operation block drawn at a plot position, if any
+    pub(super) fn operation_at(&self, pos: PlotPoint) -> Option<&ScheduledOperation> {
+        let row = pos.y.round();
+        if row < 0.0 || (pos.y - row).abs() > BLOCK_HEIGHT / 2.0 {
+            return None;
+        }
+        let row = row as usize;
+        // The schedule may have been edited since the chart was drawn this frame
+        self.gantt_index.overlapping(row..=row, pos.x, pos.x)
+            .filter_map(|position| self.schedule.get(position))
+            .find(|op| pos.x >= op.start_time && pos.x <= op.end_time)
+    }
+
+    /// Whether the plot should pan on drag (disabled while editing a block)
+    pub(super) fn gantt_allows_panning(&self) -> bool {
+        !(self.drag_editing && (self.hovered_block.is_some() || self.gantt_drag.is_some()))
+    }
+
+    /// Live makespan delta (or rejection reason) of the move being dragged
+    pub(super) fn render_drag_status(&self, ui: &mut egui::Ui) {
+        if let Some(drag) = &self.gantt_drag {
+            let (job_id, operation_id) = drag.operation;
+            match (&drag.preview, &self.solver) {
+                (Ok(preview), Some(solver)) => {
+                    let makespan = solver.calculate_makespan(preview);
+                    let time = solver.time_mode();
+                    let delta = makespan - self.makespan;
+                    let color = if delta > 1e-6 {
+                        egui::Color32::LIGHT_RED
+                    } else {
+                        egui::Color32::LIGHT_GREEN
+                    };
+                    ui.colored_label(color, format!(
+                        "Moving Job {} Op {} ➡ Machine {} at {}: makespan {} (Δ {:+.p$})",
+                        job_id, operation_id, drag.target_machine, time.format(drag.target_start), time.format(makespan), delta,
+                        p = time.decimals()
+                    ));
+                }
+                (Err(error), _) => {
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("Cannot move here: {}", error));
+                }
+                _ => {}
+            }
+        } else if let Some(status) = &self.edit_status {
+            match status {
+                Ok(message) => ui.colored_label(egui::Color32::LIGHT_GREEN, message),
+                Err(message) => ui.colored_label(egui::Color32::LIGHT_RED, message),
+            };
+        }
+    }
+
+    /// Ghost of the dragged block at its target position
+    pub(super) fn draw_drag_preview(&self, plot_ui: &mut PlotUi) {
+        let Some(drag) = &self.gantt_drag else {
+            return;
+        };
+        let Some(op) = self.schedule.iter().find(|op| (op.job_id, op.operation_id) == drag.operation) else {
+            return;
+        };
+
+        let duration = match &drag.preview {
+            Ok(preview) => preview.iter()
+                .find(|p| (p.job_id, p.operation_id) == drag.operation)
+                .map_or(op.duration, |p| p.duration),
+            Err(_) => op.duration,
+        };
+        let y_pos = match self.gantt_rows {
+            GanttRows::Machines => drag.target_machine as f64,
+            GanttRows::Jobs => op.job_id as f64,
+        };
+        let (start, end) = (drag.target_start, drag.target_start + duration);
+        let color = if drag.preview.is_ok() {
+            egui::Color32::WHITE
+        } else {
+            egui::Color32::RED
+        };
+
+        plot_ui.polygon(
+            egui_plot::Polygon::new(vec![
+                [start, y_pos - BLOCK_HEIGHT / 2.0],
+                [end, y_pos - BLOCK_HEIGHT / 2.0],
+                [end, y_pos + BLOCK_HEIGHT / 2.0],
+                [start, y_pos + BLOCK_HEIGHT / 2.0],
+            ])
+            .fill_color(color.gamma_multiply(0.25))
+            .stroke(egui::Stroke::new(2.0, color))
+            .allow_hover(false)
+        );
+    }
+
+    /// Start, update and finish dragging operation blocks
+    pub(super) fn handle_gantt_drag(&mut self, plot_response: &PlotResponse<()>) {
+        let response = &plot_response.response;
+        let transform = &plot_response.transform;
+
+        if !self.drag_editing {
+            self.gantt_drag = None;
+            self.hovered_block = None;
+            return;
+        }
+
+        self.hovered_block = response.hover_pos()
+            .and_then(|pos| self.operation_at(transform.value_from_position(pos)))
+            .map(|op| (op.job_id, op.operation_id));
+
+        if response.drag_started() {
+            let origin = response.ctx.input(|i| i.pointer.press_origin());
+            let grabbed = origin
+                .map(|pos| transform.value_from_position(pos))
+                .and_then(|pos| self.operation_at(pos).map(|op| (op, pos)));
+            if let Some((op, pos)) = grabbed {
+                self.gantt_drag = Some(GanttDrag {
+                    operation: (op.job_id, op.operation_id),
+                    grab_offset: pos.x - op.start_time,
+                    target_machine: op.machine_id,
+                    target_start: op.start_time,
+                    preview: Ok(self.schedule.clone()),
+                });
+                self.edit_status = None;
+            }
+        }
+
+        if let (Some(drag), Some(solver)) = (&mut self.gantt_drag, &self.solver) {
+            if let Some(pointer) = response.ctx.input(|i| i.pointer.latest_pos()) {
+                let pos = transform.value_from_position(pointer);
+                // Rows are jobs in the job layout, so dragging there only moves in time
+                let target_machine = match self.gantt_rows {
+                    GanttRows::Machines => {
+                        let max_machine = solver.num_machines().saturating_sub(1) as f64;
+                        pos.y.round().clamp(0.0, max_machine) as usize
+                    }
+                    GanttRows::Jobs => drag.target_machine,
+                };
+                // Integer instances only start operations on whole time units
+                let target_start = solver.time_mode().snap((pos.x - drag.grab_offset).max(0.0));
+
+                // Only re-run the repair when the target actually changed
+                if target_machine != drag.target_machine || (target_start - drag.target_start).abs() > 1e-9 {
+                    drag.target_machine = target_machine;
+                    drag.target_start = target_start;
+                    drag.preview = solver.move_operation(&self.schedule, drag.operation, target_machine, target_start);
+                }
+            }
+        }
+
+        if response.drag_stopped() {
+            if let Some(drag) = self.gantt_drag.take() {
+                let (job_id, operation_id) = drag.operation;
+                let time = self.time_mode();
+                self.edit_status = Some(match drag.preview {
+                    Ok(schedule) => {
+                        let old_makespan = self.makespan;
+                        self.remember("Move Operation");
+                        self.apply_edited_schedule(schedule);
+                        Ok(format!(
+                            "Moved Job {} Op {} to machine {}: makespan {} ➡ {} (Δ {:+.p$})",
+                            job_id, operation_id, drag.target_machine,
+                            time.format(old_makespan), time.format(self.makespan), self.makespan - old_makespan,
+                            p = time.decimals()
+                        ))
+                    }
+                    Err(error) => Err(format!("Move rejected: {}", error)),
+                });
+            }
+        }
+    }
+
+    /// Pin or unpin the block right-clicked in the Gantt chart
+    pub(super) fn handle_gantt_context_menu(&mut self, plot_response: &PlotResponse<()>) {
+        let response = &plot_response.response;
+        if response.secondary_clicked() {
+            self.context_block = response.interact_pointer_pos()
+                .and_then(|pos| self.operation_at(plot_response.transform.value_from_position(pos)))
+                .map(|op| (op.job_id, op.operation_id));
+        }
+        let (Some(solver), Some((job_id, operation_id))) = (&self.solver, self.context_block) else {
+            return;
+        };
+        let supported = solver.buffering == Buffering::Unlimited && !solver.preemptive && !solver.is_open_shop();
+        let pinned = solver.pin(job_id, operation_id).is_some();
+        let any_pinned = !solver.pins().is_empty();
+
+        let mut action = None;
+        response.context_menu(|ui| {
+            ui.label(format!("{} | Op {}", self.job_name(job_id), operation_id));
+            ui.separator();
+            ui.add_enabled_ui(supported, |ui| {
+                if pinned {
+                    if ui.button("Unpin").on_hover_text("Also unpins the later operations of the job").clicked() {
+                        action = Some(PinAction::Unpin);
+                        ui.close_menu();
+                    }
+                } else if ui.button("📌 Pin here")
+                    .on_hover_text("Keep this operation, and the earlier ones of its job, on their machines at these times in later solves")
+                    .clicked()
+                {
+                    action = Some(PinAction::Pin);
+                    ui.close_menu();
+                }
+                if ui.add_enabled(any_pinned, egui::Button::new("Unpin All")).clicked() {
+                    action = Some(PinAction::UnpinAll);
+                    ui.close_menu();
+                }
+            })
+            .response
+            .on_disabled_hover_text("Pins apply with unlimited buffers, without preemption and outside open shops");
+        });
+
+        let Some(action) = action else {
+            return;
+        };
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        // Changed on a copy, so that a rejected pin leaves no undo step behind
+        let mut instance = solver.instance().clone();
+        let status = match action {
+            PinAction::Pin => instance.pin_operation(&self.schedule, (job_id, operation_id))
+                .map(|count| format!("Pinned Job {} Op {} ({} operation(s) pinned)", job_id, operation_id, count))
+                .map_err(|error| format!("Pin rejected: {}", error)),
+            PinAction::Unpin => {
+                let count = instance.unpin_operation((job_id, operation_id));
+                Ok(format!("Unpinned Job {} Op {} ({} operation(s) released)", job_id, operation_id, count))
+            }
+            PinAction::UnpinAll => {
+                let count = instance.pins().len();
+                instance.set_pins(Vec::new()).ok();
+                Ok(format!("Unpinned all {} operation(s)", count))
+            }
+        };
+        if status.is_ok() {
+            self.remember("Pin");
+            let solver = JsspSolver::new(instance);
+            self.violations = solver.validate(&self.schedule).err().unwrap_or_default();
+            self.solver = Some(solver);
+        }
+        self.edit_status = Some(status);
+        self.context_block = None;
+    }
+
+    /// Replace the current schedule after a manual edit and re-validate it; the schedule
+    /// no longer is the one its run produced
+    pub(super) fn apply_edited_schedule(&mut self, schedule: Vec<ScheduledOperation>) {
+        if let Some(solver) = &self.solver {
+            self.makespan = solver.calculate_makespan(&schedule);
+            self.violations = solver.validate(&schedule).err().unwrap_or_default();
+        }
+        if let Some(run) = &mut self.run {
+            run.edited = true;
+        }
+        self.schedule = schedule;
+        self.swap_suggestions = None;
+        self.record_kpis();
+    }
+}
@@ -0,0 +1,53 @@
+use std::ops::RangeInclusive;
+
+/// Intervals of the Gantt chart by row, for finding what lies under the pointer without
+/// scanning every block. Each row keeps its intervals sorted by start with the latest
+/// end so far, so a lookup is a binary search plus the few intervals reaching the time.
+#[derive(Default)]
+pub(super) struct GanttIndex {
+    rows: Vec<Vec<Entry>>,
+}
+
+struct Entry {
+    start: f64,
+    end: f64,
+    /// Latest end of this and every earlier entry of the row
+    reach: f64,
+    /// What the interval belongs to, e.g. a position in the schedule
+    item: usize,
+}
+
+impl GanttIndex {
+    /// Index `(row, start, end, item)` intervals
+    pub fn new(intervals: impl IntoIterator<Item = (usize, f64, f64, usize)>) -> Self {
+        let mut rows: Vec<Vec<Entry>> = Vec::new();
+        for (row, start, end, item) in intervals {
+            if row >= rows.len() {
+                rows.resize_with(row + 1, Vec::new);
+            }
+            rows[row].push(Entry { start, end, reach: end, item });
+        }
+        for entries in &mut rows {
+            entries.sort_by(|a, b| a.start.total_cmp(&b.start));
+            let mut reach = f64::NEG_INFINITY;
+            for entry in entries.iter_mut() {
+                reach = reach.max(entry.end);
+                entry.reach = reach;
+            }
+        }
+        Self { rows }
+    }
+
+    /// Items in `rows` whose interval overlaps `from..=to`, by row and latest start first
+    pub fn overlapping(&self, rows: RangeInclusive<usize>, from: f64, to: f64) -> impl Iterator<Item = usize> + '_ {
+        let rows = *rows.start()..(*rows.end()).saturating_add(1).min(self.rows.len());
+        self.rows[rows.start.min(rows.end)..rows.end].iter().flat_map(move |entries| {
+            let started = entries.partition_point(|entry| entry.start <= to);
+            entries[..started].iter()
+                .rev()
+                .take_while(move |entry| entry.reach >= from)
+                .filter(move |entry| entry.end >= from)
+                .map(|entry| entry.item)
+        })
+    }
+}
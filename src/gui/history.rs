@@ -0,0 +1,160 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::{Instance, JsspSolver, ScheduledOperation};
+
+/// Undo steps kept before the oldest is dropped
+const HISTORY_LEN: usize = 50;
+
+/// The problem and schedule as they were before a change
+struct Snapshot {
+    /// What the change was, e.g. "Solve"
+    label: String,
+    /// Identifies the problem, so restoring a schedule of the same problem keeps the
+    /// state derived from it (KPI history, Pareto front, ...)
+    problem_id: u64,
+    instance: Option<Instance>,
+    schedule: Vec<ScheduledOperation>,
+    run: Option<RunMetadata>,
+}
+
+#[derive(Default)]
+pub(super) struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// Incremented whenever the problem is replaced
+    pub(super) problem_id: u64,
+}
+
+impl History {
+    pub(super) fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|snapshot| snapshot.label.as_str())
+    }
+
+    pub(super) fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|snapshot| snapshot.label.as_str())
+    }
+}
+
+impl JsspApp {
+    fn snapshot(&self, label: &str) -> Snapshot {
+        Snapshot {
+            label: label.to_string(),
+            problem_id: self.history.problem_id,
+            instance: self.solver.as_ref().map(|solver| solver.instance().clone()),
+            schedule: self.schedule.clone(),
+            run: self.run.clone(),
+        }
+    }
+
+    /// Save the current problem and schedule before a change described by `label`
+    pub(super) fn remember(&mut self, label: &str) {
+        let snapshot = self.snapshot(label);
+        self.history.undo.push(snapshot);
+        if self.history.undo.len() > HISTORY_LEN {
+            self.history.undo.remove(0);
+        }
+        self.history.redo.clear();
+    }
+
+    /// Bring back a snapshot; the current state goes to the other stack under the same label
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        let current = self.snapshot(&snapshot.label);
+        if snapshot.problem_id != self.history.problem_id || self.solver.is_none() != snapshot.instance.is_none() {
+            self.replace_problem(snapshot.instance.map(JsspSolver::new));
+            self.history.problem_id = snapshot.problem_id;
+        } else if let (Some(solver), Some(instance)) = (&mut self.solver, snapshot.instance) {
+            *solver = JsspSolver::new(instance);
+        }
+        if let Some(solver) = &self.solver {
+            self.makespan = solver.calculate_makespan(&snapshot.schedule);
+            self.violations = solver.validate(&snapshot.schedule).err().unwrap_or_default();
+        }
+        self.schedule = snapshot.schedule;
+        self.run = snapshot.run;
+        self.swap_suggestions = None;
+        self.edit_status = None;
+        current
+    }
+
+    pub(super) fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo.pop() {
+            let current = self.restore(snapshot);
+            self.history.redo.push(current);
+        }
+    }
+
+    pub(super) fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo.pop() {
+            let current = self.restore(snapshot);
+            self.history.undo.push(current);
+        }
+    }
+
+    /// Ctrl+Z undoes; Ctrl+Y or Ctrl+Shift+Z redoes. Text fields keep their own undo.
+    pub(super) fn handle_history_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let redo_shift = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z);
+        let redo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y);
+        let undo = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z);
+        // Check the shifted shortcut first, since Ctrl+Z also matches Ctrl+Shift+Z
+        if ctx.input_mut(|i| i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo)) {
+            self.redo();
+        } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+            self.undo();
+        }
+    }
+
+    /// Undo and Redo buttons, with the change they revert or repeat as tooltip
+    pub(super) fn render_history_buttons(&mut self, ui: &mut egui::Ui) {
+        let undo_label = self.history.undo_label().map(|label| format!("Undo {} (Ctrl+Z)", label));
+        if ui.add_enabled(undo_label.is_some(), egui::Button::new("Undo").min_size(egui::vec2(80.0, 32.0)))
+            .on_hover_text(undo_label.unwrap_or_default())
+            .clicked()
+        {
+            self.undo();
+        }
+        let redo_label = self.history.redo_label().map(|label| format!("Redo {} (Ctrl+Y)", label));
+        if ui.add_enabled(redo_label.is_some(), egui::Button::new("Redo").min_size(egui::vec2(80.0, 32.0)))
+            .on_hover_text(redo_label.unwrap_or_default())
+            .clicked()
+        {
+            self.redo();
+        }
+    }
+
+    /// Asks before Clear All throws the problem away
+    pub(super) fn render_clear_confirmation(&mut self, ctx: &egui::Context) {
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new("Clear All?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label("Remove the current problem and schedule?");
+                ui.colored_label(egui::Color32::GRAY, "You can bring them back with Undo (Ctrl+Z).");
+                ui.horizontal(|ui| {
+                    if ui.add_sized([100.0, 32.0], egui::Button::new("Clear")).clicked() {
+                        confirmed = true;
+                    }
+                    if ui.add_sized([100.0, 32.0], egui::Button::new("Cancel")).clicked() {
+                        cancelled = true;
+                    }
+                });
+            });
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            cancelled = true;
+        }
+        if confirmed {
+            self.remember("Clear All");
+            self.replace_problem(None);
+        }
+        if confirmed || cancelled {
+            self.confirm_clear = false;
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use eframe::egui;
+
+use super::files::write_file;
+use super::JsspApp;
+use crate::jssp::ortools::to_ortools_json;
+use crate::jssp::{Downtime, Instance, Job, JsspError, MachineOption, Operation};
+
+/// A draft of the problem being edited in the "Edit Instance" tab. Edits stay in the
+/// draft until applied, so a half-finished edit never leaves the schedule inconsistent.
+pub(super) struct InstanceEditor {
+    jobs: Vec<Job>,
+    num_machines: usize,
+    name: String,
+    /// One per job and per machine; blank for the default name
+    job_names: Vec<String>,
+    machine_names: Vec<String>,
+    downtime: Vec<Downtime>,
+    /// `history.problem_id` of the problem the draft was taken from
+    loaded_from: Option<u64>,
+    /// The draft has changes that were not applied yet
+    modified: bool,
+    /// Why the draft is not a valid instance
+    error: Option<String>,
+    status: Option<String>,
+}
+
+impl Default for InstanceEditor {
+    fn default() -> Self {
+        Self {
+            jobs: Vec::new(),
+            num_machines: 3,
+            name: String::new(),
+            job_names: Vec::new(),
+            machine_names: Vec::new(),
+            downtime: Vec::new(),
+            loaded_from: None,
+            modified: false,
+            error: None,
+            status: None,
+        }
+    }
+}
+
+/// A structural change picked in the grid, applied after drawing it
+enum Edit {
+    AddJob,
+    RemoveJob(usize),
+    AddOperation(usize),
+    MoveOperation { job: usize, operation: usize, up: bool },
+    RemoveOperation(usize, usize),
+    AddAlternative(usize, usize),
+    RemoveAlternative(usize, usize, usize),
+    AddDowntime,
+    RemoveDowntime(usize),
+}
+
+impl InstanceEditor {
+    fn load(&mut self, instance: Option<&Instance>, num_machines: usize, problem_id: u64) {
+        *self = match instance {
+            Some(instance) => Self {
+                jobs: instance.jobs().to_vec(),
+                num_machines: instance.num_machines(),
+                name: instance.name.clone(),
+                job_names: instance.job_names().to_vec(),
+                machine_names: instance.machine_names().to_vec(),
+                downtime: instance.downtime().to_vec(),
+                ..Self::default()
+            },
+            None => Self { num_machines, ..Self::default() },
+        };
+        self.fit_names();
+        self.loaded_from = Some(problem_id);
+    }
+
+    /// Take the problem again the next time the editor is shown, unless the draft has
+    /// edits that were not applied
+    pub(super) fn reload(&mut self) {
+        if !self.modified {
+            self.loaded_from = None;
+        }
+    }
+
+    /// Keep a name field for every job and machine, blank for new ones
+    fn fit_names(&mut self) {
+        self.job_names.resize(self.jobs.len(), String::new());
+        self.machine_names.resize(self.num_machines, String::new());
+    }
+
+    fn apply(&mut self, edit: Edit) {
+        match edit {
+            Edit::AddJob => {
+                let operations = (0..self.num_machines)
+                    .map(|machine_id| Operation { job_id: 0, operation_id: 0, machine_id, duration: 1.0, alternatives: Vec::new() })
+                    .collect();
+                self.jobs.push(Job { id: 0, operations, release_time: 0.0, due_date: None, weight: 1.0 });
+            }
+            Edit::RemoveJob(job) => {
+                self.jobs.remove(job);
+                self.job_names.remove(job);
+            }
+            Edit::AddOperation(job) => {
+                let operations = &mut self.jobs[job].operations;
+                let machine_id = (0..self.num_machines)
+                    .find(|machine| operations.iter().all(|op| op.machine_id != *machine))
+                    .unwrap_or(0);
+                operations.push(Operation { job_id: 0, operation_id: 0, machine_id, duration: 1.0, alternatives: Vec::new() });
+            }
+            Edit::MoveOperation { job, operation, up } => {
+                let operations = &mut self.jobs[job].operations;
+                let other = if up { operation.checked_sub(1) } else { Some(operation + 1) };
+                if let Some(other) = other.filter(|other| *other < operations.len()) {
+                    operations.swap(operation, other);
+                }
+            }
+            Edit::RemoveOperation(job, operation) => {
+                self.jobs[job].operations.remove(operation);
+            }
+            Edit::AddAlternative(job, operation) => {
+                let op = &mut self.jobs[job].operations[operation];
+                let used: Vec<usize> = op.options().map(|option| option.machine_id).collect();
+                let machine_id = (0..self.num_machines).find(|machine| !used.contains(machine)).unwrap_or(0);
+                op.alternatives.push(MachineOption { machine_id, duration: op.duration });
+            }
+            Edit::RemoveAlternative(job, operation, alternative) => {
+                self.jobs[job].operations[operation].alternatives.remove(alternative);
+            }
+            Edit::AddDowntime => {
+                // After the last window, so it does not merge into an existing one
+                let start = self.downtime.iter().map(|window| window.end + 1.0).fold(0.0, f64::max);
+                self.downtime.push(Downtime { machine_id: 0, start, end: start + 5.0 });
+            }
+            Edit::RemoveDowntime(index) => {
+                self.downtime.remove(index);
+            }
+        }
+        self.changed();
+    }
+
+    /// Renumber jobs and operations by position and re-check the draft
+    fn changed(&mut self) {
+        for (job_id, job) in self.jobs.iter_mut().enumerate() {
+            job.id = job_id;
+            for (operation_id, op) in job.operations.iter_mut().enumerate() {
+                op.job_id = job_id;
+                op.operation_id = operation_id;
+            }
+        }
+        self.fit_names();
+        self.modified = true;
+        self.status = None;
+        self.error = self.build(None).err();
+    }
+
+    /// The draft as an instance, keeping what the grid does not edit (calendar, soft
+    /// constraints, metadata, job colors, and setup and transfer times, secondary
+    /// resources, pins, duration spreads and a flow shop while they still fit) from `current`
+    fn build(&self, current: Option<&Instance>) -> Result<Instance, String> {
+        let mut instance = Instance::new(self.jobs.clone(), self.num_machines)?.with_name(self.name.trim());
+        instance.set_downtime(self.downtime.clone())?;
+        instance.set_job_names(self.job_names.clone())?;
+        instance.set_machine_names(self.machine_names.clone())?;
+        if let Some(current) = current {
+            instance.set_calendar(current.calendar().cloned())?;
+            instance.metadata = current.metadata.clone();
+            instance.soft_constraints = current.soft_constraints.clone();
+            instance.job_colors = current.job_colors.clone();
+            instance.job_colors.retain(|&job_id, _| job_id < self.jobs.len());
+            // Setup and transfer matrices only carry over while the jobs and machines still match them
+            instance.set_setup_times(current.setup_times().cloned()).ok();
+            instance.set_transfer_times(current.transfer_times().cloned()).ok();
+            instance.set_resources(current.resources().to_vec(), current.resource_requirements()).ok();
+            instance.set_pins(current.pins().to_vec()).ok();
+            instance.set_duration_spreads(current.duration_spreads(), current.scenario_count(), current.scenario_seed()).ok();
+            instance.set_problem_type(current.problem_type()).ok();
+        }
+        Ok(instance)
+    }
+}
+
+impl JsspApp {
+    pub(super) fn render_instance_editor(&mut self, ui: &mut egui::Ui) {
+        let problem_id = self.history.problem_id;
+        let editor = &mut self.instance_editor;
+        // Follow the loaded problem unless there are edits that would be lost
+        if editor.loaded_from != Some(problem_id) && !editor.modified {
+            editor.load(self.solver.as_ref().map(|solver| solver.instance()), self.num_machines, problem_id);
+        }
+
+        let mut edit = None;
+        let mut apply = false;
+        let mut revert = false;
+        let mut export = false;
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            if ui.add(egui::TextEdit::singleline(&mut editor.name).desired_width(160.0)).changed() {
+                editor.changed();
+            }
+            ui.separator();
+            ui.label("Machines:");
+            if ui.add(egui::DragValue::new(&mut editor.num_machines).range(1..=100)).changed() {
+                editor.changed();
+            }
+            ui.separator();
+            if ui.add_sized([120.0, 32.0], egui::Button::new("Add Job")).clicked() {
+                edit = Some(Edit::AddJob);
+            }
+            let valid = editor.error.is_none() && !editor.jobs.is_empty();
+            if ui.add_enabled(editor.modified && valid, egui::Button::new("Apply Changes").min_size(egui::vec2(140.0, 32.0)))
+                .on_hover_text("Replace the current problem with the edited one; the schedule is discarded")
+                .clicked()
+            {
+                apply = true;
+            }
+            if ui.add_enabled(editor.modified, egui::Button::new("Revert").min_size(egui::vec2(100.0, 32.0)))
+                .on_hover_text("Discard the edits and reload the current problem")
+                .clicked()
+            {
+                revert = true;
+            }
+            if ui.add_enabled(valid, egui::Button::new("Export Instance...").min_size(egui::vec2(160.0, 32.0)))
+                .on_hover_text("Save the edited instance in the OR-Tools JSON layout")
+                .clicked()
+            {
+                export = true;
+            }
+        });
+
+        if let Some(error) = &editor.error {
+            ui.colored_label(egui::Color32::RED, format!("⚠ {}", error));
+        } else if editor.modified && !self.schedule.is_empty() {
+            ui.colored_label(egui::Color32::YELLOW, "Unapplied changes. Applying them discards the current schedule.");
+        } else if editor.modified {
+            ui.colored_label(egui::Color32::YELLOW, "Unapplied changes.");
+        }
+        if let Some(status) = &editor.status {
+            ui.label(status);
+        }
+        ui.separator();
+
+        if editor.jobs.is_empty() {
+            ui.colored_label(egui::Color32::GRAY, "No jobs yet. Click \"Add Job\" or generate a problem to start from.");
+        }
+        let num_machines = editor.num_machines;
+        let collapse = editor.jobs.len() > 10;
+        let mut values_changed = false;
+        egui::ScrollArea::vertical().id_salt("instance_editor_scroll").show(ui, |ui| {
+            let named = editor.machine_names.iter().filter(|name| !name.trim().is_empty()).count();
+            egui::CollapsingHeader::new(format!("Machine Names ({} of {} named)", named, num_machines))
+                .id_salt("instance_editor_machine_names")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.label("Shown in the chart, tables and exports instead of \"Machine <number>\"; leave blank for the default.");
+                    egui::Grid::new("instance_editor_machine_names_grid").num_columns(2).show(ui, |ui| {
+                        for (machine_id, name) in editor.machine_names.iter_mut().enumerate() {
+                            ui.label(format!("M{}", machine_id));
+                            values_changed |= ui.add(
+                                egui::TextEdit::singleline(name).hint_text(format!("Machine {}", machine_id)).desired_width(160.0),
+                            ).changed();
+                            ui.end_row();
+                        }
+                    });
+                });
+            egui::CollapsingHeader::new(format!("Machine Downtime ({} windows)", editor.downtime.len()))
+                .id_salt("instance_editor_downtime")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.label("Periods in which a machine can neither set up nor process; operations wait until they end.");
+                    if !editor.downtime.is_empty() {
+                        egui::Grid::new("instance_editor_downtime_grid").striped(true).num_columns(4).show(ui, |ui| {
+                            for header in ["Machine", "Start", "End", ""] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+                            for (index, window) in editor.downtime.iter_mut().enumerate() {
+                                values_changed |= ui.add(egui::DragValue::new(&mut window.machine_id).range(0..=num_machines - 1).prefix("M")).changed();
+                                values_changed |= ui.add(egui::DragValue::new(&mut window.start).range(0.0..=100_000.0).speed(0.5)).changed();
+                                values_changed |= ui.add(egui::DragValue::new(&mut window.end).range(0.0..=100_000.0).speed(0.5)).changed();
+                                if ui.button("Remove").clicked() {
+                                    edit = Some(Edit::RemoveDowntime(index));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    }
+                    if ui.button("Add Window").clicked() {
+                        edit = Some(Edit::AddDowntime);
+                    }
+                });
+            ui.separator();
+
+            for (job_index, (job, job_name)) in editor.jobs.iter_mut().zip(&mut editor.job_names).enumerate() {
+                let total = job.operations.iter().fold(0.0, |total, op| total + op.duration);
+                let mut changed = false;
+                let title = match job_name.trim() {
+                    "" => format!("Job {}", job.id),
+                    name => format!("Job {} ({})", job.id, name),
+                };
+                egui::CollapsingHeader::new(format!("{}: {} operations, {:.1} time units", title, job.operations.len(), total))
+                    .id_salt(("instance_editor_job", job_index))
+                    .default_open(!collapse)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Name:");
+                            changed |= ui.add(
+                                egui::TextEdit::singleline(job_name).hint_text(format!("Job {}", job.id)).desired_width(120.0),
+                            ).changed();
+                            ui.label("Release:");
+                            changed |= ui.add(egui::DragValue::new(&mut job.release_time).range(0.0..=100_000.0).speed(0.5)).changed();
+                            let mut has_due_date = job.due_date.is_some();
+                            if ui.checkbox(&mut has_due_date, "Due:").changed() {
+                                job.due_date = has_due_date.then(|| job.earliest_completion());
+                                changed = true;
+                            }
+                            if let Some(due_date) = &mut job.due_date {
+                                changed |= ui.add(egui::DragValue::new(due_date).range(0.0..=100_000.0).speed(0.5)).changed();
+                            }
+                            ui.label("Priority:").on_hover_text(
+                                "Weight of the job in weighted objectives and dispatching; jobs above the average get a bold border in the Gantt chart",
+                            );
+                            changed |= ui.add(egui::DragValue::new(&mut job.weight).range(0.0..=1000.0).speed(0.1)).changed();
+                            ui.separator();
+                            if ui.button("Add Operation").clicked() {
+                                edit = Some(Edit::AddOperation(job_index));
+                            }
+                            if ui.button("Remove Job").clicked() {
+                                edit = Some(Edit::RemoveJob(job_index));
+                            }
+                        });
+
+                        let last = job.operations.len().saturating_sub(1);
+                        egui::Grid::new(("instance_editor_grid", job_index))
+                            .striped(true)
+                            .num_columns(5)
+                            .show(ui, |ui| {
+                                for header in ["Op", "Machine", "Duration", "Alternative Machines", ""] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+
+                                for (op_index, op) in job.operations.iter_mut().enumerate() {
+                                    ui.label(op_index.to_string());
+                                    changed |= ui.add(egui::DragValue::new(&mut op.machine_id).range(0..=num_machines - 1).prefix("M")).changed();
+                                    changed |= ui.add(egui::DragValue::new(&mut op.duration).range(0.0..=10_000.0).speed(0.5)).changed();
+                                    ui.horizontal(|ui| {
+                                        for (alt_index, alternative) in op.alternatives.iter_mut().enumerate() {
+                                            changed |= ui.add(egui::DragValue::new(&mut alternative.machine_id).range(0..=num_machines - 1).prefix("M")).changed();
+                                            changed |= ui.add(egui::DragValue::new(&mut alternative.duration).range(0.0..=10_000.0).speed(0.5)).changed();
+                                            if ui.small_button("✖").on_hover_text("Remove this alternative").clicked() {
+                                                edit = Some(Edit::RemoveAlternative(job_index, op_index, alt_index));
+                                            }
+                                        }
+                                        if ui.small_button("+").on_hover_text("Add an alternative machine (flexible job shop)").clicked() {
+                                            edit = Some(Edit::AddAlternative(job_index, op_index));
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        if ui.add_enabled(op_index > 0, egui::Button::new("⏶")).on_hover_text("Move up").clicked() {
+                                            edit = Some(Edit::MoveOperation { job: job_index, operation: op_index, up: true });
+                                        }
+                                        if ui.add_enabled(op_index < last, egui::Button::new("⏷")).on_hover_text("Move down").clicked() {
+                                            edit = Some(Edit::MoveOperation { job: job_index, operation: op_index, up: false });
+                                        }
+                                        if ui.button("Remove").clicked() {
+                                            edit = Some(Edit::RemoveOperation(job_index, op_index));
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                values_changed |= changed;
+            }
+        });
+
+        if let Some(edit) = edit {
+            editor.apply(edit);
+        } else if values_changed {
+            editor.changed();
+        }
+
+        if revert {
+            let editor = &mut self.instance_editor;
+            editor.load(self.solver.as_ref().map(|solver| solver.instance()), self.num_machines, problem_id);
+        }
+        if export {
+            self.export_edited_instance();
+        }
+        if apply {
+            match self.instance_editor.build(self.solver.as_ref().map(|solver| solver.instance())) {
+                Ok(instance) => {
+                    self.remember("Edit Instance");
+                    self.replace_problem(Some(instance.into()));
+                    let editor = &mut self.instance_editor;
+                    editor.loaded_from = Some(self.history.problem_id);
+                    editor.modified = false;
+                    editor.status = Some("Applied. Solve the edited problem on the Schedule tab.".to_string());
+                }
+                Err(e) => self.instance_editor.error = Some(e),
+            }
+        }
+    }
+
+    fn export_edited_instance(&mut self) {
+        let instance = match self.instance_editor.build(self.solver.as_ref().map(|solver| solver.instance())) {
+            Ok(instance) => instance,
+            Err(e) => {
+                self.instance_editor.error = Some(e);
+                return;
+            }
+        };
+        let file_name = if instance.name.is_empty() { "instance.json".to_string() } else { format!("{}.json", instance.name) };
+        let Some(path) = self.save_path(&file_name, &[("JSON", &["json"])]) else {
+            return;
+        };
+        let saved = serde_json::to_string_pretty(&to_ortools_json(&instance, None))
+            .map_err(|e| JsspError::Serialization(format!("Failed to serialize JSON: {}", e)))
+            .and_then(|text| write_file(&path, text.as_bytes()));
+        self.report_export("instance", saved);
+    }
+}
@@ -0,0 +1,90 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Plot};
+
+use super::JsspApp;
+use crate::jssp::bounds::{estimate_greedy_upper_bound, estimate_lower_bound};
+use crate::jssp::profile::Difficulty;
+
+impl JsspApp {
+    /// Work per machine and job, duration spread, lower bound and a difficulty estimate
+    /// of the loaded problem, to help choose an algorithm before solving
+    pub(super) fn render_instance_statistics(&self, ui: &mut egui::Ui) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        egui::CollapsingHeader::new("Instance Statistics")
+            .id_salt("instance_statistics")
+            .show(ui, |ui| {
+                let profile = solver.profile();
+                let time = solver.time_mode();
+
+                let color = match profile.difficulty {
+                    Difficulty::Easy => egui::Color32::from_rgb(60, 170, 80),
+                    Difficulty::Moderate => egui::Color32::from_rgb(200, 170, 40),
+                    Difficulty::Hard => egui::Color32::from_rgb(230, 120, 40),
+                    Difficulty::VeryHard => egui::Color32::from_rgb(210, 60, 60),
+                };
+                ui.horizontal(|ui| {
+                    ui.label("Estimated difficulty:");
+                    ui.colored_label(color, profile.difficulty.name()).on_hover_text(profile.reason(solver));
+                });
+                ui.weak(profile.difficulty.advice());
+
+                egui::Grid::new("instance_statistics_grid").striped(true).num_columns(2).show(ui, |ui| {
+                    let bounds = &profile.lower_bounds;
+                    ui.label("Lower bound");
+                    ui.label(time.format(bounds.best())).on_hover_text(format!(
+                        "No schedule can finish earlier.\n\nJob length: {}\nMachine load: {}\nOne-machine relaxation: {}\nPooled machines: {}",
+                        time.format(bounds.job_length), time.format(bounds.machine_load),
+                        time.format(bounds.one_machine), time.format(bounds.pooled)
+                    ));
+                    ui.end_row();
+                    ui.label("Makespan range");
+                    ui.label(format!("{} to {}", time.format(estimate_lower_bound(solver)), time.format(estimate_greedy_upper_bound(solver))))
+                        .on_hover_text(
+                            "Quick estimates without solving: the cheap lower bounds, and the makespan a greedy dispatcher \
+                            reaches (ignoring downtime, working hours, secondary resources and buffer limits)",
+                        );
+                    ui.end_row();
+                    ui.label("Load imbalance");
+                    let busiest = profile.busiest_machine().map(|machine_id| solver.machine_name(machine_id)).unwrap_or_default();
+                    ui.label(format!("{:.2}× the mean machine work ({})", profile.load_imbalance, busiest))
+                        .on_hover_text("Work of the busiest machine over the mean; near 1 no single machine is a bottleneck");
+                    ui.end_row();
+                    ui.label("Durations");
+                    ui.label(format!(
+                        "{} to {}, mean {:.2}, std dev {:.2} (CV {:.2})",
+                        time.format(profile.shortest_duration), time.format(profile.longest_duration),
+                        profile.duration_mean, profile.duration_std_dev, profile.duration_variation()
+                    ));
+                    ui.end_row();
+                });
+
+                let work_chart = |ui: &mut egui::Ui, id: &str, axis: &str, work: &[f64], name: &dyn Fn(usize) -> String| {
+                    let bars: Vec<Bar> = work.iter()
+                        .enumerate()
+                        .map(|(index, &work)| Bar::new(index as f64, work).name(name(index)).width(0.6))
+                        .collect();
+                    Plot::new(id)
+                        .height(120.0)
+                        .include_y(0.0)
+                        .x_axis_label(axis)
+                        .y_axis_label("Work")
+                        .allow_drag(false)
+                        .allow_zoom(false)
+                        .allow_scroll(false)
+                        .show(ui, |plot_ui| {
+                            plot_ui.bar_chart(
+                                BarChart::new(bars)
+                                    .color(egui::Color32::from_rgb(70, 130, 180))
+                                    .element_formatter(Box::new(move |bar, _| format!("{}\n{}", bar.name, time.format(bar.value)))),
+                            );
+                        });
+                };
+                ui.label("Total work per machine");
+                work_chart(ui, "instance_machine_work", "Machine", &profile.machine_work, &|machine_id| solver.machine_name(machine_id));
+                ui.label("Processing time per job");
+                work_chart(ui, "instance_job_work", "Job", &profile.job_work, &|job_id| solver.job_name(job_id));
+            });
+    }
+}
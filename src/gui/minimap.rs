@@ -0,0 +1,143 @@
+use eframe::egui;
+use egui_plot::{PlotBounds, PlotUi};
+
+use super::gantt_edit::{GanttRows, BLOCK_HEIGHT};
+use super::JsspApp;
+use crate::jssp::ScheduledOperation;
+
+/// Height of the overview strip below the Gantt chart
+const MINIMAP_HEIGHT: f32 = 48.0;
+/// How close (in points) the pointer must be to an operation boundary to snap to it
+const SNAP_DISTANCE: f64 = 6.0;
+
+/// The time under the pointer in the Gantt chart, kept after the pointer leaves
+#[derive(Clone, Copy)]
+pub(super) struct CursorReadout {
+    pub time: f64,
+    /// The operation boundary the time snapped to: (job, operation, whether it is the end)
+    pub boundary: Option<(usize, usize, bool)>,
+}
+
+impl JsspApp {
+    /// The area the Fit button frames: every operation with its setup, and every row
+    pub(super) fn gantt_extent(&self) -> PlotBounds {
+        let visible = self.visibility();
+        let (mut from, mut to, mut rows) = (0.0_f64, 1.0_f64, 1.0_f64);
+        for op in self.schedule.iter().filter(|op| visible(op)) {
+            from = from.min(op.setup_start());
+            to = to.max(op.end_time);
+            rows = rows.max(self.gantt_row(op) + 1.0);
+        }
+        let resource_rows = self.resource_rows().len();
+        if resource_rows > 0 {
+            rows = rows.max((self.instance_machines() + resource_rows) as f64);
+        }
+        let mut extent = PlotBounds::from_min_max([from, -0.5], [to, rows - 0.5]);
+        extent.add_relative_margin_x(egui::vec2(0.02, 0.02));
+        extent
+    }
+
+    /// The time under the pointer, snapped to the nearest start or end of a visible
+    /// operation within a few points (else to a whole unit in integer mode), marked in
+    /// the plot by a vertical line
+    pub(super) fn track_gantt_cursor(&self, plot_ui: &mut PlotUi) -> Option<CursorReadout> {
+        let pointer = plot_ui.pointer_coordinate()?;
+        let tolerance = SNAP_DISTANCE / plot_ui.transform().dpos_dvalue_x();
+        let visible = self.visibility();
+        let boundary = self.schedule.iter()
+            .filter(|op| visible(op))
+            .flat_map(|op| [(op, op.start_time, false), (op, op.end_time, true)])
+            .map(|(op, time, end)| ((time - pointer.x).abs(), time, (op.job_id, op.operation_id, end)))
+            .filter(|(distance, _, _)| *distance <= tolerance)
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        let readout = match boundary {
+            Some((_, time, boundary)) => CursorReadout { time, boundary: Some(boundary) },
+            None => CursorReadout { time: self.time_mode().snap(pointer.x), boundary: None },
+        };
+        let color = if readout.boundary.is_some() { egui::Color32::from_rgb(0, 140, 220) } else { egui::Color32::GRAY };
+        plot_ui.vline(egui_plot::VLine::new(readout.time).color(color).style(egui_plot::LineStyle::dashed_dense()).name(""));
+        Some(readout)
+    }
+
+    /// "Cursor: 12 | end of Job 3 Op 2", with the date if a calendar is attached
+    pub(super) fn render_cursor_readout(&self, ui: &mut egui::Ui) {
+        let Some(readout) = self.gantt_cursor else {
+            ui.weak("Cursor: point at the chart to read the time");
+            return;
+        };
+        let mut text = format!("Cursor: {}", self.time_mode().format(readout.time));
+        if let Some(calendar) = self.solver.as_ref().and_then(|solver| solver.calendar()) {
+            text.push_str(&format!(" ({})", calendar.to_datetime(readout.time).format("%Y-%m-%d %H:%M")));
+        }
+        if let Some((job_id, operation_id, end)) = readout.boundary {
+            text.push_str(&format!(" | {} of {} Op {}", if end { "end" } else { "start" }, self.job_name(job_id), operation_id));
+        }
+        ui.monospace(text);
+    }
+
+    /// The whole schedule in a thin strip, with the part the chart shows outlined.
+    /// Clicking or dragging in it moves the chart's view there.
+    pub(super) fn render_gantt_minimap(
+        &mut self,
+        ui: &mut egui::Ui,
+        view: &PlotBounds,
+        color_of: impl Fn(&ScheduledOperation) -> egui::Color32,
+    ) {
+        let extent = self.gantt_extent();
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), MINIMAP_HEIGHT), egui::Sense::click_and_drag());
+        let x_of = |time: f64| rect.left() + ((time - extent.min()[0]) / extent.width()) as f32 * rect.width();
+        // Row 0 at the bottom, like in the chart
+        let y_of = |row: f64| rect.bottom() - ((row - extent.min()[1]) / extent.height()) as f32 * rect.height();
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        // One mesh, as the chart does, so long schedules stay cheap to draw
+        let mesh = {
+            let mut mesh = egui::Mesh::default();
+            if let (Some(solver), GanttRows::Machines) = (&self.solver, self.gantt_rows) {
+                for window in solver.downtime() {
+                    let row = window.machine_id as f64;
+                    let band = egui::Rect::from_min_max(
+                        egui::pos2(x_of(window.start), y_of(row + 0.5)),
+                        egui::pos2(x_of(window.end), y_of(row - 0.5)),
+                    );
+                    mesh.add_colored_rect(band, egui::Color32::from_gray(110).gamma_multiply(0.35));
+                }
+            }
+            let visible = self.visibility();
+            for op in self.schedule.iter().filter(|op| visible(op)) {
+                let row = self.gantt_row(op);
+                for (from, to) in op.intervals() {
+                    let left = x_of(from);
+                    let block = egui::Rect::from_min_max(
+                        egui::pos2(left, y_of(row + BLOCK_HEIGHT / 2.0)),
+                        egui::pos2(x_of(to).max(left + 1.0), y_of(row - BLOCK_HEIGHT / 2.0)),
+                    );
+                    mesh.add_colored_rect(block, color_of(op));
+                }
+            }
+            mesh
+        };
+        painter.add(egui::Shape::mesh(mesh));
+
+        let shown = egui::Rect::from_x_y_ranges(x_of(view.min()[0])..=x_of(view.max()[0]), rect.y_range()).intersect(rect);
+        let stroke_color = ui.visuals().strong_text_color();
+        painter.rect(shown, 2.0, stroke_color.gamma_multiply(0.1), egui::Stroke::new(1.5, stroke_color));
+
+        if let Some(pos) = response.interact_pointer_pos().filter(|_| response.clicked() || response.dragged()) {
+            let time = extent.min()[0] + f64::from((pos.x - rect.left()) / rect.width()) * extent.width();
+            self.gantt_navigation.center_on(time);
+            ui.ctx().request_repaint();
+        }
+        response.on_hover_text("Click or drag to move the chart's view; the outline marks what it shows");
+    }
+
+    /// Fit button and minimap switch, next to the chart
+    pub(super) fn render_gantt_view_controls(&mut self, ui: &mut egui::Ui) {
+        if ui.button("↔ Fit").on_hover_text("Frame the whole schedule (Home)").clicked() {
+            self.gantt_navigation.fit();
+        }
+        ui.checkbox(&mut self.show_minimap, "Minimap")
+            .on_hover_text("An overview strip below the chart for moving around long schedules");
+    }
+}
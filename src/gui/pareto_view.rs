@@ -0,0 +1,154 @@
+use eframe::egui;
+use egui_plot::{Plot, PlotPoint, Points};
+
+use super::JsspApp;
+use crate::jssp::pareto::explore_pareto_front;
+use crate::jssp::Objective;
+
+impl JsspApp {
+    pub(super) fn render_pareto_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_pareto_window;
+        egui::Window::new("Pareto Front Explorer")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for (axis, label) in ["X Objective:", "Y Objective:"].iter().enumerate() {
+                        ui.label(*label);
+                        egui::ComboBox::from_id_salt(("pareto_objective", axis))
+                            .selected_text(self.pareto_config.objectives[axis].name())
+                            .show_ui(ui, |ui| {
+                                for objective in Objective::ALL {
+                                    ui.selectable_value(&mut self.pareto_config.objectives[axis], objective, objective.name());
+                                }
+                            });
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Population:");
+                    ui.add(egui::Slider::new(&mut self.pareto_config.population_size, 10..=200));
+                    ui.label("Generations:");
+                    ui.add(egui::Slider::new(&mut self.pareto_config.generations, 1..=500));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut self.pareto_config.seed));
+
+                    let can_run = self.solver.is_some()
+                        && self.pareto_config.objectives[0] != self.pareto_config.objectives[1];
+                    if ui.add_enabled(can_run, egui::Button::new("Run NSGA-II")).clicked() {
+                        if let Some(solver) = &self.solver {
+                            self.pareto_front = Some(explore_pareto_front(solver, &self.pareto_config));
+                            self.selected_pareto = None;
+                        }
+                    }
+                });
+
+                if self.pareto_config.objectives[0] == self.pareto_config.objectives[1] {
+                    ui.colored_label(egui::Color32::YELLOW, "Choose two different objectives.");
+                }
+
+                ui.separator();
+
+                let Some(front) = &self.pareto_front else {
+                    ui.colored_label(egui::Color32::GRAY, "Run NSGA-II to compute a Pareto front.");
+                    return;
+                };
+
+                ui.label(format!("{} non-dominated schedule(s). Click a point to load it.", front.solutions.len()));
+
+                let points: Vec<[f64; 2]> = front.solutions.iter()
+                    .map(|s| [s.values[0], s.values[1]])
+                    .collect();
+                let selected = self.selected_pareto;
+
+                let plot_response = Plot::new("pareto_plot")
+                    .height(300.0)
+                    .x_axis_label(front.objectives[0].name())
+                    .y_axis_label(front.objectives[1].name())
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(
+                            egui_plot::Line::new(points.clone())
+                                .color(egui::Color32::from_gray(120))
+                                .allow_hover(false)
+                        );
+                        plot_ui.points(
+                            Points::new(points.clone())
+                                .radius(5.0)
+                                .color(egui::Color32::from_rgb(70, 130, 180))
+                                .name("Pareto front")
+                        );
+                        if let Some(index) = selected {
+                            plot_ui.points(
+                                Points::new(vec![points[index]])
+                                    .radius(8.0)
+                                    .filled(false)
+                                    .color(egui::Color32::RED)
+                            );
+                        }
+                    });
+
+                // Load the point closest to the click, if it is near enough
+                let mut clicked = None;
+                if plot_response.response.clicked() {
+                    if let Some(pointer) = plot_response.response.interact_pointer_pos() {
+                        clicked = points.iter()
+                            .enumerate()
+                            .map(|(i, p)| {
+                                let screen = plot_response.transform.position_from_point(&PlotPoint::new(p[0], p[1]));
+                                (i, screen.distance(pointer))
+                            })
+                            .filter(|(_, distance)| *distance < 12.0)
+                            .min_by(|a, b| a.1.total_cmp(&b.1))
+                            .map(|(i, _)| i);
+                    }
+                }
+
+                egui::ScrollArea::vertical()
+                    .id_salt("pareto_solutions")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        for (i, solution) in front.solutions.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let text = format!(
+                                    "#{}  {}: {:.2}  |  {}: {:.2}",
+                                    i + 1,
+                                    front.objectives[0].name(),
+                                    solution.values[0],
+                                    front.objectives[1].name(),
+                                    solution.values[1]
+                                );
+                                if ui.selectable_label(selected == Some(i), text).clicked() {
+                                    clicked = Some(i);
+                                }
+                            });
+                        }
+                    });
+
+                if let Some(index) = clicked {
+                    self.load_pareto_solution(index);
+                }
+            });
+        self.show_pareto_window = open;
+    }
+
+    fn load_pareto_solution(&mut self, index: usize) {
+        let Some(solution) = self.pareto_front.as_ref().and_then(|front| front.solutions.get(index)) else {
+            return;
+        };
+        let schedule = solution.schedule.clone();
+        self.remember("Load Pareto Solution");
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        self.makespan = solver.calculate_makespan(&schedule);
+        self.violations = solver.validate(&schedule).err().unwrap_or_default();
+        self.schedule = schedule;
+        self.run = None;
+        self.selected_pareto = Some(index);
+        self.swap_suggestions = None;
+        self.record_kpis();
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui_plot::{PlotUi, VLine};
+
+use super::JsspApp;
+use crate::jssp::ScheduledOperation;
+
+/// Seconds a 1x playback takes to sweep the whole schedule
+const PLAYBACK_SECONDS: f64 = 20.0;
+
+/// State of the time cursor sweeping through the Gantt chart
+pub(super) struct Playback {
+    pub(super) enabled: bool,
+    pub(super) playing: bool,
+    pub(super) time: f64,
+    /// Multiple of the base speed, which plays the schedule in `PLAYBACK_SECONDS`
+    pub(super) speed: f64,
+    /// Leave out jobs that have not been released by the cursor time, as in a shop
+    /// where jobs arrive over time
+    pub(super) hide_unreleased: bool,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self { enabled: false, playing: false, time: 0.0, speed: 1.0, hide_unreleased: false }
+    }
+}
+
+/// Where an operation stands at the playback time
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum OperationState {
+    Done,
+    /// Setting up or processing
+    Running,
+    Pending,
+}
+
+impl JsspApp {
+    /// The cursor time while playback mode is on
+    pub(super) fn playback_time(&self) -> Option<f64> {
+        self.playback.enabled.then_some(self.playback.time)
+    }
+
+    /// Whether playback hides the job because it is not released at the cursor time
+    pub(super) fn hidden_by_playback(&self, job_id: usize) -> bool {
+        let Some(solver) = self.solver.as_ref().filter(|_| self.playback.enabled && self.playback.hide_unreleased) else {
+            return false;
+        };
+        solver.jobs().get(job_id).is_some_and(|job| job.release_time > self.playback.time)
+    }
+
+    pub(super) fn operation_state(op: &ScheduledOperation, time: f64) -> OperationState {
+        if op.end_time <= time {
+            OperationState::Done
+        } else if op.setup_start() <= time {
+            OperationState::Running
+        } else {
+            OperationState::Pending
+        }
+    }
+
+    /// Play/pause, restart, speed and a scrubber for the cursor; advances it while playing
+    pub(super) fn render_playback_controls(&mut self, ui: &mut egui::Ui) {
+        if !self.playback.enabled {
+            return;
+        }
+        let makespan = self.makespan.max(0.0);
+
+        if self.playback.playing {
+            let dt = ui.input(|i| i.stable_dt).min(0.1) as f64;
+            self.playback.time += dt * self.playback.speed * makespan / PLAYBACK_SECONDS;
+            if self.playback.time >= makespan {
+                self.playback.time = makespan;
+                self.playback.playing = false;
+            }
+            ui.ctx().request_repaint();
+        }
+
+        ui.horizontal(|ui| {
+            let label = if self.playback.playing { "⏸ Pause" } else { "▶ Play" };
+            if ui.add_sized([100.0, 28.0], egui::Button::new(label)).clicked() {
+                if !self.playback.playing && self.playback.time >= makespan {
+                    self.playback.time = 0.0;
+                }
+                self.playback.playing = !self.playback.playing;
+            }
+            if ui.add_sized([100.0, 28.0], egui::Button::new("⏮ Restart")).clicked() {
+                self.playback.time = 0.0;
+            }
+            ui.label("Speed:");
+            ui.add(
+                egui::Slider::new(&mut self.playback.speed, 0.1..=10.0)
+                    .logarithmic(true)
+                    .suffix("x")
+            ).on_hover_text(format!("At 1x the whole schedule plays in {} seconds", PLAYBACK_SECONDS));
+            ui.label("Time:");
+            ui.add(egui::Slider::new(&mut self.playback.time, 0.0..=makespan).fixed_decimals(1));
+            ui.checkbox(&mut self.playback.hide_unreleased, "Hide Unreleased Jobs")
+                .on_hover_text("Show jobs only from their release time on");
+        });
+    }
+
+    pub(super) fn draw_playback_cursor(&self, plot_ui: &mut PlotUi) {
+        if let Some(time) = self.playback_time() {
+            plot_ui.vline(
+                VLine::new(time)
+                    .color(egui::Color32::YELLOW)
+                    .width(2.0)
+                    .name(format!("Time {:.1}", time))
+            );
+        }
+    }
+
+    /// Per machine, the operation in progress and the operations whose job is waiting for it
+    pub(super) fn render_machine_queues(&self, ui: &mut egui::Ui) {
+        let Some(time) = self.playback_time() else {
+            return;
+        };
+        let release: HashMap<usize, f64> = self.solver.as_ref()
+            .map(|solver| solver.jobs().iter().map(|job| (job.id, job.release_time)).collect())
+            .unwrap_or_default();
+        let end_of: HashMap<(usize, usize), f64> = self.schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.end_time))
+            .collect();
+        // The operation each job processes before another, in schedule order
+        let mut job_previous: HashMap<(usize, usize), usize> = HashMap::new();
+        if let Some(solver) = &self.solver {
+            for (job_id, order) in solver.job_sequences(&self.schedule).into_iter().enumerate() {
+                job_previous.extend(order.windows(2).map(|pair| ((job_id, pair[1]), pair[0])));
+            }
+        }
+        // Ready once the job is released and its previous operation has finished
+        let is_ready = |op: &ScheduledOperation| {
+            release.get(&op.job_id).copied().unwrap_or(0.0) <= time
+                && job_previous.get(&(op.job_id, op.operation_id))
+                    .is_none_or(|&previous| end_of.get(&(op.job_id, previous)).is_some_and(|end| *end <= time))
+        };
+
+        egui::CollapsingHeader::new(format!("Machine Queues at t = {:.1}", time))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("machine_queues")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        ui.strong("Machine");
+                        ui.strong("Running");
+                        ui.strong("Queue (in planned order)");
+                        ui.end_row();
+
+                        for machine_id in 0..self.instance_machines() {
+                            let mut ops: Vec<&ScheduledOperation> = self.schedule.iter()
+                                .filter(|op| op.machine_id == machine_id)
+                                .collect();
+                            ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+                            let running = ops.iter()
+                                .find(|op| Self::operation_state(op, time) == OperationState::Running)
+                                .map(|op| {
+                                    let phase = if op.start_time > time { " (setup)" } else { "" };
+                                    format!("J{}-O{}{}", op.job_id, op.operation_id, phase)
+                                })
+                                .unwrap_or_else(|| "idle".to_string());
+                            let queue: Vec<String> = ops.iter()
+                                .filter(|op| Self::operation_state(op, time) == OperationState::Pending && is_ready(op))
+                                .map(|op| format!("J{}-O{}", op.job_id, op.operation_id))
+                                .collect();
+
+                            ui.label(self.machine_name(machine_id));
+                            ui.label(running);
+                            ui.label(if queue.is_empty() { "-".to_string() } else { queue.join(", ") });
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
@@ -0,0 +1,133 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::pool::SolutionPool;
+use crate::jssp::Objective;
+
+/// The solution pool of the last single solve, with what the window shows of it
+pub(super) struct PoolResult {
+    pool: SolutionPool,
+    objective: Objective,
+    makespans: Vec<f64>,
+    /// `order_distance` of every schedule from the best
+    distances: Vec<usize>,
+    /// Rank of the schedule last put into the Gantt chart
+    loaded: Option<usize>,
+}
+
+impl PoolResult {
+    /// The pool of a run on `objective`, whose best schedule is the one shown
+    pub(super) fn new(pool: SolutionPool, objective: Objective) -> Self {
+        let makespans = pool.entries().iter()
+            .map(|entry| entry.schedule.iter().map(|op| op.end_time).fold(0.0, f64::max))
+            .collect();
+        let distances = pool.distances();
+        let loaded = (!pool.is_empty()).then_some(0);
+        Self { pool, objective, makespans, distances, loaded }
+    }
+}
+
+impl JsspApp {
+    /// Show the schedule of `rank` in the Gantt chart, if it still fits the problem
+    fn load_pool_solution(&mut self, rank: usize) {
+        let Some(schedule) = self.solution_pool.as_ref().and_then(|result| result.pool.entries().get(rank)).map(|entry| entry.schedule.clone()) else {
+            return;
+        };
+        if let Some(Err(violations)) = self.solver.as_ref().map(|solver| solver.validate(&schedule)) {
+            self.edit_status = Some(Err(format!("The pooled schedule no longer fits the problem: {}", violations[0])));
+            return;
+        }
+        self.remember("Load Pool Solution");
+        self.apply_edited_schedule(schedule);
+        if let Some(result) = &mut self.solution_pool {
+            result.loaded = Some(rank);
+        }
+    }
+
+    pub(super) fn render_solution_pool_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_solution_pool_window;
+        let mut load = None;
+        egui::Window::new("Solution Pool")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let Some(result) = &self.solution_pool else {
+                    ui.label("Check \"Keep solution pool\" and solve with an algorithm that supports it, such as \
+                              Simulated Annealing or the genetic algorithms, to keep the best distinct schedules \
+                              the search came across.");
+                    return;
+                };
+                if result.pool.is_empty() {
+                    ui.label("The run found no schedule for the pool.");
+                    return;
+                }
+
+                // Diversity: how far the alternatives are from the best, and what they cost
+                let time = self.time_mode();
+                let alternatives = &result.distances[1..];
+                let values: Vec<f64> = result.pool.entries().iter().map(|entry| entry.value).collect();
+                ui.label(format!(
+                    "{} distinct schedules, {} from {:.2} to {:.2}.",
+                    values.len(),
+                    result.objective.name(),
+                    values[0],
+                    values[values.len() - 1]
+                ));
+                if !alternatives.is_empty() {
+                    ui.label(format!(
+                        "Distance from the best: mean {:.1}, max {}.",
+                        alternatives.iter().sum::<usize>() as f64 / alternatives.len() as f64,
+                        alternatives.iter().max().copied().unwrap_or(0)
+                    ));
+                }
+                ui.label("The distance counts operations on another machine plus pairs of operations in the \
+                          opposite order on their machine.")
+                    .on_hover_text("0 would be the same machine orders; the pool keeps no two schedules that are");
+
+                ui.separator();
+                let show_objective = result.objective != Objective::Makespan;
+                egui::ScrollArea::vertical().id_salt("solution_pool").max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("solution_pool_grid").striped(true).num_columns(if show_objective { 5 } else { 4 }).show(ui, |ui| {
+                        ui.strong("Rank");
+                        ui.strong("Makespan");
+                        if show_objective {
+                            ui.strong(result.objective.name());
+                        }
+                        ui.strong("Distance");
+                        ui.label("");
+                        ui.end_row();
+                        for (rank, entry) in result.pool.entries().iter().enumerate() {
+                            ui.label(format!("#{}", rank + 1));
+                            ui.label(time.format(result.makespans[rank]));
+                            if show_objective {
+                                ui.label(format!("{:.2}", entry.value));
+                            }
+                            ui.label(result.distances[rank].to_string());
+                            if result.loaded == Some(rank) {
+                                ui.label("shown");
+                            } else if ui.small_button("Load").on_hover_text("Show this schedule in the Gantt chart").clicked() {
+                                load = Some(rank);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_solution_pool_window = open;
+        if let Some(rank) = load {
+            self.load_pool_solution(rank);
+        }
+    }
+
+    /// Keep the pool of a finished solve, unless it is empty because the run kept none
+    pub(super) fn set_solution_pool(&mut self, pool: SolutionPool, objective: Objective) {
+        self.solution_pool = (pool.capacity() > 0).then(|| PoolResult::new(pool, objective));
+    }
+
+    /// Capacity of the pool for the next single solve, by whether its algorithm keeps
+    /// one; 0 keeps none
+    pub(super) fn solution_pool_capacity(&self, supports_pool: bool) -> usize {
+        if self.keep_solution_pool && supports_pool { self.solution_pool_size.max(2) } else { 0 }
+    }
+}
+
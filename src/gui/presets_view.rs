@@ -0,0 +1,161 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::presets::{self, AlgorithmParams, Parameter, Presets};
+
+/// The parameter presets and the values the next solve uses
+pub(super) struct PresetState {
+    presets: Presets,
+    /// Name of the chosen preset
+    pub name: String,
+    /// The chosen preset's values, possibly changed for the next runs
+    pub params: AlgorithmParams,
+    status: Option<Result<String, String>>,
+}
+
+impl Default for PresetState {
+    fn default() -> Self {
+        Self {
+            presets: Presets::default(),
+            name: presets::DEFAULT_PRESET.to_string(),
+            params: AlgorithmParams::default(),
+            status: None,
+        }
+    }
+}
+
+impl PresetState {
+    /// Whether the values differ from the chosen preset
+    fn is_modified(&self) -> bool {
+        self.presets.get(&self.name) != Some(&self.params)
+    }
+
+    /// Switch to a preset by name, dropping any changed values; unknown names are ignored
+    pub fn choose(&mut self, name: &str) {
+        if let Some(params) = self.presets.get(name) {
+            self.name = name.to_string();
+            self.params = params.clone();
+        }
+    }
+
+    /// Read the presets file again, keeping the chosen preset if it is still there
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload(&mut self) {
+        let Some(path) = Presets::default_path() else {
+            return;
+        };
+        match Presets::load(&path) {
+            Ok(presets) => {
+                self.presets = presets;
+                let name = if self.presets.get(&self.name).is_some() { self.name.clone() } else { presets::DEFAULT_PRESET.to_string() };
+                self.choose(&name);
+                self.status = path.exists().then(|| Ok(format!("Loaded {}", path.display())));
+            }
+            Err(e) => self.status = Some(Err(e.to_string())),
+        }
+    }
+
+    /// Write the presets as a file to edit, unless one exists already
+    #[cfg(not(target_arch = "wasm32"))]
+    fn create_file(&mut self) {
+        let Some(path) = Presets::default_path() else {
+            self.status = Some(Err("No folder to keep the presets file in".to_string()));
+            return;
+        };
+        let result = path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&path, self.presets.to_toml()));
+        self.status = Some(match result {
+            Ok(()) => Ok(format!("Created {}; edit it and click Reload", path.display())),
+            Err(e) => Err(format!("Failed to write {}: {}", path.display(), e)),
+        });
+    }
+}
+
+impl JsspApp {
+    /// The preset drop-down and a popover with the selected algorithm's parameters
+    pub(super) fn render_preset_controls(&mut self, ui: &mut egui::Ui) {
+        let before = self.presets.params.clone();
+        ui.label("Preset:");
+        let selected = if self.presets.is_modified() { format!("{} (modified)", self.presets.name) } else { self.presets.name.clone() };
+        let mut chosen = None;
+        egui::ComboBox::from_id_salt("preset")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for name in self.presets.presets.names() {
+                    if ui.selectable_label(name == self.presets.name, name).clicked() {
+                        chosen = Some(name.to_string());
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Parameter set of the metaheuristics: fast, balanced, quality or one from the presets file");
+        if let Some(name) = chosen {
+            self.presets.choose(&name);
+        }
+
+        let button = ui.small_button("⚙").on_hover_text("Change the parameters of the selected algorithm for the next runs");
+        let popup = ui.make_persistent_id("preset_parameters");
+        if button.clicked() {
+            ui.memory_mut(|memory| memory.toggle_popup(popup));
+        }
+        let algorithm = self.selected_solver_name();
+        let state = &mut self.presets;
+        egui::popup_below_widget(ui, popup, &button, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+            ui.set_min_width(320.0);
+            ui.strong(format!("{}: preset '{}'", algorithm, state.name));
+            let mut parameters = Parameter::of_algorithm(&algorithm).peekable();
+            if parameters.peek().is_none() {
+                ui.label("This algorithm has no parameters to tune.");
+            }
+            egui::Grid::new("preset_parameters_grid").num_columns(2).show(ui, |ui| {
+                for parameter in parameters {
+                    let mut value = state.params.value(parameter.key).unwrap_or_default();
+                    let speed = value * 0.01;
+                    ui.label(parameter.label);
+                    let drag = if parameter.integer {
+                        egui::DragValue::new(&mut value).range(1.0..=100_000_000.0).speed(speed.max(1.0)).max_decimals(0)
+                    } else {
+                        egui::DragValue::new(&mut value).range(0.000_01..=3600.0).speed(speed).max_decimals(5)
+                    };
+                    if ui.add(drag).on_hover_text(parameter.description).changed() {
+                        // Out-of-range values are clamped by the drag value already
+                        let _ = state.params.set_value(parameter.key, value);
+                    }
+                    ui.end_row();
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.add_enabled(state.is_modified(), egui::Button::new("Reset to Preset")).clicked() {
+                    let name = state.name.clone();
+                    state.choose(&name);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let exists = Presets::default_path().is_some_and(|path| path.exists());
+                    let hover = Presets::default_path().map_or_else(String::new, |path| path.display().to_string());
+                    if exists {
+                        if ui.button("Reload File").on_hover_text(hover).clicked() {
+                            state.reload();
+                        }
+                    } else if ui.button("Create File").on_hover_text(format!("Write the presets to {} for editing", hover)).clicked() {
+                        state.create_file();
+                    }
+                }
+            });
+            match &state.status {
+                Some(Ok(message)) => {
+                    ui.label(message);
+                }
+                Some(Err(message)) => {
+                    ui.colored_label(egui::Color32::RED, message);
+                }
+                None => {}
+            }
+        });
+
+        if self.presets.params != before {
+            self.presets.params.apply(&mut self.solvers);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::time::Duration;
+
+use eframe::egui;
+use egui_plot::{HLine, Legend, Line, Plot, Points};
+use web_time::Instant;
+
+use super::files::write_file;
+use super::JsspApp;
+use crate::jssp::scalability::{ScalabilityTest, SCALABILITY_SIZES};
+use crate::jssp::{Instance, JsspSolver, Schedule};
+
+/// Time one frame may take at 60 fps; drawing the Gantt chart slower than this makes
+/// panning and zooming stutter
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+/// Size of the offscreen frame the Gantt chart is drawn into, like a maximized window
+const RENDER_SIZE: egui::Vec2 = egui::vec2(1600.0, 900.0);
+
+/// Wall-clock time to lay out and tessellate one frame of the Gantt chart of
+/// `schedule`, drawn offscreen by a fresh app
+fn measure_render(instance: Instance, schedule: Schedule) -> Duration {
+    let mut app = JsspApp::with_solution(JsspSolver::new(instance), schedule);
+    let ctx = egui::Context::default();
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, RENDER_SIZE)),
+        ..Default::default()
+    };
+    let frame = |app: &mut JsspApp| {
+        let output = ctx.run(input.clone(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| app.render_gantt_chart(ui));
+        });
+        ctx.tessellate(output.shapes, output.pixels_per_point)
+    };
+    // The first frame also loads fonts and settles the plot bounds
+    frame(&mut app);
+    let started = Instant::now();
+    frame(&mut app);
+    started.elapsed()
+}
+
+impl JsspApp {
+    /// Start solving generated instances of growing size with the selected algorithm
+    pub(super) fn start_scalability_test(&mut self) {
+        let Some(algorithm) = self.solvers.solvers().nth(self.selected_solver) else {
+            return;
+        };
+        self.scalability = Some(ScalabilityTest::new(algorithm, &SCALABILITY_SIZES));
+        self.show_scalability_window = true;
+    }
+
+    /// Solve and draw the next size of a running scalability test, one size per frame
+    pub(super) fn step_scalability(&mut self, ctx: &egui::Context) {
+        let Some(test) = self.scalability.as_mut().filter(|test| !test.is_complete()) else {
+            return;
+        };
+        let Some(algorithm) = self.solvers.get(&test.solver) else {
+            self.scalability = None;
+            return;
+        };
+        if let Some((instance, schedule)) = test.run_next(algorithm) {
+            let render_time = measure_render(instance, schedule);
+            if let Some(point) = test.points.last_mut() {
+                point.render_time = Some(render_time);
+            }
+        }
+        ctx.request_repaint();
+    }
+
+    pub(super) fn render_scalability_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_scalability_window;
+        egui::Window::new("Scalability Test")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                let Some(test) = &self.scalability else {
+                    ui.colored_label(egui::Color32::GRAY, "Run a scalability test from the toolbar to see how the selected algorithm scales.");
+                    return;
+                };
+                let mut cancel = false;
+                if let Some((jobs, machines)) = test.next_size() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.add(
+                            egui::ProgressBar::new(test.progress())
+                                .desired_width(280.0)
+                                .text(format!("{}: solving {} × {}", test.solver, jobs, machines))
+                        );
+                        cancel = ui.button("Cancel").clicked();
+                    });
+                } else {
+                    ui.label(format!("{} on generated instances from {} to {} operations", test.solver,
+                        test.points.first().map_or(0, |point| point.operations), test.points.last().map_or(0, |point| point.operations)));
+                }
+
+                if let Some(point) = test.render_bottleneck(FRAME_BUDGET) {
+                    let render_ms = point.render_time.unwrap_or_default().as_secs_f64() * 1000.0;
+                    ui.colored_label(
+                        egui::Color32::from_rgb(255, 165, 0),
+                        format!(
+                            "⚠ From {} operations ({} × {}) one frame of the Gantt chart takes {:.1} ms, over the {:.1} ms of a 60 fps frame: \
+                             at this size the chart, not the solver, slows the app down. Hiding jobs reduces what is drawn.",
+                            point.operations, point.jobs, point.machines, render_ms, FRAME_BUDGET.as_secs_f64() * 1000.0
+                        ),
+                    );
+                }
+
+                let milliseconds = |duration: Duration| duration.as_secs_f64() * 1000.0;
+                let solve: Vec<[f64; 2]> = test.points.iter()
+                    .map(|point| [point.operations as f64, milliseconds(point.solve_time)])
+                    .collect();
+                let render: Vec<[f64; 2]> = test.points.iter()
+                    .filter_map(|point| Some([point.operations as f64, milliseconds(point.render_time?)]))
+                    .collect();
+                Plot::new("scalability_plot")
+                    .height(240.0)
+                    .legend(Legend::default())
+                    .x_axis_label("Operations")
+                    .y_axis_label("Time (ms)")
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| {
+                        for (series, name, color) in [
+                            (solve, "Solve", egui::Color32::from_rgb(70, 130, 180)),
+                            (render, "Render (per frame)", egui::Color32::from_rgb(220, 90, 90)),
+                        ] {
+                            plot_ui.line(Line::new(series.clone()).color(color).name(name));
+                            plot_ui.points(Points::new(series).radius(3.0).color(color).name(name));
+                        }
+                        plot_ui.hline(
+                            HLine::new(milliseconds(FRAME_BUDGET))
+                                .color(egui::Color32::from_rgb(255, 165, 0))
+                                .style(egui_plot::LineStyle::dashed_loose())
+                                .name("60 fps frame")
+                        );
+                    });
+
+                egui::Grid::new("scalability_grid")
+                    .striped(true)
+                    .num_columns(5)
+                    .show(ui, |ui| {
+                        for header in ["Size", "Operations", "Makespan", "Solve (ms)", "Render (ms)"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+                        for point in &test.points {
+                            ui.label(format!("{} × {}", point.jobs, point.machines));
+                            ui.label(point.operations.to_string());
+                            if point.feasible {
+                                ui.label(format!("{:.2}", point.makespan));
+                            } else {
+                                ui.colored_label(egui::Color32::RED, format!("{:.2} (infeasible)", point.makespan));
+                            }
+                            ui.label(format!("{:.3}", milliseconds(point.solve_time)));
+                            match point.render_time {
+                                Some(render) if render > FRAME_BUDGET => {
+                                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), format!("{:.3}", milliseconds(render)));
+                                }
+                                Some(render) => {
+                                    ui.label(format!("{:.3}", milliseconds(render)));
+                                }
+                                None => {
+                                    ui.label("-");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(8.0);
+                let export = ui.add_enabled(test.is_complete(), egui::Button::new("Export CSV")).clicked();
+                if cancel {
+                    self.scalability = None;
+                } else if export {
+                    self.export_scalability_with_dialog();
+                }
+            });
+        self.show_scalability_window = open;
+    }
+
+    fn export_scalability_with_dialog(&mut self) {
+        let Some(test) = &self.scalability else {
+            return;
+        };
+        let csv = test.to_csv();
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        if let Some(path) = self.save_path(&format!("jssp_scalability_{}.csv", timestamp), &[("CSV", &["csv"])]) {
+            let saved = write_file(&path, csv.as_bytes());
+            self.report_export("scalability results", saved);
+        }
+    }
+}
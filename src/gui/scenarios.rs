@@ -0,0 +1,392 @@
+use eframe::egui;
+
+use super::compare_view::Baseline;
+use super::convergence_view::Convergence;
+use super::dashboard::KpiSnapshot;
+use super::explain_view::ExplainViewer;
+use super::filters::ScheduleFilter;
+use super::gantt_edit::GanttDrag;
+use super::history::History;
+use super::instance_editor::InstanceEditor;
+use super::minimap::CursorReadout;
+use super::playback::Playback;
+use super::selection::Selection;
+use super::shortcuts::GanttNavigation;
+use super::trace_view::TraceViewer;
+use super::JsspApp;
+use crate::jssp::benchmark::Benchmark;
+use crate::jssp::bounds::optimality_gap;
+use crate::jssp::critical_path::SwapSuggestion;
+use crate::jssp::pareto::ParetoFront;
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::repair::RightShiftRepair;
+use crate::jssp::simulation::SimulationReport;
+use crate::jssp::{JsspSolver, Objective, ScheduledOperation, Violation};
+
+/// One open problem with everything derived from it. The scenario being shown lives in
+/// the fields of `JsspApp`, where the rest of the GUI finds it; its entry in
+/// `Scenarios::tabs` only keeps the name until another tab is chosen.
+#[derive(Default)]
+pub(super) struct Scenario {
+    pub name: String,
+    solver: Option<JsspSolver>,
+    schedule: Vec<ScheduledOperation>,
+    makespan: f64,
+    violations: Vec<Violation>,
+    filter: ScheduleFilter,
+    history: History,
+    instance_editor: InstanceEditor,
+    edit_status: Option<Result<String, String>>,
+    selection: Selection,
+    swap_suggestions: Option<Vec<SwapSuggestion>>,
+    kpi_history: Vec<KpiSnapshot>,
+    pareto_front: Option<ParetoFront>,
+    selected_pareto: Option<usize>,
+    simulation_report: Option<SimulationReport>,
+    disruption_repair: Option<RightShiftRepair>,
+    benchmark: Option<Benchmark>,
+    baseline: Option<Baseline>,
+    trace_viewer: TraceViewer,
+    explain_viewer: ExplainViewer,
+    convergence: Convergence,
+    run: Option<RunMetadata>,
+    playback: Playback,
+    gantt_drag: Option<GanttDrag>,
+    gantt_navigation: GanttNavigation,
+    gantt_cursor: Option<CursorReadout>,
+    hovered_block: Option<(usize, usize)>,
+}
+
+impl Scenario {
+    fn named(name: String) -> Self {
+        Self { name, ..Self::default() }
+    }
+}
+
+/// The open scenarios, shown as tabs above the chart
+pub(super) struct Scenarios {
+    pub tabs: Vec<Scenario>,
+    pub active: usize,
+    /// Tab whose name is being edited
+    renaming: Option<usize>,
+    /// Scenarios opened so far, for numbering new ones
+    opened: usize,
+    pub show_compare_window: bool,
+}
+
+impl Default for Scenarios {
+    fn default() -> Self {
+        Self { tabs: vec![Scenario::named("Scenario 1".to_string())], active: 0, renaming: None, opened: 1, show_compare_window: false }
+    }
+}
+
+impl Scenarios {
+    fn next_name(&mut self) -> String {
+        self.opened += 1;
+        format!("Scenario {}", self.opened)
+    }
+}
+
+/// What a click in the tab strip asks for, carried out after drawing it
+enum TabAction {
+    Switch(usize),
+    /// A new tab, empty or with a copy of the shown problem
+    Open { copy: bool },
+    Close(usize),
+}
+
+/// The key figures of one scenario for the comparison table
+struct ScenarioSummary {
+    index: usize,
+    name: String,
+    size: Option<(usize, usize)>,
+    makespan: Option<f64>,
+    gap: Option<f64>,
+    utilization: Option<f64>,
+    objective: Option<f64>,
+    violations: usize,
+}
+
+impl JsspApp {
+    /// Exchange the shown scenario with `scenario`, all but the name
+    fn exchange_scenario(&mut self, scenario: &mut Scenario) {
+        use std::mem::swap;
+        swap(&mut self.solver, &mut scenario.solver);
+        swap(&mut self.schedule, &mut scenario.schedule);
+        swap(&mut self.makespan, &mut scenario.makespan);
+        swap(&mut self.violations, &mut scenario.violations);
+        swap(&mut self.filter, &mut scenario.filter);
+        swap(&mut self.history, &mut scenario.history);
+        swap(&mut self.instance_editor, &mut scenario.instance_editor);
+        swap(&mut self.edit_status, &mut scenario.edit_status);
+        swap(&mut self.selection, &mut scenario.selection);
+        swap(&mut self.swap_suggestions, &mut scenario.swap_suggestions);
+        swap(&mut self.kpi_history, &mut scenario.kpi_history);
+        swap(&mut self.pareto_front, &mut scenario.pareto_front);
+        swap(&mut self.selected_pareto, &mut scenario.selected_pareto);
+        swap(&mut self.simulation_report, &mut scenario.simulation_report);
+        swap(&mut self.disruption_repair, &mut scenario.disruption_repair);
+        swap(&mut self.benchmark, &mut scenario.benchmark);
+        swap(&mut self.baseline, &mut scenario.baseline);
+        swap(&mut self.trace_viewer, &mut scenario.trace_viewer);
+        swap(&mut self.explain_viewer, &mut scenario.explain_viewer);
+        swap(&mut self.convergence, &mut scenario.convergence);
+        swap(&mut self.run, &mut scenario.run);
+        swap(&mut self.playback, &mut scenario.playback);
+        swap(&mut self.gantt_drag, &mut scenario.gantt_drag);
+        swap(&mut self.gantt_navigation, &mut scenario.gantt_navigation);
+        swap(&mut self.gantt_cursor, &mut scenario.gantt_cursor);
+        swap(&mut self.hovered_block, &mut scenario.hovered_block);
+    }
+
+    /// Show another tab, keeping the current one as it is
+    fn switch_scenario(&mut self, index: usize) {
+        if index == self.scenarios.active || index >= self.scenarios.tabs.len() {
+            return;
+        }
+        let mut tabs = std::mem::take(&mut self.scenarios.tabs);
+        self.exchange_scenario(&mut tabs[self.scenarios.active]);
+        self.exchange_scenario(&mut tabs[index]);
+        self.scenarios.tabs = tabs;
+        self.scenarios.active = index;
+        if let Some(solver) = &self.solver {
+            self.num_jobs = solver.jobs().len();
+            self.num_machines = solver.num_machines();
+        }
+    }
+
+    /// Open a tab with no problem, or with a copy of the current problem and schedule
+    fn open_scenario(&mut self, copy: bool) {
+        let mut scenario = Scenario::named(self.scenarios.next_name());
+        if copy {
+            scenario.name = format!("{} (copy)", self.scenarios.tabs[self.scenarios.active].name);
+            scenario.solver = self.solver.as_ref().map(|solver| JsspSolver::new(solver.instance().clone()));
+            scenario.schedule = self.schedule.clone();
+            scenario.makespan = self.makespan;
+            scenario.violations = self.violations.clone();
+            scenario.run = self.run.clone();
+        }
+        self.scenarios.tabs.push(scenario);
+        self.switch_scenario(self.scenarios.tabs.len() - 1);
+    }
+
+    fn close_scenario(&mut self, index: usize) {
+        if self.scenarios.tabs.len() < 2 {
+            return;
+        }
+        if index == self.scenarios.active {
+            let neighbour = if index + 1 < self.scenarios.tabs.len() { index + 1 } else { index - 1 };
+            self.switch_scenario(neighbour);
+        }
+        self.scenarios.tabs.remove(index);
+        if self.scenarios.active > index {
+            self.scenarios.active -= 1;
+        }
+        self.scenarios.renaming = None;
+    }
+
+    /// The tab strip: one tab per scenario, with a context menu to rename, duplicate or
+    /// close it. Switching waits for a running solve, whose result belongs to its tab.
+    pub(super) fn render_scenario_tabs(&mut self, ui: &mut egui::Ui) {
+        let solving = self.convergence.is_running();
+        let mut action = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Scenarios:");
+            let closable = self.scenarios.tabs.len() > 1;
+            for index in 0..self.scenarios.tabs.len() {
+                let active = index == self.scenarios.active;
+                if self.scenarios.renaming == Some(index) {
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.scenarios.tabs[index].name).desired_width(120.0));
+                    if response.lost_focus() {
+                        self.scenarios.renaming = None;
+                        let name = &mut self.scenarios.tabs[index].name;
+                        if name.trim().is_empty() {
+                            *name = format!("Scenario {}", index + 1);
+                        }
+                    } else {
+                        response.request_focus();
+                    }
+                    continue;
+                }
+                let size = if active {
+                    self.solver.as_ref().map(|solver| (solver.jobs().len(), solver.num_machines()))
+                } else {
+                    self.scenarios.tabs[index].solver.as_ref().map(|solver| (solver.jobs().len(), solver.num_machines()))
+                };
+                let hover = match size {
+                    Some((jobs, machines)) => format!("{} jobs × {} machines; right-click for more", jobs, machines),
+                    None => "No problem yet; right-click for more".to_string(),
+                };
+                let response = ui
+                    .add_enabled(active || !solving, egui::Button::new(&self.scenarios.tabs[index].name).selected(active))
+                    .on_hover_text(hover)
+                    .on_disabled_hover_text("Wait for the solve to finish, or pause it, before switching scenarios");
+                if response.clicked() {
+                    action = Some(TabAction::Switch(index));
+                }
+                if response.double_clicked() {
+                    self.scenarios.renaming = Some(index);
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Rename").clicked() {
+                        self.scenarios.renaming = Some(index);
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(active, egui::Button::new("Duplicate"))
+                        .on_disabled_hover_text("Switch to the scenario to duplicate it")
+                        .clicked()
+                    {
+                        action = Some(TabAction::Open { copy: true });
+                        ui.close_menu();
+                    }
+                    if ui.add_enabled(closable && !solving, egui::Button::new("Close")).clicked() {
+                        action = Some(TabAction::Close(index));
+                        ui.close_menu();
+                    }
+                });
+            }
+            if ui.add_enabled(!solving, egui::Button::new("+")).on_hover_text("Open an empty scenario to generate or import another problem in, keeping this one").clicked() {
+                action = Some(TabAction::Open { copy: false });
+            }
+            if ui.add_enabled(self.scenarios.tabs.len() > 1, egui::Button::new("Compare Scenarios"))
+                .on_hover_text("Key figures of every open scenario side by side")
+                .clicked()
+            {
+                self.scenarios.show_compare_window = true;
+            }
+        });
+        match action {
+            Some(TabAction::Switch(index)) => self.switch_scenario(index),
+            Some(TabAction::Open { copy }) => self.open_scenario(copy),
+            Some(TabAction::Close(index)) => self.close_scenario(index),
+            None => {}
+        }
+    }
+
+    fn scenario_summaries(&self) -> Vec<ScenarioSummary> {
+        self.scenarios.tabs.iter().enumerate()
+            .map(|(index, scenario)| {
+                let (solver, schedule) = if index == self.scenarios.active {
+                    (&self.solver, &self.schedule)
+                } else {
+                    (&scenario.solver, &scenario.schedule)
+                };
+                let mut summary = ScenarioSummary {
+                    index,
+                    name: scenario.name.clone(),
+                    size: solver.as_ref().map(|solver| (solver.jobs().len(), solver.num_machines())),
+                    makespan: None,
+                    gap: None,
+                    utilization: None,
+                    objective: None,
+                    violations: 0,
+                };
+                if let Some(solver) = solver.as_ref().filter(|_| !schedule.is_empty()) {
+                    let makespan = solver.calculate_makespan(schedule);
+                    summary.makespan = Some(makespan);
+                    summary.gap = Some(optimality_gap(makespan, solver.makespan_lower_bound()));
+                    summary.utilization = Some(solver.statistics(schedule).average_utilization());
+                    summary.objective = Some(solver.evaluate(self.objective, schedule));
+                    summary.violations = solver.validate(schedule).err().map_or(0, |violations| violations.len());
+                }
+                summary
+            })
+            .collect()
+    }
+
+    /// Table of every scenario's makespan, gap, utilization and selected objective, best
+    /// values highlighted; a row's name switches to it
+    pub(super) fn render_scenario_compare_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.scenarios.show_compare_window;
+        let summaries = self.scenario_summaries();
+        let best = |value: fn(&ScenarioSummary) -> Option<f64>, maximize: bool| {
+            summaries.iter().filter_map(value).reduce(if maximize { f64::max } else { f64::min })
+        };
+        let best_makespan = best(|s| s.makespan, false);
+        let best_utilization = best(|s| s.utilization, true);
+        let best_objective = best(|s| s.objective, false);
+        let solving = self.convergence.is_running();
+        let mut switch_to = None;
+        let mut baseline_from = None;
+        egui::Window::new("Compare Scenarios")
+            .open(&mut open)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                let mut headers = vec!["Scenario", "Size", "Makespan", "Gap", "Avg. Utilization"];
+                if self.objective != Objective::Makespan {
+                    headers.push(self.objective.name());
+                }
+                headers.extend(["Violations", ""]);
+                egui::Grid::new("scenario_compare_grid").striped(true).show(ui, |ui| {
+                    for header in &headers {
+                        ui.strong(*header);
+                    }
+                    ui.end_row();
+                    let highlight = |ui: &mut egui::Ui, value: Option<f64>, best: Option<f64>, text: String| {
+                        match (value, best) {
+                            (Some(value), Some(best)) if (value - best).abs() < 1e-9 && summaries.len() > 1 => {
+                                ui.colored_label(egui::Color32::from_rgb(80, 180, 80), text)
+                            }
+                            (Some(_), _) => ui.label(text),
+                            (None, _) => ui.weak("–"),
+                        }
+                    };
+                    for summary in &summaries {
+                        let active = summary.index == self.scenarios.active;
+                        if ui.add_enabled(active || !solving, egui::SelectableLabel::new(active, &summary.name)).clicked() {
+                            switch_to = Some(summary.index);
+                        }
+                        match summary.size {
+                            Some((jobs, machines)) => ui.label(format!("{}×{}", jobs, machines)),
+                            None => ui.weak("no problem"),
+                        };
+                        let format = |value: Option<f64>| value.map_or_else(String::new, |value| self.time_mode().format(value));
+                        highlight(ui, summary.makespan, best_makespan, format(summary.makespan));
+                        highlight(ui, summary.gap, None, summary.gap.map_or_else(String::new, |gap| format!("{:.1}%", gap * 100.0)));
+                        highlight(ui, summary.utilization, best_utilization, summary.utilization.map_or_else(String::new, |u| format!("{:.1}%", u * 100.0)));
+                        if self.objective != Objective::Makespan {
+                            highlight(ui, summary.objective, best_objective, summary.objective.map_or_else(String::new, |v| format!("{:.2}", v)));
+                        }
+                        if summary.makespan.is_none() {
+                            ui.weak("–");
+                        } else if summary.violations > 0 {
+                            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), summary.violations.to_string());
+                        } else {
+                            ui.label("0");
+                        }
+                        let comparable = !active
+                            && summary.makespan.is_some()
+                            && self.scenario_has_same_operations(summary.index);
+                        if ui.add_enabled(comparable, egui::Button::new("Use as Baseline").small())
+                            .on_hover_text("Compare the shown schedule with this scenario's in the Compare with Baseline window")
+                            .on_disabled_hover_text("Needs a solved scenario with the same jobs and operations as the shown one")
+                            .clicked()
+                        {
+                            baseline_from = Some(summary.index);
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.weak("Best values in green. Gap is to the makespan lower bound; the objective column follows the objective selected for solving.");
+            });
+        self.scenarios.show_compare_window = open;
+        if let Some(index) = baseline_from {
+            let scenario = &self.scenarios.tabs[index];
+            self.baseline = Some(Baseline { label: scenario.name.clone(), schedule: scenario.schedule.clone() });
+            self.show_compare_window = true;
+        }
+        if let Some(index) = switch_to {
+            self.switch_scenario(index);
+        }
+    }
+
+    /// Whether an inactive scenario's problem has the shown one's jobs and operations,
+    /// so that their schedules can be diffed
+    fn scenario_has_same_operations(&self, index: usize) -> bool {
+        let (Some(shown), Some(other)) = (&self.solver, &self.scenarios.tabs[index].solver) else {
+            return false;
+        };
+        shown.jobs().len() == other.jobs().len()
+            && shown.jobs().iter().zip(other.jobs()).all(|(a, b)| a.operations.len() == b.operations.len())
+    }
+}
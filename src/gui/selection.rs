@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use eframe::egui;
+use egui_plot::PlotResponse;
+use web_time::Instant;
+
+use super::JsspApp;
+
+/// How long a block flashes after its row is clicked in the table
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+/// Time the flashing block spends in each of its two colors
+const FLASH_PERIOD: Duration = Duration::from_millis(250);
+
+/// The operation picked in the Gantt chart or the Schedule Details table, shown in both
+#[derive(Default)]
+pub(super) struct Selection {
+    pub operation: Option<(usize, usize)>,
+    /// Row the pointer is over in the table, outlined in the chart on the next frame
+    pub table_hover: Option<(usize, usize)>,
+    /// The table scrolls the selected row into view on its next frame
+    scroll_to_row: bool,
+    flash_started: Option<Instant>,
+}
+
+impl Selection {
+    /// Select an operation clicked in the chart, or clear the selection on a click
+    /// beside every block
+    pub fn select_in_chart(&mut self, operation: Option<(usize, usize)>) {
+        self.operation = operation;
+        self.scroll_to_row = operation.is_some();
+        self.flash_started = None;
+    }
+
+    /// Select an operation clicked in the table, flashing its block
+    pub fn select_in_table(&mut self, operation: (usize, usize)) {
+        self.operation = Some(operation);
+        self.flash_started = Some(Instant::now());
+    }
+
+    /// Whether the selected block is in the bright half of a flash, or `None` once the
+    /// flash is over
+    pub fn flash_on(&mut self) -> Option<bool> {
+        let elapsed = self.flash_started?.elapsed();
+        if elapsed > FLASH_DURATION {
+            self.flash_started = None;
+            return None;
+        }
+        Some((elapsed.as_millis() / FLASH_PERIOD.as_millis()).is_multiple_of(2))
+    }
+
+    /// Whether the table should scroll to the selection, which it does only once
+    pub fn take_scroll(&mut self) -> bool {
+        std::mem::take(&mut self.scroll_to_row)
+    }
+}
+
+impl JsspApp {
+    /// Select the block under a click in the Gantt chart
+    pub(super) fn handle_gantt_click(&mut self, plot_response: &PlotResponse<()>) {
+        let response = &plot_response.response;
+        if !response.clicked() {
+            return;
+        }
+        let clicked = response.interact_pointer_pos()
+            .and_then(|pos| self.operation_at(plot_response.transform.value_from_position(pos)))
+            .map(|op| (op.job_id, op.operation_id));
+        self.selection.select_in_chart(clicked);
+    }
+
+    /// Outline in `color` of a block that is selected or hovered in the table, and its
+    /// fill while flashing; `None` for other blocks
+    pub(super) fn selection_style(
+        &self,
+        operation: (usize, usize),
+        flash: Option<bool>,
+        color: egui::Color32,
+    ) -> Option<(egui::Stroke, Option<egui::Color32>)> {
+        if self.selection.operation == Some(operation) {
+            let fill = flash.filter(|&on| on).map(|_| color);
+            Some((egui::Stroke::new(3.0, color), fill))
+        } else if self.selection.table_hover == Some(operation) {
+            Some((egui::Stroke::new(2.0, color.gamma_multiply(0.7)), None))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,325 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+use std::path::PathBuf;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use super::coloring::ColorBy;
+use super::gantt_edit::GanttRows;
+use super::JsspApp;
+use crate::chart::Palette;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::jssp::external::ExternalSolver;
+use crate::jssp::{DurationDistribution, Objective, ProblemType};
+
+/// Key of the settings in eframe's storage
+const SETTINGS_KEY: &str = "jssp_settings";
+
+/// Entries kept in File ➡ Recent Instances
+#[cfg(not(target_arch = "wasm32"))]
+const RECENT_LEN: usize = 10;
+
+/// What is remembered between sessions. The window size and position are kept by eframe
+/// itself. Missing fields fall back to the defaults, so older settings still load.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct Settings {
+    num_jobs: usize,
+    num_machines: usize,
+    min_duration: f64,
+    max_duration: f64,
+    duration_distribution: DurationDistribution,
+    use_machine_slowdown: bool,
+    max_machine_slowdown: f64,
+    alternative_machines: usize,
+    partial_routing: bool,
+    reentrant_routing: bool,
+    problem_type: ProblemType,
+    min_operations: usize,
+    max_operations: usize,
+    use_setup_times: bool,
+    max_setup_time: f64,
+    use_transfer_times: bool,
+    max_transfer_time: f64,
+    use_operators: bool,
+    num_operators: usize,
+    use_duration_variation: bool,
+    duration_variation: f64,
+    use_release_dates: bool,
+    max_release_time: f64,
+    use_due_dates: bool,
+    due_date_slack: f64,
+    use_weights: bool,
+    max_weight: f64,
+    integer_times: bool,
+    /// Algorithm by name, since the registry may differ between sessions
+    algorithm: String,
+    /// Objective by key
+    objective: String,
+    multi_start: bool,
+    warm_start: bool,
+    keep_solution_pool: bool,
+    solution_pool_size: usize,
+    multi_start_runs: usize,
+    multi_start_threads: usize,
+    /// Parameter preset by name; changed values are not kept
+    preset: String,
+    /// Programs run as algorithms, listed after the built-in ones
+    #[cfg(not(target_arch = "wasm32"))]
+    external_solvers: Vec<ExternalSolver>,
+    drag_editing: bool,
+    gantt_rows: GanttRows,
+    color_by: ColorBy,
+    show_critical_path: bool,
+    show_bottleneck: bool,
+    show_minimap: bool,
+    date_axis: bool,
+    show_analytics: bool,
+    theme: egui::ThemePreference,
+    palette: Palette,
+    last_export_dir: Option<PathBuf>,
+    recent_instances: Vec<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        JsspApp::default().settings()
+    }
+}
+
+impl JsspApp {
+    /// The app with the settings of the previous session, if eframe kept any
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            app.presets.reload();
+            app.presets.params.apply(&mut app.solvers);
+        }
+        if let Some(storage) = cc.storage {
+            app.restore_settings(storage);
+        }
+        app
+    }
+
+    /// Load generator settings, external solvers, the selected algorithm and preset, the
+    /// last export directory and the recent instances saved by a previous session
+    pub fn restore_settings(&mut self, storage: &dyn eframe::Storage) {
+        let Some(settings) = eframe::get_value::<Settings>(storage, SETTINGS_KEY) else {
+            return;
+        };
+        self.num_jobs = settings.num_jobs;
+        self.num_machines = settings.num_machines;
+        self.min_duration = settings.min_duration;
+        self.max_duration = settings.max_duration;
+        self.duration_distribution = settings.duration_distribution;
+        self.use_machine_slowdown = settings.use_machine_slowdown;
+        self.max_machine_slowdown = settings.max_machine_slowdown;
+        self.alternative_machines = settings.alternative_machines;
+        self.partial_routing = settings.partial_routing;
+        self.reentrant_routing = settings.reentrant_routing;
+        self.problem_type = settings.problem_type;
+        self.min_operations = settings.min_operations;
+        self.max_operations = settings.max_operations;
+        self.use_setup_times = settings.use_setup_times;
+        self.max_setup_time = settings.max_setup_time;
+        self.use_transfer_times = settings.use_transfer_times;
+        self.max_transfer_time = settings.max_transfer_time;
+        self.use_operators = settings.use_operators;
+        self.num_operators = settings.num_operators;
+        self.use_duration_variation = settings.use_duration_variation;
+        self.duration_variation = settings.duration_variation;
+        self.use_release_dates = settings.use_release_dates;
+        self.max_release_time = settings.max_release_time;
+        self.use_due_dates = settings.use_due_dates;
+        self.due_date_slack = settings.due_date_slack;
+        self.use_weights = settings.use_weights;
+        self.max_weight = settings.max_weight;
+        self.integer_times = settings.integer_times;
+        #[cfg(not(target_arch = "wasm32"))]
+        self.set_external_solvers(settings.external_solvers);
+        if let Some(index) = self.solvers.solvers().position(|solver| solver.name() == settings.algorithm) {
+            self.selected_solver = index;
+        }
+        if let Some(objective) = Objective::from_key(&settings.objective) {
+            self.objective = objective;
+        }
+        self.multi_start = settings.multi_start;
+        self.warm_start = settings.warm_start;
+        self.keep_solution_pool = settings.keep_solution_pool;
+        self.solution_pool_size = settings.solution_pool_size.clamp(2, 50);
+        self.multi_start_config.starts = settings.multi_start_runs;
+        self.multi_start_config.threads = settings.multi_start_threads;
+        self.presets.choose(&settings.preset);
+        self.presets.params.apply(&mut self.solvers);
+        self.drag_editing = settings.drag_editing;
+        self.gantt_rows = settings.gantt_rows;
+        self.color_by = settings.color_by;
+        self.show_critical_path = settings.show_critical_path;
+        self.show_bottleneck = settings.show_bottleneck;
+        self.show_minimap = settings.show_minimap;
+        self.date_axis = settings.date_axis;
+        self.show_analytics = settings.show_analytics;
+        self.theme = settings.theme;
+        self.palette = settings.palette;
+        self.last_export_dir = settings.last_export_dir;
+        self.recent_instances = settings.recent_instances;
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            num_jobs: self.num_jobs,
+            num_machines: self.num_machines,
+            min_duration: self.min_duration,
+            max_duration: self.max_duration,
+            duration_distribution: self.duration_distribution,
+            use_machine_slowdown: self.use_machine_slowdown,
+            max_machine_slowdown: self.max_machine_slowdown,
+            alternative_machines: self.alternative_machines,
+            partial_routing: self.partial_routing,
+            reentrant_routing: self.reentrant_routing,
+            problem_type: self.problem_type,
+            min_operations: self.min_operations,
+            max_operations: self.max_operations,
+            use_setup_times: self.use_setup_times,
+            max_setup_time: self.max_setup_time,
+            use_transfer_times: self.use_transfer_times,
+            max_transfer_time: self.max_transfer_time,
+            use_operators: self.use_operators,
+            num_operators: self.num_operators,
+            use_duration_variation: self.use_duration_variation,
+            duration_variation: self.duration_variation,
+            use_release_dates: self.use_release_dates,
+            max_release_time: self.max_release_time,
+            use_due_dates: self.use_due_dates,
+            due_date_slack: self.due_date_slack,
+            use_weights: self.use_weights,
+            max_weight: self.max_weight,
+            integer_times: self.integer_times,
+            algorithm: self.selected_solver_name(),
+            objective: self.objective.key().to_string(),
+            multi_start: self.multi_start,
+            warm_start: self.warm_start,
+            keep_solution_pool: self.keep_solution_pool,
+            solution_pool_size: self.solution_pool_size,
+            multi_start_runs: self.multi_start_config.starts,
+            multi_start_threads: self.multi_start_config.threads,
+            preset: self.presets.name.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            external_solvers: self.external_solvers.configured.clone(),
+            drag_editing: self.drag_editing,
+            gantt_rows: self.gantt_rows,
+            color_by: self.color_by,
+            show_critical_path: self.show_critical_path,
+            show_bottleneck: self.show_bottleneck,
+            show_minimap: self.show_minimap,
+            date_axis: self.date_axis,
+            show_analytics: self.show_analytics,
+            theme: self.theme,
+            palette: self.palette,
+            last_export_dir: self.last_export_dir.clone(),
+            recent_instances: self.recent_instances.clone(),
+        }
+    }
+
+    pub(super) fn save_settings(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SETTINGS_KEY, &self.settings());
+    }
+
+    /// A save dialog opening in the directory of the last export
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn export_dialog(&self) -> rfd::FileDialog {
+        match &self.last_export_dir {
+            Some(dir) if dir.is_dir() => rfd::FileDialog::new().set_directory(dir),
+            _ => rfd::FileDialog::new(),
+        }
+    }
+
+    /// Remember where a file was exported to, for the next export dialog
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn remember_export_dir(&mut self, path: &Path) {
+        let dir = if path.is_dir() { Some(path) } else { path.parent() };
+        self.last_export_dir = dir.map(Path::to_path_buf);
+    }
+
+    /// Put an opened instance file at the top of the recent list
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn add_recent_instance(&mut self, path: &Path) {
+        self.recent_instances.retain(|recent| recent != path);
+        self.recent_instances.insert(0, path.to_path_buf());
+        self.recent_instances.truncate(RECENT_LEN);
+    }
+
+    pub(super) fn render_menu_bar(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            egui::menu::bar(ui, |ui| {
+                ui.menu_button("File", |ui| {
+                    if ui.button("Import OR-Tools...").clicked() {
+                        ui.close_menu();
+                        self.import_ortools_with_dialog();
+                    }
+                    if ui.add_enabled(self.solver.is_some(), egui::Button::new("Import Solution...")).clicked() {
+                        ui.close_menu();
+                        self.import_solution_with_dialog();
+                    }
+                    if ui.add_enabled(!self.schedule.is_empty(), egui::Button::new("Export...")).clicked() {
+                        ui.close_menu();
+                        self.show_export_dialog = true;
+                    }
+                    // The browser gives no paths to reopen
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.render_recent_instances_menu(ui);
+                });
+                ui.menu_button("Settings", |ui| self.render_appearance_menu(ui));
+                Self::render_help_menu(ui);
+            });
+        });
+    }
+
+    /// Theme and chart palette
+    fn render_appearance_menu(&mut self, ui: &mut egui::Ui) {
+        ui.strong("Theme");
+        for (theme, label) in [
+            (egui::ThemePreference::System, "Follow System"),
+            (egui::ThemePreference::Dark, "Dark"),
+            (egui::ThemePreference::Light, "Light"),
+        ] {
+            ui.radio_value(&mut self.theme, theme, label);
+        }
+        ui.separator();
+        ui.strong("Job Colors");
+        for palette in Palette::ALL {
+            ui.radio_value(&mut self.palette, palette, palette.name());
+        }
+        ui.colored_label(egui::Color32::GRAY, "Right-click a job in the legend to give it its own color.");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_recent_instances_menu(&mut self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.menu_button("Recent Instances", |ui| {
+            if self.recent_instances.is_empty() {
+                ui.colored_label(egui::Color32::GRAY, "No recent instances");
+            }
+            let mut open = None;
+            for path in &self.recent_instances {
+                let name = path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned());
+                if ui.button(name).on_hover_text(path.display().to_string()).clicked() {
+                    open = Some(path.clone());
+                }
+            }
+            if !self.recent_instances.is_empty() {
+                ui.separator();
+                if ui.button("Clear Recent").clicked() {
+                    self.recent_instances.clear();
+                    ui.close_menu();
+                }
+            }
+            if let Some(path) = open {
+                ui.close_menu();
+                self.import_ortools_file(&path);
+            }
+        });
+    }
+}
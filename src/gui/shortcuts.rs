@@ -0,0 +1,153 @@
+use eframe::egui;
+use egui_plot::{PlotBounds, PlotUi};
+
+use super::dashboard::AppTab;
+use super::JsspApp;
+
+/// Share of the visible time range one arrow key press pans by
+const PAN_STEP: f64 = 0.1;
+/// Factor one +/- press zooms the time axis by
+const ZOOM_STEP: f32 = 1.25;
+
+/// Key and action, as listed in the Help menu
+const SHORTCUTS: [(&str, &str); 10] = [
+    ("G", "Generate a problem"),
+    ("S", "Solve the schedule"),
+    ("E", "Export the solution"),
+    ("+ / -", "Zoom the Gantt chart in or out"),
+    ("⬅ ➡", "Pan the Gantt chart in time"),
+    ("⬆ ⬇", "Pan the Gantt chart by one row"),
+    ("Home", "Fit the Gantt chart to the schedule"),
+    ("0-9", "Show or hide Jobs 0 to 9"),
+    ("Ctrl+Z", "Undo"),
+    ("Ctrl+Y", "Redo"),
+];
+
+/// Zooming and panning asked for by the keyboard, applied by the Gantt chart when it
+/// is next drawn
+pub(super) struct GanttNavigation {
+    zoom: f32,
+    /// Fractions of the visible time range
+    pan_time: f64,
+    /// Rows
+    pan_rows: f64,
+    fit: bool,
+    /// Time to center the view on, picked in the minimap
+    center: Option<f64>,
+}
+
+impl Default for GanttNavigation {
+    fn default() -> Self {
+        Self { zoom: 1.0, pan_time: 0.0, pan_rows: 0.0, fit: false, center: None }
+    }
+}
+
+impl GanttNavigation {
+    /// Frame the whole schedule
+    pub fn fit(&mut self) {
+        self.fit = true;
+    }
+
+    /// Move the view in time, keeping its zoom, so `time` is in the middle
+    pub fn center_on(&mut self, time: f64) {
+        self.center = Some(time);
+    }
+
+    /// Apply the requested navigation; fitting frames `extent`
+    pub fn apply(&self, plot_ui: &mut PlotUi, extent: PlotBounds) {
+        if self.fit {
+            plot_ui.set_plot_bounds(extent);
+            return;
+        }
+        let mut bounds = plot_ui.plot_bounds();
+        if let Some(center) = self.center {
+            bounds.set_x_center_width(center, bounds.width());
+            plot_ui.set_plot_bounds(bounds);
+        }
+        if self.zoom != 1.0 {
+            plot_ui.zoom_bounds(egui::vec2(self.zoom, 1.0), bounds.center());
+        }
+        if self.pan_time != 0.0 || self.pan_rows != 0.0 {
+            let delta = egui::vec2((self.pan_time * bounds.width()) as f32, self.pan_rows as f32);
+            plot_ui.translate_bounds(delta);
+        }
+    }
+}
+
+impl JsspApp {
+    /// The single-key shortcuts of `SHORTCUTS`, ignored while a text field has focus
+    pub(super) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let pressed = |key: egui::Key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, key));
+
+        if pressed(egui::Key::G) {
+            self.generate_problem();
+        }
+        if pressed(egui::Key::S) {
+            self.solve_schedule();
+        }
+        if pressed(egui::Key::E) && !self.schedule.is_empty() {
+            self.show_export_dialog = true;
+        }
+
+        if self.tab != AppTab::Schedule {
+            return;
+        }
+        let navigation = &mut self.gantt_navigation;
+        // Shift is needed for + on most layouts
+        let zoom_in = ctx.input_mut(|i| {
+            i.consume_key(egui::Modifiers::NONE, egui::Key::Plus)
+                || i.consume_key(egui::Modifiers::SHIFT, egui::Key::Plus)
+                || i.consume_key(egui::Modifiers::NONE, egui::Key::Equals)
+        });
+        if zoom_in {
+            navigation.zoom *= ZOOM_STEP;
+        }
+        if pressed(egui::Key::Minus) {
+            navigation.zoom /= ZOOM_STEP;
+        }
+        if pressed(egui::Key::ArrowLeft) {
+            navigation.pan_time -= PAN_STEP;
+        }
+        if pressed(egui::Key::ArrowRight) {
+            navigation.pan_time += PAN_STEP;
+        }
+        if pressed(egui::Key::ArrowUp) {
+            navigation.pan_rows += 1.0;
+        }
+        if pressed(egui::Key::ArrowDown) {
+            navigation.pan_rows -= 1.0;
+        }
+        if pressed(egui::Key::Home) {
+            navigation.fit();
+        }
+
+        let digits = [
+            egui::Key::Num0, egui::Key::Num1, egui::Key::Num2, egui::Key::Num3, egui::Key::Num4,
+            egui::Key::Num5, egui::Key::Num6, egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+        ];
+        for (job_id, key) in digits.into_iter().enumerate() {
+            // Removing a hidden job shows it again; otherwise hide it
+            if pressed(key) && self.schedule.iter().any(|op| op.job_id == job_id) && !self.filter.hidden_jobs.remove(&job_id) {
+                self.filter.hidden_jobs.insert(job_id);
+            }
+        }
+    }
+
+    /// The Help menu, listing the keyboard shortcuts
+    pub(super) fn render_help_menu(ui: &mut egui::Ui) {
+        ui.menu_button("Help", |ui| {
+            ui.strong("Keyboard Shortcuts");
+            egui::Grid::new("shortcuts_grid").num_columns(2).spacing([16.0, 4.0]).show(ui, |ui| {
+                for (key, action) in SHORTCUTS {
+                    ui.monospace(key);
+                    ui.label(action);
+                    ui.end_row();
+                }
+            });
+            ui.colored_label(egui::Color32::GRAY, "Single-key shortcuts are off while typing in a field.");
+        });
+    }
+}
@@ -0,0 +1,148 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Plot, VLine};
+
+use super::JsspApp;
+use crate::jssp::simulation::{FailureModel, ReactionPolicy};
+
+impl JsspApp {
+    pub(super) fn render_simulation_window(&mut self, ctx: &egui::Context) {
+        let num_machines = self.instance_machines();
+        self.simulation_config.failures.resize(num_machines, FailureModel::default());
+
+        let mut open = self.show_simulation_window;
+        egui::Window::new("Robustness Simulation")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                ui.label("Executes the current schedule many times under uncertain durations and random machine failures (exponential up and repair times).");
+
+                ui.horizontal(|ui| {
+                    ui.label("Duration variation: ±");
+                    let mut percent = self.simulation_config.duration_variation * 100.0;
+                    let slider = ui.add(egui::Slider::new(&mut percent, 0.0..=50.0).suffix("%"))
+                        .on_hover_text("Each replication draws every operation's duration uniformly from this range around the planned one");
+                    if slider.changed() {
+                        self.simulation_config.duration_variation = percent / 100.0;
+                    }
+                    ui.separator();
+                    ui.checkbox(&mut self.simulation_failures, "Machine failures")
+                        .on_hover_text("Off: only the durations vary, and the fixed sequences show how sensitive the schedule is to estimation errors");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Policy:");
+                    egui::ComboBox::from_id_salt("simulation_policy")
+                        .selected_text(self.simulation_config.policy.name())
+                        .show_ui(ui, |ui| {
+                            for policy in ReactionPolicy::ALL {
+                                ui.selectable_value(&mut self.simulation_config.policy, policy, policy.name());
+                            }
+                        });
+                    ui.label("Replications:");
+                    ui.add(egui::Slider::new(&mut self.simulation_config.replications, 10..=2000).logarithmic(true));
+                });
+
+                ui.add_enabled_ui(self.simulation_failures, |ui| egui::CollapsingHeader::new("Failure model per machine")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Set all:");
+                            if ui.button("Reliable (MTBF 200, MTTR 5)").clicked() {
+                                self.simulation_config.failures.fill(FailureModel { mtbf: 200.0, mttr: 5.0 });
+                            }
+                            if ui.button("Unreliable (MTBF 50, MTTR 10)").clicked() {
+                                self.simulation_config.failures.fill(FailureModel { mtbf: 50.0, mttr: 10.0 });
+                            }
+                        });
+                        egui::ScrollArea::vertical()
+                            .id_salt("failure_models")
+                            .max_height(160.0)
+                            .show(ui, |ui| {
+                                egui::Grid::new("failure_model_grid")
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label("Machine");
+                                        ui.label("MTBF");
+                                        ui.label("MTTR");
+                                        ui.end_row();
+                                        for (machine_id, model) in self.simulation_config.failures.iter_mut().enumerate() {
+                                            ui.label(machine_id.to_string());
+                                            ui.add(egui::DragValue::new(&mut model.mtbf).range(1.0..=100_000.0).speed(1.0));
+                                            ui.add(egui::DragValue::new(&mut model.mttr).range(0.0..=10_000.0).speed(0.5));
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                    }));
+
+                ui.horizontal(|ui| {
+                    ui.label("Seed:");
+                    ui.add(egui::DragValue::new(&mut self.simulation_config.seed));
+                    let can_run = self.solver.is_some() && !self.schedule.is_empty();
+                    if ui.add_enabled(can_run, egui::Button::new("Run Simulation")).clicked() {
+                        if let Some(solver) = &self.solver {
+                            let mut config = self.simulation_config.clone();
+                            if !self.simulation_failures {
+                                // Machines without a failure model never fail
+                                config.failures.clear();
+                            }
+                            self.simulation_report = Some(solver.simulate(&self.schedule, &config));
+                        }
+                    }
+                });
+
+                ui.separator();
+
+                let Some(report) = &self.simulation_report else {
+                    ui.colored_label(egui::Color32::GRAY, "Run the simulation to see the realized makespan distribution.");
+                    return;
+                };
+
+                ui.label(format!(
+                    "{} over {} replications{}. Planned makespan: {:.2}",
+                    report.policy.name(),
+                    report.runs.len(),
+                    if report.duration_variation > 0.0 { format!(", durations ±{:.0}%", report.duration_variation * 100.0) } else { String::new() },
+                    report.planned_makespan
+                ));
+                ui.label(format!(
+                    "Realized makespan: mean {:.2}  |  std dev {:.2}  |  min {:.2}  |  P50 {:.2}  |  P90 {:.2}  |  P95 {:.2}  |  max {:.2}",
+                    report.mean(), report.std_dev(), report.min(), report.percentile(0.5),
+                    report.percentile(0.9), report.percentile(0.95), report.max()
+                ));
+                ui.label(format!(
+                    "Finished by the planned makespan in {:.1}% of replications; P90 is {:+.1}% over plan",
+                    report.on_time_share() * 100.0,
+                    (report.percentile(0.9) / report.planned_makespan.max(1e-9) - 1.0) * 100.0
+                ));
+                let runs = report.runs.len().max(1) as f64;
+                ui.label(format!(
+                    "Per run: {:.1} failures, {:.2} time units of processing interrupted",
+                    report.runs.iter().map(|r| r.failures as f64).sum::<f64>() / runs,
+                    report.runs.iter().map(|r| r.lost_time).sum::<f64>() / runs
+                ));
+
+                let bars: Vec<Bar> = report.histogram(20).into_iter()
+                    .map(|(start, width, count)| {
+                        Bar::new(start + width / 2.0, count as f64)
+                            .width(width * 0.95)
+                            .name(format!("{:.1} - {:.1}", start, start + width))
+                    })
+                    .collect();
+                Plot::new("simulation_histogram")
+                    .height(220.0)
+                    .x_axis_label("Realized makespan")
+                    .y_axis_label("Replications")
+                    .include_y(0.0)
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(BarChart::new(bars).color(egui::Color32::from_rgb(70, 130, 180)));
+                        plot_ui.vline(
+                            VLine::new(report.planned_makespan)
+                                .color(egui::Color32::YELLOW)
+                                .name("Planned makespan")
+                        );
+                    });
+            });
+        self.show_simulation_window = open;
+    }
+}
@@ -0,0 +1,127 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::soft::SoftConstraint;
+use crate::jssp::Objective;
+
+impl JsspApp {
+    pub(super) fn render_soft_constraints_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_soft_constraints_window;
+        egui::Window::new("Soft Constraints")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                let Some(solver) = &mut self.solver else {
+                    ui.colored_label(egui::Color32::GRAY, "Generate or import a problem first.");
+                    return;
+                };
+                ui.label(format!(
+                    "Soft constraints may be broken at a penalty of weight × amount. Choose the '{}' objective to have solvers trade penalties against makespan.",
+                    Objective::Penalized.name()
+                ));
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Add:");
+                    if ui.button("Due Dates").on_hover_text("Penalize tardiness, scaled by each job's weight").clicked() {
+                        solver.soft_constraints.push(SoftConstraint::DueDates { weight: 1.0 });
+                    }
+                    if ui.button("Max Wait").on_hover_text("Penalize waiting between consecutive operations of a job").clicked() {
+                        solver.soft_constraints.push(SoftConstraint::MaxWait { max_wait: 5.0, weight: 1.0 });
+                    }
+                    if ui.button("Operator Limit").on_hover_text("Penalize running more operations at once than there are operators").clicked() {
+                        let operators = solver.num_machines().saturating_sub(1).max(1);
+                        solver.soft_constraints.push(SoftConstraint::OperatorLimit { operators, weight: 1.0 });
+                    }
+                    if ui.button("Pin").on_hover_text("Penalize an operation starting away from a given time").clicked() {
+                        solver.soft_constraints.push(SoftConstraint::Pin { operation: (0, 0), start_time: 0.0, weight: 1.0 });
+                    }
+                });
+
+                if solver.soft_constraints.is_empty() {
+                    ui.colored_label(egui::Color32::GRAY, "No soft constraints.");
+                    return;
+                }
+                let num_jobs = solver.jobs().len();
+                let mut remove = None;
+                egui::Grid::new("soft_constraint_grid")
+                    .striped(true)
+                    .num_columns(4)
+                    .show(ui, |ui| {
+                        for header in ["Constraint", "Parameters", "Weight", ""] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+
+                        for (index, constraint) in solver.soft_constraints.iter_mut().enumerate() {
+                            ui.label(constraint.name());
+                            ui.horizontal(|ui| match constraint {
+                                SoftConstraint::DueDates { .. } => {
+                                    ui.label("every job with a due date");
+                                }
+                                SoftConstraint::MaxWait { max_wait, .. } => {
+                                    ui.label("max wait");
+                                    ui.add(egui::DragValue::new(max_wait).range(0.0..=10_000.0).speed(0.5));
+                                }
+                                SoftConstraint::OperatorLimit { operators, .. } => {
+                                    ui.label("operators");
+                                    ui.add(egui::DragValue::new(operators).range(1..=1000));
+                                }
+                                SoftConstraint::Pin { operation, start_time, .. } => {
+                                    ui.label("job");
+                                    ui.add(egui::DragValue::new(&mut operation.0).range(0..=num_jobs.saturating_sub(1)));
+                                    ui.label("op");
+                                    ui.add(egui::DragValue::new(&mut operation.1).range(0..=1000));
+                                    ui.label("at");
+                                    ui.add(egui::DragValue::new(start_time).range(0.0..=100_000.0).speed(0.5));
+                                }
+                            });
+                            ui.add(egui::DragValue::new(constraint.weight_mut()).range(0.0..=1000.0).speed(0.1));
+                            if ui.button("Remove").clicked() {
+                                remove = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(index) = remove {
+                    solver.soft_constraints.remove(index);
+                }
+
+                ui.separator();
+                if self.schedule.is_empty() {
+                    return;
+                }
+                let violations = solver.soft_violations(&self.schedule);
+                let penalty = violations.iter().map(|v| v.penalty).fold(0.0, |total, p| total + p);
+                if violations.is_empty() {
+                    ui.colored_label(egui::Color32::GREEN, "The current schedule meets every soft constraint.");
+                    return;
+                }
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    format!("⚠ {} soft violation(s), total penalty {:.2}", violations.len(), penalty),
+                );
+                egui::ScrollArea::vertical()
+                    .id_salt("soft_violations_scroll")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("soft_violation_grid")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                for header in ["Constraint", "Violation", "Amount", "Penalty"] {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+                                for violation in &violations {
+                                    ui.label(solver.soft_constraints[violation.constraint].name());
+                                    ui.label(&violation.description);
+                                    ui.label(format!("{:.2}", violation.amount));
+                                    ui.label(format!("{:.2}", violation.penalty));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        self.show_soft_constraints_window = open;
+    }
+}
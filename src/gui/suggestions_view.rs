@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+use super::JsspApp;
+
+/// Number of swaps listed in the suggestions panel
+const MAX_SUGGESTIONS: usize = 5;
+
+/// How long "Improve Solution" climbs before keeping what it has; the UI waits meanwhile
+const IMPROVE_TIME_LIMIT: Duration = Duration::from_secs(3);
+
+impl JsspApp {
+    /// Hill-climb from the current schedule and report the makespan before and after
+    pub(super) fn improve_schedule(&mut self) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        if self.schedule.is_empty() {
+            return;
+        }
+        let result = solver.improve(&self.schedule, self.improve_strategy, IMPROVE_TIME_LIMIT);
+        if result.moves == 0 {
+            self.edit_status = Some(Ok(format!(
+                "No {} move improves the makespan of {}",
+                self.improve_strategy.name().to_lowercase(), solver.time_mode().format(result.makespan)
+            )));
+            return;
+        }
+        let time = solver.time_mode();
+        self.remember("Improve Solution");
+        self.apply_edited_schedule(result.schedule);
+        self.edit_status = Some(Ok(format!(
+            "Improved with {} move(s): makespan {} ➡ {}{}",
+            result.moves,
+            time.format(result.initial_makespan),
+            time.format(result.makespan),
+            if result.timed_out { " (time limit reached)" } else { "" }
+        )));
+    }
+
+    /// Advisory list of critical-arc swaps that a planner can apply one at a time
+    pub(super) fn render_swap_suggestions(&mut self, ui: &mut egui::Ui) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let time = solver.time_mode();
+
+        let mut apply = None;
+        egui::CollapsingHeader::new("Re-sequencing Suggestions")
+            .default_open(false)
+            .show(ui, |ui| {
+                if ui.button("Analyze Critical Path").clicked() {
+                    self.swap_suggestions = Some(solver.suggest_swaps(&self.schedule, MAX_SUGGESTIONS));
+                }
+
+                let Some(suggestions) = &self.swap_suggestions else {
+                    ui.colored_label(egui::Color32::GRAY, "Swaps adjacent operations on the critical path and predicts the new makespan.");
+                    return;
+                };
+                if suggestions.is_empty() {
+                    ui.colored_label(egui::Color32::GRAY, "No critical-arc swaps available for this schedule.");
+                    return;
+                }
+
+                egui::Grid::new("swap_suggestions_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Machine");
+                        ui.label("Swap");
+                        ui.label("Makespan");
+                        ui.label("Change");
+                        ui.label("");
+                        ui.end_row();
+
+                        for (i, suggestion) in suggestions.iter().enumerate() {
+                            let (first_job, first_op) = suggestion.first;
+                            let (second_job, second_op) = suggestion.second;
+                            ui.label(suggestion.machine_id.to_string());
+                            ui.label(format!("J{} Op {} ↔ J{} Op {}", first_job, first_op, second_job, second_op));
+                            ui.label(time.format(suggestion.makespan));
+                            let color = if suggestion.delta < -1e-6 {
+                                egui::Color32::LIGHT_GREEN
+                            } else if suggestion.delta > 1e-6 {
+                                egui::Color32::LIGHT_RED
+                            } else {
+                                egui::Color32::GRAY
+                            };
+                            ui.colored_label(color, format!("{:+.p$}", suggestion.delta, p = time.decimals()));
+                            if ui.button(if i == 0 { "Apply Best" } else { "Apply" }).clicked() {
+                                apply = Some(suggestion.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if let Some(suggestion) = apply {
+            let old_makespan = self.makespan;
+            let swapped = self.solver.as_ref().and_then(|s| s.apply_swap(&self.schedule, &suggestion));
+            if let Some(schedule) = swapped {
+                self.remember("Apply Swap");
+                self.apply_edited_schedule(schedule);
+                self.edit_status = Some(Ok(format!(
+                    "Swapped J{} Op {} and J{} Op {} on machine {}: makespan {} ➡ {}",
+                    suggestion.first.0, suggestion.first.1, suggestion.second.0, suggestion.second.1,
+                    suggestion.machine_id, time.format(old_makespan), time.format(self.makespan)
+                )));
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use eframe::egui;
+use web_time::{Duration, Instant};
+
+use super::JsspApp;
+use crate::jssp::JsspError;
+
+/// How long a success message stays up
+const SUCCESS_DURATION: Duration = Duration::from_secs(4);
+
+struct Toast {
+    title: String,
+    message: String,
+    error: bool,
+    shown: Instant,
+}
+
+/// Messages about exports and imports in the bottom right corner: successes disappear
+/// after a few seconds, failures stay until dismissed
+#[derive(Default)]
+pub(super) struct Toasts {
+    toasts: Vec<Toast>,
+}
+
+impl Toasts {
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push("Done", message.into(), false);
+    }
+
+    pub fn error(&mut self, error: &JsspError) {
+        self.push(error.kind(), error.to_string(), true);
+    }
+
+    fn push(&mut self, title: &str, message: String, error: bool) {
+        self.toasts.push(Toast { title: title.to_string(), message, error, shown: Instant::now() });
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) {
+        self.toasts.retain(|toast| toast.error || toast.shown.elapsed() < SUCCESS_DURATION);
+        if self.toasts.is_empty() {
+            return;
+        }
+        if self.toasts.iter().any(|toast| !toast.error) {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for (index, toast) in self.toasts.iter().enumerate() {
+                    let (color, fill) = if toast.error {
+                        (egui::Color32::from_rgb(200, 40, 40), egui::Color32::from_rgb(255, 235, 235))
+                    } else {
+                        (egui::Color32::from_rgb(40, 140, 60), ui.visuals().window_fill)
+                    };
+                    egui::Frame::popup(ui.style())
+                        .fill(fill)
+                        .stroke(egui::Stroke::new(1.5, color))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(color, egui::RichText::new(&toast.title).strong());
+                                if toast.error && ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                                    dismissed = Some(index);
+                                }
+                            });
+                            if toast.error {
+                                ui.label(egui::RichText::new(&toast.message).color(egui::Color32::from_gray(30)));
+                            } else {
+                                ui.label(&toast.message);
+                            }
+                        });
+                    ui.add_space(4.0);
+                }
+            });
+        if let Some(index) = dismissed {
+            self.toasts.remove(index);
+        }
+    }
+}
+
+impl JsspApp {
+    /// Toast the outcome of writing an export: where it went, or why it failed
+    pub(super) fn report_export(&mut self, what: &str, saved: Result<String, JsspError>) {
+        match saved {
+            Ok(location) => self.toasts.success(format!("Exported {} to {}", what, location)),
+            Err(e) => self.toasts.error(&e),
+        }
+    }
+}
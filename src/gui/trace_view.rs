@@ -0,0 +1,182 @@
+use eframe::egui;
+use egui_plot::{Line, Plot, PlotPoints, VLine};
+
+use super::JsspApp;
+use crate::jssp::trace::SearchTrace;
+use crate::jssp::Schedule;
+
+/// A recorded search being scrubbed through; the shown step is drawn in the Gantt chart
+pub(super) struct TraceViewer {
+    trace: Option<SearchTrace>,
+    /// Step selected with the scrubber
+    step: usize,
+    /// The step last put into the Gantt chart and its schedule, kept separately so
+    /// edits made in the chart meanwhile don't disturb the replay
+    shown: Option<(usize, Schedule)>,
+    playing: bool,
+    /// Steps advanced per second while playing
+    speed: f64,
+    /// Fraction of a step accumulated between frames while playing
+    carry: f64,
+    error: Option<String>,
+}
+
+impl Default for TraceViewer {
+    fn default() -> Self {
+        Self { trace: None, step: 0, shown: None, playing: false, speed: 50.0, carry: 0.0, error: None }
+    }
+}
+
+impl JsspApp {
+    fn record_trace(&mut self) {
+        let Some(solver) = &self.solver else {
+            return;
+        };
+        let algorithm = self.solvers.solvers().nth(self.selected_solver);
+        let (seed, started) = (rand::random(), web_time::Instant::now());
+        let Some((schedule, trace)) = algorithm.and_then(|a| a.solve_traced(solver, seed)) else {
+            self.trace_viewer.error = Some(format!(
+                "{} does not record its search; choose a local-search algorithm such as Simulated Annealing.",
+                self.selected_solver_name()
+            ));
+            return;
+        };
+        let run = self.run_metadata(&self.selected_solver_name(), seed, 1, started.elapsed());
+        self.remember("Record Search Trace");
+        let best = trace.best_step();
+        self.trace_viewer = TraceViewer {
+            step: best,
+            shown: Some((best, schedule.clone())),
+            trace: Some(trace),
+            speed: self.trace_viewer.speed,
+            ..TraceViewer::default()
+        };
+        self.apply_edited_schedule(schedule);
+        self.run = Some(run);
+    }
+
+    /// Rebuild the schedule of the selected step if it changed
+    fn show_trace_step(&mut self) {
+        let (Some(solver), Some(trace)) = (&self.solver, &self.trace_viewer.trace) else {
+            return;
+        };
+        let target = self.trace_viewer.step;
+        let schedule = match &self.trace_viewer.shown {
+            Some((shown, _)) if *shown == target => return,
+            Some((shown, schedule)) => trace.seek(solver, schedule, *shown, target),
+            None => trace.schedule_at(solver, target),
+        };
+        match schedule {
+            Some(schedule) => {
+                self.makespan = solver.calculate_makespan(&schedule);
+                self.violations = solver.validate(&schedule).err().unwrap_or_default();
+                self.schedule = schedule.clone();
+                // Other steps than the best are not what the run returned
+                if let Some(run) = &mut self.run {
+                    run.edited = target != trace.best_step();
+                }
+                self.swap_suggestions = None;
+                self.trace_viewer.shown = Some((target, schedule));
+            }
+            None => {
+                self.trace_viewer.error = Some("The recorded moves do not fit the current problem.".to_string());
+                self.trace_viewer.trace = None;
+            }
+        }
+    }
+
+    pub(super) fn render_trace_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_trace_window;
+        egui::Window::new("Search Trace")
+            .open(&mut open)
+            .default_width(620.0)
+            .show(ctx, |ui| {
+                ui.label("Record every accepted move of a local-search run, then scrub through the run to see the schedule at any step.");
+                ui.horizontal(|ui| {
+                    ui.label(format!("Algorithm: {}", self.selected_solver_name()));
+                    if ui.add_enabled(self.solver.is_some(), egui::Button::new("Record Run")).clicked() {
+                        self.record_trace();
+                    }
+                });
+                if let Some(error) = &self.trace_viewer.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                let viewer = &mut self.trace_viewer;
+                let Some(trace) = &viewer.trace else {
+                    return;
+                };
+                let last = trace.len() - 1;
+                let best = trace.best_step();
+                ui.label(format!(
+                    "{}: {} accepted moves over {} iterations, makespan {:.2} ➡ best {:.2} at step {}",
+                    trace.solver,
+                    trace.steps.len(),
+                    trace.iterations,
+                    trace.initial_makespan,
+                    trace.makespan_at(best),
+                    best
+                ));
+
+                if viewer.playing {
+                    viewer.carry += ui.input(|i| i.stable_dt).min(0.1) as f64 * viewer.speed;
+                    let advance = viewer.carry.floor();
+                    viewer.carry -= advance;
+                    viewer.step = (viewer.step + advance as usize).min(last);
+                    if viewer.step == last {
+                        viewer.playing = false;
+                    }
+                    ui.ctx().request_repaint();
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("⏮ Start").clicked() {
+                        viewer.step = 0;
+                    }
+                    if ui.button("Prev").clicked() {
+                        viewer.step = viewer.step.saturating_sub(1);
+                    }
+                    let label = if viewer.playing { "⏸ Pause" } else { "▶ Play" };
+                    if ui.button(label).clicked() {
+                        if !viewer.playing && viewer.step == last {
+                            viewer.step = 0;
+                        }
+                        viewer.playing = !viewer.playing;
+                    }
+                    if ui.button("Next").clicked() {
+                        viewer.step = (viewer.step + 1).min(last);
+                    }
+                    if ui.button("Best").clicked() {
+                        viewer.step = best;
+                    }
+                    ui.label("Steps/s:");
+                    ui.add(egui::DragValue::new(&mut viewer.speed).range(1.0..=10_000.0).speed(1.0));
+                });
+                ui.spacing_mut().slider_width = (ui.available_width() - 120.0).max(100.0);
+                ui.add(egui::Slider::new(&mut viewer.step, 0..=last).text("step"));
+                ui.label(format!(
+                    "Step {} of {}: iteration {}, makespan {:.2}",
+                    viewer.step,
+                    last,
+                    trace.iteration_at(viewer.step),
+                    trace.makespan_at(viewer.step)
+                ));
+
+                let points: PlotPoints = (0..trace.len()).map(|step| [step as f64, trace.makespan_at(step)]).collect();
+                Plot::new("trace_plot")
+                    .height(160.0)
+                    .x_axis_label("Accepted move")
+                    .y_axis_label("Makespan")
+                    .allow_drag(false)
+                    .allow_scroll(false)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(points).name("Makespan"));
+                        plot_ui.vline(VLine::new(viewer.step as f64).color(egui::Color32::YELLOW));
+                    });
+            });
+        self.show_trace_window = open;
+        if self.show_trace_window {
+            self.show_trace_step();
+        }
+    }
+}
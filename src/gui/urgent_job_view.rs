@@ -0,0 +1,177 @@
+use eframe::egui;
+
+use super::JsspApp;
+use crate::jssp::{Job, Operation};
+
+/// Inputs of the "Urgent Job" window
+pub(super) struct UrgentJobForm {
+    /// Operations that started before this time stay where they are
+    now: f64,
+    /// Machine and duration of every operation, in order
+    operations: Vec<(usize, f64)>,
+    due_date: Option<f64>,
+    error: Option<String>,
+}
+
+impl Default for UrgentJobForm {
+    fn default() -> Self {
+        Self { now: 0.0, operations: vec![(0, 10.0)], due_date: None, error: None }
+    }
+}
+
+impl UrgentJobForm {
+    fn job(&self) -> Job {
+        let operations = self.operations.iter().enumerate()
+            .map(|(operation_id, &(machine_id, duration))| Operation {
+                job_id: 0,
+                operation_id,
+                machine_id,
+                duration,
+                alternatives: Vec::new(),
+            })
+            .collect();
+        Job { id: 0, operations, release_time: self.now, due_date: self.due_date, weight: 1.0 }
+    }
+}
+
+impl JsspApp {
+    /// Add the urgent job to the problem and reschedule everything that has not started yet
+    fn insert_urgent_job(&mut self) {
+        let job = self.urgent_job_form.job();
+        let Some(mut instance) = self.solver.as_ref().map(|solver| solver.instance().clone()) else {
+            return;
+        };
+        match instance.insert_urgent_job(&self.schedule, job, self.urgent_job_form.now) {
+            Ok(insertion) => {
+                self.remember("Urgent Job");
+                if let Some(solver) = &mut self.solver {
+                    **solver = instance;
+                }
+                self.instance_editor.reload();
+                self.edit_status = None;
+                self.apply_edited_schedule(insertion.schedule.clone());
+                self.urgent_job_form.error = None;
+                self.urgent_insertion = Some(insertion);
+            }
+            Err(e) => self.urgent_job_form.error = Some(e),
+        }
+    }
+
+    pub(super) fn render_urgent_job_window(&mut self, ctx: &egui::Context) {
+        let num_machines = self.instance_machines();
+        let playback_time = self.playback_time();
+
+        let mut open = self.show_urgent_job_window;
+        let mut apply = false;
+        egui::Window::new("Urgent Job")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.label("Fit a rush order into the current schedule. Operations that started before \"now\" stay \
+                          where they are; the new job goes first on its machines and everything else still to \
+                          come keeps its machine order.");
+
+                let form = &mut self.urgent_job_form;
+                ui.horizontal(|ui| {
+                    ui.label("Now:");
+                    ui.add(egui::DragValue::new(&mut form.now).range(0.0..=f64::MAX).speed(1.0));
+                    if ui.add_enabled(playback_time.is_some(), egui::Button::new("At Playback Cursor"))
+                        .on_hover_text("Take the time of the playback cursor")
+                        .clicked()
+                    {
+                        form.now = playback_time.unwrap_or(form.now);
+                    }
+                });
+
+                let mut remove = None;
+                egui::Grid::new("urgent_job_operations").num_columns(4).show(ui, |ui| {
+                    for (operation_id, (machine_id, duration)) in form.operations.iter_mut().enumerate() {
+                        ui.label(format!("Op {}", operation_id));
+                        ui.horizontal(|ui| {
+                            ui.label("Machine:");
+                            ui.add(egui::DragValue::new(machine_id).range(0..=num_machines.saturating_sub(1)));
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Duration:");
+                            ui.add(egui::DragValue::new(duration).range(0.0..=f64::MAX).speed(1.0));
+                        });
+                        if ui.small_button("✖").on_hover_text("Remove this operation").clicked() {
+                            remove = Some(operation_id);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(operation_id) = remove.filter(|_| form.operations.len() > 1) {
+                    form.operations.remove(operation_id);
+                }
+                if ui.button("+ Operation").clicked() {
+                    let next_machine = form.operations.last().map_or(0, |&(machine_id, _)| (machine_id + 1) % num_machines.max(1));
+                    form.operations.push((next_machine, 10.0));
+                }
+
+                ui.horizontal(|ui| {
+                    let mut has_due_date = form.due_date.is_some();
+                    if ui.checkbox(&mut has_due_date, "Due date").changed() {
+                        form.due_date = has_due_date.then_some(form.now + 50.0);
+                    }
+                    if let Some(due_date) = &mut form.due_date {
+                        ui.add(egui::DragValue::new(due_date).range(0.0..=f64::MAX).speed(1.0));
+                    }
+                });
+
+                let can_apply = self.solver.is_some() && !self.schedule.is_empty();
+                if ui.add_enabled(can_apply, egui::Button::new("Insert and Reschedule"))
+                    .on_hover_text("Adds the job to the problem; Undo takes it back")
+                    .clicked()
+                {
+                    apply = true;
+                }
+                if let Some(error) = &self.urgent_job_form.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+
+                let Some(insertion) = &self.urgent_insertion else {
+                    return;
+                };
+                ui.separator();
+                let time = self.time_mode();
+                ui.label(format!(
+                    "{} finishes at {}; {} operations had started and were kept.",
+                    self.job_name(insertion.job_id),
+                    time.format(insertion.completion()),
+                    insertion.frozen
+                ));
+                ui.label(format!(
+                    "Makespan: {} → {} (+{})",
+                    time.format(insertion.makespan_before),
+                    time.format(insertion.makespan_after),
+                    time.format(insertion.makespan_after - insertion.makespan_before)
+                ));
+                if insertion.slipped.is_empty() {
+                    ui.label("No other job finishes later than planned.");
+                    return;
+                }
+                ui.label(format!("{} jobs slipped:", insertion.slipped.len()));
+                egui::ScrollArea::vertical().id_salt("urgent_slipped_jobs").max_height(200.0).show(ui, |ui| {
+                    egui::Grid::new("urgent_slipped_jobs_grid").striped(true).num_columns(4).show(ui, |ui| {
+                        ui.strong("Job");
+                        ui.strong("Planned");
+                        ui.strong("Now");
+                        ui.strong("Delay");
+                        ui.end_row();
+                        for slip in &insertion.slipped {
+                            ui.label(self.job_name(slip.job_id));
+                            ui.label(time.format(slip.planned_completion));
+                            ui.label(time.format(slip.repaired_completion));
+                            ui.colored_label(egui::Color32::from_rgb(200, 80, 40), format!("+{}", time.format(slip.delay())));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_urgent_job_window = open;
+        if apply {
+            self.insert_urgent_job();
+        }
+    }
+}
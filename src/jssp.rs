@@ -1,10 +1,92 @@
-use std::collections::HashMap;
-use serde::Serialize;
+pub mod analytics;
+pub mod annealing;
+pub mod batch;
+pub mod benchmark;
+pub mod best_known;
+pub mod bounds;
+pub mod buffering;
+pub mod cache;
+pub mod checkpoint;
+pub mod critical_path;
+#[cfg(feature = "cp-solver")]
+pub mod cp;
+pub mod diff;
+pub mod dynamic;
+pub mod error;
+#[cfg(all(feature = "experiment-log", not(target_arch = "wasm32")))]
+pub mod experiments;
+pub mod explain;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod external;
+pub mod incremental;
+pub mod insertion;
+pub mod instance;
+pub mod local_search;
+pub mod memetic;
+pub mod milp;
+pub mod multistart;
+pub mod ortools;
+pub mod pareto;
+pub mod pins;
+pub mod pool;
+pub mod preemption;
+pub mod presets;
+pub mod priority;
+pub mod profile;
+pub mod provenance;
+pub mod repair;
+pub mod resources;
+pub mod scalability;
+pub mod shifting_bottleneck;
+pub mod shop;
+pub mod simulation;
+pub mod solution_formats;
+pub mod soft;
+pub mod solvers;
+pub mod stochastic;
+pub mod testing;
+pub mod trace;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::calendar::Calendar;
+use resources::{ResourceRequirement, ResourceUsage, SecondaryResource};
+use stochastic::{DurationSpread, DEFAULT_SCENARIOS};
+
+pub use buffering::Buffering;
+pub use error::JsspError;
+pub use instance::{Instance, TimeMode};
+pub use shop::ProblemType;
+pub use solvers::{Greedy, Solver, SolverConfig, SolverInfo, SolverRegistry};
+
+/// Operations with their assigned machines and times, in no particular order
+pub type Schedule = Vec<ScheduledOperation>;
+
+/// Tolerance used when comparing floating point times during validation
+const TIME_EPSILON: f64 = 1e-6;
 
 #[derive(Debug, Clone)]
 pub struct Job {
     pub id: usize,
     pub operations: Vec<Operation>,
+    /// Earliest time the first operation of the job may start
+    pub release_time: f64,
+    /// Time by which the job should be completed, if any
+    pub due_date: Option<f64>,
+    /// Importance of the job in weighted objectives
+    pub weight: f64,
+}
+
+impl Job {
+    /// Lower bound on the job's completion: release time plus the shortest
+    /// processing time of every operation, ignoring machine contention
+    pub fn earliest_completion(&self) -> f64 {
+        self.release_time
+            + self.operations.iter()
+                .map(|op| op.options().map(|o| o.duration).fold(f64::INFINITY, f64::min))
+                .sum::<f64>()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -14,9 +96,37 @@ pub struct Operation {
     pub operation_id: usize,
     pub machine_id: usize,
     pub duration: f64,
+    /// Additional eligible machines for the flexible job shop (FJSP) variant
+    pub alternatives: Vec<MachineOption>,
+}
+
+/// An eligible machine for an operation together with its processing time there
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MachineOption {
+    pub machine_id: usize,
+    pub duration: f64,
+}
+
+impl Operation {
+    /// All machines this operation may run on, starting with its default machine
+    pub fn options(&self) -> impl Iterator<Item = MachineOption> + '_ {
+        std::iter::once(MachineOption { machine_id: self.machine_id, duration: self.duration })
+            .chain(self.alternatives.iter().copied())
+    }
+
+    /// Processing time on the given machine, or `None` if the machine is not eligible
+    pub fn duration_on(&self, machine_id: usize) -> Option<f64> {
+        self.options()
+            .find(|option| option.machine_id == machine_id)
+            .map(|option| option.duration)
+    }
+
+    pub fn is_flexible(&self) -> bool {
+        !self.alternatives.is_empty()
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledOperation {
     pub job_id: usize,
     pub operation_id: usize,
@@ -24,102 +134,1382 @@ pub struct ScheduledOperation {
     pub start_time: f64,
     pub end_time: f64,
     pub duration: f64,
+    /// Sequence-dependent setup performed on the machine right before `start_time`
+    pub setup_time: f64,
+    /// The pieces `(start, end)` a preempted operation is processed in, from `start_time`
+    /// to `end_time` around downtime (see `preemption`); empty when it runs in one go
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<(f64, f64)>,
+}
+
+impl ScheduledOperation {
+    /// Time at which the machine becomes busy for this operation, including its setup
+    pub fn setup_start(&self) -> f64 {
+        self.start_time - self.setup_time
+    }
+
+    /// The intervals the operation is processed in: its segments if it is preempted,
+    /// otherwise just `start_time` to `end_time`
+    pub fn intervals(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let whole = self.segments.is_empty().then_some((self.start_time, self.end_time));
+        self.segments.iter().copied().chain(whole)
+    }
+}
+
+/// Sequence-dependent setup times: `times[machine][from_job][to_job]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetupTimes {
+    pub times: Vec<Vec<Vec<f64>>>,
+}
+
+impl SetupTimes {
+    /// Shorten entries so that `s(a, c) <= s(a, b) + s(b, c)` holds on every machine
+    pub fn enforce_triangle_inequality(&mut self) {
+        for matrix in &mut self.times {
+            let n = matrix.len();
+            for via in 0..n {
+                for from in 0..n {
+                    for to in 0..n {
+                        let detour = matrix[from][via] + matrix[via][to];
+                        if detour < matrix[from][to] {
+                            matrix[from][to] = detour;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Setup needed on `machine_id` when `to_job` follows `from_job`.
+    /// The first operation on a machine (`from_job == None`) needs no setup.
+    pub fn get(&self, machine_id: usize, from_job: Option<usize>, to_job: usize) -> f64 {
+        let Some(from_job) = from_job else {
+            return 0.0;
+        };
+        if from_job == to_job {
+            return 0.0;
+        }
+        self.times.get(machine_id)
+            .and_then(|m| m.get(from_job))
+            .and_then(|row| row.get(to_job))
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+/// Times a job needs to travel between machines, e.g. on an AGV:
+/// `times[from_machine][to_machine]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferTimes {
+    pub times: Vec<Vec<f64>>,
+}
+
+impl TransferTimes {
+    /// Shorten entries so that travelling via another machine is never faster
+    pub fn enforce_triangle_inequality(&mut self) {
+        let n = self.times.len();
+        for via in 0..n {
+            for from in 0..n {
+                for to in 0..n {
+                    let detour = self.times[from][via] + self.times[via][to];
+                    if detour < self.times[from][to] {
+                        self.times[from][to] = detour;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Travel time from `from_machine` to `to_machine`; staying on a machine takes none
+    pub fn get(&self, from_machine: usize, to_machine: usize) -> f64 {
+        if from_machine == to_machine {
+            return 0.0;
+        }
+        self.times.get(from_machine)
+            .and_then(|row| row.get(to_machine))
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+/// A planned period in which a machine can neither set up nor process anything,
+/// e.g. a maintenance window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Downtime {
+    pub machine_id: usize,
+    pub start: f64,
+    pub end: f64,
 }
 
+impl Downtime {
+    /// Whether the machine is busy during part of this window when it works from
+    /// `from` to `to`
+    pub fn overlaps(&self, from: f64, to: f64) -> bool {
+        from < self.end - TIME_EPSILON && to > self.start + TIME_EPSILON
+    }
+}
+
+/// Criterion used to judge the quality of a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Objective {
+    Makespan,
+    /// Sum over jobs of completion minus release time
+    TotalFlowTime,
+    /// Sum over jobs of weight times completion time
+    WeightedCompletion,
+    TotalTardiness,
+    MaxLateness,
+    WeightedTardiness,
+    LateJobs,
+    /// Makespan plus the penalty of the instance's soft constraints
+    Penalized,
+    /// Mean makespan over sampled scenarios of the uncertain durations (see `stochastic`)
+    ExpectedMakespan,
+}
+
+impl Objective {
+    pub const ALL: [Objective; 9] = [
+        Objective::Makespan,
+        Objective::TotalFlowTime,
+        Objective::WeightedCompletion,
+        Objective::TotalTardiness,
+        Objective::MaxLateness,
+        Objective::WeightedTardiness,
+        Objective::LateJobs,
+        Objective::Penalized,
+        Objective::ExpectedMakespan,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Objective::Makespan => "Makespan",
+            Objective::TotalFlowTime => "Total Flow Time",
+            Objective::WeightedCompletion => "Weighted Completion Time",
+            Objective::TotalTardiness => "Total Tardiness",
+            Objective::MaxLateness => "Max Lateness",
+            Objective::WeightedTardiness => "Weighted Tardiness",
+            Objective::LateJobs => "Late Jobs",
+            Objective::Penalized => "Makespan + Penalties",
+            Objective::ExpectedMakespan => "Expected Makespan",
+        }
+    }
+
+    /// Identifier used as key in exported files
+    pub fn key(&self) -> &'static str {
+        match self {
+            Objective::Makespan => "makespan",
+            Objective::TotalFlowTime => "total_flow_time",
+            Objective::WeightedCompletion => "weighted_completion",
+            Objective::TotalTardiness => "total_tardiness",
+            Objective::MaxLateness => "max_lateness",
+            Objective::WeightedTardiness => "weighted_tardiness",
+            Objective::LateJobs => "late_jobs",
+            Objective::Penalized => "penalized_makespan",
+            Objective::ExpectedMakespan => "expected_makespan",
+        }
+    }
+
+    /// The objective with the given `key`
+    pub fn from_key(key: &str) -> Option<Objective> {
+        Objective::ALL.into_iter().find(|objective| objective.key() == key)
+    }
+
+    /// Whether the objective only means something for jobs with due dates
+    pub fn uses_due_dates(&self) -> bool {
+        matches!(
+            self,
+            Objective::TotalTardiness | Objective::MaxLateness | Objective::WeightedTardiness | Objective::LateJobs
+        )
+    }
+}
+
+/// A single feasibility problem found by `JsspSolver::validate`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// Two operations are processed on the same machine at the same time
+    MachineOverlap {
+        machine_id: usize,
+        first: (usize, usize),
+        second: (usize, usize),
+    },
+    /// An operation starts before the previous operation of its job has finished
+    Precedence {
+        job_id: usize,
+        operation_id: usize,
+        previous_end: f64,
+        start_time: f64,
+    },
+    /// An operation starts before its job has arrived from the machine of the previous operation
+    Transfer {
+        job_id: usize,
+        operation_id: usize,
+        arrival: f64,
+        start_time: f64,
+    },
+    /// An operation of the instance does not appear in the schedule
+    MissingOperation { job_id: usize, operation_id: usize },
+    /// An operation appears more than once in the schedule
+    DuplicateOperation { job_id: usize, operation_id: usize },
+    /// The schedule contains an operation that does not exist in the instance
+    UnknownOperation { job_id: usize, operation_id: usize },
+    /// An operation is scheduled on a machine that is not eligible for it
+    WrongMachine {
+        job_id: usize,
+        operation_id: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// The scheduled interval is shorter than the operation's processing time
+    DurationMismatch {
+        job_id: usize,
+        operation_id: usize,
+        expected: f64,
+        actual: f64,
+    },
+    /// The first operation of a job starts before the job is released
+    EarlyStart {
+        job_id: usize,
+        operation_id: usize,
+        release_time: f64,
+        start_time: f64,
+    },
+    /// The setup before an operation is shorter than the sequence requires
+    InsufficientSetup {
+        machine_id: usize,
+        job_id: usize,
+        operation_id: usize,
+        required: f64,
+        actual: f64,
+    },
+    /// An operation or its setup overlaps a window in which the machine is down
+    Downtime {
+        machine_id: usize,
+        job_id: usize,
+        operation_id: usize,
+        window: (f64, f64),
+    },
+    /// In a no-wait instance, an operation starts after the previous operation of its job ended
+    Wait {
+        job_id: usize,
+        operation_id: usize,
+        previous_end: f64,
+        start_time: f64,
+    },
+    /// In a blocking instance, an operation (or its setup) starts on a machine that the
+    /// job before it has not left yet
+    Blocked {
+        machine_id: usize,
+        first: (usize, usize),
+        second: (usize, usize),
+        released: f64,
+    },
+    /// An operation is split into segments although the instance is not preemptive, or
+    /// its segments do not run in order from its start to its end
+    Preemption { job_id: usize, operation_id: usize },
+    /// More operations use a secondary resource at once than it has units; `second`
+    /// starts while `capacity` others, the latest-ending of them `first`, still hold one
+    ResourceOverload {
+        resource_id: usize,
+        capacity: usize,
+        first: (usize, usize),
+        second: (usize, usize),
+    },
+    /// A pinned operation is not on its pinned machine at its pinned start
+    PinMoved { job_id: usize, operation_id: usize, machine_id: usize, start_time: f64 },
+}
+
+impl Violation {
+    /// The (job_id, operation_id) pairs involved in this violation
+    pub fn operations(&self) -> Vec<(usize, usize)> {
+        match *self {
+            Violation::MachineOverlap { first, second, .. }
+            | Violation::Blocked { first, second, .. }
+            | Violation::ResourceOverload { first, second, .. } => vec![first, second],
+            Violation::Precedence { job_id, operation_id, .. }
+            | Violation::Transfer { job_id, operation_id, .. }
+            | Violation::Wait { job_id, operation_id, .. } => {
+                let mut ops = vec![(job_id, operation_id)];
+                if operation_id > 0 {
+                    ops.push((job_id, operation_id - 1));
+                }
+                ops
+            }
+            Violation::MissingOperation { job_id, operation_id }
+            | Violation::DuplicateOperation { job_id, operation_id }
+            | Violation::UnknownOperation { job_id, operation_id }
+            | Violation::WrongMachine { job_id, operation_id, .. }
+            | Violation::DurationMismatch { job_id, operation_id, .. }
+            | Violation::EarlyStart { job_id, operation_id, .. }
+            | Violation::InsufficientSetup { job_id, operation_id, .. }
+            | Violation::Downtime { job_id, operation_id, .. }
+            | Violation::Preemption { job_id, operation_id }
+            | Violation::PinMoved { job_id, operation_id, .. } => vec![(job_id, operation_id)],
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::MachineOverlap { machine_id, first, second } => write!(
+                f,
+                "Machine {}: Job {} Op {} overlaps Job {} Op {}",
+                machine_id, first.0, first.1, second.0, second.1
+            ),
+            Violation::Precedence { job_id, operation_id, previous_end, start_time } => write!(
+                f,
+                "Job {} Op {} starts at {:.2} before previous operation ends at {:.2}",
+                job_id, operation_id, start_time, previous_end
+            ),
+            Violation::Transfer { job_id, operation_id, arrival, start_time } => write!(
+                f,
+                "Job {} Op {} starts at {:.2} before the job arrives from the previous machine at {:.2}",
+                job_id, operation_id, start_time, arrival
+            ),
+            Violation::MissingOperation { job_id, operation_id } => {
+                write!(f, "Job {} Op {} is not scheduled", job_id, operation_id)
+            }
+            Violation::DuplicateOperation { job_id, operation_id } => {
+                write!(f, "Job {} Op {} is scheduled more than once", job_id, operation_id)
+            }
+            Violation::UnknownOperation { job_id, operation_id } => {
+                write!(f, "Job {} Op {} does not exist in the problem", job_id, operation_id)
+            }
+            Violation::WrongMachine { job_id, operation_id, expected, actual } => write!(
+                f,
+                "Job {} Op {} runs on machine {} which is not eligible (default machine {})",
+                job_id, operation_id, actual, expected
+            ),
+            Violation::DurationMismatch { job_id, operation_id, expected, actual } => write!(
+                f,
+                "Job {} Op {} is scheduled for {:.2} but needs {:.2}",
+                job_id, operation_id, actual, expected
+            ),
+            Violation::EarlyStart { job_id, operation_id, release_time, start_time } => write!(
+                f,
+                "Job {} Op {} starts at {:.2} before the job is released at {:.2}",
+                job_id, operation_id, start_time, release_time
+            ),
+            Violation::InsufficientSetup { machine_id, job_id, operation_id, required, actual } => write!(
+                f,
+                "Machine {}: setup before Job {} Op {} is {:.2} but needs {:.2}",
+                machine_id, job_id, operation_id, actual, required
+            ),
+            Violation::Downtime { machine_id, job_id, operation_id, window } => write!(
+                f,
+                "Machine {}: Job {} Op {} runs into downtime from {:.2} to {:.2}",
+                machine_id, job_id, operation_id, window.0, window.1
+            ),
+            Violation::Wait { job_id, operation_id, previous_end, start_time } => write!(
+                f,
+                "Job {} Op {} starts at {:.2} but may not wait after the previous operation ends at {:.2}",
+                job_id, operation_id, start_time, previous_end
+            ),
+            Violation::Blocked { machine_id, first, second, released } => write!(
+                f,
+                "Machine {}: Job {} Op {} starts before Job {} Op {} leaves the machine at {:.2}",
+                machine_id, second.0, second.1, first.0, first.1, released
+            ),
+            Violation::Preemption { job_id, operation_id } => {
+                write!(f, "Job {} Op {} is split into segments out of order or on a problem without preemption", job_id, operation_id)
+            }
+            Violation::ResourceOverload { resource_id, capacity, first, second } => write!(
+                f,
+                "Resource {}: Job {} Op {} starts while Job {} Op {} and all {} unit(s) are in use",
+                resource_id, second.0, second.1, first.0, first.1, capacity
+            ),
+            Violation::PinMoved { job_id, operation_id, machine_id, start_time } => write!(
+                f,
+                "Job {} Op {} is pinned to Machine {} at {:.2}",
+                job_id, operation_id, machine_id, start_time
+            ),
+        }
+    }
+}
+
+/// An instance being scheduled. Evaluation and decoding live on `Instance`, which this
+/// derefs to; algorithms implement `Solver`.
 pub struct JsspSolver {
-    pub jobs: Vec<Job>,
-    pub num_machines: usize,
+    instance: Instance,
+}
+
+impl std::ops::Deref for JsspSolver {
+    type Target = Instance;
+
+    fn deref(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl std::ops::DerefMut for JsspSolver {
+    fn deref_mut(&mut self) -> &mut Instance {
+        &mut self.instance
+    }
+}
+
+impl From<Instance> for JsspSolver {
+    fn from(instance: Instance) -> Self {
+        Self::new(instance)
+    }
 }
 
 impl JsspSolver {
-    pub fn new(jobs: Vec<Job>, num_machines: usize) -> Self {
-        Self { jobs, num_machines }
+    pub fn new(instance: Instance) -> Self {
+        Self { instance }
+    }
+
+    pub fn instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    pub fn into_instance(self) -> Instance {
+        self.instance
     }
 
-    /// Greedy algorithm: Schedule operations based on earliest available time
+    /// Run an algorithm on the instance
+    pub fn solve(&self, solver: &dyn Solver) -> Schedule {
+        solver.solve(&self.instance)
+    }
+}
+
+impl Instance {
+    /// Setup needed on a machine between two consecutive jobs (0 without a setup matrix)
+    pub fn setup_time(&self, machine_id: usize, from_job: Option<usize>, to_job: usize) -> f64 {
+        self.setup_times()
+            .map_or(0.0, |setups| setups.get(machine_id, from_job, to_job))
+    }
+
+    /// Travel time of a job to `to_machine` from the machine of its previous operation
+    /// (0 for a job's first operation or without a transfer matrix)
+    pub fn transfer_time(&self, from_machine: Option<usize>, to_machine: usize) -> f64 {
+        match (self.transfer_times(), from_machine) {
+            (Some(transfers), Some(from_machine)) => transfers.get(from_machine, to_machine),
+            _ => 0.0,
+        }
+    }
+
+    /// Shorthand for solving with `solvers::Greedy`
     pub fn solve_greedy(&self) -> Vec<ScheduledOperation> {
-        let mut schedule = Vec::new();
-        let mut machine_available_time: HashMap<usize, f64> = HashMap::new();
-        let mut job_completion_time: HashMap<usize, f64> = HashMap::new();
+        Greedy.solve(self)
+    }
+
+    /// Best of `restarts` randomized greedy passes (see `solvers::RandomRestartGreedy`),
+    /// reproducible for a given seed
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let ft06 = ft06().instance;
+    /// let schedule = ft06.solve_greedy_randomized(200, 42);
+    /// assert!(ft06.validate(&schedule).is_ok());
+    /// assert!(ft06.calculate_makespan(&schedule) < ft06.calculate_makespan(&ft06.solve_greedy()));
+    /// let again = ft06.solve_greedy_randomized(200, 42);
+    /// assert_eq!(ft06.calculate_makespan(&again), ft06.calculate_makespan(&schedule));
+    /// ```
+    pub fn solve_greedy_randomized(&self, restarts: usize, seed: u64) -> Vec<ScheduledOperation> {
+        solvers::RandomRestartGreedy { restarts }.solve_seeded(self, seed)
+    }
+
+    /// Operation sequence that lists every operation of job 0, then job 1, and so on
+    pub fn job_by_job_sequence(&self) -> Vec<usize> {
+        self.jobs().iter()
+            .enumerate()
+            .flat_map(|(job_idx, job)| std::iter::repeat_n(job_idx, job.operations.len()))
+            .collect()
+    }
 
-        // Initialize machine and job availability
-        for i in 0..self.num_machines {
-            machine_available_time.insert(i, 0.0);
+    /// Build a semi-active schedule from an operation-based sequence.
+    ///
+    /// Each entry is a job index; its k-th occurrence stands for the job's k-th operation.
+    /// Operations are appended to their machine in sequence order, starting as early as
+    /// the machine (after setup), the job's previous operation (plus the transfer from
+    /// its machine) and the machine's downtime allow. Without buffers, whole jobs are placed instead (see `buffering`);
+    /// in an open shop, whichever operation of the job finishes first (see `shop`).
+    pub fn decode(&self, sequence: &[usize]) -> Vec<ScheduledOperation> {
+        if self.buffering != Buffering::Unlimited {
+            return self.decode_without_buffers(sequence);
         }
-        for job in &self.jobs {
-            job_completion_time.insert(job.id, 0.0);
+        if self.is_open_shop() {
+            return self.decode_open_shop(sequence);
         }
+        let mut schedule = Vec::with_capacity(sequence.len());
+        // Indexed by machine and job id, which `Instance` guarantees to be dense
+        let mut machine_available_time: Vec<f64> = vec![0.0; self.num_machines()];
+        let mut machine_last_job: Vec<Option<usize>> = vec![None; self.num_machines()];
+        let mut job_completion_time: Vec<f64> = self.jobs().iter().map(|job| job.release_time).collect();
+        let mut job_last_machine: Vec<Option<usize>> = vec![None; self.jobs().len()];
+        let mut next_operation: Vec<usize> = vec![0; self.jobs().len()];
+        let mut resources = ResourceUsage::new(self);
+        let pins = self.uses_pins();
+
+        for &job_id in sequence {
+            let op_idx = next_operation.get(job_id).copied().unwrap_or(0);
+            let Some(node) = self.operation_index(job_id, op_idx) else {
+                continue;
+            };
+            next_operation[job_id] += 1;
 
-        // Create a list of all operations with their dependencies
-        let mut pending_operations: Vec<(usize, usize, &Operation)> = Vec::new();
-        for job in &self.jobs {
-            for (op_idx, op) in job.operations.iter().enumerate() {
-                pending_operations.push((job.id, op_idx, op));
+            // A pinned operation stays where it is; its machine's time is blocked anyway
+            if let Some(pin) = self.pin(job_id, op_idx).filter(|_| pins) {
+                job_completion_time[job_id] = pin.end_time;
+                job_last_machine[job_id] = Some(pin.machine_id);
+                schedule.push(pin.clone());
+                continue;
             }
+
+            // Starts at the job's release time for its first operation
+            let job_time = job_completion_time[job_id];
+
+            // Pick the eligible machine that finishes the operation first
+            let choice = self.operation_options(node).iter()
+                .map(|&option| {
+                    let machine_time = machine_available_time[option.machine_id];
+                    let setup = self.setup_time(option.machine_id, machine_last_job[option.machine_id], job_id);
+                    let arrival = job_time + self.transfer_time(job_last_machine[job_id], option.machine_id);
+                    // Operation can start when both the machine (after setup) and previous job operation
+                    // are done, and neither the setup nor the operation runs into a downtime window
+                    // (on a preemptive instance downtime only pauses the operation), and a unit
+                    // of any secondary resource it needs is free
+                    let ready = (machine_time + setup).max(arrival);
+                    let start_time = self.resource_start(&resources, (job_id, op_idx), option.machine_id, ready, setup, option.duration);
+                    (option, setup, start_time, self.processing_end(option.machine_id, start_time, option.duration))
+                })
+                .min_by(|a, b| a.3.total_cmp(&b.3))
+                .unwrap();
+            let (option, setup_time, start_time, end_time) = choice;
+
+            let operation = self.timed_operation(job_id, op_idx, option.machine_id, start_time, option.duration, setup_time);
+            resources.reserve(self, &operation);
+            schedule.push(operation);
+
+            // Update availability times
+            machine_available_time[option.machine_id] = end_time;
+            machine_last_job[option.machine_id] = Some(job_id);
+            job_completion_time[job_id] = end_time;
+            job_last_machine[job_id] = Some(option.machine_id);
+        }
+
+        if pins {
+            self.settle_pinned_setups(&mut schedule);
         }
+        schedule
+    }
 
-        // Schedule operations in order for each job
-        for job in &self.jobs {
-            for (op_idx, operation) in job.operations.iter().enumerate() {
-                let machine_time = *machine_available_time.get(&operation.machine_id).unwrap_or(&0.0);
-                let job_time = *job_completion_time.get(&job.id).unwrap_or(&0.0);
-                
-                // Operation can start when both the machine and previous job operation are done
-                let start_time = machine_time.max(job_time);
-                let end_time = start_time + operation.duration;
+    /// Check a schedule for machine overlaps, job precedence and transfers, downtime, buffering,
+    /// secondary resources, pins and operation coverage
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::instance_from_routes;
+    /// use jssp_scheduler::jssp::Violation;
+    ///
+    /// // Job 0 runs 3 units on machine 0, then 2 on machine 1; job 1 runs 2 units on machine 0
+    /// let instance = instance_from_routes(&[&[(0, 3.0), (1, 2.0)], &[(0, 2.0)]], 2);
+    /// let mut schedule = instance.solve_greedy();
+    /// assert!(instance.validate(&schedule).is_ok());
+    ///
+    /// // Start job 1 at 0 on machine 0 next to job 0, and job 0's second operation at 1
+    /// for op in &mut schedule {
+    ///     let start = if (op.job_id, op.operation_id) == (0, 1) { 1.0 } else if op.job_id == 1 { 0.0 } else { op.start_time };
+    ///     op.end_time += start - op.start_time;
+    ///     op.start_time = start;
+    /// }
+    /// let violations = instance.validate(&schedule).unwrap_err();
+    /// assert!(violations.iter().any(|v| matches!(v, Violation::MachineOverlap { machine_id: 0, .. })));
+    /// assert!(violations.iter().any(|v| matches!(v, Violation::Precedence { job_id: 0, operation_id: 1, .. })));
+    /// ```
+    pub fn validate(&self, schedule: &[ScheduledOperation]) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
 
-                schedule.push(ScheduledOperation {
-                    job_id: job.id,
-                    operation_id: op_idx,
-                    machine_id: operation.machine_id,
-                    start_time,
-                    end_time,
-                    duration: operation.duration,
-                });
+        // Coverage: every operation of the instance exactly once, nothing extra
+        let mut seen: HashMap<(usize, usize), &ScheduledOperation> = HashMap::new();
+        for op in schedule {
+            let key = (op.job_id, op.operation_id);
+            if seen.insert(key, op).is_some() {
+                violations.push(Violation::DuplicateOperation { job_id: op.job_id, operation_id: op.operation_id });
+                continue;
+            }
 
-                // Update availability times
-                machine_available_time.insert(operation.machine_id, end_time);
-                job_completion_time.insert(job.id, end_time);
+            let expected = self.jobs().iter()
+                .find(|job| job.id == op.job_id)
+                .and_then(|job| job.operations.get(op.operation_id));
+            match expected {
+                Some(operation) => match operation.duration_on(op.machine_id) {
+                    Some(duration) => {
+                        let actual = op.intervals().map(|(from, to)| to - from).sum::<f64>();
+                        if actual + TIME_EPSILON < duration {
+                            violations.push(Violation::DurationMismatch {
+                                job_id: op.job_id,
+                                operation_id: op.operation_id,
+                                expected: duration,
+                                actual,
+                            });
+                        }
+                    }
+                    None => violations.push(Violation::WrongMachine {
+                        job_id: op.job_id,
+                        operation_id: op.operation_id,
+                        expected: operation.machine_id,
+                        actual: op.machine_id,
+                    }),
+                },
+                None => violations.push(Violation::UnknownOperation { job_id: op.job_id, operation_id: op.operation_id }),
+            }
+
+            // Segments must run one after another from the start to the end
+            if !op.segments.is_empty() {
+                let mut from = op.start_time;
+                let ordered = op.segments.iter().all(|&(start, end)| {
+                    let ok = start + TIME_EPSILON >= from && end + TIME_EPSILON >= start;
+                    from = end;
+                    ok
+                }) && (op.segments[0].0 - op.start_time).abs() < TIME_EPSILON
+                    && (from - op.end_time).abs() < TIME_EPSILON;
+                if !self.preempts() || !ordered {
+                    violations.push(Violation::Preemption { job_id: op.job_id, operation_id: op.operation_id });
+                }
             }
         }
 
-        schedule
+        // Pinned operations must be where they are pinned
+        if self.uses_pins() {
+            for pin in self.pins() {
+                let moved = seen.get(&(pin.job_id, pin.operation_id))
+                    .is_some_and(|op| op.machine_id != pin.machine_id || (op.start_time - pin.start_time).abs() > TIME_EPSILON);
+                if moved {
+                    violations.push(Violation::PinMoved {
+                        job_id: pin.job_id,
+                        operation_id: pin.operation_id,
+                        machine_id: pin.machine_id,
+                        start_time: pin.start_time,
+                    });
+                }
+            }
+        }
+
+        // Precedence: operations of a job must run in order (in an open shop, one at a
+        // time in any order), after the job's release
+        for (job, order) in self.jobs().iter().zip(self.job_sequences(schedule)) {
+            let mut previous: Option<&ScheduledOperation> = None;
+            for op_idx in order {
+                match seen.get(&(job.id, op_idx)) {
+                    Some(&op) => {
+                        if op.start_time + TIME_EPSILON < job.release_time {
+                            violations.push(Violation::EarlyStart {
+                                job_id: job.id,
+                                operation_id: op_idx,
+                                release_time: job.release_time,
+                                start_time: op.start_time,
+                            });
+                        }
+                        if let Some(prev) = previous {
+                            let arrival = prev.end_time + self.transfer_time(Some(prev.machine_id), op.machine_id);
+                            if op.start_time + TIME_EPSILON < prev.end_time {
+                                violations.push(Violation::Precedence {
+                                    job_id: job.id,
+                                    operation_id: op_idx,
+                                    previous_end: prev.end_time,
+                                    start_time: op.start_time,
+                                });
+                            } else if op.start_time + TIME_EPSILON < arrival {
+                                violations.push(Violation::Transfer {
+                                    job_id: job.id,
+                                    operation_id: op_idx,
+                                    arrival,
+                                    start_time: op.start_time,
+                                });
+                            } else if self.buffering == Buffering::NoWait && op.start_time > arrival + TIME_EPSILON {
+                                violations.push(Violation::Wait {
+                                    job_id: job.id,
+                                    operation_id: op_idx,
+                                    previous_end: arrival,
+                                    start_time: op.start_time,
+                                });
+                            }
+                        }
+                        previous = Some(op);
+                    }
+                    None => violations.push(Violation::MissingOperation { job_id: job.id, operation_id: op_idx }),
+                }
+            }
+        }
+
+        // Machine capacity: no two operations (including their setups) on the same machine at once
+        let mut by_machine: HashMap<usize, Vec<&ScheduledOperation>> = HashMap::new();
+        for op in seen.values() {
+            by_machine.entry(op.machine_id).or_default().push(op);
+        }
+        let mut machines: Vec<usize> = by_machine.keys().copied().collect();
+        machines.sort();
+        for machine_id in machines {
+            let ops = by_machine.get_mut(&machine_id).unwrap();
+            ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+            // Each operation needs the setup for the job processed before it
+            let mut previous_job = None;
+            for &op in ops.iter() {
+                let required = self.setup_time(machine_id, previous_job, op.job_id);
+                if op.setup_time + TIME_EPSILON < required {
+                    violations.push(Violation::InsufficientSetup {
+                        machine_id,
+                        job_id: op.job_id,
+                        operation_id: op.operation_id,
+                        required,
+                        actual: op.setup_time,
+                    });
+                }
+                previous_job = Some(op.job_id);
+            }
+
+            // Neither the setup nor the operation may run into planned downtime; a
+            // preempted operation only has to pause during it
+            for &op in ops.iter() {
+                let hit = |w: &&Downtime| w.overlaps(op.setup_start(), op.start_time) || op.intervals().any(|(from, to)| w.overlaps(from, to));
+                if let Some(window) = self.machine_downtime(machine_id).iter().find(hit) {
+                    violations.push(Violation::Downtime {
+                        machine_id,
+                        job_id: op.job_id,
+                        operation_id: op.operation_id,
+                        window: (window.start, window.end),
+                    });
+                }
+            }
+
+            // A blocked machine is free once the previous job has left for its next operation
+            if self.buffering == Buffering::Blocking {
+                for pair in ops.windows(2) {
+                    let (prev, op) = (pair[0], pair[1]);
+                    let released = seen.get(&(prev.job_id, prev.operation_id + 1))
+                        .map_or(prev.end_time, |next| next.start_time - self.transfer_time(Some(prev.machine_id), next.machine_id));
+                    if released > prev.end_time + TIME_EPSILON && op.setup_start() + TIME_EPSILON < released {
+                        violations.push(Violation::Blocked {
+                            machine_id,
+                            first: (prev.job_id, prev.operation_id),
+                            second: (op.job_id, op.operation_id),
+                            released,
+                        });
+                    }
+                }
+            }
+
+            // Compare against the operation that finishes latest so far
+            let mut latest: Option<&ScheduledOperation> = None;
+            for &op in ops.iter() {
+                if let Some(prev) = latest {
+                    if op.setup_start() + TIME_EPSILON < prev.end_time {
+                        violations.push(Violation::MachineOverlap {
+                            machine_id,
+                            first: (prev.job_id, prev.operation_id),
+                            second: (op.job_id, op.operation_id),
+                        });
+                    }
+                }
+                if latest.is_none_or(|prev| op.end_time > prev.end_time) {
+                    latest = Some(op);
+                }
+            }
+        }
+
+        // Secondary resources: an operation may only start while a unit is free
+        if self.uses_resources() {
+            let mut by_resource: Vec<Vec<&ScheduledOperation>> = vec![Vec::new(); self.resources().len()];
+            for op in seen.values() {
+                if let Some(resource_id) = self.resource_of(op.job_id, op.operation_id) {
+                    by_resource[resource_id].push(op);
+                }
+            }
+            for (resource_id, mut ops) in by_resource.into_iter().enumerate() {
+                ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+                let capacity = self.resources()[resource_id].capacity;
+                // Operations holding a unit when the next one starts
+                let mut holding: Vec<&ScheduledOperation> = Vec::new();
+                for op in ops {
+                    holding.retain(|other| other.end_time > op.start_time + TIME_EPSILON);
+                    if holding.len() >= capacity {
+                        let first = holding.iter().max_by(|a, b| a.end_time.total_cmp(&b.end_time)).unwrap();
+                        violations.push(Violation::ResourceOverload {
+                            resource_id,
+                            capacity,
+                            first: (first.job_id, first.operation_id),
+                            second: (op.job_id, op.operation_id),
+                        });
+                    }
+                    holding.push(op);
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
     }
 
+    /// Completion time of the last operation; setups always precede an operation,
+    /// so they are covered by the operation end times
     pub fn calculate_makespan(&self, schedule: &[ScheduledOperation]) -> f64 {
         schedule.iter()
             .map(|op| op.end_time)
             .fold(0.0, f64::max)
     }
+
+    /// Completion time of every job that appears in the schedule
+    pub fn job_completion_times(&self, schedule: &[ScheduledOperation]) -> HashMap<usize, f64> {
+        let mut completion: HashMap<usize, f64> = HashMap::new();
+        for op in schedule {
+            let entry = completion.entry(op.job_id).or_insert(0.0);
+            *entry = entry.max(op.end_time);
+        }
+        completion
+    }
+
+    /// Total time jobs spend in the shop, from release to completion
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::two_by_two;
+    ///
+    /// let instance = two_by_two().instance;
+    /// let schedule = instance.solve_greedy();
+    /// let completion = instance.job_completion_times(&schedule);
+    /// assert_eq!(instance.calculate_flow_time(&schedule), completion.values().sum::<f64>());
+    /// // Unit weights make the weighted completion time equal to the flow time without releases
+    /// assert_eq!(instance.calculate_weighted_completion(&schedule), instance.calculate_flow_time(&schedule));
+    /// ```
+    pub fn calculate_flow_time(&self, schedule: &[ScheduledOperation]) -> f64 {
+        let completion = self.job_completion_times(schedule);
+        self.jobs().iter()
+            .filter_map(|job| Some(completion.get(&job.id)? - job.release_time))
+            .fold(0.0, |total, flow| total + flow)
+    }
+
+    /// Ids of the jobs weighing more than the average job, which the Gantt chart marks
+    /// out as high priority
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// assert!(ft06.high_priority_jobs().is_empty());
+    /// ft06.set_job_weight(2, 3.0);
+    /// assert_eq!(ft06.high_priority_jobs().into_iter().collect::<Vec<_>>(), vec![2]);
+    /// // The heaviest job is placed first
+    /// let first = ft06.solve_greedy().into_iter().find(|op| op.start_time == 0.0 && op.job_id == 2);
+    /// assert!(first.is_some());
+    /// ```
+    pub fn high_priority_jobs(&self) -> HashSet<usize> {
+        let jobs = self.jobs();
+        let mean = jobs.iter().fold(0.0, |total, job| total + job.weight) / jobs.len().max(1) as f64;
+        jobs.iter().filter(|job| job.weight > mean + TIME_EPSILON).map(|job| job.id).collect()
+    }
+
+    /// Sum of job completion times, each multiplied by the job's weight
+    pub fn calculate_weighted_completion(&self, schedule: &[ScheduledOperation]) -> f64 {
+        let completion = self.job_completion_times(schedule);
+        self.jobs().iter()
+            .filter_map(|job| Some(job.weight * completion.get(&job.id)?))
+            .fold(0.0, |total, weighted| total + weighted)
+    }
+
+    /// Lateness (completion - due date) of every job with a due date
+    fn job_lateness(&self, schedule: &[ScheduledOperation]) -> Vec<(&Job, f64)> {
+        let completion = self.job_completion_times(schedule);
+        self.jobs().iter()
+            .filter_map(|job| {
+                let due_date = job.due_date?;
+                let completed = *completion.get(&job.id)?;
+                Some((job, completed - due_date))
+            })
+            .collect()
+    }
+
+    // Folding from 0.0 avoids `sum()` returning -0.0 when no job has a due date
+    pub fn calculate_total_tardiness(&self, schedule: &[ScheduledOperation]) -> f64 {
+        self.job_lateness(schedule).iter()
+            .map(|(_, lateness)| lateness.max(0.0))
+            .fold(0.0, |total, tardiness| total + tardiness)
+    }
+
+    /// Largest lateness over all jobs with a due date (negative if every job is early)
+    pub fn calculate_max_lateness(&self, schedule: &[ScheduledOperation]) -> f64 {
+        let lateness = self.job_lateness(schedule);
+        if lateness.is_empty() {
+            return 0.0;
+        }
+        lateness.iter()
+            .map(|(_, lateness)| *lateness)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    pub fn calculate_weighted_tardiness(&self, schedule: &[ScheduledOperation]) -> f64 {
+        self.job_lateness(schedule).iter()
+            .map(|(job, lateness)| job.weight * lateness.max(0.0))
+            .fold(0.0, |total, tardiness| total + tardiness)
+    }
+
+    pub fn count_late_jobs(&self, schedule: &[ScheduledOperation]) -> usize {
+        self.job_lateness(schedule).iter()
+            .filter(|(_, lateness)| *lateness > TIME_EPSILON)
+            .count()
+    }
+
+    /// Value of the given objective for a schedule (lower is better)
+    pub fn evaluate(&self, objective: Objective, schedule: &[ScheduledOperation]) -> f64 {
+        match objective {
+            Objective::Makespan => self.calculate_makespan(schedule),
+            Objective::TotalFlowTime => self.calculate_flow_time(schedule),
+            Objective::WeightedCompletion => self.calculate_weighted_completion(schedule),
+            Objective::TotalTardiness => self.calculate_total_tardiness(schedule),
+            Objective::MaxLateness => self.calculate_max_lateness(schedule),
+            Objective::WeightedTardiness => self.calculate_weighted_tardiness(schedule),
+            Objective::LateJobs => self.count_late_jobs(schedule) as f64,
+            Objective::Penalized => self.calculate_makespan(schedule) + self.soft_penalty(schedule),
+            Objective::ExpectedMakespan => self.expected_makespan(schedule),
+        }
+    }
+
+    /// Total time machines spend on sequence-dependent setups
+    pub fn calculate_total_setup(&self, schedule: &[ScheduledOperation]) -> f64 {
+        schedule.iter().map(|op| op.setup_time).fold(0.0, |total, setup| total + setup)
+    }
+
+    /// Share of the makespan each machine spends processing operations (setups excluded)
+    pub fn machine_utilization(&self, schedule: &[ScheduledOperation]) -> Vec<f64> {
+        self.statistics(schedule).machines.iter()
+            .map(|machine| machine.utilization)
+            .collect()
+    }
+
+    /// Mean of `machine_utilization` over all machines
+    pub fn calculate_average_utilization(&self, schedule: &[ScheduledOperation]) -> f64 {
+        self.statistics(schedule).average_utilization()
+    }
 }
 
-/// Generate a random JSSP instance
-pub fn generate_random_instance(num_jobs: usize, num_machines: usize, min_duration: f64, max_duration: f64) -> Vec<Job> {
+/// Generate a random setup time matrix for every machine and ordered pair of jobs
+pub fn generate_setup_times(num_jobs: usize, num_machines: usize, min_setup: f64, max_setup: f64) -> SetupTimes {
     use rand::Rng;
     let mut rng = rand::thread_rng();
+
+    let min_setup = min_setup.max(0.0);
+    let max_setup = max_setup.max(min_setup);
+
+    let times = (0..num_machines)
+        .map(|_| {
+            (0..num_jobs)
+                .map(|from| {
+                    (0..num_jobs)
+                        .map(|to| if from == to { 0.0 } else { rng.gen_range(min_setup..=max_setup) })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+
+    SetupTimes { times }
+}
+
+/// Travel times between machines placed at random spots of a square shop floor, scaled
+/// so that opposite corners are `max_transfer` apart. Straight-line distances never
+/// make a detour shorter than the direct route.
+fn generate_transfer_times(num_machines: usize, max_transfer: f64, rng: &mut impl rand::Rng) -> TransferTimes {
+    let spots: Vec<(f64, f64)> = (0..num_machines).map(|_| (rng.gen::<f64>(), rng.gen::<f64>())).collect();
+    let scale = max_transfer.max(0.0) / std::f64::consts::SQRT_2;
+    let times = spots.iter()
+        .map(|a| spots.iter().map(|b| (a.0 - b.0).hypot(a.1 - b.1) * scale).collect())
+        .collect();
+    TransferTimes { times }
+}
+
+/// Which machines the jobs of a generated instance visit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Routing {
+    /// Every machine exactly once, in random order (classic job shop)
+    #[default]
+    Complete,
+    /// Between `min` and `max` operations, each on a different machine; `max` is capped
+    /// at the number of machines
+    Partial { min: usize, max: usize },
+    /// Between `min` and `max` operations on random machines, which a job may visit
+    /// again later (reentrant flow), though never twice in a row
+    Reentrant { min: usize, max: usize },
+}
+
+impl Routing {
+    /// Random machine order of one job's operations
+    fn machines(self, num_machines: usize, rng: &mut impl rand::Rng) -> Vec<usize> {
+        use rand::seq::SliceRandom;
+
+        let mut machines: Vec<usize> = (0..num_machines).collect();
+        machines.shuffle(rng);
+        match self {
+            Routing::Complete => machines,
+            Routing::Partial { min, max } => {
+                let max = max.clamp(1, num_machines);
+                machines.truncate(rng.gen_range(min.clamp(1, max)..=max));
+                machines
+            }
+            Routing::Reentrant { min, max } => {
+                let max = max.max(1);
+                let count = rng.gen_range(min.clamp(1, max)..=max);
+                let mut route: Vec<usize> = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let previous = route.last().copied();
+                    let choices: Vec<usize> = (0..num_machines).filter(|&m| Some(m) != previous || num_machines == 1).collect();
+                    route.push(*choices.choose(rng).expect("at least one machine"));
+                }
+                route
+            }
+        }
+    }
+}
+
+/// How the processing times of a generated instance spread between the minimum and
+/// maximum duration. Uniform durations make unrealistically easy instances; real shops
+/// mix many similar operations with a few long ones.
+///
+/// ```
+/// use jssp_scheduler::jssp::{generate_instance, DurationDistribution, GeneratorConfig};
+///
+/// let durations = |distribution| {
+///     let config = GeneratorConfig { num_jobs: 20, num_machines: 5, min_duration: 1.0, max_duration: 50.0,
+///         duration_distribution: distribution, ..GeneratorConfig::default() };
+///     let instance = generate_instance(&config);
+///     instance.jobs().iter().flat_map(|job| &job.operations).map(|op| op.duration).collect::<Vec<f64>>()
+/// };
+/// let normal = durations(DurationDistribution::Normal { mean: 20.0, std_dev: 3.0 });
+/// assert!(normal.iter().all(|&d| (1.0..=50.0).contains(&d)));
+/// let mean = normal.iter().sum::<f64>() / normal.len() as f64;
+/// assert!((mean - 20.0).abs() < 2.0);
+/// assert!(durations(DurationDistribution::IntegerUniform).iter().all(|d| d.fract() == 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DurationDistribution {
+    /// Every duration in the range equally likely
+    #[default]
+    Uniform,
+    /// Bell-shaped around `mean` with standard deviation `std_dev`, cut off at the range
+    Normal { mean: f64, std_dev: f64 },
+    /// Short and long operations: bells at a quarter and at three quarters of the range,
+    /// `short_share` of the durations from the first
+    Bimodal { short_share: f64 },
+    /// Whole numbers in the range, equally likely, also in fractional time mode
+    IntegerUniform,
+}
+
+impl DurationDistribution {
+    /// Each kind of distribution, with parameters for a `min..=max` range
+    pub fn all(min: f64, max: f64) -> [DurationDistribution; 4] {
+        [
+            DurationDistribution::Uniform,
+            DurationDistribution::Normal { mean: (min + max) / 2.0, std_dev: (max - min) / 6.0 },
+            DurationDistribution::Bimodal { short_share: 0.5 },
+            DurationDistribution::IntegerUniform,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DurationDistribution::Uniform => "Uniform",
+            DurationDistribution::Normal { .. } => "Normal",
+            DurationDistribution::Bimodal { .. } => "Bimodal",
+            DurationDistribution::IntegerUniform => "Integer uniform",
+        }
+    }
+
+    /// One duration between `min` and `max`, a whole number if `integer`
+    fn sample(self, min: f64, max: f64, integer: bool, rng: &mut impl rand::Rng) -> f64 {
+        let duration = match self {
+            DurationDistribution::Uniform if integer => rng.gen_range(min.round() as u64..=max.round() as u64) as f64,
+            DurationDistribution::Uniform => rng.gen_range(min..=max),
+            DurationDistribution::Normal { mean, std_dev } => (mean + std_dev.max(0.0) * standard_normal(rng)).clamp(min, max),
+            DurationDistribution::Bimodal { short_share } => {
+                let range = max - min;
+                let peak = if rng.gen_bool(short_share.clamp(0.0, 1.0)) { min + range / 4.0 } else { min + range * 0.75 };
+                (peak + range / 10.0 * standard_normal(rng)).clamp(min, max)
+            }
+            DurationDistribution::IntegerUniform => {
+                let low = min.ceil();
+                rng.gen_range(low as u64..=max.floor().max(low) as u64) as f64
+            }
+        };
+        if integer { duration.round() } else { duration }
+    }
+}
+
+/// A draw from the standard normal distribution, by the Box-Muller transform
+fn standard_normal(rng: &mut impl rand::Rng) -> f64 {
+    let u = 1.0 - rng.gen::<f64>();
+    let v = rng.gen::<f64>();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
+
+/// Options for `generate_instance`; every optional constraint is generated so that
+/// the resulting instance stays feasible
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub num_jobs: usize,
+    pub num_machines: usize,
+    pub min_duration: f64,
+    pub max_duration: f64,
+    pub alternatives_per_operation: usize,
+    pub routing: Routing,
+    /// A flow shop sends every job along one route drawn for all of them; an open shop
+    /// lets the jobs take their operations in any order (see `shop`)
+    pub problem_type: ProblemType,
+    pub duration_distribution: DurationDistribution,
+    /// Every machine multiplies the durations on it by a factor drawn once from
+    /// `1..=max_machine_slowdown`, so some machines are systematically slower
+    pub max_machine_slowdown: Option<f64>,
+    /// Release times are drawn from `0..=max_release_time`
+    pub max_release_time: Option<f64>,
+    /// Jobs arrive one after another as a Poisson process with this mean time between
+    /// arrivals; takes precedence over `max_release_time`
+    pub mean_interarrival: Option<f64>,
+    /// Due dates are the earliest possible completion times stretched by a factor from `1..=due_date_slack`
+    pub due_date_slack: Option<f64>,
+    /// Setup times are drawn from `0..=max_setup_time`
+    pub max_setup_time: Option<f64>,
+    /// Machines stand at random spots on the shop floor, at most `max_transfer_time`
+    /// of travel apart
+    pub max_transfer_time: Option<f64>,
+    /// Job weights are drawn from `1..=max_weight`; otherwise every job weighs 1
+    pub max_weight: Option<f64>,
+    /// Every operation needs one of this many operators besides its machine (see
+    /// `resources`)
+    pub operators: Option<usize>,
+    /// Every duration is uncertain, with a standard deviation of this share of its mean
+    /// (see `stochastic`)
+    pub duration_variation: Option<f64>,
+    /// Attached to the instance, working hours included; ignored if it leaves no time to
+    /// work (see `Calendar::check`)
+    pub calendar: Option<Calendar>,
+    /// In integer mode every generated time is a whole number of units
+    pub time_mode: TimeMode,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            num_jobs: 5,
+            num_machines: 3,
+            min_duration: 1.0,
+            max_duration: 10.0,
+            alternatives_per_operation: 0,
+            routing: Routing::Complete,
+            problem_type: ProblemType::JobShop,
+            duration_distribution: DurationDistribution::Uniform,
+            max_machine_slowdown: None,
+            max_release_time: None,
+            mean_interarrival: None,
+            due_date_slack: None,
+            max_setup_time: None,
+            max_transfer_time: None,
+            max_weight: None,
+            operators: None,
+            duration_variation: None,
+            calendar: None,
+            time_mode: TimeMode::Fractional,
+        }
+    }
+}
+
+/// Generate a random instance with the optional constraints of `config`.
+///
+/// Feasibility is guaranteed by construction: release times are non-negative,
+/// every due date is at least the job's earliest possible completion, and setup and
+/// transfer matrices satisfy the triangle inequality so no detour is ever shorter than
+/// the direct way. Without machines the instance has no jobs.
+///
+/// ```
+/// use jssp_scheduler::jssp::{generate_instance, GeneratorConfig, Routing};
+///
+/// let config = GeneratorConfig {
+///     num_machines: 0,
+///     routing: Routing::Partial { min: 1, max: 3 },
+///     max_setup_time: Some(5.0),
+///     max_transfer_time: Some(3.0),
+///     ..GeneratorConfig::default()
+/// };
+/// assert!(generate_instance(&config).jobs().is_empty());
+/// ```
+pub fn generate_instance(config: &GeneratorConfig) -> Instance {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+
+    let integer = config.time_mode == TimeMode::Integer;
+    let mut jobs = random_jobs(config);
+
+    let mut arrival = 0.0;
+    for job in &mut jobs {
+        if let Some(mean) = config.mean_interarrival {
+            // Exponential gaps between arrivals
+            arrival += -mean.max(0.0) * (1.0 - rng.gen::<f64>()).ln();
+            job.release_time = if integer { arrival.round() } else { arrival };
+        } else if let Some(max_release) = config.max_release_time {
+            job.release_time = if integer {
+                rng.gen_range(0..=max_release.max(0.0).round() as u64) as f64
+            } else {
+                rng.gen_range(0.0..=max_release.max(0.0))
+            };
+        }
+        if let Some(slack) = config.due_date_slack {
+            let factor = rng.gen_range(1.0..=slack.max(1.0));
+            let due_date = job.release_time + (job.earliest_completion() - job.release_time) * factor;
+            // Rounded up, so a due date is still never earlier than the job can finish
+            job.due_date = Some(if integer { due_date.ceil() } else { due_date });
+        }
+        if let Some(max_weight) = config.max_weight {
+            job.weight = rng.gen_range(1.0..=max_weight.max(1.0)).round();
+        }
+    }
+
+    let mut instance = Instance::new(jobs, config.num_machines)
+        .expect("generated jobs reference only existing machines")
+        .with_name(format!("random {}x{}", config.num_jobs, config.num_machines));
+    instance.set_calendar(config.calendar.clone().filter(|calendar| calendar.check().is_ok()))
+        .expect("checked calendars can be attached");
+    instance.metadata.insert("generator".to_string(), "random".to_string());
+    instance.set_problem_type(config.problem_type).expect("flow shop jobs share one route");
+    let setups = config.max_setup_time.map(|max_setup| {
+        let mut setups = generate_setup_times(config.num_jobs, config.num_machines, 0.0, max_setup);
+        if integer {
+            for time in setups.times.iter_mut().flatten().flatten() {
+                *time = time.round();
+            }
+        }
+        setups.enforce_triangle_inequality();
+        setups
+    });
+    instance.set_setup_times(setups).expect("generated setup matrices match the instance");
+    let transfers = config.max_transfer_time.map(|max_transfer| {
+        let mut transfers = generate_transfer_times(config.num_machines, max_transfer, &mut rng);
+        if integer {
+            for time in transfers.times.iter_mut().flatten() {
+                *time = time.round();
+            }
+            // Rounding may break the triangle inequality by a unit
+            transfers.enforce_triangle_inequality();
+        }
+        transfers
+    });
+    instance.set_transfer_times(transfers).expect("generated transfer matrix matches the instance");
+    if let Some(operators) = config.operators {
+        let requirements = instance.jobs().iter()
+            .flat_map(|job| &job.operations)
+            .map(|op| ResourceRequirement { job_id: op.job_id, operation_id: op.operation_id, resource_id: 0 })
+            .collect();
+        instance.set_resources(vec![SecondaryResource::new("Operators", operators.max(1))], requirements)
+            .expect("generated requirements reference only existing operations");
+    }
+    if let Some(variation) = config.duration_variation {
+        let spreads = instance.jobs().iter()
+            .flat_map(|job| &job.operations)
+            .map(|op| DurationSpread { job_id: op.job_id, operation_id: op.operation_id, std_dev: op.duration * variation.max(0.0) })
+            .collect();
+        instance.set_duration_spreads(spreads, DEFAULT_SCENARIOS, rng.gen())
+            .expect("generated spreads reference only existing operations");
+    }
+    instance.set_time_mode(config.time_mode);
+    instance
+}
+
+/// Generate a random JSSP instance with uniformly distributed durations (see
+/// `GeneratorConfig::duration_distribution` for others).
+/// With `alternatives_per_operation > 0` each operation additionally gets up to that many
+/// other eligible machines with their own durations (flexible job shop).
+pub fn generate_random_instance(
+    num_jobs: usize,
+    num_machines: usize,
+    min_duration: f64,
+    max_duration: f64,
+    alternatives_per_operation: usize,
+) -> Vec<Job> {
+    generate_random_jobs(num_jobs, num_machines, min_duration, max_duration, alternatives_per_operation, Routing::Complete)
+}
+
+/// Like `generate_random_instance`, with jobs visiting the machines given by `routing`;
+/// no jobs at all without machines
+///
+/// ```
+/// use jssp_scheduler::jssp::{generate_random_jobs, Instance, Routing};
+///
+/// let jobs = generate_random_jobs(6, 3, 1.0, 10.0, 0, Routing::Reentrant { min: 2, max: 7 });
+/// assert!(jobs.iter().all(|job| (2..=7).contains(&job.operations.len())));
+/// let instance = Instance::new(jobs, 3).unwrap();
+/// let schedule = instance.solve_greedy();
+/// assert!(instance.validate(&schedule).is_ok());
+/// assert!(generate_random_jobs(6, 0, 1.0, 10.0, 2, Routing::Reentrant { min: 2, max: 7 }).is_empty());
+/// ```
+pub fn generate_random_jobs(
+    num_jobs: usize,
+    num_machines: usize,
+    min_duration: f64,
+    max_duration: f64,
+    alternatives_per_operation: usize,
+    routing: Routing,
+) -> Vec<Job> {
+    random_jobs(&GeneratorConfig {
+        num_jobs,
+        num_machines,
+        min_duration,
+        max_duration,
+        alternatives_per_operation,
+        routing,
+        ..GeneratorConfig::default()
+    })
+}
+
+/// The jobs of `config`'s instance, with whole durations in integer mode
+fn random_jobs(config: &GeneratorConfig) -> Vec<Job> {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let (num_jobs, num_machines) = (config.num_jobs, config.num_machines);
+    let (alternatives_per_operation, routing) = (config.alternatives_per_operation, config.routing);
+    // Jobs need a machine for every operation, so without machines there are none
+    if num_machines == 0 {
+        return Vec::new();
+    }
     
     // Ensure valid duration range
-    let min_dur = min_duration.max(1.0);
-    let max_dur = max_duration.max(min_dur + 0.1);
+    let min_dur = config.min_duration.max(1.0);
+    let max_dur = config.max_duration.max(min_dur + 0.1);
+    let integer = config.time_mode == TimeMode::Integer;
+    let slowdown: Vec<f64> = (0..num_machines)
+        .map(|_| config.max_machine_slowdown.map_or(1.0, |max| rng.gen_range(1.0..=max.max(1.0))))
+        .collect();
+    let draw_duration = |rng: &mut rand::rngs::ThreadRng, machine_id: usize| {
+        let duration = config.duration_distribution.sample(min_dur, max_dur, integer, rng) * slowdown[machine_id];
+        if integer { duration.round() } else { duration }
+    };
     
     let mut jobs = Vec::new();
+    let flow_route = (config.problem_type == ProblemType::FlowShop).then(|| routing.machines(num_machines, &mut rng));
     
     for job_id in 0..num_jobs {
-        let mut machines: Vec<usize> = (0..num_machines).collect();
-        // Shuffle machines for random order
-        for i in (1..machines.len()).rev() {
-            let j = rng.gen_range(0..=i);
-            machines.swap(i, j);
-        }
+        let machines = flow_route.clone().unwrap_or_else(|| routing.machines(num_machines, &mut rng));
         
         let operations: Vec<Operation> = machines.iter().enumerate()
             .map(|(op_id, &machine_id)| {
-                let duration = rng.gen_range(min_dur..=max_dur);
+                let duration = draw_duration(&mut rng, machine_id);
+
+                let num_alternatives = rng.gen_range(0..=alternatives_per_operation.min(num_machines - 1));
+                let mut others: Vec<usize> = (0..num_machines).filter(|&m| m != machine_id).collect();
+                let mut alternatives = Vec::with_capacity(num_alternatives);
+                for _ in 0..num_alternatives {
+                    let alt_machine = others.swap_remove(rng.gen_range(0..others.len()));
+                    alternatives.push(MachineOption {
+                        machine_id: alt_machine,
+                        duration: draw_duration(&mut rng, alt_machine),
+                    });
+                }
+
                 Operation {
                     job_id,
                     operation_id: op_id,
                     machine_id,
                     duration,
+                    alternatives,
                 }
             })
             .collect();
@@ -127,6 +1517,9 @@ pub fn generate_random_instance(num_jobs: usize, num_machines: usize, min_durati
         jobs.push(Job {
             id: job_id,
             operations,
+            release_time: 0.0,
+            due_date: None,
+            weight: 1.0,
         });
     }
     
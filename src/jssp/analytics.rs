@@ -0,0 +1,156 @@
+//! Utilization, idle time and flow time statistics of a schedule.
+
+use super::{Instance, ScheduledOperation, TIME_EPSILON};
+
+/// How one machine spends the makespan
+#[derive(Debug, Clone, PartialEq)]
+pub struct MachineStatistics {
+    pub machine_id: usize,
+    /// Time spent processing operations
+    pub busy: f64,
+    /// Time spent on sequence-dependent setups
+    pub setup: f64,
+    /// Time neither processing nor setting up, up to the makespan
+    pub idle: f64,
+    /// `busy / makespan`
+    pub utilization: f64,
+}
+
+/// Time a job spends in the shop
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobStatistics {
+    pub job_id: usize,
+    /// Completion time minus release time
+    pub flow_time: f64,
+    /// Part of the flow time not spent being processed
+    pub waiting_time: f64,
+}
+
+/// How heavily one machine is loaded, for ranking bottlenecks
+#[derive(Debug, Clone, PartialEq)]
+pub struct MachineLoad {
+    pub machine_id: usize,
+    /// Processing plus setup time
+    pub load: f64,
+    /// Idle periods between consecutive operations
+    pub idle_gaps: usize,
+    /// Total length of those periods
+    pub gap_time: f64,
+    pub longest_gap: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleStatistics {
+    pub makespan: f64,
+    pub machines: Vec<MachineStatistics>,
+    pub jobs: Vec<JobStatistics>,
+}
+
+impl ScheduleStatistics {
+    pub fn total_idle(&self) -> f64 {
+        self.machines.iter().map(|m| m.idle).fold(0.0, |total, idle| total + idle)
+    }
+
+    pub fn average_utilization(&self) -> f64 {
+        mean(self.machines.iter().map(|m| m.utilization))
+    }
+
+    pub fn mean_flow_time(&self) -> f64 {
+        mean(self.jobs.iter().map(|j| j.flow_time))
+    }
+
+    pub fn mean_waiting_time(&self) -> f64 {
+        mean(self.jobs.iter().map(|j| j.waiting_time))
+    }
+
+    /// `time` as a percentage of the makespan
+    pub fn percent_of_makespan(&self, time: f64) -> f64 {
+        if self.makespan > 0.0 { time / self.makespan * 100.0 } else { 0.0 }
+    }
+
+    /// Per-machine totals and percentages, for the export formats
+    pub fn machines_json(&self) -> serde_json::Value {
+        self.machines.iter()
+            .map(|m| serde_json::json!({
+                "machine_id": m.machine_id,
+                "busy": m.busy,
+                "setup": m.setup,
+                "idle": m.idle,
+                "busy_percent": self.percent_of_makespan(m.busy),
+                "setup_percent": self.percent_of_makespan(m.setup),
+                "idle_percent": self.percent_of_makespan(m.idle),
+            }))
+            .collect()
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+impl Instance {
+    /// Per-machine and per-job statistics of a schedule
+    pub fn statistics(&self, schedule: &[ScheduledOperation]) -> ScheduleStatistics {
+        let makespan = self.calculate_makespan(schedule);
+
+        let mut machines: Vec<MachineStatistics> = (0..self.num_machines())
+            .map(|machine_id| MachineStatistics { machine_id, busy: 0.0, setup: 0.0, idle: 0.0, utilization: 0.0 })
+            .collect();
+        for op in schedule {
+            if let Some(machine) = machines.get_mut(op.machine_id) {
+                machine.busy += op.duration;
+                machine.setup += op.setup_time;
+            }
+        }
+        for machine in &mut machines {
+            machine.idle = (makespan - machine.busy - machine.setup).max(0.0);
+            machine.utilization = if makespan > 0.0 { machine.busy / makespan } else { 0.0 };
+        }
+
+        let completion = self.job_completion_times(schedule);
+        let jobs = self.jobs().iter()
+            .filter_map(|job| {
+                let completed = *completion.get(&job.id)?;
+                let processing: f64 = schedule.iter()
+                    .filter(|op| op.job_id == job.id)
+                    .map(|op| op.duration)
+                    .fold(0.0, |total, duration| total + duration);
+                let flow_time = completed - job.release_time;
+                Some(JobStatistics {
+                    job_id: job.id,
+                    flow_time,
+                    waiting_time: (flow_time - processing).max(0.0),
+                })
+            })
+            .collect();
+
+        ScheduleStatistics { makespan, machines, jobs }
+    }
+
+    /// Machines from the most to the least loaded; among equal loads, the one with less
+    /// idle time between its operations comes first. Idle time before the first and
+    /// after the last operation does not count as a gap.
+    pub fn bottleneck_ranking(&self, schedule: &[ScheduledOperation]) -> Vec<MachineLoad> {
+        let mut ranking: Vec<MachineLoad> = (0..self.num_machines())
+            .map(|machine_id| {
+                let mut ops: Vec<&ScheduledOperation> = schedule.iter().filter(|op| op.machine_id == machine_id).collect();
+                ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+                let load = ops.iter().fold(0.0, |total, op| total + op.duration + op.setup_time);
+                let gaps: Vec<f64> = ops.windows(2)
+                    .map(|pair| pair[1].setup_start() - pair[0].end_time)
+                    .filter(|&gap| gap > TIME_EPSILON)
+                    .collect();
+                MachineLoad {
+                    machine_id,
+                    load,
+                    idle_gaps: gaps.len(),
+                    gap_time: gaps.iter().fold(0.0, |total, gap| total + gap),
+                    longest_gap: gaps.iter().copied().fold(0.0, f64::max),
+                }
+            })
+            .collect();
+        ranking.sort_by(|a, b| b.load.total_cmp(&a.load).then(a.gap_time.total_cmp(&b.gap_time)));
+        ranking
+    }
+}
@@ -0,0 +1,393 @@
+//! Simulated annealing over adjacent swaps of critical operations.
+//!
+//! The search starts from a randomized greedy schedule, or from the initial solution
+//! of a warm start (see `SolverConfig`), and repeatedly picks a random swap inside a
+//! critical block (see `DisjunctiveGraph::critical_swaps`). A move is
+//! rated in constant time with `swap_estimate` and accepted if it does not lengthen the
+//! estimate, or otherwise with the Metropolis probability at the current temperature.
+//!
+//! Aimed at another objective (`Solver::solve_objective`), critical blocks say less
+//! about the cost, so half of the moves swap random adjacent operations on any machine,
+//! and every move is rated by evaluating the resulting schedule. No-wait and blocking
+//! instances (see `buffering`) are annealed that way for every objective, with each
+//! schedule re-timed to respect the missing buffers. Swaps leave pinned operations out of
+//! account, so the best schedule is re-timed around the pins at the end (see
+//! `Instance::hold_pins`).
+//!
+//! Long runs can be paused and resumed (see `checkpoint`): a checkpoint keeps the
+//! current schedule and the configuration and temperature scale the run started with.
+//!
+//! ```
+//! use jssp_scheduler::jssp::annealing::SimulatedAnnealing;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::{Objective, Solver};
+//!
+//! let ft06 = ft06().instance;
+//! let (schedule, trace) = SimulatedAnnealing::default().solve_traced(&ft06, 7).unwrap();
+//! let best = trace.best_step();
+//! assert_eq!(trace.makespan_at(best), ft06.calculate_makespan(&schedule));
+//! assert!(ft06.validate(&trace.schedule_at(&ft06, best).unwrap()).is_ok());
+//!
+//! // The best makespan as the search goes, e.g. for a convergence plot
+//! let mut points = Vec::new();
+//! let schedule = SimulatedAnnealing::default()
+//!     .solve_with_progress(&ft06, Objective::Makespan, 7, &mut |point| points.push(point))
+//!     .unwrap();
+//! assert!(points.windows(2).all(|pair| pair[1].best <= pair[0].best && pair[1].iteration >= pair[0].iteration));
+//! assert_eq!(points.last().unwrap().best, ft06.calculate_makespan(&schedule));
+//! ```
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::checkpoint::{self, Checkpoint, Checkpointing, RunEnd, RunStart};
+use super::incremental::DisjunctiveGraph;
+use super::pool::SolutionPool;
+use super::solvers::RandomizedGreedy;
+use super::trace::{ConvergencePoint, ProgressReporter, SearchTrace, TraceStep};
+use super::{Buffering, Instance, Objective, Schedule, Solver, SolverConfig, SolverInfo};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnnealingConfig {
+    pub iterations: usize,
+    /// Starting temperature as a fraction of the starting makespan
+    pub start_temperature: f64,
+    /// Temperature at the last iteration, as a fraction of the starting makespan; the
+    /// temperature falls geometrically in between
+    pub end_temperature: f64,
+}
+
+impl Default for AnnealingConfig {
+    fn default() -> Self {
+        Self { iterations: 20_000, start_temperature: 0.05, end_temperature: 0.001 }
+    }
+}
+
+#[derive(Default)]
+pub struct SimulatedAnnealing {
+    pub config: AnnealingConfig,
+}
+
+/// What a checkpoint keeps of an annealing run besides the best schedule
+#[derive(Serialize, Deserialize)]
+struct AnnealState {
+    /// The configuration the run started with, which a resumed run keeps
+    config: AnnealingConfig,
+    /// The starting value, which the temperatures are fractions of
+    scale: f64,
+    /// The schedule of the current machine sequences
+    current: Schedule,
+    current_value: f64,
+}
+
+/// Where a run begins
+enum Start {
+    /// A new run from this schedule
+    Fresh(Schedule),
+    /// A run to continue from a checkpoint
+    Resume(Resume),
+}
+
+/// A run to continue at `iteration`
+struct Resume {
+    iteration: usize,
+    state: AnnealState,
+    best: Schedule,
+    best_value: f64,
+}
+
+impl SimulatedAnnealing {
+    /// Run the search, recording accepted moves into `trace` if given
+    fn anneal(
+        &self,
+        instance: &Instance,
+        seed: u64,
+        start: Start,
+        mut trace: Option<&mut SearchTrace>,
+        mut reporter: ProgressReporter,
+        mut checkpointing: Option<&mut Checkpointing>,
+    ) -> RunEnd {
+        let (first, config, initial, resumed) = match start {
+            Start::Resume(Resume { iteration, state, best, best_value }) => (iteration, state.config, state.current, Some((best, best_value, state.scale))),
+            Start::Fresh(initial) => (0, self.config.clone(), initial, None),
+        };
+        let Some(mut graph) = DisjunctiveGraph::new(instance, &initial) else {
+            return RunEnd::Finished(initial);
+        };
+        let mut rng = checkpoint::checkpoint_rng(seed, first);
+        let (mut best, mut best_makespan, scale) = resumed.unwrap_or_else(|| (initial, graph.makespan(), graph.makespan().max(1.0)));
+        reporter.offer(best_makespan, || best.clone());
+
+        let (start, end) = (config.start_temperature * scale, config.end_temperature * scale);
+        let iterations = config.iterations.max(1);
+        let mut completed = first;
+        for iteration in first..iterations {
+            if iteration.is_multiple_of(checkpoint::INTERVAL) {
+                rng = checkpoint::checkpoint_rng(seed, iteration);
+            }
+            if let Some(checkpointing) = checkpointing.as_deref_mut() {
+                let checkpoint = || {
+                    let state = AnnealState { config: config.clone(), scale, current: graph.schedule(), current_value: graph.makespan() };
+                    self.checkpoint(instance, Objective::Makespan, seed, Resume { iteration, state, best: best.clone(), best_value: best_makespan })
+                };
+                if let Some(checkpoint) = checkpointing.step(iteration, first, checkpoint) {
+                    return RunEnd::Paused(checkpoint);
+                }
+            }
+            reporter.report(iteration, best_makespan);
+            let swaps = graph.critical_swaps();
+            if swaps.is_empty() {
+                break;
+            }
+            completed = iteration + 1;
+            let (machine_id, position) = swaps[rng.gen_range(0..swaps.len())];
+            let estimate = graph.swap_estimate(machine_id, position).unwrap_or(f64::INFINITY);
+            let delta = estimate - graph.makespan();
+
+            let temperature = start * (end / start).powf(iteration as f64 / iterations as f64);
+            if delta > 0.0 && rng.gen::<f64>() >= (-delta / temperature).exp() {
+                continue;
+            }
+            if !graph.apply_swap(machine_id, position) {
+                continue;
+            }
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.steps.push(TraceStep { iteration, machine_id, position, makespan: graph.makespan() });
+            }
+            reporter.offer(graph.makespan(), || graph.schedule());
+            if graph.makespan() < best_makespan {
+                best_makespan = graph.makespan();
+                best = graph.schedule();
+            }
+        }
+        if let Some(trace) = trace {
+            trace.iterations = completed;
+        }
+        reporter.finish(completed, best_makespan);
+        RunEnd::Finished(instance.hold_pins(best))
+    }
+
+    /// Annealing on `objective` over critical and random adjacent swaps, each rated by
+    /// evaluating the full schedule
+    fn anneal_objective(
+        &self,
+        instance: &Instance,
+        seed: u64,
+        objective: Objective,
+        start: Start,
+        mut reporter: ProgressReporter,
+        mut checkpointing: Option<&mut Checkpointing>,
+    ) -> RunEnd {
+        let (first, config, initial, resumed) = match start {
+            Start::Resume(Resume { iteration, state, best, best_value }) => {
+                (iteration, state.config, state.current, Some((best, best_value, state.scale, state.current_value)))
+            }
+            Start::Fresh(initial) => (0, self.config.clone(), initial, None),
+        };
+        let Some(mut graph) = DisjunctiveGraph::new(instance, &initial) else {
+            return RunEnd::Finished(initial);
+        };
+        let machines: Vec<usize> = (0..instance.num_machines())
+            .filter(|&machine_id| graph.machine_sequence(machine_id).nth(1).is_some())
+            .collect();
+        if machines.is_empty() {
+            return RunEnd::Finished(initial);
+        }
+        let mut rng = checkpoint::checkpoint_rng(seed, first);
+        let (mut best, mut best_value, scale, mut current) = resumed.unwrap_or_else(|| {
+            let value = instance.evaluate(objective, &initial);
+            (initial, value, value.abs().max(1.0), value)
+        });
+        reporter.offer(best_value, || best.clone());
+
+        let (start, end) = (config.start_temperature * scale, config.end_temperature * scale);
+        let iterations = config.iterations.max(1);
+        for iteration in first..iterations {
+            if iteration.is_multiple_of(checkpoint::INTERVAL) {
+                rng = checkpoint::checkpoint_rng(seed, iteration);
+            }
+            if let Some(checkpointing) = checkpointing.as_deref_mut() {
+                let checkpoint = || {
+                    let state = AnnealState { config: config.clone(), scale, current: graph.schedule(), current_value: current };
+                    self.checkpoint(instance, objective, seed, Resume { iteration, state, best: best.clone(), best_value })
+                };
+                if let Some(checkpoint) = checkpointing.step(iteration, first, checkpoint) {
+                    return RunEnd::Paused(checkpoint);
+                }
+            }
+            reporter.report(iteration, best_value);
+            let swaps = graph.critical_swaps();
+            let (machine_id, position) = if !swaps.is_empty() && rng.gen_bool(0.5) {
+                swaps[rng.gen_range(0..swaps.len())]
+            } else {
+                let machine_id = machines[rng.gen_range(0..machines.len())];
+                (machine_id, rng.gen_range(0..graph.machine_sequence(machine_id).count() - 1))
+            };
+            if !graph.apply_swap(machine_id, position) {
+                continue;
+            }
+            let schedule = instance.enforce_buffering(graph.schedule());
+            let value = instance.evaluate(objective, &schedule);
+            let delta = value - current;
+
+            let temperature = start * (end / start).powf(iteration as f64 / iterations as f64);
+            if delta > 0.0 && rng.gen::<f64>() >= (-delta / temperature).exp() {
+                graph.apply_swap(machine_id, position);
+                continue;
+            }
+            current = value;
+            reporter.offer(value, || schedule.clone());
+            if value < best_value {
+                best_value = value;
+                best = schedule;
+            }
+        }
+        reporter.finish(iterations, best_value);
+        RunEnd::Finished(instance.hold_pins(best))
+    }
+
+    fn checkpoint(&self, instance: &Instance, objective: Objective, seed: u64, run: Resume) -> Checkpoint {
+        Checkpoint {
+            algorithm: self.name().to_string(),
+            instance_name: instance.name.clone(),
+            instance_hash: instance.fingerprint(),
+            objective: objective.key().to_string(),
+            seed,
+            iteration: run.iteration,
+            iterations: run.state.config.iterations.max(1),
+            best: run.best,
+            best_value: run.best_value,
+            state: serde_json::to_value(&run.state).unwrap_or_default(),
+            saved_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+
+    /// The objective, seed and state a run starts from
+    fn prepare(&self, instance: &Instance, start: RunStart) -> Result<(Objective, u64, Start), String> {
+        match start {
+            RunStart::New { objective, seed } => Ok((objective, seed, Start::Fresh(RandomizedGreedy.solve_seeded(instance, seed)))),
+            RunStart::Resume(checkpoint) => {
+                checkpoint.check(self.name(), instance)?;
+                let objective = checkpoint.objective().unwrap_or(Objective::Makespan);
+                let state = serde_json::from_value(checkpoint.state).map_err(|e| format!("Invalid annealing state in the checkpoint: {}", e))?;
+                let resume = Resume { iteration: checkpoint.iteration, state, best: checkpoint.best, best_value: checkpoint.best_value };
+                Ok((objective, checkpoint.seed, Start::Resume(resume)))
+            }
+        }
+    }
+}
+
+impl Solver for SimulatedAnnealing {
+    fn name(&self) -> &str {
+        "Simulated Annealing"
+    }
+
+    fn description(&self) -> &str {
+        "Swaps operations in critical blocks, sometimes accepting worse schedules while the temperature is high"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "Starts from a randomized greedy schedule and repeatedly swaps two adjacent operations in a \
+                      block of the critical path. Moves that do not lengthen the schedule are always accepted, \
+                      worse ones with a probability that falls with the temperature, which lets the search \
+                      escape local optima early on and settle later.",
+            complexity: "O(N) per iteration to find the critical swaps, for a fixed number of iterations (20,000 by default)",
+            recommended_sizes: "Small to medium instances, up to a few thousand operations",
+            citation: Some("van Laarhoven, P. J. M., Aarts, E. H. L., & Lenstra, J. K. (1992). Job shop scheduling by simulated annealing. Operations Research, 40(1), 113-125."),
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_seeded(instance, rand::random())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        self.solve_objective(instance, Objective::Makespan, seed)
+    }
+
+    fn solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Schedule {
+        self.solve_with_config(instance, &SolverConfig::new(objective, seed), &mut |_| {})
+    }
+
+    /// Not for no-wait and blocking instances or ones with secondary resources, whose
+    /// schedules the trace cannot replay
+    fn solve_traced(&self, instance: &Instance, seed: u64) -> Option<(Schedule, SearchTrace)> {
+        if instance.buffering != Buffering::Unlimited || instance.uses_resources() {
+            return None;
+        }
+        let mut trace = SearchTrace::new(self.name(), instance, RandomizedGreedy.solve_seeded(instance, seed));
+        let initial = Start::Fresh(trace.initial.clone());
+        let schedule = self.anneal(instance, seed, initial, Some(&mut trace), ProgressReporter::new(None, 0), None).into_schedule();
+        Some((schedule, trace))
+    }
+
+    fn solve_with_progress(
+        &self,
+        instance: &Instance,
+        objective: Objective,
+        seed: u64,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Schedule> {
+        Some(self.solve_with_config(instance, &SolverConfig::new(objective, seed), progress))
+    }
+
+    fn supports_warm_start(&self) -> bool {
+        true
+    }
+
+    fn solve_with_config(&self, instance: &Instance, config: &SolverConfig, progress: &mut dyn FnMut(ConvergencePoint)) -> Schedule {
+        self.solve_with_pool(instance, config, &mut SolutionPool::new(0), progress)
+    }
+
+    fn supports_pool(&self) -> bool {
+        true
+    }
+
+    /// Offers the pool the starting schedule and every accepted move
+    fn solve_with_pool(
+        &self,
+        instance: &Instance,
+        config: &SolverConfig,
+        pool: &mut SolutionPool,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Schedule {
+        let reporter = ProgressReporter::new(Some(progress), self.config.iterations.max(1)).with_pool(pool);
+        let initial = config.valid_initial_solution(instance).cloned()
+            .unwrap_or_else(|| RandomizedGreedy.solve_seeded(instance, config.seed));
+        let (objective, seed) = (config.objective, config.seed);
+        match objective {
+            Objective::Makespan if instance.buffering == Buffering::Unlimited && !instance.uses_resources() => self.anneal(instance, seed, Start::Fresh(initial), None, reporter, None),
+            _ => self.anneal_objective(instance, seed, objective, Start::Fresh(initial), reporter, None),
+        }
+        .into_schedule()
+    }
+
+    fn solve_resumable(
+        &self,
+        instance: &Instance,
+        start: RunStart,
+        checkpointing: &mut Checkpointing,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Result<RunEnd, String>> {
+        let (objective, seed, begin) = match self.prepare(instance, start) {
+            Ok(prepared) => prepared,
+            Err(e) => return Some(Err(e)),
+        };
+        let iterations = match &begin {
+            Start::Resume(resume) => resume.state.config.iterations,
+            Start::Fresh(_) => self.config.iterations,
+        };
+        let reporter = ProgressReporter::new(Some(progress), iterations.max(1));
+        Some(Ok(match objective {
+            Objective::Makespan if instance.buffering == Buffering::Unlimited && !instance.uses_resources() => {
+                self.anneal(instance, seed, begin, None, reporter, Some(checkpointing))
+            }
+            _ => self.anneal_objective(instance, seed, objective, begin, reporter, Some(checkpointing)),
+        }))
+    }
+}
@@ -0,0 +1,192 @@
+//! Solving every instance file of a folder, spread over worker threads.
+//!
+//! Instances are read in the OR-Tools JSON layout (see `ortools`); any solution stored
+//! in a file is ignored. Results arrive on a channel as they complete, so a caller can
+//! show them while the rest are still being solved.
+//!
+//! Files are loaded through an `InstanceSet` (see `cache`), so solving the same folder
+//! again, e.g. with another algorithm, reuses the loaded instances.
+//!
+//! The results of a batch can be written as a CSV table with one row per file
+//! (`results_csv`) and the schedules as one solution file per instance
+//! (`write_solutions`), each recording the run that produced it (see `provenance`).
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::bounds::optimality_gap;
+use super::cache::InstanceSet;
+use super::ortools::from_ortools_json;
+use super::provenance::RunMetadata;
+use super::solution_formats::SolutionFormat;
+use super::{Instance, JsspError, Schedule, Solver};
+
+/// A solved instance of a batch
+#[derive(Debug, Clone)]
+pub struct BatchSolution {
+    pub instance: Instance,
+    pub schedule: Schedule,
+    pub algorithm: String,
+    /// Seed of a stochastic algorithm's run
+    pub seed: Option<u64>,
+    pub makespan: f64,
+    pub lower_bound: f64,
+    pub feasible: bool,
+    pub runtime: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchResult {
+    /// Position of the file in the list given to `spawn_batch`
+    pub index: usize,
+    pub path: PathBuf,
+    /// The solution, or why the file could not be loaded or solved
+    pub outcome: Result<BatchSolution, String>,
+}
+
+/// The instance files (`*.json`) directly inside `dir`, sorted by name
+pub fn instance_files(dir: &Path) -> Result<Vec<PathBuf>, JsspError> {
+    let entries = std::fs::read_dir(dir).map_err(JsspError::io("read", dir))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Read an instance file, named after the file unless it names itself
+pub fn load_instance(path: &Path) -> Result<Instance, JsspError> {
+    let text = std::fs::read_to_string(path).map_err(JsspError::io("read", path))?;
+    let (mut instance, _) = from_ortools_json(&text)?;
+    if instance.name.is_empty() {
+        instance.name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    }
+    instance.metadata.insert("source".to_string(), path.display().to_string());
+    Ok(instance)
+}
+
+pub fn solve_file(path: &Path, solver: &dyn Solver) -> Result<BatchSolution, JsspError> {
+    solve_instance(load_instance(path)?, solver)
+}
+
+fn solve_instance(instance: Instance, solver: &dyn Solver) -> Result<BatchSolution, JsspError> {
+    let seed = rand::random();
+    let started = Instant::now();
+    let schedule = solver.try_solve_seeded(&instance, seed)?;
+    let runtime = started.elapsed();
+    Ok(BatchSolution {
+        algorithm: solver.name().to_string(),
+        seed: solver.is_stochastic().then_some(seed),
+        makespan: instance.calculate_makespan(&schedule),
+        lower_bound: instance.makespan_lower_bound(),
+        feasible: instance.validate(&schedule).is_ok(),
+        runtime,
+        schedule,
+        instance,
+    })
+}
+
+/// Solve the files of `instances` on `threads` worker threads (at least one), returning
+/// a channel that yields one result per file in completion order. Dropping the receiver
+/// makes the workers stop after their current file.
+pub fn spawn_batch(instances: Arc<InstanceSet>, solver: Arc<dyn Solver>, threads: usize) -> Receiver<BatchResult> {
+    let (sender, receiver) = mpsc::channel();
+    let next = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..threads.clamp(1, instances.len().max(1)) {
+        let (sender, instances, next, solver) = (sender.clone(), Arc::clone(&instances), Arc::clone(&next), Arc::clone(&solver));
+        std::thread::spawn(move || loop {
+            let index = next.fetch_add(1, Ordering::Relaxed);
+            let Some(path) = instances.paths().get(index) else {
+                break;
+            };
+            let outcome = instances.get(index)
+                .and_then(|instance| solve_instance(Instance::clone(&instance), solver.as_ref()).map_err(|e| e.to_string()));
+            let result = BatchResult { index, path: path.clone(), outcome };
+            if sender.send(result).is_err() {
+                break;
+            }
+        });
+    }
+
+    receiver
+}
+
+/// One row per result, in the order given: file, size, makespan, lower bound, gap,
+/// the best-known makespan of benchmark instances and the gap to it, feasibility and
+/// runtime, or the error for files that could not be solved
+pub fn results_csv(algorithm: &str, results: &[BatchResult]) -> String {
+    let mut csv = String::from("File,Instance,Algorithm,Jobs,Machines,Operations,Makespan,Lower Bound,Gap (%),Best Known,Gap to Best Known (%),Feasible,Runtime (ms),Error\n");
+    for result in results {
+        let file = result.path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let _ = match &result.outcome {
+            Ok(solution) => {
+                let known = solution.instance.best_known();
+                writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{:.2},{},{},{},{:.3},",
+                    csv_field(&file),
+                    csv_field(&solution.instance.name),
+                    csv_field(algorithm),
+                    solution.instance.jobs().len(),
+                    solution.instance.num_machines(),
+                    solution.instance.num_operations(),
+                    solution.instance.time_mode().format(solution.makespan),
+                    solution.instance.time_mode().format(solution.lower_bound),
+                    optimality_gap(solution.makespan, solution.lower_bound) * 100.0,
+                    known.map_or_else(String::new, |known| known.makespan.to_string()),
+                    known.map_or_else(String::new, |known| format!("{:.2}", known.gap(solution.makespan) * 100.0)),
+                    solution.feasible,
+                    solution.runtime.as_secs_f64() * 1000.0,
+                )
+            }
+            Err(e) => writeln!(csv, "{},,{},,,,,,,,,,,{}", csv_field(&file), csv_field(algorithm), csv_field(e)),
+        };
+    }
+    csv
+}
+
+/// A CSV field, quoted when it contains a separator, quote or line break
+pub(crate) fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+impl BatchSolution {
+    /// The run that produced the schedule, given the algorithm's `parameters`
+    pub fn run(&self, parameters: serde_json::Value) -> RunMetadata {
+        RunMetadata::new(&self.algorithm, parameters, self.seed, 1, self.runtime)
+    }
+}
+
+/// Write the schedule of every solved result into `dir` as `<file stem>_solution.<ext>`,
+/// so solutions never overwrite instance files, each with its run and the algorithm's
+/// `parameters`; returns the number of files written
+pub fn write_solutions(results: &[BatchResult], dir: &Path, format: SolutionFormat, parameters: &serde_json::Value) -> Result<usize, JsspError> {
+    std::fs::create_dir_all(dir).map_err(JsspError::io("create", dir))?;
+    let mut written = 0;
+    for result in results {
+        let Ok(solution) = &result.outcome else {
+            continue;
+        };
+        let stem = result.path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+        let path = dir.join(format!("{}_solution.{}", stem, format.extension()));
+        let text = format.write_with_run(&solution.instance, &solution.schedule, Some(&solution.run(parameters.clone())))?;
+        std::fs::write(&path, text).map_err(JsspError::io("write", &path))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Number of worker threads to use by default: one per available core
+pub fn default_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
@@ -0,0 +1,184 @@
+//! Repeated runs of a solver on one instance, summarized for comparing algorithms.
+//!
+//! Stochastic algorithms give a different schedule on every run, so a single makespan
+//! says little; a `Benchmark` collects the makespan and wall-clock runtime of each run.
+//! Runs can be added one at a time (`run_once`) so a caller can report progress.
+//! A run that fails (see `Solver::try_solve_seeded`) is kept with its error and left
+//! out of the makespan and runtime statistics.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+// std's Instant panics in the browser; this is the same type on other targets
+use web_time::Instant;
+
+use super::batch::csv_field;
+use super::bounds::GapTracker;
+use super::{Instance, JsspError, Solver};
+
+/// Outcome of one solver run
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkRun {
+    pub makespan: f64,
+    pub runtime: Duration,
+    pub feasible: bool,
+    /// Why the run failed; it then has no schedule
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    pub solver: String,
+    pub instance: String,
+    /// Number of runs the benchmark is meant to reach
+    pub planned_runs: usize,
+    pub runs: Vec<BenchmarkRun>,
+    /// Best feasible makespan so far against the instance's lower bound
+    pub gap: GapTracker,
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 { 0.0 } else { sum / count as f64 }
+}
+
+/// Sample standard deviation, 0 with fewer than two values
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values.iter().copied());
+    let variance = values.iter()
+        .map(|value| (value - mean).powi(2))
+        .fold(0.0, |total, value| total + value) / (values.len() - 1) as f64;
+    variance.sqrt()
+}
+
+impl Benchmark {
+    pub fn new(solver: &dyn Solver, instance: &Instance, planned_runs: usize) -> Self {
+        Self {
+            solver: solver.name().to_string(),
+            instance: if instance.name.is_empty() { "unnamed".to_string() } else { instance.name.clone() },
+            planned_runs,
+            runs: Vec::with_capacity(planned_runs),
+            gap: GapTracker::for_instance(instance),
+        }
+    }
+
+    /// Solve once more and record the result, returning the error of a failed run
+    pub fn run_once(&mut self, solver: &dyn Solver, instance: &Instance) -> Result<(), JsspError> {
+        let started = Instant::now();
+        let solved = solver.try_solve_seeded(instance, rand::random());
+        let runtime = started.elapsed();
+        let run = match &solved {
+            Ok(schedule) => BenchmarkRun {
+                makespan: instance.calculate_makespan(schedule),
+                runtime,
+                feasible: instance.validate(schedule).is_ok(),
+                error: None,
+            },
+            Err(e) => BenchmarkRun { makespan: f64::INFINITY, runtime, feasible: false, error: Some(e.to_string()) },
+        };
+        if run.feasible {
+            self.gap.update(run.makespan);
+        }
+        self.runs.push(run);
+        solved.map(|_| ())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.runs.len() >= self.planned_runs
+    }
+
+    /// Fraction of the planned runs done, in `0..=1`
+    pub fn progress(&self) -> f32 {
+        if self.planned_runs == 0 { 1.0 } else { (self.runs.len() as f32 / self.planned_runs as f32).min(1.0) }
+    }
+
+    /// The runs that did not fail
+    fn completed_runs(&self) -> impl Iterator<Item = &BenchmarkRun> {
+        self.runs.iter().filter(|run| run.error.is_none())
+    }
+
+    fn makespans(&self) -> Vec<f64> {
+        self.completed_runs().map(|run| run.makespan).collect()
+    }
+
+    fn runtimes_ms(&self) -> Vec<f64> {
+        self.completed_runs().map(|run| run.runtime.as_secs_f64() * 1000.0).collect()
+    }
+
+    pub fn best_makespan(&self) -> f64 {
+        self.completed_runs().map(|run| run.makespan).fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn worst_makespan(&self) -> f64 {
+        self.completed_runs().map(|run| run.makespan).fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    pub fn mean_makespan(&self) -> f64 {
+        mean(self.makespans().into_iter())
+    }
+
+    pub fn std_makespan(&self) -> f64 {
+        std_dev(&self.makespans())
+    }
+
+    pub fn mean_runtime_ms(&self) -> f64 {
+        mean(self.runtimes_ms().into_iter())
+    }
+
+    pub fn std_runtime_ms(&self) -> f64 {
+        std_dev(&self.runtimes_ms())
+    }
+
+    pub fn feasible_runs(&self) -> usize {
+        self.runs.iter().filter(|run| run.feasible).count()
+    }
+
+    pub fn failed_runs(&self) -> usize {
+        self.runs.len() - self.completed_runs().count()
+    }
+
+    /// The error of the first failed run
+    pub fn first_error(&self) -> Option<&str> {
+        self.runs.iter().find_map(|run| run.error.as_deref())
+    }
+}
+
+/// Run `solver` `runs` times on `instance`, recording failed runs with their errors
+pub fn run_benchmark(solver: &dyn Solver, instance: &Instance, runs: usize) -> Benchmark {
+    let mut benchmark = Benchmark::new(solver, instance, runs);
+    while !benchmark.is_complete() {
+        let _ = benchmark.run_once(solver, instance);
+    }
+    benchmark
+}
+
+/// One summary row per benchmark, with the error of its first failed run, if any
+pub fn benchmarks_to_csv(benchmarks: &[Benchmark]) -> String {
+    let mut csv = String::from(
+        "Solver,Instance,Runs,Feasible Runs,Failed Runs,Best Makespan,Mean Makespan,Std Makespan,Worst Makespan,Lower Bound,Best Gap (%),Mean Runtime (ms),Std Runtime (ms),Error\n"
+    );
+    for benchmark in benchmarks {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{:.2},{:.2},{:.2},{:.2},{:.2},{},{:.3},{:.3},{}",
+            csv_field(&benchmark.solver),
+            csv_field(&benchmark.instance),
+            benchmark.runs.len(),
+            benchmark.feasible_runs(),
+            benchmark.failed_runs(),
+            benchmark.best_makespan(),
+            benchmark.mean_makespan(),
+            benchmark.std_makespan(),
+            benchmark.worst_makespan(),
+            benchmark.gap.lower_bound,
+            benchmark.gap.current_gap().map_or(String::new(), |gap| format!("{:.2}", gap * 100.0)),
+            benchmark.mean_runtime_ms(),
+            benchmark.std_runtime_ms(),
+            csv_field(benchmark.first_error().unwrap_or_default())
+        );
+    }
+    csv
+}
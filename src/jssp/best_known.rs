@@ -0,0 +1,208 @@
+//! Best-known makespans of the classic benchmark instances.
+//!
+//! Fisher and Thompson's ft06, ft10 and ft20, Lawrence's la01–la40 and Taillard's
+//! ta01–ta80 are recognized by name and size. For most of them the best-known makespan
+//! has been proven optimal; for the others (`optimal == false`) it is the best upper
+//! bound in the literature when the table was compiled, so a result below it would be
+//! a new record.
+//!
+//! ```
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Buffering;
+//!
+//! let mut instance = ft06().instance;
+//! assert!(instance.best_known().is_none());
+//! instance.name = "ft06".to_string();
+//!
+//! let known = instance.best_known().unwrap();
+//! assert_eq!((known.makespan, known.optimal), (55.0, true));
+//! assert!((known.gap(60.5) - 0.1).abs() < 1e-9);
+//!
+//! // Only the original problem: the no-wait variant of ft06 has another optimum
+//! instance.buffering = Buffering::NoWait;
+//! assert!(instance.best_known().is_none());
+//! ```
+
+use super::{Buffering, Instance};
+
+/// The best makespan known for a benchmark instance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BestKnown {
+    pub name: &'static str,
+    pub num_jobs: usize,
+    pub num_machines: usize,
+    pub makespan: f64,
+    /// Whether the makespan is proven optimal
+    pub optimal: bool,
+}
+
+impl BestKnown {
+    /// How far a makespan is above the best-known one, `(makespan - best) / best`, as
+    /// benchmark papers report it; negative for a makespan below it
+    pub fn gap(&self, makespan: f64) -> f64 {
+        (makespan - self.makespan) / self.makespan
+    }
+
+    /// "optimum" or "best known", for messages
+    pub fn kind(&self) -> &'static str {
+        if self.optimal { "optimum" } else { "best known" }
+    }
+}
+
+const fn known(name: &'static str, num_jobs: usize, num_machines: usize, makespan: f64, optimal: bool) -> BestKnown {
+    BestKnown { name, num_jobs, num_machines, makespan, optimal }
+}
+
+/// From Fisher & Thompson (1963), Lawrence (1984) and Taillard (1993), with the
+/// optima and upper bounds reported since
+const BEST_KNOWN: &[BestKnown] = &[
+    known("ft06", 6, 6, 55.0, true),
+    known("ft10", 10, 10, 930.0, true),
+    known("ft20", 20, 5, 1165.0, true),
+    known("la01", 10, 5, 666.0, true),
+    known("la02", 10, 5, 655.0, true),
+    known("la03", 10, 5, 597.0, true),
+    known("la04", 10, 5, 590.0, true),
+    known("la05", 10, 5, 593.0, true),
+    known("la06", 15, 5, 926.0, true),
+    known("la07", 15, 5, 890.0, true),
+    known("la08", 15, 5, 863.0, true),
+    known("la09", 15, 5, 951.0, true),
+    known("la10", 15, 5, 958.0, true),
+    known("la11", 20, 5, 1222.0, true),
+    known("la12", 20, 5, 1039.0, true),
+    known("la13", 20, 5, 1150.0, true),
+    known("la14", 20, 5, 1292.0, true),
+    known("la15", 20, 5, 1207.0, true),
+    known("la16", 10, 10, 945.0, true),
+    known("la17", 10, 10, 784.0, true),
+    known("la18", 10, 10, 848.0, true),
+    known("la19", 10, 10, 842.0, true),
+    known("la20", 10, 10, 902.0, true),
+    known("la21", 15, 10, 1046.0, true),
+    known("la22", 15, 10, 927.0, true),
+    known("la23", 15, 10, 1032.0, true),
+    known("la24", 15, 10, 935.0, true),
+    known("la25", 15, 10, 977.0, true),
+    known("la26", 20, 10, 1218.0, true),
+    known("la27", 20, 10, 1235.0, true),
+    known("la28", 20, 10, 1216.0, true),
+    known("la29", 20, 10, 1152.0, true),
+    known("la30", 20, 10, 1355.0, true),
+    known("la31", 30, 10, 1784.0, true),
+    known("la32", 30, 10, 1850.0, true),
+    known("la33", 30, 10, 1719.0, true),
+    known("la34", 30, 10, 1721.0, true),
+    known("la35", 30, 10, 1888.0, true),
+    known("la36", 15, 15, 1268.0, true),
+    known("la37", 15, 15, 1397.0, true),
+    known("la38", 15, 15, 1196.0, true),
+    known("la39", 15, 15, 1233.0, true),
+    known("la40", 15, 15, 1222.0, true),
+    known("ta01", 15, 15, 1231.0, true),
+    known("ta02", 15, 15, 1244.0, true),
+    known("ta03", 15, 15, 1218.0, true),
+    known("ta04", 15, 15, 1175.0, true),
+    known("ta05", 15, 15, 1224.0, true),
+    known("ta06", 15, 15, 1238.0, true),
+    known("ta07", 15, 15, 1227.0, true),
+    known("ta08", 15, 15, 1217.0, true),
+    known("ta09", 15, 15, 1274.0, true),
+    known("ta10", 15, 15, 1241.0, true),
+    known("ta11", 20, 15, 1357.0, false),
+    known("ta12", 20, 15, 1367.0, false),
+    known("ta13", 20, 15, 1342.0, false),
+    known("ta14", 20, 15, 1345.0, false),
+    known("ta15", 20, 15, 1339.0, false),
+    known("ta16", 20, 15, 1360.0, false),
+    known("ta17", 20, 15, 1462.0, false),
+    known("ta18", 20, 15, 1396.0, false),
+    known("ta19", 20, 15, 1332.0, false),
+    known("ta20", 20, 15, 1348.0, false),
+    known("ta21", 20, 20, 1642.0, false),
+    known("ta22", 20, 20, 1600.0, false),
+    known("ta23", 20, 20, 1557.0, false),
+    known("ta24", 20, 20, 1644.0, false),
+    known("ta25", 20, 20, 1595.0, false),
+    known("ta26", 20, 20, 1643.0, false),
+    known("ta27", 20, 20, 1680.0, false),
+    known("ta28", 20, 20, 1603.0, false),
+    known("ta29", 20, 20, 1625.0, false),
+    known("ta30", 20, 20, 1584.0, false),
+    known("ta31", 30, 15, 1764.0, false),
+    known("ta32", 30, 15, 1784.0, false),
+    known("ta33", 30, 15, 1791.0, false),
+    known("ta34", 30, 15, 1828.0, false),
+    known("ta35", 30, 15, 2007.0, false),
+    known("ta36", 30, 15, 1819.0, false),
+    known("ta37", 30, 15, 1771.0, false),
+    known("ta38", 30, 15, 1673.0, false),
+    known("ta39", 30, 15, 1795.0, false),
+    known("ta40", 30, 15, 1669.0, false),
+    known("ta41", 30, 20, 2005.0, false),
+    known("ta42", 30, 20, 1937.0, false),
+    known("ta43", 30, 20, 1846.0, false),
+    known("ta44", 30, 20, 1979.0, false),
+    known("ta45", 30, 20, 2000.0, false),
+    known("ta46", 30, 20, 2004.0, false),
+    known("ta47", 30, 20, 1889.0, false),
+    known("ta48", 30, 20, 1937.0, false),
+    known("ta49", 30, 20, 1960.0, false),
+    known("ta50", 30, 20, 1923.0, false),
+    known("ta51", 50, 15, 2760.0, true),
+    known("ta52", 50, 15, 2756.0, true),
+    known("ta53", 50, 15, 2717.0, true),
+    known("ta54", 50, 15, 2839.0, true),
+    known("ta55", 50, 15, 2679.0, true),
+    known("ta56", 50, 15, 2781.0, true),
+    known("ta57", 50, 15, 2943.0, true),
+    known("ta58", 50, 15, 2885.0, true),
+    known("ta59", 50, 15, 2655.0, true),
+    known("ta60", 50, 15, 2723.0, true),
+    known("ta61", 50, 20, 2868.0, true),
+    known("ta62", 50, 20, 2869.0, true),
+    known("ta63", 50, 20, 2755.0, true),
+    known("ta64", 50, 20, 2702.0, true),
+    known("ta65", 50, 20, 2725.0, true),
+    known("ta66", 50, 20, 2845.0, true),
+    known("ta67", 50, 20, 2825.0, true),
+    known("ta68", 50, 20, 2784.0, true),
+    known("ta69", 50, 20, 3071.0, true),
+    known("ta70", 50, 20, 2995.0, true),
+    known("ta71", 100, 20, 5464.0, true),
+    known("ta72", 100, 20, 5181.0, true),
+    known("ta73", 100, 20, 5568.0, true),
+    known("ta74", 100, 20, 5339.0, true),
+    known("ta75", 100, 20, 5392.0, true),
+    known("ta76", 100, 20, 5342.0, true),
+    known("ta77", 100, 20, 5436.0, true),
+    known("ta78", 100, 20, 5394.0, true),
+    known("ta79", 100, 20, 5358.0, true),
+    known("ta80", 100, 20, 5183.0, true),
+];
+
+/// The entry for a benchmark name like "la01", "LA01" or "la01.json"
+pub fn lookup(name: &str) -> Option<&'static BestKnown> {
+    let name = name.trim();
+    let name = name.strip_suffix(".json").or_else(|| name.strip_suffix(".txt")).unwrap_or(name);
+    BEST_KNOWN.iter().find(|known| known.name.eq_ignore_ascii_case(name))
+}
+
+impl Instance {
+    /// The best-known makespan if this is a classic benchmark instance: the name must be
+    /// in the table and the instance the plain job shop of that size, without release
+    /// dates, setups, transfer times, alternative machines, downtime, working hours,
+    /// secondary resources, pins or buffer limits, and not an open shop
+    pub fn best_known(&self) -> Option<&'static BestKnown> {
+        let known = lookup(&self.name)?;
+        let plain = self.buffering == Buffering::Unlimited
+            && !self.is_open_shop()
+            && self.setup_times().is_none()
+            && self.transfer_times().is_none()
+            && self.unavailable().is_empty()
+            && self.resources().is_empty()
+            && self.pins().is_empty()
+            && self.jobs().iter().all(|job| job.release_time == 0.0 && job.operations.iter().all(|op| op.alternatives.is_empty()));
+        (plain && known.num_jobs == self.jobs().len() && known.num_machines == self.num_machines()).then_some(known)
+    }
+}
@@ -0,0 +1,301 @@
+//! Makespan lower bounds, quick estimates of the makespan range, and the optimality gap
+//! of a schedule against them.
+//!
+//! `estimate_lower_bound` and `estimate_greedy_upper_bound` bracket the optimal makespan
+//! without building a schedule, in time linear in the operations for the lower estimate
+//! and in operations times jobs for the upper one, for triaging many instances at once:
+//!
+//! ```
+//! use jssp_scheduler::jssp::bounds::{estimate_greedy_upper_bound, estimate_lower_bound};
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let (low, high) = (estimate_lower_bound(&ft06), estimate_greedy_upper_bound(&ft06));
+//! assert!(low <= 55.0 && 55.0 <= high); // the proven optimum lies in between
+//! assert!(low <= ft06.makespan_lower_bound());
+//! ```
+
+use serde::Serialize;
+
+use super::{Instance, TIME_EPSILON};
+
+/// The makespan lower bounds of an instance, each from a different relaxation that
+/// ignores setups. `best` is the strongest of them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LowerBounds {
+    /// Longest job: its release time plus the shortest processing time of every operation
+    pub job_length: f64,
+    /// Busiest machine: the earliest any of its operations can start, plus all of its
+    /// work, plus the shortest remaining work after any of them (operations that can run
+    /// elsewhere are left out)
+    pub machine_load: f64,
+    /// Each machine scheduled alone with operations that may be interrupted, each
+    /// released after its job's earlier work and followed by its job's later work, or in
+    /// an open shop released with its job and followed by nothing (Jackson's preemptive
+    /// schedule, optimal for that relaxation)
+    pub one_machine: f64,
+    /// The total shortest work spread evenly over all machines
+    pub pooled: f64,
+}
+
+impl LowerBounds {
+    pub fn best(&self) -> f64 {
+        self.job_length.max(self.machine_load).max(self.one_machine).max(self.pooled)
+    }
+}
+
+impl Instance {
+    /// A makespan no schedule can beat: the best of `lower_bounds`
+    pub fn makespan_lower_bound(&self) -> f64 {
+        self.lower_bounds().best()
+    }
+
+    /// Every lower bound of `LowerBounds`, e.g. to show which relaxation is binding
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let bounds = ft06().instance.lower_bounds();
+    /// assert!(bounds.one_machine >= bounds.machine_load);
+    /// assert!(bounds.best() <= 55.0); // the proven optimum
+    /// ```
+    pub fn lower_bounds(&self) -> LowerBounds {
+        let relaxation = self.relaxation();
+        let machine_load = relaxation.machine_load();
+        let pooled = relaxation.pooled(self.num_machines());
+        let one_machine = relaxation.machines.into_iter()
+            .map(preemptive_one_machine)
+            .fold(0.0, f64::max);
+        LowerBounds { job_length: relaxation.job_length, machine_load, one_machine, pooled }
+    }
+
+    fn relaxation(&self) -> Relaxation {
+        let shortest = |op: &super::Operation| op.options().map(|o| o.duration).fold(f64::INFINITY, f64::min);
+
+        let job_length = self.jobs().iter()
+            .map(|job| job.earliest_completion())
+            .fold(0.0, f64::max);
+
+        let mut machines: Vec<Vec<(f64, f64, f64)>> = vec![Vec::new(); self.num_machines()];
+        let mut total_work = 0.0;
+        // An open shop job may take any operation first or last
+        let open = self.is_open_shop();
+        for job in self.jobs() {
+            let durations: Vec<f64> = job.operations.iter().map(shortest).collect();
+            let mut head = job.release_time;
+            let mut tail: f64 = durations.iter().fold(0.0, |total, d| total + d);
+            for (op, duration) in job.operations.iter().zip(&durations) {
+                tail -= duration;
+                total_work += duration;
+                if !op.is_flexible() {
+                    let bounds = if open { (job.release_time, *duration, 0.0) } else { (head, *duration, tail.max(0.0)) };
+                    machines[op.machine_id].push(bounds);
+                }
+                head += duration;
+            }
+        }
+        Relaxation { job_length, machines, total_work }
+    }
+}
+
+/// What the relaxations of `LowerBounds` are computed from, with setups ignored and
+/// every operation at its shortest processing time
+struct Relaxation {
+    job_length: f64,
+    /// (head, duration, tail) of the operations bound to each machine
+    machines: Vec<Vec<(f64, f64, f64)>>,
+    total_work: f64,
+}
+
+impl Relaxation {
+    fn machine_load(&self) -> f64 {
+        self.machines.iter()
+            .filter(|ops| !ops.is_empty())
+            .map(|ops| {
+                let head = ops.iter().map(|op| op.0).fold(f64::INFINITY, f64::min);
+                let work = ops.iter().fold(0.0, |total, op| total + op.1);
+                let tail = ops.iter().map(|op| op.2).fold(f64::INFINITY, f64::min);
+                head + work + tail
+            })
+            .fold(0.0, f64::max)
+    }
+
+    fn pooled(&self, num_machines: usize) -> f64 {
+        if num_machines > 0 { self.total_work / num_machines as f64 } else { 0.0 }
+    }
+}
+
+/// A makespan no schedule can beat, from the job-length, machine-load and pooled bounds
+/// of `LowerBounds` only: linear in the operations, it skips the one-machine relaxation
+/// and so is never above `Instance::makespan_lower_bound`
+///
+/// ```
+/// use jssp_scheduler::jssp::bounds::estimate_lower_bound;
+/// use jssp_scheduler::jssp::testing::ft06;
+///
+/// let ft06 = ft06().instance;
+/// let bounds = ft06.lower_bounds();
+/// assert_eq!(estimate_lower_bound(&ft06), bounds.job_length.max(bounds.machine_load).max(bounds.pooled));
+/// ```
+pub fn estimate_lower_bound(instance: &Instance) -> f64 {
+    let relaxation = instance.relaxation();
+    relaxation.job_length.max(relaxation.machine_load()).max(relaxation.pooled(instance.num_machines()))
+}
+
+/// The makespan a greedy dispatcher reaches, computed from machine and job ready times
+/// alone without building the schedule: it repeatedly starts the next operation of
+/// whichever job can finish one first, on its quickest eligible machine, with setups
+/// and transfers, breaking ties towards the job with the most work left.
+///
+/// This is the makespan of a feasible schedule, and so an upper bound on the optimum,
+/// for instances without downtime, working hours, secondary resources or buffer limits;
+/// it ignores those and is only an estimate where they apply.
+///
+/// ```
+/// use jssp_scheduler::jssp::bounds::estimate_greedy_upper_bound;
+/// use jssp_scheduler::jssp::testing::ft06;
+///
+/// let ft06 = ft06().instance;
+/// let estimate = estimate_greedy_upper_bound(&ft06);
+/// assert!(estimate >= 55.0); // the proven optimum
+/// // One machine's worth of every operation in a row is always feasible
+/// let serial: f64 = ft06.jobs().iter().flat_map(|job| &job.operations).map(|op| op.duration).sum();
+/// assert!(estimate <= serial);
+/// ```
+pub fn estimate_greedy_upper_bound(instance: &Instance) -> f64 {
+    let jobs = instance.jobs();
+    let mut machine_ready = vec![0.0; instance.num_machines()];
+    let mut machine_last_job: Vec<Option<usize>> = vec![None; instance.num_machines()];
+    let mut job_ready: Vec<f64> = jobs.iter().map(|job| job.release_time).collect();
+    let mut job_last_machine: Vec<Option<usize>> = vec![None; jobs.len()];
+    let mut next_operation = vec![0; jobs.len()];
+    let mut work_left: Vec<f64> = jobs.iter()
+        .map(|job| job.operations.iter().map(|op| op.duration).sum())
+        .collect();
+
+    for _ in 0..instance.num_operations() {
+        // (job, machine, end, setup-free work of the operation) of the earliest finish
+        let mut best: Option<(usize, usize, f64, f64)> = None;
+        for (job_id, job) in jobs.iter().enumerate() {
+            let Some(op) = job.operations.get(next_operation[job_id]) else {
+                continue;
+            };
+            for option in op.options() {
+                let machine_id = option.machine_id;
+                let setup = instance.setup_time(machine_id, machine_last_job[machine_id], job_id);
+                let arrival = job_ready[job_id] + instance.transfer_time(job_last_machine[job_id], machine_id);
+                let end = (machine_ready[machine_id] + setup).max(arrival) + option.duration;
+                let better = best.is_none_or(|(best_job, _, best_end, _)| {
+                    end < best_end - TIME_EPSILON || (end <= best_end + TIME_EPSILON && work_left[job_id] > work_left[best_job])
+                });
+                if better {
+                    best = Some((job_id, machine_id, end, op.duration));
+                }
+            }
+        }
+        let Some((job_id, machine_id, end, work)) = best else {
+            break;
+        };
+        machine_ready[machine_id] = end;
+        machine_last_job[machine_id] = Some(job_id);
+        job_ready[job_id] = end;
+        job_last_machine[job_id] = Some(machine_id);
+        next_operation[job_id] += 1;
+        work_left[job_id] -= work;
+    }
+    job_ready.into_iter().fold(0.0, f64::max)
+}
+
+/// Optimal makespan of one machine whose operations `(head, duration, tail)` may be
+/// interrupted: whenever the machine is free or an operation is released, it works on
+/// the released operation with the longest tail
+fn preemptive_one_machine(mut ops: Vec<(f64, f64, f64)>) -> f64 {
+    ops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // (remaining duration, tail) of released, unfinished operations
+    let mut ready: Vec<(f64, f64)> = Vec::new();
+    let (mut time, mut next, mut bound) = (0.0_f64, 0, 0.0_f64);
+    while next < ops.len() || !ready.is_empty() {
+        if ready.is_empty() {
+            time = time.max(ops[next].0);
+        }
+        while next < ops.len() && ops[next].0 <= time + TIME_EPSILON {
+            ready.push((ops[next].1, ops[next].2));
+            next += 1;
+        }
+        let (index, _) = ready.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.1.total_cmp(&b.1.1))
+            .unwrap();
+        let next_release = ops.get(next).map_or(f64::INFINITY, |op| op.0);
+        let run = ready[index].0.min(next_release - time);
+        time += run;
+        ready[index].0 -= run;
+        if ready[index].0 <= TIME_EPSILON {
+            bound = bound.max(time + ready[index].1);
+            ready.swap_remove(index);
+        }
+    }
+    bound
+}
+
+/// Relative distance of a makespan above a lower bound, `(makespan - bound) / makespan`,
+/// in `0..=1`
+pub fn optimality_gap(makespan: f64, lower_bound: f64) -> f64 {
+    if makespan <= 0.0 {
+        0.0
+    } else {
+        ((makespan - lower_bound) / makespan).clamp(0.0, 1.0)
+    }
+}
+
+/// Follows the incumbent of an anytime search, to report progress as a closing gap
+/// rather than as iterations
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapTracker {
+    pub lower_bound: f64,
+    /// Gap of the first incumbent
+    pub initial_gap: Option<f64>,
+    pub best_makespan: Option<f64>,
+}
+
+impl GapTracker {
+    pub fn new(lower_bound: f64) -> Self {
+        Self { lower_bound, initial_gap: None, best_makespan: None }
+    }
+
+    pub fn for_instance(instance: &Instance) -> Self {
+        Self::new(instance.makespan_lower_bound())
+    }
+
+    /// Record a new solution's makespan; returns whether it improved the incumbent
+    pub fn update(&mut self, makespan: f64) -> bool {
+        if self.initial_gap.is_none() {
+            self.initial_gap = Some(optimality_gap(makespan, self.lower_bound));
+        }
+        let improved = self.best_makespan.is_none_or(|best| makespan < best);
+        if improved {
+            self.best_makespan = Some(makespan);
+        }
+        improved
+    }
+
+    pub fn current_gap(&self) -> Option<f64> {
+        self.best_makespan.map(|best| optimality_gap(best, self.lower_bound))
+    }
+
+    /// How much of the way to the bound the incumbent is, `lower_bound / best`, in `0..=1`
+    pub fn fraction(&self) -> f32 {
+        self.current_gap().map_or(0.0, |gap| (1.0 - gap) as f32)
+    }
+
+    /// E.g. "gap 14.0% ➡ 6.0%", or "gap 6.0%" before any improvement
+    pub fn label(&self) -> String {
+        match (self.initial_gap, self.current_gap()) {
+            (Some(initial), Some(current)) if initial - current > 1e-9 => {
+                format!("gap {:.1}% ➡ {:.1}%", initial * 100.0, current * 100.0)
+            }
+            (_, Some(current)) => format!("gap {:.1}%", current * 100.0),
+            _ => "no solution yet".to_string(),
+        }
+    }
+}
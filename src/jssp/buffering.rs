@@ -0,0 +1,375 @@
+//! Job shops without buffers between machines: the no-wait and blocking variants.
+//!
+//! In the classic job shop a job that finishes on one machine waits in a buffer, for as
+//! long as it takes, until its next machine is free. Chemical and steel plants often
+//! have no such buffer:
+//!
+//! - **No-wait**: every operation starts the moment the previous operation of its job
+//!   ends (plus the transfer between their machines), e.g. because the material would
+//!   cool down in between.
+//! - **Blocking**: a finished job stays on its machine, which can do nothing else, until
+//!   the job's next machine takes it over. Two jobs may swap machines at the same instant.
+//!
+//! For such instances `Instance::decode` places the jobs one after another, in the order
+//! they first appear in the sequence, a no-wait job as one rigid block.
+//! `Instance::schedule_from_sequences` keeps the given machine orders and returns `None`
+//! if they admit no start times, e.g. when blocked jobs wait for each other in a circle.
+//! `Instance::enforce_buffering` turns any schedule into one that respects the variant.
+//!
+//! ```
+//! use jssp_scheduler::jssp::Buffering;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance;
+//! let buffered = ft06.solve_greedy();
+//!
+//! ft06.buffering = Buffering::NoWait;
+//! assert!(ft06.validate(&buffered).is_err());
+//! let no_wait = ft06.solve_greedy();
+//! assert!(ft06.validate(&no_wait).is_ok());
+//!
+//! ft06.buffering = Buffering::Blocking;
+//! assert!(ft06.validate(&no_wait).is_ok());
+//! let blocking = ft06.enforce_buffering(buffered);
+//! assert!(ft06.validate(&blocking).is_ok());
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Instance, Job, Schedule, ScheduledOperation};
+
+/// What happens to a job between two of its operations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Buffering {
+    /// The job waits in an unlimited buffer, leaving its machine free (classic job shop)
+    #[default]
+    Unlimited,
+    /// The job never waits: each operation starts when the previous one ends
+    NoWait,
+    /// The job waits on the machine it finished on, blocking it
+    Blocking,
+}
+
+impl Buffering {
+    pub const ALL: [Buffering; 3] = [Buffering::Unlimited, Buffering::NoWait, Buffering::Blocking];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Buffering::Unlimited => "Buffered",
+            Buffering::NoWait => "No-Wait",
+            Buffering::Blocking => "Blocking",
+        }
+    }
+
+    /// Identifier used in files and on the command line
+    pub fn key(&self) -> &'static str {
+        match self {
+            Buffering::Unlimited => "unlimited",
+            Buffering::NoWait => "no_wait",
+            Buffering::Blocking => "blocking",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Buffering> {
+        Buffering::ALL.into_iter().find(|buffering| buffering.key() == key)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Buffering::Unlimited => "Jobs wait between operations in a buffer, leaving the machine free",
+            Buffering::NoWait => "Every operation starts the moment the previous operation of its job ends",
+            Buffering::Blocking => "A finished job occupies its machine until its next machine is free",
+        }
+    }
+}
+
+/// An operation of `timetable_without_buffers`, linked to its neighbours by index
+struct Node {
+    key: (usize, usize),
+    machine_id: usize,
+    duration: f64,
+    setup: f64,
+    /// Travel time from the machine of the job predecessor
+    transfer: f64,
+    /// Earliest start from the release time and the caller's bounds
+    earliest: f64,
+    job_predecessor: Option<usize>,
+    job_successor: Option<usize>,
+    machine_predecessor: Option<usize>,
+}
+
+impl Instance {
+    /// The schedule with start times that respect the instance's buffering, keeping the
+    /// machine orders if they allow it and otherwise placing the jobs in the order they
+    /// start. Schedules of buffered instances are returned unchanged, unless operations
+    /// wait for secondary resources, which they are then re-timed for (see `resources`).
+    pub fn enforce_buffering(&self, schedule: Schedule) -> Schedule {
+        if (self.buffering == Buffering::Unlimited && !self.uses_resources()) || schedule.is_empty() {
+            return schedule;
+        }
+        if let Some(retimed) = self.schedule_from_sequences(&self.machine_sequences(&schedule), &HashMap::new()) {
+            return retimed;
+        }
+        let mut first_starts: Vec<(f64, usize)> = self.jobs().iter()
+            .filter_map(|job| {
+                let start = schedule.iter()
+                    .filter(|op| op.job_id == job.id)
+                    .map(|op| op.start_time)
+                    .fold(f64::INFINITY, f64::min);
+                start.is_finite().then_some((start, job.id))
+            })
+            .collect();
+        first_starts.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let sequence: Vec<usize> = first_starts.into_iter()
+            .flat_map(|(_, job_id)| std::iter::repeat_n(job_id, self.jobs()[job_id].operations.len()))
+            .collect();
+        self.decode(&sequence)
+    }
+
+    /// `decode` without buffers: jobs in the order of their first occurrence, each
+    /// placed after the jobs before it, with as many operations as it occurs
+    pub(super) fn decode_without_buffers(&self, sequence: &[usize]) -> Schedule {
+        let mut counts = vec![0; self.jobs().len()];
+        let mut order = Vec::new();
+        for &job_idx in sequence {
+            let Some(count) = counts.get_mut(job_idx) else {
+                continue;
+            };
+            if *count == 0 {
+                order.push(job_idx);
+            }
+            *count += 1;
+        }
+
+        // When each machine can start its next setup, and the job it processed last
+        let mut machine_free = vec![0.0; self.num_machines()];
+        let mut machine_last_job: Vec<Option<usize>> = vec![None; self.num_machines()];
+        let mut schedule = Vec::with_capacity(sequence.len());
+        for job_idx in order {
+            let job = &self.jobs()[job_idx];
+            let count = counts[job_idx].min(job.operations.len());
+            let placed = match self.buffering {
+                Buffering::NoWait => self.place_no_wait(job, count, &machine_free, &machine_last_job),
+                _ => self.place_blocking(job, count, &mut machine_free, &machine_last_job),
+            };
+            for op in &placed {
+                machine_free[op.machine_id] = f64::max(machine_free[op.machine_id], op.end_time);
+                machine_last_job[op.machine_id] = Some(job.id);
+            }
+            schedule.extend(placed);
+        }
+        schedule
+    }
+
+    /// The first `count` operations of a no-wait job as one block, started as early as
+    /// the machines and their downtime allow. Flexible operations take the machine that
+    /// lets the block end there earliest.
+    fn place_no_wait(&self, job: &Job, count: usize, machine_free: &[f64], machine_last_job: &[Option<usize>]) -> Schedule {
+        // Machine, setup, offset from the block start and duration of every operation
+        let mut plan: Vec<(usize, f64, f64, f64)> = Vec::with_capacity(count);
+        let mut start = job.release_time;
+        // Where the previous operation ends, relative to the block start
+        let mut offset = 0.0;
+        for operation in &job.operations[..count] {
+            let previous_machine = plan.last().map(|&(machine_id, ..)| machine_id);
+            let (machine_id, setup, earliest, at, duration) = operation.options()
+                .map(|option| {
+                    // The job travels straight on, so the transfer lengthens the block
+                    let at = offset + self.transfer_time(previous_machine, option.machine_id);
+                    // A machine the job visited before is free for it by then, without setup
+                    let revisit = plan.iter().any(|&(machine_id, ..)| machine_id == option.machine_id);
+                    let setup = if revisit { 0.0 } else { self.setup_time(option.machine_id, machine_last_job[option.machine_id], job.id) };
+                    let earliest = if revisit { start } else { start.max(machine_free[option.machine_id] + setup - at) };
+                    (option.machine_id, setup, earliest, at, option.duration)
+                })
+                .min_by(|a, b| (a.2 + a.3 + a.4).total_cmp(&(b.2 + b.3 + b.4)))
+                .unwrap();
+            start = earliest;
+            plan.push((machine_id, setup, at, duration));
+            offset = at + duration;
+        }
+
+        // Shift the block until neither an operation nor a setup runs into downtime
+        loop {
+            let delayed = plan.iter()
+                .filter_map(|&(machine_id, setup, offset, duration)| {
+                    let available = self.available_start(machine_id, start + offset, setup, duration);
+                    (available > start + offset).then_some(available - offset)
+                })
+                .fold(f64::NEG_INFINITY, f64::max);
+            if delayed <= start {
+                break;
+            }
+            start = delayed;
+        }
+
+        plan.into_iter()
+            .enumerate()
+            .map(|(operation_id, (machine_id, setup_time, offset, duration))| ScheduledOperation {
+                job_id: job.id,
+                operation_id,
+                machine_id,
+                start_time: start + offset,
+                end_time: start + offset + duration,
+                duration,
+                setup_time,
+                segments: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// The first `count` operations of a job that blocks its machine until the next
+    /// one is free, each as early as possible on the machine that finishes it first.
+    /// Marks each machine free from when the job leaves it.
+    fn place_blocking(&self, job: &Job, count: usize, machine_free: &mut [f64], machine_last_job: &[Option<usize>]) -> Schedule {
+        let mut placed: Schedule = Vec::with_capacity(count);
+        let mut ready = job.release_time;
+        for operation in &job.operations[..count] {
+            let (option, setup_time, start_time) = operation.options()
+                .map(|option| {
+                    let revisit = placed.iter().any(|op| op.machine_id == option.machine_id);
+                    let last_job = if revisit { Some(job.id) } else { machine_last_job[option.machine_id] };
+                    let setup = self.setup_time(option.machine_id, last_job, job.id);
+                    let arrival = ready + self.transfer_time(placed.last().map(|op| op.machine_id), option.machine_id);
+                    let start = (machine_free[option.machine_id] + setup).max(arrival);
+                    (option, setup, self.available_start(option.machine_id, start, setup, option.duration))
+                })
+                .min_by(|a, b| (a.2 + a.0.duration).total_cmp(&(b.2 + b.0.duration)))
+                .unwrap();
+            // The job leaves its previous machine as it sets off for this one
+            if let Some(previous) = placed.last() {
+                let departure = start_time - self.transfer_time(Some(previous.machine_id), option.machine_id);
+                machine_free[previous.machine_id] = f64::max(machine_free[previous.machine_id], departure);
+            }
+            machine_free[option.machine_id] = start_time + option.duration;
+            ready = start_time + option.duration;
+            placed.push(ScheduledOperation {
+                job_id: job.id,
+                operation_id: placed.len(),
+                machine_id: option.machine_id,
+                start_time,
+                end_time: start_time + option.duration,
+                duration: option.duration,
+                setup_time,
+                segments: Vec::new(),
+            });
+        }
+        placed
+    }
+
+    /// `schedule_from_sequences` without buffers. Start times only ever grow from the
+    /// lower bounds until every constraint holds; if they keep growing, the machine
+    /// orders leave jobs waiting for each other and there is no timetable.
+    pub(super) fn timetable_without_buffers(
+        &self,
+        sequences: &[Vec<(usize, usize)>],
+        earliest_starts: &HashMap<(usize, usize), f64>,
+    ) -> Option<Schedule> {
+        let mut index: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut nodes = Vec::new();
+        for (machine_id, sequence) in sequences.iter().enumerate() {
+            for (position, &key) in sequence.iter().enumerate() {
+                let (job_id, operation_id) = key;
+                let job = self.jobs().get(job_id)?;
+                let operation = job.operations.get(operation_id)?;
+                let previous_job = position.checked_sub(1).map(|previous| sequence[previous].0);
+                index.insert(key, nodes.len());
+                nodes.push(Node {
+                    key,
+                    machine_id,
+                    duration: operation.duration_on(machine_id).unwrap_or(operation.duration),
+                    setup: self.setup_time(machine_id, previous_job, job_id),
+                    transfer: 0.0,
+                    earliest: earliest_starts.get(&key).copied().unwrap_or(0.0)
+                        .max(if operation_id == 0 { job.release_time } else { 0.0 }),
+                    job_predecessor: None,
+                    job_successor: None,
+                    machine_predecessor: (position > 0).then(|| nodes.len() - 1),
+                });
+            }
+        }
+        for node in 0..nodes.len() {
+            let (job_id, operation_id) = nodes[node].key;
+            if let Some(&previous) = operation_id.checked_sub(1).and_then(|previous| index.get(&(job_id, previous))) {
+                nodes[node].job_predecessor = Some(previous);
+                nodes[node].transfer = self.transfer_time(Some(nodes[previous].machine_id), nodes[node].machine_id);
+                nodes[previous].job_successor = Some(node);
+            }
+        }
+
+        // Relax in topological order of the job and machine arcs; a cycle there is infeasible
+        let mut in_degree: Vec<usize> = nodes.iter()
+            .map(|node| usize::from(node.job_predecessor.is_some()) + usize::from(node.machine_predecessor.is_some()))
+            .collect();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for (node, entry) in nodes.iter().enumerate() {
+            for predecessor in [entry.job_predecessor, entry.machine_predecessor].into_iter().flatten() {
+                successors[predecessor].push(node);
+            }
+        }
+        let mut ready: VecDeque<usize> = (0..nodes.len()).filter(|&node| in_degree[node] == 0).collect();
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &next in &successors[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        if order.len() < nodes.len() {
+            return None;
+        }
+
+        let mut start = vec![0.0; nodes.len()];
+        let rounds = (nodes.len() + 1) * (self.unavailable().len() + 1) + 1;
+        for _ in 0..rounds {
+            let mut changed = false;
+            for &node in &order {
+                let entry = &nodes[node];
+                let mut earliest = entry.earliest;
+                if let Some(previous) = entry.job_predecessor {
+                    earliest = earliest.max(start[previous] + nodes[previous].duration + entry.transfer);
+                }
+                if let Some(previous) = entry.machine_predecessor {
+                    earliest = earliest.max(start[previous] + nodes[previous].duration + entry.setup);
+                    // The machine is free only once the previous job has moved on
+                    if let (Buffering::Blocking, Some(moved_on)) = (self.buffering, nodes[previous].job_successor) {
+                        earliest = earliest.max(start[moved_on] - nodes[moved_on].transfer + entry.setup);
+                    }
+                }
+                // Starting too early would leave the job waiting for its next operation
+                if let (Buffering::NoWait, Some(next)) = (self.buffering, entry.job_successor) {
+                    earliest = earliest.max(start[next] - nodes[next].transfer - entry.duration);
+                }
+                let earliest = self.available_start(entry.machine_id, earliest, entry.setup, entry.duration);
+                if earliest > start[node] {
+                    start[node] = earliest;
+                    changed = true;
+                }
+            }
+            if !changed {
+                let mut schedule: Schedule = nodes.iter()
+                    .zip(start)
+                    .map(|(node, start_time)| ScheduledOperation {
+                        job_id: node.key.0,
+                        operation_id: node.key.1,
+                        machine_id: node.machine_id,
+                        start_time,
+                        end_time: start_time + node.duration,
+                        duration: node.duration,
+                        setup_time: node.setup,
+                        segments: Vec::new(),
+                    })
+                    .collect();
+                schedule.sort_by_key(|op| (op.job_id, op.operation_id));
+                return Some(schedule);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,482 @@
+//! A compact binary instance format, memory-mapped loading, and lazily loaded instance
+//! sets shared between worker threads.
+//!
+//! Parsing JSON dominates the cost of loading small instances, which adds up over
+//! benchmark sets of thousands of files solved with several algorithms and seeds. An
+//! `InstanceCache` keeps a binary copy of every instance file next to it (in a
+//! `.jssp-cache` folder) and reads that copy through a memory map while the source file
+//! is unchanged. An `InstanceSet` loads each of its files at most once, on first use,
+//! and hands out the same `Arc<Instance>` to every thread and run.
+//!
+//! The binary layout stores the jobs, machine count, job and machine names, setup and
+//! transfer times, downtime, buffering, preemption, secondary resources, duration
+//! spreads with their scenario count and seed, name and metadata; calendars and soft
+//! constraints are left out, like in the OR-Tools layout.
+//!
+//! ```
+//! use jssp_scheduler::jssp::cache::{decode_instance, encode_instance};
+//! use jssp_scheduler::jssp::stochastic::DurationSpread;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance.with_name("ft06");
+//! ft06.set_job_names((0..6).map(|job_id| format!("Order #{}", 4711 + job_id)).collect()).unwrap();
+//! ft06.set_duration_spreads(vec![DurationSpread { job_id: 0, operation_id: 1, std_dev: 0.5 }], 8, 42).unwrap();
+//! let bytes = encode_instance(&ft06);
+//! let decoded = decode_instance(&bytes).unwrap();
+//! assert_eq!(decoded.name, "ft06");
+//! assert_eq!(decoded.job_name(5), "Order #4716");
+//! assert_eq!(decoded.machine_name(2), "Machine 2");
+//! assert_eq!(decoded.calculate_makespan(&decoded.solve_greedy()), ft06.calculate_makespan(&ft06.solve_greedy()));
+//! assert_eq!(decoded.duration_spreads(), ft06.duration_spreads());
+//! let schedule = ft06.solve_greedy();
+//! assert_eq!(decoded.scenario_makespans(&schedule), ft06.scenario_makespans(&schedule));
+//! assert!(decode_instance(&bytes[..bytes.len() - 1]).is_err());
+//! ```
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use memmap2::Mmap;
+
+use super::batch::load_instance;
+use super::resources::{ResourceRequirement, SecondaryResource};
+use super::stochastic::DurationSpread;
+use super::{Buffering, Downtime, Instance, Job, JsspError, MachineOption, Operation, ProblemType, SetupTimes, TransferTimes};
+
+const MAGIC: &[u8; 8] = b"JSSPBIN\0";
+const VERSION: u32 = 9;
+/// Versions before 2 have no downtime, before 3 no buffering, before 4 no preemption,
+/// before 5 no job names, before 6 no transfer times, before 7 no secondary resources,
+/// before 8 no problem type and before 9 no duration spreads; they are still read
+const OLDEST_VERSION: u32 = 1;
+
+/// Size and modification time of the file an instance was read from, stored in the
+/// binary copy so a changed source is noticed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceStamp {
+    pub len: u64,
+    /// Nanoseconds since the Unix epoch
+    pub modified: u64,
+}
+
+impl SourceStamp {
+    pub fn of(path: &Path) -> Result<Self, JsspError> {
+        let metadata = std::fs::metadata(path).map_err(JsspError::io("read", path))?;
+        let modified = metadata.modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |since| since.as_nanos() as u64);
+        Ok(Self { len: metadata.len(), modified })
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u32(&mut self, value: usize) {
+        self.0.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.u32(value.len());
+        self.0.extend_from_slice(value.as_bytes());
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.at.checked_add(n).filter(|end| *end <= self.bytes.len()).ok_or("Binary instance is truncated")?;
+        let slice = &self.bytes[self.at..end];
+        self.at = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<usize, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<String, String> {
+        let len = self.u32()?;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| "Binary instance holds invalid UTF-8".to_string())
+    }
+
+    /// A count of items that each take at least `item_size` bytes, checked against the
+    /// bytes left so corrupt counts fail instead of allocating
+    fn count(&mut self, item_size: usize) -> Result<usize, String> {
+        let count = self.u32()?;
+        if count.saturating_mul(item_size) > self.bytes.len() - self.at {
+            return Err("Binary instance is truncated".to_string());
+        }
+        Ok(count)
+    }
+}
+
+/// The instance in the binary layout, without a source stamp
+pub fn encode_instance(instance: &Instance) -> Vec<u8> {
+    encode_with_stamp(instance, SourceStamp::default())
+}
+
+fn encode_with_stamp(instance: &Instance, stamp: SourceStamp) -> Vec<u8> {
+    let mut w = Writer(Vec::with_capacity(64 + instance.num_operations() * 24));
+    w.0.extend_from_slice(MAGIC);
+    w.u32(VERSION as usize);
+    w.u64(stamp.len);
+    w.u64(stamp.modified);
+    w.str(&instance.name);
+    w.u32(instance.num_machines());
+
+    w.u32(instance.jobs().len());
+    for job in instance.jobs() {
+        w.f64(job.release_time);
+        w.f64(job.due_date.unwrap_or(f64::NAN));
+        w.f64(job.weight);
+        w.u32(job.operations.len());
+        for op in &job.operations {
+            w.u32(op.alternatives.len() + 1);
+            for option in op.options() {
+                w.u32(option.machine_id);
+                w.f64(option.duration);
+            }
+        }
+    }
+
+    w.u32(instance.machine_names().len());
+    for name in instance.machine_names() {
+        w.str(name);
+    }
+
+    match instance.setup_times() {
+        Some(setups) => {
+            w.u32(1);
+            for value in setups.times.iter().flatten().flatten() {
+                w.f64(*value);
+            }
+        }
+        None => w.u32(0),
+    }
+
+    w.u32(instance.downtime().len());
+    for window in instance.downtime() {
+        w.u32(window.machine_id);
+        w.f64(window.start);
+        w.f64(window.end);
+    }
+
+    w.u32(Buffering::ALL.iter().position(|&buffering| buffering == instance.buffering).unwrap_or(0));
+    w.u32(instance.preemptive as usize);
+    w.u32(instance.job_names().len());
+    for name in instance.job_names() {
+        w.str(name);
+    }
+    match instance.transfer_times() {
+        Some(transfers) => {
+            w.u32(1);
+            for value in transfers.times.iter().flatten() {
+                w.f64(*value);
+            }
+        }
+        None => w.u32(0),
+    }
+    w.u32(instance.resources().len());
+    for resource in instance.resources() {
+        w.str(&resource.name);
+        w.u32(resource.capacity);
+    }
+    let requirements = instance.resource_requirements();
+    w.u32(requirements.len());
+    for requirement in requirements {
+        w.u32(requirement.job_id);
+        w.u32(requirement.operation_id);
+        w.u32(requirement.resource_id);
+    }
+    w.u32(ProblemType::ALL.iter().position(|&problem_type| problem_type == instance.problem_type()).unwrap_or(0));
+    let spreads = instance.duration_spreads();
+    w.u32(spreads.len());
+    for spread in &spreads {
+        w.u32(spread.job_id);
+        w.u32(spread.operation_id);
+        w.f64(spread.std_dev);
+    }
+    w.u32(instance.scenario_count());
+    w.u64(instance.scenario_seed());
+
+    w.u32(instance.metadata.len());
+    for (key, value) in &instance.metadata {
+        w.str(key);
+        w.str(value);
+    }
+    w.0
+}
+
+/// Read an instance written by `encode_instance`, checking it like `Instance::new` does
+pub fn decode_instance(bytes: &[u8]) -> Result<Instance, JsspError> {
+    decode_with_stamp(bytes).map(|(instance, _)| instance).map_err(JsspError::Parse)
+}
+
+/// The layout version and source stamp
+fn read_header(r: &mut Reader) -> Result<(usize, SourceStamp), String> {
+    if r.take(MAGIC.len())? != MAGIC {
+        return Err("Not a binary instance file".to_string());
+    }
+    let version = r.u32()?;
+    if !(OLDEST_VERSION as usize..=VERSION as usize).contains(&version) {
+        return Err(format!("Unsupported binary instance version {}", version));
+    }
+    Ok((version, SourceStamp { len: r.u64()?, modified: r.u64()? }))
+}
+
+fn decode_with_stamp(bytes: &[u8]) -> Result<(Instance, SourceStamp), String> {
+    let mut r = Reader { bytes, at: 0 };
+    let (version, stamp) = read_header(&mut r)?;
+    let name = r.str()?;
+    let num_machines = r.u32()?;
+
+    let num_jobs = r.count(28)?;
+    let mut jobs = Vec::with_capacity(num_jobs);
+    for id in 0..num_jobs {
+        let release_time = r.f64()?;
+        let due_date = Some(r.f64()?).filter(|due| !due.is_nan());
+        let weight = r.f64()?;
+        let num_operations = r.count(16)?;
+        let mut operations = Vec::with_capacity(num_operations);
+        for operation_id in 0..num_operations {
+            let num_options = r.count(12)?;
+            let mut options = Vec::with_capacity(num_options);
+            for _ in 0..num_options {
+                options.push(MachineOption { machine_id: r.u32()?, duration: r.f64()? });
+            }
+            let Some((first, alternatives)) = options.split_first() else {
+                return Err(format!("Job {} operation {} has no machine", id, operation_id));
+            };
+            operations.push(Operation {
+                job_id: id,
+                operation_id,
+                machine_id: first.machine_id,
+                duration: first.duration,
+                alternatives: alternatives.to_vec(),
+            });
+        }
+        jobs.push(Job { id, operations, release_time, due_date, weight });
+    }
+    let mut instance = Instance::new(jobs, num_machines)?.with_name(name);
+
+    let num_names = r.count(4)?;
+    let mut names = (0..num_names).map(|_| r.str()).collect::<Result<Vec<_>, _>>()?;
+    if version < 5 {
+        // Older versions wrote the default names of unnamed machines
+        for (machine_id, name) in names.iter_mut().enumerate() {
+            if *name == format!("Machine {}", machine_id) {
+                name.clear();
+            }
+        }
+    }
+    instance.set_machine_names(names)?;
+
+    if r.u32()? == 1 {
+        let values = num_machines.saturating_mul(num_jobs).saturating_mul(num_jobs);
+        if values.saturating_mul(8) > bytes.len() - r.at {
+            return Err("Binary instance is truncated".to_string());
+        }
+        let mut times = vec![vec![vec![0.0; num_jobs]; num_jobs]; num_machines];
+        for value in times.iter_mut().flatten().flatten() {
+            *value = r.f64()?;
+        }
+        instance.set_setup_times(Some(SetupTimes { times }))?;
+    }
+
+    if version >= 2 {
+        let num_windows = r.count(20)?;
+        let downtime = (0..num_windows)
+            .map(|_| Ok(Downtime { machine_id: r.u32()?, start: r.f64()?, end: r.f64()? }))
+            .collect::<Result<Vec<_>, String>>()?;
+        instance.set_downtime(downtime)?;
+    }
+
+    if version >= 3 {
+        let code = r.u32()?;
+        instance.buffering = *Buffering::ALL.get(code).ok_or_else(|| format!("Unknown buffering {} in binary instance", code))?;
+    }
+
+    if version >= 4 {
+        instance.preemptive = r.u32()? != 0;
+    }
+
+    if version >= 5 {
+        let num_names = r.count(4)?;
+        let names = (0..num_names).map(|_| r.str()).collect::<Result<Vec<_>, _>>()?;
+        instance.set_job_names(names)?;
+    }
+
+    if version >= 6 && r.u32()? == 1 {
+        if num_machines.saturating_mul(num_machines).saturating_mul(8) > bytes.len() - r.at {
+            return Err("Binary instance is truncated".to_string());
+        }
+        let mut times = vec![vec![0.0; num_machines]; num_machines];
+        for value in times.iter_mut().flatten() {
+            *value = r.f64()?;
+        }
+        instance.set_transfer_times(Some(TransferTimes { times }))?;
+    }
+
+    if version >= 7 {
+        let num_resources = r.count(8)?;
+        let resources = (0..num_resources)
+            .map(|_| Ok(SecondaryResource { name: r.str()?, capacity: r.u32()? }))
+            .collect::<Result<Vec<_>, String>>()?;
+        let num_requirements = r.count(12)?;
+        let requirements = (0..num_requirements)
+            .map(|_| Ok(ResourceRequirement { job_id: r.u32()?, operation_id: r.u32()?, resource_id: r.u32()? }))
+            .collect::<Result<Vec<_>, String>>()?;
+        instance.set_resources(resources, requirements)?;
+    }
+
+    if version >= 8 {
+        let code = r.u32()?;
+        let problem_type = *ProblemType::ALL.get(code).ok_or_else(|| format!("Unknown problem type {} in binary instance", code))?;
+        instance.set_problem_type(problem_type)?;
+    }
+
+    if version >= 9 {
+        let num_spreads = r.count(16)?;
+        let spreads = (0..num_spreads)
+            .map(|_| Ok(DurationSpread { job_id: r.u32()?, operation_id: r.u32()?, std_dev: r.f64()? }))
+            .collect::<Result<Vec<_>, String>>()?;
+        let scenarios = r.u32()?;
+        instance.set_duration_spreads(spreads, scenarios, r.u64()?)?;
+    }
+
+    let num_entries = r.count(8)?;
+    for _ in 0..num_entries {
+        let key = r.str()?;
+        instance.metadata.insert(key, r.str()?);
+    }
+    Ok((instance, stamp))
+}
+
+/// Read a binary instance file through a memory map
+pub fn read_binary(path: &Path) -> Result<Instance, JsspError> {
+    map_binary(path).map(|(instance, _)| instance)
+}
+
+fn map_binary(path: &Path) -> Result<(Instance, SourceStamp), JsspError> {
+    let file = File::open(path).map_err(JsspError::io("open", path))?;
+    // Safety: the map is only read while decoding; cache files are replaced by rename,
+    // never rewritten in place, so the mapped bytes do not change underneath
+    let map = unsafe { Mmap::map(&file) }.map_err(JsspError::io("map", path))?;
+    decode_with_stamp(&map).map_err(|e| JsspError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Binary copies of instance files, kept in one folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceCache {
+    pub dir: PathBuf,
+}
+
+impl InstanceCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache of the instance files in `folder`, stored in `folder/.jssp-cache`
+    pub fn in_folder(folder: &Path) -> Self {
+        Self::new(folder.join(".jssp-cache"))
+    }
+
+    /// Where the binary copy of `source` is kept
+    pub fn entry(&self, source: &Path) -> PathBuf {
+        let stem = source.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        self.dir.join(format!("{}.jsspbin", stem))
+    }
+
+    /// Load an instance file, from its binary copy if that is up to date, otherwise by
+    /// parsing it and then refreshing the copy. Failing to write the copy is not an error.
+    pub fn load(&self, source: &Path) -> Result<Instance, JsspError> {
+        let stamp = SourceStamp::of(source)?;
+        let entry = self.entry(source);
+        if let Ok((instance, cached)) = map_binary(&entry) {
+            if cached == stamp {
+                return Ok(instance);
+            }
+        }
+        let instance = load_instance(source)?;
+        self.store(&entry, &instance, stamp).ok();
+        Ok(instance)
+    }
+
+    fn store(&self, entry: &Path, instance: &Instance, stamp: SourceStamp) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        // Write elsewhere and rename, so readers never map a half-written file
+        let temp = entry.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&temp, encode_with_stamp(instance, stamp))?;
+        std::fs::rename(&temp, entry)
+    }
+}
+
+/// Instance files loaded on first use and shared between threads; each file is read
+/// (and parsed or mapped) at most once for the lifetime of the set
+pub struct InstanceSet {
+    paths: Vec<PathBuf>,
+    cache: Option<InstanceCache>,
+    loaded: Vec<OnceLock<Result<Arc<Instance>, String>>>,
+}
+
+impl InstanceSet {
+    pub fn new(paths: Vec<PathBuf>, cache: Option<InstanceCache>) -> Self {
+        let loaded = paths.iter().map(|_| OnceLock::new()).collect();
+        Self { paths, cache, loaded }
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    pub fn cache(&self) -> Option<&InstanceCache> {
+        self.cache.as_ref()
+    }
+
+    /// The instance of file `index`, loading it if this is the first request
+    pub fn get(&self, index: usize) -> Result<Arc<Instance>, String> {
+        let (Some(path), Some(slot)) = (self.paths.get(index), self.loaded.get(index)) else {
+            return Err(format!("No instance {} in a set of {}", index, self.len()));
+        };
+        slot.get_or_init(|| {
+            let instance = match &self.cache {
+                Some(cache) => cache.load(path),
+                None => load_instance(path),
+            };
+            instance.map(Arc::new).map_err(String::from)
+        })
+        .clone()
+    }
+}
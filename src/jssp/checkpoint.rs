@@ -0,0 +1,190 @@
+//! Checkpoints of long searches, so a run can be paused and resumed later.
+//!
+//! A resumable run (`Solver::solve_resumable`) stops every `INTERVAL` iterations to
+//! hand a `Checkpoint` to `Checkpointing::save` and to ask `Checkpointing::pause`
+//! whether to stop there. A checkpoint holds the best schedule so far and whatever else
+//! the algorithm needs to continue, e.g. the current schedule of an annealing run, and
+//! is saved as JSON. Resumable algorithms seed their random number generator again
+//! from the run's seed every `INTERVAL` iterations (see `checkpoint_rng`), whether or
+//! not the run takes checkpoints, so a resumed run continues exactly like one that never
+//! paused, and gives the same result as a plain run with that seed.
+//!
+//! ```
+//! use std::cell::Cell;
+//! use jssp_scheduler::jssp::annealing::SimulatedAnnealing;
+//! use jssp_scheduler::jssp::checkpoint::{Checkpoint, Checkpointing, RunEnd, RunStart};
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::{Objective, Solver};
+//!
+//! let ft06 = ft06().instance;
+//! let annealing = SimulatedAnnealing::default();
+//! let start = || RunStart::New { objective: Objective::Makespan, seed: 3 };
+//! let run = |start, pause_at: usize| {
+//!     let saved = Cell::new(0);
+//!     let mut checkpointing = Checkpointing {
+//!         save: &mut |_| saved.set(saved.get() + 1),
+//!         pause: &|| saved.get() == pause_at,
+//!     };
+//!     annealing.solve_resumable(&ft06, start, &mut checkpointing, &mut |_| {}).unwrap().unwrap()
+//! };
+//!
+//! // Without pausing, and paused at the second checkpoint then resumed from it
+//! let RunEnd::Finished(uninterrupted) = run(start(), 0) else { panic!("no pause asked for") };
+//! let RunEnd::Paused(checkpoint) = run(start(), 2) else { panic!("should pause") };
+//! assert_eq!(checkpoint.iteration, 2 * jssp_scheduler::jssp::checkpoint::INTERVAL);
+//!
+//! let checkpoint = Checkpoint::from_json(&checkpoint.to_json().unwrap()).unwrap();
+//! let RunEnd::Finished(resumed) = run(RunStart::Resume(checkpoint), 0) else { panic!("no pause asked for") };
+//! assert_eq!(ft06.calculate_makespan(&resumed), ft06.calculate_makespan(&uninterrupted));
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use super::{Instance, JsspError, Objective, Schedule};
+
+/// Iterations between checkpoints
+pub const INTERVAL: usize = 1000;
+
+/// The state of a paused or interrupted run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Name of the algorithm, which alone can resume the run
+    pub algorithm: String,
+    pub instance_name: String,
+    /// `Instance::fingerprint` of the instance the run solves
+    pub instance_hash: String,
+    /// Key of the objective (see `Objective::key`)
+    pub objective: String,
+    pub seed: u64,
+    /// Iterations done; a resumed run continues with this one
+    pub iteration: usize,
+    /// Iterations the run is meant to reach
+    pub iterations: usize,
+    pub best: Schedule,
+    pub best_value: f64,
+    /// Everything else the algorithm continues from
+    pub state: serde_json::Value,
+    /// When the checkpoint was taken, as RFC 3339
+    pub saved_at: String,
+}
+
+impl Checkpoint {
+    pub fn to_json(&self) -> Result<String, JsspError> {
+        serde_json::to_string(self).map_err(|e| JsspError::Serialization(format!("Failed to serialize checkpoint: {}", e)))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, JsspError> {
+        serde_json::from_str(text).map_err(|e| JsspError::Parse(format!("Invalid checkpoint: {}", e)))
+    }
+
+    /// Read a checkpoint file; `None` if there is none
+    pub fn load(path: &Path) -> Result<Option<Self>, JsspError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::from_json(&text).map(Some).map_err(|e| e.context(path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(JsspError::io("read", path)(e)),
+        }
+    }
+
+    /// Write the checkpoint to `path`, through a temporary file so that an interrupted
+    /// write leaves the previous checkpoint intact
+    pub fn save(&self, path: &Path) -> Result<(), JsspError> {
+        let temporary = path.with_extension("json.tmp");
+        std::fs::write(&temporary, self.to_json()?)
+            .and_then(|()| std::fs::rename(&temporary, path))
+            .map_err(JsspError::io("write", path))
+    }
+
+    /// Where the GUI keeps the checkpoint of its last run: next to the app's other saved state
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_path() -> Option<PathBuf> {
+        eframe::storage_dir("JSSP Scheduler").map(|dir| dir.join("checkpoint.json"))
+    }
+
+    pub fn objective(&self) -> Option<Objective> {
+        Objective::from_key(&self.objective)
+    }
+
+    /// Whether the checkpoint is of `algorithm` solving `instance`, and if not, why
+    pub fn check(&self, algorithm: &str, instance: &Instance) -> Result<(), String> {
+        if self.algorithm != algorithm {
+            return Err(format!("The checkpoint is of {}, not {}", self.algorithm, algorithm));
+        }
+        if self.instance_hash != instance.fingerprint() {
+            return Err(format!("The checkpoint is of another instance ({})", self.instance_name));
+        }
+        if self.objective().is_none() {
+            return Err(format!("Unknown objective '{}' in the checkpoint", self.objective));
+        }
+        Ok(())
+    }
+
+    /// The share of the run done, in `0..=1`
+    pub fn progress(&self) -> f64 {
+        self.iteration as f64 / self.iterations.max(1) as f64
+    }
+
+    /// E.g. "Simulated Annealing on ft06, 4000/20000 iterations, best 58"
+    pub fn summary(&self) -> String {
+        let instance = if self.instance_name.is_empty() { "unnamed" } else { &self.instance_name };
+        format!(
+            "{} on {}, {}/{} iterations, best {}",
+            self.algorithm, instance, self.iteration, self.iterations, self.best_value
+        )
+    }
+}
+
+/// How a resumable run starts
+pub enum RunStart {
+    New { objective: Objective, seed: u64 },
+    Resume(Checkpoint),
+}
+
+/// How a resumable run ends
+#[derive(Debug)]
+pub enum RunEnd {
+    Finished(Schedule),
+    Paused(Checkpoint),
+}
+
+impl RunEnd {
+    /// The final schedule, or the best so far of a paused run
+    pub fn into_schedule(self) -> Schedule {
+        match self {
+            RunEnd::Finished(schedule) => schedule,
+            RunEnd::Paused(checkpoint) => checkpoint.best,
+        }
+    }
+}
+
+/// What a resumable run does at its checkpoints
+pub struct Checkpointing<'a> {
+    /// Receives every checkpoint, e.g. to write it to a file
+    pub save: &'a mut dyn FnMut(&Checkpoint),
+    /// Asked at every checkpoint whether to stop there
+    pub pause: &'a dyn Fn() -> bool,
+}
+
+impl Checkpointing<'_> {
+    /// Called before every iteration of a run that began at iteration `first`: at a
+    /// checkpoint, unless the run only just began there, saves the checkpoint that
+    /// `checkpoint` builds. Returns it if the run should pause.
+    pub fn step(&mut self, iteration: usize, first: usize, checkpoint: impl FnOnce() -> Checkpoint) -> Option<Checkpoint> {
+        if !iteration.is_multiple_of(INTERVAL) || iteration == first {
+            return None;
+        }
+        let checkpoint = checkpoint();
+        (self.save)(&checkpoint);
+        (self.pause)().then_some(checkpoint)
+    }
+}
+
+/// The random number generator of a run from iteration `iteration`, a multiple of
+/// `INTERVAL`, on; at iteration 0 the same as seeding with `seed` alone
+pub fn checkpoint_rng(seed: u64, iteration: usize) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (iteration as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
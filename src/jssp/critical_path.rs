@@ -0,0 +1,209 @@
+//! Critical path of a schedule and re-sequencing suggestions derived from it.
+
+use std::collections::HashMap;
+
+use super::incremental::DisjunctiveGraph;
+use super::{Buffering, Instance, ScheduledOperation, TIME_EPSILON};
+
+/// Swapping two adjacent operations on a machine, with its effect on the makespan
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapSuggestion {
+    pub machine_id: usize,
+    /// (job_id, operation_id) currently processed first
+    pub first: (usize, usize),
+    /// (job_id, operation_id) currently processed right after `first`
+    pub second: (usize, usize),
+    /// Makespan after the swap
+    pub makespan: f64,
+    /// Change relative to the current makespan (negative is an improvement)
+    pub delta: f64,
+}
+
+impl Instance {
+    /// Operations on a longest path through the schedule, in processing order.
+    ///
+    /// Starting from the operation that finishes last, the path follows whichever
+    /// predecessor (previous operation on the machine, or of the job plus its transfer)
+    /// ends exactly when the operation can start.
+    pub fn critical_path(&self, schedule: &[ScheduledOperation]) -> Vec<(usize, usize)> {
+        let by_key: HashMap<(usize, usize), &ScheduledOperation> = schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op))
+            .collect();
+        let mut machine_pred: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for sequence in self.machine_sequences(schedule) {
+            for pair in sequence.windows(2) {
+                machine_pred.insert(pair[1], pair[0]);
+            }
+        }
+        let job_pred = self.job_neighbours(schedule, |pair| (pair[1], pair[0]));
+
+        let Some(mut current) = schedule.iter().max_by(|a, b| a.end_time.total_cmp(&b.end_time)) else {
+            return Vec::new();
+        };
+        let mut path = vec![(current.job_id, current.operation_id)];
+        loop {
+            let key = (current.job_id, current.operation_id);
+            let on_machine = machine_pred.get(&key)
+                .map(|pred| by_key[pred])
+                .filter(|pred| (pred.end_time + current.setup_time - current.start_time).abs() < TIME_EPSILON);
+            let in_job = job_pred.get(&key)
+                .and_then(|pred| by_key.get(pred).copied())
+                .filter(|pred| (pred.end_time + self.transfer_time(Some(pred.machine_id), current.machine_id) - current.start_time).abs() < TIME_EPSILON);
+
+            match on_machine.or(in_job) {
+                Some(pred) => {
+                    path.push((pred.job_id, pred.operation_id));
+                    current = pred;
+                }
+                None => break,
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Total slack of every operation: how far it could be delayed without increasing
+    /// the makespan, given the job order and the machine orders of the schedule.
+    ///
+    /// Computed with a backward pass over the disjunctive graph of the schedule (job
+    /// arcs plus the chosen machine arcs, setups and transfers included); an operation is never
+    /// delayed into machine downtime.
+    pub fn operation_slack(&self, schedule: &[ScheduledOperation]) -> HashMap<(usize, usize), f64> {
+        let makespan = self.calculate_makespan(schedule);
+        let by_key: HashMap<(usize, usize), &ScheduledOperation> = schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op))
+            .collect();
+        let mut machine_succ: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+        for sequence in self.machine_sequences(schedule) {
+            for pair in sequence.windows(2) {
+                machine_succ.insert(pair[0], pair[1]);
+            }
+        }
+        let job_succ = self.job_neighbours(schedule, |pair| (pair[0], pair[1]));
+
+        // Successors always start later, so visiting by descending start is a valid
+        // reverse topological order for a feasible schedule
+        let mut order: Vec<&ScheduledOperation> = schedule.iter().collect();
+        order.sort_by(|a, b| b.start_time.total_cmp(&a.start_time).then(b.end_time.total_cmp(&a.end_time)));
+
+        let mut latest_start: HashMap<(usize, usize), f64> = HashMap::new();
+        for op in order {
+            let key = (op.job_id, op.operation_id);
+            let mut latest_end = makespan;
+            if let Some(next_key) = job_succ.get(&key) {
+                if let (Some(next), Some(next_op)) = (latest_start.get(next_key), by_key.get(next_key)) {
+                    latest_end = latest_end.min(next - self.transfer_time(Some(op.machine_id), next_op.machine_id));
+                }
+            }
+            if let Some(next) = machine_succ.get(&key) {
+                if let (Some(start), Some(next_op)) = (latest_start.get(next), by_key.get(next)) {
+                    latest_end = latest_end.min(start - next_op.setup_time);
+                }
+            }
+            let latest_end = self.preemptive_end(op.machine_id, latest_end, op.setup_time, op.duration);
+            latest_start.insert(key, self.processing_start(op.machine_id, latest_end, op.duration));
+        }
+
+        schedule.iter()
+            .map(|op| {
+                let key = (op.job_id, op.operation_id);
+                (key, (latest_start[&key] - op.start_time).max(0.0))
+            })
+            .collect()
+    }
+
+    /// Consecutive operations of every job in the order the schedule processes them
+    /// (see `job_sequences`), each pair mapped to a (key, value) entry by `entry`
+    fn job_neighbours(
+        &self,
+        schedule: &[ScheduledOperation],
+        entry: impl Fn(&[(usize, usize)]) -> ((usize, usize), (usize, usize)),
+    ) -> HashMap<(usize, usize), (usize, usize)> {
+        self.job_sequences(schedule).into_iter()
+            .enumerate()
+            .flat_map(|(job_id, order)| {
+                let keys: Vec<(usize, usize)> = order.into_iter().map(|operation_id| (job_id, operation_id)).collect();
+                keys.windows(2).map(&entry).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Adjacent swaps inside critical blocks (runs of critical operations on one
+    /// machine), best first, each with the makespan `apply_swap` gives it. At most
+    /// `limit` are returned. Swaps are rated on the disjunctive graph, which leaves
+    /// secondary resources, missing buffers and pins out and keeps the job orders of an
+    /// open shop fixed, so with any of them every swap is re-timed in full instead.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// assert!(ft06.pin_operation(&ft06.solve_greedy(), (2, 2)).is_ok());
+    /// let schedule = ft06.solve_greedy();
+    /// let suggestions = ft06.suggest_swaps(&schedule, 10);
+    /// assert!(!suggestions.is_empty());
+    /// for suggestion in &suggestions {
+    ///     let swapped = ft06.apply_swap(&schedule, suggestion).unwrap();
+    ///     assert_eq!(ft06.calculate_makespan(&swapped), suggestion.makespan);
+    /// }
+    /// ```
+    pub fn suggest_swaps(&self, schedule: &[ScheduledOperation], limit: usize) -> Vec<SwapSuggestion> {
+        let makespan = self.calculate_makespan(schedule);
+        let Some(mut graph) = DisjunctiveGraph::new(self, schedule) else {
+            return Vec::new();
+        };
+        let retime = self.uses_resources() || self.buffering != Buffering::Unlimited || self.is_open_shop() || self.uses_pins();
+        let makespan_of = |graph: &DisjunctiveGraph| {
+            if !retime {
+                return Some(graph.makespan());
+            }
+            let sequences: Vec<Vec<(usize, usize)>> = (0..self.num_machines())
+                .map(|machine_id| graph.machine_sequence(machine_id).collect())
+                .collect();
+            self.schedule_from_sequences(&sequences, &HashMap::new()).map(|schedule| self.calculate_makespan(&schedule))
+        };
+
+        let path = self.critical_path(schedule);
+        let mut suggestions: Vec<SwapSuggestion> = Vec::new();
+        for pair in path.windows(2) {
+            let (Some((machine_id, position)), Some(next)) = (graph.location(pair[0]), graph.location(pair[1])) else {
+                continue;
+            };
+            if next != (machine_id, position + 1) {
+                continue;
+            }
+
+            // Apply, read the makespan, and swap back
+            if !graph.apply_swap(machine_id, position) {
+                continue;
+            }
+            let new_makespan = makespan_of(&graph);
+            graph.apply_swap(machine_id, position);
+            if let Some(new_makespan) = new_makespan {
+                suggestions.push(SwapSuggestion {
+                    machine_id,
+                    first: pair[0],
+                    second: pair[1],
+                    makespan: new_makespan,
+                    delta: new_makespan - makespan,
+                });
+            }
+        }
+
+        suggestions.sort_by(|a, b| a.delta.total_cmp(&b.delta));
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// The schedule with a suggested swap applied, or `None` if it no longer fits
+    pub fn apply_swap(&self, schedule: &[ScheduledOperation], suggestion: &SwapSuggestion) -> Option<Vec<ScheduledOperation>> {
+        let mut sequences = self.machine_sequences(schedule);
+        let sequence = sequences.get_mut(suggestion.machine_id)?;
+        let position = sequence.iter().position(|&key| key == suggestion.first)?;
+        if sequence.get(position + 1) != Some(&suggestion.second) {
+            return None;
+        }
+        sequence.swap(position, position + 1);
+        self.schedule_from_sequences(&sequences, &HashMap::new())
+    }
+}
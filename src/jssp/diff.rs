@@ -0,0 +1,157 @@
+//! Differences between two schedules of the same instance, e.g. a pinned baseline and
+//! a new solve after tuning an algorithm's parameters.
+//!
+//! ```
+//! use jssp_scheduler::jssp::diff::diff_schedules;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let baseline = ft06.solve_greedy();
+//! let improved = ft06.solve_greedy_randomized(50, 7);
+//! let diff = diff_schedules(&baseline, &improved);
+//! assert!(diff.makespan_delta() < 0.0);
+//! assert_eq!(diff.moves.len() + diff.unchanged, 36);
+//! assert!(diff_schedules(&baseline, &baseline).moves.is_empty());
+//! ```
+
+use std::collections::HashMap;
+
+use super::{ScheduledOperation, TIME_EPSILON};
+
+/// An operation placed differently in the compared schedule than in the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationMove {
+    pub job_id: usize,
+    pub operation_id: usize,
+    pub baseline_machine: usize,
+    pub machine: usize,
+    pub baseline_start: f64,
+    pub start: f64,
+}
+
+impl OperationMove {
+    pub fn changed_machine(&self) -> bool {
+        self.machine != self.baseline_machine
+    }
+
+    /// How much later the operation starts than in the baseline (negative if earlier)
+    pub fn shift(&self) -> f64 {
+        self.start - self.baseline_start
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleDiff {
+    pub baseline_makespan: f64,
+    pub makespan: f64,
+    /// Operations on another machine or at another start time, by job and operation
+    pub moves: Vec<OperationMove>,
+    /// Operations scheduled identically in both
+    pub unchanged: usize,
+    /// (job_id, operation_id) of operations scheduled in only one of the two
+    pub unmatched: Vec<(usize, usize)>,
+}
+
+impl ScheduleDiff {
+    /// Makespan change relative to the baseline (negative is an improvement)
+    pub fn makespan_delta(&self) -> f64 {
+        self.makespan - self.baseline_makespan
+    }
+
+    pub fn machine_changes(&self) -> usize {
+        self.moves.iter().filter(|m| m.changed_machine()).count()
+    }
+}
+
+/// Compare `schedule` against `baseline`, matching operations by job and operation id
+pub fn diff_schedules(baseline: &[ScheduledOperation], schedule: &[ScheduledOperation]) -> ScheduleDiff {
+    let makespan = |ops: &[ScheduledOperation]| ops.iter().map(|op| op.end_time).fold(0.0, f64::max);
+    let mut before: HashMap<(usize, usize), &ScheduledOperation> = baseline.iter()
+        .map(|op| ((op.job_id, op.operation_id), op))
+        .collect();
+
+    let mut moves = Vec::new();
+    let mut unchanged = 0;
+    let mut unmatched = Vec::new();
+    for op in schedule {
+        let Some(old) = before.remove(&(op.job_id, op.operation_id)) else {
+            unmatched.push((op.job_id, op.operation_id));
+            continue;
+        };
+        if old.machine_id == op.machine_id && (old.start_time - op.start_time).abs() < TIME_EPSILON {
+            unchanged += 1;
+        } else {
+            moves.push(OperationMove {
+                job_id: op.job_id,
+                operation_id: op.operation_id,
+                baseline_machine: old.machine_id,
+                machine: op.machine_id,
+                baseline_start: old.start_time,
+                start: op.start_time,
+            });
+        }
+    }
+    unmatched.extend(before.into_keys());
+    moves.sort_by_key(|m| (m.job_id, m.operation_id));
+    unmatched.sort();
+
+    ScheduleDiff {
+        baseline_makespan: makespan(baseline),
+        makespan: makespan(schedule),
+        moves,
+        unchanged,
+        unmatched,
+    }
+}
+
+/// Each machine's operations as (job_id, operation_id), by machine id and start time
+fn machine_orders(schedule: &[ScheduledOperation]) -> HashMap<usize, Vec<(usize, usize)>> {
+    let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+    ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    let mut orders: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    for op in ops {
+        orders.entry(op.machine_id).or_default().push((op.job_id, op.operation_id));
+    }
+    orders
+}
+
+/// How far apart the machine orders of two schedules are, whatever their start times:
+/// operations assigned to another machine, plus pairs of operations that share a
+/// machine in both schedules but run in opposite order. 0 means the same orders.
+///
+/// ```
+/// use jssp_scheduler::jssp::diff::order_distance;
+/// use jssp_scheduler::jssp::testing::ft06;
+///
+/// let ft06 = ft06().instance;
+/// let greedy = ft06.solve_greedy();
+/// let randomized = ft06.solve_greedy_randomized(50, 7);
+/// assert_eq!(order_distance(&greedy, &greedy), 0);
+/// assert!(order_distance(&greedy, &randomized) > 0);
+/// assert_eq!(order_distance(&greedy, &randomized), order_distance(&randomized, &greedy));
+/// ```
+pub fn order_distance(a: &[ScheduledOperation], b: &[ScheduledOperation]) -> usize {
+    // Machine and position there of every operation in `b`
+    let mut place: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    for (machine_id, order) in machine_orders(b) {
+        for (position, &key) in order.iter().enumerate() {
+            place.insert(key, (machine_id, position));
+        }
+    }
+
+    let mut distance = 0;
+    for (machine_id, order) in machine_orders(a) {
+        // Positions in `b` of the operations that stayed on the machine, in `a`'s order
+        let mut positions = Vec::with_capacity(order.len());
+        for key in &order {
+            match place.get(key) {
+                Some(&(machine, position)) if machine == machine_id => positions.push(position),
+                _ => distance += 1,
+            }
+        }
+        for (i, first) in positions.iter().enumerate() {
+            distance += positions[i + 1..].iter().filter(|second| *second < first).count();
+        }
+    }
+    distance
+}
@@ -0,0 +1,326 @@
+//! Scheduling jobs as they arrive, by dispatching rules.
+//!
+//! A discrete-event simulation of a shop in which no job is known before its release
+//! time: whenever a machine is idle (and not down), it starts the waiting operation its
+//! dispatching rule ranks first. Nothing is planned ahead, so a run shows how a rule
+//! copes with a stream of arrivals rather than how good a static schedule could be.
+//! Generate such a stream with `GeneratorConfig::mean_interarrival`.
+//! Dispatching assumes buffers between machines and ignores `Instance::buffering`.
+//! Besides the built-in rules, a rule can be any `PriorityExpression` (see `priority`).
+//!
+//! ```
+//! use jssp_scheduler::jssp::dynamic::DispatchRule;
+//! use jssp_scheduler::jssp::{generate_instance, GeneratorConfig};
+//!
+//! let config = GeneratorConfig { num_jobs: 30, mean_interarrival: Some(8.0), ..GeneratorConfig::default() };
+//! let instance = generate_instance(&config);
+//! let run = instance.dispatch(DispatchRule::ShortestProcessingTime);
+//! assert!(instance.validate(&run.schedule).is_ok());
+//! assert_eq!(run.completions.len(), 30);
+//! assert_eq!(run.makespan(), instance.calculate_makespan(&run.schedule));
+//!
+//! let rolling = run.rolling(10);
+//! assert_eq!(rolling.last().unwrap().completed, 30);
+//! assert_eq!(rolling.last().unwrap().in_system, 0);
+//! ```
+
+use super::priority::{PriorityContext, PriorityExpression};
+use super::{Instance, Schedule, ScheduledOperation, TIME_EPSILON};
+
+/// Which waiting operation an idle machine starts next
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchRule {
+    /// The operation that has waited longest
+    FirstInFirstOut,
+    ShortestProcessingTime,
+    LongestProcessingTime,
+    /// The operation whose job has the most processing left, including this operation
+    MostWorkRemaining,
+    LeastWorkRemaining,
+    /// The operation whose job is due first; jobs without a due date come last
+    EarliestDueDate,
+    /// Shortest processing time divided by the job's weight, so important jobs go first
+    WeightedShortestProcessingTime,
+    /// The operation with the lowest value of a user-defined expression
+    Custom(PriorityExpression),
+}
+
+impl DispatchRule {
+    pub const ALL: [DispatchRule; 7] = [
+        DispatchRule::FirstInFirstOut,
+        DispatchRule::ShortestProcessingTime,
+        DispatchRule::LongestProcessingTime,
+        DispatchRule::MostWorkRemaining,
+        DispatchRule::LeastWorkRemaining,
+        DispatchRule::EarliestDueDate,
+        DispatchRule::WeightedShortestProcessingTime,
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            DispatchRule::FirstInFirstOut => "FIFO",
+            DispatchRule::ShortestProcessingTime => "SPT",
+            DispatchRule::LongestProcessingTime => "LPT",
+            DispatchRule::MostWorkRemaining => "MWKR",
+            DispatchRule::LeastWorkRemaining => "LWKR",
+            DispatchRule::EarliestDueDate => "EDD",
+            DispatchRule::WeightedShortestProcessingTime => "WSPT",
+            DispatchRule::Custom(expression) => expression.source(),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            DispatchRule::FirstInFirstOut => "First in, first out: the operation that has waited longest",
+            DispatchRule::ShortestProcessingTime => "Shortest processing time on the idle machine",
+            DispatchRule::LongestProcessingTime => "Longest processing time on the idle machine",
+            DispatchRule::MostWorkRemaining => "Most work remaining in the job",
+            DispatchRule::LeastWorkRemaining => "Least work remaining in the job",
+            DispatchRule::EarliestDueDate => "Earliest due date of the job",
+            DispatchRule::WeightedShortestProcessingTime => "Shortest processing time per unit of job weight",
+            DispatchRule::Custom(_) => "Custom priority expression: the lowest value goes first",
+        }
+    }
+}
+
+/// When a job left the shop
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobCompletion {
+    pub job_id: usize,
+    pub release_time: f64,
+    pub completion_time: f64,
+}
+
+impl JobCompletion {
+    /// Time the job spent in the shop
+    pub fn flow_time(&self) -> f64 {
+        self.completion_time - self.release_time
+    }
+}
+
+/// Statistics of a run up to one job completion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RollingStats {
+    /// The completion time, which is also the makespan so far
+    pub time: f64,
+    /// Jobs completed by `time`
+    pub completed: usize,
+    /// Mean flow time of the last jobs completed (up to the rolling window)
+    pub mean_flow_time: f64,
+    /// Mean flow time of all jobs completed so far
+    pub cumulative_mean_flow_time: f64,
+    /// Jobs released but not completed right after this completion
+    pub in_system: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct DynamicRun {
+    pub rule: DispatchRule,
+    pub schedule: Schedule,
+    /// In order of completion
+    pub completions: Vec<JobCompletion>,
+}
+
+impl DynamicRun {
+    pub fn makespan(&self) -> f64 {
+        self.completions.iter().map(|c| c.completion_time).fold(0.0, f64::max)
+    }
+
+    pub fn mean_flow_time(&self) -> f64 {
+        if self.completions.is_empty() {
+            return 0.0;
+        }
+        self.completions.iter().map(JobCompletion::flow_time).fold(0.0, |total, flow| total + flow)
+            / self.completions.len() as f64
+    }
+
+    pub fn max_flow_time(&self) -> f64 {
+        self.completions.iter().map(JobCompletion::flow_time).fold(0.0, f64::max)
+    }
+
+    /// Statistics after every completion, with the mean flow time taken over the last
+    /// `window` completed jobs
+    pub fn rolling(&self, window: usize) -> Vec<RollingStats> {
+        let window = window.max(1);
+        let mut releases: Vec<f64> = self.completions.iter().map(|c| c.release_time).collect();
+        releases.sort_by(f64::total_cmp);
+
+        let mut total = 0.0;
+        self.completions.iter()
+            .enumerate()
+            .map(|(index, completion)| {
+                total += completion.flow_time();
+                let recent = &self.completions[(index + 1).saturating_sub(window)..=index];
+                let released = releases.partition_point(|release| *release <= completion.completion_time);
+                RollingStats {
+                    time: completion.completion_time,
+                    completed: index + 1,
+                    mean_flow_time: recent.iter().map(JobCompletion::flow_time).fold(0.0, |sum, flow| sum + flow)
+                        / recent.len() as f64,
+                    cumulative_mean_flow_time: total / (index + 1) as f64,
+                    in_system: released.saturating_sub(index + 1),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Instance {
+    /// Run the shop with `rule`, revealing each job at its release time.
+    ///
+    /// Flexible operations wait at every eligible machine and go to whichever takes
+    /// them first. Setups are done once the operation is chosen, and a machine in a
+    /// downtime window starts nothing until the window ends.
+    pub fn dispatch(&self, rule: DispatchRule) -> DynamicRun {
+        let jobs = self.jobs();
+        let shortest = |job: usize, operation: usize| {
+            jobs[job].operations[operation].options().map(|o| o.duration).fold(f64::INFINITY, f64::min)
+        };
+        let mut next_operation = vec![0; jobs.len()];
+        // When each waiting job's next operation joined the queues
+        let mut queued_since: Vec<f64> = jobs.iter().map(|job| job.release_time).collect();
+        let mut work_remaining: Vec<f64> = (0..jobs.len())
+            .map(|job| (0..jobs[job].operations.len()).map(|op| shortest(job, op)).fold(0.0, |total, d| total + d))
+            .collect();
+        let mut machine_free = vec![0.0; self.num_machines()];
+        let mut machine_last_job: Vec<Option<usize>> = vec![None; self.num_machines()];
+        // The job each machine is processing until `machine_free`
+        let mut machine_job: Vec<Option<usize>> = vec![None; self.num_machines()];
+        // The machine each job left last, from which it travels to the next
+        let mut job_machine: Vec<Option<usize>> = vec![None; jobs.len()];
+        let mut schedule = Vec::with_capacity(self.num_operations());
+        let mut completions = Vec::with_capacity(jobs.len());
+
+        let mut arrivals: Vec<usize> = (0..jobs.len()).collect();
+        arrivals.sort_by(|&a, &b| jobs[a].release_time.total_cmp(&jobs[b].release_time));
+        let mut arrived = 0;
+        let mut waiting: Vec<usize> = Vec::new();
+
+        let mut now = 0.0;
+        loop {
+            // Newly released jobs and jobs whose operation just finished join the queues
+            while let Some(&job) = arrivals.get(arrived).filter(|&&job| jobs[job].release_time <= now + TIME_EPSILON) {
+                arrived += 1;
+                if jobs[job].operations.is_empty() {
+                    completions.push(JobCompletion { job_id: job, release_time: jobs[job].release_time, completion_time: jobs[job].release_time });
+                } else {
+                    waiting.push(job);
+                }
+            }
+            for machine_id in 0..self.num_machines() {
+                if machine_free[machine_id] <= now + TIME_EPSILON {
+                    if let Some(job) = machine_job[machine_id].take() {
+                        if next_operation[job] < jobs[job].operations.len() {
+                            queued_since[job] = machine_free[machine_id];
+                            waiting.push(job);
+                        }
+                    }
+                }
+            }
+
+            for machine_id in 0..self.num_machines() {
+                let down = self.available_start(machine_id, now, 0.0, 0.0) > now;
+                if machine_free[machine_id] > now + TIME_EPSILON || down {
+                    continue;
+                }
+                // A job joins the queue of a machine once it has travelled there
+                let arrived_at = |job: usize| queued_since[job] + self.transfer_time(job_machine[job], machine_id) <= now + TIME_EPSILON;
+                let queue_length = waiting.iter()
+                    .filter(|&&job| jobs[job].operations[next_operation[job]].duration_on(machine_id).is_some() && arrived_at(job))
+                    .count();
+                let priority = |job: usize, duration: f64| match &rule {
+                    DispatchRule::FirstInFirstOut => queued_since[job],
+                    DispatchRule::ShortestProcessingTime => duration,
+                    DispatchRule::LongestProcessingTime => -duration,
+                    DispatchRule::MostWorkRemaining => -work_remaining[job],
+                    DispatchRule::LeastWorkRemaining => work_remaining[job],
+                    DispatchRule::EarliestDueDate => jobs[job].due_date.unwrap_or(f64::INFINITY),
+                    DispatchRule::WeightedShortestProcessingTime if jobs[job].weight > 0.0 => duration / jobs[job].weight,
+                    DispatchRule::WeightedShortestProcessingTime => f64::INFINITY,
+                    DispatchRule::Custom(expression) => {
+                        let due_in = jobs[job].due_date.map_or(f64::INFINITY, |due_date| due_date - now);
+                        let value = expression.evaluate(&PriorityContext {
+                            duration,
+                            remaining_work: work_remaining[job],
+                            remaining_operations: (jobs[job].operations.len() - next_operation[job]) as f64,
+                            slack: due_in - work_remaining[job],
+                            due_in,
+                            queue_length: queue_length as f64,
+                            waiting: now - queued_since[job],
+                            weight: jobs[job].weight,
+                            release_time: jobs[job].release_time,
+                            now,
+                        });
+                        // Undefined values, e.g. from dividing zero by zero, go last
+                        if value.is_nan() { f64::INFINITY } else { value }
+                    }
+                };
+                let choice = waiting.iter()
+                    .enumerate()
+                    .filter(|&(_, &job)| arrived_at(job))
+                    .filter_map(|(index, &job)| {
+                        let duration = jobs[job].operations[next_operation[job]].duration_on(machine_id)?;
+                        Some((index, job, duration, priority(job, duration)))
+                    })
+                    // Ties go to the heavier job, then the operation that has waited longest,
+                    // then the lowest job id
+                    .min_by(|a, b| {
+                        a.3.total_cmp(&b.3)
+                            .then(jobs[b.1].weight.total_cmp(&jobs[a.1].weight))
+                            .then(queued_since[a.1].total_cmp(&queued_since[b.1]))
+                            .then(a.1.cmp(&b.1))
+                    });
+                let Some((index, job, duration, _)) = choice else {
+                    continue;
+                };
+                waiting.swap_remove(index);
+
+                let operation_id = next_operation[job];
+                let setup_time = self.setup_time(machine_id, machine_last_job[machine_id], job);
+                let start_time = self.available_start(machine_id, now + setup_time, setup_time, duration);
+                let end_time = start_time + duration;
+                schedule.push(ScheduledOperation {
+                    job_id: job,
+                    operation_id,
+                    machine_id,
+                    start_time,
+                    end_time,
+                    duration,
+                    setup_time,
+                    segments: Vec::new(),
+                });
+                machine_free[machine_id] = end_time;
+                machine_last_job[machine_id] = Some(job);
+                machine_job[machine_id] = Some(job);
+                job_machine[job] = Some(machine_id);
+                work_remaining[job] -= shortest(job, operation_id);
+                next_operation[job] += 1;
+                if next_operation[job] == jobs[job].operations.len() {
+                    completions.push(JobCompletion { job_id: job, release_time: jobs[job].release_time, completion_time: end_time });
+                }
+            }
+
+            // Advance to the next arrival, operation completion, end of a transfer or end of downtime
+            let (queued_since, job_machine) = (&queued_since, &job_machine);
+            let transfers = waiting.iter().flat_map(|&job| {
+                jobs[job].operations[next_operation[job]].options()
+                    .map(move |option| queued_since[job] + self.transfer_time(job_machine[job], option.machine_id))
+            });
+            let next = machine_free.iter()
+                .copied()
+                .chain(arrivals.get(arrived).map(|&job| jobs[job].release_time))
+                .chain(transfers)
+                .chain(self.unavailable().iter().map(|window| window.end))
+                .filter(|&time| time > now + TIME_EPSILON)
+                .fold(f64::INFINITY, f64::min);
+            if next.is_infinite() {
+                break;
+            }
+            now = next;
+        }
+
+        schedule.sort_by_key(|op| (op.job_id, op.operation_id));
+        completions.sort_by(|a, b| a.completion_time.total_cmp(&b.completion_time));
+        DynamicRun { rule, schedule, completions }
+    }
+}
@@ -0,0 +1,99 @@
+//! Errors of importing and exporting problems, solutions and charts.
+//!
+//! Functions that read or write files return `JsspError`, so callers can tell a full
+//! disk from a malformed file. Where a plain message is enough, e.g. on the command
+//! line, `?` turns it into a `String`.
+//!
+//! ```
+//! use std::path::Path;
+//! use jssp_scheduler::jssp::batch::load_instance;
+//! use jssp_scheduler::jssp::ortools::from_ortools_json;
+//! use jssp_scheduler::jssp::JsspError;
+//!
+//! let missing = load_instance(Path::new("no/such/instance.json")).unwrap_err();
+//! assert!(matches!(missing, JsspError::Io { .. }));
+//! assert!(missing.to_string().starts_with("Failed to read no/such/instance.json"));
+//!
+//! let malformed = from_ortools_json("{ \"jobs_data\": 3 }").unwrap_err();
+//! assert!(matches!(malformed, JsspError::Parse(_)));
+//! assert_eq!(malformed.kind(), "Invalid file");
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum JsspError {
+    /// Reading or writing a file failed, e.g. because it is missing or the disk is full
+    Io {
+        /// What was done, e.g. "read" or "write"
+        action: &'static str,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// Turning data into a document or image failed
+    Serialization(String),
+    /// A file is not in the expected format, or describes an invalid problem
+    Parse(String),
+    /// A solution or request does not fit the problem at hand, or there is no problem
+    Infeasible(String),
+    /// An external program failed, ran out of time or gave no usable schedule
+    Process(String),
+}
+
+impl JsspError {
+    /// For `map_err` on an I/O result: `.map_err(JsspError::io("write", &path))`
+    pub fn io<'a>(action: &'static str, path: &'a Path) -> impl FnOnce(std::io::Error) -> JsspError + 'a {
+        move |source| JsspError::Io { action, path: path.to_path_buf(), source }
+    }
+
+    /// A short title for the kind of failure, e.g. for an error dialog
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JsspError::Io { .. } => "File error",
+            JsspError::Serialization(_) => "Export failed",
+            JsspError::Parse(_) => "Invalid file",
+            JsspError::Infeasible(_) => "Not possible",
+            JsspError::Process(_) => "Solver failed",
+        }
+    }
+
+    /// The error with `context` (e.g. the file name) in front of its message; I/O errors
+    /// name their file already and stay as they are
+    pub fn context(self, context: impl fmt::Display) -> JsspError {
+        match self {
+            JsspError::Io { .. } => self,
+            JsspError::Serialization(message) => JsspError::Serialization(format!("{}: {}", context, message)),
+            JsspError::Parse(message) => JsspError::Parse(format!("{}: {}", context, message)),
+            JsspError::Infeasible(message) => JsspError::Infeasible(format!("{}: {}", context, message)),
+            JsspError::Process(message) => JsspError::Process(format!("{}: {}", context, message)),
+        }
+    }
+}
+
+impl fmt::Display for JsspError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsspError::Io { action, path, source } => write!(f, "Failed to {} {}: {}", action, path.display(), source),
+            JsspError::Serialization(message)
+            | JsspError::Parse(message)
+            | JsspError::Infeasible(message)
+            | JsspError::Process(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for JsspError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsspError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<JsspError> for String {
+    fn from(error: JsspError) -> String {
+        error.to_string()
+    }
+}
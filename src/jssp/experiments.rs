@@ -0,0 +1,201 @@
+//! An SQLite log of solver runs, so results survive between sessions.
+//!
+//! Every recorded run keeps the algorithm, its parameters, seed, objective value,
+//! makespan and runtime, plus the instance (in the binary layout of `cache`) and the
+//! schedule, so any past run can be loaded again. Runs on the same instance share an
+//! `instance_hash` regardless of the instance's name. Like the binary layout, the log
+//! leaves out calendars and soft constraints.
+//!
+//! ```
+//! use std::time::Duration;
+//! use jssp_scheduler::jssp::experiments::{ExperimentLog, Run};
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Objective;
+//!
+//! let ft06 = ft06().instance;
+//! let schedule = ft06.solve_greedy();
+//! let log = ExperimentLog::open_in_memory().unwrap();
+//! let id = log.record(&Run {
+//!     instance: &ft06,
+//!     schedule: &schedule,
+//!     algorithm: "Greedy",
+//!     parameters: serde_json::json!({}),
+//!     seed: None,
+//!     objective: Objective::Makespan,
+//!     runtime: Duration::from_millis(3),
+//! }).unwrap();
+//!
+//! let runs = log.runs().unwrap();
+//! assert_eq!(runs[0].id, id);
+//! assert_eq!(runs[0].makespan, ft06.calculate_makespan(&schedule));
+//! let (instance, reloaded) = log.load(id).unwrap();
+//! assert_eq!(instance.num_operations(), 36);
+//! assert_eq!(instance.calculate_makespan(&reloaded), runs[0].makespan);
+//! ```
+
+use std::path::Path;
+use std::time::Duration;
+
+use rusqlite::{params, Connection};
+
+use super::cache::{decode_instance, encode_instance};
+use super::{Instance, Objective, Schedule, ScheduledOperation};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        recorded_at TEXT NOT NULL,
+        instance_name TEXT NOT NULL,
+        instance_hash TEXT NOT NULL,
+        algorithm TEXT NOT NULL,
+        parameters TEXT NOT NULL,
+        seed INTEGER,
+        objective TEXT NOT NULL,
+        objective_value REAL NOT NULL,
+        makespan REAL NOT NULL,
+        runtime_ms REAL NOT NULL,
+        feasible INTEGER NOT NULL,
+        instance BLOB NOT NULL,
+        schedule TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS runs_by_instance ON runs (instance_hash);
+";
+
+/// A finished solve to record
+pub struct Run<'a> {
+    pub instance: &'a Instance,
+    pub schedule: &'a [ScheduledOperation],
+    pub algorithm: &'a str,
+    /// Settings the run used, e.g. the number of multi-start runs
+    pub parameters: serde_json::Value,
+    pub seed: Option<u64>,
+    pub objective: Objective,
+    pub runtime: Duration,
+}
+
+/// A recorded run without its instance and schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSummary {
+    pub id: i64,
+    /// Local time of recording, as "YYYY-MM-DD HH:MM:SS"
+    pub recorded_at: String,
+    pub instance_name: String,
+    pub instance_hash: String,
+    pub algorithm: String,
+    /// JSON object
+    pub parameters: String,
+    pub seed: Option<u64>,
+    /// Objective key (see `Objective::key`)
+    pub objective: String,
+    pub objective_value: f64,
+    pub makespan: f64,
+    pub runtime_ms: f64,
+    pub feasible: bool,
+}
+
+pub struct ExperimentLog {
+    connection: Connection,
+}
+
+impl ExperimentLog {
+    /// Open the log at `path`, creating the file and its table if needed
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let connection = Connection::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        Self::with_connection(connection)
+    }
+
+    /// A log that lives only as long as the value, for tests
+    pub fn open_in_memory() -> Result<Self, String> {
+        Self::with_connection(Connection::open_in_memory().map_err(|e| e.to_string())?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, String> {
+        connection.execute_batch(SCHEMA).map_err(|e| format!("Failed to set up the experiment log: {}", e))?;
+        Ok(Self { connection })
+    }
+
+    /// Store a run, returning its id
+    pub fn record(&self, run: &Run) -> Result<i64, String> {
+        let instance = run.instance;
+        let schedule = serde_json::to_string(run.schedule).map_err(|e| e.to_string())?;
+        self.connection
+            .execute(
+                "INSERT INTO runs (recorded_at, instance_name, instance_hash, algorithm, parameters, seed, objective,
+                                   objective_value, makespan, runtime_ms, feasible, instance, schedule)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    instance.name,
+                    instance_hash(instance),
+                    run.algorithm,
+                    run.parameters.to_string(),
+                    // SQLite integers are signed; the bits round-trip
+                    run.seed.map(|seed| seed as i64),
+                    run.objective.key(),
+                    instance.evaluate(run.objective, run.schedule),
+                    instance.calculate_makespan(run.schedule),
+                    run.runtime.as_secs_f64() * 1000.0,
+                    instance.validate(run.schedule).is_ok(),
+                    encode_instance(instance),
+                    schedule,
+                ],
+            )
+            .map_err(|e| format!("Failed to record the run: {}", e))?;
+        Ok(self.connection.last_insert_rowid())
+    }
+
+    /// Every recorded run, newest first
+    pub fn runs(&self) -> Result<Vec<RunSummary>, String> {
+        let mut statement = self.connection
+            .prepare(
+                "SELECT id, recorded_at, instance_name, instance_hash, algorithm, parameters, seed, objective,
+                        objective_value, makespan, runtime_ms, feasible
+                 FROM runs ORDER BY id DESC",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = statement
+            .query_map([], |row| {
+                Ok(RunSummary {
+                    id: row.get(0)?,
+                    recorded_at: row.get(1)?,
+                    instance_name: row.get(2)?,
+                    instance_hash: row.get(3)?,
+                    algorithm: row.get(4)?,
+                    parameters: row.get(5)?,
+                    seed: row.get::<_, Option<i64>>(6)?.map(|seed| seed as u64),
+                    objective: row.get(7)?,
+                    objective_value: row.get(8)?,
+                    makespan: row.get(9)?,
+                    runtime_ms: row.get(10)?,
+                    feasible: row.get(11)?,
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<_, _>>().map_err(|e| format!("Failed to read the experiment log: {}", e))
+    }
+
+    /// The instance and schedule of a recorded run
+    pub fn load(&self, id: i64) -> Result<(Instance, Schedule), String> {
+        let (instance, schedule): (Vec<u8>, String) = self.connection
+            .query_row("SELECT instance, schedule FROM runs WHERE id = ?1", [id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| format!("Failed to load run {}: {}", id, e))?;
+        let instance = decode_instance(&instance)?;
+        let schedule = serde_json::from_str(&schedule).map_err(|e| format!("Run {} has an unreadable schedule: {}", id, e))?;
+        Ok((instance, schedule))
+    }
+
+    pub fn delete(&self, id: i64) -> Result<(), String> {
+        self.connection
+            .execute("DELETE FROM runs WHERE id = ?1", [id])
+            .map(|_| ())
+            .map_err(|e| format!("Failed to delete run {}: {}", id, e))
+    }
+}
+
+/// Fingerprint of an instance (see `Instance::fingerprint`)
+pub fn instance_hash(instance: &Instance) -> String {
+    instance.fingerprint()
+}
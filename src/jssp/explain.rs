@@ -0,0 +1,315 @@
+//! Constructive algorithms run one placement at a time, recording which operations
+//! competed at every step, which one was placed and why, for teaching and for
+//! debugging dispatching rules.
+//!
+//! Greedy places all operations of one job before the next job, so its only choice is
+//! the machine of a flexible operation. Giffler–Thompson builds an active schedule: at
+//! every step it finds the operation that could finish first, and among the operations
+//! that could start on that machine before then (the conflict set) dispatches the one
+//! with the shortest processing time. Every candidate records when its job and its
+//! machine are ready, so a step can say whether the machine or the job held it back.
+//!
+//! ```
+//! use jssp_scheduler::jssp::explain::ExplainedAlgorithm;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let greedy = ft06.explain(ExplainedAlgorithm::Greedy).unwrap();
+//! assert_eq!(greedy.steps.len(), 36);
+//! assert_eq!(ft06.calculate_makespan(&greedy.schedule), ft06.calculate_makespan(&ft06.solve_greedy()));
+//!
+//! let explained = ft06.explain(ExplainedAlgorithm::GifflerThompson).unwrap();
+//! assert!(ft06.validate(&explained.schedule).is_ok());
+//! let first = &explained.steps[0];
+//! assert!(first.chosen().competing);
+//! assert!(first.candidates.len() >= 6);
+//! assert!(first.reason.contains("conflict set") || first.reason.contains("Nothing else"));
+//! // The schedule after a step holds exactly the operations placed so far
+//! assert_eq!(explained.schedule_after(9).len(), 10);
+//! ```
+
+use super::{Buffering, Greedy, Instance, MachineOption, Objective, Schedule, ScheduledOperation, TIME_EPSILON};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplainedAlgorithm {
+    Greedy,
+    GifflerThompson,
+}
+
+impl ExplainedAlgorithm {
+    pub const ALL: [ExplainedAlgorithm; 2] = [ExplainedAlgorithm::Greedy, ExplainedAlgorithm::GifflerThompson];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExplainedAlgorithm::Greedy => "Greedy",
+            ExplainedAlgorithm::GifflerThompson => "Giffler–Thompson",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ExplainedAlgorithm::Greedy => "Job by job in greedy's job order; flexible operations take the machine that finishes them first",
+            ExplainedAlgorithm::GifflerThompson => "Active schedule: the shortest operation of the conflict set on the machine that could finish first",
+        }
+    }
+}
+
+/// What decided when a candidate can start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartBound {
+    /// The job's first operation waits for its release
+    Release,
+    /// The job's previous operation ends after the machine is free
+    Job,
+    /// The machine, set up for the job, is free after the job is ready
+    Machine,
+    /// Downtime on the machine pushes the start past both
+    Downtime,
+}
+
+impl StartBound {
+    pub fn description(&self) -> &'static str {
+        match self {
+            StartBound::Release => "the job is released",
+            StartBound::Job => "the job's previous operation ends",
+            StartBound::Machine => "the machine is free",
+            StartBound::Downtime => "downtime on the machine is over",
+        }
+    }
+}
+
+/// An operation that could be placed next, on one of its eligible machines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub job_id: usize,
+    pub operation_id: usize,
+    pub machine_id: usize,
+    /// Release of the job, or the end of its previous operation
+    pub job_ready: f64,
+    /// When the machine is free and set up for the job
+    pub machine_ready: f64,
+    pub start: f64,
+    pub end: f64,
+    pub duration: f64,
+    pub setup: f64,
+    /// Whether the algorithm weighed it at this step: every machine of Greedy's
+    /// operation, the conflict set of Giffler–Thompson
+    pub competing: bool,
+}
+
+impl Candidate {
+    pub fn bound(&self) -> StartBound {
+        if self.start > self.job_ready.max(self.machine_ready) + TIME_EPSILON {
+            StartBound::Downtime
+        } else if self.machine_ready > self.job_ready {
+            StartBound::Machine
+        } else if self.operation_id == 0 {
+            StartBound::Release
+        } else {
+            StartBound::Job
+        }
+    }
+}
+
+/// One placement: the operations that could have gone next and the one that did
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainStep {
+    pub candidates: Vec<Candidate>,
+    /// Index of the placed operation in `candidates`
+    pub chosen: usize,
+    pub reason: String,
+}
+
+impl ExplainStep {
+    pub fn chosen(&self) -> &Candidate {
+        &self.candidates[self.chosen]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    pub algorithm: ExplainedAlgorithm,
+    pub steps: Vec<ExplainStep>,
+    /// The finished schedule, in placement order
+    pub schedule: Schedule,
+}
+
+impl Explanation {
+    /// The operations placed up to and including `step`
+    pub fn schedule_after(&self, step: usize) -> &[ScheduledOperation] {
+        &self.schedule[..(step + 1).min(self.schedule.len())]
+    }
+}
+
+/// Machines and jobs as far as the schedule is built
+struct Partial {
+    machine_free: Vec<f64>,
+    machine_last_job: Vec<Option<usize>>,
+    job_ready: Vec<f64>,
+    /// Machine of each job's last placed operation, which it travels from
+    job_machine: Vec<Option<usize>>,
+    next_operation: Vec<usize>,
+}
+
+impl Partial {
+    fn new(instance: &Instance) -> Self {
+        Self {
+            machine_free: vec![0.0; instance.num_machines()],
+            machine_last_job: vec![None; instance.num_machines()],
+            job_ready: instance.jobs().iter().map(|job| job.release_time).collect(),
+            job_machine: vec![None; instance.jobs().len()],
+            next_operation: vec![0; instance.jobs().len()],
+        }
+    }
+
+    /// The next operation of `job_id` on one of its machines, timed like `Instance::decode`
+    fn candidate(&self, instance: &Instance, job_id: usize, option: MachineOption) -> Candidate {
+        let machine_id = option.machine_id;
+        let setup = instance.setup_time(machine_id, self.machine_last_job[machine_id], job_id);
+        let machine_ready = self.machine_free[machine_id] + setup;
+        let job_ready = self.job_ready[job_id] + instance.transfer_time(self.job_machine[job_id], machine_id);
+        let start = instance.preemptive_start(machine_id, machine_ready.max(job_ready), setup, option.duration);
+        Candidate {
+            job_id,
+            operation_id: self.next_operation[job_id],
+            machine_id,
+            job_ready,
+            machine_ready,
+            start,
+            end: instance.processing_end(machine_id, start, option.duration),
+            duration: option.duration,
+            setup,
+            competing: false,
+        }
+    }
+
+    /// Candidates for the next operation of `job_id`, one per eligible machine
+    fn candidates(&self, instance: &Instance, job_id: usize) -> Vec<Candidate> {
+        instance.jobs()[job_id].operations.get(self.next_operation[job_id])
+            .map(|operation| operation.options().map(|option| self.candidate(instance, job_id, option)).collect())
+            .unwrap_or_default()
+    }
+
+    fn place(&mut self, instance: &Instance, candidate: &Candidate) -> ScheduledOperation {
+        self.machine_free[candidate.machine_id] = candidate.end;
+        self.machine_last_job[candidate.machine_id] = Some(candidate.job_id);
+        self.job_ready[candidate.job_id] = candidate.end;
+        self.job_machine[candidate.job_id] = Some(candidate.machine_id);
+        self.next_operation[candidate.job_id] += 1;
+        instance.timed_operation(candidate.job_id, candidate.operation_id, candidate.machine_id, candidate.start, candidate.duration, candidate.setup)
+    }
+}
+
+impl Instance {
+    /// Run `algorithm` step by step (see `explain`). Shops without buffers between
+    /// machines place whole jobs at once and open shops pick among a job's operations,
+    /// so neither is explained.
+    pub fn explain(&self, algorithm: ExplainedAlgorithm) -> Result<Explanation, String> {
+        if self.buffering != Buffering::Unlimited {
+            return Err(format!("Explain mode needs unlimited buffers; this is a {} shop", self.buffering.name().to_lowercase()));
+        }
+        if self.is_open_shop() {
+            return Err("Explain mode follows the listed order of every job and does not support open shops".to_string());
+        }
+        if self.uses_resources() {
+            return Err("Explain mode does not support secondary resources".to_string());
+        }
+        let mut partial = Partial::new(self);
+        let mut steps = Vec::with_capacity(self.num_operations());
+        let mut schedule = Vec::with_capacity(self.num_operations());
+        match algorithm {
+            ExplainedAlgorithm::Greedy => {
+                for job_id in Greedy::job_order(self, Objective::Makespan) {
+                    for _ in 0..self.jobs()[job_id].operations.len() {
+                        let step = self.greedy_step(&partial, job_id);
+                        schedule.push(partial.place(self, step.chosen()));
+                        steps.push(step);
+                    }
+                }
+            }
+            ExplainedAlgorithm::GifflerThompson => {
+                while let Some(step) = self.giffler_thompson_step(&partial) {
+                    schedule.push(partial.place(self, step.chosen()));
+                    steps.push(step);
+                }
+            }
+        }
+        Ok(Explanation { algorithm, steps, schedule })
+    }
+
+    fn greedy_step(&self, partial: &Partial, job_id: usize) -> ExplainStep {
+        let mut candidates = partial.candidates(self, job_id);
+        for candidate in &mut candidates {
+            candidate.competing = true;
+        }
+        // The first machine that finishes it, as `decode` picks
+        let chosen = (0..candidates.len()).fold(0, |best, index| if candidates[index].end < candidates[best].end { index } else { best });
+        let placed = &candidates[chosen];
+        let mut reason = match placed.operation_id {
+            0 => format!("{} is next in greedy's job order.", self.job_name(job_id)),
+            operation_id => format!(
+                "Operation {} of {} follows, as greedy places all operations of a job before the next job.",
+                operation_id, self.job_name(job_id)
+            ),
+        };
+        if candidates.len() > 1 {
+            reason.push_str(&format!(
+                " Of its {} eligible machines, {} finishes it first, at {}.",
+                candidates.len(), self.machine_name(placed.machine_id), self.time_mode().format(placed.end)
+            ));
+        }
+        reason.push(' ');
+        reason.push_str(&self.start_reason(placed));
+        ExplainStep { candidates, chosen, reason }
+    }
+
+    fn giffler_thompson_step(&self, partial: &Partial) -> Option<ExplainStep> {
+        let mut candidates: Vec<Candidate> = (0..self.jobs().len()).flat_map(|job_id| partial.candidates(self, job_id)).collect();
+        let first = (0..candidates.len()).reduce(|best, index| if candidates[index].end < candidates[best].end { index } else { best })?;
+        let (machine_id, earliest_end) = (candidates[first].machine_id, candidates[first].end);
+        for (index, candidate) in candidates.iter_mut().enumerate() {
+            candidate.competing = index == first || (candidate.machine_id == machine_id && candidate.start < earliest_end - TIME_EPSILON);
+        }
+        let chosen = (0..candidates.len())
+            .filter(|&index| candidates[index].competing)
+            .min_by(|&a, &b| {
+                let (a, b) = (&candidates[a], &candidates[b]);
+                a.duration.total_cmp(&b.duration).then(a.start.total_cmp(&b.start)).then(a.job_id.cmp(&b.job_id))
+            })?;
+
+        let time = self.time_mode();
+        let finisher = &candidates[first];
+        let conflicts = candidates.iter().filter(|candidate| candidate.competing).count();
+        let placed = &candidates[chosen];
+        let mut reason = format!(
+            "{}'s operation {} could finish first, at {} on {}.",
+            self.job_name(finisher.job_id), finisher.operation_id, time.format(earliest_end), self.machine_name(machine_id)
+        );
+        if conflicts == 1 {
+            reason.push_str(" Nothing else could start there before then, so it goes next.");
+        } else {
+            reason.push_str(&format!(
+                " {} operations could start there before then (the conflict set); the shortest, {}'s operation {} (duration {}), goes first.",
+                conflicts, self.job_name(placed.job_id), placed.operation_id, time.format(placed.duration)
+            ));
+        }
+        reason.push(' ');
+        reason.push_str(&self.start_reason(placed));
+        Some(ExplainStep { candidates, chosen, reason })
+    }
+
+    /// When the candidate starts, and whether its machine or its job held it back
+    fn start_reason(&self, candidate: &Candidate) -> String {
+        let time = self.time_mode();
+        let bound = candidate.bound();
+        let other = match bound {
+            StartBound::Machine => format!("the job was ready at {}", time.format(candidate.job_ready)),
+            StartBound::Release | StartBound::Job => format!("the machine was free at {}", time.format(candidate.machine_ready)),
+            StartBound::Downtime => format!(
+                "the job was ready at {} and the machine free at {}",
+                time.format(candidate.job_ready), time.format(candidate.machine_ready)
+            ),
+        };
+        format!("It starts at {} when {} ({}).", time.format(candidate.start), bound.description(), other)
+    }
+}
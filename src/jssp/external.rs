@@ -0,0 +1,294 @@
+//! Solvers outside the crate, e.g. a C++ program, run as a subprocess and offered
+//! beside the built-in algorithms.
+//!
+//! An external solver is a command line. For every run:
+//!
+//! 1. The instance is written to a temporary file in the OR-Tools `jobs_data` JSON
+//!    layout the app imports and exports (see `ortools`): `jobs_data` lists every job's
+//!    operations as `[machine, duration]`, or a list of `[duration, machine]` options
+//!    for flexible operations, next to `machines_count`, `horizon` and, where the
+//!    instance has them, `downtime`, `buffering`, `preemptive` and names.
+//! 2. The command runs without a shell, split at spaces outside quotes. In every
+//!    argument `{instance}` becomes the path of that file, `{solution}` a path to write
+//!    the schedule to and `{seed}` the run's seed. Without `{instance}`, the path is
+//!    passed as the last argument.
+//! 3. The schedule is read from the solution file, or from standard output if the
+//!    command has no `{solution}`, in any format `solution_formats::read_solution`
+//!    understands: the app's schedule JSON or CSV, OR-Tools or JobShopLib JSON, a
+//!    machine order, or a MILP solver's solution of the model in `milp`.
+//!
+//! A run fails if the command cannot start, exits with an error, runs past its time
+//! limit or leaves operations out of the schedule. The error of a failed exit quotes
+//! what the command wrote to standard error. Multi-start, batch and benchmark runs get
+//! it through `Solver::try_solve_seeded` and `Solver::try_solve_objective`.
+//!
+//! ```
+//! use jssp_scheduler::jssp::external::ExternalSolver;
+//! use jssp_scheduler::jssp::multistart::{multi_start, MultiStartConfig};
+//! use jssp_scheduler::jssp::solution_formats::SolutionFormat;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::{JsspError, Solver};
+//!
+//! let ft06 = ft06().instance;
+//! let order = std::env::temp_dir().join("jssp_external_doctest_order.txt");
+//! std::fs::write(&order, SolutionFormat::MachineOrder.write(&ft06, &ft06.solve_greedy()).unwrap()).unwrap();
+//!
+//! // A stand-in for a real solver: it prints a machine order computed beforehand
+//! let external = ExternalSolver::new("Fixed Order", format!("sh -c 'cat \"$1\"' sh {} {{instance}}", order.display()));
+//! let schedule = external.run(&ft06, 7).unwrap();
+//! assert_eq!(ft06.calculate_makespan(&schedule), ft06.calculate_makespan(&ft06.solve_greedy()));
+//! assert!(!external.is_stochastic());
+//!
+//! // Copying the instance back is no schedule
+//! let echo = ExternalSolver::new("Echo", "cp {instance} {solution}");
+//! assert!(matches!(echo.run(&ft06, 7), Err(JsspError::Parse(_))));
+//! let missing = ExternalSolver::new("Missing", "no-such-solver-program");
+//! assert!(matches!(missing.run(&ft06, 7), Err(JsspError::Io { .. })));
+//! let result = multi_start(&missing, &ft06, &MultiStartConfig { starts: 2, ..Default::default() });
+//! assert!(result.failure().is_some_and(|error| error.contains("no-such-solver-program")));
+//! ```
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::checkpoint::{Checkpointing, RunEnd, RunStart};
+use super::ortools::to_ortools_json;
+use super::solution_formats::read_solution;
+use super::trace::ConvergencePoint;
+use super::{Instance, JsspError, Objective, Schedule, Solver, SolverInfo};
+
+/// How often a running command is checked for having exited
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+/// Characters of the command's error output quoted when it fails
+const STDERR_EXCERPT: usize = 2000;
+
+/// Runs so far in this process, so concurrent runs get their own files
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// A user-configured program that solves instances (see the module documentation)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalSolver {
+    /// Name in the algorithm list; replaces a built-in algorithm of the same name
+    pub name: String,
+    /// Command line with `{instance}`, `{solution}` and `{seed}` placeholders
+    pub command: String,
+    /// The command is stopped and the run fails after this long
+    pub time_limit: Option<Duration>,
+}
+
+impl ExternalSolver {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self { name: name.into(), command: command.into(), time_limit: None }
+    }
+
+    /// Parse `NAME=COMMAND`, as given on the command line
+    pub fn parse(definition: &str) -> Result<Self, String> {
+        match definition.split_once('=') {
+            Some((name, command)) if !name.trim().is_empty() && !command.trim().is_empty() => {
+                Ok(Self::new(name.trim(), command.trim()))
+            }
+            _ => Err(format!("Expected NAME=COMMAND for an external solver, got '{}'", definition)),
+        }
+    }
+
+    /// Write the instance, run the command and read its schedule back
+    pub fn run(&self, instance: &Instance, seed: u64) -> Result<Schedule, JsspError> {
+        let dir = std::env::temp_dir().join(format!(
+            "jssp-external-{}-{}",
+            std::process::id(),
+            RUNS.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&dir).map_err(JsspError::io("create", &dir))?;
+        let result = self.run_in(&dir, instance, seed);
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+
+    fn run_in(&self, dir: &Path, instance: &Instance, seed: u64) -> Result<Schedule, JsspError> {
+        let instance_path = dir.join("instance.json");
+        let solution_path = dir.join("solution.txt");
+        let document = serde_json::to_string_pretty(&to_ortools_json(instance, None))
+            .map_err(|e| JsspError::Serialization(e.to_string()))?;
+        std::fs::write(&instance_path, document).map_err(JsspError::io("write", &instance_path))?;
+
+        let mut arguments = split_command(&self.command)?;
+        if !arguments.iter().any(|argument| argument.contains("{instance}")) {
+            arguments.push("{instance}".to_string());
+        }
+        let to_file = arguments.iter().any(|argument| argument.contains("{solution}"));
+        let arguments: Vec<String> = arguments.iter()
+            .map(|argument| {
+                argument
+                    .replace("{instance}", &instance_path.to_string_lossy())
+                    .replace("{solution}", &solution_path.to_string_lossy())
+                    .replace("{seed}", &seed.to_string())
+            })
+            .collect();
+
+        let output = self.execute(&arguments)?;
+        let text = if to_file {
+            std::fs::read_to_string(&solution_path).map_err(JsspError::io("read", &solution_path))?
+        } else {
+            output
+        };
+        let schedule = read_solution(instance, &text).map_err(|e| e.context(format!("Output of {}", self.name)))?;
+        if schedule.len() != instance.num_operations() {
+            return Err(JsspError::Process(format!(
+                "{} scheduled {} of the {} operations",
+                self.name, schedule.len(), instance.num_operations()
+            )));
+        }
+        Ok(schedule)
+    }
+
+    /// Run the split command line to its end, returning its standard output
+    fn execute(&self, arguments: &[String]) -> Result<String, JsspError> {
+        let program = PathBuf::from(&arguments[0]);
+        let mut child = Command::new(&program)
+            .args(&arguments[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(JsspError::io("run", &program))?;
+        // Read both pipes while waiting, so a chatty command cannot fill one and stall
+        let read_all = |mut pipe: Box<dyn Read + Send>| {
+            std::thread::spawn(move || {
+                let mut bytes = Vec::new();
+                let _ = pipe.read_to_end(&mut bytes);
+                String::from_utf8_lossy(&bytes).into_owned()
+            })
+        };
+        let stdout = read_all(Box::new(child.stdout.take().expect("piped")));
+        let stderr = read_all(Box::new(child.stderr.take().expect("piped")));
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().map_err(JsspError::io("wait for", &program))? {
+                break Some(status);
+            }
+            if self.time_limit.is_some_and(|limit| started.elapsed() > limit) {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        let Some(status) = status else {
+            // Processes the command started may still hold the pipes, so the readers
+            // are left to finish on their own
+            return Err(JsspError::Process(format!(
+                "{} was stopped after its time limit of {:.1} s",
+                self.name, self.time_limit.unwrap_or_default().as_secs_f64()
+            )));
+        };
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        if !status.success() {
+            let excerpt = match stderr.trim() {
+                "" => String::new(),
+                text => format!(":\n{}", text.chars().take(STDERR_EXCERPT).collect::<String>()),
+            };
+            return Err(JsspError::Process(format!("{} failed ({}){}", self.name, status, excerpt)));
+        }
+        Ok(stdout)
+    }
+}
+
+impl Solver for ExternalSolver {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.command
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_seeded(instance, 0)
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "A program outside the app, run as a subprocess: it reads the instance as OR-Tools JSON and \
+                      writes a schedule in any format the app imports.",
+            complexity: "That of the program",
+            recommended_sizes: "Those the program handles",
+            citation: None,
+        }
+    }
+
+    /// Stochastic when the command takes the seed
+    fn is_stochastic(&self) -> bool {
+        self.command.contains("{seed}")
+    }
+
+    /// The schedule of the command, or none if it failed; `try_solve_seeded` tells why
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        self.run(instance, seed).unwrap_or_default()
+    }
+
+    fn try_solve_seeded(&self, instance: &Instance, seed: u64) -> Result<Schedule, JsspError> {
+        self.run(instance, seed)
+    }
+
+    /// The command has no objective to aim at, so this is `try_solve_seeded`
+    fn try_solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Result<Schedule, JsspError> {
+        let _ = objective;
+        self.run(instance, seed)
+    }
+
+    /// A single run that reports why it failed; there are no checkpoints to resume
+    fn solve_resumable(
+        &self,
+        instance: &Instance,
+        start: RunStart,
+        checkpointing: &mut Checkpointing,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Result<RunEnd, String>> {
+        let _ = (checkpointing, progress);
+        Some(match start {
+            RunStart::New { seed, .. } => self.run(instance, seed).map(RunEnd::Finished).map_err(|e| e.to_string()),
+            RunStart::Resume(_) => Err(format!("{} is an external solver and cannot resume a checkpoint", self.name)),
+        })
+    }
+}
+
+/// Split a command line into arguments at whitespace outside quotes; a backslash
+/// escapes the next character outside single quotes
+fn split_command(command: &str) -> Result<Vec<String>, JsspError> {
+    let mut arguments = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (None | Some('"'), '\\') => {
+                if let Some(next) = chars.next() {
+                    current.get_or_insert_with(String::new).push(next);
+                }
+            }
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => arguments.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(JsspError::Parse(format!("Unclosed quote in the command '{}'", command)));
+    }
+    arguments.extend(current);
+    if arguments.is_empty() {
+        return Err(JsspError::Parse("The external solver has no command".to_string()));
+    }
+    Ok(arguments)
+}
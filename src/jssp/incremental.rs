@@ -0,0 +1,361 @@
+//! Head and tail times on the disjunctive graph of a schedule, for evaluating machine
+//! re-sequencing moves without rebuilding the schedule.
+//!
+//! The head of an operation is its earliest start (delayed past machine downtime, which
+//! interrupts it on a preemptive instance); its tail is the length of the longest path
+//! from its completion to the end of the schedule, ignoring downtime. With both known,
+//! the effect of swapping two adjacent operations on a machine can be estimated in
+//! constant time, and a swap can be applied by updating only the operations it reaches.
+//!
+//! ```
+//! use jssp_scheduler::jssp::incremental::DisjunctiveGraph;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let mut graph = DisjunctiveGraph::new(&ft06, &ft06.solve_greedy()).unwrap();
+//! assert_eq!(graph.makespan(), 152.0);
+//!
+//! // Swap the third and fourth operation on machine 1
+//! let estimate = graph.swap_estimate(1, 2).unwrap();
+//! assert!(graph.apply_swap(1, 2));
+//! assert!(estimate <= graph.makespan());
+//! assert_eq!(graph.makespan(), ft06.calculate_makespan(&graph.schedule()));
+//! ```
+
+use super::{Instance, Schedule, ScheduledOperation, TIME_EPSILON};
+
+/// A schedule held as machine orders plus the head and tail of every operation.
+///
+/// Operations are numbered job by job, as by `Instance::operation_index`.
+#[derive(Debug, Clone)]
+pub struct DisjunctiveGraph<'a> {
+    instance: &'a Instance,
+    /// (job_id, operation_id) of every node
+    keys: Vec<(usize, usize)>,
+    machine: Vec<usize>,
+    duration: Vec<f64>,
+    /// Nodes in processing order on every machine
+    sequences: Vec<Vec<usize>>,
+    /// Index of every node in its machine's sequence
+    position: Vec<usize>,
+    /// The nodes processed before and after every node in its job
+    job_prev: Vec<Option<usize>>,
+    job_next: Vec<Option<usize>>,
+    head: Vec<f64>,
+    tail: Vec<f64>,
+    makespan: f64,
+}
+
+impl<'a> DisjunctiveGraph<'a> {
+    /// The graph of a schedule's machine orders, with every operation as early as
+    /// they allow. `None` if the schedule does not contain every operation of the
+    /// instance exactly once, or its machine orders contradict the job orders. In an
+    /// open shop the jobs keep the order of the schedule (see `Instance::job_sequences`).
+    pub fn new(instance: &'a Instance, schedule: &[ScheduledOperation]) -> Option<Self> {
+        let keys: Vec<(usize, usize)> = instance.jobs().iter()
+            .flat_map(|job| (0..job.operations.len()).map(|operation_id| (job.id, operation_id)))
+            .collect();
+
+        let count = keys.len();
+        let mut machine = vec![usize::MAX; count];
+        let mut duration = vec![0.0; count];
+        let mut position = vec![0; count];
+        let mut sequences = Vec::new();
+        for (machine_id, sequence) in instance.machine_sequences(schedule).into_iter().enumerate() {
+            let mut nodes = Vec::with_capacity(sequence.len());
+            for key in sequence {
+                let node = instance.operation_index(key.0, key.1)?;
+                if machine[node] != usize::MAX {
+                    return None;
+                }
+                machine[node] = machine_id;
+                duration[node] = instance.jobs()[key.0].operations[key.1].duration_on(machine_id)?;
+                position[node] = nodes.len();
+                nodes.push(node);
+            }
+            sequences.push(nodes);
+        }
+        if machine.contains(&usize::MAX) {
+            return None;
+        }
+        let mut job_prev = vec![None; count];
+        let mut job_next = vec![None; count];
+        for (job_id, order) in instance.job_sequences(schedule).into_iter().enumerate() {
+            let nodes: Vec<usize> = order.into_iter().filter_map(|operation_id| instance.operation_index(job_id, operation_id)).collect();
+            for pair in nodes.windows(2) {
+                job_next[pair[0]] = Some(pair[1]);
+                job_prev[pair[1]] = Some(pair[0]);
+            }
+        }
+
+        let mut graph = Self {
+            instance,
+            keys,
+            machine,
+            duration,
+            sequences,
+            position,
+            job_prev,
+            job_next,
+            head: vec![0.0; count],
+            tail: vec![0.0; count],
+            makespan: 0.0,
+        };
+        let all: Vec<usize> = (0..count).collect();
+        if !graph.update_heads(&all) {
+            return None;
+        }
+        graph.update_tails(&all);
+        graph.update_makespan();
+        Some(graph)
+    }
+
+    pub fn makespan(&self) -> f64 {
+        self.makespan
+    }
+
+    /// Earliest start of an operation, given as (job_id, operation_id)
+    pub fn head(&self, operation: (usize, usize)) -> Option<f64> {
+        self.node(operation).map(|node| self.head[node])
+    }
+
+    /// Longest path from an operation's completion to the end of the schedule
+    pub fn tail(&self, operation: (usize, usize)) -> Option<f64> {
+        self.node(operation).map(|node| self.tail[node])
+    }
+
+    /// Machine and position in that machine's order of an operation
+    pub fn location(&self, operation: (usize, usize)) -> Option<(usize, usize)> {
+        self.node(operation).map(|node| (self.machine[node], self.position[node]))
+    }
+
+    /// (job_id, operation_id) pairs on a machine in processing order
+    pub fn machine_sequence(&self, machine_id: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.sequences.get(machine_id).into_iter().flatten().map(|&node| self.keys[node])
+    }
+
+    /// Adjacent pairs (machine_id, position) on a critical path: both operations end a
+    /// longest path and the second starts right after the first. Swapping such a pair
+    /// never creates a cycle.
+    pub fn critical_swaps(&self) -> Vec<(usize, usize)> {
+        let critical = |node: usize| self.end(node) + self.tail[node] >= self.makespan - TIME_EPSILON;
+        let mut swaps = Vec::new();
+        for (machine_id, sequence) in self.sequences.iter().enumerate() {
+            for (position, pair) in sequence.windows(2).enumerate() {
+                let (u, v) = (pair[0], pair[1]);
+                let tight = self.end(u) + self.setup(u, v) >= self.head[v] - TIME_EPSILON;
+                if tight && critical(u) && critical(v) {
+                    swaps.push((machine_id, position));
+                }
+            }
+        }
+        swaps
+    }
+
+    /// Makespan after swapping the operations at `position` and `position + 1` on a
+    /// machine, in constant time, or `None` if there is no such pair.
+    ///
+    /// The estimate is the longest path through either swapped operation. Paths through
+    /// neither are unchanged, so it never exceeds the new makespan and equals it whenever
+    /// it is at least the current makespan. Tails leave machine downtime out, so on an
+    /// instance with downtime the estimate is only a lower bound. Swaps of adjacent operations on a critical
+    /// path keep the graph acyclic; for other pairs use `apply_swap`, which checks.
+    pub fn swap_estimate(&self, machine_id: usize, position: usize) -> Option<f64> {
+        let sequence = self.sequences.get(machine_id)?;
+        let (&u, &v) = (sequence.get(position)?, sequence.get(position + 1)?);
+        let before = position.checked_sub(1).map(|p| sequence[p]);
+        let after = sequence.get(position + 2).copied();
+
+        // v moves in front of u
+        let mut head_v = self.job_head(v);
+        let mut setup_v = 0.0;
+        if let Some(p) = before {
+            setup_v = self.setup(p, v);
+            head_v = head_v.max(self.end(p) + setup_v);
+        }
+        let head_v = self.instance.preemptive_start(machine_id, head_v, setup_v, self.duration[v]);
+        let end_v = self.instance.processing_end(machine_id, head_v, self.duration[v]);
+        let setup_u = self.setup(v, u);
+        let head_u = self.job_head(u).max(end_v + setup_u);
+        let head_u = self.instance.preemptive_start(machine_id, head_u, setup_u, self.duration[u]);
+        let end_u = self.instance.processing_end(machine_id, head_u, self.duration[u]);
+
+        let mut tail_u = self.job_tail(u);
+        if let Some(s) = after {
+            tail_u = tail_u.max(self.setup(u, s) + self.duration[s] + self.tail[s]);
+        }
+        let tail_v = self.job_tail(v).max(self.setup(v, u) + self.duration[u] + tail_u);
+
+        Some((end_v + tail_v).max(end_u + tail_u))
+    }
+
+    /// Swap the operations at `position` and `position + 1` on a machine, updating the
+    /// heads and tails of the operations the swap reaches (O(n) at worst). Returns false
+    /// and leaves the graph unchanged if there is no such pair or the swap would create
+    /// a cycle. Applying the same swap again undoes it.
+    pub fn apply_swap(&mut self, machine_id: usize, position: usize) -> bool {
+        let Some(sequence) = self.sequences.get_mut(machine_id) else {
+            return false;
+        };
+        if position + 1 >= sequence.len() {
+            return false;
+        }
+        sequence.swap(position, position + 1);
+        let (v, u) = (sequence[position], sequence[position + 1]);
+        self.position[v] = position;
+        self.position[u] = position + 1;
+
+        if !self.update_heads(&[v, u]) {
+            let sequence = &mut self.sequences[machine_id];
+            sequence.swap(position, position + 1);
+            self.position[u] = position;
+            self.position[v] = position + 1;
+            self.update_heads(&[u, v]);
+            return false;
+        }
+        self.update_tails(&[u, v]);
+        self.update_makespan();
+        true
+    }
+
+    /// The semi-active schedule of the current machine orders, sorted by job and operation
+    pub fn schedule(&self) -> Schedule {
+        (0..self.keys.len())
+            .map(|node| {
+                let (job_id, operation_id) = self.keys[node];
+                let setup_time = self.machine_pred(node).map_or(0.0, |pred| self.setup(pred, node));
+                self.instance.timed_operation(job_id, operation_id, self.machine[node], self.head[node], self.duration[node], setup_time)
+            })
+            .collect()
+    }
+
+    fn node(&self, (job_id, operation_id): (usize, usize)) -> Option<usize> {
+        self.instance.operation_index(job_id, operation_id)
+    }
+
+    fn job_pred(&self, node: usize) -> Option<usize> {
+        self.job_prev[node]
+    }
+
+    fn job_succ(&self, node: usize) -> Option<usize> {
+        self.job_next[node]
+    }
+
+    fn machine_pred(&self, node: usize) -> Option<usize> {
+        let position = self.position[node].checked_sub(1)?;
+        Some(self.sequences[self.machine[node]][position])
+    }
+
+    fn machine_succ(&self, node: usize) -> Option<usize> {
+        self.sequences[self.machine[node]].get(self.position[node] + 1).copied()
+    }
+
+    fn setup(&self, from: usize, to: usize) -> f64 {
+        self.instance.setup_time(self.machine[to], Some(self.keys[from].0), self.keys[to].0)
+    }
+
+    /// Travel time of the job from the machine of `from` to that of `to`
+    fn transfer(&self, from: usize, to: usize) -> f64 {
+        self.instance.transfer_time(Some(self.machine[from]), self.machine[to])
+    }
+
+    /// When the node finishes, after any downtime that interrupts it
+    fn end(&self, node: usize) -> f64 {
+        self.instance.processing_end(self.machine[node], self.head[node], self.duration[node])
+    }
+
+    /// Earliest start allowed by the job alone (release time, or previous operation and
+    /// the transfer from its machine)
+    fn job_head(&self, node: usize) -> f64 {
+        match self.job_pred(node) {
+            Some(pred) => self.end(pred) + self.transfer(pred, node),
+            None => self.instance.jobs()[self.keys[node].0].release_time,
+        }
+    }
+
+    /// Work that must follow the node within its job
+    fn job_tail(&self, node: usize) -> f64 {
+        self.job_succ(node).map_or(0.0, |next| self.transfer(node, next) + self.duration[next] + self.tail[next])
+    }
+
+    /// Recompute the heads of `sources` and every node after them, in topological
+    /// order. Returns false if a cycle is reached.
+    fn update_heads(&mut self, sources: &[usize]) -> bool {
+        let affected = self.reachable(sources, |graph, node| [graph.job_succ(node), graph.machine_succ(node)]);
+        self.relax(&affected, |graph, node| [graph.job_pred(node), graph.machine_pred(node)], |graph, node| {
+            let mut head = graph.job_head(node);
+            let mut setup = 0.0;
+            if let Some(pred) = graph.machine_pred(node) {
+                setup = graph.setup(pred, node);
+                head = head.max(graph.end(pred) + setup);
+            }
+            graph.head[node] = graph.instance.preemptive_start(graph.machine[node], head, setup, graph.duration[node]);
+        })
+    }
+
+    /// Recompute the tails of `sources` and every node before them
+    fn update_tails(&mut self, sources: &[usize]) -> bool {
+        let affected = self.reachable(sources, |graph, node| [graph.job_pred(node), graph.machine_pred(node)]);
+        self.relax(&affected, |graph, node| [graph.job_succ(node), graph.machine_succ(node)], |graph, node| {
+            let mut tail = graph.job_tail(node);
+            if let Some(next) = graph.machine_succ(node) {
+                tail = tail.max(graph.setup(node, next) + graph.duration[next] + graph.tail[next]);
+            }
+            graph.tail[node] = tail;
+        })
+    }
+
+    fn update_makespan(&mut self) {
+        self.makespan = (0..self.keys.len())
+            .map(|node| self.end(node))
+            .fold(0.0, f64::max);
+    }
+
+    /// Marks the nodes reachable from `sources` along `next`
+    fn reachable(&self, sources: &[usize], next: impl Fn(&Self, usize) -> [Option<usize>; 2]) -> Vec<bool> {
+        let mut seen = vec![false; self.keys.len()];
+        let mut stack = sources.to_vec();
+        while let Some(node) = stack.pop() {
+            if !std::mem::replace(&mut seen[node], true) {
+                stack.extend(next(self, node).into_iter().flatten());
+            }
+        }
+        seen
+    }
+
+    /// Visit the marked nodes in topological order (a node after all of its marked
+    /// `before` nodes) and update each. Returns false if they contain a cycle.
+    fn relax(
+        &mut self,
+        marked: &[bool],
+        before: impl Fn(&Self, usize) -> [Option<usize>; 2],
+        mut update: impl FnMut(&mut Self, usize),
+    ) -> bool {
+        let mut waiting = vec![0u8; self.keys.len()];
+        let mut after: Vec<Vec<usize>> = vec![Vec::new(); self.keys.len()];
+        let mut ready = Vec::new();
+        let mut remaining = 0;
+        for node in (0..self.keys.len()).filter(|&node| marked[node]) {
+            remaining += 1;
+            for earlier in before(self, node).into_iter().flatten().filter(|&earlier| marked[earlier]) {
+                waiting[node] += 1;
+                after[earlier].push(node);
+            }
+            if waiting[node] == 0 {
+                ready.push(node);
+            }
+        }
+
+        while let Some(node) = ready.pop() {
+            update(self, node);
+            remaining -= 1;
+            for &later in &after[node] {
+                waiting[later] -= 1;
+                if waiting[later] == 0 {
+                    ready.push(later);
+                }
+            }
+        }
+        remaining == 0
+    }
+}
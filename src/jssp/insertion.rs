@@ -0,0 +1,178 @@
+//! Greedy construction that fills idle gaps on machines.
+//!
+//! `Instance::decode` appends every operation to the end of its machine, so time a
+//! machine spends waiting for a job stays idle even when a later operation would fit
+//! into it. `Instance::decode_active` instead places each operation in the earliest idle
+//! interval of its machine that is long enough, given its job predecessor, the setups
+//! before it and after it, and downtime. Operations already placed never move; the one
+//! after an inserted operation only has its setup recomputed. The result is close to an
+//! active schedule, for little more work than a plain decode.
+//!
+//! ```
+//! use jssp_scheduler::jssp::insertion::InsertionGreedy;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Solver;
+//!
+//! let ft06 = ft06().instance;
+//! let schedule = InsertionGreedy.solve(&ft06);
+//! assert!(ft06.validate(&schedule).is_ok());
+//! assert!(ft06.calculate_makespan(&schedule) < ft06.calculate_makespan(&ft06.solve_greedy()));
+//!
+//! // Other sequences can be decoded with insertion too, e.g. the job-by-job one
+//! let sequence = ft06.job_by_job_sequence();
+//! let appended = ft06.decode(&sequence);
+//! let inserted = ft06.decode_active(&sequence);
+//! assert!(ft06.calculate_makespan(&inserted) <= ft06.calculate_makespan(&appended));
+//! ```
+
+use super::solvers::Greedy;
+use super::resources::ResourceUsage;
+use super::{Buffering, Instance, Objective, Operation, Schedule, ScheduledOperation, Solver, SolverInfo, TIME_EPSILON};
+
+impl Instance {
+    /// Build a schedule from an operation-based sequence (see `decode`), placing each
+    /// operation in the earliest idle interval of an eligible machine where it fits.
+    /// Flexible operations take the machine that finishes them first, and pinned ones
+    /// stay where they are pinned. Without buffers between machines this is the same as
+    /// `decode`, as jobs are placed whole.
+    pub fn decode_active(&self, sequence: &[usize]) -> Vec<ScheduledOperation> {
+        if self.buffering != Buffering::Unlimited {
+            return self.decode(sequence);
+        }
+        let jobs = self.jobs();
+        let mut schedule: Vec<ScheduledOperation> = Vec::with_capacity(sequence.len());
+        // Indices into `schedule` of each machine's operations, by start time
+        let mut timelines: Vec<Vec<usize>> = vec![Vec::new(); self.num_machines()];
+        let mut next_operation = vec![0; jobs.len()];
+        let mut job_ready: Vec<f64> = jobs.iter().map(|job| job.release_time).collect();
+        let mut job_machine: Vec<Option<usize>> = vec![None; jobs.len()];
+        let mut resources = ResourceUsage::new(self);
+        let pins = self.uses_pins();
+
+        for &job_index in sequence {
+            let job = &jobs[job_index];
+            let Some(operation) = job.operations.get(next_operation[job_index]) else {
+                continue;
+            };
+            next_operation[job_index] += 1;
+
+            // A pinned operation stays where it is, outside the timelines: the time it
+            // holds its machine is blocked for every other operation anyway
+            if let Some(pin) = self.pin(job.id, operation.operation_id).filter(|_| pins) {
+                job_ready[job_index] = pin.end_time;
+                job_machine[job_index] = Some(pin.machine_id);
+                schedule.push(pin.clone());
+                continue;
+            }
+
+            let (machine_id, duration, placement) = operation.options()
+                .map(|option| {
+                    let ready = job_ready[job_index] + self.transfer_time(job_machine[job_index], option.machine_id);
+                    let machine = (option.machine_id, &timelines[option.machine_id][..]);
+                    let placement = self.earliest_gap(&schedule, &resources, machine, operation, option.duration, ready);
+                    let end = self.processing_end(option.machine_id, placement.start, option.duration);
+                    (option.machine_id, option.duration, placement, end)
+                })
+                .min_by(|a, b| a.3.total_cmp(&b.3))
+                .map(|(machine_id, duration, placement, _)| (machine_id, duration, placement))
+                .unwrap();
+
+            if let Some((next, setup)) = placement.next_setup {
+                schedule[next].setup_time = setup;
+            }
+            timelines[machine_id].insert(placement.position, schedule.len());
+            let placed = self.timed_operation(job.id, operation.operation_id, machine_id, placement.start, duration, placement.setup);
+            job_ready[job_index] = placed.end_time;
+            job_machine[job_index] = Some(machine_id);
+            resources.reserve(self, &placed);
+            schedule.push(placed);
+        }
+
+        if pins {
+            self.settle_pinned_setups(&mut schedule);
+        }
+        schedule
+    }
+
+    /// The first position on a machine's timeline (given with the machine's id) where
+    /// `operation` fits, starting no earlier than `ready` and once a unit of any
+    /// secondary resource it needs is free; the end of the timeline always fits
+    fn earliest_gap(
+        &self,
+        schedule: &[ScheduledOperation],
+        resources: &ResourceUsage,
+        (machine_id, timeline): (usize, &[usize]),
+        operation: &Operation,
+        duration: f64,
+        ready: f64,
+    ) -> Placement {
+        let job_id = operation.job_id;
+        let key = (job_id, operation.operation_id);
+        for position in 0..=timeline.len() {
+            let previous = position.checked_sub(1).map(|index| &schedule[timeline[index]]);
+            let setup = self.setup_time(machine_id, previous.map(|op| op.job_id), job_id);
+            let free = previous.map_or(0.0, |op| op.end_time);
+            let start = self.resource_start(resources, key, machine_id, (free + setup).max(ready), setup, duration);
+            let Some(&next) = timeline.get(position) else {
+                return Placement { position, start, setup, next_setup: None };
+            };
+            // The next operation keeps its start, after a setup from this job instead
+            let following = &schedule[next];
+            let next_setup = self.setup_time(machine_id, Some(job_id), following.job_id);
+            let fits = self.processing_end(machine_id, start, duration) + next_setup <= following.start_time + TIME_EPSILON
+                && self.preemptive_start(machine_id, following.start_time, next_setup, following.duration) <= following.start_time + TIME_EPSILON;
+            if fits {
+                return Placement { position, start, setup, next_setup: Some((next, next_setup)) };
+            }
+        }
+        unreachable!("the end of the timeline always fits")
+    }
+}
+
+/// Where an operation goes on its machine
+struct Placement {
+    /// Index in the machine's timeline
+    position: usize,
+    start: f64,
+    setup: f64,
+    /// The operation after it, by index into the schedule, and its new setup
+    next_setup: Option<(usize, f64)>,
+}
+
+/// Greedy in the job order of `Greedy`, but with every operation placed in the
+/// earliest idle gap of its machine where it fits (see `Instance::decode_active`)
+pub struct InsertionGreedy;
+
+impl Solver for InsertionGreedy {
+    fn name(&self) -> &str {
+        "Insertion Greedy"
+    }
+
+    fn description(&self) -> &str {
+        "Job by job like Greedy, but each operation fills the earliest idle gap on its machine that it fits into"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "List scheduling in the same job order as Greedy, except that an operation is not simply \
+                      appended to its machine: it goes into the earliest idle interval that is long enough for \
+                      it and its setups, without moving anything placed before. Gaps left while machines wait \
+                      for jobs get used by later jobs, which shortens the makespan considerably compared to \
+                      Greedy at almost the same cost.",
+            complexity: "O(N²/M) for M machines, as each placement scans the gaps of one machine",
+            recommended_sizes: "Any size; instant even for thousands of operations",
+            citation: Some("Giffler, B., & Thompson, G. L. (1960). Algorithms for solving production-scheduling problems. Operations Research, 8(4), 487-503."),
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_objective(instance, Objective::Makespan, 0)
+    }
+
+    fn solve_objective(&self, instance: &Instance, objective: Objective, _seed: u64) -> Schedule {
+        let sequence: Vec<usize> = Greedy::job_order(instance, objective).into_iter()
+            .flat_map(|job| std::iter::repeat_n(job, instance.jobs()[job].operations.len()))
+            .collect();
+        instance.decode_active(&sequence)
+    }
+}
@@ -0,0 +1,935 @@
+//! The problem data of a job shop: jobs, machines, setups and descriptive extras.
+
+use std::collections::BTreeMap;
+use std::hash::Hasher;
+
+use serde::{Deserialize, Serialize};
+
+use super::resources::SecondaryResource;
+use super::shop::{check_flow_shop, ProblemType};
+use super::soft::SoftConstraint;
+use super::{Buffering, Downtime, Job, MachineOption, ScheduledOperation, SetupTimes, TransferTimes, TIME_EPSILON};
+use crate::calendar::Calendar;
+
+/// A validated job shop instance.
+///
+/// Construction checks that job ids are `0..n` in order, that every operation belongs
+/// to its job and is numbered by position, and that every machine reference (including
+/// alternatives, setup and transfer matrices) lies below `num_machines`. The jobs,
+/// machine count, setup and transfer matrices can only be replaced through checked
+/// methods, so every consumer may index by job and machine id without re-validating.
+///
+/// ```
+/// use jssp_scheduler::jssp::{Instance, JsspSolver, Job, Operation};
+///
+/// let jobs = vec![Job {
+///     id: 0,
+///     operations: vec![Operation { job_id: 0, operation_id: 0, machine_id: 2, duration: 3.0, alternatives: Vec::new() }],
+///     release_time: 0.0,
+///     due_date: None,
+///     weight: 1.0,
+/// }];
+/// assert!(Instance::new(jobs.clone(), 2).is_err());
+///
+/// let solver = JsspSolver::new(Instance::new(jobs, 3).unwrap().with_name("tiny"));
+/// assert_eq!(solver.calculate_makespan(&solver.solve_greedy()), 3.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Instance {
+    jobs: Vec<Job>,
+    /// Flat index of every job's first operation, numbering operations job by job,
+    /// followed by the number of operations
+    operation_offsets: Vec<usize>,
+    /// The eligible machines of every operation, default machine first, in flat order:
+    /// the decoders read them here instead of walking jobs and operations
+    options: Vec<MachineOption>,
+    /// Index in `options` of every operation's first option, followed by their number
+    option_offsets: Vec<usize>,
+    num_machines: usize,
+    setup_times: Option<SetupTimes>,
+    transfer_times: Option<TransferTimes>,
+    /// Sorted by machine and start; windows on one machine never overlap or touch
+    downtime: Vec<Downtime>,
+    /// The downtime together with the calendar's time off shift, sorted and merged like
+    /// `downtime`; the windows scheduling avoids
+    unavailable: Vec<Downtime>,
+    /// `unavailable` together with the time pinned operations hold, sorted and merged the
+    /// same way; the windows other operations are placed around while pins apply (see
+    /// `pins`). Empty without pins.
+    blocked: Vec<Downtime>,
+    /// Operations held where they are, by job and operation (see `pins`)
+    pins: Vec<ScheduledOperation>,
+    /// Operators, tools or fixtures operations need besides their machine (see `resources`)
+    resources: Vec<SecondaryResource>,
+    /// The resource every operation needs, if any, by `operation_index`
+    resource_of: Vec<Option<usize>>,
+    /// Standard deviation of every operation's duration, by `operation_index`; empty
+    /// when durations are certain (see `stochastic`)
+    duration_std_devs: Vec<f64>,
+    /// Duration factors of every sampled scenario, by `operation_index`
+    scenarios: Vec<Vec<f64>>,
+    scenario_seed: u64,
+    /// Empty when unnamed; blank entries fall back to the default name
+    job_names: Vec<String>,
+    machine_names: Vec<String>,
+    /// Human-readable name, e.g. the file it was loaded from
+    pub name: String,
+    /// Maps time units to wall-clock dates; its working hours block the time off shift
+    calendar: Option<Calendar>,
+    /// Free-form key/value information such as source or generator settings
+    pub metadata: BTreeMap<String, String>,
+    /// Requirements that may be broken at a penalty (see `soft`)
+    pub soft_constraints: Vec<SoftConstraint>,
+    /// RGB colors chosen for individual jobs, by job id; charts use them instead of
+    /// their palette's. Does not affect scheduling.
+    pub job_colors: BTreeMap<usize, [u8; 3]>,
+    /// Whether jobs may wait between operations (see `buffering`)
+    pub buffering: Buffering,
+    /// Whether downtime may interrupt operations, which resume after it (see `preemption`)
+    pub preemptive: bool,
+    /// How the operations of a job are ordered (see `shop`)
+    problem_type: ProblemType,
+    time_mode: TimeMode,
+}
+
+/// Whether an instance counts time in whole units, like the benchmark instances of the
+/// literature, or in fractions of a unit. Decides how times are shown and exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeMode {
+    Integer,
+    #[default]
+    Fractional,
+}
+
+impl TimeMode {
+    pub const ALL: [TimeMode; 2] = [TimeMode::Integer, TimeMode::Fractional];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeMode::Integer => "Integer",
+            TimeMode::Fractional => "Fractional",
+        }
+    }
+
+    /// Decimal places times are shown with
+    pub fn decimals(&self) -> usize {
+        match self {
+            TimeMode::Integer => 0,
+            TimeMode::Fractional => 2,
+        }
+    }
+
+    /// A time (or a sum of times) as text, e.g. "12" or "12.50"
+    pub fn format(&self, time: f64) -> String {
+        format!("{:.*}", self.decimals(), time)
+    }
+
+    /// The nearest time an edit may pick: whole units in integer mode, any time otherwise
+    pub fn snap(&self, time: f64) -> f64 {
+        match self {
+            TimeMode::Integer => time.round(),
+            TimeMode::Fractional => time,
+        }
+    }
+}
+
+fn is_whole(value: f64) -> bool {
+    value.fract() == 0.0
+}
+
+fn check_time(value: f64, what: impl FnOnce() -> String) -> Result<(), String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(format!("{} must be a non-negative number, got {}", what(), value))
+    }
+}
+
+/// Windows sorted by machine and start, with overlapping or touching windows on a
+/// machine merged
+fn merged_windows(mut windows: Vec<Downtime>) -> Vec<Downtime> {
+    windows.sort_by(|a, b| a.machine_id.cmp(&b.machine_id).then(a.start.total_cmp(&b.start)));
+    let mut merged: Vec<Downtime> = Vec::with_capacity(windows.len());
+    for window in windows {
+        match merged.last_mut() {
+            Some(last) if last.machine_id == window.machine_id && window.start <= last.end => {
+                last.end = last.end.max(window.end);
+            }
+            _ => merged.push(window),
+        }
+    }
+    merged
+}
+
+/// Trims every name, and drops the list when none is left
+fn trimmed_names(names: Vec<String>) -> Vec<String> {
+    let names: Vec<String> = names.into_iter().map(|name| name.trim().to_string()).collect();
+    if names.iter().all(String::is_empty) { Vec::new() } else { names }
+}
+
+impl Instance {
+    pub fn new(jobs: Vec<Job>, num_machines: usize) -> Result<Self, String> {
+        if num_machines == 0 && !jobs.is_empty() {
+            return Err("An instance with jobs needs at least one machine".to_string());
+        }
+        for (index, job) in jobs.iter().enumerate() {
+            if job.id != index {
+                return Err(format!("Job at position {} has id {}; ids must be 0..{} in order", index, job.id, jobs.len()));
+            }
+            check_time(job.release_time, || format!("Release time of job {}", job.id))?;
+            check_time(job.weight, || format!("Weight of job {}", job.id))?;
+            if let Some(due_date) = job.due_date {
+                check_time(due_date, || format!("Due date of job {}", job.id))?;
+            }
+            for (position, op) in job.operations.iter().enumerate() {
+                if op.job_id != job.id || op.operation_id != position {
+                    return Err(format!(
+                        "Operation {} of job {} is labelled as job {} operation {}",
+                        position, job.id, op.job_id, op.operation_id
+                    ));
+                }
+                let mut seen = Vec::new();
+                for option in op.options() {
+                    if option.machine_id >= num_machines {
+                        return Err(format!(
+                            "Job {} operation {} uses machine {}, but the instance has {} machines",
+                            job.id, position, option.machine_id, num_machines
+                        ));
+                    }
+                    if seen.contains(&option.machine_id) {
+                        return Err(format!("Job {} operation {} lists machine {} twice", job.id, position, option.machine_id));
+                    }
+                    seen.push(option.machine_id);
+                    check_time(option.duration, || {
+                        format!("Duration of job {} operation {} on machine {}", job.id, position, option.machine_id)
+                    })?;
+                }
+            }
+        }
+
+        let num_operations = jobs.iter().map(|job| job.operations.len()).sum();
+        let mut instance = Self {
+            jobs,
+            operation_offsets: Vec::new(),
+            options: Vec::new(),
+            option_offsets: Vec::new(),
+            num_machines,
+            setup_times: None,
+            transfer_times: None,
+            downtime: Vec::new(),
+            unavailable: Vec::new(),
+            blocked: Vec::new(),
+            pins: Vec::new(),
+            resources: Vec::new(),
+            resource_of: vec![None; num_operations],
+            duration_std_devs: Vec::new(),
+            scenarios: Vec::new(),
+            scenario_seed: 0,
+            job_names: Vec::new(),
+            machine_names: Vec::new(),
+            name: String::new(),
+            calendar: None,
+            metadata: BTreeMap::new(),
+            soft_constraints: Vec::new(),
+            job_colors: BTreeMap::new(),
+            buffering: Buffering::Unlimited,
+            preemptive: false,
+            problem_type: ProblemType::JobShop,
+            time_mode: TimeMode::Fractional,
+        };
+        instance.index_operations();
+        Ok(instance.with_detected_time_mode())
+    }
+
+    /// Integer mode if every duration, release time and due date is a whole number
+    fn with_detected_time_mode(mut self) -> Self {
+        let whole = self.jobs.iter().all(|job| {
+            is_whole(job.release_time)
+                && job.due_date.is_none_or(is_whole)
+                && job.operations.iter().flat_map(|op| op.options()).all(|option| is_whole(option.duration))
+        });
+        self.time_mode = if whole { TimeMode::Integer } else { TimeMode::Fractional };
+        self
+    }
+
+    pub fn time_mode(&self) -> TimeMode {
+        self.time_mode
+    }
+
+    /// Switch the time mode. Switching to integer mode rounds every duration, release
+    /// time, due date, setup time, transfer time and downtime window to the nearest whole unit;
+    /// windows that round to nothing are dropped.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::{generate_instance, GeneratorConfig, TimeMode};
+    ///
+    /// let mut instance = generate_instance(&GeneratorConfig::default());
+    /// assert_eq!(instance.time_mode(), TimeMode::Fractional);
+    /// instance.set_time_mode(TimeMode::Integer);
+    /// let durations = instance.jobs().iter().flat_map(|job| &job.operations).map(|op| op.duration);
+    /// assert!(durations.into_iter().all(|d| d.fract() == 0.0));
+    /// assert_eq!(TimeMode::Integer.format(12.0), "12");
+    /// assert_eq!(TimeMode::Fractional.format(12.0), "12.00");
+    /// ```
+    pub fn set_time_mode(&mut self, mode: TimeMode) {
+        if mode == TimeMode::Integer {
+            for job in &mut self.jobs {
+                job.release_time = job.release_time.round();
+                job.due_date = job.due_date.map(f64::round);
+                for op in &mut job.operations {
+                    op.duration = op.duration.round();
+                    for alternative in &mut op.alternatives {
+                        alternative.duration = alternative.duration.round();
+                    }
+                }
+            }
+            self.index_operations();
+            if let Some(setups) = &mut self.setup_times {
+                for time in setups.times.iter_mut().flatten().flatten() {
+                    *time = time.round();
+                }
+            }
+            if let Some(transfers) = &mut self.transfer_times {
+                for time in transfers.times.iter_mut().flatten() {
+                    *time = time.round();
+                }
+            }
+            let downtime = self.downtime.iter()
+                .map(|window| Downtime { start: window.start.round(), end: window.end.round(), ..*window })
+                .filter(|window| window.end > window.start)
+                .collect();
+            self.set_downtime(downtime).expect("rounding keeps downtime windows valid");
+            let whole = |time: f64| (time - time.round()).abs() < TIME_EPSILON;
+            if !self.pins.iter().all(|pin| whole(pin.setup_start()) && whole(pin.start_time) && whole(pin.end_time)) {
+                self.pins.clear();
+            }
+        }
+        self.time_mode = mode;
+        self.refresh_unavailable();
+    }
+
+    /// Attach (or remove) a sequence-dependent setup matrix, which must be
+    /// `num_machines` matrices of `num_jobs x num_jobs` non-negative times
+    pub fn set_setup_times(&mut self, setup_times: Option<SetupTimes>) -> Result<(), String> {
+        if let Some(setups) = &setup_times {
+            let n = self.jobs.len();
+            if setups.times.len() != self.num_machines {
+                return Err(format!(
+                    "Setup times are given for {} machines, but the instance has {}",
+                    setups.times.len(), self.num_machines
+                ));
+            }
+            for (machine_id, matrix) in setups.times.iter().enumerate() {
+                if matrix.len() != n || matrix.iter().any(|row| row.len() != n) {
+                    return Err(format!("Setup matrix of machine {} must be {}x{}", machine_id, n, n));
+                }
+                for value in matrix.iter().flatten() {
+                    check_time(*value, || format!("Setup time on machine {}", machine_id))?;
+                }
+            }
+            if !setups.times.iter().flatten().flatten().all(|&value| is_whole(value)) {
+                self.time_mode = TimeMode::Fractional;
+            }
+        }
+        self.setup_times = setup_times;
+        self.refresh_unavailable();
+        Ok(())
+    }
+
+    /// Attach (or remove) the times jobs need to travel between machines, which must be
+    /// a `num_machines x num_machines` matrix of non-negative times. An operation can
+    /// start no earlier than the previous operation of its job ends plus the transfer
+    /// from that operation's machine.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::TransferTimes;
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// let before = ft06.calculate_makespan(&ft06.solve_greedy());
+    /// ft06.set_transfer_times(Some(TransferTimes { times: vec![vec![2.0; 6]; 6] })).unwrap();
+    /// let schedule = ft06.solve_greedy();
+    /// assert!(ft06.validate(&schedule).is_ok());
+    /// assert!(ft06.calculate_makespan(&schedule) > before);
+    /// assert!(ft06.set_transfer_times(Some(TransferTimes { times: vec![vec![2.0; 5]; 5] })).is_err());
+    /// ```
+    pub fn set_transfer_times(&mut self, transfer_times: Option<TransferTimes>) -> Result<(), String> {
+        if let Some(transfers) = &transfer_times {
+            let m = self.num_machines;
+            if transfers.times.len() != m || transfers.times.iter().any(|row| row.len() != m) {
+                return Err(format!("Transfer time matrix must be {}x{}", m, m));
+            }
+            for value in transfers.times.iter().flatten() {
+                check_time(*value, || "Transfer time".to_string())?;
+            }
+            if !transfers.times.iter().flatten().all(|&value| is_whole(value)) {
+                self.time_mode = TimeMode::Fractional;
+            }
+        }
+        self.transfer_times = transfer_times;
+        self.refresh_unavailable();
+        Ok(())
+    }
+
+    /// Declare when machines are unavailable. Windows must have `start < end` on an
+    /// existing machine; overlapping or touching windows on a machine are merged.
+    ///
+    /// Schedules built by the decoders and solvers delay an operation whose setup or
+    /// processing would reach into a window until the window ends; operations are
+    /// never split around one.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::Downtime;
+    /// use jssp_scheduler::jssp::testing::two_by_two;
+    ///
+    /// let mut instance = two_by_two().instance;
+    /// let before = instance.calculate_makespan(&instance.solve_greedy());
+    /// instance.set_downtime(vec![Downtime { machine_id: 0, start: 1.0, end: 4.0 }]).unwrap();
+    /// let schedule = instance.solve_greedy();
+    /// assert!(instance.validate(&schedule).is_ok());
+    /// assert!(instance.calculate_makespan(&schedule) > before);
+    /// assert!(instance.set_downtime(vec![Downtime { machine_id: 5, start: 1.0, end: 4.0 }]).is_err());
+    /// ```
+    pub fn set_downtime(&mut self, downtime: Vec<Downtime>) -> Result<(), String> {
+        for window in &downtime {
+            if window.machine_id >= self.num_machines {
+                return Err(format!(
+                    "Downtime is given for machine {}, but the instance has {} machines",
+                    window.machine_id, self.num_machines
+                ));
+            }
+            check_time(window.start, || format!("Downtime start on machine {}", window.machine_id))?;
+            check_time(window.end, || format!("Downtime end on machine {}", window.machine_id))?;
+            if window.end <= window.start {
+                return Err(format!(
+                    "Downtime on machine {} ends at {} before it starts at {}",
+                    window.machine_id, window.end, window.start
+                ));
+            }
+        }
+        if !downtime.iter().all(|window| is_whole(window.start) && is_whole(window.end)) {
+            self.time_mode = TimeMode::Fractional;
+        }
+        self.downtime = merged_windows(downtime);
+        self.refresh_unavailable();
+        Ok(())
+    }
+
+    /// Attach (or remove) a calendar. Its working hours, if any, make the time off shift
+    /// unavailable on every machine, like downtime: operations wait for the next shift,
+    /// or on a preemptive instance pause until it. Off-shift time is rolled out until
+    /// the whole workload could be done twice over after the last release or downtime,
+    /// plus a week; machines are always available after that. In integer mode windows
+    /// are widened to whole units.
+    ///
+    /// ```
+    /// use jssp_scheduler::calendar::{Calendar, WorkingHours};
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// let before = ft06.calculate_makespan(&ft06.solve_greedy());
+    /// let calendar = Calendar { working_hours: Some(WorkingHours::default()), ..Calendar::default() };
+    /// ft06.set_calendar(Some(calendar)).unwrap();
+    /// assert!(ft06.downtime().is_empty());
+    /// assert!(!ft06.unavailable().is_empty());
+    /// let schedule = ft06.solve_greedy();
+    /// assert!(ft06.validate(&schedule).is_ok());
+    /// assert!(ft06.calculate_makespan(&schedule) > before);
+    ///
+    /// let closed = WorkingHours { working_days: [false; 7], ..WorkingHours::default() };
+    /// assert!(ft06.set_calendar(Some(Calendar { working_hours: Some(closed), ..Calendar::default() })).is_err());
+    /// ```
+    pub fn set_calendar(&mut self, calendar: Option<Calendar>) -> Result<(), String> {
+        if let Some(calendar) = &calendar {
+            calendar.check()?;
+        }
+        self.calendar = calendar;
+        self.refresh_unavailable();
+        Ok(())
+    }
+
+    pub fn calendar(&self) -> Option<&Calendar> {
+        self.calendar.as_ref()
+    }
+
+    /// Whether the calendar limits when machines work
+    pub fn has_working_hours(&self) -> bool {
+        self.calendar.as_ref().is_some_and(|calendar| calendar.working_hours.is_some())
+    }
+
+    /// Recompute `unavailable` and `blocked` after the downtime, calendar, pins or
+    /// workload changed
+    fn refresh_unavailable(&mut self) {
+        self.unavailable = self.off_shift_with_downtime();
+        self.blocked = if self.pins.is_empty() {
+            Vec::new()
+        } else {
+            merged_windows(self.unavailable.iter().copied().chain(self.pin_windows()).collect())
+        };
+    }
+
+    /// The downtime and, with working hours, the time off shift over a horizon long
+    /// enough for any schedule
+    fn off_shift_with_downtime(&self) -> Vec<Downtime> {
+        let Some(calendar) = self.calendar.as_ref().filter(|calendar| calendar.working_hours.is_some()) else {
+            return self.downtime.clone();
+        };
+        let longest_setup = self.setup_times.iter().flat_map(|setups| setups.times.iter().flatten().flatten()).fold(0.0, |a: f64, &b| a.max(b));
+        let longest_transfer = self.transfer_times.iter().flat_map(|transfers| transfers.times.iter().flatten()).fold(0.0, |a: f64, &b| a.max(b));
+        let workload: f64 = self.jobs.iter()
+            .flat_map(|job| &job.operations)
+            .map(|op| op.options().map(|option| option.duration).fold(0.0, f64::max) + longest_setup + longest_transfer)
+            .sum();
+        let begin = self.jobs.iter().map(|job| job.release_time)
+            .chain(self.downtime.iter().map(|window| window.end))
+            .chain(self.pins.iter().map(|pin| pin.end_time))
+            .fold(0.0, f64::max);
+        let horizon = calendar.working_time_end(begin, 2.0 * workload) + 7.0 * 24.0 * 60.0 / calendar.minutes_per_unit;
+        let integer = self.time_mode == TimeMode::Integer;
+        let off_shift: Vec<(f64, f64)> = calendar.off_shift(0.0, horizon).into_iter()
+            .map(|(start, end)| if integer { (start.floor(), end.ceil()) } else { (start, end) })
+            .collect();
+        let mut windows = self.downtime.clone();
+        for machine_id in 0..self.num_machines {
+            windows.extend(off_shift.iter().map(|&(start, end)| Downtime { machine_id, start, end }));
+        }
+        merged_windows(windows)
+    }
+
+    /// Name the machines; an empty list, or a blank name, falls back to "Machine <id>"
+    pub fn set_machine_names(&mut self, names: Vec<String>) -> Result<(), String> {
+        if !names.is_empty() && names.len() != self.num_machines {
+            return Err(format!("Got {} machine names for {} machines", names.len(), self.num_machines));
+        }
+        self.machine_names = trimmed_names(names);
+        Ok(())
+    }
+
+    /// Name the jobs, e.g. after the orders they fulfil; an empty list, or a blank name,
+    /// falls back to "Job <id>"
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// let mut names = vec![String::new(); 6];
+    /// names[4] = "Order #4711".to_string();
+    /// ft06.set_job_names(names).unwrap();
+    /// assert_eq!(ft06.job_name(4), "Order #4711");
+    /// assert_eq!(ft06.job_name(0), "Job 0");
+    /// assert!(ft06.set_job_names(vec!["Too few".to_string()]).is_err());
+    /// ```
+    pub fn set_job_names(&mut self, names: Vec<String>) -> Result<(), String> {
+        if !names.is_empty() && names.len() != self.jobs.len() {
+            return Err(format!("Got {} job names for {} jobs", names.len(), self.jobs.len()));
+        }
+        self.job_names = trimmed_names(names);
+        Ok(())
+    }
+
+    /// The job names as set, one per job, or empty when no job has one
+    pub fn job_names(&self) -> &[String] {
+        &self.job_names
+    }
+
+    /// The machine names as set, one per machine, or empty when no machine has one
+    pub fn machine_names(&self) -> &[String] {
+        &self.machine_names
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Change the priority weight of a job; ids beyond the last job are ignored
+    pub fn set_job_weight(&mut self, job_id: usize, weight: f64) {
+        if let Some(job) = self.jobs.get_mut(job_id) {
+            job.weight = weight;
+        }
+    }
+
+    /// Lengthen an operation on `machine_id` by `extra`, e.g. when it overran on the shop floor
+    pub fn extend_operation(&mut self, job_id: usize, operation_id: usize, machine_id: usize, extra: f64) -> Result<(), String> {
+        check_time(extra, || format!("Extra time of job {} operation {}", job_id, operation_id))?;
+        let operation = self.jobs.get_mut(job_id)
+            .and_then(|job| job.operations.get_mut(operation_id))
+            .ok_or_else(|| format!("Job {} has no operation {}", job_id, operation_id))?;
+        let duration = if operation.machine_id == machine_id {
+            &mut operation.duration
+        } else {
+            &mut operation.alternatives.iter_mut()
+                .find(|option| option.machine_id == machine_id)
+                .ok_or_else(|| format!("Job {} operation {} cannot run on machine {}", job_id, operation_id, machine_id))?
+                .duration
+        };
+        *duration += extra;
+        if !is_whole(*duration) {
+            self.time_mode = TimeMode::Fractional;
+        }
+        self.index_operations();
+        // The pinned operation no longer fits its pin, and its job's later ones may not either
+        self.pins.retain(|pin| pin.job_id != job_id || pin.operation_id < operation_id);
+        self.refresh_unavailable();
+        Ok(())
+    }
+
+    /// Append a job, e.g. an order that came in after the schedule was made, and return
+    /// its id. The job and its operations are renumbered to fit; setup matrices grow by
+    /// a row and a column of zeros for it.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::{Job, Operation};
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// let operation = Operation { job_id: 0, operation_id: 0, machine_id: 3, duration: 4.0, alternatives: Vec::new() };
+    /// let job = Job { id: 0, operations: vec![operation], release_time: 0.0, due_date: None, weight: 1.0 };
+    /// assert_eq!(ft06.add_job(job.clone()), Ok(6));
+    /// assert_eq!(ft06.jobs()[6].operations[0].job_id, 6);
+    /// assert_eq!(ft06.num_operations(), 37);
+    ///
+    /// let elsewhere = Operation { machine_id: 6, ..job.operations[0].clone() };
+    /// assert!(ft06.add_job(Job { operations: vec![elsewhere], ..job }).is_err());
+    /// ```
+    pub fn add_job(&mut self, mut job: Job) -> Result<usize, String> {
+        let job_id = self.jobs.len();
+        job.id = job_id;
+        for (operation_id, op) in job.operations.iter_mut().enumerate() {
+            op.job_id = job_id;
+            op.operation_id = operation_id;
+        }
+        let mut jobs = self.jobs.clone();
+        jobs.push(job);
+        let checked = Instance::new(jobs, self.num_machines)?;
+        if self.problem_type == ProblemType::FlowShop {
+            check_flow_shop(&checked.jobs)?;
+        }
+
+        if let Some(setups) = &mut self.setup_times {
+            for matrix in &mut setups.times {
+                for row in matrix.iter_mut() {
+                    row.push(0.0);
+                }
+                matrix.push(vec![0.0; job_id + 1]);
+            }
+        }
+        if !self.job_names.is_empty() {
+            self.job_names.push(String::new());
+        }
+        self.resource_of.resize(checked.num_operations(), None);
+        // The new job's durations are certain
+        if !self.duration_std_devs.is_empty() {
+            self.duration_std_devs.resize(checked.num_operations(), 0.0);
+            for factors in &mut self.scenarios {
+                factors.resize(checked.num_operations(), 1.0);
+            }
+        }
+        if checked.time_mode == TimeMode::Fractional {
+            self.time_mode = TimeMode::Fractional;
+        }
+        self.jobs = checked.jobs;
+        self.index_operations();
+        self.refresh_unavailable();
+        Ok(job_id)
+    }
+
+    pub fn num_machines(&self) -> usize {
+        self.num_machines
+    }
+
+    /// Fingerprint of the jobs, machines, setup and transfer times, downtime, time off
+    /// shift, pins, secondary resources, duration spreads and problem type (not the name
+    /// or metadata), as 16 hex digits
+    pub fn fingerprint(&self) -> String {
+        // FNV-1a, which unlike `DefaultHasher` is stable across Rust versions
+        struct Fnv(u64);
+        impl Hasher for Fnv {
+            fn finish(&self) -> u64 {
+                self.0
+            }
+            fn write(&mut self, bytes: &[u8]) {
+                for byte in bytes {
+                    self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+                }
+            }
+        }
+
+        let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+        hasher.write_u64(self.num_machines() as u64);
+        for job in self.jobs() {
+            hasher.write_u64(job.operations.len() as u64);
+            for time in [job.release_time, job.due_date.unwrap_or(f64::NAN), job.weight] {
+                hasher.write_u64(time.to_bits());
+            }
+            for op in &job.operations {
+                for option in op.options() {
+                    hasher.write_u64(option.machine_id as u64);
+                    hasher.write_u64(option.duration.to_bits());
+                }
+                hasher.write_u8(0xff);
+            }
+        }
+        if let Some(setups) = self.setup_times() {
+            for value in setups.times.iter().flatten().flatten() {
+                hasher.write_u64(value.to_bits());
+            }
+        }
+        if let Some(transfers) = self.transfer_times() {
+            hasher.write_u8(0xfe);
+            for value in transfers.times.iter().flatten() {
+                hasher.write_u64(value.to_bits());
+            }
+        }
+        for window in self.unavailable() {
+            hasher.write_u64(window.machine_id as u64);
+            hasher.write_u64(window.start.to_bits());
+            hasher.write_u64(window.end.to_bits());
+        }
+        if !self.pins.is_empty() {
+            hasher.write_u8(0xfc);
+            for pin in &self.pins {
+                for value in [pin.job_id, pin.operation_id, pin.machine_id] {
+                    hasher.write_u64(value as u64);
+                }
+                hasher.write_u64(pin.start_time.to_bits());
+            }
+        }
+        if !self.resources.is_empty() {
+            hasher.write_u8(0xfd);
+            for resource in &self.resources {
+                hasher.write_u64(resource.capacity as u64);
+            }
+            for resource_id in &self.resource_of {
+                hasher.write_u64(resource_id.map_or(u64::MAX, |id| id as u64));
+            }
+        }
+        if !self.scenarios.is_empty() {
+            hasher.write_u8(0xfb);
+            for std_dev in &self.duration_std_devs {
+                hasher.write_u64(std_dev.to_bits());
+            }
+            hasher.write_u64(self.scenarios.len() as u64);
+            hasher.write_u64(self.scenario_seed);
+        }
+        if self.problem_type != ProblemType::JobShop {
+            hasher.write_u8(0xfa);
+            hasher.write_u8(self.problem_type as u8);
+        }
+        format!("{:016x}", hasher.finish())
+    }
+
+    pub fn setup_times(&self) -> Option<&SetupTimes> {
+        self.setup_times.as_ref()
+    }
+
+    pub fn transfer_times(&self) -> Option<&TransferTimes> {
+        self.transfer_times.as_ref()
+    }
+
+    /// Every downtime window, by machine and start
+    pub fn downtime(&self) -> &[Downtime] {
+        &self.downtime
+    }
+
+    /// Every window in which a machine cannot work: its downtime and, with working
+    /// hours, the time off shift; by machine and start
+    pub fn unavailable(&self) -> &[Downtime] {
+        &self.unavailable
+    }
+
+    /// The windows in which one machine cannot work (see `unavailable`), by start
+    pub fn machine_downtime(&self, machine_id: usize) -> &[Downtime] {
+        let from = self.unavailable.partition_point(|window| window.machine_id < machine_id);
+        let to = self.unavailable.partition_point(|window| window.machine_id <= machine_id);
+        &self.unavailable[from..to]
+    }
+
+    /// The windows operations are placed around on one machine: `machine_downtime`, and
+    /// while pins apply also the time the pinned operations hold
+    fn machine_blocked(&self, machine_id: usize) -> &[Downtime] {
+        let windows = if self.uses_pins() { &self.blocked } else { &self.unavailable };
+        let from = windows.partition_point(|window| window.machine_id < machine_id);
+        let to = windows.partition_point(|window| window.machine_id <= machine_id);
+        &windows[from..to]
+    }
+
+    /// The windows of a machine operations are placed around (downtime, time off shift
+    /// and pinned operations) from the first one that ends after `time`; windows never
+    /// overlap, so they end in the order they start
+    pub(super) fn downtime_after(&self, machine_id: usize, time: f64) -> &[Downtime] {
+        let windows = self.machine_blocked(machine_id);
+        &windows[windows.partition_point(|window| window.end <= time + TIME_EPSILON)..]
+    }
+
+    /// Earliest start at or after `start` for an operation of `duration` on a machine,
+    /// such that neither the operation nor the `setup` right before it overlaps a
+    /// downtime window
+    pub fn available_start(&self, machine_id: usize, start: f64, setup: f64, duration: f64) -> f64 {
+        let mut start = start;
+        for window in self.downtime_after(machine_id, start - setup) {
+            if window.overlaps(start - setup, start + duration) {
+                start = window.end + setup;
+            }
+        }
+        start
+    }
+
+    /// Latest end at or before `end` for an operation of `duration` on a machine, such
+    /// that neither the operation nor the `setup` right before it overlaps a downtime
+    /// window. The mirror image of `available_start`.
+    pub fn available_end(&self, machine_id: usize, end: f64, setup: f64, duration: f64) -> f64 {
+        let mut end = end;
+        for window in self.machine_blocked(machine_id).iter().rev() {
+            if window.overlaps(end - duration - setup, end) {
+                end = window.start;
+            }
+        }
+        end
+    }
+
+    /// The secondary resources, by id
+    pub fn resources(&self) -> &[SecondaryResource] {
+        &self.resources
+    }
+
+    /// The resource an operation needs besides its machine, if any
+    pub fn resource_of(&self, job_id: usize, operation_id: usize) -> Option<usize> {
+        self.operation_index(job_id, operation_id).and_then(|index| self.resource_of[index])
+    }
+
+    /// Operations held on their machine at their start time, by job and operation
+    pub fn pins(&self) -> &[ScheduledOperation] {
+        &self.pins
+    }
+
+    /// Replace the pins, already checked by `set_pins`
+    pub(super) fn set_pin_data(&mut self, pins: Vec<ScheduledOperation>) {
+        self.pins = pins;
+        self.refresh_unavailable();
+    }
+
+    /// Replace the resources, already checked by `set_resources`
+    pub(super) fn set_resource_data(&mut self, resources: Vec<SecondaryResource>, resource_of: Vec<Option<usize>>) {
+        self.resources = resources;
+        self.resource_of = resource_of;
+    }
+
+    /// How the operations of a job are ordered (see `shop`)
+    pub fn problem_type(&self) -> ProblemType {
+        self.problem_type
+    }
+
+    /// Replace the problem type, already checked by `set_problem_type`
+    pub(super) fn set_problem_type_data(&mut self, problem_type: ProblemType) {
+        self.problem_type = problem_type;
+    }
+
+    /// Number of scenarios drawn for the uncertain durations, 0 if they are certain
+    pub fn scenario_count(&self) -> usize {
+        self.scenarios.len()
+    }
+
+    /// Seed the scenarios were drawn with
+    pub fn scenario_seed(&self) -> u64 {
+        self.scenario_seed
+    }
+
+    pub(super) fn duration_std_devs(&self) -> &[f64] {
+        &self.duration_std_devs
+    }
+
+    pub(super) fn scenarios(&self) -> &[Vec<f64>] {
+        &self.scenarios
+    }
+
+    /// Replace the duration spreads and scenarios, already drawn by `set_duration_spreads`
+    pub(super) fn set_stochastic_data(&mut self, duration_std_devs: Vec<f64>, scenarios: Vec<Vec<f64>>, seed: u64) {
+        self.duration_std_devs = duration_std_devs;
+        self.scenarios = scenarios;
+        self.scenario_seed = seed;
+    }
+
+    pub fn machine_name(&self, machine_id: usize) -> String {
+        self.machine_names.get(machine_id)
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Machine {}", machine_id))
+    }
+
+    pub fn job_name(&self, job_id: usize) -> String {
+        self.job_names.get(job_id)
+            .filter(|name| !name.is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Job {}", job_id))
+    }
+
+    pub fn num_operations(&self) -> usize {
+        self.operation_offsets[self.jobs.len()]
+    }
+
+    /// Rebuild the flat operation arrays after the jobs changed
+    fn index_operations(&mut self) {
+        self.operation_offsets.clear();
+        self.options.clear();
+        self.option_offsets.clear();
+        self.operation_offsets.push(0);
+        for job in &self.jobs {
+            self.operation_offsets.push(self.operation_offsets[self.operation_offsets.len() - 1] + job.operations.len());
+            for op in &job.operations {
+                self.option_offsets.push(self.options.len());
+                self.options.extend(op.options());
+            }
+        }
+        self.option_offsets.push(self.options.len());
+    }
+
+    /// The eligible machines of the operation at flat index `node` (see
+    /// `operation_index`) with their processing times, default machine first
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let ft06 = ft06().instance;
+    /// let node = ft06.operation_index(2, 1).unwrap();
+    /// assert_eq!(ft06.operation_options(node)[0].machine_id, ft06.jobs()[2].operations[1].machine_id);
+    /// ```
+    pub fn operation_options(&self, node: usize) -> &[MachineOption] {
+        &self.options[self.option_offsets[node]..self.option_offsets[node + 1]]
+    }
+
+    /// Processing time of the operation at flat index `node` on `machine_id`, or on its
+    /// default machine if that one is not eligible
+    pub(super) fn duration_at(&self, node: usize, machine_id: usize) -> f64 {
+        let options = self.operation_options(node);
+        options.iter().find(|option| option.machine_id == machine_id).unwrap_or(&options[0]).duration
+    }
+
+    /// Position of an operation when all operations are numbered job by job, for
+    /// keeping per-operation data in flat arrays of length `num_operations`
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let ft06 = ft06().instance;
+    /// assert_eq!(ft06.operation_index(0, 0), Some(0));
+    /// assert_eq!(ft06.operation_index(2, 1), Some(13));
+    /// assert_eq!(ft06.operation_index(5, 5), Some(ft06.num_operations() - 1));
+    /// assert_eq!(ft06.operation_index(0, 6), None);
+    /// ```
+    pub fn operation_index(&self, job_id: usize, operation_id: usize) -> Option<usize> {
+        let start = *self.operation_offsets.get(job_id)?;
+        let end = *self.operation_offsets.get(job_id + 1)?;
+        (start + operation_id < end).then_some(start + operation_id)
+    }
+
+    /// Whether any operation can choose between several machines (FJSP)
+    pub fn is_flexible(&self) -> bool {
+        self.jobs.iter().flat_map(|j| &j.operations).any(|op| op.is_flexible())
+    }
+}
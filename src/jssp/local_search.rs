@@ -0,0 +1,135 @@
+//! Hill climbing on the machine sequences of an existing schedule.
+//!
+//! `Instance::improve` polishes any complete schedule, such as a greedy one or one
+//! imported from another tool: it keeps every operation on its machine and tries
+//! re-sequencing moves one by one, rebuilding the schedule from the changed sequences
+//! (see `schedule_from_sequences`) and taking the first move that shortens it. It stops
+//! at a schedule no single move improves, or when the time limit runs out.
+//!
+//! ```
+//! use std::time::Duration;
+//! use jssp_scheduler::jssp::local_search::MoveStrategy;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let greedy = ft06.solve_greedy();
+//! let result = ft06.improve(&greedy, MoveStrategy::SwapAndInsertion, Duration::from_secs(10));
+//! assert_eq!(result.initial_makespan, 152.0);
+//! assert!(result.makespan < result.initial_makespan);
+//! assert_eq!(result.makespan, ft06.calculate_makespan(&result.schedule));
+//! assert!(ft06.validate(&result.schedule).is_ok());
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use super::{Instance, Schedule, ScheduledOperation, TIME_EPSILON};
+
+/// The moves hill climbing tries on each machine's sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveStrategy {
+    /// Exchange two operations on the same machine
+    Swap,
+    /// Take an operation out of its machine's sequence and put it back elsewhere
+    Insertion,
+    /// Swaps, then insertions once no swap helps
+    SwapAndInsertion,
+}
+
+impl MoveStrategy {
+    pub const ALL: [MoveStrategy; 3] = [MoveStrategy::Swap, MoveStrategy::Insertion, MoveStrategy::SwapAndInsertion];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoveStrategy::Swap => "Swap",
+            MoveStrategy::Insertion => "Insertion",
+            MoveStrategy::SwapAndInsertion => "Swap + Insertion",
+        }
+    }
+}
+
+/// The result of `Instance::improve`
+#[derive(Debug, Clone)]
+pub struct Improvement {
+    pub schedule: Schedule,
+    pub initial_makespan: f64,
+    pub makespan: f64,
+    /// Improving moves applied
+    pub moves: usize,
+    /// The time limit ran out before reaching a local optimum
+    pub timed_out: bool,
+}
+
+impl Instance {
+    /// Apply improving `strategy` moves to `schedule` until none is left or `time_limit`
+    /// has passed. Every operation stays on its machine. A schedule whose machine order
+    /// contradicts its jobs comes back unchanged.
+    pub fn improve(&self, schedule: &[ScheduledOperation], strategy: MoveStrategy, time_limit: Duration) -> Improvement {
+        let deadline = Instant::now() + time_limit;
+        let initial_makespan = self.calculate_makespan(schedule);
+        let no_starts = HashMap::new();
+        let mut sequences = self.machine_sequences(schedule);
+        let Some(mut best) = self.schedule_from_sequences(&sequences, &no_starts) else {
+            return Improvement { schedule: schedule.to_vec(), initial_makespan, makespan: initial_makespan, moves: 0, timed_out: false };
+        };
+        let mut makespan = self.calculate_makespan(&best);
+        if makespan > initial_makespan {
+            // Rebuilding only moves operations earlier, unless the schedule ignored a constraint
+            best = schedule.to_vec();
+            makespan = initial_makespan;
+        }
+
+        let neighborhoods: &[MoveStrategy] = match strategy {
+            MoveStrategy::SwapAndInsertion => &[MoveStrategy::Swap, MoveStrategy::Insertion],
+            _ => std::slice::from_ref(&strategy),
+        };
+        let mut moves = 0;
+        let mut timed_out = false;
+        'climb: loop {
+            for &neighborhood in neighborhoods {
+                for machine in 0..sequences.len() {
+                    let len = sequences[machine].len();
+                    for from in 0..len {
+                        for to in 0..len {
+                            let valid = match neighborhood {
+                                MoveStrategy::Swap => from < to,
+                                // Moving one step back is the same as swapping neighbours
+                                _ => from != to && to + 1 != from,
+                            };
+                            if !valid {
+                                continue;
+                            }
+                            if Instant::now() >= deadline {
+                                timed_out = true;
+                                break 'climb;
+                            }
+                            let mut candidate = sequences[machine].clone();
+                            if neighborhood == MoveStrategy::Swap {
+                                candidate.swap(from, to);
+                            } else {
+                                let operation = candidate.remove(from);
+                                candidate.insert(to, operation);
+                            }
+                            let original = std::mem::replace(&mut sequences[machine], candidate);
+                            let rebuilt = self.schedule_from_sequences(&sequences, &no_starts);
+                            if let Some(rebuilt) = rebuilt {
+                                let value = self.calculate_makespan(&rebuilt);
+                                if value < makespan - TIME_EPSILON {
+                                    best = rebuilt;
+                                    makespan = value;
+                                    moves += 1;
+                                    continue 'climb;
+                                }
+                            }
+                            sequences[machine] = original;
+                        }
+                    }
+                }
+            }
+            break;
+        }
+        Improvement { schedule: best, initial_makespan, makespan, moves, timed_out }
+    }
+}
@@ -0,0 +1,438 @@
+//! The instance as a mixed-integer linear program, for Gurobi, CBC, HiGHS or any other
+//! MILP solver.
+//!
+//! The model is the disjunctive formulation of Manne (1960): a start variable
+//! `s_J_O` for operation `O` of job `J`, the makespan `cmax`, and for every two
+//! operations of different jobs that can share a machine `M` a binary `y_J_O_K_P_M`
+//! that is 1 when operation `O` of job `J` comes first. Big-M constraints with a horizon
+//! as long as running everything one after another order the pair either way. Flexible
+//! operations get an assignment binary `x_J_O_M` per eligible machine, and their
+//! ordering constraints only bind when both are assigned to that machine. Release times
+//! are lower bounds, sequence-dependent setups are added to the gap of every ordered
+//! pair and transfer times to the gap between consecutive operations of a job. Machine
+//! downtime, pins, buffering limits, preemption, open shops and soft constraints are
+//! left out; the file header says so when the instance has them.
+//!
+//! `write_milp` writes the CPLEX LP format or free MPS, and `read_milp_solution` reads
+//! the start and assignment values from a solver's solution file (Gurobi `.sol`, CBC
+//! or HiGHS output all list variable names with their values) and times the machine
+//! orders they imply, so `solution_formats::read_solution` can load them.
+//!
+//! ```
+//! use jssp_scheduler::jssp::milp::{read_milp_solution, write_milp, MilpFormat};
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let lp = write_milp(&ft06, MilpFormat::Lp);
+//! assert!(lp.contains("Minimize"));
+//! assert!(lp.contains("Binaries"));
+//! // 6 machines with 6 operations each: 15 ordered pairs per machine
+//! assert_eq!(lp.lines().filter(|line| line.starts_with(" y_")).count(), 6 * 15);
+//! let mps = write_milp(&ft06, MilpFormat::Mps);
+//! assert!(mps.starts_with("NAME") && mps.trim_end().ends_with("ENDATA"));
+//!
+//! // A solution file from a MILP solver, here written from a greedy schedule
+//! let schedule = ft06.solve_greedy();
+//! let sol: String = schedule.iter()
+//!     .map(|op| format!("s_{}_{} {}\n", op.job_id, op.operation_id, op.start_time))
+//!     .collect();
+//! let imported = read_milp_solution(&ft06, &sol).unwrap();
+//! assert_eq!(ft06.calculate_makespan(&imported), ft06.calculate_makespan(&schedule));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::{Buffering, Instance, Operation, Schedule};
+
+/// Longest LP line before a row continues on the next one
+const LP_LINE_WIDTH: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilpFormat {
+    /// CPLEX LP, readable by every common solver
+    Lp,
+    /// Free-format MPS
+    Mps,
+}
+
+impl MilpFormat {
+    pub const ALL: [MilpFormat; 2] = [MilpFormat::Lp, MilpFormat::Mps];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MilpFormat::Lp => "LP",
+            MilpFormat::Mps => "MPS",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MilpFormat::Lp => "lp",
+            MilpFormat::Mps => "mps",
+        }
+    }
+
+    /// The format a file extension stands for, ignoring case
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Sense {
+    AtLeast,
+    Equal,
+}
+
+struct Variable {
+    name: String,
+    binary: bool,
+    lower: f64,
+}
+
+/// `terms` (variable index, coefficient) compared to `rhs`
+struct Row {
+    name: String,
+    terms: Vec<(usize, f64)>,
+    sense: Sense,
+    rhs: f64,
+}
+
+/// Minimize `cmax` subject to `rows`
+struct Model {
+    variables: Vec<Variable>,
+    rows: Vec<Row>,
+    cmax: usize,
+    notes: Vec<String>,
+}
+
+impl Model {
+    fn variable(&mut self, name: String, binary: bool, lower: f64) -> usize {
+        self.variables.push(Variable { name, binary, lower });
+        self.variables.len() - 1
+    }
+
+    fn row(&mut self, name: String, terms: Vec<(usize, f64)>, sense: Sense, rhs: f64) {
+        self.rows.push(Row { name, terms, sense, rhs });
+    }
+}
+
+/// Processing time of an operation: a constant, or a sum over its assignment binaries
+enum Processing {
+    Fixed(f64),
+    Assigned(Vec<(usize, f64)>),
+}
+
+impl Processing {
+    /// Add `sign` times the processing time to `terms`, returning the constant part
+    fn add_to(&self, terms: &mut Vec<(usize, f64)>, sign: f64) -> f64 {
+        match self {
+            Processing::Fixed(duration) => sign * duration,
+            Processing::Assigned(options) => {
+                terms.extend(options.iter().map(|&(variable, duration)| (variable, sign * duration)));
+                0.0
+            }
+        }
+    }
+}
+
+fn build_model(instance: &Instance) -> Model {
+    let jobs = instance.jobs();
+    let mut model = Model { variables: Vec::new(), rows: Vec::new(), cmax: 0, notes: Vec::new() };
+    if !instance.downtime().is_empty() {
+        model.notes.push("machine downtime is not modeled".to_string());
+    }
+    if instance.has_working_hours() {
+        model.notes.push("working hours are not modeled".to_string());
+    }
+    if instance.buffering != Buffering::Unlimited {
+        model.notes.push(format!("{} buffering is not modeled; jobs may wait", instance.buffering.name()));
+    }
+    if instance.is_open_shop() {
+        model.notes.push("the open shop is not modeled; jobs keep their listed order".to_string());
+    }
+    if instance.uses_resources() {
+        model.notes.push("secondary resources are not modeled".to_string());
+    }
+    if instance.uses_pins() {
+        model.notes.push("pinned operations are not fixed; they may move".to_string());
+    }
+    if instance.preemptive {
+        model.notes.push("preemption is not modeled; operations run without interruption".to_string());
+    }
+    if !instance.soft_constraints.is_empty() {
+        model.notes.push("soft constraints are not modeled".to_string());
+    }
+    if instance.transfer_times().is_some() && instance.is_flexible() {
+        model.notes.push("transfers between flexible operations use the shortest eligible route".to_string());
+    }
+
+    let operations = || jobs.iter().flat_map(|job| job.operations.iter().enumerate().map(move |(o, op)| (job, o, op)));
+    let mut max_setup: f64 = 0.0;
+    for machine_id in 0..instance.num_machines() {
+        for to in jobs {
+            max_setup = max_setup.max(instance.setup_time(machine_id, None, to.id));
+            for from in jobs {
+                max_setup = max_setup.max(instance.setup_time(machine_id, Some(from.id), to.id));
+            }
+        }
+    }
+    let max_transfer = instance.transfer_times()
+        .map_or(0.0, |transfers| transfers.times.iter().flatten().copied().fold(0.0, f64::max));
+    let horizon = jobs.iter().map(|job| job.release_time).fold(0.0, f64::max)
+        + operations().map(|(_, _, op)| op.options().map(|option| option.duration).fold(0.0, f64::max) + max_setup + max_transfer).sum::<f64>();
+
+    let mut starts = HashMap::new();
+    let mut processing = HashMap::new();
+    for (job, o, op) in operations() {
+        let initial_setup = if op.alternatives.is_empty() { instance.setup_time(op.machine_id, None, job.id) } else { 0.0 };
+        let start = model.variable(format!("s_{}_{}", job.id, o), false, job.release_time.max(initial_setup));
+        starts.insert((job.id, o), start);
+        let time = if op.alternatives.is_empty() {
+            Processing::Fixed(op.duration)
+        } else {
+            let options: Vec<(usize, f64)> = op.options()
+                .map(|option| (model.variable(format!("x_{}_{}_{}", job.id, o, option.machine_id), true, 0.0), option.duration))
+                .collect();
+            let terms = options.iter().map(|&(variable, _)| (variable, 1.0)).collect();
+            model.row(format!("assign_{}_{}", job.id, o), terms, Sense::Equal, 1.0);
+            Processing::Assigned(options)
+        };
+        processing.insert((job.id, o), time);
+    }
+    model.cmax = model.variable("cmax".to_string(), false, 0.0);
+
+    for job in jobs {
+        for o in 1..=job.operations.len() {
+            // s_next - s_prev - p_prev >= 0, with cmax after the last operation
+            let (name, next) = match o < job.operations.len() {
+                true => (format!("prec_{}_{}", job.id, o), starts[&(job.id, o)]),
+                false => (format!("end_{}", job.id), model.cmax),
+            };
+            let mut terms = vec![(next, 1.0), (starts[&(job.id, o - 1)], -1.0)];
+            let rhs = processing[&(job.id, o - 1)].add_to(&mut terms, -1.0);
+            let transfer = match job.operations.get(o) {
+                Some(op) => shortest_transfer(instance, &job.operations[o - 1], op),
+                None => 0.0,
+            };
+            model.row(name, terms, Sense::AtLeast, transfer - rhs);
+        }
+    }
+
+    for machine_id in 0..instance.num_machines() {
+        // (job, operation, duration, assignment binary if flexible)
+        let eligible: Vec<(usize, usize, f64, Option<usize>)> = operations()
+            .filter_map(|(job, o, op)| {
+                let duration = op.duration_on(machine_id)?;
+                let assignment = match &processing[&(job.id, o)] {
+                    Processing::Assigned(options) => {
+                        let index = op.options().position(|option| option.machine_id == machine_id)?;
+                        Some(options[index].0)
+                    }
+                    Processing::Fixed(_) => None,
+                };
+                Some((job.id, o, duration, assignment))
+            })
+            .collect();
+        for (i, &(job_a, op_a, duration_a, assign_a)) in eligible.iter().enumerate() {
+            for &(job_b, op_b, duration_b, assign_b) in &eligible[i + 1..] {
+                if job_a == job_b {
+                    continue; // ordered by precedence already
+                }
+                let order = model.variable(format!("y_{}_{}_{}_{}_{}", job_a, op_a, job_b, op_b, machine_id), true, 0.0);
+                let (s_a, s_b) = (starts[&(job_a, op_a)], starts[&(job_b, op_b)]);
+                let relax: Vec<(usize, f64)> = assign_a.into_iter().chain(assign_b).map(|x| (x, -horizon)).collect();
+                let relaxed = relax.len() as f64 * horizon;
+
+                // y = 1: b starts after a and the setup between them
+                let mut terms = vec![(s_b, 1.0), (s_a, -1.0), (order, -horizon)];
+                terms.extend(&relax);
+                let gap = duration_a + instance.setup_time(machine_id, Some(job_a), job_b);
+                model.row(format!("ab_{}_{}_{}_{}_{}", job_a, op_a, job_b, op_b, machine_id), terms, Sense::AtLeast, gap - horizon - relaxed);
+
+                // y = 0: a starts after b
+                let mut terms = vec![(s_a, 1.0), (s_b, -1.0), (order, horizon)];
+                terms.extend(&relax);
+                let gap = duration_b + instance.setup_time(machine_id, Some(job_b), job_a);
+                model.row(format!("ba_{}_{}_{}_{}_{}", job_a, op_a, job_b, op_b, machine_id), terms, Sense::AtLeast, gap - relaxed);
+            }
+        }
+    }
+    model
+}
+
+/// Travel time between two consecutive operations of a job; for flexible operations,
+/// the shortest over their eligible machines, which keeps the model a relaxation
+fn shortest_transfer(instance: &Instance, from: &Operation, to: &Operation) -> f64 {
+    from.options()
+        .flat_map(|a| to.options().map(move |b| instance.transfer_time(Some(a.machine_id), b.machine_id)))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// The instance as a disjunctive MILP minimizing the makespan
+pub fn write_milp(instance: &Instance, format: MilpFormat) -> String {
+    let model = build_model(instance);
+    match format {
+        MilpFormat::Lp => write_lp(instance, &model),
+        MilpFormat::Mps => write_mps(instance, &model),
+    }
+}
+
+fn header(instance: &Instance, model: &Model, comment: &str) -> String {
+    let binaries = model.variables.iter().filter(|variable| variable.binary).count();
+    let mut text = format!(
+        "{} Job shop{}: {} jobs, {} machines; disjunctive model with {} variables ({} binary) and {} constraints\n",
+        comment,
+        if instance.name.is_empty() { String::new() } else { format!(" '{}'", instance.name) },
+        instance.jobs().len(),
+        instance.num_machines(),
+        model.variables.len(),
+        binaries,
+        model.rows.len(),
+    );
+    let _ = writeln!(text, "{} s_J_O: start of operation O of job J; x_J_O_M: operation runs on machine M; y_J_O_K_P_M: it precedes operation P of job K on M", comment);
+    for note in &model.notes {
+        let _ = writeln!(text, "{} Note: {}", comment, note);
+    }
+    text
+}
+
+fn write_lp(instance: &Instance, model: &Model) -> String {
+    let mut text = header(instance, model, "\\");
+    let _ = writeln!(text, "Minimize\n makespan: {}\nSubject To", model.variables[model.cmax].name);
+    for row in &model.rows {
+        let mut line = format!(" {}:", row.name);
+        for (index, &(variable, coefficient)) in row.terms.iter().enumerate() {
+            let mut term = match (coefficient < 0.0, index) {
+                (true, _) => " -".to_string(),
+                (false, 0) => String::new(),
+                (false, _) => " +".to_string(),
+            };
+            if coefficient.abs() != 1.0 {
+                let _ = write!(term, " {}", coefficient.abs());
+            }
+            let _ = write!(term, " {}", model.variables[variable].name);
+            if line.len() + term.len() > LP_LINE_WIDTH {
+                text.push_str(&line);
+                text.push('\n');
+                line.clear();
+            }
+            line.push_str(&term);
+        }
+        let sense = if row.sense == Sense::Equal { "=" } else { ">=" };
+        let _ = writeln!(text, "{} {} {}", line, sense, row.rhs);
+    }
+    text.push_str("Bounds\n");
+    for variable in model.variables.iter().filter(|variable| !variable.binary && variable.lower > 0.0) {
+        let _ = writeln!(text, " {} >= {}", variable.name, variable.lower);
+    }
+    text.push_str("Binaries\n");
+    for variable in model.variables.iter().filter(|variable| variable.binary) {
+        let _ = writeln!(text, " {}", variable.name);
+    }
+    text.push_str("End\n");
+    text
+}
+
+fn write_mps(instance: &Instance, model: &Model) -> String {
+    let mut text = format!("NAME jssp\n{}ROWS\n N makespan\n", header(instance, model, "*"));
+    for row in &model.rows {
+        let _ = writeln!(text, " {} {}", if row.sense == Sense::Equal { "E" } else { "G" }, row.name);
+    }
+
+    let mut columns: Vec<Vec<(&str, f64)>> = vec![Vec::new(); model.variables.len()];
+    columns[model.cmax].push(("makespan", 1.0));
+    for row in &model.rows {
+        for &(variable, coefficient) in &row.terms {
+            columns[variable].push((&row.name, coefficient));
+        }
+    }
+    text.push_str("COLUMNS\n");
+    for (variable, entries) in model.variables.iter().zip(&columns) {
+        for (row, coefficient) in entries {
+            let _ = writeln!(text, " {} {} {}", variable.name, row, coefficient);
+        }
+    }
+    text.push_str("RHS\n");
+    for row in model.rows.iter().filter(|row| row.rhs != 0.0) {
+        let _ = writeln!(text, " RHS {} {}", row.name, row.rhs);
+    }
+    text.push_str("BOUNDS\n");
+    for variable in &model.variables {
+        if variable.binary {
+            let _ = writeln!(text, " BV BND {}", variable.name);
+        } else if variable.lower > 0.0 {
+            let _ = writeln!(text, " LO BND {} {}", variable.name, variable.lower);
+        }
+    }
+    text.push_str("ENDATA\n");
+    text
+}
+
+/// `(job, operation)` of a start variable name `s_J_O`
+fn parse_start(name: &str) -> Option<(usize, usize)> {
+    let (job, operation) = name.strip_prefix("s_")?.split_once('_')?;
+    Some((job.parse().ok()?, operation.parse().ok()?))
+}
+
+/// `(job, operation, machine)` of an assignment variable name `x_J_O_M`
+fn parse_assignment(name: &str) -> Option<(usize, usize, usize)> {
+    let mut parts = name.strip_prefix("x_")?.split('_').map(|part| part.parse().ok());
+    let ids = (parts.next()??, parts.next()??, parts.next()??);
+    parts.next().is_none().then_some(ids)
+}
+
+/// Whether `text` looks like a solution of the model `write_milp` writes
+pub fn is_milp_solution(text: &str) -> bool {
+    text.split_whitespace().any(|token| parse_start(token).is_some())
+}
+
+/// A schedule from the variable values in a MILP solver's solution file. Each line
+/// naming a start or assignment variable is read with the first number after the name;
+/// machine orders follow the start times and are timed again, so the schedule is
+/// feasible even where the model left out downtime.
+pub fn read_milp_solution(instance: &Instance, text: &str) -> Result<Schedule, String> {
+    let mut starts: HashMap<(usize, usize), f64> = HashMap::new();
+    let mut assigned: HashMap<(usize, usize), usize> = HashMap::new();
+    for line in text.lines() {
+        let mut tokens = line.split(|c: char| c.is_whitespace() || c == '=').filter(|token| !token.is_empty());
+        let Some(name) = tokens.by_ref().find(|token| parse_start(token).is_some() || parse_assignment(token).is_some()) else {
+            continue;
+        };
+        let Some(value) = tokens.find_map(|token| token.parse::<f64>().ok()) else {
+            continue;
+        };
+        if let Some(operation) = parse_start(name) {
+            starts.insert(operation, value);
+        } else if let Some((job_id, operation_id, machine_id)) = parse_assignment(name) {
+            if value > 0.5 {
+                assigned.insert((job_id, operation_id), machine_id);
+            }
+        }
+    }
+
+    let mut sequences: Vec<Vec<(usize, usize)>> = vec![Vec::new(); instance.num_machines()];
+    for job in instance.jobs() {
+        for (operation_id, op) in job.operations.iter().enumerate() {
+            let key = (job.id, operation_id);
+            if !starts.contains_key(&key) {
+                return Err(format!("The solution has no start for operation {} of job {}", operation_id, job.id));
+            }
+            let machine_id = match assigned.get(&key) {
+                Some(&machine_id) => machine_id,
+                None if op.alternatives.is_empty() => op.machine_id,
+                None => return Err(format!("The solution assigns operation {} of job {} to no machine", operation_id, job.id)),
+            };
+            if op.duration_on(machine_id).is_none() {
+                return Err(format!("Operation {} of job {} cannot run on machine {}", operation_id, job.id, machine_id));
+            }
+            sequences[machine_id].push(key);
+        }
+    }
+    for sequence in &mut sequences {
+        sequence.sort_by(|a, b| starts[a].total_cmp(&starts[b]).then(a.cmp(b)));
+    }
+    instance.schedule_from_sequences(&sequences, &starts)
+        .ok_or_else(|| "The start times contradict the job order".to_string())
+}
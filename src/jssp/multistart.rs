@@ -0,0 +1,162 @@
+//! Independent runs of a stochastic solver spread over CPU cores, keeping the best.
+//!
+//! Each run aims at the configured objective (makespan by default, see
+//! `Solver::solve_objective`) and runs are ranked by it. Run `k` is seeded
+//! with `seed + k`, and ties go to the lowest run, so the result depends only on the
+//! configuration and not on the thread count. A run that fails (see
+//! `Solver::try_solve_objective`) counts as infeasible, and its error is kept.
+//!
+//! ```
+//! use jssp_scheduler::jssp::multistart::{multi_start, MultiStartConfig};
+//! use jssp_scheduler::jssp::solvers::RandomizedGreedy;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let config = MultiStartConfig { starts: 8, threads: 2, seed: 1, ..Default::default() };
+//! let result = multi_start(&RandomizedGreedy, &ft06, &config);
+//! assert_eq!(result.values.len(), 8);
+//! assert_eq!(result.best_value, ft06.calculate_makespan(&result.best));
+//! assert!(result.errors.is_empty());
+//! ```
+
+use rayon::prelude::*;
+
+use super::{Instance, JsspError, Objective, Schedule, Solver, SolverInfo};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiStartConfig {
+    /// Number of independent runs
+    pub starts: usize,
+    /// Worker threads; 0 uses every available core
+    pub threads: usize,
+    /// Seed of the first run
+    pub seed: u64,
+    /// What the best run minimizes
+    pub objective: Objective,
+}
+
+impl Default for MultiStartConfig {
+    fn default() -> Self {
+        Self { starts: 16, threads: 0, seed: 0, objective: Objective::Makespan }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MultiStartResult {
+    pub best: Schedule,
+    /// Objective value of `best`
+    pub best_value: f64,
+    /// Objective value of every run, in run order; infinite for failed runs
+    pub values: Vec<f64>,
+    /// Why runs failed, in run order
+    pub errors: Vec<String>,
+}
+
+impl MultiStartResult {
+    /// The first error, if every run failed
+    pub fn failure(&self) -> Option<&str> {
+        self.errors.first().filter(|_| self.errors.len() == self.values.len()).map(String::as_str)
+    }
+}
+
+/// Run `solver` `config.starts` times (at least once) in parallel and return the
+/// schedule with the smallest objective value. Infeasible runs are only kept if no run is feasible.
+pub fn multi_start(solver: &dyn Solver, instance: &Instance, config: &MultiStartConfig) -> MultiStartResult {
+    multi_start_with_progress(solver, instance, config, &|_, _| {})
+}
+
+/// Like `multi_start`, calling `progress` with each successful run's schedule and
+/// objective value as it finishes. Runs finish in any order and the callback may be called from several
+/// threads at once.
+pub fn multi_start_with_progress(
+    solver: &dyn Solver,
+    instance: &Instance,
+    config: &MultiStartConfig,
+    progress: &(dyn Fn(&Schedule, f64) + Sync),
+) -> MultiStartResult {
+    let run = |k: usize| match solver.try_solve_objective(instance, config.objective, config.seed.wrapping_add(k as u64)) {
+        Ok(schedule) => {
+            let value = instance.evaluate(config.objective, &schedule);
+            let feasible = instance.validate(&schedule).is_ok();
+            progress(&schedule, value);
+            (schedule, value, feasible, None)
+        }
+        Err(e) => (Schedule::new(), f64::INFINITY, false, Some(e.to_string())),
+    };
+    let runs: Vec<(Schedule, f64, bool, Option<String>)> = match rayon::ThreadPoolBuilder::new().num_threads(config.threads).build() {
+        Ok(pool) => pool.install(|| (0..config.starts.max(1)).into_par_iter().map(run).collect()),
+        Err(_) => (0..config.starts.max(1)).map(run).collect(),
+    };
+
+    let values = runs.iter().map(|(_, value, _, _)| *value).collect();
+    let errors = runs.iter().filter_map(|(_, _, _, error)| error.clone()).collect();
+    let (best, best_value, _, _) = runs
+        .into_iter()
+        .reduce(|best, candidate| {
+            // Feasible first, then lower; the earlier run wins ties
+            if (candidate.2, -candidate.1) > (best.2, -best.1) { candidate } else { best }
+        })
+        .unwrap();
+    MultiStartResult { best, best_value, values, errors }
+}
+
+/// A solver that runs another one several times in parallel, so multi-start can be
+/// used anywhere a `Solver` is expected (benchmarks, batch solving)
+pub struct MultiStart<S> {
+    pub solver: S,
+    pub config: MultiStartConfig,
+    name: String,
+}
+
+impl<S: Solver> MultiStart<S> {
+    pub fn new(solver: S, config: MultiStartConfig) -> Self {
+        let name = format!("{} (best of {})", solver.name(), config.starts.max(1));
+        Self { solver, config, name }
+    }
+}
+
+impl<S: Solver> Solver for MultiStart<S> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "Several independent runs on all cores, keeping the best schedule"
+    }
+
+    fn info(&self) -> SolverInfo {
+        self.solver.info()
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_seeded(instance, rand::random())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        self.solver.is_stochastic()
+    }
+
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        let config = MultiStartConfig { seed, ..self.config.clone() };
+        multi_start(&self.solver, instance, &config).best
+    }
+
+    fn solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Schedule {
+        let config = MultiStartConfig { seed, objective, ..self.config.clone() };
+        multi_start(&self.solver, instance, &config).best
+    }
+
+    /// Fails only if every run failed
+    fn try_solve_seeded(&self, instance: &Instance, seed: u64) -> Result<Schedule, JsspError> {
+        self.try_solve_objective(instance, self.config.objective, seed)
+    }
+
+    fn try_solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Result<Schedule, JsspError> {
+        let config = MultiStartConfig { seed, objective, ..self.config.clone() };
+        let result = multi_start(&self.solver, instance, &config);
+        match result.failure() {
+            Some(error) => Err(JsspError::Process(error.to_string())),
+            None => Ok(result.best),
+        }
+    }
+}
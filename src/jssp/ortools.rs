@@ -0,0 +1,422 @@
+//! Reading and writing the JSON layout of Google OR-Tools' job shop examples.
+//!
+//! An instance is the `jobs_data` list of the examples: one list of tasks per job,
+//! where a task is `[machine, duration]`. Tasks of the flexible job shop example
+//! are lists of `[duration, machine]` alternatives instead. Both a bare list and an
+//! object with a `jobs_data` key are accepted:
+//!
+//! ```json
+//! {
+//!   "jobs_data": [[[0, 3], [1, 2], [2, 2]], [[0, 2], [2, 1], [1, 4]], [[1, 4], [2, 3]]],
+//!   "machines_count": 3,
+//!   "horizon": 21,
+//!   "solution": {
+//!     "makespan": 11,
+//!     "assigned_jobs": [[{ "start": 0, "job": 0, "index": 0, "duration": 3 }], ...]
+//!   }
+//! }
+//! ```
+//!
+//! `solution.assigned_jobs` lists the tasks on every machine in start order, with the
+//! same field names as the examples' `assigned_task_type`. An optional `name` holds the
+//! instance name, an optional `downtime` lists `[machine, start, end]` windows in
+//! which a machine is unavailable, and an optional `job_colors` object maps job indices
+//! to `"#rrggbb"` display colors. Optional `job_names` and `machine_names` lists give
+//! jobs and machines readable names, such as `"Order #4711"` or `"CNC-2"`. An optional
+//! `buffering` of `"no_wait"` or `"blocking"` marks a job shop without buffers (see
+//! `buffering`), and `"preemptive": true` one in which downtime interrupts operations
+//! (see `preemption`). An optional `problem_type` of `"flow_shop"` or `"open_shop"`
+//! marks a flow shop or an open shop, whose task lists are then routes or sets (see
+//! `shop`). An optional `transfer_times` matrix gives the time a job needs
+//! to travel from the machine of its row to the machine of its column. Optional
+//! `resources` (`{ "name": "Operators", "capacity": 2 }`) are secondary resources, and
+//! `resource_requirements` lists the `[job, index, resource]` tasks that need one (see
+//! `resources`). `duration_std_devs` lists `[job, index, std_dev]` for tasks whose
+//! duration is uncertain, sampled in `scenarios` scenarios (20 if not given) from
+//! `scenario_seed` (see `stochastic`). Optional `release_dates`, `due_dates` and
+//! `weights` list every job's release date, due date (`null` for none) and weight (1 if
+//! not given), and `setup_times` holds a `[from job][to job]` matrix per machine (see
+//! `SetupTimes`).
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::resources::{ResourceRequirement, SecondaryResource};
+use super::stochastic::{DurationSpread, DEFAULT_SCENARIOS};
+use super::{Buffering, Downtime, Instance, Job, JsspError, MachineOption, Operation, ProblemType, ScheduledOperation, SetupTimes, TransferTimes};
+
+/// Most machines an instance may have. The machine count follows the largest machine
+/// id, and a few tables hold a value per machine, so a stray id must not size them.
+const MAX_MACHINES: usize = 1_000_000;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Task {
+    Single(usize, f64),
+    /// (duration, machine) alternatives, as in the flexible job shop example
+    Flexible(Vec<(f64, usize)>),
+}
+
+#[derive(Deserialize)]
+struct AssignedTask {
+    start: f64,
+    job: usize,
+    index: usize,
+    duration: f64,
+}
+
+#[derive(Deserialize)]
+struct Solution {
+    assigned_jobs: Vec<Vec<AssignedTask>>,
+}
+
+impl Solution {
+    /// The tasks as operations of `instance`, whose downtime interrupts them if it is
+    /// preemptive
+    fn into_schedule(self, instance: &Instance) -> Vec<ScheduledOperation> {
+        let mut schedule = Vec::new();
+        for (machine_id, tasks) in self.assigned_jobs.into_iter().enumerate() {
+            for task in tasks {
+                schedule.push(instance.timed_operation(task.job, task.index, machine_id, task.start, task.duration, 0.0));
+            }
+        }
+        schedule.sort_by_key(|op| (op.job_id, op.operation_id));
+        schedule
+    }
+}
+
+/// The schedule of an OR-Tools `solution` object, which lists `assigned_jobs` per machine
+pub(super) fn schedule_from_solution(instance: &Instance, solution: &Value) -> Result<Vec<ScheduledOperation>, String> {
+    Solution::deserialize(solution)
+        .map(|solution| solution.into_schedule(instance))
+        .map_err(|e| format!("Not an OR-Tools solution: {}", e))
+}
+
+#[derive(Deserialize, Default)]
+struct InstanceDocument {
+    #[serde(default)]
+    name: Option<String>,
+    jobs_data: Vec<Vec<Task>>,
+    #[serde(default)]
+    machines_count: Option<usize>,
+    #[serde(default)]
+    solution: Option<Solution>,
+    /// (machine, start, end) windows
+    #[serde(default)]
+    downtime: Vec<(usize, f64, f64)>,
+    /// "#rrggbb" by job index; the keys stay strings, as the untagged `Document` cannot
+    /// read numbers from them
+    #[serde(default)]
+    job_colors: BTreeMap<String, String>,
+    #[serde(default)]
+    buffering: Buffering,
+    #[serde(default)]
+    preemptive: bool,
+    #[serde(default)]
+    problem_type: ProblemType,
+    #[serde(default)]
+    job_names: Vec<String>,
+    #[serde(default)]
+    machine_names: Vec<String>,
+    /// Travel time from the machine of each row to the machine of each column
+    #[serde(default)]
+    transfer_times: Option<Vec<Vec<f64>>>,
+    #[serde(default)]
+    resources: Vec<SecondaryResource>,
+    /// (job, task index, resource) of every task that needs a secondary resource
+    #[serde(default)]
+    resource_requirements: Vec<(usize, usize, usize)>,
+    /// (job, task index, standard deviation) of every task with an uncertain duration
+    #[serde(default)]
+    duration_std_devs: Vec<(usize, usize, f64)>,
+    #[serde(default)]
+    scenarios: Option<usize>,
+    #[serde(default)]
+    scenario_seed: u64,
+    /// Release date of every job
+    #[serde(default)]
+    release_dates: Vec<f64>,
+    /// Due date of every job, if it has one
+    #[serde(default)]
+    due_dates: Vec<Option<f64>>,
+    /// Weight of every job in weighted objectives and dispatching
+    #[serde(default)]
+    weights: Vec<f64>,
+    /// Setup from the job of each row to the job of each column, per machine
+    #[serde(default)]
+    setup_times: Option<Vec<Vec<Vec<f64>>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Document {
+    Instance(Box<InstanceDocument>),
+    JobsData(Vec<Vec<Task>>),
+}
+
+/// Write whole numbers as JSON integers, since CP-SAT only accepts integer durations
+pub(super) fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+/// `[r, g, b]` of a "#rrggbb" color
+fn parse_color(text: &str) -> Option<[u8; 3]> {
+    let digits = text.strip_prefix('#').filter(|digits| digits.len() == 6 && digits.is_ascii())?;
+    let channel = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Parse an OR-Tools style instance, plus the schedule if the file carries a solution.
+/// The machine count is `machines_count` or one past the largest machine id, whichever
+/// is larger, and at most a million.
+///
+/// ```
+/// use jssp_scheduler::jssp::ortools::from_ortools_json;
+///
+/// let (instance, _) = from_ortools_json("[[[0, 3], [2, 2]]]").unwrap();
+/// assert_eq!(instance.num_machines(), 3);
+/// assert!(from_ortools_json("[[[18446744073709551615, 3]]]").is_err());
+/// ```
+pub fn from_ortools_json(text: &str) -> Result<(Instance, Option<Vec<ScheduledOperation>>), JsspError> {
+    parse_ortools_json(text).map_err(JsspError::Parse)
+}
+
+fn parse_ortools_json(text: &str) -> Result<(Instance, Option<Vec<ScheduledOperation>>), String> {
+    let document: Document = serde_json::from_str(text)
+        .map_err(|e| format!("Not an OR-Tools job shop JSON file: {}", e))?;
+    let document = match document {
+        Document::Instance(instance) => *instance,
+        Document::JobsData(jobs_data) => InstanceDocument { jobs_data, ..InstanceDocument::default() },
+    };
+
+    let mut num_machines = document.machines_count.unwrap_or(0);
+    if num_machines > MAX_MACHINES {
+        return Err(format!("machines_count {} is above the supported {}", num_machines, MAX_MACHINES));
+    }
+    let num_jobs = document.jobs_data.len();
+    let counts = [
+        ("release_dates", document.release_dates.len()),
+        ("due_dates", document.due_dates.len()),
+        ("weights", document.weights.len()),
+    ];
+    for (field, count) in counts {
+        if count != 0 && count != num_jobs {
+            return Err(format!("{} lists {} jobs, but there are {}", field, count, num_jobs));
+        }
+    }
+    let mut jobs = Vec::with_capacity(num_jobs);
+    for (job_id, tasks) in document.jobs_data.into_iter().enumerate() {
+        let mut operations = Vec::with_capacity(tasks.len());
+        for (operation_id, task) in tasks.into_iter().enumerate() {
+            let alternatives: Vec<MachineOption> = match task {
+                Task::Single(machine_id, duration) => vec![MachineOption { machine_id, duration }],
+                Task::Flexible(options) => options.into_iter()
+                    .map(|(duration, machine_id)| MachineOption { machine_id, duration })
+                    .collect(),
+            };
+            let Some(&first) = alternatives.first() else {
+                return Err(format!("Job {} task {} has no machine", job_id, operation_id));
+            };
+            let last_machine = alternatives.iter().map(|o| o.machine_id).max().unwrap_or(0);
+            if last_machine >= MAX_MACHINES {
+                return Err(format!("Job {} task {} uses machine {}, above the supported {}", job_id, operation_id, last_machine, MAX_MACHINES - 1));
+            }
+            num_machines = num_machines.max(last_machine + 1);
+
+            operations.push(Operation {
+                job_id,
+                operation_id,
+                machine_id: first.machine_id,
+                duration: first.duration,
+                alternatives: alternatives[1..].to_vec(),
+            });
+        }
+        let release_time = document.release_dates.get(job_id).copied().unwrap_or(0.0);
+        let due_date = document.due_dates.get(job_id).copied().flatten();
+        let weight = document.weights.get(job_id).copied().unwrap_or(1.0);
+        jobs.push(Job { id: job_id, operations, release_time, due_date, weight });
+    }
+    let mut instance = Instance::new(jobs, num_machines)?;
+    instance.name = document.name.unwrap_or_default();
+    instance.buffering = document.buffering;
+    instance.preemptive = document.preemptive;
+    instance.set_problem_type(document.problem_type)?;
+    instance.set_job_names(document.job_names)?;
+    instance.set_machine_names(document.machine_names)?;
+    instance.set_downtime(
+        document.downtime.into_iter().map(|(machine_id, start, end)| Downtime { machine_id, start, end }).collect(),
+    )?;
+    instance.set_setup_times(document.setup_times.map(|times| SetupTimes { times }))?;
+    instance.set_transfer_times(document.transfer_times.map(|times| TransferTimes { times }))?;
+    instance.set_resources(
+        document.resources,
+        document.resource_requirements.into_iter()
+            .map(|(job_id, operation_id, resource_id)| ResourceRequirement { job_id, operation_id, resource_id })
+            .collect(),
+    )?;
+    instance.set_duration_spreads(
+        document.duration_std_devs.into_iter()
+            .map(|(job_id, operation_id, std_dev)| DurationSpread { job_id, operation_id, std_dev })
+            .collect(),
+        document.scenarios.unwrap_or(DEFAULT_SCENARIOS),
+        document.scenario_seed,
+    )?;
+    for (job, color) in document.job_colors {
+        let job_id: usize = job.parse().map_err(|_| format!("Color given for \"{}\", which is not a job index", job))?;
+        if job_id >= instance.jobs().len() {
+            return Err(format!("Color given for job {}, but there are only {} jobs", job_id, instance.jobs().len()));
+        }
+        let rgb = parse_color(&color).ok_or_else(|| format!("Color of job {} is not \"#rrggbb\": {}", job_id, color))?;
+        instance.job_colors.insert(job_id, rgb);
+    }
+
+    let schedule = document.solution.map(|solution| solution.into_schedule(&instance));
+    Ok((instance, schedule))
+}
+
+/// The instance in OR-Tools' `jobs_data` layout, with the schedule as `solution` if given
+///
+/// ```
+/// use jssp_scheduler::jssp::ortools::{from_ortools_json, to_ortools_json};
+///
+/// let text = r#"{
+///     "jobs_data": [[[0, 3], [1, 2]], [[1, 4], [0, 1]]],
+///     "release_dates": [0, 5],
+///     "due_dates": [null, 12],
+///     "weights": [1, 2.5],
+///     "setup_times": [[[0, 1], [2, 0]], [[0, 1], [1, 0]]]
+/// }"#;
+/// let (instance, _) = from_ortools_json(text).unwrap();
+/// assert_eq!(instance.jobs()[1].release_time, 5.0);
+/// assert_eq!(instance.jobs()[1].due_date, Some(12.0));
+/// assert_eq!(instance.jobs()[1].weight, 2.5);
+///
+/// let written = to_ortools_json(&instance, None);
+/// assert_eq!(written["due_dates"], serde_json::json!([null, 12]));
+/// let (reread, _) = from_ortools_json(&written.to_string()).unwrap();
+/// assert_eq!(reread.fingerprint(), instance.fingerprint());
+/// ```
+pub fn to_ortools_json(instance: &Instance, schedule: Option<&[ScheduledOperation]>) -> Value {
+    let jobs_data: Vec<Value> = instance.jobs().iter()
+        .map(|job| {
+            job.operations.iter()
+                .map(|op| if op.is_flexible() {
+                    Value::Array(op.options().map(|o| json!([number(o.duration), o.machine_id])).collect())
+                } else {
+                    json!([op.machine_id, number(op.duration)])
+                })
+                .collect()
+        })
+        .collect();
+    // Upper bound on the makespan, computed like the examples do; with downtime and
+    // release dates, every operation can still run one after another, each after the
+    // longest setup, once the last window is over and every job is released
+    let last_window_end = instance.unavailable().iter().map(|window| window.end).fold(0.0, f64::max);
+    let last_release = instance.jobs().iter().map(|job| job.release_time).fold(0.0, f64::max);
+    let longest_setup = instance.setup_times().map_or(0.0, |setups| setups.times.iter().flatten().flatten().copied().fold(0.0, f64::max));
+    let horizon: f64 = instance.jobs().iter()
+        .flat_map(|job| &job.operations)
+        .map(|op| longest_setup + op.options().map(|o| o.duration).fold(0.0, f64::max))
+        .fold(last_window_end.max(last_release), |total, duration| total + duration);
+
+    let mut document = json!({
+        "jobs_data": jobs_data,
+        "machines_count": instance.num_machines(),
+        "horizon": number(horizon),
+    });
+    if !instance.name.is_empty() {
+        document["name"] = json!(instance.name);
+    }
+    if !instance.downtime().is_empty() {
+        document["downtime"] = instance.downtime().iter()
+            .map(|window| json!([window.machine_id, number(window.start), number(window.end)]))
+            .collect();
+    }
+
+    if instance.jobs().iter().any(|job| job.release_time > 0.0) {
+        document["release_dates"] = instance.jobs().iter().map(|job| number(job.release_time)).collect();
+    }
+    if instance.jobs().iter().any(|job| job.due_date.is_some()) {
+        document["due_dates"] = instance.jobs().iter().map(|job| job.due_date.map_or(Value::Null, number)).collect();
+    }
+    if instance.jobs().iter().any(|job| job.weight != 1.0) {
+        document["weights"] = instance.jobs().iter().map(|job| number(job.weight)).collect();
+    }
+    if let Some(setups) = instance.setup_times() {
+        document["setup_times"] = setups.times.iter()
+            .map(|matrix| matrix.iter().map(|row| row.iter().map(|&time| number(time)).collect::<Value>()).collect::<Value>())
+            .collect();
+    }
+
+    if let Some(transfers) = instance.transfer_times() {
+        document["transfer_times"] = transfers.times.iter()
+            .map(|row| row.iter().map(|&time| number(time)).collect::<Value>())
+            .collect();
+    }
+
+    if !instance.resources().is_empty() {
+        document["resources"] = json!(instance.resources());
+        document["resource_requirements"] = instance.resource_requirements().iter()
+            .map(|requirement| json!([requirement.job_id, requirement.operation_id, requirement.resource_id]))
+            .collect();
+    }
+
+    if instance.is_stochastic() {
+        document["duration_std_devs"] = instance.duration_spreads().iter()
+            .map(|spread| json!([spread.job_id, spread.operation_id, number(spread.std_dev)]))
+            .collect();
+        document["scenarios"] = json!(instance.scenario_count());
+        document["scenario_seed"] = json!(instance.scenario_seed());
+    }
+
+    if instance.buffering != Buffering::Unlimited {
+        document["buffering"] = json!(instance.buffering);
+    }
+    if instance.preemptive {
+        document["preemptive"] = json!(true);
+    }
+    if instance.problem_type() != ProblemType::JobShop {
+        document["problem_type"] = json!(instance.problem_type());
+    }
+
+    if !instance.job_names().is_empty() {
+        document["job_names"] = json!(instance.job_names());
+    }
+    if !instance.machine_names().is_empty() {
+        document["machine_names"] = json!(instance.machine_names());
+    }
+
+    if !instance.job_colors.is_empty() {
+        document["job_colors"] = instance.job_colors.iter()
+            .map(|(job_id, [r, g, b])| (job_id.to_string(), json!(format!("#{:02x}{:02x}{:02x}", r, g, b))))
+            .collect();
+    }
+
+    if let Some(schedule) = schedule {
+        let assigned_jobs: Vec<Value> = instance.machine_sequences(schedule).iter()
+            .map(|sequence| {
+                sequence.iter()
+                    .filter_map(|key| schedule.iter().find(|op| (op.job_id, op.operation_id) == *key))
+                    .map(|op| json!({
+                        "start": number(op.start_time),
+                        "job": op.job_id,
+                        "index": op.operation_id,
+                        "duration": number(op.duration),
+                    }))
+                    .collect()
+            })
+            .collect();
+        document["solution"] = json!({
+            "makespan": number(instance.calculate_makespan(schedule)),
+            "assigned_jobs": assigned_jobs,
+        });
+    }
+
+    document
+}
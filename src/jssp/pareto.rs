@@ -0,0 +1,201 @@
+//! Multi-objective exploration with NSGA-II over operation-based sequences.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::{Instance, Objective, ScheduledOperation};
+
+/// Parameters of an NSGA-II run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParetoConfig {
+    /// Objectives to minimize simultaneously (two or three make sense for plotting)
+    pub objectives: Vec<Objective>,
+    pub population_size: usize,
+    pub generations: usize,
+    pub mutation_rate: f64,
+    pub seed: u64,
+}
+
+impl Default for ParetoConfig {
+    fn default() -> Self {
+        Self {
+            objectives: vec![Objective::Makespan, Objective::TotalTardiness],
+            population_size: 40,
+            generations: 60,
+            mutation_rate: 0.2,
+            seed: 42,
+        }
+    }
+}
+
+/// A non-dominated schedule together with its objective values
+#[derive(Debug, Clone)]
+pub struct ParetoSolution {
+    /// Objective values in the order of `ParetoConfig::objectives`
+    pub values: Vec<f64>,
+    pub sequence: Vec<usize>,
+    pub schedule: Vec<ScheduledOperation>,
+}
+
+/// The first non-dominated front found by NSGA-II, sorted by the first objective
+#[derive(Debug, Clone)]
+pub struct ParetoFront {
+    pub objectives: Vec<Objective>,
+    pub solutions: Vec<ParetoSolution>,
+}
+
+struct Individual {
+    sequence: Vec<usize>,
+    values: Vec<f64>,
+    rank: usize,
+    crowding: f64,
+}
+
+/// `a` dominates `b` if it is no worse in every objective and better in at least one
+pub fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// Run NSGA-II and return the non-dominated solutions of the final population
+pub fn explore_pareto_front(instance: &Instance, config: &ParetoConfig) -> ParetoFront {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let population_size = config.population_size.max(4);
+
+    let evaluate = |sequence: Vec<usize>| {
+        let schedule = instance.decode(&sequence);
+        let values = config.objectives.iter()
+            .map(|&objective| instance.evaluate(objective, &schedule))
+            .collect();
+        Individual { sequence, values, rank: 0, crowding: 0.0 }
+    };
+
+    // Seed with the deterministic greedy order plus random interleavings
+    let base = instance.job_by_job_sequence();
+    let mut population: Vec<Individual> = Vec::with_capacity(population_size * 2);
+    population.push(evaluate(base.clone()));
+    while population.len() < population_size {
+        let mut sequence = base.clone();
+        sequence.shuffle(&mut rng);
+        population.push(evaluate(sequence));
+    }
+    assign_rank_and_crowding(&mut population);
+
+    for _ in 0..config.generations {
+        let mut offspring = Vec::with_capacity(population_size);
+        while offspring.len() < population_size {
+            let a = tournament(&population, &mut rng);
+            let b = tournament(&population, &mut rng);
+            let mut child = precedence_preserving_crossover(&population[a].sequence, &population[b].sequence, instance.jobs().len(), &mut rng);
+            if rng.gen_bool(config.mutation_rate.clamp(0.0, 1.0)) && child.len() > 1 {
+                let i = rng.gen_range(0..child.len());
+                let j = rng.gen_range(0..child.len());
+                child.swap(i, j);
+            }
+            offspring.push(evaluate(child));
+        }
+
+        population.extend(offspring);
+        assign_rank_and_crowding(&mut population);
+        population.sort_by(|a, b| a.rank.cmp(&b.rank).then(b.crowding.total_cmp(&a.crowding)));
+        population.truncate(population_size);
+    }
+
+    // Collect the first front, dropping solutions with identical objective values
+    let mut solutions: Vec<ParetoSolution> = Vec::new();
+    for individual in population.into_iter().filter(|i| i.rank == 0) {
+        if solutions.iter().any(|s| s.values == individual.values) {
+            continue;
+        }
+        solutions.push(ParetoSolution {
+            schedule: instance.decode(&individual.sequence),
+            values: individual.values,
+            sequence: individual.sequence,
+        });
+    }
+    solutions.sort_by(|a, b| a.values[0].total_cmp(&b.values[0]));
+
+    ParetoFront { objectives: config.objectives.clone(), solutions }
+}
+
+/// Binary tournament on (rank, crowding distance)
+fn tournament(population: &[Individual], rng: &mut StdRng) -> usize {
+    let a = rng.gen_range(0..population.len());
+    let b = rng.gen_range(0..population.len());
+    let (x, y) = (&population[a], &population[b]);
+    if x.rank < y.rank || (x.rank == y.rank && x.crowding > y.crowding) {
+        a
+    } else {
+        b
+    }
+}
+
+/// POX: genes of a random job subset keep their positions from the first parent,
+/// the remaining positions are filled with the other jobs in the second parent's order
+pub(super) fn precedence_preserving_crossover(first: &[usize], second: &[usize], num_jobs: usize, rng: &mut StdRng) -> Vec<usize> {
+    let keep: Vec<bool> = (0..num_jobs).map(|_| rng.gen_bool(0.5)).collect();
+    let mut fill = second.iter().copied().filter(|&job| !keep[job]);
+    first.iter()
+        .map(|&job| if keep[job] { job } else { fill.next().unwrap() })
+        .collect()
+}
+
+/// Fast non-dominated sorting followed by crowding distance per front
+fn assign_rank_and_crowding(population: &mut [Individual]) {
+    let n = population.len();
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count = vec![0usize; n];
+    let mut fronts: Vec<Vec<usize>> = vec![Vec::new()];
+
+    for p in 0..n {
+        for q in 0..n {
+            if dominates(&population[p].values, &population[q].values) {
+                dominated_by[p].push(q);
+            } else if dominates(&population[q].values, &population[p].values) {
+                domination_count[p] += 1;
+            }
+        }
+        if domination_count[p] == 0 {
+            population[p].rank = 0;
+            fronts[0].push(p);
+        }
+    }
+
+    let mut current = 0;
+    while !fronts[current].is_empty() {
+        let mut next = Vec::new();
+        for &p in &fronts[current] {
+            for &q in &dominated_by[p] {
+                domination_count[q] -= 1;
+                if domination_count[q] == 0 {
+                    population[q].rank = current + 1;
+                    next.push(q);
+                }
+            }
+        }
+        fronts.push(next);
+        current += 1;
+    }
+
+    let num_objectives = population.first().map_or(0, |i| i.values.len());
+    for front in fronts.iter().filter(|f| !f.is_empty()) {
+        for &i in front {
+            population[i].crowding = 0.0;
+        }
+        for m in 0..num_objectives {
+            let mut sorted = front.clone();
+            sorted.sort_by(|&a, &b| population[a].values[m].total_cmp(&population[b].values[m]));
+            let min = population[sorted[0]].values[m];
+            let max = population[*sorted.last().unwrap()].values[m];
+            population[sorted[0]].crowding = f64::INFINITY;
+            population[*sorted.last().unwrap()].crowding = f64::INFINITY;
+            if max - min <= f64::EPSILON {
+                continue;
+            }
+            for k in 1..sorted.len().saturating_sub(1) {
+                let gap = population[sorted[k + 1]].values[m] - population[sorted[k - 1]].values[m];
+                population[sorted[k]].crowding += gap / (max - min);
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Pinned operations: operations held on their machine at their start time, e.g. because
+//! they are already physically committed on the shop floor.
+//!
+//! A pin is the operation as it was scheduled. Pinning an operation also pins the
+//! earlier operations of its job, which have to be done by then anyway, so the pinned
+//! operations of a job are always its first ones and together admit a schedule.
+//!
+//! The time a pinned operation holds its machine is blocked for every other operation,
+//! like downtime, widened by the longest setup into and out of the pinned job on that
+//! machine so that whichever operations end up next to it have room for their setups.
+//! Every solver therefore plans around the pins. `Instance::decode`, `decode_active` and
+//! `Instance::schedule_from_sequences`, and with them local search (`Instance::improve`)
+//! and moving operations by hand, keep pinned operations exactly where they are pinned;
+//! `Instance::hold_pins` re-times the schedule of any other algorithm the same way, with
+//! its machine orders, as simulated annealing and the memetic algorithm do with the
+//! schedules of their swap search. `Instance::validate` reports a pinned operation that moved.
+//!
+//! Pins apply with unlimited buffers, without preemption and outside open shops; otherwise
+//! they are kept but ignored.
+//!
+//! ```
+//! use std::time::Duration;
+//! use jssp_scheduler::jssp::local_search::MoveStrategy;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance;
+//! let schedule = ft06.solve_greedy();
+//! // Job 2 has started its third operation: the first three stay where they are
+//! assert_eq!(ft06.pin_operation(&schedule, (2, 2)), Ok(3));
+//! let pinned = ft06.pin(2, 2).unwrap().start_time;
+//!
+//! let improved = ft06.improve(&schedule, MoveStrategy::SwapAndInsertion, Duration::from_secs(10)).schedule;
+//! let resolved = ft06.hold_pins(ft06.solve_greedy_randomized(20, 7));
+//! for schedule in [&improved, &resolved] {
+//!     assert!(ft06.validate(schedule).is_ok());
+//!     let op = schedule.iter().find(|op| (op.job_id, op.operation_id) == (2, 2)).unwrap();
+//!     assert_eq!(op.start_time, pinned);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use super::{Buffering, Downtime, Instance, ScheduledOperation, TIME_EPSILON};
+
+impl Instance {
+    /// Pin operations where they are scheduled. Every pinned operation needs the earlier
+    /// operations of its job pinned too, after its job's release and each other (with
+    /// transfers), on an eligible machine and for at least its processing time; pinned
+    /// operations on one machine must leave room for the setups between them.
+    pub fn set_pins(&mut self, pins: Vec<ScheduledOperation>) -> Result<(), String> {
+        let mut pins = pins;
+        pins.sort_by_key(|pin| (pin.job_id, pin.operation_id));
+        for (index, pin) in pins.iter().enumerate() {
+            let (job_id, operation_id) = (pin.job_id, pin.operation_id);
+            let job = self.jobs().get(job_id)
+                .filter(|job| operation_id < job.operations.len())
+                .ok_or_else(|| format!("Job {} has no operation {} to pin", job_id, operation_id))?;
+            let duration = job.operations[operation_id].duration_on(pin.machine_id)
+                .ok_or_else(|| format!("Job {} operation {} cannot run on machine {}", job_id, operation_id, pin.machine_id))?;
+            if pin.end_time - pin.start_time + TIME_EPSILON < duration || pin.setup_time < 0.0 {
+                return Err(format!("Job {} operation {} is pinned for less than its processing time", job_id, operation_id));
+            }
+            let previous = index.checked_sub(1).map(|index| &pins[index]);
+            let ready = match previous {
+                _ if operation_id == 0 => job.release_time,
+                Some(previous) if (previous.job_id, previous.operation_id + 1) == (job_id, operation_id) => {
+                    previous.end_time + self.transfer_time(Some(previous.machine_id), pin.machine_id)
+                }
+                _ => return Err(format!("Job {} operation {} is pinned, but not the operation before it", job_id, operation_id)),
+            };
+            if pin.start_time + TIME_EPSILON < ready {
+                return Err(format!("Job {} operation {} is pinned before its job is ready at {}", job_id, operation_id, ready));
+            }
+        }
+
+        let mut by_machine: Vec<&ScheduledOperation> = pins.iter().collect();
+        by_machine.sort_by(|a, b| a.machine_id.cmp(&b.machine_id).then(a.start_time.total_cmp(&b.start_time)));
+        for pair in by_machine.windows(2).filter(|pair| pair[0].machine_id == pair[1].machine_id) {
+            let (first, second) = (pair[0], pair[1]);
+            let setup = self.setup_time(second.machine_id, Some(first.job_id), second.job_id);
+            if second.start_time - setup + TIME_EPSILON < first.end_time {
+                return Err(format!(
+                    "Machine {}: pinned Job {} Op {} leaves no room for Job {} Op {} and its setup",
+                    first.machine_id, first.job_id, first.operation_id, second.job_id, second.operation_id
+                ));
+            }
+        }
+        self.set_pin_data(pins);
+        Ok(())
+    }
+
+    /// Whether pinned operations are held on this instance
+    pub fn uses_pins(&self) -> bool {
+        !self.pins().is_empty() && self.buffering == Buffering::Unlimited && !self.preemptive && !self.is_open_shop()
+    }
+
+    /// Where an operation is pinned, if it is
+    pub fn pin(&self, job_id: usize, operation_id: usize) -> Option<&ScheduledOperation> {
+        let pins = self.pins();
+        pins.binary_search_by_key(&(job_id, operation_id), |pin| (pin.job_id, pin.operation_id))
+            .ok()
+            .map(|index| &pins[index])
+    }
+
+    /// Pin an operation of `schedule` where it is, with the earlier operations of its
+    /// job, and return how many operations were newly pinned
+    pub fn pin_operation(&mut self, schedule: &[ScheduledOperation], operation: (usize, usize)) -> Result<usize, String> {
+        let (job_id, operation_id) = operation;
+        let mut pins = self.pins().to_vec();
+        let mut added = 0;
+        for earlier in 0..=operation_id {
+            if self.pin(job_id, earlier).is_some() {
+                continue;
+            }
+            let op = schedule.iter()
+                .find(|op| (op.job_id, op.operation_id) == (job_id, earlier))
+                .ok_or_else(|| format!("Job {} Op {} is not in the schedule", job_id, earlier))?;
+            pins.push(op.clone());
+            added += 1;
+        }
+        self.set_pins(pins)?;
+        Ok(added)
+    }
+
+    /// Release an operation's pin, with those of the later operations of its job, and
+    /// return how many operations were unpinned
+    pub fn unpin_operation(&mut self, operation: (usize, usize)) -> usize {
+        let (job_id, operation_id) = operation;
+        let before = self.pins().len();
+        let pins = self.pins().iter()
+            .filter(|pin| pin.job_id != job_id || pin.operation_id < operation_id)
+            .cloned()
+            .collect();
+        self.set_pin_data(pins);
+        before - self.pins().len()
+    }
+
+    /// `schedule` re-timed with its machine orders so that every pinned operation is
+    /// where it is pinned (see `schedule_from_sequences`); unchanged without pins, or if
+    /// its machine orders contradict its jobs
+    pub fn hold_pins(&self, schedule: Vec<ScheduledOperation>) -> Vec<ScheduledOperation> {
+        if !self.uses_pins() {
+            return schedule;
+        }
+        let sequences = self.machine_sequences(&schedule);
+        self.schedule_from_sequences(&sequences, &HashMap::new()).unwrap_or(schedule)
+    }
+
+    /// The time each pinned operation holds its machine, with room before it for the
+    /// longest setup into its job and after it for the longest setup out of its job
+    pub(super) fn pin_windows(&self) -> Vec<Downtime> {
+        self.pins().iter()
+            .map(|pin| {
+                let machine_id = pin.machine_id;
+                let jobs = 0..self.jobs().len();
+                let into = jobs.clone().map(|job_id| self.setup_time(machine_id, Some(job_id), pin.job_id)).fold(pin.setup_time, f64::max);
+                let out = jobs.map(|job_id| self.setup_time(machine_id, Some(pin.job_id), job_id)).fold(0.0, f64::max);
+                Downtime { machine_id, start: pin.start_time - into, end: pin.end_time + out }
+            })
+            .collect()
+    }
+
+    /// Give every operation next to a pinned one the setup its actual neighbour needs.
+    /// Placement keeps other operations out of the pins' windows, which leave room for
+    /// any setup, so this never makes operations overlap.
+    pub(super) fn settle_pinned_setups(&self, schedule: &mut [ScheduledOperation]) {
+        let mut order: Vec<usize> = (0..schedule.len()).collect();
+        order.sort_by(|&a, &b| {
+            schedule[a].machine_id.cmp(&schedule[b].machine_id).then(schedule[a].start_time.total_cmp(&schedule[b].start_time))
+        });
+        for pair in order.windows(2) {
+            let (previous, next) = (&schedule[pair[0]], &schedule[pair[1]]);
+            let pinned = |op: &ScheduledOperation| self.pin(op.job_id, op.operation_id).is_some();
+            if previous.machine_id != next.machine_id || !(pinned(previous) || pinned(next)) {
+                continue;
+            }
+            let setup = self.setup_time(next.machine_id, Some(previous.job_id), next.job_id);
+            schedule[pair[1]].setup_time = setup;
+        }
+    }
+}
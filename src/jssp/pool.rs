@@ -0,0 +1,104 @@
+//! The best distinct schedules a search came across, not only the very best.
+//!
+//! The best schedule of a run is not always the one to release: the second best may
+//! finish an important job earlier or change less of the current plan. A
+//! `SolutionPool` keeps the `capacity` best schedules with different machine orders
+//! that an algorithm offers it while it searches (see `Solver::solve_with_pool`), and
+//! `order_distance` tells how different each one is from the best.
+//!
+//! ```
+//! use jssp_scheduler::jssp::annealing::SimulatedAnnealing;
+//! use jssp_scheduler::jssp::diff::order_distance;
+//! use jssp_scheduler::jssp::pool::SolutionPool;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::{Objective, Solver, SolverConfig};
+//!
+//! let ft06 = ft06().instance;
+//! let mut pool = SolutionPool::new(5);
+//! let config = SolverConfig::new(Objective::Makespan, 1);
+//! let best = SimulatedAnnealing::default().solve_with_pool(&ft06, &config, &mut pool, &mut |_| {});
+//! assert_eq!(pool.len(), 5);
+//! assert_eq!(pool.best().unwrap().value, ft06.calculate_makespan(&best));
+//! assert!(pool.entries().windows(2).all(|pair| pair[0].value <= pair[1].value));
+//! // Every schedule is valid and has machine orders of its own
+//! for (entry, distance) in pool.entries().iter().zip(pool.distances()).skip(1) {
+//!     assert!(ft06.validate(&entry.schedule).is_ok());
+//!     assert_eq!(distance, order_distance(&pool.best().unwrap().schedule, &entry.schedule));
+//!     assert!(distance > 0);
+//! }
+//! ```
+
+use super::diff::order_distance;
+use super::{Schedule, ScheduledOperation, TIME_EPSILON};
+
+/// A schedule in the pool with its value under the run's objective
+#[derive(Debug, Clone)]
+pub struct PoolEntry {
+    pub schedule: Schedule,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SolutionPool {
+    capacity: usize,
+    /// Best first
+    entries: Vec<PoolEntry>,
+}
+
+impl SolutionPool {
+    /// An empty pool for up to `capacity` schedules; 0 keeps none
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: Vec::with_capacity(capacity) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The schedules, best first
+    pub fn entries(&self) -> &[PoolEntry] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> Option<&PoolEntry> {
+        self.entries.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether a schedule of `value` would get in, unless the pool already has its
+    /// machine orders. Cheap, so searches check it before building a schedule to offer.
+    pub fn admits(&self, value: f64) -> bool {
+        self.capacity > 0 && self.entries.get(self.capacity - 1).is_none_or(|worst| value < worst.value - TIME_EPSILON)
+    }
+
+    /// Add `schedule` if it is among the `capacity` best and no schedule in the pool has
+    /// the same machine orders; returns whether it was added
+    pub fn offer(&mut self, value: f64, schedule: Schedule) -> bool {
+        if !self.admits(value) || self.entries.iter().any(|entry| same_orders(&entry.schedule, &schedule)) {
+            return false;
+        }
+        let position = self.entries.partition_point(|entry| entry.value <= value);
+        self.entries.insert(position, PoolEntry { schedule, value });
+        self.entries.truncate(self.capacity);
+        true
+    }
+
+    /// `order_distance` of every schedule from the best, in pool order
+    pub fn distances(&self) -> Vec<usize> {
+        let Some(best) = self.best() else {
+            return Vec::new();
+        };
+        self.entries.iter().map(|entry| order_distance(&best.schedule, &entry.schedule)).collect()
+    }
+}
+
+fn same_orders(a: &[ScheduledOperation], b: &[ScheduledOperation]) -> bool {
+    a.len() == b.len() && order_distance(a, b) == 0
+}
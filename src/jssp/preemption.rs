@@ -0,0 +1,161 @@
+//! Preemptive job shops: downtime interrupts operations instead of delaying them.
+//!
+//! Normally an operation has to fit between two downtime windows of its machine, so a
+//! long operation may wait for a long gap. With `Instance::preemptive` set only the
+//! setup and the first moment of processing have to be clear of downtime: when a
+//! window comes, processing pauses and resumes right after it. The operation is then
+//! processed in several pieces, its `segments`, and `end_time` is when the last one
+//! ends.
+//!
+//! `Instance::decode`, the insertion heuristic, the disjunctive graph behind annealing
+//! and local search, `Instance::schedule_from_sequences` and the slack analysis all
+//! split operations this way. Other solvers keep placing operations in one piece,
+//! which is still a feasible preemptive schedule. Preemption only applies with
+//! unlimited buffers; no-wait and blocking shops ignore the flag.
+//!
+//! ```
+//! use jssp_scheduler::jssp::Downtime;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance;
+//! ft06.set_downtime(vec![Downtime { machine_id: 1, start: 8.0, end: 12.0 }]).unwrap();
+//! let whole = ft06.solve_greedy();
+//!
+//! ft06.preemptive = true;
+//! let split = ft06.solve_greedy();
+//! assert!(ft06.validate(&split).is_ok());
+//! assert!(ft06.calculate_makespan(&split) < ft06.calculate_makespan(&whole));
+//!
+//! let op = split.iter().find(|op| !op.segments.is_empty()).unwrap();
+//! let processed: f64 = op.intervals().map(|(from, to)| to - from).sum();
+//! assert!((processed - op.duration).abs() < 1e-9);
+//! ```
+
+use super::{Buffering, Instance, ScheduledOperation, TIME_EPSILON};
+
+impl Instance {
+    /// Whether downtime interrupts operations on this instance
+    pub fn preempts(&self) -> bool {
+        self.preemptive && self.buffering == Buffering::Unlimited
+    }
+
+    /// Earliest start at or after `start` for an operation of `duration` on a machine:
+    /// like `available_start`, but on a preemptive instance only the `setup` and the
+    /// start itself have to be clear of downtime
+    pub fn preemptive_start(&self, machine_id: usize, start: f64, setup: f64, duration: f64) -> f64 {
+        if !self.preempts() {
+            return self.available_start(machine_id, start, setup, duration);
+        }
+        let mut start = start;
+        for window in self.downtime_after(machine_id, start - setup) {
+            let covered = start > window.start - TIME_EPSILON && start < window.end - TIME_EPSILON;
+            if covered || window.overlaps(start - setup, start) {
+                start = window.end + setup;
+            }
+        }
+        start
+    }
+
+    /// When an operation of `duration` that starts processing at `start` ends, paused by
+    /// every downtime window it runs into on a preemptive instance
+    pub fn processing_end(&self, machine_id: usize, start: f64, duration: f64) -> f64 {
+        if !self.preempts() {
+            return start + duration;
+        }
+        self.process(machine_id, start, duration, |_, _| {}).1
+    }
+
+    /// The pieces an operation that starts processing at `start` is split into by
+    /// downtime; empty when it runs in one go
+    pub fn processing_segments(&self, machine_id: usize, start: f64, duration: f64) -> Vec<(f64, f64)> {
+        if !self.preempts() {
+            return Vec::new();
+        }
+        let mut segments = Vec::new();
+        let last = self.process(machine_id, start, duration, |from, to| segments.push((from, to)));
+        if !segments.is_empty() {
+            segments.push(last);
+        }
+        segments
+    }
+
+    /// Walks from `start` through the machine's downtime, calling `interrupted` with
+    /// every piece that ends at a window, and returns the last piece
+    fn process(&self, machine_id: usize, start: f64, duration: f64, mut interrupted: impl FnMut(f64, f64)) -> (f64, f64) {
+        let mut from = start;
+        let mut remaining = duration;
+        for window in self.downtime_after(machine_id, from) {
+            if window.end <= from + TIME_EPSILON {
+                continue;
+            }
+            if window.start >= from + remaining - TIME_EPSILON {
+                break;
+            }
+            if window.start > from + TIME_EPSILON {
+                interrupted(from, window.start);
+                remaining -= window.start - from;
+            }
+            from = from.max(window.end);
+        }
+        (from, from + remaining)
+    }
+
+    /// When an operation of `duration` that ends at `end` must start processing: the
+    /// mirror image of `processing_end`
+    pub fn processing_start(&self, machine_id: usize, end: f64, duration: f64) -> f64 {
+        if !self.preempts() {
+            return end - duration;
+        }
+        let mut to = end;
+        let mut remaining = duration;
+        for window in self.machine_downtime(machine_id).iter().rev() {
+            if window.start >= to - TIME_EPSILON {
+                continue;
+            }
+            if window.end <= to - remaining + TIME_EPSILON {
+                break;
+            }
+            if window.end < to - TIME_EPSILON {
+                remaining -= to - window.end;
+            }
+            to = to.min(window.start);
+        }
+        to - remaining
+    }
+
+    /// Latest end at or before `end` for an operation of `duration` on a machine: like
+    /// `available_end`, but on a preemptive instance processing may span downtime. When
+    /// the setup would hit a window the operation moves in front of it as a whole, so
+    /// the result may be a little early, never too late.
+    pub fn preemptive_end(&self, machine_id: usize, end: f64, setup: f64, duration: f64) -> f64 {
+        if !self.preempts() {
+            return self.available_end(machine_id, end, setup, duration);
+        }
+        let windows = self.machine_downtime(machine_id);
+        let mut end = end;
+        loop {
+            for window in windows.iter().rev() {
+                if end > window.start + TIME_EPSILON && end < window.end + TIME_EPSILON {
+                    end = window.start;
+                }
+            }
+            let start = self.processing_start(machine_id, end, duration);
+            let blocking = windows.iter().rev().find(|window| {
+                let covered = start > window.start - TIME_EPSILON && start < window.end - TIME_EPSILON;
+                covered || window.overlaps(start - setup, start)
+            });
+            match blocking {
+                Some(window) if window.start < end - TIME_EPSILON => end = window.start,
+                _ => return end,
+            }
+        }
+    }
+
+    /// An operation that starts processing at `start_time`, with its end and, on a
+    /// preemptive instance, its segments filled in
+    pub fn timed_operation(&self, job_id: usize, operation_id: usize, machine_id: usize, start_time: f64, duration: f64, setup_time: f64) -> ScheduledOperation {
+        let segments = self.processing_segments(machine_id, start_time, duration);
+        let end_time = segments.last().map_or(start_time + duration, |&(_, end)| end);
+        ScheduledOperation { job_id, operation_id, machine_id, start_time, end_time, duration, setup_time, segments }
+    }
+}
@@ -0,0 +1,400 @@
+//! Named parameter sets for the tunable algorithms, kept in a TOML file.
+//!
+//! Three presets are built in: "fast", "balanced" (the algorithms' defaults) and
+//! "quality". A config file may change any of their values and add presets of its own.
+//! Each preset is a table with one sub-table per algorithm:
+//!
+//! ```toml
+//! [quality.simulated_annealing]
+//! iterations = 500000
+//!
+//! [overnight.random_restart_greedy]
+//! restarts = 20000
+//! ```
+//!
+//! Values a file leaves out keep those of the built-in preset of the same name, or the
+//! balanced ones for a new preset. A single run can override any value by its
+//! `<algorithm>.<parameter>` key without touching the file.
+//!
+//! ```
+//! use jssp_scheduler::jssp::presets::Presets;
+//!
+//! let presets = Presets::parse("[overnight.random_restart_greedy]\nrestarts = 20000\n").unwrap();
+//! assert_eq!(presets.names().collect::<Vec<_>>(), ["fast", "balanced", "quality", "overnight"]);
+//! assert_eq!(presets.get("overnight").unwrap().restarts, 20_000);
+//! assert_eq!(presets.get("overnight").unwrap().annealing, presets.get("balanced").unwrap().annealing);
+//!
+//! let mut params = presets.get("fast").unwrap().clone();
+//! params.set("simulated_annealing.iterations=500").unwrap();
+//! assert_eq!(params.annealing.iterations, 500);
+//! assert!(params.set("simulated_annealing.iteration=500").is_err());
+//!
+//! // The file written for editing reads back the same
+//! assert_eq!(Presets::parse(&presets.to_toml()).unwrap(), presets);
+//! ```
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use toml_edit::DocumentMut;
+
+use super::annealing::{AnnealingConfig, SimulatedAnnealing};
+use super::memetic::{GeneticAlgorithm, GeneticConfig};
+use super::solvers::{RandomRestartGreedy, SolverRegistry};
+use super::JsspError;
+
+/// The preset used when none is chosen
+pub const DEFAULT_PRESET: &str = "balanced";
+
+/// A tunable value, addressed as `<algorithm>.<parameter>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    /// The key in config files and overrides, e.g. "simulated_annealing.iterations"
+    pub key: &'static str,
+    /// Name of the algorithm it belongs to, as in the algorithm list
+    pub algorithm: &'static str,
+    pub label: &'static str,
+    pub description: &'static str,
+    /// Whole numbers only
+    pub integer: bool,
+}
+
+impl Parameter {
+    pub const ALL: [Parameter; 10] = [
+        Parameter {
+            key: "simulated_annealing.iterations",
+            algorithm: "Simulated Annealing",
+            label: "Iterations",
+            description: "Moves tried before the search stops",
+            integer: true,
+        },
+        Parameter {
+            key: "simulated_annealing.start_temperature",
+            algorithm: "Simulated Annealing",
+            label: "Start Temperature",
+            description: "Temperature at the first move, as a fraction of the starting makespan",
+            integer: false,
+        },
+        Parameter {
+            key: "simulated_annealing.end_temperature",
+            algorithm: "Simulated Annealing",
+            label: "End Temperature",
+            description: "Temperature at the last move, as a fraction of the starting makespan",
+            integer: false,
+        },
+        Parameter {
+            key: "random_restart_greedy.restarts",
+            algorithm: "Random Restart Greedy",
+            label: "Restarts",
+            description: "Greedy passes, of which the best is kept",
+            integer: true,
+        },
+        Parameter {
+            key: "genetic_algorithm.population_size",
+            algorithm: "Genetic Algorithm",
+            label: "Population",
+            description: "Individuals kept from one generation to the next",
+            integer: true,
+        },
+        Parameter {
+            key: "genetic_algorithm.generations",
+            algorithm: "Genetic Algorithm",
+            label: "Generations",
+            description: "Generations bred before the search stops",
+            integer: true,
+        },
+        Parameter {
+            key: "memetic_algorithm.population_size",
+            algorithm: "Memetic Algorithm",
+            label: "Population",
+            description: "Individuals kept from one generation to the next",
+            integer: true,
+        },
+        Parameter {
+            key: "memetic_algorithm.generations",
+            algorithm: "Memetic Algorithm",
+            label: "Generations",
+            description: "Generations bred before the search stops",
+            integer: true,
+        },
+        Parameter {
+            key: "memetic_algorithm.local_search_moves",
+            algorithm: "Memetic Algorithm",
+            label: "Local Search Moves",
+            description: "Swaps the local search tries on each offspring",
+            integer: true,
+        },
+        Parameter {
+            key: "constraint_programming.time_limit",
+            algorithm: "Constraint Programming",
+            label: "Time Limit (s)",
+            description: "Seconds of search before the best schedule found is returned",
+            integer: false,
+        },
+    ];
+
+    pub fn find(key: &str) -> Option<Parameter> {
+        Self::ALL.into_iter().find(|parameter| parameter.key == key)
+    }
+
+    /// The parameters of one algorithm, in display order
+    pub fn of_algorithm(algorithm: &str) -> impl Iterator<Item = Parameter> + '_ {
+        Self::ALL.into_iter().filter(move |parameter| parameter.algorithm == algorithm)
+    }
+}
+
+/// Parameters of the tunable algorithms
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlgorithmParams {
+    pub annealing: AnnealingConfig,
+    /// Restarts of Random Restart Greedy
+    pub restarts: usize,
+    pub genetic: GeneticConfig,
+    pub memetic: GeneticConfig,
+    /// Search time of the constraint programming solver (with the `cp-solver` feature)
+    pub cp_time_limit: Duration,
+}
+
+impl Default for AlgorithmParams {
+    /// The balanced preset: each algorithm's own defaults
+    fn default() -> Self {
+        Self {
+            annealing: AnnealingConfig::default(),
+            restarts: RandomRestartGreedy::default().restarts,
+            genetic: GeneticAlgorithm::default().config,
+            memetic: GeneticAlgorithm::memetic().config,
+            cp_time_limit: Duration::from_secs(5),
+        }
+    }
+}
+
+impl AlgorithmParams {
+    /// About a tenth of the balanced effort, for quick looks at large instances
+    pub fn fast() -> Self {
+        Self {
+            annealing: AnnealingConfig { iterations: 2_000, ..AnnealingConfig::default() },
+            restarts: 20,
+            genetic: GeneticConfig { generations: 20, ..GeneticAlgorithm::default().config },
+            memetic: GeneticConfig { generations: 10, local_search_moves: 50, ..GeneticAlgorithm::memetic().config },
+            cp_time_limit: Duration::from_secs(1),
+        }
+    }
+
+    /// About ten times the balanced effort, for final schedules
+    pub fn quality() -> Self {
+        Self {
+            annealing: AnnealingConfig { iterations: 200_000, start_temperature: 0.1, end_temperature: 0.0005 },
+            restarts: 1_000,
+            genetic: GeneticConfig { population_size: 100, generations: 500, ..GeneticAlgorithm::default().config },
+            memetic: GeneticConfig { population_size: 50, generations: 300, local_search_moves: 1_000, ..GeneticAlgorithm::memetic().config },
+            cp_time_limit: Duration::from_secs(60),
+        }
+    }
+
+    /// A parameter by key, `None` for unknown keys
+    pub fn value(&self, key: &str) -> Option<f64> {
+        Some(match key {
+            "simulated_annealing.iterations" => self.annealing.iterations as f64,
+            "simulated_annealing.start_temperature" => self.annealing.start_temperature,
+            "simulated_annealing.end_temperature" => self.annealing.end_temperature,
+            "random_restart_greedy.restarts" => self.restarts as f64,
+            "genetic_algorithm.population_size" => self.genetic.population_size as f64,
+            "genetic_algorithm.generations" => self.genetic.generations as f64,
+            "memetic_algorithm.population_size" => self.memetic.population_size as f64,
+            "memetic_algorithm.generations" => self.memetic.generations as f64,
+            "memetic_algorithm.local_search_moves" => self.memetic.local_search_moves as f64,
+            "constraint_programming.time_limit" => self.cp_time_limit.as_secs_f64(),
+            _ => return None,
+        })
+    }
+
+    /// Change a parameter by key, rejecting unknown keys and values out of range
+    pub fn set_value(&mut self, key: &str, value: f64) -> Result<(), String> {
+        let parameter = Parameter::find(key).ok_or_else(|| {
+            let keys: Vec<&str> = Parameter::ALL.iter().map(|parameter| parameter.key).collect();
+            format!("unknown parameter '{}'; expected one of: {}", key, keys.join(", "))
+        })?;
+        if !value.is_finite() || value <= 0.0 {
+            return Err(format!("{} must be positive, got {}", key, value));
+        }
+        if parameter.integer && value.fract() != 0.0 {
+            return Err(format!("{} must be a whole number, got {}", key, value));
+        }
+        match key {
+            "simulated_annealing.iterations" => self.annealing.iterations = value as usize,
+            "simulated_annealing.start_temperature" => self.annealing.start_temperature = value,
+            "simulated_annealing.end_temperature" => self.annealing.end_temperature = value,
+            "random_restart_greedy.restarts" => self.restarts = value as usize,
+            "genetic_algorithm.population_size" => self.genetic.population_size = value as usize,
+            "genetic_algorithm.generations" => self.genetic.generations = value as usize,
+            "memetic_algorithm.population_size" => self.memetic.population_size = value as usize,
+            "memetic_algorithm.generations" => self.memetic.generations = value as usize,
+            "memetic_algorithm.local_search_moves" => self.memetic.local_search_moves = value as usize,
+            "constraint_programming.time_limit" => self.cp_time_limit = Duration::from_secs_f64(value),
+            _ => unreachable!("every parameter is handled"),
+        }
+        Ok(())
+    }
+
+    /// Apply a `<key>=<value>` override, as given on the command line
+    pub fn set(&mut self, assignment: &str) -> Result<(), String> {
+        let (key, value) = assignment.split_once('=')
+            .ok_or_else(|| format!("expected <algorithm>.<parameter>=<value>, got '{}'", assignment))?;
+        let value: f64 = value.trim().parse().map_err(|_| format!("{} is not a number: '{}'", key.trim(), value.trim()))?;
+        self.set_value(key.trim(), value)
+    }
+
+    /// Replace the tunable algorithms of `registry` by ones with these parameters;
+    /// algorithms the registry does not offer are left out
+    pub fn apply(&self, registry: &mut SolverRegistry) {
+        if registry.get("Simulated Annealing").is_some() {
+            registry.register(SimulatedAnnealing { config: self.annealing.clone() });
+        }
+        if registry.get("Random Restart Greedy").is_some() {
+            registry.register(RandomRestartGreedy { restarts: self.restarts });
+        }
+        if registry.get("Genetic Algorithm").is_some() {
+            registry.register(GeneticAlgorithm { config: self.genetic.clone() });
+        }
+        if registry.get("Memetic Algorithm").is_some() {
+            registry.register(GeneticAlgorithm { config: self.memetic.clone() });
+        }
+        #[cfg(feature = "cp-solver")]
+        if registry.get("Constraint Programming").is_some() {
+            registry.register(super::cp::CpSolver { time_limit: self.cp_time_limit });
+        }
+    }
+
+    /// The built-in algorithms with these parameters
+    pub fn registry(&self) -> SolverRegistry {
+        let mut registry = SolverRegistry::default();
+        self.apply(&mut registry);
+        registry
+    }
+
+    /// The parameters of one algorithm by key, empty for algorithms without any
+    pub fn algorithm_json(&self, algorithm: &str) -> serde_json::Value {
+        Parameter::of_algorithm(algorithm)
+            .filter_map(|parameter| Some((parameter.key.to_string(), serde_json::json!(self.value(parameter.key)?))))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    /// Every parameter by key, for recording a run
+    pub fn to_json(&self) -> serde_json::Value {
+        Parameter::ALL.iter()
+            .filter_map(|parameter| Some((parameter.key.to_string(), serde_json::json!(self.value(parameter.key)?))))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+/// The presets on offer, built-in ones first
+#[derive(Debug, Clone, PartialEq)]
+pub struct Presets {
+    presets: Vec<(String, AlgorithmParams)>,
+}
+
+impl Default for Presets {
+    fn default() -> Self {
+        Self {
+            presets: vec![
+                ("fast".to_string(), AlgorithmParams::fast()),
+                (DEFAULT_PRESET.to_string(), AlgorithmParams::default()),
+                ("quality".to_string(), AlgorithmParams::quality()),
+            ],
+        }
+    }
+}
+
+impl Presets {
+    /// The built-in presets, changed and extended by a TOML config
+    pub fn parse(text: &str) -> Result<Self, JsspError> {
+        Self::parse_document(text).map_err(JsspError::Parse)
+    }
+
+    fn parse_document(text: &str) -> Result<Self, String> {
+        let document: DocumentMut = text.parse().map_err(|e| format!("Invalid TOML: {}", e))?;
+        let mut presets = Self::default();
+        for (name, item) in document.iter() {
+            let table = item.as_table_like().ok_or_else(|| format!("'{}' should be a table of algorithms", name))?;
+            let position = match presets.presets.iter().position(|(preset, _)| preset == name) {
+                Some(position) => position,
+                None => {
+                    presets.presets.push((name.to_string(), AlgorithmParams::default()));
+                    presets.presets.len() - 1
+                }
+            };
+            let params = &mut presets.presets[position].1;
+            for (algorithm, item) in table.iter() {
+                let values = item.as_table_like()
+                    .ok_or_else(|| format!("'{}.{}' should be a table of parameters", name, algorithm))?;
+                for (parameter, item) in values.iter() {
+                    let key = format!("{}.{}", algorithm, parameter);
+                    let value = item.as_integer().map(|value| value as f64)
+                        .or_else(|| item.as_float())
+                        .ok_or_else(|| format!("{}.{} should be a number", name, key))?;
+                    params.set_value(&key, value).map_err(|e| format!("In preset '{}': {}", name, e))?;
+                }
+            }
+        }
+        Ok(presets)
+    }
+
+    /// Read a config file; a missing file gives the built-in presets
+    pub fn load(path: &Path) -> Result<Self, JsspError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| e.context(path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(JsspError::io("read", path)(e)),
+        }
+    }
+
+    /// Where the GUI and the command line look for the config: next to the app's other
+    /// saved state
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_path() -> Option<PathBuf> {
+        eframe::storage_dir("JSSP Scheduler").map(|dir| dir.join("presets.toml"))
+    }
+
+    /// Every preset as a commented TOML config, to start editing from
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from(
+            "# Algorithm parameter presets of jssp-scheduler.\n\
+             # Change values or add presets; values left out keep the built-in ones.\n",
+        );
+        for (name, params) in &self.presets {
+            let mut algorithm = "";
+            for parameter in Parameter::ALL {
+                let (section, key) = parameter.key.split_once('.').unwrap();
+                if section != algorithm {
+                    let _ = write!(text, "\n[{}.{}]\n", name, section);
+                    algorithm = section;
+                }
+                let value = params.value(parameter.key).unwrap();
+                if parameter.integer {
+                    let _ = writeln!(text, "{} = {} # {}", key, value, parameter.description);
+                } else {
+                    let _ = writeln!(text, "{} = {:?} # {}", key, value, parameter.description);
+                }
+            }
+        }
+        text
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AlgorithmParams> {
+        self.presets.iter().find(|(preset, _)| preset == name).map(|(_, params)| params)
+    }
+
+    /// `get`, with the preset names in the error
+    pub fn find(&self, name: &str) -> Result<&AlgorithmParams, String> {
+        self.get(name).ok_or_else(|| {
+            format!("unknown preset '{}'; expected one of: {}", name, self.names().collect::<Vec<_>>().join(", "))
+        })
+    }
+}
@@ -0,0 +1,367 @@
+//! Priority expressions for custom dispatching rules.
+//!
+//! A rule is an arithmetic expression over the attributes of a waiting operation; the
+//! operation with the lowest value goes first, as with the built-in rules. Expressions
+//! know `+ - * / ^`, parentheses, numbers, the functions `min`, `max`, `abs` and
+//! `sqrt`, and the variables in `PriorityExpression::VARIABLES`.
+//!
+//! ```
+//! use jssp_scheduler::jssp::dynamic::DispatchRule;
+//! use jssp_scheduler::jssp::priority::{PriorityContext, PriorityExpression};
+//! use jssp_scheduler::jssp::{generate_instance, GeneratorConfig};
+//!
+//! let spt_with_aging = PriorityExpression::parse("duration - 0.1 * waiting").unwrap();
+//! let context = PriorityContext { duration: 8.0, waiting: 30.0, ..PriorityContext::default() };
+//! assert_eq!(spt_with_aging.evaluate(&context), 5.0);
+//!
+//! // Usable wherever a built-in rule is
+//! let config = GeneratorConfig { num_jobs: 20, mean_interarrival: Some(8.0), ..GeneratorConfig::default() };
+//! let instance = generate_instance(&config);
+//! let run = instance.dispatch(DispatchRule::Custom(spt_with_aging));
+//! assert!(instance.validate(&run.schedule).is_ok());
+//!
+//! let error = PriorityExpression::parse("duration * (remaining_work").unwrap_err();
+//! assert!(error.contains("')'"));
+//! assert!(PriorityExpression::parse("urgency").unwrap_err().contains("Unknown variable"));
+//! ```
+
+use std::fmt;
+
+/// What a priority expression can refer to, for one waiting operation when a machine
+/// becomes idle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorityContext {
+    /// Processing time on the idle machine
+    pub duration: f64,
+    /// Processing left in the job, including this operation
+    pub remaining_work: f64,
+    /// Operations left in the job, including this one
+    pub remaining_operations: f64,
+    /// Due date minus the current time and the remaining work; infinite without a due date
+    pub slack: f64,
+    /// Time until the due date; infinite without one
+    pub due_in: f64,
+    /// Operations waiting for the idle machine
+    pub queue_length: f64,
+    /// Time the operation has been waiting
+    pub waiting: f64,
+    pub weight: f64,
+    pub release_time: f64,
+    pub now: f64,
+}
+
+impl Default for PriorityContext {
+    fn default() -> Self {
+        Self {
+            duration: 0.0,
+            remaining_work: 0.0,
+            remaining_operations: 0.0,
+            slack: f64::INFINITY,
+            due_in: f64::INFINITY,
+            queue_length: 0.0,
+            waiting: 0.0,
+            weight: 1.0,
+            release_time: 0.0,
+            now: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+    Duration,
+    RemainingWork,
+    RemainingOperations,
+    Slack,
+    DueIn,
+    QueueLength,
+    Waiting,
+    Weight,
+    ReleaseTime,
+    Now,
+}
+
+impl Variable {
+    const ALL: [Variable; 10] = [
+        Variable::Duration,
+        Variable::RemainingWork,
+        Variable::RemainingOperations,
+        Variable::Slack,
+        Variable::DueIn,
+        Variable::QueueLength,
+        Variable::Waiting,
+        Variable::Weight,
+        Variable::ReleaseTime,
+        Variable::Now,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Variable::Duration => "duration",
+            Variable::RemainingWork => "remaining_work",
+            Variable::RemainingOperations => "remaining_operations",
+            Variable::Slack => "slack",
+            Variable::DueIn => "due_in",
+            Variable::QueueLength => "queue_length",
+            Variable::Waiting => "waiting",
+            Variable::Weight => "weight",
+            Variable::ReleaseTime => "release_time",
+            Variable::Now => "now",
+        }
+    }
+
+    fn value(self, context: &PriorityContext) -> f64 {
+        match self {
+            Variable::Duration => context.duration,
+            Variable::RemainingWork => context.remaining_work,
+            Variable::RemainingOperations => context.remaining_operations,
+            Variable::Slack => context.slack,
+            Variable::DueIn => context.due_in,
+            Variable::QueueLength => context.queue_length,
+            Variable::Waiting => context.waiting,
+            Variable::Weight => context.weight,
+            Variable::ReleaseTime => context.release_time,
+            Variable::Now => context.now,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Min,
+    Max,
+    Abs,
+    Sqrt,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "abs" => Some(Function::Abs),
+            "sqrt" => Some(Function::Sqrt),
+            _ => None,
+        }
+    }
+
+    /// Whether the function takes `count` arguments
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Function::Min | Function::Max => count >= 2,
+            Function::Abs | Function::Sqrt => count == 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self, context: &PriorityContext) -> f64 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Variable(variable) => variable.value(context),
+            Expr::Negate(operand) => -operand.evaluate(context),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.evaluate(context), right.evaluate(context));
+                match op {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    _ => left.powf(right),
+                }
+            }
+            Expr::Call(function, arguments) => {
+                let mut values = arguments.iter().map(|argument| argument.evaluate(context));
+                match function {
+                    Function::Min => values.fold(f64::INFINITY, f64::min),
+                    Function::Max => values.fold(f64::NEG_INFINITY, f64::max),
+                    Function::Abs => values.next().unwrap_or(0.0).abs(),
+                    Function::Sqrt => values.next().unwrap_or(0.0).sqrt(),
+                }
+            }
+        }
+    }
+}
+
+/// A parsed priority expression; lower values are dispatched first
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityExpression {
+    source: String,
+    root: Expr,
+}
+
+impl PriorityExpression {
+    /// The variables an expression can use, with what they mean
+    pub const VARIABLES: [(&'static str, &'static str); 10] = [
+        ("duration", "processing time on the idle machine"),
+        ("remaining_work", "processing left in the job, including this operation"),
+        ("remaining_operations", "operations left in the job, including this one"),
+        ("slack", "due date minus now and the remaining work (infinite without a due date)"),
+        ("due_in", "due date minus now (infinite without a due date)"),
+        ("queue_length", "operations waiting for the idle machine"),
+        ("waiting", "time the operation has been waiting"),
+        ("weight", "weight of the job"),
+        ("release_time", "release time of the job"),
+        ("now", "the current time"),
+    ];
+
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser { source, position: 0 };
+        let root = parser.expression()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(format!("Unexpected '{}' at position {}", c, parser.position + 1));
+        }
+        Ok(Self { source: source.trim().to_string(), root })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn evaluate(&self, context: &PriorityContext) -> f64 {
+        self.root.evaluate(context)
+    }
+}
+
+impl std::str::FromStr for PriorityExpression {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, String> {
+        Self::parse(source)
+    }
+}
+
+impl fmt::Display for PriorityExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Recursive descent over `source`:
+///
+/// ```text
+/// expression := term (("+" | "-") term)*
+/// term       := unary (("*" | "/") unary)*
+/// unary      := "-" unary | power
+/// power      := atom ("^" unary)?
+/// atom       := number | variable | function "(" expression ("," expression)* ")" | "(" expression ")"
+/// ```
+struct Parser<'a> {
+    source: &'a str,
+    /// Byte offset of the next character
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    /// Consume `expected` if it comes next, after any whitespace
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(c) => Err(format!("Expected '{}' at position {}, found '{}'", expected, self.position + 1, c)),
+            None => Err(format!("Expected '{}' at the end of the expression", expected)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        loop {
+            let op = if self.eat('+') { '+' } else if self.eat('-') { '-' } else { return Ok(left) };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat('*') { '*' } else if self.eat('/') { '/' } else { return Ok(left) };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        let base = self.atom()?;
+        if self.eat('^') {
+            return Ok(Expr::Binary('^', Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        let start = self.position;
+        match self.peek() {
+            None => Err("The expression ends too early".to_string()),
+            Some('(') => {
+                self.position += 1;
+                let inner = self.expression()?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.position += 1;
+                }
+                let text = &self.source[start..self.position];
+                text.parse().map(Expr::Number).map_err(|_| format!("Invalid number '{}' at position {}", text, start + 1))
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    self.position += 1;
+                }
+                let name = &self.source[start..self.position];
+                if self.eat('(') {
+                    let function = Function::from_name(name).ok_or_else(|| format!("Unknown function '{}'", name))?;
+                    let mut arguments = vec![self.expression()?];
+                    while self.eat(',') {
+                        arguments.push(self.expression()?);
+                    }
+                    self.expect(')')?;
+                    if !function.accepts(arguments.len()) {
+                        return Err(format!("{}() does not take {} arguments", name, arguments.len()));
+                    }
+                    return Ok(Expr::Call(function, arguments));
+                }
+                Variable::ALL.iter()
+                    .find(|variable| variable.name() == name)
+                    .map(|&variable| Expr::Variable(variable))
+                    .ok_or_else(|| format!("Unknown variable '{}'", name))
+            }
+            Some(c) => Err(format!("Unexpected '{}' at position {}", c, start + 1)),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Statistics of an instance before it is solved, and a rough estimate of how hard it is.
+//!
+//! Work is counted with the shortest processing time of every operation, as the lower
+//! bounds do; an operation that can run on several machines spreads that time evenly
+//! over them. The difficulty estimate follows what benchmark studies report: small
+//! instances are solved to optimality quickly, instances with many more jobs than
+//! machines usually reach the machine-load bound, and square instances with hundreds of
+//! operations (like Taillard's 20×20 and 30×20) are the hardest.
+//!
+//! ```
+//! use jssp_scheduler::jssp::profile::Difficulty;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let profile = ft06.profile();
+//! assert_eq!(profile.machine_work.len(), 6);
+//! let total: f64 = profile.job_work.iter().sum();
+//! assert!((profile.machine_work.iter().sum::<f64>() - total).abs() < 1e-9);
+//! assert!(profile.load_imbalance >= 1.0);
+//! assert!(profile.lower_bounds.best() <= 55.0);
+//! assert_eq!(profile.difficulty, Difficulty::Easy);
+//! ```
+
+use super::bounds::LowerBounds;
+use super::Instance;
+
+/// Operations up to which an instance counts as small
+const SMALL_OPERATIONS: usize = 60;
+/// Jobs per machine from which the busiest machine usually decides the makespan
+const RECTANGULAR_RATIO: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Moderate,
+    Hard,
+    VeryHard,
+}
+
+impl Difficulty {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Moderate => "Moderate",
+            Difficulty::Hard => "Hard",
+            Difficulty::VeryHard => "Very hard",
+        }
+    }
+
+    /// Which algorithms suit instances of this difficulty
+    pub fn advice(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Constructive heuristics get close; Simulated Annealing, or Constraint Programming if built in, should reach the optimum.",
+            Difficulty::Moderate => "Simulated Annealing or the Memetic Algorithm usually gets within a few percent of the lower bound.",
+            Difficulty::Hard => "Use the Memetic Algorithm or Simulated Annealing with several runs; expect a gap of several percent.",
+            Difficulty::VeryHard => "Start from Shifting Bottleneck or Insertion Greedy and improve the schedule; optimal schedules are out of reach.",
+        }
+    }
+}
+
+/// What an instance asks of its machines and jobs
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceProfile {
+    /// Processing each machine has to do, by machine id
+    pub machine_work: Vec<f64>,
+    /// Processing each job needs, by job id
+    pub job_work: Vec<f64>,
+    /// The busiest machine's work over the mean; 1 when every machine has as much to do
+    pub load_imbalance: f64,
+    pub duration_mean: f64,
+    pub duration_std_dev: f64,
+    pub shortest_duration: f64,
+    pub longest_duration: f64,
+    pub lower_bounds: LowerBounds,
+    pub difficulty: Difficulty,
+}
+
+impl InstanceProfile {
+    /// Standard deviation of the durations over their mean
+    pub fn duration_variation(&self) -> f64 {
+        if self.duration_mean > 0.0 { self.duration_std_dev / self.duration_mean } else { 0.0 }
+    }
+
+    /// The machine with the most work
+    pub fn busiest_machine(&self) -> Option<usize> {
+        (0..self.machine_work.len()).max_by(|&a, &b| self.machine_work[a].total_cmp(&self.machine_work[b]))
+    }
+
+    /// Why the instance got its difficulty
+    pub fn reason(&self, instance: &Instance) -> String {
+        let operations = instance.num_operations();
+        let ratio = instance.jobs().len() as f64 / instance.num_machines().max(1) as f64;
+        match self.difficulty {
+            Difficulty::Easy if operations <= SMALL_OPERATIONS => format!("Only {} operations", operations),
+            _ if ratio >= RECTANGULAR_RATIO => {
+                format!("{:.1} jobs per machine: the busiest machine tends to decide the makespan", ratio)
+            }
+            _ => format!("{} operations on {:.1} jobs per machine", operations, ratio),
+        }
+    }
+}
+
+impl Instance {
+    pub fn profile(&self) -> InstanceProfile {
+        let mut machine_work = vec![0.0; self.num_machines()];
+        let mut job_work = Vec::with_capacity(self.jobs().len());
+        let mut durations = Vec::with_capacity(self.num_operations());
+        for job in self.jobs() {
+            let mut work = 0.0;
+            for op in &job.operations {
+                let shortest = op.options().map(|option| option.duration).fold(f64::INFINITY, f64::min);
+                let share = shortest / op.options().count() as f64;
+                for option in op.options() {
+                    machine_work[option.machine_id] += share;
+                }
+                work += shortest;
+                durations.push(shortest);
+            }
+            job_work.push(work);
+        }
+
+        let count = durations.len().max(1) as f64;
+        let duration_mean = durations.iter().sum::<f64>() / count;
+        let variance = durations.iter().map(|d| (d - duration_mean).powi(2)).sum::<f64>() / count;
+        let mean_work = machine_work.iter().sum::<f64>() / machine_work.len().max(1) as f64;
+        let busiest = machine_work.iter().copied().fold(0.0, f64::max);
+
+        InstanceProfile {
+            load_imbalance: if mean_work > 0.0 { busiest / mean_work } else { 1.0 },
+            duration_mean,
+            duration_std_dev: variance.sqrt(),
+            shortest_duration: durations.iter().copied().reduce(f64::min).unwrap_or(0.0),
+            longest_duration: durations.iter().copied().fold(0.0, f64::max),
+            lower_bounds: self.lower_bounds(),
+            difficulty: self.difficulty(),
+            machine_work,
+            job_work,
+        }
+    }
+
+    fn difficulty(&self) -> Difficulty {
+        let operations = self.num_operations();
+        let ratio = self.jobs().len() as f64 / self.num_machines().max(1) as f64;
+        if operations <= SMALL_OPERATIONS {
+            Difficulty::Easy
+        } else if ratio >= RECTANGULAR_RATIO {
+            if operations <= 1000 { Difficulty::Easy } else { Difficulty::Moderate }
+        } else if operations <= 150 {
+            Difficulty::Moderate
+        } else if operations <= 600 {
+            Difficulty::Hard
+        } else {
+            Difficulty::VeryHard
+        }
+    }
+}
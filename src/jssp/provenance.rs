@@ -0,0 +1,85 @@
+//! Where a schedule came from, written into exports so results can be traced and
+//! reproduced: the algorithm with its parameters, the seed, how many runs the schedule
+//! is the best of, the wall-clock runtime and the version of this crate.
+//!
+//! The GUI's JSON, CSV, summary and Excel exports, the SVG and PNG charts and the
+//! OR-Tools and JobShopLib solution files carry it, as do `solve --json` and
+//! `solve --output`. Machine orders stay plain for the validators that read them.
+//!
+//! ```
+//! use std::time::Duration;
+//! use jssp_scheduler::jssp::presets::AlgorithmParams;
+//! use jssp_scheduler::jssp::provenance::{RunMetadata, CRATE_VERSION};
+//! use jssp_scheduler::jssp::solution_formats::SolutionFormat;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let parameters = AlgorithmParams::default().algorithm_json("Simulated Annealing");
+//! let run = RunMetadata::new("Simulated Annealing", parameters, Some(42), 1, Duration::from_millis(1500));
+//! let json = run.to_json();
+//! assert_eq!(json["seed"], 42);
+//! assert_eq!(json["parameters"]["simulated_annealing.iterations"], 20000.0);
+//! assert!(json["parameters"].get("genetic_algorithm.generations").is_none());
+//! assert_eq!(json["version"], CRATE_VERSION);
+//! assert!(run.fields().contains(&("Runtime", "1.500 s".to_string())));
+//!
+//! let ft06 = ft06().instance;
+//! let text = SolutionFormat::OrTools.write_with_run(&ft06, &ft06.solve_greedy(), Some(&run)).unwrap();
+//! let document: serde_json::Value = serde_json::from_str(&text).unwrap();
+//! assert_eq!(document["run"]["algorithm"], "Simulated Annealing");
+//! ```
+
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Version of this crate, as recorded with every run
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The run that produced a schedule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunMetadata {
+    pub algorithm: String,
+    /// Parameter values by key (see `presets::Parameter`), with the preset they came from
+    pub parameters: Value,
+    /// Seed of a stochastic algorithm; `None` for deterministic ones
+    pub seed: Option<u64>,
+    /// Independent runs the schedule is the best of
+    pub runs: usize,
+    /// Wall-clock time of the run
+    pub runtime_seconds: f64,
+    pub version: &'static str,
+    /// Whether the schedule was changed by hand or by a repair after the run
+    pub edited: bool,
+}
+
+impl RunMetadata {
+    pub fn new(algorithm: impl Into<String>, parameters: Value, seed: Option<u64>, runs: usize, runtime: Duration) -> Self {
+        Self {
+            algorithm: algorithm.into(),
+            parameters,
+            seed,
+            runs,
+            runtime_seconds: runtime.as_secs_f64(),
+            version: CRATE_VERSION,
+            edited: false,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Label and value pairs, for text and spreadsheet exports
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Algorithm", self.algorithm.clone()),
+            ("Parameters", self.parameters.to_string()),
+            ("Seed", self.seed.map_or_else(|| "-".to_string(), |seed| seed.to_string())),
+            ("Runs", self.runs.to_string()),
+            ("Runtime", format!("{:.3} s", self.runtime_seconds)),
+            ("Version", format!("jssp-scheduler {}", self.version)),
+            ("Edited after the run", if self.edited { "yes" } else { "no" }.to_string()),
+        ]
+    }
+}
@@ -0,0 +1,450 @@
+//! Rebuilding schedules from fixed machine sequences, used for manual edits and for
+//! repairing a schedule after a disruption.
+//!
+//! ```
+//! use jssp_scheduler::jssp::repair::Disruption;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance;
+//! let schedule = ft06.solve_greedy();
+//! let breakdown = Disruption::Breakdown { machine_id: 2, start: 10.0, end: 30.0 };
+//! let repair = ft06.right_shift_repair(&schedule, &breakdown).unwrap();
+//! assert!(ft06.validate(&repair.schedule).is_ok());
+//! assert!(repair.makespan_after > repair.makespan_before);
+//! assert!(!repair.slipped.is_empty());
+//! // Every job that finishes later is listed, and nothing starts earlier than planned
+//! assert!(repair.slipped.iter().all(|slip| slip.delay() > 0.0));
+//! assert!(repair.schedule.iter().zip(&schedule).all(|(after, before)| after.start_time >= before.start_time));
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use super::resources::ResourceUsage;
+use super::{Buffering, Downtime, Job, Instance, ScheduledOperation, TIME_EPSILON};
+
+/// Why a manual move of an operation was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveError {
+    /// The operation is not part of the schedule
+    UnknownOperation { job_id: usize, operation_id: usize },
+    /// The operation cannot be processed on the target machine
+    IneligibleMachine { job_id: usize, operation_id: usize, machine_id: usize },
+    /// The new machine order contradicts the job's operation order
+    Cycle,
+    /// No start times fit the new machine order in a no-wait or blocking instance
+    NoTimetable,
+    /// The operation is pinned where it is
+    Pinned { job_id: usize, operation_id: usize },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::UnknownOperation { job_id, operation_id } => {
+                write!(f, "Job {} Op {} is not in the schedule", job_id, operation_id)
+            }
+            MoveError::IneligibleMachine { job_id, operation_id, machine_id } => {
+                write!(f, "Job {} Op {} cannot run on machine {}", job_id, operation_id, machine_id)
+            }
+            MoveError::Cycle => write!(f, "This machine order conflicts with the job's operation order"),
+            MoveError::NoTimetable => write!(f, "No start times fit this machine order without buffers between machines"),
+            MoveError::Pinned { job_id, operation_id } => {
+                write!(f, "Job {} Op {} is pinned; unpin it to move it", job_id, operation_id)
+            }
+        }
+    }
+}
+
+/// Something that went wrong on the shop floor after a schedule was released
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Disruption {
+    /// The machine is out of order from `start` to `end`
+    Breakdown { machine_id: usize, start: f64, end: f64 },
+    /// The operation takes `extra` longer than planned on its scheduled machine
+    Overrun { job_id: usize, operation_id: usize, extra: f64 },
+}
+
+/// A job that finishes later after a repair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobSlip {
+    pub job_id: usize,
+    pub planned_completion: f64,
+    pub repaired_completion: f64,
+}
+
+impl JobSlip {
+    pub fn delay(&self) -> f64 {
+        self.repaired_completion - self.planned_completion
+    }
+}
+
+/// The outcome of `Instance::right_shift_repair`
+#[derive(Debug, Clone)]
+pub struct RightShiftRepair {
+    pub schedule: Vec<ScheduledOperation>,
+    pub makespan_before: f64,
+    pub makespan_after: f64,
+    /// Jobs that finish later than planned, by job id
+    pub slipped: Vec<JobSlip>,
+}
+
+/// The outcome of `Instance::insert_urgent_job`
+#[derive(Debug, Clone)]
+pub struct JobInsertion {
+    pub schedule: Vec<ScheduledOperation>,
+    /// Id the inserted job got
+    pub job_id: usize,
+    /// Operations that had started before the cursor and kept their place
+    pub frozen: usize,
+    pub makespan_before: f64,
+    pub makespan_after: f64,
+    /// Jobs that finish later than planned, by job id
+    pub slipped: Vec<JobSlip>,
+}
+
+impl JobInsertion {
+    /// When the inserted job is done
+    pub fn completion(&self) -> f64 {
+        completions(&self.schedule).get(&self.job_id).copied().unwrap_or(0.0)
+    }
+}
+
+/// Completion time of every job in the schedule, by job id
+fn completions(schedule: &[ScheduledOperation]) -> HashMap<usize, f64> {
+    let mut completions = HashMap::new();
+    for op in schedule {
+        let completion = completions.entry(op.job_id).or_insert(op.end_time);
+        *completion = f64::max(*completion, op.end_time);
+    }
+    completions
+}
+
+/// Jobs that complete later in `repaired` than in `planned`, by job id
+fn slipped(planned: &[ScheduledOperation], repaired: &[ScheduledOperation]) -> Vec<JobSlip> {
+    let planned = completions(planned);
+    let mut slipped: Vec<JobSlip> = completions(repaired).into_iter()
+        .filter_map(|(job_id, repaired_completion)| {
+            let planned_completion = planned.get(&job_id).copied()?;
+            (repaired_completion > planned_completion + TIME_EPSILON)
+                .then_some(JobSlip { job_id, planned_completion, repaired_completion })
+        })
+        .collect();
+    slipped.sort_by_key(|slip| slip.job_id);
+    slipped
+}
+
+impl Instance {
+    /// Add the disruption to the instance (a downtime window or a longer duration) and
+    /// repair the schedule by right-shifting: every machine keeps its order and no
+    /// operation starts earlier than planned, so only what the disruption pushes back moves.
+    pub fn right_shift_repair(&mut self, schedule: &[ScheduledOperation], disruption: &Disruption) -> Result<RightShiftRepair, String> {
+        match *disruption {
+            Disruption::Breakdown { machine_id, start, end } => {
+                let mut downtime = self.downtime().to_vec();
+                downtime.push(Downtime { machine_id, start, end });
+                self.set_downtime(downtime)?;
+            }
+            Disruption::Overrun { job_id, operation_id, extra } => {
+                let machine_id = schedule.iter()
+                    .find(|op| (op.job_id, op.operation_id) == (job_id, operation_id))
+                    .ok_or_else(|| MoveError::UnknownOperation { job_id, operation_id }.to_string())?
+                    .machine_id;
+                self.extend_operation(job_id, operation_id, machine_id, extra)?;
+            }
+        }
+
+        let planned_starts = schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.start_time))
+            .collect();
+        let repaired = self.schedule_from_sequences(&self.machine_sequences(schedule), &planned_starts)
+            .ok_or_else(|| MoveError::NoTimetable.to_string())?;
+
+        Ok(RightShiftRepair {
+            makespan_before: self.calculate_makespan(schedule),
+            makespan_after: self.calculate_makespan(&repaired),
+            slipped: slipped(schedule, &repaired),
+            schedule: repaired,
+        })
+    }
+
+    /// Add an urgent job to the instance and fit it into a running schedule. Operations
+    /// that started before `now` are frozen where they are; the urgent job goes ahead of
+    /// every other operation still to come, each of its operations on the eligible
+    /// machine where it finishes first, and the rest of the future keeps its machine
+    /// orders but is rescheduled as early as possible from `now` on.
+    ///
+    /// ```
+    /// use jssp_scheduler::jssp::{Job, Operation};
+    /// use jssp_scheduler::jssp::testing::ft06;
+    ///
+    /// let mut ft06 = ft06().instance;
+    /// let schedule = ft06.solve_greedy();
+    /// let operations = [(2, 5.0), (0, 3.0)].iter().enumerate()
+    ///     .map(|(operation_id, &(machine_id, duration))| Operation { job_id: 0, operation_id, machine_id, duration, alternatives: Vec::new() })
+    ///     .collect();
+    /// let rush = Job { id: 0, operations, release_time: 0.0, due_date: Some(40.0), weight: 1.0 };
+    /// let insertion = ft06.insert_urgent_job(&schedule, rush, 20.0).unwrap();
+    /// assert_eq!(insertion.job_id, 6);
+    /// assert!(ft06.validate(&insertion.schedule).is_ok());
+    /// // What had started by 20 stays put, and nothing else starts before 20
+    /// let frozen: Vec<_> = schedule.iter().filter(|op| op.start_time < 20.0).collect();
+    /// assert_eq!(insertion.frozen, frozen.len());
+    /// assert!(frozen.iter().all(|op| insertion.schedule.iter().any(|after| after.job_id == op.job_id
+    ///     && after.operation_id == op.operation_id && after.start_time == op.start_time)));
+    /// assert!(insertion.schedule.iter().filter(|op| op.job_id == 6).all(|op| op.start_time >= 20.0));
+    /// assert!(insertion.completion() <= 40.0);
+    /// ```
+    pub fn insert_urgent_job(&mut self, schedule: &[ScheduledOperation], job: Job, now: f64) -> Result<JobInsertion, String> {
+        if schedule.len() != self.num_operations() {
+            return Err("The urgent job can only be inserted into a complete schedule".to_string());
+        }
+        let mut instance = self.clone();
+        let job_id = instance.add_job(job)?;
+
+        let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+        ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        let (frozen, future): (Vec<&ScheduledOperation>, Vec<&ScheduledOperation>) =
+            ops.into_iter().partition(|op| op.start_time < now);
+        let mut sequences = vec![Vec::new(); instance.num_machines()];
+        for op in &frozen {
+            sequences[op.machine_id].push((op.job_id, op.operation_id));
+        }
+
+        // The urgent job takes the earliest finishing machine after the frozen work
+        let mut machine_free = vec![now; instance.num_machines()];
+        let mut last_job: Vec<Option<usize>> = vec![None; instance.num_machines()];
+        for op in &frozen {
+            machine_free[op.machine_id] = machine_free[op.machine_id].max(op.end_time);
+            last_job[op.machine_id] = Some(op.job_id);
+        }
+        let urgent = &instance.jobs()[job_id];
+        let mut ready = urgent.release_time.max(now);
+        let mut previous_machine = None;
+        for operation in &urgent.operations {
+            let (machine_id, end_time) = operation.options()
+                .map(|option| {
+                    let machine_id = option.machine_id;
+                    let setup_time = instance.setup_time(machine_id, last_job[machine_id], job_id);
+                    let earliest = (ready + instance.transfer_time(previous_machine, machine_id)).max(machine_free[machine_id] + setup_time);
+                    let start_time = instance.preemptive_start(machine_id, earliest, setup_time, option.duration);
+                    (machine_id, instance.processing_end(machine_id, start_time, option.duration))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .ok_or_else(|| format!("Operation {} of the urgent job has no machine", operation.operation_id))?;
+            sequences[machine_id].push((job_id, operation.operation_id));
+            machine_free[machine_id] = end_time;
+            last_job[machine_id] = Some(job_id);
+            ready = end_time;
+            previous_machine = Some(machine_id);
+        }
+
+        for op in &future {
+            sequences[op.machine_id].push((op.job_id, op.operation_id));
+        }
+
+        let mut earliest_starts: HashMap<(usize, usize), f64> = frozen.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.start_time))
+            .collect();
+        earliest_starts.extend(future.iter().map(|op| ((op.job_id, op.operation_id), now)));
+        earliest_starts.extend((0..urgent.operations.len()).map(|operation_id| ((job_id, operation_id), now)));
+        let rescheduled = instance.schedule_from_sequences(&sequences, &earliest_starts)
+            .ok_or_else(|| MoveError::NoTimetable.to_string())?;
+        let moved_frozen = frozen.iter().any(|op| {
+            rescheduled.iter()
+                .find(|after| (after.job_id, after.operation_id) == (op.job_id, op.operation_id))
+                .is_none_or(|after| (after.start_time - op.start_time).abs() > TIME_EPSILON)
+        });
+        if moved_frozen {
+            return Err("Operations that have already started cannot keep their times with the urgent job".to_string());
+        }
+
+        let insertion = JobInsertion {
+            job_id,
+            frozen: frozen.len(),
+            makespan_before: self.calculate_makespan(schedule),
+            makespan_after: instance.calculate_makespan(&rescheduled),
+            slipped: slipped(schedule, &rescheduled),
+            schedule: rescheduled,
+        };
+        *self = instance;
+        Ok(insertion)
+    }
+
+    fn job_by_id(&self, job_id: usize) -> Option<&Job> {
+        self.jobs().iter().find(|job| job.id == job_id)
+    }
+
+    /// (job_id, operation_id) pairs on every machine, ordered by start time
+    pub fn machine_sequences(&self, schedule: &[ScheduledOperation]) -> Vec<Vec<(usize, usize)>> {
+        let num_machines = schedule.iter()
+            .map(|op| op.machine_id + 1)
+            .max()
+            .unwrap_or(0)
+            .max(self.num_machines());
+        let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+        ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+        let mut sequences = vec![Vec::new(); num_machines];
+        for op in ops {
+            sequences[op.machine_id].push((op.job_id, op.operation_id));
+        }
+        sequences
+    }
+
+    /// Earliest start times that respect job order, the given order on each machine
+    /// (indexed by machine id), setups, transfers, release times, machine downtime and
+    /// secondary resources, which go to operations in the order they are timed. `earliest_starts` adds lower
+    /// bounds for individual operations. Returns `None` if the machine orders contradict
+    /// job precedence, list an operation twice or one the instance does not have, or
+    /// admit no start times without buffers (see `buffering`). An open shop has no job
+    /// order to respect; its jobs take their operations in the order they can start (see `shop`).
+    pub fn schedule_from_sequences(
+        &self,
+        sequences: &[Vec<(usize, usize)>],
+        earliest_starts: &HashMap<(usize, usize), f64>,
+    ) -> Option<Vec<ScheduledOperation>> {
+        if self.buffering != Buffering::Unlimited {
+            return self.timetable_without_buffers(sequences, earliest_starts);
+        }
+        if self.is_open_shop() {
+            return self.open_shop_timetable(sequences, earliest_starts);
+        }
+        // Per operation, by `operation_index`: its key, machine and machine neighbours
+        let count = self.num_operations();
+        let mut keys: Vec<Option<(usize, usize)>> = vec![None; count];
+        let mut machine_of = vec![0; count];
+        let mut machine_pred: Vec<Option<usize>> = vec![None; count];
+        let mut machine_succ: Vec<Option<usize>> = vec![None; count];
+        // Pinned operations are where they are pinned, outside the machine orders
+        let mut scheduled: Vec<Option<ScheduledOperation>> = vec![None; count];
+        let pins = self.uses_pins();
+        let mut listed = 0;
+        for (machine_id, sequence) in sequences.iter().enumerate() {
+            let mut previous = None;
+            for &(job_id, operation_id) in sequence {
+                let node = self.operation_index(job_id, operation_id)?;
+                if keys[node].is_some() || scheduled[node].is_some() {
+                    return None;
+                }
+                if let Some(pin) = self.pin(job_id, operation_id).filter(|_| pins) {
+                    scheduled[node] = Some(pin.clone());
+                    continue;
+                }
+                keys[node] = Some((job_id, operation_id));
+                listed += 1;
+                machine_of[node] = machine_id;
+                machine_pred[node] = previous;
+                if let Some(pred) = previous {
+                    machine_succ[pred] = Some(node);
+                }
+                previous = Some(node);
+            }
+        }
+
+        // In-degrees over job and machine arcs; an operation's job successor is the next node
+        let job_pred = |node: usize| match keys[node] {
+            Some((_, operation_id)) if operation_id > 0 && keys[node - 1].is_some() => Some(node - 1),
+            _ => None,
+        };
+        let job_succ = |node: usize| (node + 1 < count && keys[node + 1].is_some_and(|(_, operation_id)| operation_id > 0)).then_some(node + 1);
+        let mut in_degree: Vec<usize> = (0..count)
+            .map(|node| job_pred(node).is_some() as usize + machine_pred[node].is_some() as usize)
+            .collect();
+
+        let mut ready: VecDeque<usize> = (0..count).filter(|&node| keys[node].is_some() && in_degree[node] == 0).collect();
+        let mut placed = 0;
+        let mut resources = ResourceUsage::new(self);
+
+        while let Some(node) = ready.pop_front() {
+            let Some(key @ (job_id, operation_id)) = keys[node] else {
+                continue;
+            };
+            let machine_id = machine_of[node];
+            let duration = self.duration_at(node, machine_id);
+
+            let mut start_time = earliest_starts.get(&key).copied().unwrap_or(0.0);
+            if operation_id == 0 {
+                start_time = start_time.max(self.jobs()[job_id].release_time);
+            } else if let Some(prev) = scheduled[node - 1].as_ref() {
+                start_time = start_time.max(prev.end_time + self.transfer_time(Some(prev.machine_id), machine_id));
+            }
+            let mut setup_time = 0.0;
+            if let Some(pred) = machine_pred[node].and_then(|pred| scheduled[pred].as_ref()) {
+                setup_time = self.setup_time(machine_id, Some(pred.job_id), job_id);
+                start_time = start_time.max(pred.end_time + setup_time);
+            }
+            start_time = self.resource_start(&resources, key, machine_id, start_time, setup_time, duration);
+
+            let timed = self.timed_operation(job_id, operation_id, machine_id, start_time, duration, setup_time);
+            resources.reserve(self, &timed);
+            scheduled[node] = Some(timed);
+            placed += 1;
+
+            for next in [job_succ(node), machine_succ[node]].into_iter().flatten() {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+
+        if placed < listed {
+            return None;
+        }
+        // Flat indices run job by job, so the schedule comes out sorted
+        let mut schedule: Vec<ScheduledOperation> = scheduled.into_iter().flatten().collect();
+        if pins {
+            self.settle_pinned_setups(&mut schedule);
+        }
+        Some(schedule)
+    }
+
+    /// Move one operation to `target_machine`, starting no earlier than `target_start`.
+    ///
+    /// The operation is inserted into the target machine's order at the given time; all
+    /// other machine orders are kept and start times are recomputed as early as possible.
+    /// Pinned operations cannot be moved.
+    pub fn move_operation(
+        &self,
+        schedule: &[ScheduledOperation],
+        operation: (usize, usize),
+        target_machine: usize,
+        target_start: f64,
+    ) -> Result<Vec<ScheduledOperation>, MoveError> {
+        let (job_id, operation_id) = operation;
+        if !schedule.iter().any(|op| (op.job_id, op.operation_id) == operation) {
+            return Err(MoveError::UnknownOperation { job_id, operation_id });
+        }
+        if self.uses_pins() && self.pin(job_id, operation_id).is_some() {
+            return Err(MoveError::Pinned { job_id, operation_id });
+        }
+        let eligible = self.job_by_id(job_id)
+            .and_then(|job| job.operations.get(operation_id))
+            .and_then(|op| op.duration_on(target_machine))
+            .is_some();
+        if !eligible {
+            return Err(MoveError::IneligibleMachine { job_id, operation_id, machine_id: target_machine });
+        }
+
+        let mut sequences = self.machine_sequences(schedule);
+        for sequence in &mut sequences {
+            sequence.retain(|&key| key != operation);
+        }
+        if target_machine >= sequences.len() {
+            sequences.resize(target_machine + 1, Vec::new());
+        }
+
+        let start_of: HashMap<(usize, usize), f64> = schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.start_time))
+            .collect();
+        let position = sequences[target_machine].iter()
+            .take_while(|key| start_of[key] < target_start)
+            .count();
+        sequences[target_machine].insert(position, operation);
+
+        let earliest_starts = HashMap::from([(operation, target_start.max(0.0))]);
+        self.schedule_from_sequences(&sequences, &earliest_starts)
+            .ok_or(if self.buffering == Buffering::Unlimited { MoveError::Cycle } else { MoveError::NoTimetable })
+    }
+}
@@ -0,0 +1,221 @@
+//! Secondary resources: operators, tools or fixtures an operation needs besides its machine.
+//!
+//! In a dual-resource constrained shop a machine cannot run without, say, a qualified
+//! operator, and there are fewer operators than machines. A `SecondaryResource` is
+//! such a pool of interchangeable units with a capacity; an operation that requires
+//! it holds one unit from its start to its end (its setup does not need one). Two
+//! operations on different machines that need the same single operator can therefore
+//! not overlap, and no more operations than the capacity use a pool at once.
+//!
+//! `Instance::decode`, `Instance::schedule_from_sequences` and with them every solver
+//! delay an operation until a unit is free; searches that rate moves on the disjunctive
+//! graph re-time their schedules instead (see `Instance::enforce_buffering`).
+//! Resources only apply with unlimited buffers; no-wait and blocking shops ignore them.
+//!
+//! ```
+//! use jssp_scheduler::jssp::resources::{ResourceRequirement, SecondaryResource};
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let mut ft06 = ft06().instance;
+//! let requirements: Vec<ResourceRequirement> = ft06.jobs().iter()
+//!     .flat_map(|job| &job.operations)
+//!     .map(|op| ResourceRequirement { job_id: op.job_id, operation_id: op.operation_id, resource_id: 0 })
+//!     .collect();
+//!
+//! // Two operators run the whole shop: never more than two operations at once
+//! ft06.set_resources(vec![SecondaryResource::new("Operators", 2)], requirements.clone()).unwrap();
+//! let schedule = ft06.solve_greedy_randomized(20, 1);
+//! assert!(ft06.validate(&schedule).is_ok());
+//! assert_eq!(ft06.resource_lanes(&schedule)[0].len(), 2);
+//!
+//! // With a single operator, the operations run one after another
+//! ft06.set_resources(vec![SecondaryResource::new("Operator", 1)], requirements).unwrap();
+//! let total: f64 = ft06.jobs().iter().flat_map(|job| &job.operations).map(|op| op.duration).sum();
+//! assert_eq!(ft06.calculate_makespan(&ft06.solve_greedy()), total);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::{Buffering, Instance, ScheduledOperation, TIME_EPSILON};
+
+/// A pool of `capacity` interchangeable units, e.g. operators
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecondaryResource {
+    pub name: String,
+    pub capacity: usize,
+}
+
+impl SecondaryResource {
+    pub fn new(name: impl Into<String>, capacity: usize) -> Self {
+        Self { name: name.into(), capacity }
+    }
+}
+
+/// An operation that needs a unit of `resource_id` while it is processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceRequirement {
+    pub job_id: usize,
+    pub operation_id: usize,
+    pub resource_id: usize,
+}
+
+/// When the units of every resource are taken, for placing operations one at a time in
+/// any order
+#[derive(Debug, Clone, Default)]
+pub struct ResourceUsage {
+    /// (start, end) of every reservation, by resource
+    busy: Vec<Vec<(f64, f64)>>,
+}
+
+impl ResourceUsage {
+    /// Nothing reserved yet but what pinned operations hold
+    pub fn new(instance: &Instance) -> Self {
+        let resources = if instance.uses_resources() { instance.resources().len() } else { 0 };
+        let mut usage = Self { busy: vec![Vec::new(); resources] };
+        if instance.uses_pins() {
+            for pin in instance.pins() {
+                usage.take(instance, pin);
+            }
+        }
+        usage
+    }
+
+    /// Most units of `resource_id` in use at once between `from` and `to`
+    fn peak(&self, resource_id: usize, from: f64, to: f64) -> usize {
+        let mut events: Vec<(f64, i32)> = Vec::new();
+        for &(start, end) in &self.busy[resource_id] {
+            if start < to - TIME_EPSILON && end > from + TIME_EPSILON {
+                events.push((start.max(from), 1));
+                events.push((end.min(to), -1));
+            }
+        }
+        // A unit freed at an instant can be taken at that same instant
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let (mut current, mut peak) = (0, 0);
+        for (_, change) in events {
+            current += change;
+            peak = peak.max(current);
+        }
+        peak as usize
+    }
+
+    /// Earliest time at or after `start` from which a unit of `resource_id` is free for
+    /// `length`
+    fn earliest_free(&self, resource_id: usize, capacity: usize, start: f64, length: f64) -> f64 {
+        let mut start = start;
+        while self.peak(resource_id, start, start + length) >= capacity {
+            // No start before the first reservation in the way ends can do better
+            start = self.busy[resource_id].iter()
+                .filter(|&&(from, end)| from < start + length - TIME_EPSILON && end > start + TIME_EPSILON)
+                .map(|&(_, end)| end)
+                .fold(f64::INFINITY, f64::min);
+        }
+        start
+    }
+
+    /// Take a unit of the resource `op` needs, if any, for as long as it runs; pinned
+    /// operations hold theirs from the start
+    pub fn reserve(&mut self, instance: &Instance, op: &ScheduledOperation) {
+        if !(instance.uses_pins() && instance.pin(op.job_id, op.operation_id).is_some()) {
+            self.take(instance, op);
+        }
+    }
+
+    fn take(&mut self, instance: &Instance, op: &ScheduledOperation) {
+        if let Some(resource_id) = instance.resource_of(op.job_id, op.operation_id).filter(|&id| id < self.busy.len()) {
+            self.busy[resource_id].push((op.start_time, op.end_time));
+        }
+    }
+}
+
+impl Instance {
+    /// Declare the secondary resources and which operations need one. Every resource
+    /// needs a capacity of at least 1, and an operation may need at most one resource.
+    pub fn set_resources(&mut self, resources: Vec<SecondaryResource>, requirements: Vec<ResourceRequirement>) -> Result<(), String> {
+        if let Some(resource) = resources.iter().find(|resource| resource.capacity == 0) {
+            return Err(format!("Resource \"{}\" needs a capacity of at least 1", resource.name));
+        }
+        let mut resource_of = vec![None; self.num_operations()];
+        for requirement in &requirements {
+            let (job_id, operation_id) = (requirement.job_id, requirement.operation_id);
+            let index = self.operation_index(job_id, operation_id)
+                .ok_or_else(|| format!("Resource required by job {} operation {}, which does not exist", job_id, operation_id))?;
+            if requirement.resource_id >= resources.len() {
+                return Err(format!(
+                    "Job {} operation {} requires resource {}, but there are {} resources",
+                    job_id, operation_id, requirement.resource_id, resources.len()
+                ));
+            }
+            if resource_of[index].replace(requirement.resource_id).is_some() {
+                return Err(format!("Job {} operation {} requires more than one resource", job_id, operation_id));
+            }
+        }
+        let resources = resources.into_iter()
+            .map(|resource| SecondaryResource { name: resource.name.trim().to_string(), ..resource })
+            .collect();
+        self.set_resource_data(resources, resource_of);
+        Ok(())
+    }
+
+    /// Whether operations wait for secondary resources on this instance
+    pub fn uses_resources(&self) -> bool {
+        !self.resources().is_empty() && self.buffering == Buffering::Unlimited
+    }
+
+    /// Name of a resource, "Resource <id>" if it has none
+    pub fn resource_name(&self, resource_id: usize) -> String {
+        self.resources().get(resource_id)
+            .map(|resource| resource.name.clone())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("Resource {}", resource_id))
+    }
+
+    /// Every operation that needs a resource, job by job
+    pub fn resource_requirements(&self) -> Vec<ResourceRequirement> {
+        self.jobs().iter()
+            .flat_map(|job| &job.operations)
+            .filter_map(|op| {
+                self.resource_of(op.job_id, op.operation_id)
+                    .map(|resource_id| ResourceRequirement { job_id: op.job_id, operation_id: op.operation_id, resource_id })
+            })
+            .collect()
+    }
+
+    /// Like `preemptive_start`, but also waiting until a unit of the resource the
+    /// operation needs is free for as long as it runs
+    pub fn resource_start(&self, usage: &ResourceUsage, key: (usize, usize), machine_id: usize, start: f64, setup: f64, duration: f64) -> f64 {
+        let mut start = self.preemptive_start(machine_id, start, setup, duration);
+        let Some(resource_id) = self.resource_of(key.0, key.1).filter(|&id| id < usage.busy.len()) else {
+            return start;
+        };
+        let capacity = self.resources()[resource_id].capacity;
+        loop {
+            let length = self.processing_end(machine_id, start, duration) - start;
+            let free = usage.earliest_free(resource_id, capacity, start, length);
+            if free <= start + TIME_EPSILON {
+                return start;
+            }
+            start = self.preemptive_start(machine_id, free, setup, duration);
+        }
+    }
+
+    /// The operations of `schedule` that hold a unit of each resource, spread over as
+    /// few lanes per resource as overlaps allow (as many as the capacity when the
+    /// schedule is feasible); every lane is ordered by start time
+    pub fn resource_lanes<'a>(&self, schedule: &'a [ScheduledOperation]) -> Vec<Vec<Vec<&'a ScheduledOperation>>> {
+        let mut lanes: Vec<Vec<Vec<&ScheduledOperation>>> = vec![Vec::new(); self.resources().len()];
+        let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+        ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        for op in ops {
+            let Some(resource_id) = self.resource_of(op.job_id, op.operation_id) else {
+                continue;
+            };
+            let lanes = &mut lanes[resource_id];
+            match lanes.iter_mut().find(|lane| lane.last().is_none_or(|last| last.end_time <= op.start_time + TIME_EPSILON)) {
+                Some(lane) => lane.push(op),
+                None => lanes.push(vec![op]),
+            }
+        }
+        lanes
+    }
+}
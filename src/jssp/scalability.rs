@@ -0,0 +1,116 @@
+//! How a solver's runtime grows with the instance size.
+//!
+//! A `ScalabilityTest` solves one generated instance per size, from 10 jobs on 5
+//! machines up to 200 on 50, and records the runtime against the number of operations.
+//! Sizes are run one at a time (`run_next`) so a caller can report progress, or time
+//! something else on each solved instance, such as drawing its schedule.
+//!
+//! ```
+//! use jssp_scheduler::jssp::scalability::ScalabilityTest;
+//! use jssp_scheduler::jssp::Greedy;
+//!
+//! let mut test = ScalabilityTest::new(&Greedy, &[(10, 5), (20, 10)]);
+//! while let Some((instance, schedule)) = test.run_next(&Greedy) {
+//!     assert!(instance.validate(&schedule).is_ok());
+//! }
+//! assert!(test.is_complete());
+//! assert_eq!(test.points[1].operations, 200);
+//! assert!(test.points.iter().all(|point| point.feasible));
+//! ```
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use super::{generate_instance, GeneratorConfig, Instance, Schedule, Solver};
+
+/// Jobs × machines of the default test, smallest first
+pub const SCALABILITY_SIZES: [(usize, usize); 7] = [(10, 5), (20, 5), (20, 10), (50, 10), (100, 20), (200, 20), (200, 50)];
+
+/// Outcome at one instance size
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalabilityPoint {
+    pub jobs: usize,
+    pub machines: usize,
+    pub operations: usize,
+    pub makespan: f64,
+    pub feasible: bool,
+    pub solve_time: Duration,
+    /// Time to draw the schedule once, if the caller measured it
+    pub render_time: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalabilityTest {
+    pub solver: String,
+    /// Sizes to run, as jobs × machines
+    pub sizes: Vec<(usize, usize)>,
+    pub points: Vec<ScalabilityPoint>,
+}
+
+impl ScalabilityTest {
+    pub fn new(solver: &dyn Solver, sizes: &[(usize, usize)]) -> Self {
+        Self { solver: solver.name().to_string(), sizes: sizes.to_vec(), points: Vec::with_capacity(sizes.len()) }
+    }
+
+    /// The size `run_next` will solve
+    pub fn next_size(&self) -> Option<(usize, usize)> {
+        self.sizes.get(self.points.len()).copied()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.points.len() >= self.sizes.len()
+    }
+
+    /// Fraction of the sizes done, in `0..=1`
+    pub fn progress(&self) -> f32 {
+        if self.sizes.is_empty() { 1.0 } else { (self.points.len() as f32 / self.sizes.len() as f32).min(1.0) }
+    }
+
+    /// Generate and solve an instance of the next size, returning it with its schedule;
+    /// `None` once every size is done
+    pub fn run_next(&mut self, solver: &dyn Solver) -> Option<(Instance, Schedule)> {
+        let (jobs, machines) = self.next_size()?;
+        let instance = generate_instance(&GeneratorConfig { num_jobs: jobs, num_machines: machines, ..GeneratorConfig::default() });
+        let started = Instant::now();
+        let schedule = solver.solve(&instance);
+        let solve_time = started.elapsed();
+        self.points.push(ScalabilityPoint {
+            jobs,
+            machines,
+            operations: instance.num_operations(),
+            makespan: instance.calculate_makespan(&schedule),
+            feasible: instance.validate(&schedule).is_ok(),
+            solve_time,
+            render_time: None,
+        });
+        Some((instance, schedule))
+    }
+
+    /// The first size at which drawing the schedule took longer than `budget`, the time
+    /// one frame may take
+    pub fn render_bottleneck(&self, budget: Duration) -> Option<&ScalabilityPoint> {
+        self.points.iter().find(|point| point.render_time.is_some_and(|render| render > budget))
+    }
+
+    /// One row per size
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("Solver,Jobs,Machines,Operations,Makespan,Feasible,Solve Time (ms),Render Time (ms)\n");
+        for point in &self.points {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{:.2},{},{:.3},{}",
+                self.solver,
+                point.jobs,
+                point.machines,
+                point.operations,
+                point.makespan,
+                point.feasible,
+                point.solve_time.as_secs_f64() * 1000.0,
+                point.render_time.map_or(String::new(), |render| format!("{:.3}", render.as_secs_f64() * 1000.0)),
+            );
+        }
+        csv
+    }
+}
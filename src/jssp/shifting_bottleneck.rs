@@ -0,0 +1,280 @@
+//! The shifting bottleneck heuristic.
+//!
+//! Machines are sequenced one at a time. With the machines sequenced so far fixed in the
+//! disjunctive graph, every remaining machine is relaxed to a one-machine problem: each
+//! of its operations gets a head (earliest start, the longest path into it) and a tail
+//! (the longest path out of it), and Schrage's rule sequences the machine to minimize
+//! the latest completion plus tail. The machine whose one-machine problem ends latest is
+//! the bottleneck; its sequence is fixed, and every machine fixed before it is then
+//! re-sequenced in turn, keeping the new order if the makespan does not grow.
+//!
+//! The one-machine problems ignore setups and downtime, and flexible operations keep the
+//! machine a greedy schedule gives them; the final schedule is rebuilt from the machine
+//! sequences with every constraint (see `schedule_from_sequences`).
+//!
+//! ```
+//! use jssp_scheduler::jssp::shifting_bottleneck::ShiftingBottleneck;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Solver;
+//!
+//! let ft06 = ft06().instance;
+//! let schedule = ShiftingBottleneck.solve(&ft06);
+//! assert!(ft06.validate(&schedule).is_ok());
+//! assert!(ft06.calculate_makespan(&schedule) < ft06.calculate_makespan(&ft06.solve_greedy()));
+//! ```
+
+use std::collections::HashMap;
+
+use super::solvers::Greedy;
+use super::{Buffering, Instance, Schedule, Solver, SolverInfo, TIME_EPSILON};
+
+/// Rounds of re-sequencing the fixed machines after each new bottleneck
+const REOPTIMIZE_ROUNDS: usize = 2;
+
+pub struct ShiftingBottleneck;
+
+/// An operation with its machine fixed, indexed job by job
+struct Task {
+    job_id: usize,
+    operation_id: usize,
+    machine_id: usize,
+    duration: f64,
+    release_time: f64,
+    /// Index of the next operation of the job
+    next: Option<usize>,
+    /// Travel time to the machine of the next operation of the job
+    transfer: f64,
+}
+
+/// The fixed-machine problem with the sequences chosen so far
+struct Graph {
+    tasks: Vec<Task>,
+    /// Tasks on each machine
+    machine_tasks: Vec<Vec<usize>>,
+    /// Fixed order of each sequenced machine
+    sequences: Vec<Option<Vec<usize>>>,
+}
+
+/// Heads and tails of every task, and the makespan they imply
+struct Paths {
+    heads: Vec<f64>,
+    tails: Vec<f64>,
+    makespan: f64,
+}
+
+impl Graph {
+    fn new(instance: &Instance) -> Self {
+        let greedy = Greedy.solve(instance);
+        let machine_of: HashMap<(usize, usize), usize> = greedy.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.machine_id))
+            .collect();
+        let mut tasks = Vec::with_capacity(instance.num_operations());
+        for job in instance.jobs() {
+            let first = tasks.len();
+            for (operation_id, op) in job.operations.iter().enumerate() {
+                let machine_id = machine_of.get(&(job.id, operation_id)).copied().unwrap_or(op.machine_id);
+                tasks.push(Task {
+                    job_id: job.id,
+                    operation_id,
+                    machine_id,
+                    duration: op.duration_on(machine_id).unwrap_or(op.duration),
+                    release_time: job.release_time,
+                    next: (operation_id + 1 < job.operations.len()).then_some(first + operation_id + 1),
+                    transfer: 0.0,
+                });
+            }
+        }
+        for index in 0..tasks.len() {
+            if let Some(next) = tasks[index].next {
+                tasks[index].transfer = instance.transfer_time(Some(tasks[index].machine_id), tasks[next].machine_id);
+            }
+        }
+        let mut machine_tasks = vec![Vec::new(); instance.num_machines()];
+        for (index, task) in tasks.iter().enumerate() {
+            machine_tasks[task.machine_id].push(index);
+        }
+        Self { tasks, sequences: vec![None; machine_tasks.len()], machine_tasks }
+    }
+
+    /// Longest paths over the job arcs (with their transfers) and the arcs of the
+    /// sequenced machines, or `None` if the sequences close a cycle
+    fn paths(&self) -> Option<Paths> {
+        let n = self.tasks.len();
+        // Every successor with the gap it needs after the task ends
+        let mut successors: Vec<Vec<(usize, f64)>> = self.tasks.iter()
+            .map(|task| task.next.map(|next| (next, task.transfer)).into_iter().collect())
+            .collect();
+        for sequence in self.sequences.iter().flatten() {
+            for pair in sequence.windows(2) {
+                successors[pair[0]].push((pair[1], 0.0));
+            }
+        }
+        let mut in_degree = vec![0; n];
+        for &(successor, _) in successors.iter().flatten() {
+            in_degree[successor] += 1;
+        }
+        let mut order: Vec<usize> = (0..n).filter(|&task| in_degree[task] == 0).collect();
+        let mut index = 0;
+        while let Some(&task) = order.get(index) {
+            index += 1;
+            for &(successor, _) in &successors[task] {
+                in_degree[successor] -= 1;
+                if in_degree[successor] == 0 {
+                    order.push(successor);
+                }
+            }
+        }
+        if order.len() < n {
+            return None;
+        }
+
+        let mut heads: Vec<f64> = self.tasks.iter().map(|task| task.release_time).collect();
+        for &task in &order {
+            let end = heads[task] + self.tasks[task].duration;
+            for &(successor, gap) in &successors[task] {
+                heads[successor] = heads[successor].max(end + gap);
+            }
+        }
+        let mut tails = vec![0.0; n];
+        for &task in order.iter().rev() {
+            tails[task] = successors[task].iter()
+                .map(|&(successor, gap)| gap + self.tasks[successor].duration + tails[successor])
+                .fold(0.0, f64::max);
+        }
+        let makespan = (0..n).map(|task| heads[task] + self.tasks[task].duration + tails[task]).fold(0.0, f64::max);
+        Some(Paths { heads, tails, makespan })
+    }
+
+    /// Schrage's rule on one machine: whenever the machine is free, start the released
+    /// task with the longest tail. Returns the order and its latest completion plus tail.
+    fn schrage(&self, machine_id: usize, paths: &Paths) -> (Vec<usize>, f64) {
+        let mut pending = self.machine_tasks[machine_id].clone();
+        pending.sort_by(|&a, &b| paths.heads[a].total_cmp(&paths.heads[b]));
+        let mut sequence = Vec::with_capacity(pending.len());
+        let mut ready: Vec<usize> = Vec::new();
+        let (mut time, mut value) = (0.0, 0.0_f64);
+        let mut next = 0;
+        while sequence.len() < pending.len() {
+            while next < pending.len() && paths.heads[pending[next]] <= time + TIME_EPSILON {
+                ready.push(pending[next]);
+                next += 1;
+            }
+            // Ties go to the earlier head, so zero-length tasks keep their path order
+            let Some(position) = (0..ready.len()).max_by(|&a, &b| {
+                let (a, b) = (ready[a], ready[b]);
+                paths.tails[a].total_cmp(&paths.tails[b]).then(paths.heads[b].total_cmp(&paths.heads[a]))
+            }) else {
+                time = paths.heads[pending[next]];
+                continue;
+            };
+            let task = ready.swap_remove(position);
+            time = time.max(paths.heads[task]) + self.tasks[task].duration;
+            value = value.max(time + paths.tails[task]);
+            sequence.push(task);
+        }
+        (sequence, value)
+    }
+
+    /// Re-sequence each fixed machine with the others' arcs in place, keeping an order
+    /// only if the makespan does not grow
+    fn reoptimize(&mut self, fixed: &[usize]) {
+        let Some(mut makespan) = self.paths().map(|paths| paths.makespan) else {
+            return;
+        };
+        for _ in 0..REOPTIMIZE_ROUNDS {
+            let mut improved = false;
+            for &machine_id in fixed {
+                let previous = self.sequences[machine_id].take();
+                let Some(paths) = self.paths() else {
+                    self.sequences[machine_id] = previous;
+                    continue;
+                };
+                let (sequence, _) = self.schrage(machine_id, &paths);
+                self.sequences[machine_id] = Some(sequence);
+                match self.paths() {
+                    Some(paths) if paths.makespan <= makespan + TIME_EPSILON => {
+                        improved |= paths.makespan < makespan - TIME_EPSILON;
+                        makespan = paths.makespan;
+                    }
+                    _ => self.sequences[machine_id] = previous,
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+    }
+}
+
+impl ShiftingBottleneck {
+    /// Machine sequences as (job, operation) pairs, or `None` if they could not be
+    /// completed without a cycle
+    fn sequences(instance: &Instance) -> Option<Vec<Vec<(usize, usize)>>> {
+        let mut graph = Graph::new(instance);
+        let mut fixed = Vec::new();
+        loop {
+            let paths = graph.paths()?;
+            let bottleneck = (0..graph.machine_tasks.len())
+                .filter(|&machine_id| graph.sequences[machine_id].is_none() && !graph.machine_tasks[machine_id].is_empty())
+                .map(|machine_id| (machine_id, graph.schrage(machine_id, &paths)))
+                .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b));
+            let Some((machine_id, (sequence, _))) = bottleneck else {
+                break;
+            };
+            graph.sequences[machine_id] = Some(sequence);
+            graph.reoptimize(&fixed);
+            fixed.push(machine_id);
+        }
+        Some(graph.sequences.iter()
+            .map(|sequence| {
+                sequence.iter().flatten()
+                    .map(|&task| (graph.tasks[task].job_id, graph.tasks[task].operation_id))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+impl Solver for ShiftingBottleneck {
+    fn name(&self) -> &str {
+        "Shifting Bottleneck"
+    }
+
+    fn description(&self) -> &str {
+        "Sequences the most critical machine first, as a one-machine problem, and re-optimizes the machines sequenced before it"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "Repeatedly relaxes every unsequenced machine to a one-machine problem with heads and tails \
+                      taken from the disjunctive graph, sequences each by Schrage's rule, and fixes the machine \
+                      whose sequence ends latest: the current bottleneck. After each step the machines fixed \
+                      earlier are re-sequenced against the new arcs. Deterministic, and usually far better than \
+                      dispatching rules at a fraction of a metaheuristic's cost.",
+            complexity: "O(M^2 (N + n log n) · R) for M machines, n operations per machine and R re-optimization rounds",
+            recommended_sizes: "Any size up to thousands of operations; strongest on classic job shops",
+            citation: Some("Adams, J., Balas, E., & Zawack, D. (1988). The shifting bottleneck procedure for job shop scheduling. Management Science, 34(3), 391-401."),
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        let Some(sequences) = Self::sequences(instance) else {
+            return Greedy.solve(instance);
+        };
+        if let Some(schedule) = instance.schedule_from_sequences(&sequences, &HashMap::new()) {
+            return schedule;
+        }
+        if instance.buffering == Buffering::Unlimited {
+            return Greedy.solve(instance);
+        }
+        // Without buffers the machine orders may admit no start times; the jobs then keep
+        // the order in which they start in the buffered schedule, unless greedy does better
+        let mut buffered = instance.clone();
+        buffered.buffering = Buffering::Unlimited;
+        let greedy = Greedy.solve(instance);
+        match buffered.schedule_from_sequences(&sequences, &HashMap::new()).map(|schedule| instance.enforce_buffering(schedule)) {
+            Some(schedule) if instance.calculate_makespan(&schedule) < instance.calculate_makespan(&greedy) => schedule,
+            _ => greedy,
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Flow shops and open shops: relatives of the job shop that differ in how the
+//! operations of a job are ordered.
+//!
+//! - **Job shop**: every job follows its own route, in the order its operations are listed.
+//! - **Flow shop**: every job visits the same machines in the same order, as on a
+//!   production line. Its constraints are those of the job shop; the instance only
+//!   insists that the routes agree, and the generator draws one route for all jobs.
+//! - **Open shop**: the operations of a job may run in any order, just never two at
+//!   once, e.g. the inspections of one car. Choosing the order is part of scheduling.
+//!
+//! In an open shop `Instance::decode` gives the k-th occurrence of a job in the
+//! sequence whichever of its remaining operations can finish first, and
+//! `Instance::schedule_from_sequences` repeatedly starts, of the next operations on all
+//! machines, the one that can start first. Local search keeps the job orders of the
+//! schedule it starts from (see `Instance::job_sequences`). `Instance::validate` only
+//! requires a job's operations not to overlap, with transfers in between. A schedule
+//! that keeps the listed order is feasible for an open shop too, so the algorithms that
+//! always keep it, such as `Instance::decode_active`, still return valid schedules.
+//!
+//! An open shop needs unlimited buffers and ignores pins; otherwise its jobs keep the
+//! listed order.
+//!
+//! ```
+//! use jssp_scheduler::jssp::shop::ProblemType;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::{generate_instance, GeneratorConfig};
+//!
+//! let flow_shop = generate_instance(&GeneratorConfig { problem_type: ProblemType::FlowShop, ..GeneratorConfig::default() });
+//! let route: Vec<usize> = flow_shop.jobs()[0].operations.iter().map(|op| op.machine_id).collect();
+//! assert!(flow_shop.jobs().iter().all(|job| job.operations.iter().map(|op| op.machine_id).eq(route.iter().copied())));
+//!
+//! let mut ft06 = ft06().instance;
+//! // The jobs of ft06 take different routes
+//! assert!(ft06.set_problem_type(ProblemType::FlowShop).is_err());
+//! let job_shop = ft06.solve_greedy();
+//!
+//! ft06.set_problem_type(ProblemType::OpenShop).unwrap();
+//! let open_shop = ft06.solve_greedy();
+//! assert!(ft06.validate(&open_shop).is_ok());
+//! assert!(ft06.calculate_makespan(&open_shop) < ft06.calculate_makespan(&job_shop));
+//! // Some job runs its operations out of their listed order
+//! let orders = ft06.job_sequences(&open_shop);
+//! assert!(orders.iter().any(|order| order.windows(2).any(|pair| pair[0] > pair[1])));
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::resources::ResourceUsage;
+use super::{Buffering, Instance, Job, Schedule, ScheduledOperation};
+
+/// How the operations of a job are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemType {
+    /// Every job has its own route, processed in order
+    #[default]
+    JobShop,
+    /// Every job has the same route, processed in order
+    FlowShop,
+    /// A job's operations run in any order, one at a time
+    OpenShop,
+}
+
+impl ProblemType {
+    pub const ALL: [ProblemType; 3] = [ProblemType::JobShop, ProblemType::FlowShop, ProblemType::OpenShop];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProblemType::JobShop => "Job Shop",
+            ProblemType::FlowShop => "Flow Shop",
+            ProblemType::OpenShop => "Open Shop",
+        }
+    }
+
+    /// Identifier used in files and on the command line
+    pub fn key(&self) -> &'static str {
+        match self {
+            ProblemType::JobShop => "job_shop",
+            ProblemType::FlowShop => "flow_shop",
+            ProblemType::OpenShop => "open_shop",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ProblemType> {
+        ProblemType::ALL.into_iter().find(|problem_type| problem_type.key() == key)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ProblemType::JobShop => "Every job visits its machines in its own order",
+            ProblemType::FlowShop => "Every job visits the same machines in the same order",
+            ProblemType::OpenShop => "A job's operations may run in any order, one at a time",
+        }
+    }
+}
+
+/// An error if the jobs do not all visit the same machines in the same order (their
+/// default machines, for operations with alternatives)
+pub(super) fn check_flow_shop(jobs: &[Job]) -> Result<(), String> {
+    let route = |job: &Job| job.operations.iter().map(|op| op.machine_id).collect::<Vec<usize>>();
+    let Some(first) = jobs.first() else {
+        return Ok(());
+    };
+    let expected = route(first);
+    match jobs.iter().find(|job| route(job) != expected) {
+        Some(job) => Err(format!("Job {} takes a different route than job {}, so this is no flow shop", job.id, first.id)),
+        None => Ok(()),
+    }
+}
+
+impl Instance {
+    /// Change the problem type; a flow shop needs every job on the same route
+    pub fn set_problem_type(&mut self, problem_type: ProblemType) -> Result<(), String> {
+        if problem_type == ProblemType::FlowShop {
+            check_flow_shop(self.jobs())?;
+        }
+        self.set_problem_type_data(problem_type);
+        Ok(())
+    }
+
+    /// Whether the operations of a job may run in any order (see `ProblemType::OpenShop`)
+    pub fn is_open_shop(&self) -> bool {
+        self.problem_type() == ProblemType::OpenShop && self.buffering == Buffering::Unlimited
+    }
+
+    /// Operation ids of every job in the order `schedule` processes them, by job id: the
+    /// listed order, or in an open shop the order of their start times with any
+    /// operations missing from the schedule last
+    pub fn job_sequences(&self, schedule: &[ScheduledOperation]) -> Vec<Vec<usize>> {
+        let open = self.is_open_shop();
+        let times: HashMap<(usize, usize), (f64, f64)> = schedule.iter()
+            .filter(|_| open)
+            .map(|op| ((op.job_id, op.operation_id), (op.start_time, op.end_time)))
+            .collect();
+        self.jobs().iter()
+            .map(|job| {
+                let mut order: Vec<usize> = (0..job.operations.len()).collect();
+                if open {
+                    let time = |operation_id: usize| times.get(&(job.id, operation_id)).copied().unwrap_or((f64::INFINITY, f64::INFINITY));
+                    order.sort_by(|&a, &b| {
+                        let ((start_a, end_a), (start_b, end_b)) = (time(a), time(b));
+                        start_a.total_cmp(&start_b).then(end_a.total_cmp(&end_b))
+                    });
+                }
+                order
+            })
+            .collect()
+    }
+
+    /// `decode` for an open shop: each occurrence of a job places whichever of its
+    /// remaining operations finishes first, on its quickest eligible machine
+    pub(super) fn decode_open_shop(&self, sequence: &[usize]) -> Schedule {
+        let mut schedule = Vec::with_capacity(sequence.len());
+        // (free from, last job) per machine and (free from, last machine) per job
+        let mut machine_free: Vec<(f64, Option<usize>)> = vec![(0.0, None); self.num_machines()];
+        let mut job_free: Vec<(f64, Option<usize>)> = self.jobs().iter().map(|job| (job.release_time, None)).collect();
+        let mut done: Vec<Vec<bool>> = self.jobs().iter().map(|job| vec![false; job.operations.len()]).collect();
+        let mut resources = ResourceUsage::new(self);
+
+        for &job_id in sequence {
+            let Some(job) = self.jobs().get(job_id) else {
+                continue;
+            };
+            let (ready, last_machine) = job_free[job_id];
+            let first = self.operation_index(job_id, 0).unwrap_or_default();
+            let choice = (0..job.operations.len())
+                .filter(|&operation_id| !done[job_id][operation_id])
+                .flat_map(|operation_id| self.operation_options(first + operation_id).iter().map(move |&option| (operation_id, option)))
+                .map(|(operation_id, option)| {
+                    let (machine_time, last_job) = machine_free[option.machine_id];
+                    let setup = self.setup_time(option.machine_id, last_job, job_id);
+                    let arrival = ready + self.transfer_time(last_machine, option.machine_id);
+                    let earliest = (machine_time + setup).max(arrival);
+                    let start_time = self.resource_start(&resources, (job_id, operation_id), option.machine_id, earliest, setup, option.duration);
+                    (operation_id, option, setup, start_time, self.processing_end(option.machine_id, start_time, option.duration))
+                })
+                .min_by(|a, b| a.4.total_cmp(&b.4));
+            let Some((operation_id, option, setup_time, start_time, end_time)) = choice else {
+                continue;
+            };
+
+            done[job_id][operation_id] = true;
+            let operation = self.timed_operation(job_id, operation_id, option.machine_id, start_time, option.duration, setup_time);
+            resources.reserve(self, &operation);
+            schedule.push(operation);
+            machine_free[option.machine_id] = (end_time, Some(job_id));
+            job_free[job_id] = (end_time, Some(option.machine_id));
+        }
+        schedule
+    }
+
+    /// `schedule_from_sequences` for an open shop: of the next operations on all
+    /// machines, the one that can start first goes next, until every machine is done.
+    /// The jobs are never in the way of each other's orders, so this always succeeds
+    /// for machine orders that list every operation at most once.
+    pub(super) fn open_shop_timetable(
+        &self,
+        sequences: &[Vec<(usize, usize)>],
+        earliest_starts: &HashMap<(usize, usize), f64>,
+    ) -> Option<Schedule> {
+        let count = self.num_operations();
+        let mut scheduled: Vec<Option<ScheduledOperation>> = vec![None; count];
+        let mut listed = vec![false; count];
+        for &(job_id, operation_id) in sequences.iter().flatten() {
+            let node = self.operation_index(job_id, operation_id)?;
+            if std::mem::replace(&mut listed[node], true) {
+                return None;
+            }
+        }
+
+        let mut next = vec![0; sequences.len()];
+        let mut machine_free: Vec<(f64, Option<usize>)> = vec![(0.0, None); sequences.len()];
+        let mut job_free: Vec<(f64, Option<usize>)> = self.jobs().iter().map(|job| (job.release_time, None)).collect();
+        let mut resources = ResourceUsage::new(self);
+        loop {
+            let choice = sequences.iter()
+                .enumerate()
+                .filter_map(|(machine_id, sequence)| {
+                    let key @ (job_id, operation_id) = *sequence.get(next[machine_id])?;
+                    Some((machine_id, key, self.operation_index(job_id, operation_id)?))
+                })
+                .map(|(machine_id, key @ (job_id, _), node)| {
+                    let duration = self.duration_at(node, machine_id);
+                    let (free, last_job) = machine_free[machine_id];
+                    let (ready, last_machine) = job_free[job_id];
+                    let setup = self.setup_time(machine_id, last_job, job_id);
+                    let earliest = earliest_starts.get(&key).copied().unwrap_or(0.0)
+                        .max(ready + self.transfer_time(last_machine, machine_id))
+                        .max(free + setup);
+                    let start_time = self.resource_start(&resources, key, machine_id, earliest, setup, duration);
+                    (machine_id, key, duration, setup, start_time)
+                })
+                .min_by(|a, b| a.4.total_cmp(&b.4));
+            let Some((machine_id, (job_id, operation_id), duration, setup_time, start_time)) = choice else {
+                break;
+            };
+
+            let timed = self.timed_operation(job_id, operation_id, machine_id, start_time, duration, setup_time);
+            resources.reserve(self, &timed);
+            next[machine_id] += 1;
+            machine_free[machine_id] = (timed.end_time, Some(job_id));
+            job_free[job_id] = (timed.end_time, Some(machine_id));
+            scheduled[self.operation_index(job_id, operation_id)?] = Some(timed);
+        }
+        // Flat indices run job by job, so the schedule comes out sorted
+        Some(scheduled.into_iter().flatten().collect())
+    }
+}
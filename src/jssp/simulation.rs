@@ -0,0 +1,388 @@
+//! Executing a schedule under random machine failures and uncertain durations.
+//!
+//! Every machine alternates between exponentially distributed up times (mean MTBF)
+//! and repair times (mean MTTR). An operation hit by a failure is interrupted and
+//! resumes after the repair. Planned downtime of the instance is known in advance:
+//! operations wait for it to end instead of starting into it. With a duration
+//! variation of ±p, every operation takes its planned duration times a factor drawn
+//! uniformly from [1 - p, 1 + p], unknown until it ends. Each replication draws new
+//! failures and durations and reports the realized makespan, so the spread of the
+//! makespans shows how fragile the schedule is.
+//!
+//! ```
+//! use jssp_scheduler::jssp::simulation::SimulationConfig;
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let schedule = ft06.solve_greedy();
+//! // Durations off by up to 20%, no failures: the fixed sequences run 500 times
+//! let config = SimulationConfig { duration_variation: 0.2, ..SimulationConfig::uniform(0, Default::default()) };
+//! let report = ft06.simulate(&schedule, &SimulationConfig { replications: 500, ..config });
+//! assert!(report.min() < report.planned_makespan && report.planned_makespan < report.max());
+//! assert!(report.on_time_share() > 0.0 && report.on_time_share() < 1.0);
+//! ```
+
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Instance, ScheduledOperation, TIME_EPSILON};
+
+/// Failure behaviour of one machine, in time units
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailureModel {
+    /// Mean time between failures; `f64::INFINITY` (or <= 0) means the machine never fails
+    pub mtbf: f64,
+    /// Mean time to repair
+    pub mttr: f64,
+}
+
+impl Default for FailureModel {
+    fn default() -> Self {
+        Self { mtbf: 100.0, mttr: 10.0 }
+    }
+}
+
+/// How the shop reacts when reality deviates from the plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReactionPolicy {
+    /// Keep machine assignments and sequences, never start before the planned time
+    RightShift,
+    /// Dispatch again as operations become ready: whichever ready operation can start
+    /// first goes next (ties by planned start), on the eligible machine that finishes
+    /// it first given the failures known so far
+    Reschedule,
+}
+
+impl ReactionPolicy {
+    pub const ALL: [ReactionPolicy; 2] = [ReactionPolicy::RightShift, ReactionPolicy::Reschedule];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReactionPolicy::RightShift => "Right-Shift",
+            ReactionPolicy::Reschedule => "Reschedule",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationConfig {
+    /// Failure model per machine; machines without an entry never fail
+    pub failures: Vec<FailureModel>,
+    pub policy: ReactionPolicy,
+    /// Largest deviation of a realized duration from the planned one, as a fraction
+    /// (0.1 for ±10%); 0 keeps the planned durations
+    pub duration_variation: f64,
+    pub replications: usize,
+    pub seed: u64,
+}
+
+impl SimulationConfig {
+    /// The same failure model on every machine
+    pub fn uniform(num_machines: usize, model: FailureModel) -> Self {
+        Self {
+            failures: vec![model; num_machines],
+            policy: ReactionPolicy::RightShift,
+            duration_variation: 0.0,
+            replications: 200,
+            seed: 42,
+        }
+    }
+}
+
+/// Outcome of one replication
+#[derive(Debug, Clone)]
+pub struct SimulationRun {
+    pub makespan: f64,
+    /// Failures that started before the realized makespan
+    pub failures: usize,
+    /// Repair time that overlapped processing of an operation
+    pub lost_time: f64,
+    /// Realized schedule; interrupted operations end later than `start_time + duration`
+    pub schedule: Vec<ScheduledOperation>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub planned_makespan: f64,
+    pub policy: ReactionPolicy,
+    pub duration_variation: f64,
+    pub runs: Vec<SimulationRun>,
+}
+
+impl SimulationReport {
+    fn makespans(&self) -> Vec<f64> {
+        let mut makespans: Vec<f64> = self.runs.iter().map(|run| run.makespan).collect();
+        makespans.sort_by(f64::total_cmp);
+        makespans
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+        self.runs.iter().map(|run| run.makespan).sum::<f64>() / self.runs.len() as f64
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        if self.runs.len() < 2 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        let variance = self.runs.iter()
+            .map(|run| (run.makespan - mean).powi(2))
+            .sum::<f64>() / (self.runs.len() - 1) as f64;
+        variance.sqrt()
+    }
+
+    /// Realized makespan at quantile `q` in [0, 1] (nearest rank)
+    pub fn percentile(&self, q: f64) -> f64 {
+        let makespans = self.makespans();
+        if makespans.is_empty() {
+            return 0.0;
+        }
+        let rank = (q.clamp(0.0, 1.0) * makespans.len() as f64).ceil() as usize;
+        makespans[rank.clamp(1, makespans.len()) - 1]
+    }
+
+    /// Share of replications that finished by the planned makespan
+    pub fn on_time_share(&self) -> f64 {
+        if self.runs.is_empty() {
+            return 0.0;
+        }
+        let on_time = self.runs.iter().filter(|run| run.makespan <= self.planned_makespan + TIME_EPSILON).count();
+        on_time as f64 / self.runs.len() as f64
+    }
+
+    pub fn min(&self) -> f64 {
+        self.percentile(0.0)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.percentile(1.0)
+    }
+
+    /// Counts of realized makespans in `bins` equal-width bins as (bin start, width, count)
+    pub fn histogram(&self, bins: usize) -> Vec<(f64, f64, usize)> {
+        let makespans = self.makespans();
+        let (Some(&min), Some(&max)) = (makespans.first(), makespans.last()) else {
+            return Vec::new();
+        };
+        let bins = bins.max(1);
+        let width = ((max - min) / bins as f64).max(1e-9);
+        let mut counts = vec![0usize; bins];
+        for makespan in makespans {
+            let bin = (((makespan - min) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        counts.into_iter()
+            .enumerate()
+            .map(|(i, count)| (min + i as f64 * width, width, count))
+            .collect()
+    }
+}
+
+fn sample_exponential(rng: &mut StdRng, mean: f64) -> f64 {
+    -mean * (1.0 - rng.gen::<f64>()).ln()
+}
+
+/// Down intervals `(start, end)` of one machine up to `horizon`
+fn sample_downtime(rng: &mut StdRng, model: FailureModel, horizon: f64) -> Vec<(f64, f64)> {
+    let mut intervals = Vec::new();
+    if !(model.mtbf > 0.0 && model.mtbf.is_finite()) {
+        return intervals;
+    }
+    let mut t = 0.0;
+    loop {
+        t += sample_exponential(rng, model.mtbf);
+        if t > horizon {
+            return intervals;
+        }
+        let repair = sample_exponential(rng, model.mttr.max(0.0));
+        intervals.push((t, t + repair));
+        t += repair;
+    }
+}
+
+/// Actual (start, end, lost time) of an operation of `duration` that may start at
+/// `earliest`, interrupted by every down interval it runs into
+fn run_through(downtime: &[(f64, f64)], earliest: f64, duration: f64) -> (f64, f64, f64) {
+    let mut start = earliest;
+    for &(down, up) in downtime {
+        if down <= start && start < up {
+            start = up;
+        }
+    }
+    let (mut t, mut remaining, mut lost) = (start, duration, 0.0);
+    for &(down, up) in downtime.iter().filter(|(_, up)| *up > start) {
+        if down >= t + remaining {
+            break;
+        }
+        remaining -= (down - t).max(0.0);
+        lost += up - down.max(t);
+        t = up;
+    }
+    (start, t + remaining, lost)
+}
+
+/// Factors of the realized over the planned durations in one replication, by
+/// `Instance::operation_index`
+struct Durations {
+    factors: Vec<f64>,
+}
+
+impl Durations {
+    /// Independent factors in [1 - variation, 1 + variation]; none are drawn without
+    /// variation, so that the failures drawn for a seed stay the same
+    fn sample(instance: &Instance, rng: &mut StdRng, variation: f64) -> Self {
+        let variation = variation.clamp(0.0, 1.0);
+        if variation == 0.0 {
+            return Self { factors: Vec::new() };
+        }
+        Self { factors: (0..instance.num_operations()).map(|_| 1.0 + rng.gen_range(-variation..=variation)).collect() }
+    }
+
+    fn realized(&self, instance: &Instance, job_id: usize, operation_id: usize, planned: f64) -> f64 {
+        let factor = instance.operation_index(job_id, operation_id).and_then(|index| self.factors.get(index));
+        planned * factor.copied().unwrap_or(1.0)
+    }
+}
+
+impl Instance {
+    /// Execute `schedule` `config.replications` times under random failures and
+    /// duration deviations
+    pub fn simulate(&self, schedule: &[ScheduledOperation], config: &SimulationConfig) -> SimulationReport {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let planned_makespan = self.calculate_makespan(schedule);
+        let total_processing: f64 = schedule.iter().map(|op| op.duration).sum();
+        // Generous horizon; a run that outlasts it simply sees no further failures
+        let horizon = 20.0 * (planned_makespan + total_processing) * (1.0 + config.duration_variation.clamp(0.0, 1.0)) + 1.0;
+
+        let runs = (0..config.replications)
+            .map(|_| {
+                let downtime: Vec<Vec<(f64, f64)>> = (0..self.num_machines())
+                    .map(|machine_id| {
+                        let model = config.failures.get(machine_id).copied()
+                            .unwrap_or(FailureModel { mtbf: f64::INFINITY, mttr: 0.0 });
+                        sample_downtime(&mut rng, model, horizon)
+                    })
+                    .collect();
+                let durations = Durations::sample(self, &mut rng, config.duration_variation);
+                let (realized, lost_time) = match config.policy {
+                    ReactionPolicy::RightShift => self.execute_right_shift(schedule, &downtime, &durations),
+                    ReactionPolicy::Reschedule => self.execute_rescheduling(schedule, &downtime, &durations),
+                };
+                let makespan = self.calculate_makespan(&realized);
+                let failures = downtime.iter().flatten().filter(|(down, _)| *down < makespan).count();
+                SimulationRun { makespan, failures, lost_time, schedule: realized }
+            })
+            .collect();
+
+        SimulationReport { planned_makespan, policy: config.policy, duration_variation: config.duration_variation, runs }
+    }
+
+    fn execute_right_shift(&self, schedule: &[ScheduledOperation], downtime: &[Vec<(f64, f64)>], durations: &Durations) -> (Vec<ScheduledOperation>, f64) {
+        let mut order: Vec<&ScheduledOperation> = schedule.iter().collect();
+        order.sort_by(|a, b| a.start_time.total_cmp(&b.start_time).then(a.end_time.total_cmp(&b.end_time)));
+
+        // When each job finished its last operation, and on which machine
+        let mut job_ready: HashMap<usize, (f64, usize)> = HashMap::new();
+        let mut machine_free: HashMap<usize, (f64, usize)> = HashMap::new();
+        let mut realized = Vec::with_capacity(schedule.len());
+        let mut lost_time = 0.0;
+        for op in order {
+            let setup_time = match machine_free.get(&op.machine_id) {
+                Some(&(_, previous_job)) => self.setup_time(op.machine_id, Some(previous_job), op.job_id),
+                None => op.setup_time,
+            };
+            let earliest = op.start_time
+                .max(job_ready.get(&op.job_id).map_or(0.0, |&(end, machine_id)| end + self.transfer_time(Some(machine_id), op.machine_id)))
+                .max(machine_free.get(&op.machine_id).map_or(0.0, |&(free, _)| free + setup_time));
+            let earliest = self.available_start(op.machine_id, earliest, setup_time, op.duration);
+            let no_downtime = Vec::new();
+            let duration = durations.realized(self, op.job_id, op.operation_id, op.duration);
+            let (start, end, lost) = run_through(downtime.get(op.machine_id).unwrap_or(&no_downtime), earliest, duration);
+
+            lost_time += lost;
+            job_ready.insert(op.job_id, (end, op.machine_id));
+            machine_free.insert(op.machine_id, (end, op.job_id));
+            realized.push(ScheduledOperation { start_time: start, end_time: end, duration, setup_time, segments: Vec::new(), ..op.clone() });
+        }
+        realized.sort_by_key(|op| (op.job_id, op.operation_id));
+        (realized, lost_time)
+    }
+
+    fn execute_rescheduling(&self, schedule: &[ScheduledOperation], downtime: &[Vec<(f64, f64)>], durations: &Durations) -> (Vec<ScheduledOperation>, f64) {
+        let planned_start: HashMap<(usize, usize), f64> = schedule.iter()
+            .map(|op| ((op.job_id, op.operation_id), op.start_time))
+            .collect();
+        let mut next_op: Vec<usize> = vec![0; self.jobs().len()];
+        let mut job_ready: Vec<f64> = self.jobs().iter().map(|job| job.release_time).collect();
+        let mut job_machine: Vec<Option<usize>> = vec![None; self.jobs().len()];
+        let mut machine_free: Vec<(f64, Option<usize>)> = vec![(0.0, None); self.num_machines()];
+        let mut realized = Vec::with_capacity(schedule.len());
+        let mut lost_time = 0.0;
+        let no_downtime = Vec::new();
+
+        loop {
+            // For every job's next operation, the machine that would finish it first,
+            // knowing only failures that have begun by the time it could start
+            let mut best: Option<(f64, f64, usize, usize, f64, f64)> = None;
+            for (job_index, job) in self.jobs().iter().enumerate() {
+                let Some(operation) = job.operations.get(next_op[job_index]) else {
+                    continue;
+                };
+                let priority = planned_start.get(&(job.id, operation.operation_id)).copied().unwrap_or(f64::INFINITY);
+                for option in operation.options() {
+                    let Some(&(free, previous_job)) = machine_free.get(option.machine_id) else {
+                        continue;
+                    };
+                    let setup_time = self.setup_time(option.machine_id, previous_job, job.id);
+                    let arrival = job_ready[job_index] + self.transfer_time(job_machine[job_index], option.machine_id);
+                    let earliest = self.available_start(option.machine_id, arrival.max(free + setup_time), setup_time, option.duration);
+                    let machine_downtime = downtime.get(option.machine_id).unwrap_or(&no_downtime);
+                    let known: Vec<(f64, f64)> = machine_downtime.iter().copied().filter(|(down, _)| *down <= earliest).collect();
+                    let (start, end, _) = run_through(&known, earliest, option.duration);
+                    let better = match best {
+                        None => true,
+                        Some((best_start, best_priority, _, _, _, best_end)) => {
+                            (start, priority, end) < (best_start, best_priority, best_end)
+                        }
+                    };
+                    if better {
+                        best = Some((start, priority, job_index, option.machine_id, setup_time, end));
+                    }
+                }
+            }
+
+            let Some((start, _, job_index, machine_id, setup_time, _)) = best else {
+                break;
+            };
+            let job = &self.jobs()[job_index];
+            let operation = &job.operations[next_op[job_index]];
+            // Dispatching went by the planned duration; the realized one shows only now
+            let planned = operation.duration_on(machine_id).unwrap_or(operation.duration);
+            let duration = durations.realized(self, job.id, operation.operation_id, planned);
+            let (start, end, lost) = run_through(downtime.get(machine_id).unwrap_or(&no_downtime), start, duration);
+
+            lost_time += lost;
+            job_ready[job_index] = end;
+            job_machine[job_index] = Some(machine_id);
+            machine_free[machine_id] = (end, Some(job.id));
+            next_op[job_index] += 1;
+            realized.push(ScheduledOperation {
+                job_id: job.id,
+                operation_id: operation.operation_id,
+                machine_id,
+                start_time: start,
+                end_time: end,
+                duration,
+                setup_time,
+                segments: Vec::new(),
+            });
+        }
+        realized.sort_by_key(|op| (op.job_id, op.operation_id));
+        (realized, lost_time)
+    }
+}
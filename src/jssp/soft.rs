@@ -0,0 +1,248 @@
+//! Soft constraints: requirements a schedule may break at a weighted penalty.
+//!
+//! Unlike the hard constraints checked by `Instance::validate`, a soft constraint never
+//! makes a schedule infeasible. Each one adds `weight * amount` to the penalty of a
+//! schedule, where the amount is the time by which it is broken. `Objective::Penalized`
+//! adds the total penalty to the makespan, so any search that ranks schedules by an
+//! objective can trade the two off.
+//!
+//! ```
+//! use jssp_scheduler::jssp::soft::SoftConstraint;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Objective;
+//!
+//! let mut ft06 = ft06().instance;
+//! ft06.soft_constraints.push(SoftConstraint::OperatorLimit { operators: 2, weight: 0.5 });
+//! let schedule = ft06.solve_greedy();
+//! let penalty = ft06.soft_penalty(&schedule);
+//! assert_eq!(penalty, 1.5);
+//! assert_eq!(ft06.evaluate(Objective::Penalized, &schedule), ft06.calculate_makespan(&schedule) + penalty);
+//! ```
+
+use std::fmt;
+
+use super::{Instance, ScheduledOperation, TIME_EPSILON};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoftConstraint {
+    /// Jobs should finish by their due dates; the amount is the tardiness, scaled by
+    /// the job weight
+    DueDates { weight: f64 },
+    /// A job should wait at most `max_wait` between consecutive operations
+    MaxWait { max_wait: f64, weight: f64 },
+    /// At most `operators` operations should run at the same time; the amount is the
+    /// excess operations integrated over time
+    OperatorLimit { operators: usize, weight: f64 },
+    /// An operation, given as (job_id, operation_id), should start at `start_time`
+    Pin { operation: (usize, usize), start_time: f64, weight: f64 },
+}
+
+impl SoftConstraint {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SoftConstraint::DueDates { .. } => "Due Dates",
+            SoftConstraint::MaxWait { .. } => "Max Wait",
+            SoftConstraint::OperatorLimit { .. } => "Operator Limit",
+            SoftConstraint::Pin { .. } => "Pin",
+        }
+    }
+
+    /// Penalty per unit of violation
+    pub fn weight(&self) -> f64 {
+        match *self {
+            SoftConstraint::DueDates { weight }
+            | SoftConstraint::MaxWait { weight, .. }
+            | SoftConstraint::OperatorLimit { weight, .. }
+            | SoftConstraint::Pin { weight, .. } => weight,
+        }
+    }
+
+    pub fn weight_mut(&mut self) -> &mut f64 {
+        match self {
+            SoftConstraint::DueDates { weight }
+            | SoftConstraint::MaxWait { weight, .. }
+            | SoftConstraint::OperatorLimit { weight, .. }
+            | SoftConstraint::Pin { weight, .. } => weight,
+        }
+    }
+}
+
+impl fmt::Display for SoftConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoftConstraint::DueDates { .. } => write!(f, "Jobs finish by their due dates"),
+            SoftConstraint::MaxWait { max_wait, .. } => {
+                write!(f, "Jobs wait at most {:.2} between operations", max_wait)
+            }
+            SoftConstraint::OperatorLimit { operators, .. } => {
+                write!(f, "At most {} operations at a time", operators)
+            }
+            SoftConstraint::Pin { operation, start_time, .. } => {
+                write!(f, "Job {} Op {} starts at {:.2}", operation.0, operation.1, start_time)
+            }
+        }
+    }
+}
+
+/// One breach of a soft constraint
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftViolation {
+    /// Index into `Instance::soft_constraints`
+    pub constraint: usize,
+    /// The (job_id, operation_id) pairs involved
+    pub operations: Vec<(usize, usize)>,
+    /// How far the constraint is broken, in time units
+    pub amount: f64,
+    /// `weight * amount` (times the job weight for due dates)
+    pub penalty: f64,
+    pub description: String,
+}
+
+impl Instance {
+    /// Every breach of the instance's soft constraints in a schedule, constraint by
+    /// constraint. Pins of operations missing from the schedule are ignored.
+    pub fn soft_violations(&self, schedule: &[ScheduledOperation]) -> Vec<SoftViolation> {
+        let mut violations = Vec::new();
+        for (index, constraint) in self.soft_constraints.iter().enumerate() {
+            let mut violation = |operations: Vec<(usize, usize)>, amount: f64, penalty: f64, description: String| {
+                violations.push(SoftViolation { constraint: index, operations, amount, penalty, description });
+            };
+            match *constraint {
+                SoftConstraint::DueDates { weight } => {
+                    let completion = self.job_completion_times(schedule);
+                    for job in self.jobs() {
+                        let (Some(due_date), Some(&completed)) = (job.due_date, completion.get(&job.id)) else {
+                            continue;
+                        };
+                        let tardiness = completed - due_date;
+                        if tardiness > TIME_EPSILON {
+                            let last = (job.id, job.operations.len().saturating_sub(1));
+                            let description = format!("Job {} finishes {:.2} after its due date {:.2}", job.id, tardiness, due_date);
+                            violation(vec![last], tardiness, weight * job.weight * tardiness, description);
+                        }
+                    }
+                }
+                SoftConstraint::MaxWait { max_wait, weight } => {
+                    let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+                    // An open shop job waits between whichever operations follow each other
+                    let open = self.is_open_shop();
+                    if open {
+                        ops.sort_by(|a, b| a.job_id.cmp(&b.job_id).then(a.start_time.total_cmp(&b.start_time)));
+                    } else {
+                        ops.sort_by_key(|op| (op.job_id, op.operation_id));
+                    }
+                    for pair in ops.windows(2) {
+                        let (previous, next) = (pair[0], pair[1]);
+                        if previous.job_id != next.job_id || (!open && previous.operation_id + 1 != next.operation_id) {
+                            continue;
+                        }
+                        let excess = next.start_time - previous.end_time - max_wait;
+                        if excess > TIME_EPSILON {
+                            let description = format!(
+                                "Job {} waits {:.2} before Op {}",
+                                next.job_id, next.start_time - previous.end_time, next.operation_id
+                            );
+                            violation(
+                                vec![(previous.job_id, previous.operation_id), (next.job_id, next.operation_id)],
+                                excess,
+                                weight * excess,
+                                description,
+                            );
+                        }
+                    }
+                }
+                SoftConstraint::OperatorLimit { operators, weight } => {
+                    for overload in overloaded_periods(schedule, operators) {
+                        let description = format!(
+                            "{} operations at once between {:.2} and {:.2}",
+                            overload.peak, overload.start, overload.end
+                        );
+                        violation(overload.operations, overload.excess, weight * overload.excess, description);
+                    }
+                }
+                SoftConstraint::Pin { operation, start_time, weight } => {
+                    let Some(op) = schedule.iter().find(|op| (op.job_id, op.operation_id) == operation) else {
+                        continue;
+                    };
+                    let deviation = (op.start_time - start_time).abs();
+                    if deviation > TIME_EPSILON {
+                        let description = format!(
+                            "Job {} Op {} starts at {:.2} instead of {:.2}",
+                            operation.0, operation.1, op.start_time, start_time
+                        );
+                        violation(vec![operation], deviation, weight * deviation, description);
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Total penalty of the soft constraints for a schedule
+    pub fn soft_penalty(&self, schedule: &[ScheduledOperation]) -> f64 {
+        self.soft_violations(schedule).iter()
+            .map(|violation| violation.penalty)
+            .fold(0.0, |total, penalty| total + penalty)
+    }
+}
+
+/// A maximal period with more than the allowed number of operations running
+struct Overload {
+    start: f64,
+    end: f64,
+    peak: usize,
+    /// Excess operations integrated over the period
+    excess: f64,
+    operations: Vec<(usize, usize)>,
+}
+
+fn overloaded_periods(schedule: &[ScheduledOperation], limit: usize) -> Vec<Overload> {
+    // Ends sort before starts at the same time, so back-to-back operations don't overlap
+    let mut events: Vec<(f64, bool, usize)> = Vec::with_capacity(schedule.len() * 2);
+    for (index, op) in schedule.iter().enumerate() {
+        if op.end_time - op.start_time > TIME_EPSILON {
+            events.push((op.start_time, true, index));
+            events.push((op.end_time, false, index));
+        }
+    }
+    events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut periods = Vec::new();
+    let mut running: Vec<usize> = Vec::new();
+    let mut current: Option<Overload> = None;
+    let mut last_time = 0.0;
+    for (time, is_start, index) in events {
+        if let Some(overload) = &mut current {
+            overload.excess += (running.len() - limit) as f64 * (time - last_time);
+        }
+        if is_start {
+            running.push(index);
+        } else {
+            running.retain(|&i| i != index);
+        }
+        last_time = time;
+
+        if running.len() > limit {
+            let overload = current.get_or_insert_with(|| Overload {
+                start: time,
+                end: time,
+                peak: 0,
+                excess: 0.0,
+                operations: Vec::new(),
+            });
+            overload.peak = overload.peak.max(running.len());
+            for &i in &running {
+                let key = (schedule[i].job_id, schedule[i].operation_id);
+                if !overload.operations.contains(&key) {
+                    overload.operations.push(key);
+                }
+            }
+        } else if let Some(mut overload) = current.take() {
+            overload.end = time;
+            if overload.excess > TIME_EPSILON {
+                periods.push(overload);
+            }
+        }
+    }
+    periods
+}
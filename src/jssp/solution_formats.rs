@@ -0,0 +1,341 @@
+//! Solutions in the formats other tools read, for validating them externally.
+//!
+//! - **OR-Tools JSON**: the `jobs_data` layout with a `solution` (see `ortools`).
+//! - **JobShopLib JSON**: the dictionary of JobShopLib's `Schedule.to_dict()`: the
+//!   instance as `duration_matrix` and `machines_matrix`, and the solution as
+//!   `job_sequences`, the job of every operation on each machine in processing order.
+//!   JobShopLib gives an operation one duration, so flexible operations are written with
+//!   the machine and duration the schedule chose. A no-wait or blocking instance is
+//!   marked by a `buffering` entry in the instance metadata, a flow or open shop by a
+//!   `problem_type` entry, named jobs and machines by `job_names` and `machine_names`
+//!   entries.
+//! - **Machine order**: the plain-text format of academic validators. The first line is
+//!   the number of jobs and of machines, then one line per machine lists the jobs it
+//!   processes, in order and counted from 0. A job's operations on one machine appear in
+//!   job order.
+//!
+//! `read_solution` reads any of these back, as well as the schedule JSON and CSV the
+//! app exports and MILP solver solutions of the model in `milp`, so solutions computed
+//! elsewhere can be checked and shown.
+//!
+//! ```
+//! use jssp_scheduler::jssp::solution_formats::{read_solution, SolutionFormat};
+//! use jssp_scheduler::jssp::testing::ft06;
+//!
+//! let ft06 = ft06().instance;
+//! let schedule = ft06.solve_greedy();
+//!
+//! let text = SolutionFormat::MachineOrder.write(&ft06, &schedule).unwrap();
+//! let lines: Vec<&str> = text.lines().collect();
+//! assert_eq!(lines[0], "6 6");
+//! assert_eq!(lines.len(), 7);
+//! assert!(lines[1..].iter().all(|line| line.split(' ').count() == 6));
+//!
+//! let document: serde_json::Value = serde_json::from_str(&SolutionFormat::JobShopLib.write(&ft06, &schedule).unwrap()).unwrap();
+//! assert_eq!(document["instance"]["machines_matrix"][0], serde_json::json!([2, 0, 1, 3, 5, 4]));
+//! assert_eq!(document["job_sequences"][0].as_array().unwrap().len(), 6);
+//! assert_eq!(document["metadata"]["makespan"], 152);
+//!
+//! // Machine orders are timed again on import, which gives back the same schedule
+//! let imported = read_solution(&ft06, &text).unwrap();
+//! assert_eq!(ft06.calculate_makespan(&imported), 152.0);
+//! assert!(ft06.validate(&imported).is_ok());
+//!
+//! let csv = "Job,Operation,Machine,Start Time,End Time\n0,0,2,0,1\n";
+//! let partial = read_solution(&ft06, csv).unwrap();
+//! assert_eq!(partial[0].machine_id, 2);
+//! assert!(ft06.validate(&partial).is_err());
+//! assert!(read_solution(&ft06, "Job,Operation,Machine,Start Time,End Time\n0,0,5,0,1\n").is_err());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::milp;
+use super::ortools::{number, schedule_from_solution, to_ortools_json};
+use super::provenance::RunMetadata;
+use super::{Buffering, Instance, JsspError, ProblemType, Schedule, ScheduledOperation};
+
+/// A file format a solution can be exported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionFormat {
+    OrTools,
+    JobShopLib,
+    MachineOrder,
+}
+
+impl SolutionFormat {
+    pub const ALL: [SolutionFormat; 3] = [SolutionFormat::OrTools, SolutionFormat::JobShopLib, SolutionFormat::MachineOrder];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SolutionFormat::OrTools => "OR-Tools JSON",
+            SolutionFormat::JobShopLib => "JobShopLib JSON",
+            SolutionFormat::MachineOrder => "Machine Order",
+        }
+    }
+
+    /// Identifier used on the command line
+    pub fn key(&self) -> &'static str {
+        match self {
+            SolutionFormat::OrTools => "ortools",
+            SolutionFormat::JobShopLib => "jobshoplib",
+            SolutionFormat::MachineOrder => "machine-order",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<SolutionFormat> {
+        SolutionFormat::ALL.into_iter().find(|format| format.key() == key)
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SolutionFormat::OrTools | SolutionFormat::JobShopLib => "json",
+            SolutionFormat::MachineOrder => "txt",
+        }
+    }
+
+    /// The instance and `schedule` as the text of a file in this format
+    pub fn write(&self, instance: &Instance, schedule: &[ScheduledOperation]) -> Result<String, JsspError> {
+        self.write_with_run(instance, schedule, None)
+    }
+
+    /// Like `write`, with the run that produced the schedule as `run` in OR-Tools JSON
+    /// and in the metadata of JobShopLib JSON; machine orders have no place for it
+    pub fn write_with_run(&self, instance: &Instance, schedule: &[ScheduledOperation], run: Option<&RunMetadata>) -> Result<String, JsspError> {
+        let mut document = match self {
+            SolutionFormat::OrTools => to_ortools_json(instance, Some(schedule)),
+            SolutionFormat::JobShopLib => to_jobshoplib_json(instance, schedule),
+            SolutionFormat::MachineOrder => return Ok(to_machine_order(instance, schedule)),
+        };
+        if let Some(run) = run {
+            match self {
+                SolutionFormat::JobShopLib => document["metadata"]["run"] = run.to_json(),
+                _ => document["run"] = run.to_json(),
+            }
+        }
+        serde_json::to_string_pretty(&document).map_err(|e| JsspError::Serialization(format!("Failed to serialize JSON: {}", e)))
+    }
+}
+
+/// The jobs on every machine in processing order
+fn job_sequences(instance: &Instance, schedule: &[ScheduledOperation]) -> Vec<Vec<usize>> {
+    instance.machine_sequences(schedule).into_iter()
+        .map(|sequence| sequence.into_iter().map(|(job_id, _)| job_id).collect())
+        .collect()
+}
+
+/// The instance and schedule in the layout of JobShopLib's `Schedule.to_dict()`
+pub fn to_jobshoplib_json(instance: &Instance, schedule: &[ScheduledOperation]) -> Value {
+    let scheduled: HashMap<(usize, usize), &ScheduledOperation> = schedule.iter()
+        .map(|op| ((op.job_id, op.operation_id), op))
+        .collect();
+    let (mut durations, mut machines) = (Vec::new(), Vec::new());
+    for job in instance.jobs() {
+        let (job_durations, job_machines): (Vec<Value>, Vec<usize>) = job.operations.iter()
+            .map(|op| match scheduled.get(&(op.job_id, op.operation_id)) {
+                Some(chosen) => (number(chosen.duration), chosen.machine_id),
+                None => (number(op.duration), op.machine_id),
+            })
+            .unzip();
+        durations.push(job_durations);
+        machines.push(job_machines);
+    }
+
+    let mut metadata = serde_json::Map::new();
+    if instance.jobs().iter().any(|job| job.release_time > 0.0) {
+        metadata.insert("release_dates".to_string(), instance.jobs().iter().map(|job| number(job.release_time)).collect());
+    }
+    if instance.buffering != Buffering::Unlimited {
+        metadata.insert("buffering".to_string(), json!(instance.buffering));
+    }
+    if instance.problem_type() != ProblemType::JobShop {
+        metadata.insert("problem_type".to_string(), json!(instance.problem_type()));
+    }
+    if instance.jobs().iter().any(|job| job.due_date.is_some()) {
+        metadata.insert("due_dates".to_string(), instance.jobs().iter().map(|job| job.due_date.map_or(Value::Null, number)).collect());
+    }
+    if !instance.job_names().is_empty() {
+        metadata.insert("job_names".to_string(), json!(instance.job_names()));
+    }
+    if !instance.machine_names().is_empty() {
+        metadata.insert("machine_names".to_string(), json!(instance.machine_names()));
+    }
+    json!({
+        "instance": {
+            "name": instance.name,
+            "duration_matrix": durations,
+            "machines_matrix": machines,
+            "metadata": metadata,
+        },
+        "job_sequences": job_sequences(instance, schedule),
+        "metadata": {
+            "makespan": number(instance.calculate_makespan(schedule)),
+        },
+    })
+}
+
+/// The machine order text: "jobs machines", then the jobs of each machine in order
+pub fn to_machine_order(instance: &Instance, schedule: &[ScheduledOperation]) -> String {
+    let sequences = job_sequences(instance, schedule);
+    let mut text = format!("{} {}\n", instance.jobs().len(), sequences.len());
+    for sequence in sequences {
+        let jobs: Vec<String> = sequence.iter().map(|job_id| job_id.to_string()).collect();
+        let _ = writeln!(text, "{}", jobs.join(" "));
+    }
+    text
+}
+
+/// Read a solution for `instance` from the text of a file: the app's schedule JSON or
+/// CSV (also tab-separated), an OR-Tools or JobShopLib JSON, or a machine order. The
+/// format is recognized by the content. Setups missing from a timed format are those
+/// the instance requires; sequence formats are timed as early as possible. Operations
+/// the instance does not have are errors, while other violations are left for
+/// `Instance::validate` to report.
+pub fn read_solution(instance: &Instance, text: &str) -> Result<Schedule, JsspError> {
+    let text = text.trim_start_matches('\u{feff}').trim();
+    let schedule = if text.starts_with('{') || text.starts_with('[') {
+        let document: Value = serde_json::from_str(text).map_err(|e| JsspError::Parse(format!("Invalid JSON: {}", e)))?;
+        read_json(instance, &document)
+    } else if text.starts_with("Job") {
+        read_table(instance, text)
+    } else if milp::is_milp_solution(text) {
+        milp::read_milp_solution(instance, text)
+    } else {
+        read_machine_order(instance, text)
+    }
+    .map_err(JsspError::Parse)?;
+
+    for op in &schedule {
+        let operation = instance.jobs().iter()
+            .find(|job| job.id == op.job_id)
+            .and_then(|job| job.operations.get(op.operation_id))
+            .ok_or_else(|| JsspError::Infeasible(format!("Job {} has no operation {} in this instance", op.job_id, op.operation_id)))?;
+        if operation.duration_on(op.machine_id).is_none() {
+            return Err(JsspError::Infeasible(format!("Operation {} of job {} cannot run on machine {}", op.operation_id, op.job_id, op.machine_id)));
+        }
+    }
+    Ok(schedule)
+}
+
+fn read_json(instance: &Instance, document: &Value) -> Result<Schedule, String> {
+    let operations = |value: &Value| {
+        Vec::<ScheduledOperation>::deserialize(value).map_err(|e| format!("Invalid schedule: {}", e))
+    };
+    if let Some(schedule) = document.get("schedule") {
+        operations(schedule)
+    } else if let Some(solution) = document.get("solution").or(document.get("assigned_jobs").map(|_| document)) {
+        let mut schedule = schedule_from_solution(instance, solution)?;
+        attach_setups(instance, &mut schedule);
+        Ok(schedule)
+    } else if let Some(sequences) = document.get("job_sequences") {
+        let sequences = Vec::<Vec<usize>>::deserialize(sequences).map_err(|e| format!("Invalid job_sequences: {}", e))?;
+        schedule_jobs(instance, &sequences)
+    } else if document.is_array() {
+        operations(document)
+    } else {
+        Err("No schedule, solution or job_sequences in the JSON".to_string())
+    }
+}
+
+/// A schedule table with a header row, comma- or tab-separated; a blank line ends it
+fn read_table(instance: &Instance, text: &str) -> Result<Schedule, String> {
+    let mut lines = text.lines();
+    let header = lines.next().unwrap_or_default();
+    let delimiter = if header.contains('\t') { '\t' } else { ',' };
+    let columns: Vec<&str> = header.split(delimiter).map(str::trim).collect();
+    let column = |names: &[&str]| columns.iter().position(|column| names.contains(column));
+    let required = |names: &[&str]| column(names).ok_or_else(|| format!("No {} column", names[0]));
+    let (job, operation, machine) = (required(&["Job"])?, required(&["Operation"])?, required(&["Machine"])?);
+    let (start, end) = (required(&["Start Time", "Start"])?, required(&["End Time", "End"])?);
+    let setup = column(&["Setup Time", "Setup"]);
+
+    let mut schedule = Vec::new();
+    for (row, line) in lines.take_while(|line| !line.trim().is_empty()).enumerate() {
+        let fields: Vec<&str> = line.split(delimiter).map(str::trim).collect();
+        let field = |index: usize| fields.get(index).copied().unwrap_or_default();
+        let invalid = |index: usize| format!("Row {}: invalid {} '{}'", row + 1, columns[index], field(index));
+        let id = |index: usize| field(index).parse::<usize>().map_err(|_| invalid(index));
+        let time = |index: usize| field(index).parse::<f64>().map_err(|_| invalid(index));
+        let (start_time, end_time) = (time(start)?, time(end)?);
+        schedule.push(ScheduledOperation {
+            job_id: id(job)?,
+            operation_id: id(operation)?,
+            machine_id: id(machine)?,
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            setup_time: setup.map_or(Ok(0.0), time)?,
+            segments: Vec::new(),
+        });
+    }
+    if setup.is_none() {
+        attach_setups(instance, &mut schedule);
+    }
+    Ok(schedule)
+}
+
+/// "jobs machines" followed by the jobs of each machine, as `to_machine_order` writes it
+fn read_machine_order(instance: &Instance, text: &str) -> Result<Schedule, String> {
+    let mut lines = text.lines().map(str::trim);
+    let numbers = |line: &str| -> Result<Vec<usize>, String> {
+        line.split_whitespace()
+            .map(|number| number.parse().map_err(|_| format!("Not a job number: '{}'", number)))
+            .collect()
+    };
+    let size = numbers(lines.next().unwrap_or_default())?;
+    if size != [instance.jobs().len(), instance.num_machines()] {
+        return Err(format!(
+            "The solution is for {:?} jobs and machines, the instance has {} and {}",
+            size, instance.jobs().len(), instance.num_machines(),
+        ));
+    }
+    let sequences = lines.take(instance.num_machines()).map(numbers).collect::<Result<Vec<_>, _>>()?;
+    schedule_jobs(instance, &sequences)
+}
+
+/// Time the jobs in the given order on each machine, taking each job's operations on
+/// a machine in job order
+fn schedule_jobs(instance: &Instance, job_sequences: &[Vec<usize>]) -> Result<Schedule, String> {
+    let jobs = instance.jobs();
+    let mut assigned: Vec<Vec<bool>> = jobs.iter().map(|job| vec![false; job.operations.len()]).collect();
+    let mut sequences = Vec::with_capacity(job_sequences.len());
+    for (machine_id, sequence) in job_sequences.iter().enumerate() {
+        let mut operations = Vec::with_capacity(sequence.len());
+        for &job_id in sequence {
+            let index = jobs.iter().position(|job| job.id == job_id).ok_or_else(|| format!("Unknown job {}", job_id))?;
+            let operation_id = jobs[index].operations.iter().zip(&assigned[index])
+                .position(|(op, &done)| !done && op.duration_on(machine_id).is_some())
+                .ok_or_else(|| format!("Job {} has no more operations for machine {}", job_id, machine_id))?;
+            assigned[index][operation_id] = true;
+            operations.push((job_id, operation_id));
+        }
+        sequences.push(operations);
+    }
+    if let Some((index, operation_id)) = assigned.iter().enumerate()
+        .find_map(|(index, ops)| ops.iter().position(|&done| !done).map(|op| (index, op)))
+    {
+        return Err(format!("Operation {} of job {} is on no machine", operation_id, jobs[index].id));
+    }
+    instance.schedule_from_sequences(&sequences, &HashMap::new())
+        .ok_or_else(|| "The machine orders contradict the job routes".to_string())
+}
+
+/// Give every operation the setup the instance requires after the job before it
+fn attach_setups(instance: &Instance, schedule: &mut [ScheduledOperation]) {
+    let mut order: Vec<usize> = (0..schedule.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&schedule[a], &schedule[b]);
+        a.machine_id.cmp(&b.machine_id).then(a.start_time.total_cmp(&b.start_time))
+    });
+    let mut previous: Option<(usize, usize)> = None;
+    for index in order {
+        let op = &mut schedule[index];
+        let previous_job = previous.filter(|&(machine_id, _)| machine_id == op.machine_id).map(|(_, job_id)| job_id);
+        op.setup_time = instance.setup_time(op.machine_id, previous_job, op.job_id);
+        previous = Some((op.machine_id, op.job_id));
+    }
+}
@@ -0,0 +1,550 @@
+//! The `Solver` trait implemented by every scheduling algorithm, and the registry the
+//! GUI builds its algorithm list from.
+//!
+//! ```
+//! use jssp_scheduler::jssp::{Instance, Schedule, Solver, SolverRegistry};
+//!
+//! /// Schedules jobs in reverse order
+//! struct Reversed;
+//!
+//! impl Solver for Reversed {
+//!     fn name(&self) -> &str { "Reversed" }
+//!     fn description(&self) -> &str { "Last job first" }
+//!     fn solve(&self, instance: &Instance) -> Schedule {
+//!         let mut sequence = instance.job_by_job_sequence();
+//!         sequence.reverse();
+//!         instance.decode(&sequence)
+//!     }
+//! }
+//!
+//! let mut registry = SolverRegistry::default();
+//! registry.register(Reversed);
+//! assert!(registry.get("Reversed").is_some());
+//! ```
+
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+use super::annealing::SimulatedAnnealing;
+use super::checkpoint::{Checkpointing, RunEnd, RunStart};
+use super::pool::SolutionPool;
+use super::shifting_bottleneck::ShiftingBottleneck;
+use super::trace::{ConvergencePoint, ProgressReporter, SearchTrace};
+use super::{Instance, JsspError, Objective, Schedule, TIME_EPSILON};
+
+/// A scheduling algorithm. Implementations must return a schedule containing every
+/// operation of the instance exactly once.
+pub trait Solver: Send + Sync {
+    /// Short name shown in the algorithm drop-down; unique within a registry
+    fn name(&self) -> &str;
+
+    /// One-line explanation shown as a tooltip
+    fn description(&self) -> &str;
+
+    fn solve(&self, instance: &Instance) -> Schedule;
+
+    /// Background for choosing and citing the algorithm; empty unless overridden
+    fn info(&self) -> SolverInfo {
+        SolverInfo::default()
+    }
+
+    /// Whether runs can differ; only such algorithms benefit from multi-start
+    fn is_stochastic(&self) -> bool {
+        false
+    }
+
+    /// A reproducible run for stochastic algorithms; deterministic ones ignore the seed
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        let _ = seed;
+        self.solve(instance)
+    }
+
+    /// A seeded run aimed at `objective` instead of the makespan. Algorithms that
+    /// cannot target other objectives ignore it, which is the default.
+    fn solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Schedule {
+        let _ = objective;
+        self.solve_seeded(instance, seed)
+    }
+
+    /// `solve_seeded` for algorithms that can fail, such as external programs, reporting
+    /// why instead of returning an incomplete schedule. The built-in algorithms always
+    /// succeed, which is the default.
+    fn try_solve_seeded(&self, instance: &Instance, seed: u64) -> Result<Schedule, JsspError> {
+        Ok(self.solve_seeded(instance, seed))
+    }
+
+    /// `solve_objective` that reports failures, like `try_solve_seeded`
+    fn try_solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Result<Schedule, JsspError> {
+        Ok(self.solve_objective(instance, objective, seed))
+    }
+
+    /// A seeded run that also records its search trajectory, for local-search
+    /// algorithms that support it (see `trace`)
+    fn solve_traced(&self, instance: &Instance, seed: u64) -> Option<(Schedule, SearchTrace)> {
+        let _ = (instance, seed);
+        None
+    }
+
+    /// A seeded run aimed at `objective` that passes the best value found so far to
+    /// `progress` as it goes, for iterative algorithms that support it; `None` otherwise
+    fn solve_with_progress(
+        &self,
+        instance: &Instance,
+        objective: Objective,
+        seed: u64,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Schedule> {
+        let _ = (instance, objective, seed, progress);
+        None
+    }
+
+    /// Whether the algorithm can start its search from `SolverConfig::initial_solution`
+    fn supports_warm_start(&self) -> bool {
+        false
+    }
+
+    /// A run as `config` describes it, passing progress on like `solve_with_progress`.
+    /// Algorithms without warm starts ignore the initial solution, which is the default.
+    fn solve_with_config(&self, instance: &Instance, config: &SolverConfig, progress: &mut dyn FnMut(ConvergencePoint)) -> Schedule {
+        self.solve_with_progress(instance, config.objective, config.seed, progress)
+            .unwrap_or_else(|| self.solve_objective(instance, config.objective, config.seed))
+    }
+
+    /// Whether the algorithm offers `solve_with_pool` the schedules it visits, not only its result
+    fn supports_pool(&self) -> bool {
+        false
+    }
+
+    /// Like `solve_with_config`, also keeping the best distinct schedules of the run in
+    /// `pool` (see `pool`). By default only the result goes in.
+    fn solve_with_pool(
+        &self,
+        instance: &Instance,
+        config: &SolverConfig,
+        pool: &mut SolutionPool,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Schedule {
+        let schedule = self.solve_with_config(instance, config, progress);
+        pool.offer(instance.evaluate(config.objective, &schedule), schedule.clone());
+        schedule
+    }
+
+    /// Like `solve_with_progress`, but saving checkpoints as it goes, possibly pausing
+    /// at one, or continuing the run of a checkpoint (see `checkpoint`), for algorithms
+    /// that support it; `None` otherwise. Fails for a checkpoint of another algorithm
+    /// or instance.
+    fn solve_resumable(
+        &self,
+        instance: &Instance,
+        start: RunStart,
+        checkpointing: &mut Checkpointing,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Result<RunEnd, String>> {
+        let _ = (instance, start, checkpointing, progress);
+        None
+    }
+}
+
+/// The settings of a single run (see `Solver::solve_with_config`)
+///
+/// ```
+/// use jssp_scheduler::jssp::annealing::SimulatedAnnealing;
+/// use jssp_scheduler::jssp::testing::ft06;
+/// use jssp_scheduler::jssp::{Objective, Solver, SolverConfig};
+///
+/// let ft06 = ft06().instance;
+/// let greedy = ft06.solve_greedy();
+/// let config = SolverConfig::new(Objective::Makespan, 5).with_initial_solution(greedy.clone());
+/// let annealing = SimulatedAnnealing::default();
+/// assert!(annealing.supports_warm_start());
+/// let schedule = annealing.solve_with_config(&ft06, &config, &mut |_| {});
+/// assert!(ft06.calculate_makespan(&schedule) <= ft06.calculate_makespan(&greedy));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SolverConfig {
+    pub objective: Objective,
+    pub seed: u64,
+    /// A schedule to start the search from instead of a random one, e.g. the greedy
+    /// result or an imported solution. Ignored if it is not a valid schedule of the
+    /// instance, and by algorithms without warm starts.
+    pub initial_solution: Option<Schedule>,
+}
+
+impl SolverConfig {
+    pub fn new(objective: Objective, seed: u64) -> Self {
+        Self { objective, seed, initial_solution: None }
+    }
+
+    pub fn with_initial_solution(mut self, schedule: Schedule) -> Self {
+        self.initial_solution = Some(schedule);
+        self
+    }
+
+    /// The initial solution, if it is a valid schedule of `instance`
+    pub fn valid_initial_solution(&self, instance: &Instance) -> Option<&Schedule> {
+        self.initial_solution.as_ref().filter(|schedule| instance.validate(schedule).is_ok())
+    }
+}
+
+/// Reference material on an algorithm, shown in the GUI's info popover and by
+/// `jssp-scheduler list-algos`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SolverInfo {
+    /// How the algorithm works, in a few sentences
+    pub details: &'static str,
+    /// Running time, with N the number of operations
+    pub complexity: &'static str,
+    /// Instance sizes the algorithm suits
+    pub recommended_sizes: &'static str,
+    /// The publication to cite for the method
+    pub citation: Option<&'static str>,
+}
+
+/// Schedules every operation of the heaviest job, then the next heaviest, and so on
+/// (job 0, then job 1 with equal weights), each as early as its machine and job allow.
+/// Flexible operations take the eligible machine that finishes first. Aimed at another
+/// objective than the makespan, jobs go in the order of a matching priority rule
+/// instead (see `Greedy::job_order`).
+pub struct Greedy;
+
+impl Greedy {
+    /// Jobs in the order they are placed for `objective`: shortest processing time first
+    /// for flow time, weighted shortest processing time first for weighted completion
+    /// time, earliest due date first for due-date objectives, and by weight otherwise.
+    /// Ties go to the heavier job, then the lower index.
+    pub fn job_order(instance: &Instance, objective: Objective) -> Vec<usize> {
+        let jobs = instance.jobs();
+        let key = |job: usize| -> f64 {
+            let job = &jobs[job];
+            let processing = job.earliest_completion() - job.release_time;
+            match objective {
+                Objective::TotalFlowTime => job.earliest_completion(),
+                Objective::WeightedCompletion if job.weight > 0.0 => processing / job.weight,
+                Objective::WeightedCompletion => f64::INFINITY,
+                _ if objective.uses_due_dates() => job.due_date.unwrap_or(f64::INFINITY),
+                _ => 0.0,
+            }
+        };
+        let mut order: Vec<usize> = (0..jobs.len()).collect();
+        // Stable, so full ties keep index order
+        order.sort_by(|&a, &b| key(a).total_cmp(&key(b)).then(jobs[b].weight.total_cmp(&jobs[a].weight)));
+        order
+    }
+}
+
+impl Solver for Greedy {
+    fn name(&self) -> &str {
+        "Greedy"
+    }
+
+    fn description(&self) -> &str {
+        "Job by job, each operation at the earliest time its machine and job allow"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "List scheduling in job order, heaviest job first: all operations of the first job are \
+                      placed, then those of the second, each at the earliest time its machine is free and its \
+                      job predecessor has finished. The result is semi-active but ignores interactions between jobs, so it mainly \
+                      serves as a baseline and as a starting point for improvement methods.",
+            complexity: "O(N) placements with one pass over the eligible machines each",
+            recommended_sizes: "Any size; instant even for thousands of operations",
+            citation: Some("Pinedo, M. L. (2016). Scheduling: Theory, Algorithms, and Systems (5th ed.). Springer."),
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_objective(instance, Objective::Makespan, 0)
+    }
+
+    fn solve_objective(&self, instance: &Instance, objective: Objective, _seed: u64) -> Schedule {
+        let sequence: Vec<usize> = Self::job_order(instance, objective).into_iter()
+            .flat_map(|job| std::iter::repeat_n(job, instance.jobs()[job].operations.len()))
+            .collect();
+        instance.decode(&sequence)
+    }
+}
+
+/// Repeatedly dispatches the operation that can start earliest, choosing at random
+/// among operations tied for the earliest start with odds in proportion to their job's
+/// weight. Different seeds explore different schedules, so it is meant to be run
+/// several times (see `multistart`).
+pub struct RandomizedGreedy;
+
+impl RandomizedGreedy {
+    /// The dispatch order as an operation-based sequence for `Instance::decode`
+    pub(super) fn sequence(instance: &Instance, rng: &mut impl Rng) -> Vec<usize> {
+        let jobs = instance.jobs();
+        let mut next_op = vec![0; jobs.len()];
+        let mut job_ready: Vec<f64> = jobs.iter().map(|job| job.release_time).collect();
+        let mut job_machine: Vec<Option<usize>> = vec![None; jobs.len()];
+        let mut machine_free: Vec<(f64, Option<usize>)> = vec![(0.0, None); instance.num_machines()];
+        let mut sequence = Vec::with_capacity(instance.num_operations());
+        let mut tied = Vec::new();
+
+        loop {
+            // Start, machine and end of every job's next operation, placed like `decode` does
+            let mut earliest = f64::INFINITY;
+            tied.clear();
+            for (job_index, job) in jobs.iter().enumerate() {
+                let Some(operation) = job.operations.get(next_op[job_index]) else {
+                    continue;
+                };
+                let (machine_id, start, end) = operation.options()
+                    .map(|option| {
+                        let (free, last_job) = machine_free[option.machine_id];
+                        let setup = instance.setup_time(option.machine_id, last_job, job.id);
+                        let arrival = job_ready[job_index] + instance.transfer_time(job_machine[job_index], option.machine_id);
+                        let start = (free + setup).max(arrival);
+                        (option.machine_id, start, start + option.duration)
+                    })
+                    .min_by(|a, b| a.2.total_cmp(&b.2))
+                    .unwrap();
+                if start < earliest - TIME_EPSILON {
+                    earliest = start;
+                    tied.clear();
+                }
+                if start <= earliest + TIME_EPSILON {
+                    tied.push((job_index, machine_id, end));
+                }
+            }
+            if tied.is_empty() {
+                break;
+            }
+
+            let (job_index, machine_id, end) = tied[Self::pick(instance, &tied, rng)];
+            sequence.push(job_index);
+            next_op[job_index] += 1;
+            job_ready[job_index] = end;
+            job_machine[job_index] = Some(machine_id);
+            machine_free[machine_id] = (end, Some(jobs[job_index].id));
+        }
+        sequence
+    }
+
+    /// Index of the tied operation to dispatch, uniformly at random when the jobs weigh
+    /// the same and with odds in proportion to job weight otherwise
+    fn pick(instance: &Instance, tied: &[(usize, usize, f64)], rng: &mut impl Rng) -> usize {
+        let jobs = instance.jobs();
+        let weight = |&(job_index, _, _): &(usize, usize, f64)| jobs[job_index].weight.max(0.0);
+        let total = tied.iter().map(weight).fold(0.0, |total, weight| total + weight);
+        let uniform = tied.iter().all(|tie| weight(tie) == weight(&tied[0]));
+        if uniform || total <= 0.0 {
+            return rng.gen_range(0..tied.len());
+        }
+        let mut draw = rng.gen_range(0.0..total);
+        for (index, tie) in tied.iter().enumerate() {
+            draw -= weight(tie);
+            if draw < 0.0 {
+                return index;
+            }
+        }
+        tied.len() - 1
+    }
+}
+
+impl Solver for RandomizedGreedy {
+    fn name(&self) -> &str {
+        "Randomized Greedy"
+    }
+
+    fn description(&self) -> &str {
+        "Earliest-start dispatching with random tie-breaking; differs from run to run"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "Builds a non-delay schedule: at every step the operation that can start earliest among \
+                      the next operations of all jobs is dispatched, with ties broken at random in favour of \
+                      heavier jobs. Each seed gives a different schedule, so the best of many runs is \
+                      usually kept.",
+            complexity: "O(N·J) per run for J jobs, as every dispatch scans the next operation of each job",
+            recommended_sizes: "Any size; use multi-start with tens to thousands of runs",
+            citation: Some("Giffler, B., & Thompson, G. L. (1960). Algorithms for solving production-scheduling problems. Operations Research, 8(4), 487-503."),
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_seeded(instance, rand::random())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        let mut rng = StdRng::seed_from_u64(seed);
+        instance.decode(&Self::sequence(instance, &mut rng))
+    }
+}
+
+/// Greedy restarted from random orders, keeping the best schedule under the objective
+/// (the makespan unless solved with `solve_objective`).
+/// Even restarts decode a shuffled operation sequence (a random insertion order of jobs
+/// and their operations); odd restarts dispatch earliest-start operations with random
+/// tie-breaking like `RandomizedGreedy`.
+pub struct RandomRestartGreedy {
+    pub restarts: usize,
+}
+
+impl Default for RandomRestartGreedy {
+    fn default() -> Self {
+        Self { restarts: 100 }
+    }
+}
+
+impl Solver for RandomRestartGreedy {
+    fn name(&self) -> &str {
+        "Random Restart Greedy"
+    }
+
+    fn description(&self) -> &str {
+        "Best of many greedy passes over shuffled job orders with random tie-breaking"
+    }
+
+    fn info(&self) -> SolverInfo {
+        SolverInfo {
+            details: "Repeats cheap greedy constructions from random starting points and keeps the best: \
+                      half of the restarts place operations in a shuffled job and operation order, the \
+                      other half dispatch the earliest-starting operation with ties broken at random. \
+                      Usually much better than a single greedy pass at a small multiple of its cost.",
+            complexity: "O(R·N·J) for R restarts and J jobs",
+            recommended_sizes: "Any size; lower the restarts for instances with many thousands of operations",
+            citation: None,
+        }
+    }
+
+    fn solve(&self, instance: &Instance) -> Schedule {
+        self.solve_seeded(instance, rand::random())
+    }
+
+    fn is_stochastic(&self) -> bool {
+        true
+    }
+
+    fn solve_seeded(&self, instance: &Instance, seed: u64) -> Schedule {
+        self.solve_objective(instance, Objective::Makespan, seed)
+    }
+
+    fn solve_objective(&self, instance: &Instance, objective: Objective, seed: u64) -> Schedule {
+        self.restart(instance, objective, seed, ProgressReporter::new(None, 0))
+    }
+
+    fn solve_with_progress(
+        &self,
+        instance: &Instance,
+        objective: Objective,
+        seed: u64,
+        progress: &mut dyn FnMut(ConvergencePoint),
+    ) -> Option<Schedule> {
+        Some(self.restart(instance, objective, seed, ProgressReporter::new(Some(progress), self.restarts.max(1))))
+    }
+}
+
+impl RandomRestartGreedy {
+    fn restart(&self, instance: &Instance, objective: Objective, seed: u64, mut reporter: ProgressReporter) -> Schedule {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut best: Option<(Schedule, f64)> = None;
+        let restarts = self.restarts.max(1);
+        for restart in 0..restarts {
+            if let Some((_, value)) = &best {
+                reporter.report(restart, *value);
+            }
+            let sequence = if restart % 2 == 0 {
+                let mut sequence = instance.job_by_job_sequence();
+                sequence.shuffle(&mut rng);
+                sequence
+            } else {
+                RandomizedGreedy::sequence(instance, &mut rng)
+            };
+            let schedule = instance.decode(&sequence);
+            let value = instance.evaluate(objective, &schedule);
+            if best.as_ref().is_none_or(|(_, best_value)| value < *best_value) {
+                best = Some((schedule, value));
+            }
+        }
+        if let Some((_, value)) = &best {
+            reporter.finish(restarts, *value);
+        }
+        best.map(|(schedule, _)| schedule).unwrap_or_default()
+    }
+}
+
+/// The algorithms available to the application, in menu order
+#[derive(Clone)]
+pub struct SolverRegistry {
+    solvers: Vec<Arc<dyn Solver>>,
+}
+
+impl Default for SolverRegistry {
+    /// The built-in algorithms
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Greedy);
+        registry.register(super::insertion::InsertionGreedy);
+        registry.register(RandomizedGreedy);
+        registry.register(RandomRestartGreedy::default());
+        registry.register(SimulatedAnnealing::default());
+        registry.register(super::memetic::GeneticAlgorithm::default());
+        registry.register(super::memetic::GeneticAlgorithm::memetic());
+        registry.register(ShiftingBottleneck);
+        #[cfg(feature = "cp-solver")]
+        registry.register(super::cp::CpSolver::default());
+        registry
+    }
+}
+
+impl SolverRegistry {
+    pub fn empty() -> Self {
+        Self { solvers: Vec::new() }
+    }
+
+    /// Add an algorithm, replacing any registered under the same name
+    pub fn register(&mut self, solver: impl Solver + 'static) -> &mut Self {
+        let solver: Arc<dyn Solver> = Arc::new(solver);
+        match self.solvers.iter().position(|s| s.name() == solver.name()) {
+            Some(index) => self.solvers[index] = solver,
+            None => self.solvers.push(solver),
+        }
+        self
+    }
+
+    /// Take an algorithm out of the list, returning whether it was in it
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.solvers.len();
+        self.solvers.retain(|solver| solver.name() != name);
+        self.solvers.len() < before
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Solver> {
+        self.solvers.iter().find(|s| s.name() == name).map(|s| s.as_ref())
+    }
+
+    /// Look up an algorithm by name, ignoring case and treating '-' and '_' as spaces,
+    /// so "simulated-annealing" finds "Simulated Annealing"
+    pub fn find(&self, name: &str) -> Option<&dyn Solver> {
+        let normalize = |name: &str| name.to_lowercase().replace(['-', '_'], " ");
+        self.solvers().find(|solver| normalize(solver.name()) == normalize(name))
+    }
+
+    /// A handle to an algorithm that can be moved to another thread
+    pub fn shared(&self, name: &str) -> Option<Arc<dyn Solver>> {
+        self.solvers.iter().find(|s| s.name() == name).cloned()
+    }
+
+    pub fn solvers(&self) -> impl Iterator<Item = &dyn Solver> {
+        self.solvers.iter().map(|s| s.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.solvers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvers.is_empty()
+    }
+}
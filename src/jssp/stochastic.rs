@@ -0,0 +1,177 @@
+//! Stochastic durations: operations whose processing time is a distribution rather than
+//! a number.
+//!
+//! An operation's duration is the mean of its distribution; a `DurationSpread` adds its
+//! standard deviation. Durations are normally distributed, cut off at zero, and an
+//! operation with alternative machines varies by the same factor on each of them.
+//!
+//! Sample average approximation (SAA) turns the uncertain problem into a deterministic
+//! one: a fixed set of scenarios, each a duration for every operation, is drawn once with
+//! the spreads, and a schedule is rated by how its machine sequences play out in every
+//! scenario, each operation starting as soon as its job and machine allow.
+//! `Objective::ExpectedMakespan` is the mean of those makespans, so every search that
+//! ranks schedules by an objective, such as simulated annealing or the genetic
+//! algorithm, optimizes the expected makespan instead of the makespan of the means.
+//! Because the scenarios stay the same, two schedules are compared on equal terms.
+//! Scenarios re-time with buffers between machines and ignore secondary resources.
+//!
+//! ```
+//! use jssp_scheduler::jssp::stochastic::DurationSpread;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::jssp::Objective;
+//!
+//! let mut ft06 = ft06().instance;
+//! // Every operation varies by a fifth of its mean
+//! let spreads: Vec<DurationSpread> = ft06.jobs().iter()
+//!     .flat_map(|job| &job.operations)
+//!     .map(|op| DurationSpread { job_id: op.job_id, operation_id: op.operation_id, std_dev: op.duration / 5.0 })
+//!     .collect();
+//! ft06.set_duration_spreads(spreads, 50, 7).unwrap();
+//!
+//! let schedule = ft06.solve_greedy();
+//! let expected = ft06.evaluate(Objective::ExpectedMakespan, &schedule);
+//! // Delays add up along the way, so the plan is optimistic
+//! assert!(expected > ft06.calculate_makespan(&schedule));
+//! assert!(ft06.makespan_percentile(&schedule, 0.95) >= expected);
+//! ```
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{Instance, ScheduledOperation};
+
+/// Scenarios drawn for sample average approximation unless asked for otherwise
+pub const DEFAULT_SCENARIOS: usize = 20;
+
+/// The standard deviation of an operation's duration, whose mean is its planned duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DurationSpread {
+    pub job_id: usize,
+    pub operation_id: usize,
+    pub std_dev: f64,
+}
+
+/// A standard normal draw (Box-Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+}
+
+impl Instance {
+    /// Give operations a standard deviation around their duration and draw `scenarios`
+    /// sets of durations from them with `seed`; an empty list makes every duration
+    /// certain again. Standard deviations must be non-negative, and at least one
+    /// scenario is needed.
+    pub fn set_duration_spreads(&mut self, spreads: Vec<DurationSpread>, scenarios: usize, seed: u64) -> Result<(), String> {
+        let mut std_devs = vec![0.0; self.num_operations()];
+        for spread in &spreads {
+            let (job_id, operation_id) = (spread.job_id, spread.operation_id);
+            let index = self.operation_index(job_id, operation_id)
+                .ok_or_else(|| format!("Duration spread given for job {} operation {}, which does not exist", job_id, operation_id))?;
+            if !(spread.std_dev.is_finite() && spread.std_dev >= 0.0) {
+                return Err(format!("Job {} operation {} needs a non-negative standard deviation, got {}", job_id, operation_id, spread.std_dev));
+            }
+            std_devs[index] = spread.std_dev;
+        }
+        if std_devs.iter().all(|&std_dev| std_dev == 0.0) {
+            self.set_stochastic_data(Vec::new(), Vec::new(), seed);
+            return Ok(());
+        }
+        if scenarios == 0 {
+            return Err("Sample average approximation needs at least one scenario".to_string());
+        }
+
+        let means: Vec<f64> = self.jobs().iter().flat_map(|job| &job.operations).map(|op| op.duration).collect();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let factors = (0..scenarios)
+            .map(|_| {
+                means.iter()
+                    .zip(&std_devs)
+                    .map(|(&mean, &std_dev)| {
+                        let z = standard_normal(&mut rng);
+                        if mean > 0.0 { (1.0 + z * std_dev / mean).max(0.0) } else { 1.0 }
+                    })
+                    .collect()
+            })
+            .collect();
+        self.set_stochastic_data(std_devs, factors, seed);
+        Ok(())
+    }
+
+    /// Whether any operation's duration is uncertain
+    pub fn is_stochastic(&self) -> bool {
+        !self.scenarios().is_empty()
+    }
+
+    /// Standard deviation of an operation's duration, 0 if it is certain
+    pub fn duration_std_dev(&self, job_id: usize, operation_id: usize) -> f64 {
+        self.operation_index(job_id, operation_id)
+            .and_then(|index| self.duration_std_devs().get(index))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Every operation with an uncertain duration, job by job
+    pub fn duration_spreads(&self) -> Vec<DurationSpread> {
+        self.jobs().iter()
+            .flat_map(|job| &job.operations)
+            .map(|op| DurationSpread {
+                job_id: op.job_id,
+                operation_id: op.operation_id,
+                std_dev: self.duration_std_dev(op.job_id, op.operation_id),
+            })
+            .filter(|spread| spread.std_dev > 0.0)
+            .collect()
+    }
+
+    /// Makespan of `schedule`'s machine sequences in every scenario, in scenario order;
+    /// just the makespan when durations are certain
+    pub fn scenario_makespans(&self, schedule: &[ScheduledOperation]) -> Vec<f64> {
+        if !self.is_stochastic() {
+            return vec![self.calculate_makespan(schedule)];
+        }
+        // Sorted by start, every operation comes after its job and machine predecessors
+        let mut order: Vec<&ScheduledOperation> = schedule.iter().collect();
+        order.sort_by(|a, b| a.start_time.total_cmp(&b.start_time).then(a.end_time.total_cmp(&b.end_time)));
+        self.scenarios().iter().map(|factors| self.scenario_makespan(&order, factors)).collect()
+    }
+
+    /// Mean makespan over the scenarios (see `Objective::ExpectedMakespan`)
+    pub fn expected_makespan(&self, schedule: &[ScheduledOperation]) -> f64 {
+        let makespans = self.scenario_makespans(schedule);
+        makespans.iter().sum::<f64>() / makespans.len() as f64
+    }
+
+    /// Makespan over the scenarios at quantile `q` in [0, 1] (nearest rank), e.g. 0.95
+    /// for the makespan that 95% of the scenarios meet
+    pub fn makespan_percentile(&self, schedule: &[ScheduledOperation], q: f64) -> f64 {
+        let mut makespans = self.scenario_makespans(schedule);
+        makespans.sort_by(f64::total_cmp);
+        let rank = (q.clamp(0.0, 1.0) * makespans.len() as f64).ceil() as usize;
+        makespans[rank.clamp(1, makespans.len()) - 1]
+    }
+
+    /// Makespan of operations in `order` with their durations scaled by `factors`, each
+    /// as early as its job, its machine (after setup) and downtime allow
+    fn scenario_makespan(&self, order: &[&ScheduledOperation], factors: &[f64]) -> f64 {
+        let mut job_ready: Vec<(f64, Option<usize>)> = self.jobs().iter().map(|job| (job.release_time, None)).collect();
+        let mut machine_free: Vec<(f64, Option<usize>)> = vec![(0.0, None); self.num_machines()];
+        let mut makespan: f64 = 0.0;
+        for op in order {
+            let (Some(&(ready, last_machine)), Some(&(free, last_job))) = (job_ready.get(op.job_id), machine_free.get(op.machine_id)) else {
+                continue;
+            };
+            let factor = self.operation_index(op.job_id, op.operation_id).and_then(|index| factors.get(index)).copied().unwrap_or(1.0);
+            let duration = op.duration * factor;
+            let setup = self.setup_time(op.machine_id, last_job, op.job_id);
+            let earliest = (ready + self.transfer_time(last_machine, op.machine_id)).max(free + setup);
+            let start = self.preemptive_start(op.machine_id, earliest, setup, duration);
+            let end = self.processing_end(op.machine_id, start, duration);
+            job_ready[op.job_id] = (end, Some(op.machine_id));
+            machine_free[op.machine_id] = (end, Some(op.job_id));
+            makespan = makespan.max(end);
+        }
+        makespan
+    }
+}
@@ -0,0 +1,157 @@
+//! Reference instances and assertions for checking solver implementations.
+//!
+//! ```
+//! use jssp_scheduler::jssp::testing::{assert_makespan_within, assert_valid_schedule, canonical_instances, single_machine};
+//!
+//! for reference in canonical_instances() {
+//!     let schedule = reference.instance.solve_greedy();
+//!     assert_valid_schedule(&reference.instance, &schedule);
+//! }
+//!
+//! // Without machine contention the greedy pass is optimal
+//! let reference = single_machine();
+//! let schedule = reference.instance.solve_greedy();
+//! assert_makespan_within(&reference, &schedule, 0.0);
+//! ```
+
+use super::{Instance, Job, Operation, ScheduledOperation};
+
+/// A small instance together with its proven optimal makespan
+pub struct ReferenceInstance {
+    pub name: &'static str,
+    pub instance: Instance,
+    pub optimal_makespan: f64,
+}
+
+/// Build an instance from job routes given as `(machine_id, duration)` pairs
+pub fn instance_from_routes(routes: &[&[(usize, f64)]], num_machines: usize) -> Instance {
+    let jobs: Vec<Job> = routes.iter()
+        .enumerate()
+        .map(|(job_id, route)| Job {
+            id: job_id,
+            operations: route.iter()
+                .enumerate()
+                .map(|(operation_id, &(machine_id, duration))| Operation {
+                    job_id,
+                    operation_id,
+                    machine_id,
+                    duration,
+                    alternatives: Vec::new(),
+                })
+                .collect(),
+            release_time: 0.0,
+            due_date: None,
+            weight: 1.0,
+        })
+        .collect();
+    Instance::new(jobs, num_machines).expect("routes must only use machines below num_machines")
+}
+
+/// Three jobs on a single machine: the optimum is the total load
+pub fn single_machine() -> ReferenceInstance {
+    ReferenceInstance {
+        name: "single_machine",
+        instance: instance_from_routes(&[&[(0, 2.0)], &[(0, 3.0)], &[(0, 4.0)]], 1),
+        optimal_makespan: 9.0,
+    }
+}
+
+/// Two jobs crossing two machines in opposite order
+pub fn two_by_two() -> ReferenceInstance {
+    ReferenceInstance {
+        name: "two_by_two",
+        instance: instance_from_routes(&[&[(0, 3.0), (1, 2.0)], &[(1, 2.0), (0, 4.0)]], 2),
+        optimal_makespan: 7.0,
+    }
+}
+
+/// Two-machine flow shop, solved optimally by Johnson's rule
+pub fn johnson_flow_shop() -> ReferenceInstance {
+    ReferenceInstance {
+        name: "johnson_flow_shop",
+        instance: instance_from_routes(
+            &[&[(0, 2.0), (1, 3.0)], &[(0, 3.0), (1, 2.0)], &[(0, 1.0), (1, 4.0)]],
+            2,
+        ),
+        optimal_makespan: 10.0,
+    }
+}
+
+/// The 3×3 example from the Google OR-Tools job shop documentation
+pub fn or_tools_example() -> ReferenceInstance {
+    ReferenceInstance {
+        name: "or_tools_example",
+        instance: instance_from_routes(
+            &[
+                &[(0, 3.0), (1, 2.0), (2, 2.0)],
+                &[(0, 2.0), (2, 1.0), (1, 4.0)],
+                &[(1, 4.0), (2, 3.0)],
+            ],
+            3,
+        ),
+        optimal_makespan: 11.0,
+    }
+}
+
+/// Fisher & Thompson's classic 6×6 instance (ft06)
+pub fn ft06() -> ReferenceInstance {
+    ReferenceInstance {
+        name: "ft06",
+        instance: instance_from_routes(
+            &[
+                &[(2, 1.0), (0, 3.0), (1, 6.0), (3, 7.0), (5, 3.0), (4, 6.0)],
+                &[(1, 8.0), (2, 5.0), (4, 10.0), (5, 10.0), (0, 10.0), (3, 4.0)],
+                &[(2, 5.0), (3, 4.0), (5, 8.0), (0, 9.0), (1, 1.0), (4, 7.0)],
+                &[(1, 5.0), (0, 5.0), (2, 5.0), (3, 3.0), (4, 8.0), (5, 9.0)],
+                &[(2, 9.0), (1, 3.0), (4, 5.0), (5, 4.0), (0, 3.0), (3, 1.0)],
+                &[(1, 3.0), (3, 3.0), (5, 9.0), (0, 10.0), (4, 4.0), (2, 1.0)],
+            ],
+            6,
+        ),
+        optimal_makespan: 55.0,
+    }
+}
+
+/// All reference instances, smallest first
+pub fn canonical_instances() -> Vec<ReferenceInstance> {
+    vec![single_machine(), two_by_two(), johnson_flow_shop(), or_tools_example(), ft06()]
+}
+
+/// Panic with a readable list of violations if the schedule is infeasible
+#[track_caller]
+pub fn assert_valid_schedule(instance: &Instance, schedule: &[ScheduledOperation]) {
+    if let Err(violations) = instance.validate(schedule) {
+        let details: Vec<String> = violations.iter().map(|v| format!("  - {}", v)).collect();
+        panic!(
+            "schedule is infeasible ({} violation(s)):\n{}",
+            violations.len(),
+            details.join("\n")
+        );
+    }
+}
+
+/// Panic unless the schedule's makespan is within `gap` (relative, e.g. `0.1` = 10%)
+/// of the reference optimum. A makespan below the optimum also panics, since it
+/// indicates an infeasible schedule or a broken makespan computation.
+#[track_caller]
+pub fn assert_makespan_within(reference: &ReferenceInstance, schedule: &[ScheduledOperation], gap: f64) {
+    let makespan = reference.instance.calculate_makespan(schedule);
+    let optimum = reference.optimal_makespan;
+
+    assert!(
+        makespan + 1e-6 >= optimum,
+        "{}: makespan {:.2} is below the proven optimum {:.2}",
+        reference.name,
+        makespan,
+        optimum
+    );
+    assert!(
+        makespan <= optimum * (1.0 + gap) + 1e-6,
+        "{}: makespan {:.2} exceeds the optimum {:.2} by {:.1}% (allowed {:.1}%)",
+        reference.name,
+        makespan,
+        optimum,
+        (makespan / optimum - 1.0) * 100.0,
+        gap * 100.0
+    );
+}
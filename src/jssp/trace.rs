@@ -0,0 +1,163 @@
+//! Recorded trajectories of local-search runs, for replaying a search step by step.
+//!
+//! A trace stores the starting schedule and then only the accepted moves, each an
+//! adjacent swap on one machine, so even long runs stay small. The schedule after any
+//! step is rebuilt by replaying the moves on a `DisjunctiveGraph`.
+//!
+//! Iterative algorithms can also report their convergence while they run (see
+//! `Solver::solve_with_progress`): the best value found so far, as `ConvergencePoint`s.
+
+use serde::Serialize;
+
+use super::incremental::DisjunctiveGraph;
+use super::pool::SolutionPool;
+use super::{Instance, Schedule, ScheduledOperation};
+
+/// One accepted move
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceStep {
+    /// Iteration of the search at which the move was accepted
+    pub iteration: usize,
+    /// The move: swap the operations at `position` and `position + 1` on the machine
+    pub machine_id: usize,
+    pub position: usize,
+    /// Makespan after the move
+    pub makespan: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchTrace {
+    /// Name of the algorithm that produced the trace
+    pub solver: String,
+    pub initial: Schedule,
+    pub initial_makespan: f64,
+    pub steps: Vec<TraceStep>,
+    /// Iterations the search ran, accepted or not
+    pub iterations: usize,
+}
+
+impl SearchTrace {
+    pub fn new(solver: impl Into<String>, instance: &Instance, initial: Schedule) -> Self {
+        Self {
+            solver: solver.into(),
+            initial_makespan: instance.calculate_makespan(&initial),
+            initial,
+            steps: Vec::new(),
+            iterations: 0,
+        }
+    }
+
+    /// Number of positions to scrub through: the start plus one per accepted move
+    pub fn len(&self) -> usize {
+        self.steps.len() + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Makespan after `step` moves (0 is the starting schedule)
+    pub fn makespan_at(&self, step: usize) -> f64 {
+        match step.checked_sub(1) {
+            Some(index) => self.steps[index.min(self.steps.len() - 1)].makespan,
+            None => self.initial_makespan,
+        }
+    }
+
+    /// Iteration reached after `step` moves
+    pub fn iteration_at(&self, step: usize) -> usize {
+        step.checked_sub(1).map_or(0, |index| self.steps[index.min(self.steps.len() - 1)].iteration)
+    }
+
+    /// The first step with the lowest makespan
+    pub fn best_step(&self) -> usize {
+        (0..self.len()).fold(0, |best, step| if self.makespan_at(step) < self.makespan_at(best) { step } else { best })
+    }
+
+    /// The schedule after `step` moves, or `None` if the trace does not fit the instance
+    pub fn schedule_at(&self, instance: &Instance, step: usize) -> Option<Schedule> {
+        self.seek(instance, &self.initial, 0, step)
+    }
+
+    /// The schedule after `to` moves, given the schedule `at` after `from` moves.
+    /// Moving backwards undoes moves, since a swap is its own inverse, so scrubbing
+    /// only replays the moves in between.
+    pub fn seek(&self, instance: &Instance, at: &[ScheduledOperation], from: usize, to: usize) -> Option<Schedule> {
+        let (from, to) = (from.min(self.steps.len()), to.min(self.steps.len()));
+        let mut graph = DisjunctiveGraph::new(instance, at)?;
+        let moves: Box<dyn Iterator<Item = &TraceStep>> = if to >= from {
+            Box::new(self.steps[from..to].iter())
+        } else {
+            Box::new(self.steps[to..from].iter().rev())
+        };
+        for step in moves {
+            if !graph.apply_swap(step.machine_id, step.position) {
+                return None;
+            }
+        }
+        Some(graph.schedule())
+    }
+}
+
+/// The best objective value an iterative search had found after some iterations
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ConvergencePoint {
+    /// Iterations completed
+    pub iteration: usize,
+    pub best: f64,
+}
+
+/// Passes the progress of a search to a `Solver::solve_with_progress` callback: every
+/// new best value, and the current best at regular intervals so a stalled search still
+/// shows how far it got
+pub struct ProgressReporter<'a> {
+    progress: Option<&'a mut dyn FnMut(ConvergencePoint)>,
+    /// Iterations between reports of an unchanged best value
+    interval: usize,
+    reported: f64,
+    /// Collects the good schedules the search comes across, if the run keeps a pool
+    pool: Option<&'a mut SolutionPool>,
+}
+
+impl<'a> ProgressReporter<'a> {
+    /// Points per run reported without an improvement, at most
+    const REGULAR_POINTS: usize = 100;
+
+    /// A reporter for a run of `iterations` iterations; `None` reports nothing
+    pub fn new(progress: Option<&'a mut dyn FnMut(ConvergencePoint)>, iterations: usize) -> Self {
+        Self { progress, interval: (iterations / Self::REGULAR_POINTS).max(1), reported: f64::INFINITY, pool: None }
+    }
+
+    /// Offer the schedules of the run to `pool` as well
+    pub fn with_pool(mut self, pool: &'a mut SolutionPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Offer a schedule of `value` to the pool, building it only if the pool would take
+    /// a schedule of that value
+    pub fn offer(&mut self, value: f64, schedule: impl FnOnce() -> Schedule) {
+        if let Some(pool) = self.pool.as_deref_mut().filter(|pool| pool.admits(value)) {
+            pool.offer(value, schedule());
+        }
+    }
+
+    /// Called before every iteration with the best value so far
+    pub fn report(&mut self, iteration: usize, best: f64) {
+        if best < self.reported || iteration.is_multiple_of(self.interval) {
+            self.send(iteration, best);
+        }
+    }
+
+    /// Called once the search has stopped, after `iterations` iterations
+    pub fn finish(&mut self, iterations: usize, best: f64) {
+        self.send(iterations, best);
+    }
+
+    fn send(&mut self, iteration: usize, best: f64) {
+        if let Some(progress) = self.progress.as_deref_mut() {
+            progress(ConvergencePoint { iteration, best });
+            self.reported = best;
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Job Shop Scheduling Problem (JSSP) model, solvers and egui front end.
+
+pub mod jssp;
+pub mod calendar;
+pub mod chart;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+pub mod gui;
+pub mod report;
+#[cfg(all(feature = "rest-api", not(target_arch = "wasm32")))]
+pub mod rest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rpc;
+pub mod workbook;
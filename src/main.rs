@@ -1,19 +1,50 @@
-mod jssp;
-mod gui;
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> Result<(), eframe::Error> {
+    use clap::Parser;
+    use eframe::egui;
+    use jssp_scheduler::{cli, gui};
 
-use eframe::egui;
+    let cli = cli::Cli::parse();
+    #[cfg(feature = "rest-api")]
+    if let Some(address) = &cli.serve {
+        std::process::exit(cli::serve(address));
+    }
+    if let Some(command) = cli.command {
+        std::process::exit(cli::run(command));
+    }
 
-fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
-            .with_title("JSSP Scheduler - Greedy Algorithm"),
+            .with_title("JSSP Scheduler"),
         ..Default::default()
     };
     
     eframe::run_native(
         "JSSP Scheduler",
         options,
-        Box::new(|_cc| Ok(Box::new(gui::JsspApp::default()))),
+        Box::new(|cc| Ok(Box::new(gui::JsspApp::new(cc)))),
     )
 }
+
+/// Browser entry point: runs the app in the `jssp_canvas` element of `index.html`
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use jssp_scheduler::gui;
+    use wasm_bindgen::JsCast;
+
+    wasm_bindgen_futures::spawn_local(async {
+        let canvas = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("jssp_canvas"))
+            .expect("index.html has no jssp_canvas element")
+            .dyn_into::<web_sys::HtmlCanvasElement>()
+            .expect("jssp_canvas is not a canvas");
+        let started = eframe::WebRunner::new()
+            .start(canvas, eframe::WebOptions::default(), Box::new(|cc| Ok(Box::new(gui::JsspApp::new(cc)))))
+            .await;
+        if let Err(e) = started {
+            web_sys::console::error_1(&e);
+        }
+    });
+}
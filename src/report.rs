@@ -0,0 +1,196 @@
+//! Printable HTML report of a schedule, a single self-contained file to send to people
+//! who do not run the scheduler.
+//!
+//! The report has no external stylesheets, scripts or images: the Gantt chart is inlined
+//! as SVG (see `chart::gantt_svg`) and the styles sit in the head, with print rules so
+//! that each section starts on a fresh page only when it has to. Its sections are:
+//!
+//! - **Instance**: name, size, buffering, setups, downtime and other features in use.
+//! - **Results**: makespan, every objective, the lower bound with the optimality gap,
+//!   the best-known makespan of benchmark instances, and whether the schedule is
+//!   feasible.
+//! - **Gantt Chart**: the schedule, machines as rows.
+//! - **Machine Utilization**: busy, setup and idle time per machine with the shares of
+//!   the makespan.
+//! - **Run**: the algorithm, its parameters, seed and runtime, if `schedule_html_with_run`
+//!   is given the run that produced the schedule (see `provenance`).
+//!
+//! ```
+//! use jssp_scheduler::chart::Palette;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::report::schedule_html;
+//!
+//! let ft06 = ft06().instance;
+//! let html = schedule_html(&ft06, &ft06.solve_greedy(), Palette::Standard);
+//! assert!(html.starts_with("<!DOCTYPE html>"));
+//! assert!(html.contains("<svg") && html.contains("Machine Utilization"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::chart::{gantt_svg, xml_text, Palette};
+use crate::jssp::bounds::optimality_gap;
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::{Instance, Objective, ScheduledOperation};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; color: #222; margin: 2em auto; max-width: 1000px; }
+h1 { font-size: 1.6em; margin-bottom: 0.2em; }
+h2 { font-size: 1.2em; border-bottom: 1px solid #ccc; padding-bottom: 0.2em; margin-top: 1.6em; }
+.generated { color: #777; font-size: 0.9em; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.7em; text-align: left; }
+th { background: #dde4ee; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+.bar { background: #4a7ab8; height: 0.8em; }
+svg { max-width: 100%; height: auto; }
+@media print {
+  body { margin: 0; max-width: none; }
+  section { break-inside: avoid; }
+}
+";
+
+/// The schedule as a self-contained HTML document
+pub fn schedule_html(instance: &Instance, schedule: &[ScheduledOperation], palette: Palette) -> String {
+    schedule_html_with_run(instance, schedule, palette, None)
+}
+
+/// Like `schedule_html`, with a Run section describing `run` if there is one
+pub fn schedule_html_with_run(
+    instance: &Instance,
+    schedule: &[ScheduledOperation],
+    palette: Palette,
+    run: Option<&RunMetadata>,
+) -> String {
+    let title = if instance.name.trim().is_empty() { "Schedule Report".to_string() } else { format!("Schedule Report: {}", instance.name.trim()) };
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>", xml_text(&title), STYLE);
+    let _ = writeln!(html, "<h1>{}</h1>", xml_text(&title));
+    let _ = writeln!(html, "<p class=\"generated\">Generated {}</p>", chrono::Local::now().format("%Y-%m-%d %H:%M"));
+
+    section(&mut html, "Instance", &instance_rows(instance, schedule));
+    section(&mut html, "Results", &result_rows(instance, schedule));
+
+    let _ = writeln!(html, "<section>\n<h2>Gantt Chart</h2>\n{}</section>", gantt_svg(instance, schedule, palette));
+
+    utilization_section(&mut html, instance, schedule);
+
+    if let Some(run) = run {
+        let mut rows: Vec<(String, String)> = run.fields().into_iter()
+            .filter(|(label, _)| *label != "Parameters")
+            .map(|(label, value)| (label.to_string(), value))
+            .collect();
+        for (key, value) in run.parameters.as_object().into_iter().flatten() {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            rows.push((key.clone(), value));
+        }
+        section(&mut html, "Run", &rows);
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// A heading over a two-column table of labels and values
+fn section(html: &mut String, heading: &str, rows: &[(String, String)]) {
+    let _ = writeln!(html, "<section>\n<h2>{}</h2>\n<table>", heading);
+    for (label, value) in rows {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", xml_text(label), xml_text(value));
+    }
+    html.push_str("</table>\n</section>\n");
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+fn instance_rows(instance: &Instance, schedule: &[ScheduledOperation]) -> Vec<(String, String)> {
+    let mut rows = vec![
+        ("Jobs".to_string(), instance.jobs().len().to_string()),
+        ("Machines".to_string(), instance.num_machines().to_string()),
+        ("Operations".to_string(), schedule.len().to_string()),
+        ("Time".to_string(), instance.time_mode().name().to_string()),
+        ("Problem type".to_string(), instance.problem_type().name().to_string()),
+        ("Buffering".to_string(), instance.buffering.name().to_string()),
+        ("Preemptive".to_string(), yes_no(instance.preemptive)),
+        ("Alternative machines".to_string(), yes_no(instance.is_flexible())),
+        ("Setup times".to_string(), yes_no(instance.setup_times().is_some())),
+        ("Transfer times".to_string(), yes_no(instance.transfer_times().is_some())),
+        ("Downtime windows".to_string(), instance.downtime().len().to_string()),
+        ("Working hours".to_string(), yes_no(instance.has_working_hours())),
+        ("Uncertain durations".to_string(), instance.duration_spreads().len().to_string()),
+    ];
+    if !instance.resources().is_empty() {
+        let resources = (0..instance.resources().len())
+            .map(|id| format!("{} ({})", instance.resource_name(id), instance.resources()[id].capacity))
+            .collect::<Vec<_>>()
+            .join(", ");
+        rows.push(("Secondary resources".to_string(), resources));
+    }
+    rows.extend(instance.metadata.iter().map(|(key, value)| (key.clone(), value.clone())));
+    rows
+}
+
+fn result_rows(instance: &Instance, schedule: &[ScheduledOperation]) -> Vec<(String, String)> {
+    let time = instance.time_mode();
+    let makespan = instance.calculate_makespan(schedule);
+    let lower_bound = instance.makespan_lower_bound();
+
+    let mut rows = vec![("Makespan".to_string(), time.format(makespan))];
+    for objective in Objective::ALL.iter().filter(|objective| **objective != Objective::Makespan) {
+        rows.push((objective.name().to_string(), time.format(instance.evaluate(*objective, schedule))));
+    }
+    if instance.is_stochastic() {
+        rows.push(("95th percentile makespan".to_string(), time.format(instance.makespan_percentile(schedule, 0.95))));
+    }
+    rows.push(("Total setup time".to_string(), time.format(schedule.iter().map(|op| op.setup_time).sum())));
+    rows.push(("Lower bound".to_string(), time.format(lower_bound)));
+    rows.push(("Optimality gap".to_string(), format!("{:.1}%", optimality_gap(makespan, lower_bound) * 100.0)));
+    if let Some(known) = instance.best_known() {
+        let proven = if known.optimal { " (optimal)" } else { "" };
+        rows.push(("Best known".to_string(), format!("{}{}", time.format(known.makespan), proven)));
+    }
+    let feasibility = match instance.validate(schedule) {
+        Ok(()) => "yes".to_string(),
+        Err(violations) => format!("no, {} violations", violations.len()),
+    };
+    rows.push(("Feasible".to_string(), feasibility));
+    rows
+}
+
+fn utilization_section(html: &mut String, instance: &Instance, schedule: &[ScheduledOperation]) {
+    let time = instance.time_mode();
+    let stats = instance.statistics(schedule);
+
+    html.push_str("<section>\n<h2>Machine Utilization</h2>\n<table>\n");
+    html.push_str("<tr><th>Machine</th><th>Busy</th><th>Setup</th><th>Idle</th><th>Busy %</th><th>Setup %</th><th>Idle %</th><th></th></tr>\n");
+    for machine in &stats.machines {
+        let busy = stats.percent_of_makespan(machine.busy);
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td>\
+            <td class=\"number\">{:.1}</td><td class=\"number\">{:.1}</td><td class=\"number\">{:.1}</td>\
+            <td style=\"width: 120px\"><div class=\"bar\" style=\"width: {:.0}%\"></div></td></tr>",
+            xml_text(&instance.machine_name(machine.machine_id)),
+            time.format(machine.busy),
+            time.format(machine.setup),
+            time.format(machine.idle),
+            busy,
+            stats.percent_of_makespan(machine.setup),
+            stats.percent_of_makespan(machine.idle),
+            busy.clamp(0.0, 100.0),
+        );
+    }
+    html.push_str("</table>\n");
+    let _ = writeln!(
+        html,
+        "<p>Average utilization {:.1}%, total idle time {}.</p>\n</section>",
+        stats.average_utilization() * 100.0,
+        time.format(stats.total_idle())
+    );
+}
@@ -0,0 +1,319 @@
+//! REST API for backing a web dashboard with the solver (feature `rest-api`).
+//!
+//! `jssp-scheduler --serve 127.0.0.1:8080` answers HTTP requests with JSON. Solves run
+//! in the background, so a client posts an instance, starts a solve and polls it:
+//!
+//! | request | body | response |
+//! |---|---|---|
+//! | `GET /algorithms` | | name, description, `stochastic` and `SolverInfo` of every algorithm |
+//! | `POST /instances` | an OR-Tools JSON instance | `201`, its `id` and size |
+//! | `GET /instances/{id}` | | its size and lower bound |
+//! | `POST /instances/{id}/solve` | `algorithm`, `runs`, `seed`, `objective`, `threads`, `preset`, `parameters` (all optional, as in `rpc`) | `202` and the solve's `id` |
+//! | `GET /solves/{id}` | | `status` ("running", "finished" or "failed"), `progress`, and the `result` once finished |
+//! | `GET /solves/{id}/schedule?format=` | | the schedule, in the formats of the RPC `get_schedule` |
+//!
+//! `progress` holds the runs done out of `runs`, the best objective value so far and,
+//! for a single run of an algorithm that reports it, the iterations done. Errors come
+//! with a 4xx status and an `error` message. Every response allows cross-origin
+//! requests, so a dashboard may be served from elsewhere.
+//!
+//! ```
+//! use jssp_scheduler::rest::Api;
+//!
+//! let api = Api::default();
+//! let (status, created) = api.handle("POST", "/instances", r#"{"jobs_data": [[[0, 3], [1, 2]], [[1, 4], [0, 1]]]}"#);
+//! assert_eq!(status, 201);
+//! assert_eq!(created["num_jobs"], 2);
+//!
+//! let (status, started) = api.handle("POST", "/instances/1/solve", r#"{"algorithm": "greedy"}"#);
+//! assert_eq!(status, 202);
+//! let solve = format!("/solves/{}", started["id"]);
+//! let finished = loop {
+//!     let (_, solve) = api.handle("GET", &solve, "");
+//!     if solve["status"] != "running" {
+//!         break solve;
+//!     }
+//!     std::thread::sleep(std::time::Duration::from_millis(10));
+//! };
+//! assert_eq!(finished["result"]["makespan"], 10.0);
+//!
+//! let (_, schedule) = api.handle("GET", &format!("{}/schedule?format=operations", solve), "");
+//! assert_eq!(schedule["operations"].as_array().unwrap().len(), 4);
+//! assert_eq!(api.handle("GET", "/solves/7", "").0, 404);
+//! assert_eq!(api.handle("POST", "/instances", "[[[100000000000, 3]]]").0, 400);
+//! ```
+
+use std::io::Read;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use serde_json::{json, Value};
+
+use crate::cli::{algorithm_json, find_solver};
+use crate::jssp::multistart::multi_start_with_progress;
+use crate::jssp::ortools::from_ortools_json;
+use crate::jssp::{Instance, Schedule, SolverRegistry};
+use crate::rpc::{instance_summary, multi_start_config, schedule_json, solve_summary, tuned_registry, ScheduleFormat, SolveParams};
+
+/// Largest request body read, which is plenty for instances with 100k operations
+const MAX_BODY_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Most machines a posted instance may have; the count follows the largest machine id,
+/// so a single stray id would otherwise size every per-machine table
+const MAX_MACHINES: usize = 100_000;
+
+/// A solve started by `POST /instances/{id}/solve`
+struct SolveJob {
+    instance_id: usize,
+    algorithm: String,
+    runs: usize,
+    runs_done: usize,
+    /// Iterations of the current run, from algorithms that report progress
+    iteration: Option<usize>,
+    best_value: Option<f64>,
+    started: Instant,
+    outcome: Option<Result<(Value, Schedule), String>>,
+}
+
+impl SolveJob {
+    fn to_json(&self, id: usize) -> Value {
+        let status = match &self.outcome {
+            None => "running",
+            Some(Ok(_)) => "finished",
+            Some(Err(_)) => "failed",
+        };
+        json!({
+            "id": id,
+            "instance": self.instance_id,
+            "algorithm": self.algorithm,
+            "status": status,
+            "progress": {
+                "runs": self.runs,
+                "runs_done": self.runs_done,
+                "iteration": self.iteration,
+                "best_value": self.best_value,
+                "elapsed_seconds": self.started.elapsed().as_secs_f64(),
+            },
+            "result": match &self.outcome {
+                Some(Ok((result, _))) => result.clone(),
+                _ => Value::Null,
+            },
+            "error": match &self.outcome {
+                Some(Err(e)) => json!(e),
+                _ => Value::Null,
+            },
+        })
+    }
+}
+
+/// Instances and solves, numbered from 1 in the order they were posted
+#[derive(Default)]
+struct State {
+    instances: Vec<Arc<Instance>>,
+    solves: Vec<SolveJob>,
+}
+
+/// The routes of the API, independent of the HTTP server
+#[derive(Default)]
+pub struct Api {
+    registry: SolverRegistry,
+    state: Arc<Mutex<State>>,
+}
+
+type Response = (u16, Value);
+
+fn error(status: u16, message: impl Into<String>) -> Response {
+    (status, json!({ "error": message.into() }))
+}
+
+impl Api {
+    pub fn with_solvers(registry: SolverRegistry) -> Self {
+        Self { registry, state: Arc::default() }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Answer one request with a status code and a JSON body
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> (u16, Value) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        let id = |segment: &str| segment.parse::<usize>().ok().filter(|&id| id > 0);
+        match (method, segments.as_slice()) {
+            ("GET", ["algorithms"]) => (200, self.registry.solvers().map(algorithm_json).collect()),
+            ("POST", ["instances"]) => self.post_instance(body),
+            ("GET", ["instances", instance]) => match id(instance).and_then(|id| self.state().instances.get(id - 1).cloned()) {
+                Some(instance) => (200, instance_summary(&instance)),
+                None => error(404, format!("No instance {}", instance)),
+            },
+            ("POST", ["instances", instance, "solve"]) => match id(instance) {
+                Some(instance_id) => self.start_solve(instance_id, body),
+                None => error(404, format!("No instance {}", instance)),
+            },
+            ("GET", ["solves", solve]) => match id(solve).and_then(|id| Some(self.state().solves.get(id - 1)?.to_json(id))) {
+                Some(solve) => (200, solve),
+                None => error(404, format!("No solve {}", solve)),
+            },
+            ("GET", ["solves", solve, "schedule"]) => self.get_schedule(id(solve), query),
+            _ => error(404, format!("No route for {} {}", method, path)),
+        }
+    }
+
+    fn post_instance(&self, body: &str) -> Response {
+        let instance = match from_ortools_json(body) {
+            Ok((instance, _)) => instance,
+            Err(e) => return error(400, e.to_string()),
+        };
+        if instance.num_machines() > MAX_MACHINES {
+            return error(400, format!("{} machines is more than the {} allowed", instance.num_machines(), MAX_MACHINES));
+        }
+        let mut summary = instance_summary(&instance);
+        let mut state = self.state();
+        state.instances.push(Arc::new(instance));
+        summary["id"] = json!(state.instances.len());
+        (201, summary)
+    }
+
+    fn start_solve(&self, instance_id: usize, body: &str) -> Response {
+        let Some(instance) = self.state().instances.get(instance_id - 1).cloned() else {
+            return error(404, format!("No instance {}", instance_id));
+        };
+        let params: SolveParams = if body.trim().is_empty() {
+            SolveParams::default()
+        } else {
+            match serde_json::from_str(body) {
+                Ok(params) => params,
+                Err(e) => return error(400, format!("Invalid solve parameters: {}", e)),
+            }
+        };
+        // Checked here so that mistakes are answered right away, not by a failed solve
+        let prepared = tuned_registry(&self.registry, &params).and_then(|tuned| {
+            let registry = tuned.unwrap_or_else(|| self.registry.clone());
+            let solver = find_solver(&registry, &params.algorithm)?;
+            let config = multi_start_config(solver, &params)?;
+            Ok((solver.name().to_string(), config, registry))
+        });
+        let (algorithm, config, registry) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => return error(400, e),
+        };
+
+        let id = {
+            let mut state = self.state();
+            state.solves.push(SolveJob {
+                instance_id,
+                algorithm: algorithm.clone(),
+                runs: config.starts,
+                runs_done: 0,
+                iteration: None,
+                best_value: None,
+                started: Instant::now(),
+                outcome: None,
+            });
+            state.solves.len()
+        };
+        let state = Arc::clone(&self.state);
+        let update = move |change: &dyn Fn(&mut SolveJob)| {
+            change(&mut state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).solves[id - 1]);
+        };
+        std::thread::spawn(move || {
+            let solver = registry.get(&algorithm).expect("the algorithm was found before");
+            let started = Instant::now();
+            let solved = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut traced = None;
+                if config.starts == 1 {
+                    traced = solver.solve_with_progress(&instance, config.objective, config.seed, &mut |point| {
+                        update(&|job| {
+                            job.iteration = Some(point.iteration);
+                            job.best_value = Some(point.best);
+                        });
+                    });
+                }
+                let schedule = match traced {
+                    Some(schedule) => schedule,
+                    None => {
+                        let result = multi_start_with_progress(solver, &instance, &config, &|_, value| {
+                            update(&|job| {
+                                job.runs_done += 1;
+                                job.best_value = Some(job.best_value.map_or(value, |best| best.min(value)));
+                            });
+                        });
+                        if let Some(error) = result.failure() {
+                            return Err(error.to_string());
+                        }
+                        result.best
+                    }
+                };
+                Ok((solve_summary(&instance, solver, &config, &schedule, started.elapsed()), schedule))
+            }));
+            // A crashed solve is reported as failed instead of running forever
+            let outcome = solved.map_err(|_| format!("{} stopped with an internal error", algorithm)).and_then(|solved| solved);
+            update(&|job| {
+                if let Ok((result, _)) = &outcome {
+                    job.runs_done = job.runs;
+                    job.best_value = result["objective_value"].as_f64();
+                }
+                job.outcome = Some(outcome.clone());
+            });
+        });
+        (202, json!({ "id": id, "status": "running" }))
+    }
+
+    fn get_schedule(&self, solve_id: Option<usize>, query: &str) -> Response {
+        let format = query.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "format")
+            .map_or(Ok(ScheduleFormat::default()), |(_, format)| serde_json::from_value(json!(format)));
+        let format = match format {
+            Ok(format) => format,
+            Err(_) => return error(400, "format should be operations, ortools, jobshoplib or machine_order"),
+        };
+        let state = self.state();
+        let Some(job) = solve_id.and_then(|id| state.solves.get(id - 1)) else {
+            return error(404, "No such solve");
+        };
+        match &job.outcome {
+            Some(Ok((_, schedule))) => (200, schedule_json(&state.instances[job.instance_id - 1], schedule, &format)),
+            Some(Err(e)) => error(409, format!("The solve failed: {}", e)),
+            None => error(409, "The solve is still running"),
+        }
+    }
+}
+
+/// Answer HTTP requests on `address` (e.g. "127.0.0.1:8080") until the process ends
+pub fn serve(api: Api, address: &str) -> Result<(), String> {
+    let server = tiny_http::Server::http(address).map_err(|e| format!("Cannot listen on {}: {}", address, e))?;
+    eprintln!("Serving the REST API on http://{}", address);
+    let json_type = tiny_http::Header::from_bytes("Content-Type", "application/json").unwrap();
+    let cors = [
+        tiny_http::Header::from_bytes("Access-Control-Allow-Origin", "*").unwrap(),
+        tiny_http::Header::from_bytes("Access-Control-Allow-Methods", "GET, POST, OPTIONS").unwrap(),
+        tiny_http::Header::from_bytes("Access-Control-Allow-Headers", "Content-Type").unwrap(),
+    ];
+    for mut request in server.incoming_requests() {
+        let (status, body) = if request.method() == &tiny_http::Method::Options {
+            // Preflight of a cross-origin POST
+            (204, String::new())
+        } else {
+            let mut body = String::new();
+            let read = request.as_reader().take(MAX_BODY_BYTES).read_to_string(&mut body);
+            let (status, value) = match read {
+                Ok(_) => api.handle(request.method().as_str(), request.url(), &body),
+                Err(e) => error(400, format!("Cannot read the request body: {}", e)),
+            };
+            (status, value.to_string())
+        };
+        let mut response = tiny_http::Response::from_string(body).with_status_code(status);
+        if status != 204 {
+            response.add_header(json_type.clone());
+        }
+        for header in &cors {
+            response.add_header(header.clone());
+        }
+        // A client that hung up does not stop the server
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
@@ -0,0 +1,320 @@
+//! Line-based JSON-RPC interface for driving the solver from another process.
+//!
+//! `jssp-scheduler rpc` reads one JSON-RPC 2.0 request per line on stdin and writes one
+//! response per line on stdout, so a notebook or script can keep the binary running as
+//! a subprocess. A session holds one instance and the last schedule:
+//!
+//! | method | params | result |
+//! |---|---|---|
+//! | `load_instance` | `path` (OR-Tools JSON file) or `instance` (the document itself) | instance summary |
+//! | `list_algorithms` | | name, description, `stochastic` and `SolverInfo` of every algorithm |
+//! | `solve` | `algorithm`, `runs`, `seed`, `objective`, `threads`, `preset`, `parameters` (all optional) | makespan, objective values, gap, runtime |
+//! | `get_schedule` | `format`: `"operations"` (default), `"ortools"`, `"jobshoplib"` or `"machine_order"` (as a string) | the last schedule |
+//! | `shutdown` | | `null`, then the session ends |
+//!
+//! Failures are reported as JSON-RPC errors and leave the session usable.
+//!
+//! ```
+//! use jssp_scheduler::rpc::Session;
+//!
+//! let mut session = Session::default();
+//! let load = r#"{"jsonrpc": "2.0", "id": 1, "method": "load_instance",
+//!                "params": {"instance": {"jobs_data": [[[0, 3], [1, 2]], [[1, 4], [0, 1]]]}}}"#;
+//! assert_eq!(session.handle_line(load).unwrap()["result"]["num_jobs"], 2);
+//!
+//! let solve = session.handle_line(r#"{"jsonrpc": "2.0", "id": 2, "method": "solve"}"#).unwrap();
+//! assert_eq!(solve["result"]["makespan"], 10.0);
+//!
+//! let error = session.handle_line(r#"{"jsonrpc": "2.0", "id": 3, "method": "fly"}"#).unwrap();
+//! assert_eq!(error["error"]["code"], -32601);
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::cli::{algorithm_json, find_objective, find_solver};
+use crate::jssp::batch::load_instance;
+use crate::jssp::bounds::optimality_gap;
+use crate::jssp::multistart::{multi_start, MultiStartConfig};
+use crate::jssp::ortools::{from_ortools_json, to_ortools_json};
+use crate::jssp::presets::{self, Presets};
+use crate::jssp::solution_formats::{to_jobshoplib_json, to_machine_order};
+use crate::jssp::{Instance, Objective, Schedule, ScheduledOperation, Solver, SolverRegistry};
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The request was understood but could not be carried out
+const COMMAND_FAILED: i64 = -32000;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LoadParams {
+    path: Option<PathBuf>,
+    instance: Option<Value>,
+}
+
+/// What to solve with, shared with the REST API
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct SolveParams {
+    pub algorithm: String,
+    pub runs: usize,
+    pub seed: Option<u64>,
+    pub objective: String,
+    pub threads: usize,
+    /// Parameter preset by name (see `presets`)
+    pub preset: Option<String>,
+    /// Preset values overridden for this run, by `<algorithm>.<parameter>` key
+    pub parameters: BTreeMap<String, f64>,
+}
+
+impl Default for SolveParams {
+    fn default() -> Self {
+        Self {
+            algorithm: "Greedy".to_string(),
+            runs: 1,
+            seed: None,
+            objective: "makespan".to_string(),
+            threads: 0,
+            preset: None,
+            parameters: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ScheduleFormat {
+    #[default]
+    Operations,
+    Ortools,
+    Jobshoplib,
+    MachineOrder,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct GetScheduleParams {
+    format: ScheduleFormat,
+}
+
+/// A request, decoded from its method name and params
+enum Command {
+    LoadInstance(LoadParams),
+    ListAlgorithms,
+    Solve(SolveParams),
+    GetSchedule(GetScheduleParams),
+    Shutdown,
+}
+
+impl Command {
+    fn parse(method: &str, params: Value) -> Result<Self, (i64, String)> {
+        fn params_of<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+            let params = if params.is_null() { json!({}) } else { params };
+            serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, format!("Invalid params: {}", e)))
+        }
+        Ok(match method {
+            "load_instance" => Command::LoadInstance(params_of(params)?),
+            "list_algorithms" => Command::ListAlgorithms,
+            "solve" => Command::Solve(params_of(params)?),
+            "get_schedule" => Command::GetSchedule(params_of(params)?),
+            "shutdown" => Command::Shutdown,
+            _ => return Err((METHOD_NOT_FOUND, format!("Unknown method '{}'", method))),
+        })
+    }
+}
+
+/// The state kept between requests
+#[derive(Default)]
+pub struct Session {
+    registry: SolverRegistry,
+    instance: Option<Instance>,
+    schedule: Option<Schedule>,
+    /// Set by `shutdown`
+    finished: bool,
+}
+
+impl Session {
+    pub fn with_solvers(registry: SolverRegistry) -> Self {
+        Self { registry, ..Default::default() }
+    }
+
+    /// Whether a `shutdown` request ended the session
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Answer one request line; blank lines get no response
+    pub fn handle_line(&mut self, line: &str) -> Option<Value> {
+        if line.trim().is_empty() {
+            return None;
+        }
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, format!("Parse error: {}", e))),
+        };
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id, INVALID_REQUEST, "Request has no method".to_string()));
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let outcome = Command::parse(method, params).and_then(|command| {
+            self.execute(command).map_err(|message| (COMMAND_FAILED, message))
+        });
+        Some(match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    fn execute(&mut self, command: Command) -> Result<Value, String> {
+        match command {
+            Command::LoadInstance(params) => self.load(params),
+            Command::ListAlgorithms => Ok(self.registry.solvers().map(algorithm_json).collect()),
+            Command::Solve(params) => self.solve(params),
+            Command::GetSchedule(params) => self.get_schedule(params),
+            Command::Shutdown => {
+                self.finished = true;
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    fn load(&mut self, params: LoadParams) -> Result<Value, String> {
+        let instance = match (params.path, params.instance) {
+            (Some(path), None) => load_instance(&path)?,
+            (None, Some(document)) => from_ortools_json(&document.to_string())?.0,
+            _ => return Err("Give either 'path' or 'instance'".to_string()),
+        };
+        let summary = instance_summary(&instance);
+        self.instance = Some(instance);
+        self.schedule = None;
+        Ok(summary)
+    }
+
+    fn solve(&mut self, params: SolveParams) -> Result<Value, String> {
+        let instance = self.instance.as_ref().ok_or("No instance loaded; call load_instance first")?;
+        let tuned = tuned_registry(&self.registry, &params)?;
+        let solver = find_solver(tuned.as_ref().unwrap_or(&self.registry), &params.algorithm)?;
+        let config = multi_start_config(solver, &params)?;
+
+        let started = Instant::now();
+        let result = multi_start(solver, instance, &config);
+        if let Some(error) = result.failure() {
+            return Err(error.to_string());
+        }
+        let schedule = result.best;
+        let result = solve_summary(instance, solver, &config, &schedule, started.elapsed());
+        self.schedule = Some(schedule);
+        Ok(result)
+    }
+
+    fn get_schedule(&self, params: GetScheduleParams) -> Result<Value, String> {
+        let (Some(instance), Some(schedule)) = (&self.instance, &self.schedule) else {
+            return Err("No schedule yet; call solve first".to_string());
+        };
+        Ok(schedule_json(instance, schedule, &params.format))
+    }
+}
+
+/// Size and lower bound of a loaded instance
+pub(crate) fn instance_summary(instance: &Instance) -> Value {
+    json!({
+        "name": instance.name,
+        "num_jobs": instance.jobs().len(),
+        "num_machines": instance.num_machines(),
+        "num_operations": instance.num_operations(),
+        "flexible": instance.is_flexible(),
+        "lower_bound": instance.makespan_lower_bound(),
+    })
+}
+
+/// A copy of `registry` retuned by the preset and overrides of `params`, for one run;
+/// `None` if they give neither
+pub(crate) fn tuned_registry(registry: &SolverRegistry, params: &SolveParams) -> Result<Option<SolverRegistry>, String> {
+    if params.preset.is_none() && params.parameters.is_empty() {
+        return Ok(None);
+    }
+    let presets = Presets::default_path().map_or_else(|| Ok(Presets::default()), |path| Presets::load(&path))?;
+    let mut tuning = presets.find(params.preset.as_deref().unwrap_or(presets::DEFAULT_PRESET))?.clone();
+    for (key, value) in &params.parameters {
+        tuning.set_value(key, *value)?;
+    }
+    let mut registry = registry.clone();
+    tuning.apply(&mut registry);
+    Ok(Some(registry))
+}
+
+/// Runs, seed and objective of `params`; deterministic algorithms run once
+pub(crate) fn multi_start_config(solver: &dyn Solver, params: &SolveParams) -> Result<MultiStartConfig, String> {
+    Ok(MultiStartConfig {
+        starts: if solver.is_stochastic() { params.runs.max(1) } else { 1 },
+        threads: params.threads,
+        seed: params.seed.unwrap_or_else(rand::random),
+        objective: find_objective(&params.objective)?,
+    })
+}
+
+/// Makespan, objective values, gap and runtime of a finished solve
+pub(crate) fn solve_summary(instance: &Instance, solver: &dyn Solver, config: &MultiStartConfig, schedule: &[ScheduledOperation], runtime: Duration) -> Value {
+    let makespan = instance.calculate_makespan(schedule);
+    let lower_bound = instance.makespan_lower_bound();
+    json!({
+        "algorithm": solver.name(),
+        "runs": config.starts,
+        "seed": config.seed,
+        "objective": config.objective.key(),
+        "objective_value": instance.evaluate(config.objective, schedule),
+        "objectives": Objective::ALL.iter()
+            .map(|objective| (objective.key().to_string(), json!(instance.evaluate(*objective, schedule))))
+            .collect::<serde_json::Map<_, _>>(),
+        "makespan": makespan,
+        "lower_bound": lower_bound,
+        "gap": optimality_gap(makespan, lower_bound),
+        "feasible": instance.validate(schedule).is_ok(),
+        "runtime_seconds": runtime.as_secs_f64(),
+    })
+}
+
+/// A schedule in one of the formats `get_schedule` offers
+pub(crate) fn schedule_json(instance: &Instance, schedule: &[ScheduledOperation], format: &ScheduleFormat) -> Value {
+    match format {
+        ScheduleFormat::Ortools => to_ortools_json(instance, Some(schedule)),
+        ScheduleFormat::Jobshoplib => to_jobshoplib_json(instance, schedule),
+        ScheduleFormat::MachineOrder => json!(to_machine_order(instance, schedule)),
+        ScheduleFormat::Operations => {
+            let violations: Vec<String> = instance.validate(schedule).err().unwrap_or_default().iter().map(ToString::to_string).collect();
+            json!({
+                "makespan": instance.calculate_makespan(schedule),
+                "violations": violations,
+                "operations": schedule,
+            })
+        }
+    }
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// Answer requests from `input` on `output` until the input ends or `shutdown` is called
+pub fn serve(mut session: Session, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    for line in input.lines() {
+        if let Some(response) = session.handle_line(&line?) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+        if session.is_finished() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,271 @@
+//! Excel workbook export for planners who work in spreadsheets, independent of the GUI.
+//!
+//! The workbook has three sheets:
+//!
+//! - **Schedule**: one row per operation, filterable, with job and machine names if the
+//!   instance has them.
+//! - **Utilization**: busy, setup and idle time per machine, with the shares of the
+//!   makespan as percentages.
+//! - **Gantt**: one row per operation, grouped by machine, and one narrow column per time
+//!   step. Conditional formatting fills the cells between an operation's Start and End
+//!   in its job's color, so changing those two cells in Excel moves the bar.
+//!
+//! `schedule_xlsx_with_run` adds a fourth sheet, **Run**, with the algorithm, parameters,
+//! seed and runtime that produced the schedule (see `provenance`).
+//!
+//! ```
+//! use jssp_scheduler::chart::Palette;
+//! use jssp_scheduler::jssp::testing::ft06;
+//! use jssp_scheduler::workbook::schedule_xlsx;
+//!
+//! let ft06 = ft06().instance;
+//! let bytes = schedule_xlsx(&ft06, &ft06.solve_greedy(), Palette::Standard).unwrap();
+//! // An .xlsx file is a zip archive
+//! assert!(bytes.starts_with(b"PK"));
+//! ```
+
+use rust_xlsxwriter::{
+    column_number_to_name, Color, ConditionalFormatDataBar, ConditionalFormatFormula, Format, FormatBorder, Workbook, Worksheet,
+    XlsxError,
+};
+
+use crate::chart::{tick_step, Palette};
+use crate::jssp::provenance::RunMetadata;
+use crate::jssp::{Instance, JsspError, ScheduledOperation, TimeMode};
+
+/// At most this many time steps in the Gantt sheet; longer schedules get coarser steps
+const MAX_GANTT_COLUMNS: f64 = 200.0;
+/// Columns of the Gantt sheet before the timeline: Machine, Job, Operation, Start, End
+const GANTT_FIXED_COLUMNS: u16 = 5;
+/// A time label above every this many timeline columns
+const GANTT_LABEL_EVERY: usize = 5;
+
+/// The schedule as an `.xlsx` workbook with Schedule, Utilization and Gantt sheets
+pub fn schedule_xlsx(instance: &Instance, schedule: &[ScheduledOperation], palette: Palette) -> Result<Vec<u8>, JsspError> {
+    schedule_xlsx_with_run(instance, schedule, palette, None)
+}
+
+/// Like `schedule_xlsx`, with a Run sheet describing `run` if there is one
+pub fn schedule_xlsx_with_run(
+    instance: &Instance,
+    schedule: &[ScheduledOperation],
+    palette: Palette,
+    run: Option<&RunMetadata>,
+) -> Result<Vec<u8>, JsspError> {
+    build(instance, schedule, palette, run).map_err(|e| JsspError::Serialization(format!("Failed to write the Excel workbook: {}", e)))
+}
+
+fn build(instance: &Instance, schedule: &[ScheduledOperation], palette: Palette, run: Option<&RunMetadata>) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let time_format = Format::new().set_num_format(match instance.time_mode() {
+        TimeMode::Integer => "0",
+        TimeMode::Fractional => "0.00",
+    });
+    let header = Format::new()
+        .set_bold()
+        .set_background_color(Color::RGB(0xDDE4EE))
+        .set_border(FormatBorder::Thin);
+
+    schedule_sheet(workbook.add_worksheet(), instance, schedule, &header, &time_format)?;
+    utilization_sheet(workbook.add_worksheet(), instance, schedule, &header, &time_format)?;
+    gantt_sheet(workbook.add_worksheet(), instance, schedule, palette, &header, &time_format)?;
+    if let Some(run) = run {
+        run_sheet(workbook.add_worksheet(), run, &header)?;
+    }
+    workbook.save_to_buffer()
+}
+
+/// One row per field of the run, with every parameter on a row of its own
+fn run_sheet(sheet: &mut Worksheet, run: &RunMetadata, header: &Format) -> Result<(), XlsxError> {
+    sheet.set_name("Run")?;
+    sheet.write_string_with_format(0, 0, "Field", header)?;
+    sheet.write_string_with_format(0, 1, "Value", header)?;
+    let mut row = 1;
+    for (label, value) in run.fields() {
+        if label == "Parameters" {
+            continue;
+        }
+        sheet.write_string(row, 0, label)?;
+        sheet.write_string(row, 1, value)?;
+        row += 1;
+    }
+    for (key, value) in run.parameters.as_object().into_iter().flatten() {
+        sheet.write_string(row, 0, key)?;
+        match value {
+            serde_json::Value::Number(number) => sheet.write_number(row, 1, number.as_f64().unwrap_or_default())?,
+            serde_json::Value::String(text) => sheet.write_string(row, 1, text)?,
+            other => sheet.write_string(row, 1, other.to_string())?,
+        };
+        row += 1;
+    }
+    sheet.set_column_width(0, 36.0)?;
+    sheet.set_column_width(1, 28.0)?;
+    Ok(())
+}
+
+/// Whether the instance names any job or machine, which adds name columns
+fn has_names(instance: &Instance) -> bool {
+    !instance.job_names().is_empty() || !instance.machine_names().is_empty()
+}
+
+fn schedule_sheet(
+    sheet: &mut Worksheet,
+    instance: &Instance,
+    schedule: &[ScheduledOperation],
+    header: &Format,
+    time_format: &Format,
+) -> Result<(), XlsxError> {
+    sheet.set_name("Schedule")?;
+    let mut columns = vec!["Job", "Operation", "Machine", "Start", "End", "Duration", "Setup"];
+    if has_names(instance) {
+        columns.extend(["Job Name", "Machine Name"]);
+    }
+    for (col, title) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, header)?;
+    }
+
+    let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+    ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time).then(a.machine_id.cmp(&b.machine_id)));
+    for (index, op) in ops.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_number(row, 0, op.job_id as f64)?;
+        sheet.write_number(row, 1, op.operation_id as f64)?;
+        sheet.write_number(row, 2, op.machine_id as f64)?;
+        for (col, value) in [(3, op.start_time), (4, op.end_time), (5, op.duration), (6, op.setup_time)] {
+            sheet.write_number_with_format(row, col, value, time_format)?;
+        }
+        if has_names(instance) {
+            sheet.write_string(row, 7, instance.job_name(op.job_id))?;
+            sheet.write_string(row, 8, instance.machine_name(op.machine_id))?;
+        }
+    }
+
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, ops.len() as u32, columns.len() as u16 - 1)?;
+    sheet.set_column_range_width(0, columns.len() as u16 - 1, 12.0)?;
+    if has_names(instance) {
+        sheet.set_column_range_width(7, 8, 20.0)?;
+    }
+    Ok(())
+}
+
+fn utilization_sheet(
+    sheet: &mut Worksheet,
+    instance: &Instance,
+    schedule: &[ScheduledOperation],
+    header: &Format,
+    time_format: &Format,
+) -> Result<(), XlsxError> {
+    sheet.set_name("Utilization")?;
+    let percent = Format::new().set_num_format("0.0%");
+    let columns = ["Machine", "Name", "Busy", "Setup", "Idle", "Busy %", "Setup %", "Idle %"];
+    for (col, title) in columns.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, header)?;
+    }
+
+    let stats = instance.statistics(schedule);
+    for (index, machine) in stats.machines.iter().enumerate() {
+        let row = index as u32 + 1;
+        sheet.write_number(row, 0, machine.machine_id as f64)?;
+        sheet.write_string(row, 1, instance.machine_name(machine.machine_id))?;
+        for (col, value) in [(2, machine.busy), (3, machine.setup), (4, machine.idle)] {
+            sheet.write_number_with_format(row, col, value, time_format)?;
+            sheet.write_number_with_format(row, col + 3, stats.percent_of_makespan(value) / 100.0, &percent)?;
+        }
+    }
+    let last = stats.machines.len() as u32;
+    if last > 0 {
+        sheet.add_conditional_format(1, 5, last, 5, &ConditionalFormatDataBar::new())?;
+    }
+
+    let summary = last + 2;
+    let bold = Format::new().set_bold();
+    sheet.write_string_with_format(summary, 0, "Makespan", &bold)?;
+    sheet.write_number_with_format(summary, 2, stats.makespan, time_format)?;
+    sheet.write_string_with_format(summary + 1, 0, "Average utilization", &bold)?;
+    sheet.write_number_with_format(summary + 1, 5, stats.average_utilization(), &percent)?;
+    sheet.write_string_with_format(summary + 2, 0, "Total idle", &bold)?;
+    sheet.write_number_with_format(summary + 2, 4, stats.total_idle(), time_format)?;
+
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.set_column_width(0, 20.0)?;
+    sheet.set_column_width(1, 20.0)?;
+    sheet.set_column_range_width(2, 7, 11.0)?;
+    Ok(())
+}
+
+/// Length of one timeline column, like a chart tick; whole units on integer instances
+fn gantt_step(makespan: f64, time_mode: TimeMode) -> f64 {
+    let step = tick_step(makespan, MAX_GANTT_COLUMNS);
+    match time_mode {
+        TimeMode::Integer => step.max(1.0),
+        TimeMode::Fractional => step,
+    }
+}
+
+fn gantt_sheet(
+    sheet: &mut Worksheet,
+    instance: &Instance,
+    schedule: &[ScheduledOperation],
+    palette: Palette,
+    header: &Format,
+    time_format: &Format,
+) -> Result<(), XlsxError> {
+    sheet.set_name("Gantt")?;
+    let makespan = instance.calculate_makespan(schedule).max(1.0);
+    let step = gantt_step(makespan, instance.time_mode());
+    let steps = (makespan / step).ceil() as u16;
+
+    // Row 0 labels every few steps, the hidden row 1 holds the start of every step for
+    // the formatting rules to compare with
+    for (col, title) in ["Machine", "Job", "Operation", "Start", "End"].iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, header)?;
+    }
+    for index in 0..steps {
+        let col = GANTT_FIXED_COLUMNS + index;
+        let time = f64::from(index) * step;
+        // Text, unlike numbers, runs on into the empty cells next to it
+        if usize::from(index) % GANTT_LABEL_EVERY == 0 {
+            sheet.write_string(0, col, instance.time_mode().format(time))?;
+        }
+        sheet.write_number(1, col, time)?;
+    }
+    sheet.set_row_hidden(1)?;
+
+    let mut ops: Vec<&ScheduledOperation> = schedule.iter().collect();
+    ops.sort_by(|a, b| a.machine_id.cmp(&b.machine_id).then(a.start_time.total_cmp(&b.start_time)));
+    for (index, op) in ops.iter().enumerate() {
+        let row = index as u32 + 2;
+        sheet.write_string(row, 0, instance.machine_name(op.machine_id))?;
+        sheet.write_number(row, 1, op.job_id as f64)?;
+        sheet.write_number(row, 2, op.operation_id as f64)?;
+        sheet.write_number_with_format(row, 3, op.start_time, time_format)?;
+        sheet.write_number_with_format(row, 4, op.end_time, time_format)?;
+    }
+
+    // One rule per job: a step is filled when it overlaps [Start, End) of the row.
+    // The rules are written for the top left timeline cell and shift with the range.
+    if !ops.is_empty() && steps > 0 {
+        let (first_row, last_row) = (2, ops.len() as u32 + 1);
+        let (first_col, last_col) = (GANTT_FIXED_COLUMNS, GANTT_FIXED_COLUMNS + steps - 1);
+        let step_col = column_number_to_name(first_col);
+        let mut jobs: Vec<usize> = ops.iter().map(|op| op.job_id).collect();
+        jobs.sort_unstable();
+        jobs.dedup();
+        for job_id in jobs {
+            let [r, g, b] = palette.job_color(instance, job_id);
+            let fill = Format::new().set_background_color(Color::RGB(u32::from_be_bytes([0, r, g, b])));
+            let rule = format!("=AND($B3={job_id},{step_col}$2<$E3,{step_col}$2+{step}>$D3)");
+            let format = ConditionalFormatFormula::new().set_rule(rule.as_str()).set_format(fill);
+            sheet.add_conditional_format(first_row, first_col, last_row, last_col, &format)?;
+        }
+    }
+
+    sheet.set_freeze_panes(2, GANTT_FIXED_COLUMNS)?;
+    sheet.set_column_width(0, 16.0)?;
+    sheet.set_column_range_width(1, 4, 8.0)?;
+    if steps > 0 {
+        sheet.set_column_range_width(GANTT_FIXED_COLUMNS, GANTT_FIXED_COLUMNS + steps - 1, 2.0)?;
+    }
+    Ok(())
+}
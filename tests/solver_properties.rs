@@ -0,0 +1,243 @@
+//! Property-based tests of the built-in solvers on random instances.
+//!
+//! Every algorithm of the registry must return a feasible schedule for any instance:
+//! each operation exactly once, no two operations on a machine at the same time, the
+//! operations of a job in order and after its release, and no more operations holding
+//! an operator at once than there are operators. These are checked here
+//! independently of `Instance::validate`, which must agree with the checks both on the
+//! solvers' schedules and on schedules broken on purpose. No schedule may beat the
+//! quick lower estimate, nor may the greedy upper estimate fall below the lower bounds.
+//! With an operation pinned, every solver's schedule must hold it once re-timed. In an
+//! open shop a job's operations may run in any order, but still one at a time.
+//!
+//! proptest shrinks a failing instance to a small one and stores its seed under
+//! `tests/solver_properties.proptest-regressions`; set `PROPTEST_CASES` for a longer run.
+
+use std::collections::HashMap;
+
+use jssp_scheduler::jssp::annealing::{AnnealingConfig, SimulatedAnnealing};
+use jssp_scheduler::jssp::bounds::{estimate_greedy_upper_bound, estimate_lower_bound};
+use jssp_scheduler::jssp::memetic::{GeneticAlgorithm, GeneticConfig};
+use jssp_scheduler::jssp::resources::{ResourceRequirement, SecondaryResource};
+use jssp_scheduler::jssp::{Instance, Job, MachineOption, Objective, Operation, ProblemType, ScheduledOperation, SolverRegistry};
+use proptest::prelude::*;
+
+const EPSILON: f64 = 1e-6;
+
+/// The built-in algorithms, with the metaheuristics cut short to keep the cases fast
+fn registry() -> SolverRegistry {
+    let mut registry = SolverRegistry::default();
+    registry.register(SimulatedAnnealing { config: AnnealingConfig { iterations: 300, ..AnnealingConfig::default() } });
+    registry.register(GeneticAlgorithm { config: GeneticConfig { population_size: 8, generations: 5, ..GeneticConfig::default() } });
+    registry.register(GeneticAlgorithm {
+        config: GeneticConfig { population_size: 8, generations: 5, local_search_moves: 10, ..GeneticConfig::default() },
+    });
+    registry
+}
+
+/// A job's route as (machine, duration, alternative machines with their durations)
+type Route = Vec<(usize, u32, Vec<(usize, u32)>)>;
+
+/// A route visiting distinct machines, with an occasional alternative machine
+fn route(num_machines: usize) -> impl Strategy<Value = Route> {
+    let operation = (1..=20u32, prop::option::weighted(0.2, (0..num_machines, 1..=20u32)));
+    (Just((0..num_machines).collect::<Vec<usize>>()).prop_shuffle(), prop::collection::vec(operation, 1..=num_machines))
+        .prop_map(|(machines, operations)| {
+            machines.into_iter()
+                .zip(operations)
+                .map(|(machine_id, (duration, alternative))| {
+                    let alternatives = alternative.filter(|&(other, _)| other != machine_id).into_iter().collect();
+                    (machine_id, duration, alternatives)
+                })
+                .collect()
+        })
+}
+
+/// Up to 6 jobs on up to 4 machines with whole-number durations and release times;
+/// sometimes every other operation also needs one of one or two operators
+fn instance() -> impl Strategy<Value = Instance> {
+    (1..=4usize)
+        .prop_flat_map(|num_machines| {
+            (
+                Just(num_machines),
+                prop::collection::vec((route(num_machines), 0..=10u32), 1..=6),
+                prop::option::weighted(0.3, 1..=2usize),
+            )
+        })
+        .prop_map(|(num_machines, jobs, operators)| {
+            let jobs = jobs.into_iter()
+                .enumerate()
+                .map(|(job_id, (route, release))| Job {
+                    id: job_id,
+                    operations: route.into_iter()
+                        .enumerate()
+                        .map(|(operation_id, (machine_id, duration, alternatives))| Operation {
+                            job_id,
+                            operation_id,
+                            machine_id,
+                            duration: duration as f64,
+                            alternatives: alternatives.into_iter()
+                                .map(|(machine_id, duration)| MachineOption { machine_id, duration: duration as f64 })
+                                .collect(),
+                        })
+                        .collect(),
+                    release_time: release as f64,
+                    due_date: None,
+                    weight: 1.0,
+                })
+                .collect();
+            let mut instance = Instance::new(jobs, num_machines).expect("generated instances are consistent");
+            if let Some(operators) = operators {
+                let requirements = instance.jobs().iter()
+                    .flat_map(|job| &job.operations)
+                    .filter(|op| (op.job_id + op.operation_id) % 2 == 0)
+                    .map(|op| ResourceRequirement { job_id: op.job_id, operation_id: op.operation_id, resource_id: 0 })
+                    .collect();
+                instance.set_resources(vec![SecondaryResource::new("Operators", operators)], requirements)
+                    .expect("generated requirements are consistent");
+            }
+            instance
+        })
+}
+
+/// What is wrong with a schedule, checked without `Instance::validate`; empty if nothing
+fn problems(instance: &Instance, schedule: &[ScheduledOperation]) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let mut placed: HashMap<(usize, usize), &ScheduledOperation> = HashMap::new();
+    for op in schedule {
+        if placed.insert((op.job_id, op.operation_id), op).is_some() {
+            problems.push(format!("job {} op {} is scheduled twice", op.job_id, op.operation_id));
+        }
+    }
+
+    for job in instance.jobs() {
+        let mut ops = Vec::new();
+        for operation in &job.operations {
+            let Some(op) = placed.remove(&(job.id, operation.operation_id)) else {
+                problems.push(format!("job {} op {} is missing", job.id, operation.operation_id));
+                continue;
+            };
+            match operation.duration_on(op.machine_id) {
+                Some(duration) if op.end_time - op.start_time + EPSILON >= duration => {}
+                Some(_) => problems.push(format!("job {} op {} is too short", job.id, operation.operation_id)),
+                None => problems.push(format!("job {} op {} is on an ineligible machine", job.id, operation.operation_id)),
+            }
+            ops.push(op);
+        }
+        // An open shop job takes its operations in the order they start
+        if instance.is_open_shop() {
+            ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time).then(a.end_time.total_cmp(&b.end_time)));
+        }
+        let mut ready = job.release_time;
+        for op in ops {
+            if op.start_time + EPSILON < ready {
+                problems.push(format!("job {} op {} starts before its job is ready", job.id, op.operation_id));
+            }
+            ready = op.end_time;
+        }
+    }
+    for (job_id, operation_id) in placed.keys() {
+        problems.push(format!("job {} op {} is not in the instance", job_id, operation_id));
+    }
+
+    let mut by_machine: HashMap<usize, Vec<&ScheduledOperation>> = HashMap::new();
+    for op in schedule {
+        by_machine.entry(op.machine_id).or_default().push(op);
+    }
+    for (machine_id, mut ops) in by_machine {
+        ops.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        for pair in ops.windows(2) {
+            if pair[1].start_time + EPSILON < pair[0].end_time {
+                problems.push(format!("machine {}: job {} and job {} overlap", machine_id, pair[0].job_id, pair[1].job_id));
+            }
+        }
+    }
+
+    if let Some(operators) = instance.resources().first() {
+        for op in schedule.iter().filter(|op| instance.resource_of(op.job_id, op.operation_id).is_some()) {
+            let busy = schedule.iter()
+                .filter(|other| instance.resource_of(other.job_id, other.operation_id).is_some())
+                .filter(|other| other.start_time <= op.start_time + EPSILON && other.end_time > op.start_time + EPSILON)
+                .count();
+            if busy > operators.capacity {
+                problems.push(format!("job {} op {} starts while all operators are busy", op.job_id, op.operation_id));
+            }
+        }
+    }
+    problems
+}
+
+proptest! {
+    #[test]
+    fn solvers_return_feasible_schedules(instance in instance(), seed in any::<u64>()) {
+        for solver in registry().solvers() {
+            let schedule = solver.solve_seeded(&instance, seed);
+            prop_assert_eq!(problems(&instance, &schedule), Vec::<String>::new(), "{}", solver.name());
+            prop_assert!(instance.validate(&schedule).is_ok(), "{}: {:?}", solver.name(), instance.validate(&schedule));
+
+            let last_end = schedule.iter().map(|op| op.end_time).fold(0.0, f64::max);
+            prop_assert_eq!(instance.calculate_makespan(&schedule), last_end, "{}", solver.name());
+            prop_assert_eq!(instance.evaluate(Objective::Makespan, &schedule), last_end, "{}", solver.name());
+            prop_assert!(estimate_lower_bound(&instance) <= last_end + EPSILON, "{}", solver.name());
+        }
+    }
+
+    #[test]
+    fn solvers_schedule_around_pins(instance in instance(), pick in any::<prop::sample::Index>(), seed in any::<u64>()) {
+        let mut instance = instance;
+        let greedy = instance.solve_greedy();
+        let op = &greedy[pick.index(greedy.len())];
+        let pinned = (op.job_id, op.operation_id);
+        prop_assert!(instance.pin_operation(&greedy, pinned).is_ok());
+        for solver in registry().solvers() {
+            let schedule = solver.solve_seeded(&instance, seed);
+            prop_assert_eq!(problems(&instance, &schedule), Vec::<String>::new(), "{}", solver.name());
+            prop_assert!(instance.validate(&schedule).is_ok(), "{}: {:?}", solver.name(), instance.validate(&schedule));
+        }
+    }
+
+    #[test]
+    fn solvers_keep_open_shop_jobs_one_at_a_time(instance in instance(), seed in any::<u64>()) {
+        let mut instance = instance;
+        prop_assert!(instance.set_problem_type(ProblemType::OpenShop).is_ok());
+        for solver in registry().solvers() {
+            let schedule = solver.solve_seeded(&instance, seed);
+            prop_assert_eq!(problems(&instance, &schedule), Vec::<String>::new(), "{}", solver.name());
+            prop_assert!(instance.validate(&schedule).is_ok(), "{}: {:?}", solver.name(), instance.validate(&schedule));
+            let makespan = instance.calculate_makespan(&schedule);
+            prop_assert!(instance.makespan_lower_bound() <= makespan + EPSILON, "{}", solver.name());
+        }
+    }
+
+    #[test]
+    fn estimates_bracket_the_lower_bound(instance in instance()) {
+        prop_assert!(estimate_lower_bound(&instance) <= instance.makespan_lower_bound() + EPSILON);
+        // Without operators the estimate is the makespan of a feasible schedule
+        let upper = estimate_greedy_upper_bound(&instance);
+        if instance.resources().is_empty() {
+            prop_assert!(upper + EPSILON >= instance.makespan_lower_bound(), "{} below {}", upper, instance.makespan_lower_bound());
+        }
+    }
+
+    #[test]
+    fn validator_agrees_on_broken_schedules(
+        instance in instance(),
+        pick in any::<prop::sample::Index>(),
+        shift in -15i32..=15,
+        drop in any::<bool>(),
+    ) {
+        let mut schedule = instance.solve_greedy();
+        let index = pick.index(schedule.len());
+        if drop {
+            schedule.remove(index);
+        } else {
+            let op = &mut schedule[index];
+            let start = (op.start_time + shift as f64).max(0.0);
+            op.end_time += start - op.start_time;
+            op.start_time = start;
+        }
+        let problems = problems(&instance, &schedule);
+        prop_assert_eq!(instance.validate(&schedule).is_ok(), problems.is_empty(), "{:?}", problems);
+    }
+}
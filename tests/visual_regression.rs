@@ -0,0 +1,347 @@
+//! Visual regression tests for the Gantt chart renderer.
+//!
+//! Each case renders a fixed schedule with `JsspApp::render_gantt_chart` in a headless
+//! egui context, rasterizes the tessellated output in software and compares it with a
+//! baseline PNG in `tests/baselines/`. Set `UPDATE_BASELINES=1` to (re)write baselines
+//! after an intentional change to the chart; failing renders are written next to the
+//! baselines as `<name>.actual.png` for inspection.
+//!
+//! The standalone chart exports (`chart::gantt_png`, `chart::utilization_png`) are checked
+//! the same way.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use eframe::egui;
+use egui::epaint::textures::TexturesDelta;
+use egui::epaint::{ClippedPrimitive, ImageData, Primitive, TextureId};
+use egui::Color32;
+use jssp_scheduler::chart::{gantt_png, utilization_png, Palette};
+use jssp_scheduler::gui::{ColorBy, GanttRows, JsspApp};
+use jssp_scheduler::jssp::testing::{ft06, instance_from_routes, or_tools_example};
+use jssp_scheduler::jssp::{Downtime, Instance, JsspSolver, ScheduledOperation, SetupTimes};
+
+const WIDTH: usize = 1000;
+const HEIGHT: usize = 800;
+
+/// Per-channel difference above which a pixel counts as changed
+const CHANNEL_TOLERANCE: u8 = 32;
+/// Fraction of changed pixels tolerated before a render counts as a regression
+const PIXEL_TOLERANCE: f64 = 0.01;
+
+struct Texture {
+    size: [usize; 2],
+    pixels: Vec<Color32>,
+}
+
+fn apply_textures(textures: &mut HashMap<TextureId, Texture>, delta: &TexturesDelta) {
+    for (id, image_delta) in &delta.set {
+        let (size, pixels): ([usize; 2], Vec<Color32>) = match &image_delta.image {
+            ImageData::Color(image) => (image.size, image.pixels.clone()),
+            ImageData::Font(image) => (image.size, image.srgba_pixels(None).collect()),
+        };
+        match image_delta.pos {
+            None => {
+                textures.insert(*id, Texture { size, pixels });
+            }
+            Some([x0, y0]) => {
+                let texture = textures.get_mut(id).expect("patch for unknown texture");
+                for y in 0..size[1] {
+                    for x in 0..size[0] {
+                        texture.pixels[(y0 + y) * texture.size[0] + x0 + x] = pixels[y * size[0] + x];
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn sample(texture: &Texture, uv: egui::Pos2) -> [f32; 4] {
+    let x = ((uv.x * texture.size[0] as f32) as usize).min(texture.size[0] - 1);
+    let y = ((uv.y * texture.size[1] as f32) as usize).min(texture.size[1] - 1);
+    let c = texture.pixels[y * texture.size[0] + x];
+    [c.r() as f32, c.g() as f32, c.b() as f32, c.a() as f32].map(|v| v / 255.0)
+}
+
+/// Rasterize tessellated egui meshes into an RGBA buffer (premultiplied alpha blending)
+fn rasterize(primitives: &[ClippedPrimitive], textures: &HashMap<TextureId, Texture>) -> Vec<u8> {
+    let mut canvas = vec![[0.0f32; 4]; WIDTH * HEIGHT];
+
+    for primitive in primitives {
+        let Primitive::Mesh(mesh) = &primitive.primitive else {
+            continue;
+        };
+        let Some(texture) = textures.get(&mesh.texture_id) else {
+            continue;
+        };
+        let clip = primitive.clip_rect;
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+            let area = (b.pos - a.pos).x * (c.pos - a.pos).y - (b.pos - a.pos).y * (c.pos - a.pos).x;
+            if area.abs() < f32::EPSILON {
+                continue;
+            }
+
+            let min_x = a.pos.x.min(b.pos.x).min(c.pos.x).max(clip.min.x).max(0.0).floor() as usize;
+            let min_y = a.pos.y.min(b.pos.y).min(c.pos.y).max(clip.min.y).max(0.0).floor() as usize;
+            let max_x = (a.pos.x.max(b.pos.x).max(c.pos.x).min(clip.max.x).ceil().max(0.0) as usize).min(WIDTH);
+            let max_y = (a.pos.y.max(b.pos.y).max(c.pos.y).min(clip.max.y).ceil().max(0.0) as usize).min(HEIGHT);
+
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let p = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+                    let edge = |u: egui::Pos2, v: egui::Pos2| (v - u).x * (p - u).y - (v - u).y * (p - u).x;
+                    let wa = edge(b.pos, c.pos) / area;
+                    let wb = edge(c.pos, a.pos) / area;
+                    let wc = edge(a.pos, b.pos) / area;
+                    if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                        continue;
+                    }
+
+                    let uv = egui::pos2(
+                        wa * a.uv.x + wb * b.uv.x + wc * c.uv.x,
+                        wa * a.uv.y + wb * b.uv.y + wc * c.uv.y,
+                    );
+                    let tex = sample(texture, uv);
+                    let color = [0, 1, 2, 3].map(|i| {
+                        let channel = |v: &egui::epaint::Vertex| v.color.to_array()[i] as f32 / 255.0;
+                        (wa * channel(a) + wb * channel(b) + wc * channel(c)) * tex[i]
+                    });
+
+                    let dst = &mut canvas[y * WIDTH + x];
+                    for i in 0..4 {
+                        dst[i] = color[i] + dst[i] * (1.0 - color[3]);
+                    }
+                }
+            }
+        }
+    }
+
+    canvas.iter()
+        .flat_map(|px| px.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8))
+        .collect()
+}
+
+/// Render the Gantt chart of a solved instance to RGBA pixels
+fn render(instance: Instance, schedule: Vec<ScheduledOperation>) -> Vec<u8> {
+    render_app(JsspApp::with_solution(JsspSolver::new(instance), schedule))
+}
+
+fn render_app(mut app: JsspApp) -> Vec<u8> {
+    let ctx = egui::Context::default();
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(WIDTH as f32, HEIGHT as f32),
+        )),
+        ..Default::default()
+    };
+
+    let mut textures = HashMap::new();
+    let mut output = None;
+    // A few frames let the plot settle its auto-bounds and layout
+    for _ in 0..3 {
+        let frame = ctx.run(input.clone(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| app.render_gantt_chart(ui));
+        });
+        apply_textures(&mut textures, &frame.textures_delta);
+        output = Some(frame);
+    }
+
+    let output = output.unwrap();
+    let primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+    rasterize(&primitives, &textures)
+}
+
+fn baseline_path(name: &str, suffix: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("baselines")
+        .join(format!("{}{}.png", name, suffix))
+}
+
+fn write_png(path: &PathBuf, size: (usize, usize), pixels: &[u8]) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let mut encoder = png::Encoder::new(File::create(path).unwrap(), size.0 as u32, size.1 as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header().unwrap().write_image_data(pixels).unwrap();
+}
+
+/// RGBA pixels and size of a PNG image
+fn decode_png(reader: impl std::io::BufRead + std::io::Seek) -> Option<(Vec<u8>, (usize, usize))> {
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::ALPHA);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()?];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return None;
+    }
+    buffer.truncate(info.buffer_size());
+    Some((buffer, (info.width as usize, info.height as usize)))
+}
+
+fn read_png(path: &PathBuf, size: (usize, usize)) -> Option<Vec<u8>> {
+    let (pixels, actual_size) = decode_png(BufReader::new(File::open(path).ok()?))?;
+    (actual_size == size).then_some(pixels)
+}
+
+fn assert_matches_baseline(name: &str, pixels: &[u8]) {
+    assert_image_matches_baseline(name, (WIDTH, HEIGHT), pixels);
+}
+
+fn assert_image_matches_baseline(name: &str, size: (usize, usize), pixels: &[u8]) {
+    let path = baseline_path(name, "");
+    if std::env::var_os("UPDATE_BASELINES").is_some() {
+        write_png(&path, size, pixels);
+        return;
+    }
+
+    let baseline = read_png(&path, size).unwrap_or_else(|| {
+        panic!("missing or unreadable baseline {} (run with UPDATE_BASELINES=1)", path.display())
+    });
+    let changed = pixels.chunks_exact(4)
+        .zip(baseline.chunks_exact(4))
+        .filter(|(a, b)| a.iter().zip(b.iter()).any(|(x, y)| x.abs_diff(*y) > CHANNEL_TOLERANCE))
+        .count();
+    let fraction = changed as f64 / (size.0 * size.1) as f64;
+
+    if fraction > PIXEL_TOLERANCE {
+        let actual = baseline_path(name, ".actual");
+        write_png(&actual, size, pixels);
+        panic!(
+            "{}: {:.2}% of pixels differ from the baseline (allowed {:.2}%), see {}",
+            name,
+            fraction * 100.0,
+            PIXEL_TOLERANCE * 100.0,
+            actual.display()
+        );
+    }
+}
+
+#[test]
+fn gantt_ft06_greedy() {
+    let reference = ft06();
+    let schedule = reference.instance.solve_greedy();
+    assert_matches_baseline("ft06_greedy", &render(reference.instance, schedule));
+}
+
+#[test]
+fn gantt_ft06_by_job() {
+    let reference = ft06();
+    let schedule = reference.instance.solve_greedy();
+    let app = JsspApp::with_solution(JsspSolver::new(reference.instance), schedule).with_gantt_rows(GanttRows::Jobs);
+    assert_matches_baseline("ft06_by_job", &render_app(app));
+}
+
+#[test]
+fn gantt_ft06_lateness() {
+    let reference = ft06();
+    let schedule = reference.instance.solve_greedy();
+    let completion = reference.instance.job_completion_times(&schedule);
+    // On time, near the due date, late and without a due date
+    let offsets = [Some(40.0), Some(2.0), Some(-10.0), None, Some(60.0), Some(-1.0)];
+    let mut jobs = reference.instance.jobs().to_vec();
+    for (job, offset) in jobs.iter_mut().zip(offsets) {
+        job.due_date = offset.map(|offset| completion[&job.id] + offset);
+    }
+    let instance = Instance::new(jobs, reference.instance.num_machines()).unwrap();
+    let app = JsspApp::with_solution(JsspSolver::new(instance), schedule).with_color_by(ColorBy::Lateness);
+    assert_matches_baseline("ft06_lateness", &render_app(app));
+}
+
+#[test]
+fn gantt_large_simplified() {
+    // 300 jobs on 20 machines, more operations than are drawn in full detail
+    let routes: Vec<Vec<(usize, f64)>> = (0..300)
+        .map(|job| (0..20).map(|k| ((job * 7 + k * 3) % 20, ((job * 13 + k * 5) % 9 + 1) as f64)).collect())
+        .collect();
+    let routes: Vec<&[(usize, f64)]> = routes.iter().map(Vec::as_slice).collect();
+    let instance = instance_from_routes(&routes, 20);
+    let schedule = instance.solve_greedy();
+    assert_matches_baseline("large_simplified", &render(instance, schedule));
+}
+
+#[test]
+fn gantt_or_tools_example() {
+    let reference = or_tools_example();
+    let schedule = reference.instance.solve_greedy();
+    assert_matches_baseline("or_tools_example", &render(reference.instance, schedule));
+}
+
+#[test]
+fn gantt_setup_times() {
+    let mut solver = instance_from_routes(
+        &[&[(0, 4.0), (1, 3.0)], &[(0, 2.0), (1, 5.0)], &[(1, 3.0), (0, 4.0)]],
+        2,
+    );
+    solver.set_setup_times(Some(SetupTimes {
+        times: vec![vec![vec![0.0, 2.0, 1.0], vec![1.5, 0.0, 2.0], vec![1.0, 1.0, 0.0]]; 2],
+    })).unwrap();
+    let schedule = solver.solve_greedy();
+    assert_matches_baseline("setup_times", &render(solver, schedule));
+}
+
+#[test]
+fn gantt_downtime() {
+    let mut solver = instance_from_routes(
+        &[&[(0, 4.0), (1, 3.0)], &[(0, 2.0), (1, 5.0)], &[(1, 3.0), (0, 4.0)]],
+        2,
+    );
+    solver.set_downtime(vec![
+        Downtime { machine_id: 0, start: 3.0, end: 6.0 },
+        Downtime { machine_id: 1, start: 9.0, end: 11.0 },
+    ]).unwrap();
+    let schedule = solver.solve_greedy();
+    assert_matches_baseline("downtime", &render(solver, schedule));
+}
+
+#[test]
+fn gantt_highlights_violations() {
+    let reference = or_tools_example();
+    let mut schedule = reference.instance.solve_greedy();
+    // Pull one operation back to time 0 so it overlaps and breaks precedence
+    let op = schedule.iter_mut().find(|op| op.job_id == 1 && op.operation_id == 1).unwrap();
+    op.end_time -= op.start_time;
+    op.start_time = 0.0;
+    assert_matches_baseline("violations", &render(reference.instance, schedule));
+}
+
+#[test]
+fn exported_chart_png() {
+    let reference = ft06();
+    let schedule = reference.instance.solve_greedy();
+    let png = gantt_png(&reference.instance, &schedule, Palette::Standard).unwrap();
+    let (pixels, size) = decode_png(std::io::Cursor::new(png)).unwrap();
+    assert_image_matches_baseline("chart_ft06", size, &pixels);
+}
+
+#[test]
+fn exported_chart_png_colorblind_palette() {
+    let mut instance = ft06().instance;
+    // Job 2 keeps its own color whatever the palette
+    instance.job_colors.insert(2, [128, 128, 128]);
+    let schedule = instance.solve_greedy();
+    let png = gantt_png(&instance, &schedule, Palette::OkabeIto).unwrap();
+    let (pixels, size) = decode_png(std::io::Cursor::new(png)).unwrap();
+    assert_image_matches_baseline("chart_ft06_okabe_ito", size, &pixels);
+}
+
+#[test]
+fn exported_utilization_png() {
+    let mut solver = instance_from_routes(
+        &[&[(0, 4.0), (1, 3.0)], &[(0, 2.0), (1, 5.0)], &[(1, 3.0), (0, 4.0)]],
+        2,
+    );
+    solver.set_setup_times(Some(SetupTimes {
+        times: vec![vec![vec![0.0, 2.0, 1.0], vec![1.5, 0.0, 2.0], vec![1.0, 1.0, 0.0]]; 2],
+    })).unwrap();
+    let schedule = solver.solve_greedy();
+    let png = utilization_png(&solver, &schedule).unwrap();
+    let (pixels, size) = decode_png(std::io::Cursor::new(png)).unwrap();
+    assert_image_matches_baseline("utilization_setup_times", size, &pixels);
+}